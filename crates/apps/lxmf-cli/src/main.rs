@@ -1,19 +1,40 @@
 #![allow(clippy::result_large_err)]
 
-use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum, ValueHint};
 use clap_complete::{generate, Shell};
+use lxmf_core::payload_fields::{CommandEntry, WireFields, TRANSPORT_FIELDS_MSGPACK_B64_KEY};
 use lxmf_sdk::{
-    error_code, AuthMode, BindMode, Client, ConfigPatch, ErrorCategory, EventCursor, LxmfSdk,
-    LxmfSdkManualTick, MessageId, OverflowPolicy, RpcBackendClient, SdkConfig, SdkError,
-    SendRequest, ShutdownMode, StartRequest, TickBudget,
+    error_code, AuthMode, BindMode, Client, ConfigPatch, ContactListRequest, ContactRecord,
+    DeliverySnapshot, DeliveryState, ErrorCategory, EventBatch, EventCursor, HealthReport,
+    HealthStatus, LogLevel, LxmfSdk, LxmfSdkAttachments, LxmfSdkIdentity, LxmfSdkLogs,
+    LxmfSdkManualTick, LxmfSdkPropagation, MessageExportBundle, MessageExportRequest, MessageId,
+    MessageSearchRequest, OverflowPolicy, PresenceListRequest, PresenceRecord, RpcBackendClient,
+    SdkConfig, SdkError, SendRequest, ShutdownMode, StartRequest, TickBudget,
 };
-use serde_json::{json, Value as JsonValue};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map as JsonMap, Value as JsonValue};
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+use std::path::PathBuf;
 use std::process::ExitCode;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const TRACE_FOLLOW_INTERVAL: Duration = Duration::from_secs(1);
+const POLL_FOLLOW_INTERVAL: Duration = Duration::from_secs(1);
+const SEND_WAIT_INTERVAL: Duration = Duration::from_millis(500);
+
+// Exit codes for `send --wait`; 1 stays reserved for command errors.
+const EXIT_SEND_FAILED: u8 = 2;
+const EXIT_SEND_EXPIRED: u8 = 3;
+const EXIT_SEND_CANCELLED: u8 = 4;
+const EXIT_SEND_TIMED_OUT: u8 = 5;
+
+const TEMPLATES_FILE_NAME: &str = "compose_templates.json";
 
 #[derive(Parser, Debug)]
 #[command(name = "lxmf", about = "LXMF operator CLI", version)]
 struct Cli {
-    #[arg(long, default_value = "127.0.0.1:4242")]
+    #[arg(long, default_value = "127.0.0.1:4242", value_hint = ValueHint::Hostname)]
     rpc: String,
 
     #[arg(long, value_enum, default_value_t = ProfileArg::DesktopFull)]
@@ -80,6 +101,9 @@ struct Cli {
     token_clock_skew_ms: u64,
 
     #[arg(long)]
+    api_key: Option<String>,
+
+    #[arg(long, value_hint = ValueHint::FilePath)]
     mtls_ca_bundle_path: Option<String>,
 
     #[arg(long, default_value_t = true)]
@@ -97,6 +121,28 @@ struct Cli {
     #[arg(long)]
     quiet: bool,
 
+    #[arg(long, value_enum, default_value_t = LayoutArg::Auto)]
+    layout: LayoutArg,
+
+    #[arg(long, default_value_t = DEFAULT_COMPACT_BELOW_ROWS)]
+    compact_below_rows: u16,
+
+    /// Whether human output may use ANSI colors; `auto` colors only when
+    /// stdout is a terminal. JSON output modes are never colored.
+    #[arg(long, value_enum, default_value_t = ColorArg::Auto)]
+    color: ColorArg,
+
+    /// Directory holding this CLI's local state, so several isolated
+    /// instances can share a machine; defaults to `~/.lxmf`. `start`
+    /// creates it, other commands require it to exist.
+    #[arg(long, value_hint = ValueHint::DirPath)]
+    profile_path: Option<String>,
+
+    /// JSON file holding saved compose templates; defaults to
+    /// `compose_templates.json` in the profile directory.
+    #[arg(long, value_hint = ValueHint::FilePath)]
+    templates_path: Option<String>,
+
     #[command(subcommand)]
     command: Command,
 }
@@ -127,6 +173,8 @@ enum AuthModeArg {
     Token,
     #[value(name = "mtls")]
     Mtls,
+    #[value(name = "api_key", alias = "api-key")]
+    ApiKey,
 }
 
 #[derive(Clone, Copy, Debug, ValueEnum)]
@@ -135,10 +183,26 @@ enum OverflowPolicyArg {
     Reject,
     #[value(name = "drop_oldest")]
     DropOldest,
+    #[value(name = "drop_newest")]
+    DropNewest,
     #[value(name = "block")]
     Block,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum LogLevelArg {
+    #[value(name = "trace")]
+    Trace,
+    #[value(name = "debug")]
+    Debug,
+    #[value(name = "info")]
+    Info,
+    #[value(name = "warn")]
+    Warn,
+    #[value(name = "error")]
+    Error,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
 enum OutputModeArg {
     #[value(name = "human")]
@@ -147,9 +211,35 @@ enum OutputModeArg {
     Json,
     #[value(name = "json-pretty")]
     JsonPretty,
+    /// One compact JSON object per line: one per event for `poll`, a single
+    /// result line for other commands.
+    #[value(name = "ndjson")]
+    Ndjson,
 }
 
-#[derive(Clone, Copy, Debug, ValueEnum)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum LayoutArg {
+    #[value(name = "auto")]
+    Auto,
+    #[value(name = "full")]
+    Full,
+    #[value(name = "compact")]
+    Compact,
+}
+
+const DEFAULT_COMPACT_BELOW_ROWS: u16 = 24;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum ColorArg {
+    #[value(name = "auto")]
+    Auto,
+    #[value(name = "always")]
+    Always,
+    #[value(name = "never")]
+    Never,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
 enum ShutdownModeArg {
     #[value(name = "graceful")]
     Graceful,
@@ -171,7 +261,38 @@ enum CompletionShellArg {
     Elvish,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum PeerFreshness {
+    #[value(name = "fresh")]
+    Fresh,
+    #[value(name = "recent")]
+    Recent,
+    #[value(name = "stale")]
+    Stale,
+    #[value(name = "dead")]
+    Dead,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum PeerSortArg {
+    #[value(name = "last-seen")]
+    LastSeen,
+    #[value(name = "freshness")]
+    Freshness,
+    #[value(name = "name")]
+    Name,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum NameSourceArg {
+    #[value(name = "contact-alias")]
+    ContactAlias,
+    #[value(name = "announced")]
+    Announced,
+}
+
 #[derive(Subcommand, Debug)]
+#[allow(clippy::enum_variant_names)]
 enum Command {
     Start,
     Send {
@@ -191,6 +312,45 @@ enum Command {
         ttl_ms: Option<u64>,
         #[arg(long)]
         correlation_id: Option<String>,
+        /// Id of the message this one replies to.
+        #[arg(long)]
+        reply_to: Option<String>,
+        /// Saved template filling title and content; explicit `--title` or
+        /// `--content` take precedence over the template's.
+        #[arg(long, conflicts_with = "payload_json")]
+        template: Option<String>,
+        /// Print only the message id.
+        #[arg(long)]
+        id_only: bool,
+        /// Validate the message and print the `sdk_send_v2` params it would
+        /// be sent with, without contacting the daemon.
+        #[arg(long, conflicts_with = "wait")]
+        dry_run: bool,
+        /// Return only once delivery reaches a terminal status (or the wait times
+        /// out); the exit code reflects the outcome.
+        #[arg(long)]
+        wait: bool,
+        #[arg(long, default_value_t = 60_000, requires = "wait")]
+        wait_timeout_ms: u64,
+    },
+    /// Send LXMF command entries (field 9), one per `--command ID:TEXT`.
+    SendCommand {
+        #[arg(long)]
+        source: String,
+        #[arg(long)]
+        destination: String,
+        /// Command as `<id>:<text>`, where `<id>` is 0-255; repeat to send several.
+        #[arg(long = "command", value_name = "ID:TEXT", required = true)]
+        commands: Vec<String>,
+        #[arg(long)]
+        content: Option<String>,
+        #[arg(long)]
+        title: Option<String>,
+        #[arg(long)]
+        payload_json: Option<String>,
+        /// Print only the message id.
+        #[arg(long)]
+        id_only: bool,
     },
     Cancel {
         #[arg(long)]
@@ -199,14 +359,31 @@ enum Command {
     Status {
         #[arg(long)]
         message_id: String,
+        /// Keep polling until the message reaches a terminal status, printing
+        /// each state change.
+        #[arg(long)]
+        watch: bool,
+        #[arg(long, default_value_t = 1_000, requires = "watch")]
+        interval_ms: u64,
     },
     Poll {
         #[arg(long)]
         cursor: Option<String>,
         #[arg(long, default_value_t = 64)]
         max: usize,
+        /// Keep polling from the last cursor until interrupted; requires
+        /// `--output ndjson`.
+        #[arg(long)]
+        follow: bool,
+        /// Only return events of this type; repeat for several types.
+        #[arg(long = "event-type")]
+        event_types: Vec<String>,
+    },
+    Snapshot {
+        /// Only print fields changed since this snapshot revision.
+        #[arg(long)]
+        since_revision: Option<u64>,
     },
-    Snapshot,
     Configure {
         #[arg(long)]
         expected_revision: u64,
@@ -216,6 +393,9 @@ enum Command {
     Shutdown {
         #[arg(long, value_enum, default_value_t = ShutdownModeArg::Graceful)]
         mode: ShutdownModeArg,
+        /// How long a graceful shutdown waits for queued sends to settle.
+        #[arg(long)]
+        drain_timeout_ms: Option<u64>,
     },
     Tick {
         #[arg(long, default_value_t = 128)]
@@ -223,18 +403,221 @@ enum Command {
         #[arg(long)]
         max_duration_ms: Option<u64>,
     },
+    Peers {
+        #[arg(long, default_value_t = 500)]
+        limit: usize,
+        #[arg(long, default_value_t = 300)]
+        fresh_secs: u64,
+        #[arg(long, default_value_t = 3_600)]
+        recent_secs: u64,
+        #[arg(long, default_value_t = 86_400)]
+        stale_secs: u64,
+        #[arg(long = "only", value_enum)]
+        only: Vec<PeerFreshness>,
+        #[arg(long, value_enum, default_value_t = PeerSortArg::LastSeen)]
+        sort: PeerSortArg,
+        #[arg(long)]
+        no_color: bool,
+        #[arg(
+            long,
+            value_enum,
+            value_delimiter = ',',
+            default_values_t = [NameSourceArg::ContactAlias, NameSourceArg::Announced]
+        )]
+        name_preference: Vec<NameSourceArg>,
+    },
+    Contacts {
+        #[command(subcommand)]
+        command: ContactsCommand,
+    },
+    Propagation {
+        #[command(subcommand)]
+        command: PropagationCommand,
+    },
+    StampStatus,
+    Health,
+    /// Print this node's identity hash, delivery hash, display name and profile.
+    Whoami,
+    /// Print the daemon's contract release and build without starting a session.
+    Version,
+    /// Print the start request `start` would send, marking each setting as a
+    /// profile default or an override. Secrets are redacted; the daemon is
+    /// not contacted.
+    DumpConfig,
+    /// Change the announced display name and publish it immediately.
+    SetDisplayName {
+        name: String,
+    },
+    Logs {
+        #[arg(long)]
+        after_offset: Option<u64>,
+        #[arg(long, default_value_t = 400)]
+        max_lines: usize,
+        /// Only lines at this level or above; unlabelled lines count as info.
+        #[arg(long, value_enum)]
+        level: Option<LogLevelArg>,
+    },
+    Trace {
+        #[arg(long)]
+        message_id: String,
+        #[arg(long)]
+        follow: bool,
+    },
+    Prune {
+        #[arg(long)]
+        older_than_ms: u64,
+    },
+    /// Search stored messages on the daemon, newest first.
+    Search {
+        /// Case-insensitive text matched against title and content.
+        #[arg(long)]
+        query: Option<String>,
+        #[arg(long)]
+        source: Option<String>,
+        #[arg(long)]
+        destination: Option<String>,
+        #[arg(long)]
+        from_ts_ms: Option<u64>,
+        #[arg(long)]
+        to_ts_ms: Option<u64>,
+        /// Only the given message and the replies linked to it.
+        #[arg(long)]
+        thread: Option<String>,
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+        /// `next_cursor` from a previous search, to fetch the next page.
+        #[arg(long)]
+        cursor: Option<String>,
+    },
+    /// Download one attachment of a stored message and verify its checksum.
+    Attachment {
+        #[arg(long)]
+        message_id: String,
+        /// Zero-based position of the attachment within the message.
+        #[arg(long)]
+        index: usize,
+        #[arg(long, value_hint = ValueHint::FilePath)]
+        out_path: String,
+    },
+    /// Archive stored messages as a versioned JSON bundle, oldest first.
+    Export {
+        /// Only messages sent to or received from this peer.
+        #[arg(long)]
+        peer: Option<String>,
+        #[arg(long)]
+        from_ts_ms: Option<u64>,
+        #[arg(long)]
+        to_ts_ms: Option<u64>,
+        #[arg(long, value_hint = ValueHint::FilePath)]
+        out_path: String,
+    },
+    /// Manage saved compose templates used by `send --template`.
+    Templates {
+        #[command(subcommand)]
+        command: TemplatesCommand,
+    },
     Completions {
         #[arg(long, value_enum)]
         shell: CompletionShellArg,
     },
 }
 
+#[derive(Subcommand, Debug)]
+enum TemplatesCommand {
+    List,
+    /// Save a template, replacing any existing one with the same name.
+    /// `{source}` and `{destination}` are substituted at send time.
+    Save {
+        name: String,
+        #[arg(long)]
+        title: Option<String>,
+        #[arg(long)]
+        content: String,
+    },
+    Remove {
+        name: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ContactsCommand {
+    Resolve { selector: String },
+}
+
+#[derive(Subcommand, Debug)]
+enum PropagationCommand {
+    Stats,
+    /// Start a sync against the selected propagation node now.
+    Sync,
+}
+
+/// Age thresholds (in seconds) separating the peer freshness buckets.
+///
+/// A peer is `fresh` up to `fresh_secs`, `recent` up to `recent_secs`, `stale`
+/// up to `stale_secs` and `dead` beyond that.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct FreshnessThresholds {
+    fresh_secs: u64,
+    recent_secs: u64,
+    stale_secs: u64,
+}
+
+impl FreshnessThresholds {
+    fn validate(&self) -> Result<(), SdkError> {
+        if self.fresh_secs == 0 {
+            return Err(invalid_argument("--fresh-secs must be greater than zero"));
+        }
+        if self.fresh_secs > self.recent_secs || self.recent_secs > self.stale_secs {
+            return Err(invalid_argument(
+                "freshness thresholds must satisfy fresh-secs <= recent-secs <= stale-secs",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Buckets a peer by how long ago it was last seen. Both timestamps are
+    /// epoch seconds; a `last_seen` in the future counts as fresh.
+    fn classify(&self, last_seen: i64, now: i64) -> PeerFreshness {
+        let age = now.saturating_sub(last_seen).max(0) as u64;
+        if age <= self.fresh_secs {
+            PeerFreshness::Fresh
+        } else if age <= self.recent_secs {
+            PeerFreshness::Recent
+        } else if age <= self.stale_secs {
+            PeerFreshness::Stale
+        } else {
+            PeerFreshness::Dead
+        }
+    }
+}
+
+impl PeerFreshness {
+    fn as_str(self) -> &'static str {
+        match self {
+            PeerFreshness::Fresh => "fresh",
+            PeerFreshness::Recent => "recent",
+            PeerFreshness::Stale => "stale",
+            PeerFreshness::Dead => "dead",
+        }
+    }
+
+    fn ansi_color(self) -> &'static str {
+        match self {
+            PeerFreshness::Fresh => "32",
+            PeerFreshness::Recent => "36",
+            PeerFreshness::Stale => "33",
+            PeerFreshness::Dead => "31",
+        }
+    }
+}
+
 fn main() -> ExitCode {
     let cli = Cli::parse();
     match run(&cli) {
         Ok(output) => {
+            let code = exit_code_for(&cli.command, &output);
             emit_output(&cli, output);
-            ExitCode::SUCCESS
+            ExitCode::from(code)
         }
         Err(err) => {
             emit_error(&cli, err);
@@ -251,53 +634,101 @@ fn run(cli: &Cli) -> Result<JsonValue, SdkError> {
         }));
     }
 
-    let backend = RpcBackendClient::new(cli.rpc.clone());
+    if let Command::Templates { command } = &cli.command {
+        return run_templates_command(&templates_path(cli)?, command);
+    }
+
+    if let Command::DumpConfig = &cli.command {
+        return dump_config(cli);
+    }
+
+    let backend = RpcBackendClient::new(cli.rpc.clone()).with_max_body_bytes(cli.max_body_bytes);
     let client = Client::new(backend);
 
     match &cli.command {
         Command::Start => {
+            if let Some(path) = cli.profile_path.as_deref() {
+                std::fs::create_dir_all(path).map_err(|err| {
+                    SdkError::new(
+                        error_code::INTERNAL,
+                        ErrorCategory::Storage,
+                        format!("profile path '{path}': {err}"),
+                    )
+                })?;
+            }
             let handle = client.start(build_start_request(cli)?)?;
             Ok(json!({ "runtime": handle }))
         }
-        Command::Send {
-            source,
-            destination,
-            content,
-            title,
-            payload_json,
-            idempotency_key,
-            ttl_ms,
-            correlation_id,
-        } => {
-            ensure_started(&client, cli)?;
-            let payload =
-                build_payload(content.as_deref(), title.as_deref(), payload_json.as_deref())?;
-            let mut req = SendRequest::new(source.clone(), destination.clone(), payload);
-            if let Some(key) = idempotency_key.clone() {
-                req = req.with_idempotency_key(key);
+        Command::Send { dry_run, wait, wait_timeout_ms, .. } => {
+            let req = build_send_request(cli)?;
+            if *dry_run {
+                let params = client.backend().prepare_send_params(req);
+                return Ok(json!({ "dry_run": true, "params": params }));
             }
-            if let Some(ttl_ms) = ttl_ms {
-                req = req.with_ttl_ms(*ttl_ms);
-            }
-            if let Some(correlation_id) = correlation_id.clone() {
-                req = req.with_correlation_id(correlation_id);
+            ensure_started(&client, cli)?;
+            let message_id = client.send(req)?;
+            let deduplicated = client.is_deduplicated(&message_id);
+            if !*wait {
+                return Ok(json!({ "message_id": message_id, "deduplicated": deduplicated }));
             }
+            let status = wait_for_terminal_status(&client, &message_id, *wait_timeout_ms)?;
+            Ok(json!({
+                "message_id": message_id,
+                "deduplicated": deduplicated,
+                "status": status,
+                "timed_out": !status.as_ref().is_some_and(|status| status.terminal),
+            }))
+        }
+        Command::SendCommand { .. } => {
+            let req = build_send_command_request(cli)?;
+            ensure_started(&client, cli)?;
             let message_id = client.send(req)?;
-            Ok(json!({ "message_id": message_id }))
+            let deduplicated = client.is_deduplicated(&message_id);
+            Ok(json!({ "message_id": message_id, "deduplicated": deduplicated }))
         }
         Command::Cancel { message_id } => {
             ensure_started(&client, cli)?;
             let result = client.cancel(MessageId(message_id.clone()))?;
             Ok(json!({ "result": result }))
         }
-        Command::Status { message_id } => {
+        Command::Status { message_id, watch, interval_ms } => {
             ensure_started(&client, cli)?;
-            let snapshot = client.status(MessageId(message_id.clone()))?;
-            Ok(json!({ "message": snapshot }))
+            let id = MessageId(message_id.clone());
+            if !*watch {
+                let snapshot = client.status(id)?;
+                return Ok(json!({ "message": snapshot }));
+            }
+            let live = output_mode(cli) == OutputModeArg::Human && !cli.quiet;
+            let (snapshot, transitions) = watch_status(
+                || client.status(id.clone()),
+                Duration::from_millis(*interval_ms),
+                |snapshot| {
+                    if live {
+                        println!("message status: {}", json!(snapshot));
+                    }
+                },
+            )?;
+            Ok(json!({ "message": snapshot, "transitions": transitions }))
         }
-        Command::Poll { cursor, max } => {
+        Command::Poll { cursor, max, follow, event_types } => {
+            if *follow && output_mode(cli) != OutputModeArg::Ndjson {
+                return Err(invalid_argument("--follow requires --output ndjson"));
+            }
             ensure_started(&client, cli)?;
-            let batch = client.poll_events(cursor.clone().map(EventCursor), *max)?;
+            if output_mode(cli) == OutputModeArg::Ndjson {
+                // The event lines are the command's data, so `--quiet` does
+                // not suppress them.
+                let (streamed, next_cursor) = stream_poll_events(
+                    cursor.clone().map(EventCursor),
+                    *follow,
+                    POLL_FOLLOW_INTERVAL,
+                    |cursor| client.poll_events_filtered(cursor, *max, event_types),
+                    |event| println!("{event}"),
+                )?;
+                return Ok(json!({ "streamed": streamed, "next_cursor": next_cursor }));
+            }
+            let batch =
+                client.poll_events_filtered(cursor.clone().map(EventCursor), *max, event_types)?;
             Ok(json!({
                 "events": batch.events,
                 "next_cursor": batch.next_cursor,
@@ -305,11 +736,19 @@ fn run(cli: &Cli) -> Result<JsonValue, SdkError> {
                 "snapshot_high_watermark_seq_no": batch.snapshot_high_watermark_seq_no
             }))
         }
-        Command::Snapshot => {
+        Command::Snapshot { since_revision: None } => {
             ensure_started(&client, cli)?;
             let snapshot = client.snapshot()?;
             Ok(json!({ "runtime": snapshot }))
         }
+        Command::Snapshot { since_revision: Some(since_revision) } => {
+            ensure_started(&client, cli)?;
+            let delta = client.snapshot_since(*since_revision)?;
+            if delta.unchanged {
+                return Ok(json!({ "unchanged": true }));
+            }
+            Ok(json!({ "runtime": delta.changed, "snapshot_revision": delta.snapshot_revision }))
+        }
         Command::Configure { expected_revision, patch_json } => {
             ensure_started(&client, cli)?;
             let patch: ConfigPatch = serde_json::from_str(patch_json).map_err(|err| {
@@ -318,13 +757,20 @@ fn run(cli: &Cli) -> Result<JsonValue, SdkError> {
             let ack = client.configure(*expected_revision, patch)?;
             Ok(json!({ "ack": ack }))
         }
-        Command::Shutdown { mode } => {
+        Command::Shutdown { mode, drain_timeout_ms } => {
+            if *mode == ShutdownModeArg::Immediate && drain_timeout_ms.is_some() {
+                return Err(invalid_argument(
+                    "--drain-timeout-ms only applies to graceful shutdown",
+                ));
+            }
             ensure_started(&client, cli)?;
-            let shutdown_mode = match mode {
-                ShutdownModeArg::Graceful => ShutdownMode::Graceful,
-                ShutdownModeArg::Immediate => ShutdownMode::Immediate,
+            let ack = match (mode, drain_timeout_ms) {
+                (ShutdownModeArg::Graceful, Some(drain_timeout_ms)) => {
+                    client.shutdown_graceful(*drain_timeout_ms)?
+                }
+                (ShutdownModeArg::Graceful, None) => client.shutdown(ShutdownMode::Graceful)?,
+                (ShutdownModeArg::Immediate, _) => client.shutdown(ShutdownMode::Immediate)?,
             };
-            let ack = client.shutdown(shutdown_mode)?;
             Ok(json!({ "ack": ack }))
         }
         Command::Tick { max_work_items, max_duration_ms } => {
@@ -336,75 +782,996 @@ fn run(cli: &Cli) -> Result<JsonValue, SdkError> {
             let result = client.tick(budget)?;
             Ok(json!({ "tick": result }))
         }
-        Command::Completions { .. } => unreachable!("handled before backend bootstrap"),
+        Command::Peers {
+            limit,
+            fresh_secs,
+            recent_secs,
+            stale_secs,
+            only,
+            sort,
+            no_color: _,
+            name_preference,
+        } => {
+            let thresholds = FreshnessThresholds {
+                fresh_secs: *fresh_secs,
+                recent_secs: *recent_secs,
+                stale_secs: *stale_secs,
+            };
+            thresholds.validate()?;
+            if *limit == 0 {
+                return Err(invalid_argument("--limit must be greater than zero"));
+            }
+            ensure_started(&client, cli)?;
+            let mut peers = fetch_presence(&client, *limit)?;
+            let aliases = if name_preference.contains(&NameSourceArg::ContactAlias) {
+                contact_aliases(&fetch_contacts(&client)?)
+            } else {
+                BTreeMap::new()
+            };
+            apply_name_preference(&mut peers, &aliases, name_preference);
+            Ok(build_peer_view(peers, thresholds, only, *sort, now_epoch_secs()))
+        }
+        Command::Contacts { command: ContactsCommand::Resolve { selector } } => {
+            ensure_started(&client, cli)?;
+            let contacts = fetch_contacts(&client)?;
+            resolve_contact(&contacts, selector)
+        }
+        Command::Propagation { command: PropagationCommand::Stats } => {
+            ensure_started(&client, cli)?;
+            Ok(json!({ "stats": client.propagation_stats()? }))
+        }
+        Command::Propagation { command: PropagationCommand::Sync } => {
+            ensure_started(&client, cli)?;
+            Ok(json!({ "sync": client.propagation_sync_now()? }))
+        }
+        Command::StampStatus => {
+            ensure_started(&client, cli)?;
+            Ok(json!({ "stamp_status": client.stamp_status()? }))
+        }
+        Command::Version => Ok(json!({ "version": client.version()? })),
+        Command::Whoami => {
+            ensure_started(&client, cli)?;
+            Ok(json!({ "whoami": client.whoami()? }))
+        }
+        Command::SetDisplayName { name } => {
+            ensure_started(&client, cli)?;
+            Ok(json!({ "display_name": client.set_display_name(name)? }))
+        }
+        Command::Health => {
+            ensure_started(&client, cli)?;
+            let health = client.health()?;
+            Ok(json!({ "status": health.status(), "health": health }))
+        }
+        Command::Logs { after_offset, max_lines, level } => {
+            ensure_started(&client, cli)?;
+            let logs = match level {
+                Some(level) => {
+                    client.logs_tail_at_level(*after_offset, *max_lines, log_level_value(*level))?
+                }
+                None => client.logs_tail(*after_offset, *max_lines)?,
+            };
+            Ok(json!({ "logs": logs }))
+        }
+        Command::Trace { message_id, follow } => {
+            ensure_started(&client, cli)?;
+            let id = MessageId(message_id.clone());
+            let mut trace = client.delivery_trace(id.clone())?;
+            while *follow && !trace.terminal {
+                std::thread::sleep(TRACE_FOLLOW_INTERVAL);
+                trace = client.delivery_trace(id.clone())?;
+            }
+            Ok(json!({
+                "message_id": trace.message_id,
+                "trace": trace.trace,
+                "terminal": trace.terminal,
+            }))
+        }
+        Command::Prune { older_than_ms } => {
+            ensure_started(&client, cli)?;
+            let result = client.prune_messages(*older_than_ms)?;
+            Ok(json!({ "pruned": result.pruned, "cutoff_ts_ms": result.cutoff_ts_ms }))
+        }
+        Command::Search {
+            query,
+            source,
+            destination,
+            from_ts_ms,
+            to_ts_ms,
+            thread,
+            limit,
+            cursor,
+        } => {
+            ensure_started(&client, cli)?;
+            let result = client.messages_search(MessageSearchRequest {
+                query: query.clone(),
+                source: source.clone(),
+                destination: destination.clone(),
+                from_ts_ms: *from_ts_ms,
+                to_ts_ms: *to_ts_ms,
+                thread: thread.clone(),
+                cursor: cursor.clone(),
+                limit: Some(*limit),
+            })?;
+            Ok(json!({ "messages": result.messages, "next_cursor": result.next_cursor }))
+        }
+        Command::Attachment { message_id, index, out_path } => {
+            ensure_started(&client, cli)?;
+            let attachment =
+                client.message_attachment_get(MessageId(message_id.clone()), *index)?;
+            let bytes = attachment.decode_verified()?;
+            std::fs::write(out_path, &bytes).map_err(|err| {
+                SdkError::new(
+                    error_code::INTERNAL,
+                    ErrorCategory::Storage,
+                    format!("failed to write attachment to '{out_path}': {err}"),
+                )
+            })?;
+            Ok(json!({
+                "message_id": attachment.message_id,
+                "index": attachment.index,
+                "name": attachment.name,
+                "content_type": attachment.content_type,
+                "size_bytes": bytes.len(),
+                "checksum_sha256": attachment.checksum_sha256,
+                "extensions": attachment.extensions,
+                "out_path": out_path,
+            }))
+        }
+        Command::Export { peer, from_ts_ms, to_ts_ms, out_path } => {
+            ensure_started(&client, cli)?;
+            let request = MessageExportRequest {
+                peer: peer.clone(),
+                from_ts_ms: *from_ts_ms,
+                to_ts_ms: *to_ts_ms,
+                ..MessageExportRequest::default()
+            };
+            let bundle = export_all_messages(request, |req| client.messages_export(req))?;
+            let encoded = serde_json::to_vec_pretty(&bundle).map_err(|err| {
+                SdkError::new(error_code::INTERNAL, ErrorCategory::Internal, err.to_string())
+            })?;
+            std::fs::write(out_path, encoded).map_err(|err| {
+                SdkError::new(
+                    error_code::INTERNAL,
+                    ErrorCategory::Storage,
+                    format!("failed to write export to '{out_path}': {err}"),
+                )
+            })?;
+            Ok(json!({
+                "version": bundle.version,
+                "peer": bundle.peer,
+                "messages": bundle.messages.len(),
+                "out_path": out_path,
+            }))
+        }
+        Command::Completions { .. } | Command::Templates { .. } | Command::DumpConfig => {
+            unreachable!("handled before backend bootstrap")
+        }
     }
 }
 
-fn ensure_started(client: &Client<RpcBackendClient>, cli: &Cli) -> Result<(), SdkError> {
-    let _ = client.start(build_start_request(cli)?)?;
-    Ok(())
+/// Exports every page of `request` into one bundle, following `next_cursor`
+/// so no single response has to carry the whole archive.
+fn export_all_messages(
+    mut request: MessageExportRequest,
+    mut export: impl FnMut(MessageExportRequest) -> Result<MessageExportBundle, SdkError>,
+) -> Result<MessageExportBundle, SdkError> {
+    let mut bundle = export(request.clone())?;
+    while let Some(cursor) = bundle.next_cursor.take() {
+        request.cursor = Some(cursor);
+        let mut page = export(request.clone())?;
+        bundle.messages.append(&mut page.messages);
+        bundle.next_cursor = page.next_cursor;
+    }
+    Ok(bundle)
 }
 
-fn build_payload(
-    content: Option<&str>,
-    title: Option<&str>,
-    payload_json: Option<&str>,
-) -> Result<JsonValue, SdkError> {
-    if let Some(raw) = payload_json {
-        if content.is_some() || title.is_some() {
-            return Err(invalid_argument(
-                "payload_json cannot be combined with content/title flags",
-            ));
+/// Polls from `cursor`, handing each event to `emit` as one JSON value and
+/// carrying `next_cursor` into the following poll so nothing is repeated or
+/// skipped. With `follow` it keeps polling, sleeping `interval` after empty
+/// batches, until a poll fails. Returns the emitted count and last cursor.
+fn stream_poll_events(
+    mut cursor: Option<EventCursor>,
+    follow: bool,
+    interval: Duration,
+    mut poll: impl FnMut(Option<EventCursor>) -> Result<EventBatch, SdkError>,
+    mut emit: impl FnMut(&JsonValue),
+) -> Result<(usize, Option<EventCursor>), SdkError> {
+    let mut streamed = 0;
+    loop {
+        let batch = poll(cursor.clone())?;
+        for event in &batch.events {
+            emit(&json!(event));
+        }
+        streamed += batch.events.len();
+        let idle = batch.events.is_empty();
+        cursor = Some(batch.next_cursor);
+        if !follow {
+            return Ok((streamed, cursor));
+        }
+        if idle {
+            std::thread::sleep(interval);
         }
-        return serde_json::from_str(raw)
-            .map_err(|err| invalid_argument(format!("payload_json is not valid JSON: {err}")));
     }
+}
 
-    let content = content.unwrap_or("").trim().to_owned();
-    if content.is_empty() {
-        return Err(invalid_argument("content is required when payload_json is not provided"));
+fn wait_for_terminal_status(
+    client: &Client<RpcBackendClient>,
+    message_id: &MessageId,
+    timeout_ms: u64,
+) -> Result<Option<DeliverySnapshot>, SdkError> {
+    let deadline = std::time::Instant::now() + Duration::from_millis(timeout_ms);
+    loop {
+        let status = client.status(message_id.clone())?;
+        let terminal = status.as_ref().is_some_and(|status| status.terminal);
+        if terminal || std::time::Instant::now() >= deadline {
+            return Ok(status);
+        }
+        std::thread::sleep(SEND_WAIT_INTERVAL);
     }
+}
 
-    Ok(json!({
-        "content": content,
-        "title": title.unwrap_or_default(),
-    }))
+/// Polls `fetch` every `interval` and reports each change of delivery state to
+/// `on_transition`. Stops at the first terminal snapshot, or right away when the
+/// message is unknown, and returns the last snapshot with every state seen.
+fn watch_status(
+    mut fetch: impl FnMut() -> Result<Option<DeliverySnapshot>, SdkError>,
+    interval: Duration,
+    mut on_transition: impl FnMut(Option<&DeliverySnapshot>),
+) -> Result<(Option<DeliverySnapshot>, Vec<DeliveryState>), SdkError> {
+    let mut transitions = Vec::new();
+    loop {
+        let snapshot = fetch()?;
+        let Some(current) = snapshot.as_ref() else {
+            on_transition(None);
+            return Ok((None, transitions));
+        };
+        if transitions.last() != Some(&current.state) {
+            transitions.push(current.state.clone());
+            on_transition(Some(current));
+        }
+        if current.terminal {
+            return Ok((snapshot, transitions));
+        }
+        std::thread::sleep(interval);
+    }
 }
 
-fn build_start_request(cli: &Cli) -> Result<StartRequest, SdkError> {
-    let mut config = match cli.profile {
-        ProfileArg::DesktopFull => SdkConfig::desktop_full_default(),
-        ProfileArg::DesktopLocalRuntime => SdkConfig::desktop_local_default(),
-        ProfileArg::EmbeddedAlloc => SdkConfig::embedded_alloc_default(),
+fn send_wait_exit_code(status: Option<&DeliverySnapshot>) -> u8 {
+    let Some(status) = status.filter(|status| status.terminal) else {
+        return EXIT_SEND_TIMED_OUT;
+    };
+    match status.state {
+        DeliveryState::Delivered | DeliveryState::Sent => 0,
+        DeliveryState::Expired => EXIT_SEND_EXPIRED,
+        DeliveryState::Cancelled => EXIT_SEND_CANCELLED,
+        _ => EXIT_SEND_FAILED,
     }
-    .with_rpc_listen_addr(cli.rpc.clone());
-    config.bind_mode = bind_mode_value(cli.bind_mode);
-    config.auth_mode = auth_mode_value(cli.auth_mode);
-    config.overflow_policy = overflow_policy_value(cli.overflow_policy);
-    config.block_timeout_ms = cli.block_timeout_ms;
-    config.event_stream.max_poll_events = cli.max_poll_events;
-    config.event_stream.max_event_bytes = cli.max_event_bytes;
-    config.event_stream.max_batch_bytes = cli.max_batch_bytes;
-    config.event_stream.max_extension_keys = cli.max_extension_keys;
-    config.idempotency_ttl_ms = cli.idempotency_ttl_ms;
-    if let Some(backend) = config.rpc_backend.as_mut() {
-        backend.listen_addr = cli.rpc.clone();
-        backend.read_timeout_ms = cli.read_timeout_ms;
-        backend.write_timeout_ms = cli.write_timeout_ms;
-        backend.max_header_bytes = cli.max_header_bytes;
-        backend.max_body_bytes = cli.max_body_bytes;
+}
+
+fn exit_code_for(command: &Command, output: &JsonValue) -> u8 {
+    match command {
+        Command::Send { wait: true, .. } => {
+            let status = output
+                .get("status")
+                .cloned()
+                .and_then(|status| serde_json::from_value::<DeliverySnapshot>(status).ok());
+            send_wait_exit_code(status.as_ref())
+        }
+        _ => 0,
     }
+}
 
-    match config.auth_mode {
-        AuthMode::Token => {
-            let issuer = required_string(
-                cli.token_issuer.as_deref(),
-                "--token-issuer is required in token auth mode",
-            )?;
-            let audience = required_string(
-                cli.token_audience.as_deref(),
-                "--token-audience is required in token auth mode",
-            )?;
+fn fetch_contacts(client: &Client<RpcBackendClient>) -> Result<Vec<ContactRecord>, SdkError> {
+    let mut contacts = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = client.identity_contact_list(ContactListRequest {
+            cursor,
+            limit: Some(500),
+            extensions: BTreeMap::new(),
+        })?;
+        contacts.extend(page.contacts);
+        if page.next_cursor.is_none() {
+            break;
+        }
+        cursor = page.next_cursor;
+    }
+    Ok(contacts)
+}
+
+/// Resolves an alias to its destination hash(es), or a hash to its alias(es).
+/// The selector is trimmed, may carry a leading `@`, and matches case-insensitively.
+fn resolve_contact(contacts: &[ContactRecord], selector: &str) -> Result<JsonValue, SdkError> {
+    let trimmed = selector.trim();
+    let normalized = trimmed.strip_prefix('@').unwrap_or(trimmed);
+    if normalized.is_empty() {
+        return Err(invalid_argument("contact selector must not be empty"));
+    }
+
+    let by_hash = contacts
+        .iter()
+        .filter(|contact| contact.identity.0.eq_ignore_ascii_case(normalized))
+        .collect::<Vec<_>>();
+    if let Some(first) = by_hash.first() {
+        let aliases =
+            by_hash.iter().filter_map(|contact| contact.display_name.clone()).collect::<Vec<_>>();
+        return Ok(json!({
+            "selector": selector,
+            "kind": "hash",
+            "hash": first.identity.0.to_ascii_lowercase(),
+            "aliases": aliases,
+        }));
+    }
+
+    let mut hashes = contacts
+        .iter()
+        .filter(|contact| {
+            contact
+                .display_name
+                .as_deref()
+                .is_some_and(|alias| alias.trim().eq_ignore_ascii_case(normalized))
+        })
+        .map(|contact| contact.identity.0.to_ascii_lowercase())
+        .collect::<Vec<_>>();
+    hashes.sort();
+    hashes.dedup();
+    if hashes.is_empty() {
+        return Err(invalid_argument(format!("no contact matches alias or hash '{normalized}'"))
+            .with_detail("selector", JsonValue::String(selector.to_owned()))
+            .with_detail("reason", JsonValue::String("not_found".to_owned())));
+    }
+    Ok(json!({
+        "selector": selector,
+        "kind": "alias",
+        "hashes": hashes,
+    }))
+}
+
+fn propagation_stats_lines(stats: &JsonValue) -> Vec<String> {
+    let count = |field: &str| stats.get(field).and_then(JsonValue::as_u64).unwrap_or(0);
+    let enabled = stats.get("enabled").and_then(JsonValue::as_bool).unwrap_or(false);
+    vec![
+        format!("propagation: {}", if enabled { "enabled" } else { "disabled" }),
+        format!(
+            "ingested: {} (last batch {})",
+            count("ingested_total"),
+            count("last_ingest_count")
+        ),
+        format!("fetches served: {} (misses {})", count("fetches_served"), count("fetch_misses")),
+        format!("rejected stamps: {}", count("rejected_stamps")),
+        format!("store: {} messages, {} bytes", count("store_messages"), count("store_bytes")),
+    ]
+}
+
+fn attachment_type_line(attachment: &JsonValue) -> String {
+    let content_type = attachment.get("content_type").and_then(JsonValue::as_str).unwrap_or("-");
+    let dimension = |key: &str| {
+        attachment.get("extensions").and_then(|ext| ext.get(key)).and_then(JsonValue::as_u64)
+    };
+    match (dimension("width"), dimension("height")) {
+        (Some(width), Some(height)) => format!("type: {content_type}, {width}x{height}"),
+        _ => format!("type: {content_type}"),
+    }
+}
+
+fn propagation_sync_lines(sync: &JsonValue) -> Vec<String> {
+    let text = |field: &str| sync.get(field).and_then(JsonValue::as_str);
+    let progress = sync.get("progress").and_then(JsonValue::as_f64).unwrap_or(0.0);
+    let mut lines = vec![
+        format!("sync: {} ({:.0}%)", text("state_name").unwrap_or("idle"), progress * 100.0),
+        format!("propagation node: {}", text("selected_node").unwrap_or("-")),
+    ];
+    if sync.get("already_running").and_then(JsonValue::as_bool).unwrap_or(false) {
+        lines.push("already running; no new sync started".to_string());
+    }
+    if let Some(error) = text("last_sync_error") {
+        lines.push(format!("last error: {error}"));
+    }
+    lines
+}
+
+fn whoami_lines(whoami: &JsonValue) -> Vec<String> {
+    let field = |key: &str| whoami.get(key).and_then(JsonValue::as_str).unwrap_or("-");
+    vec![
+        format!("identity: {}", field("identity_hash")),
+        format!("delivery destination: {}", field("delivery_destination_hash")),
+        format!("display name: {}", field("display_name")),
+        format!("profile: {}", field("profile")),
+    ]
+}
+
+fn version_lines(version: &JsonValue) -> Vec<String> {
+    let field = |key: &str| version.get(key).and_then(JsonValue::as_str).unwrap_or("-");
+    let supported = version
+        .get("supported_contract_versions")
+        .and_then(JsonValue::as_array)
+        .map(|versions| versions.iter().map(JsonValue::to_string).collect::<Vec<_>>().join(", "))
+        .filter(|versions| !versions.is_empty())
+        .unwrap_or_else(|| "-".to_string());
+    let features = version
+        .get("features")
+        .and_then(JsonValue::as_array)
+        .map(|features| {
+            features.iter().filter_map(JsonValue::as_str).collect::<Vec<_>>().join(", ")
+        })
+        .filter(|features| !features.is_empty())
+        .unwrap_or_else(|| "-".to_string());
+    vec![
+        format!("runtime version: {} ({})", field("crate_version"), field("git_hash")),
+        format!("contract release: {}", field("contract_release")),
+        format!("schema namespace: {}", field("schema_namespace")),
+        format!("supported contract versions: {supported}"),
+        format!("build: {}", field("build_profile")),
+        format!("features: {features}"),
+    ]
+}
+
+fn display_name_line(update: &JsonValue) -> String {
+    let name = update.get("display_name").and_then(JsonValue::as_str).unwrap_or("-");
+    if update.get("announced").and_then(JsonValue::as_bool).unwrap_or(false) {
+        format!("name published: {name}")
+    } else {
+        format!("name saved: {name}")
+    }
+}
+
+fn health_line(health: &HealthReport) -> String {
+    let status = match health.status() {
+        HealthStatus::Ok => "OK",
+        HealthStatus::Degraded => "DEGRADED",
+        HealthStatus::Down => "DOWN",
+    };
+    format!(
+        "{status}: transport {}, {}/{} interfaces up, propagation {}, {} queued outbound",
+        if health.transport_up { "up" } else { "down" },
+        health.interfaces_up,
+        health.interfaces_total,
+        health.propagation_sync_state,
+        health.outbound_queue_depth,
+    )
+}
+
+fn stamp_status_lines(status: &JsonValue) -> Vec<String> {
+    if !status.get("enabled").and_then(JsonValue::as_bool).unwrap_or(false) {
+        return vec!["propagation node: disabled".to_string()];
+    }
+    let cost = |field: &str| {
+        status.get(field).and_then(JsonValue::as_u64).map_or("-".to_string(), |v| v.to_string())
+    };
+    let range = status
+        .get("accepted_range")
+        .and_then(JsonValue::as_array)
+        .map(|bounds| {
+            bounds.iter().filter_map(JsonValue::as_u64).map(|v| v.to_string()).collect::<Vec<_>>()
+        })
+        .filter(|bounds| bounds.len() == 2)
+        .map_or("-".to_string(), |bounds| bounds.join(".."));
+    let mut lines = vec![
+        "propagation node: enabled".to_string(),
+        format!(
+            "stamp cost: {} (flexibility {})",
+            cost("propagation_stamp_cost"),
+            cost("flexibility")
+        ),
+    ];
+    if status.get("adaptive_cost").is_some() {
+        lines.push(format!("adaptive cost: {}", cost("adaptive_cost")));
+    }
+    lines.push(format!("peering cost: {}", cost("peering_cost")));
+    lines.push(format!("accepted range: {range}"));
+    lines
+}
+
+fn logs_tail_lines(logs: &JsonValue) -> Vec<String> {
+    let mut lines = Vec::new();
+    let missed = logs.get("missed_lines").and_then(JsonValue::as_u64).unwrap_or(0);
+    if missed > 0 {
+        lines.push(format!("... {missed} earlier lines no longer retained"));
+    }
+    if let Some(rows) = logs.get("lines").and_then(JsonValue::as_array) {
+        lines.extend(rows.iter().filter_map(JsonValue::as_str).map(str::to_owned));
+    }
+    lines
+}
+
+/// Renders trace entries as `ts_ms  stage  detail`, splitting statuses such as
+/// `sent: link` into stage and detail and falling back to the reason code.
+fn trace_lines(trace: &JsonValue) -> Vec<String> {
+    trace
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|entry| {
+            let ts_ms = entry.get("timestamp").and_then(JsonValue::as_i64).unwrap_or(0) * 1_000;
+            let status = entry.get("status").and_then(JsonValue::as_str).unwrap_or("-");
+            let (stage, detail) = status.split_once(':').unwrap_or((status, ""));
+            let detail = match detail.trim() {
+                "" => entry.get("reason_code").and_then(JsonValue::as_str).unwrap_or("-"),
+                detail => detail,
+            };
+            format!("{ts_ms}  {}  {detail}", stage.trim())
+        })
+        .collect()
+}
+
+fn search_result_lines(value: &JsonValue) -> Vec<String> {
+    let mut lines = value
+        .get("messages")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+        .map(|message| {
+            let field = |name| message.get(name).and_then(JsonValue::as_str).unwrap_or("-");
+            let ts_ms = message.get("timestamp").and_then(JsonValue::as_i64).unwrap_or(0) * 1_000;
+            let arrow = if field("direction") == "out" { "->" } else { "<-" };
+            let peer =
+                if field("direction") == "out" { field("destination") } else { field("source") };
+            format!(
+                "{ts_ms}  {arrow} {peer}  {}  {}  [{}]",
+                field("title"),
+                field("content"),
+                field("id")
+            )
+        })
+        .collect::<Vec<_>>();
+    if let Some(cursor) = value.get("next_cursor").and_then(JsonValue::as_str) {
+        lines.push(format!("next_cursor: {cursor}"));
+    }
+    lines
+}
+
+fn fetch_presence(
+    client: &Client<RpcBackendClient>,
+    limit: usize,
+) -> Result<Vec<PresenceRecord>, SdkError> {
+    let mut peers = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = client.identity_presence_list(PresenceListRequest {
+            cursor,
+            limit: Some((limit - peers.len()).min(500)),
+            extensions: BTreeMap::new(),
+        })?;
+        peers.extend(page.peers);
+        if peers.len() >= limit || page.next_cursor.is_none() {
+            break;
+        }
+        cursor = page.next_cursor;
+    }
+    peers.truncate(limit);
+    Ok(peers)
+}
+
+fn contact_aliases(contacts: &[ContactRecord]) -> BTreeMap<String, String> {
+    contacts
+        .iter()
+        .filter_map(|contact| {
+            let alias = contact.display_name.as_deref()?.trim();
+            (!alias.is_empty())
+                .then(|| (contact.identity.0.to_ascii_lowercase(), alias.to_string()))
+        })
+        .collect()
+}
+
+/// Picks each peer's label from the first name source in `preference` that has
+/// one, so operators can let their local contact aliases win over announced names.
+fn apply_name_preference(
+    peers: &mut [PresenceRecord],
+    aliases: &BTreeMap<String, String>,
+    preference: &[NameSourceArg],
+) {
+    for peer in peers.iter_mut() {
+        let announced = peer.name.take().filter(|name| !name.trim().is_empty());
+        let announced_source = peer.name_source.take();
+        let alias = aliases.get(&peer.peer_id.to_ascii_lowercase());
+        for source in preference {
+            match source {
+                NameSourceArg::ContactAlias => {
+                    if let Some(alias) = alias {
+                        peer.name = Some(alias.clone());
+                        peer.name_source = Some("contact_alias".to_string());
+                        peer.extensions.remove("display_name");
+                        break;
+                    }
+                }
+                NameSourceArg::Announced => {
+                    if announced.is_some() {
+                        peer.name = announced.clone();
+                        peer.name_source =
+                            announced_source.clone().or_else(|| Some("announced".to_string()));
+                        break;
+                    }
+                }
+            }
+        }
+        if peer.name.is_none() {
+            peer.extensions.remove("display_name");
+        }
+    }
+}
+
+fn build_peer_view(
+    peers: Vec<PresenceRecord>,
+    thresholds: FreshnessThresholds,
+    only: &[PeerFreshness],
+    sort: PeerSortArg,
+    now: i64,
+) -> JsonValue {
+    let mut counts = BTreeMap::from([
+        (PeerFreshness::Fresh.as_str(), 0u64),
+        (PeerFreshness::Recent.as_str(), 0),
+        (PeerFreshness::Stale.as_str(), 0),
+        (PeerFreshness::Dead.as_str(), 0),
+    ]);
+    let mut rows = peers
+        .into_iter()
+        .map(|peer| {
+            let freshness = thresholds.classify(peer.last_seen_ts_ms, now);
+            *counts.entry(freshness.as_str()).or_default() += 1;
+            (freshness, peer)
+        })
+        .filter(|(freshness, _)| only.is_empty() || only.contains(freshness))
+        .collect::<Vec<_>>();
+    rows.sort_by(|(left_freshness, left), (right_freshness, right)| {
+        let primary = match sort {
+            PeerSortArg::LastSeen => right.last_seen_ts_ms.cmp(&left.last_seen_ts_ms),
+            PeerSortArg::Freshness => left_freshness
+                .cmp(right_freshness)
+                .then_with(|| right.last_seen_ts_ms.cmp(&left.last_seen_ts_ms)),
+            PeerSortArg::Name => left
+                .name
+                .as_deref()
+                .unwrap_or(left.peer_id.as_str())
+                .to_lowercase()
+                .cmp(&right.name.as_deref().unwrap_or(right.peer_id.as_str()).to_lowercase()),
+        };
+        primary.then_with(|| left.peer_id.cmp(&right.peer_id))
+    });
+    let peers = rows
+        .into_iter()
+        .map(|(freshness, peer)| {
+            let display_name = peer
+                .extensions
+                .get("display_name")
+                .and_then(JsonValue::as_str)
+                .map(str::to_owned)
+                .or_else(|| peer.name.clone());
+            json!({
+                "peer_id": peer.peer_id,
+                "name": peer.name,
+                "display_name": display_name,
+                "name_source": peer.name_source,
+                "last_seen": peer.last_seen_ts_ms,
+                "age_secs": now.saturating_sub(peer.last_seen_ts_ms).max(0),
+                "seen_count": peer.seen_count,
+                "freshness": freshness.as_str(),
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "peers": peers,
+        "counts": counts,
+        "thresholds": {
+            "fresh_secs": thresholds.fresh_secs,
+            "recent_secs": thresholds.recent_secs,
+            "stale_secs": thresholds.stale_secs,
+        },
+    })
+}
+
+fn now_epoch_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+fn ensure_started(client: &Client<RpcBackendClient>, cli: &Cli) -> Result<(), SdkError> {
+    let _ = client.start(build_start_request(cli)?)?;
+    Ok(())
+}
+
+fn build_send_request(cli: &Cli) -> Result<SendRequest, SdkError> {
+    let Command::Send {
+        source,
+        destination,
+        content,
+        title,
+        payload_json,
+        idempotency_key,
+        ttl_ms,
+        correlation_id,
+        reply_to,
+        template,
+        ..
+    } = &cli.command
+    else {
+        return Err(invalid_argument("send request requires the send command"));
+    };
+    let source = required_string(Some(source), "source must not be empty")?;
+    let destination = required_string(Some(destination), "destination must not be empty")?;
+    let (content, title) = match template {
+        Some(name) => {
+            let store = load_templates(&templates_path(cli)?)?;
+            let template = store
+                .get(name)
+                .ok_or_else(|| invalid_argument(format!("no compose template named '{name}'")))?;
+            let (template_title, template_content) = template.render(&source, &destination);
+            (
+                Some(content.clone().unwrap_or(template_content)),
+                Some(title.clone().unwrap_or(template_title)),
+            )
+        }
+        None => (content.clone(), title.clone()),
+    };
+    let payload = build_payload(content.as_deref(), title.as_deref(), payload_json.as_deref())?;
+    let mut req = SendRequest::new(source, destination, payload);
+    if let Some(key) = idempotency_key.clone() {
+        req = req.with_idempotency_key(key);
+    }
+    if let Some(ttl_ms) = ttl_ms {
+        req = req.with_ttl_ms(*ttl_ms);
+    }
+    if let Some(correlation_id) = correlation_id.clone() {
+        req = req.with_correlation_id(correlation_id);
+    }
+    if let Some(reply_to) = reply_to.as_deref().map(str::trim).filter(|id| !id.is_empty()) {
+        req = req.with_reply_to(reply_to);
+    }
+    Ok(req)
+}
+
+/// JSON key of the LXMF commands field (`FIELD_COMMANDS`, 0x09).
+const FIELD_COMMANDS_KEY: &str = "9";
+
+/// Parses `<id>:<text>` into a command entry with the text as its payload.
+fn parse_command_entry(raw: &str) -> Result<CommandEntry, SdkError> {
+    let (id, text) = raw
+        .split_once(':')
+        .ok_or_else(|| invalid_argument(format!("command '{raw}' must look like <id>:<text>")))?;
+    let id = id.trim().parse::<u8>().map_err(|_| {
+        invalid_argument(format!("command id '{}' must be a number from 0 to 255", id.trim()))
+    })?;
+    Ok(CommandEntry::from_text(id, text))
+}
+
+fn build_send_command_request(cli: &Cli) -> Result<SendRequest, SdkError> {
+    let Command::SendCommand {
+        source, destination, commands, content, title, payload_json, ..
+    } = &cli.command
+    else {
+        return Err(invalid_argument("send-command request requires the send-command command"));
+    };
+    let source = required_string(Some(source), "source must not be empty")?;
+    let destination = required_string(Some(destination), "destination must not be empty")?;
+    let mut payload = match payload_json.as_deref() {
+        Some(raw) => {
+            let payload = build_payload(content.as_deref(), title.as_deref(), Some(raw))?;
+            let JsonValue::Object(map) = payload else {
+                return Err(invalid_argument("payload_json must be an object with --command"));
+            };
+            if [FIELD_COMMANDS_KEY, TRANSPORT_FIELDS_MSGPACK_B64_KEY, "fields"]
+                .iter()
+                .any(|key| map.contains_key(*key))
+            {
+                return Err(invalid_argument(
+                    "--command cannot be combined with fields already set in payload_json",
+                ));
+            }
+            map
+        }
+        None => {
+            let mut map = JsonMap::new();
+            map.insert(
+                "content".to_string(),
+                JsonValue::String(content.clone().unwrap_or_default()),
+            );
+            map.insert("title".to_string(), JsonValue::String(title.clone().unwrap_or_default()));
+            map
+        }
+    };
+    let entries =
+        commands.iter().map(|raw| parse_command_entry(raw)).collect::<Result<Vec<_>, _>>()?;
+    // Command payloads are msgpack binary on the wire, which JSON fields
+    // cannot express, so the field travels pre-encoded.
+    let JsonValue::Object(encoded) = WireFields::new()
+        .set_commands(entries)
+        .to_transport_json()
+        .map_err(|err| invalid_argument(format!("could not encode commands: {err}")))?
+    else {
+        return Err(invalid_argument("encoded commands must be a JSON object"));
+    };
+    payload.extend(encoded);
+    Ok(SendRequest::new(source, destination, JsonValue::Object(payload)))
+}
+
+/// A saved message preset. `{source}` and `{destination}` in the title or
+/// content are replaced with the addresses of the send that uses it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct ComposeTemplate {
+    name: String,
+    #[serde(default)]
+    title: String,
+    content: String,
+}
+
+impl ComposeTemplate {
+    fn render(&self, source: &str, destination: &str) -> (String, String) {
+        let fill =
+            |text: &str| text.replace("{source}", source).replace("{destination}", destination);
+        (fill(&self.title), fill(&self.content))
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct TemplateStore {
+    #[serde(default)]
+    templates: Vec<ComposeTemplate>,
+}
+
+impl TemplateStore {
+    fn get(&self, name: &str) -> Option<&ComposeTemplate> {
+        self.templates.iter().find(|template| template.name == name)
+    }
+
+    fn upsert(&mut self, template: ComposeTemplate) {
+        match self.templates.iter_mut().find(|existing| existing.name == template.name) {
+            Some(existing) => *existing = template,
+            None => self.templates.push(template),
+        }
+    }
+
+    fn remove(&mut self, name: &str) -> bool {
+        let before = self.templates.len();
+        self.templates.retain(|template| template.name != name);
+        self.templates.len() != before
+    }
+}
+
+/// Directory for the CLI's local state. An explicit `--profile-path` must
+/// already exist; `start` is the command that creates it.
+fn profile_dir(cli: &Cli) -> Result<PathBuf, SdkError> {
+    let Some(path) = cli.profile_path.as_deref() else {
+        let home = std::env::var_os("HOME").map(PathBuf::from).unwrap_or_default();
+        return Ok(home.join(".lxmf"));
+    };
+    let path = PathBuf::from(path);
+    if !path.is_dir() {
+        return Err(invalid_argument(format!(
+            "profile path '{}' does not exist; run `start` with it to create it",
+            path.display()
+        )));
+    }
+    Ok(path)
+}
+
+fn templates_path(cli: &Cli) -> Result<PathBuf, SdkError> {
+    if let Some(path) = cli.templates_path.as_deref() {
+        return Ok(PathBuf::from(path));
+    }
+    Ok(profile_dir(cli)?.join(TEMPLATES_FILE_NAME))
+}
+
+fn template_storage_error(path: &std::path::Path, err: impl std::fmt::Display) -> SdkError {
+    SdkError::new(
+        error_code::INTERNAL,
+        ErrorCategory::Storage,
+        format!("compose templates at '{}': {err}", path.display()),
+    )
+}
+
+/// Reads the template file; a missing file is an empty store.
+fn load_templates(path: &std::path::Path) -> Result<TemplateStore, SdkError> {
+    let raw = match std::fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(TemplateStore::default())
+        }
+        Err(err) => return Err(template_storage_error(path, err)),
+    };
+    serde_json::from_str(&raw).map_err(|err| template_storage_error(path, err))
+}
+
+fn save_templates(path: &std::path::Path, store: &TemplateStore) -> Result<(), SdkError> {
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|err| template_storage_error(path, err))?;
+    }
+    let raw =
+        serde_json::to_string_pretty(store).map_err(|err| template_storage_error(path, err))?;
+    std::fs::write(path, raw).map_err(|err| template_storage_error(path, err))
+}
+
+fn run_templates_command(
+    path: &std::path::Path,
+    command: &TemplatesCommand,
+) -> Result<JsonValue, SdkError> {
+    let mut store = load_templates(path)?;
+    match command {
+        TemplatesCommand::List => Ok(json!({ "templates": store.templates })),
+        TemplatesCommand::Save { name, title, content } => {
+            let name = required_string(Some(name), "template name must not be empty")?;
+            let template = ComposeTemplate {
+                name,
+                title: title.clone().unwrap_or_default(),
+                content: content.clone(),
+            };
+            store.upsert(template.clone());
+            save_templates(path, &store)?;
+            Ok(json!({ "saved": template }))
+        }
+        TemplatesCommand::Remove { name } => {
+            let removed = store.remove(name);
+            if removed {
+                save_templates(path, &store)?;
+            }
+            Ok(json!({ "name": name, "removed": removed }))
+        }
+    }
+}
+
+fn build_payload(
+    content: Option<&str>,
+    title: Option<&str>,
+    payload_json: Option<&str>,
+) -> Result<JsonValue, SdkError> {
+    if let Some(raw) = payload_json {
+        if content.is_some() || title.is_some() {
+            return Err(invalid_argument(
+                "payload_json cannot be combined with content/title flags",
+            ));
+        }
+        return serde_json::from_str(raw)
+            .map_err(|err| invalid_argument(format!("payload_json is not valid JSON: {err}")));
+    }
+
+    let content = content.unwrap_or("").trim().to_owned();
+    if content.is_empty() {
+        return Err(invalid_argument("content is required when payload_json is not provided"));
+    }
+
+    Ok(json!({
+        "content": content,
+        "title": title.unwrap_or_default(),
+    }))
+}
+
+fn profile_default_config(profile: ProfileArg) -> SdkConfig {
+    match profile {
+        ProfileArg::DesktopFull => SdkConfig::desktop_full_default(),
+        ProfileArg::DesktopLocalRuntime => SdkConfig::desktop_local_default(),
+        ProfileArg::EmbeddedAlloc => SdkConfig::embedded_alloc_default(),
+    }
+}
+
+fn build_start_request(cli: &Cli) -> Result<StartRequest, SdkError> {
+    let mut config = profile_default_config(cli.profile).with_rpc_listen_addr(cli.rpc.clone());
+    config.bind_mode = bind_mode_value(cli.bind_mode);
+    config.auth_mode = auth_mode_value(cli.auth_mode);
+    config.overflow_policy = overflow_policy_value(cli.overflow_policy);
+    config.block_timeout_ms = cli.block_timeout_ms;
+    config.event_stream.max_poll_events = cli.max_poll_events;
+    config.event_stream.max_event_bytes = cli.max_event_bytes;
+    config.event_stream.max_batch_bytes = cli.max_batch_bytes;
+    config.event_stream.max_extension_keys = cli.max_extension_keys;
+    config.idempotency_ttl_ms = cli.idempotency_ttl_ms;
+    if let Some(backend) = config.rpc_backend.as_mut() {
+        backend.listen_addr = cli.rpc.clone();
+        backend.read_timeout_ms = cli.read_timeout_ms;
+        backend.write_timeout_ms = cli.write_timeout_ms;
+        backend.max_header_bytes = cli.max_header_bytes;
+        backend.max_body_bytes = cli.max_body_bytes;
+    }
+
+    match config.auth_mode {
+        AuthMode::Token => {
+            let issuer = required_string(
+                cli.token_issuer.as_deref(),
+                "--token-issuer is required in token auth mode",
+            )?;
+            let audience = required_string(
+                cli.token_audience.as_deref(),
+                "--token-audience is required in token auth mode",
+            )?;
             let secret = required_string(
                 cli.token_shared_secret.as_deref(),
                 "--token-shared-secret is required in token auth mode",
@@ -445,6 +1812,13 @@ fn build_start_request(cli: &Cli) -> Result<StartRequest, SdkError> {
                 backend.max_body_bytes = cli.max_body_bytes;
             }
         }
+        AuthMode::ApiKey => {
+            let api_key = required_string(
+                cli.api_key.as_deref(),
+                "--api-key is required in api_key auth mode",
+            )?;
+            config = config.with_api_key_auth(api_key);
+        }
         AuthMode::LocalTrusted => {}
         _ => {
             return Err(invalid_argument("unsupported auth mode for this CLI build"));
@@ -462,6 +1836,87 @@ fn build_start_request(cli: &Cli) -> Result<StartRequest, SdkError> {
     Ok(request)
 }
 
+/// Config keys whose values are credentials and never printed.
+const SECRET_CONFIG_KEYS: &[&str] = &["shared_secret", "api_key"];
+
+/// Resolves the start request from the profile and flags, and tags every
+/// setting `default` when it matches the bare profile default, else
+/// `override`.
+fn dump_config(cli: &Cli) -> Result<JsonValue, SdkError> {
+    let to_json = |request: &StartRequest| {
+        serde_json::to_value(request).map_err(|err| {
+            SdkError::new(error_code::INTERNAL, ErrorCategory::Internal, err.to_string())
+        })
+    };
+    let mut resolved = to_json(&build_start_request(cli)?)?;
+    let defaults = to_json(&StartRequest::new(profile_default_config(cli.profile)))?;
+    let mut default_leaves = Vec::new();
+    flatten_config(&defaults, String::new(), &mut default_leaves);
+    let default_leaves = default_leaves.into_iter().collect::<BTreeMap<_, _>>();
+
+    let mut leaves = Vec::new();
+    flatten_config(&resolved, String::new(), &mut leaves);
+    let sources = leaves
+        .into_iter()
+        .map(|(path, value)| {
+            let source =
+                if default_leaves.get(&path) == Some(&value) { "default" } else { "override" };
+            (path, JsonValue::from(source))
+        })
+        .collect::<JsonMap<_, _>>();
+    redact_secrets(&mut resolved);
+    Ok(json!({ "start_request": resolved, "sources": sources }))
+}
+
+/// Collects the scalar, array and empty-object leaves of `value` under
+/// dotted paths.
+fn flatten_config(value: &JsonValue, path: String, out: &mut Vec<(String, JsonValue)>) {
+    match value {
+        JsonValue::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                let child_path =
+                    if path.is_empty() { key.clone() } else { format!("{path}.{key}") };
+                flatten_config(child, child_path, out);
+            }
+        }
+        _ => out.push((path, value.clone())),
+    }
+}
+
+fn redact_secrets(value: &mut JsonValue) {
+    match value {
+        JsonValue::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if SECRET_CONFIG_KEYS.contains(&key.as_str()) && child.is_string() {
+                    *child = JsonValue::from("[redacted]");
+                } else {
+                    redact_secrets(child);
+                }
+            }
+        }
+        JsonValue::Array(items) => items.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+fn dump_config_lines(dump: &JsonValue) -> Vec<String> {
+    let mut leaves = Vec::new();
+    if let Some(request) = dump.get("start_request") {
+        flatten_config(request, String::new(), &mut leaves);
+    }
+    leaves
+        .into_iter()
+        .map(|(path, value)| {
+            let source = dump
+                .get("sources")
+                .and_then(|sources| sources.get(&path))
+                .and_then(JsonValue::as_str)
+                .unwrap_or("-");
+            format!("{path} = {value} ({source})")
+        })
+        .collect()
+}
+
 fn required_string(value: Option<&str>, missing_msg: &str) -> Result<String, SdkError> {
     let value = value.map(str::trim).unwrap_or_default();
     if value.is_empty() {
@@ -487,6 +1942,7 @@ fn auth_mode_value(auth_mode: AuthModeArg) -> AuthMode {
         AuthModeArg::LocalTrusted => AuthMode::LocalTrusted,
         AuthModeArg::Token => AuthMode::Token,
         AuthModeArg::Mtls => AuthMode::Mtls,
+        AuthModeArg::ApiKey => AuthMode::ApiKey,
     }
 }
 
@@ -494,10 +1950,21 @@ fn overflow_policy_value(policy: OverflowPolicyArg) -> OverflowPolicy {
     match policy {
         OverflowPolicyArg::Reject => OverflowPolicy::Reject,
         OverflowPolicyArg::DropOldest => OverflowPolicy::DropOldest,
+        OverflowPolicyArg::DropNewest => OverflowPolicy::DropNewest,
         OverflowPolicyArg::Block => OverflowPolicy::Block,
     }
 }
 
+fn log_level_value(level: LogLevelArg) -> LogLevel {
+    match level {
+        LogLevelArg::Trace => LogLevel::Trace,
+        LogLevelArg::Debug => LogLevel::Debug,
+        LogLevelArg::Info => LogLevel::Info,
+        LogLevelArg::Warn => LogLevel::Warn,
+        LogLevelArg::Error => LogLevel::Error,
+    }
+}
+
 fn output_mode(cli: &Cli) -> OutputModeArg {
     if cli.json {
         OutputModeArg::JsonPretty
@@ -506,6 +1973,107 @@ fn output_mode(cli: &Cli) -> OutputModeArg {
     }
 }
 
+/// Resolves `--layout auto` against the terminal height. Compact layout folds
+/// multi-line summaries into one line so small terminals keep room for content.
+fn select_layout(
+    layout: LayoutArg,
+    terminal_rows: Option<u16>,
+    compact_below_rows: u16,
+) -> LayoutArg {
+    match layout {
+        LayoutArg::Auto => match terminal_rows {
+            Some(rows) if rows < compact_below_rows => LayoutArg::Compact,
+            _ => LayoutArg::Full,
+        },
+        explicit => explicit,
+    }
+}
+
+/// Resolves `--color` against whether stdout is a terminal.
+fn colors_enabled(color: ColorArg, stdout_is_terminal: bool) -> bool {
+    match color {
+        ColorArg::Auto => stdout_is_terminal,
+        ColorArg::Always => true,
+        ColorArg::Never => false,
+    }
+}
+
+/// Height of the terminal stdout writes to, asked of the terminal itself;
+/// `LINES` only answers when the terminal cannot be queried.
+fn terminal_rows() -> Option<u16> {
+    let stdout = std::io::stdout();
+    if !stdout.is_terminal() {
+        return None;
+    }
+    terminal_size::terminal_size_of(&stdout)
+        .map(|(_, terminal_size::Height(rows))| rows)
+        .filter(|rows| *rows > 0)
+        .or_else(|| std::env::var("LINES").ok()?.trim().parse().ok())
+}
+
+fn start_summary_lines(value: &JsonValue, layout: LayoutArg) -> Vec<String> {
+    let runtime = value.get("runtime").and_then(JsonValue::as_object);
+    let runtime_id =
+        runtime.and_then(|runtime| runtime.get("runtime_id")).and_then(JsonValue::as_str);
+    let contract = runtime
+        .and_then(|runtime| runtime.get("active_contract_version"))
+        .and_then(JsonValue::as_u64);
+    if layout == LayoutArg::Compact {
+        let mut line = "runtime started".to_string();
+        if let Some(runtime_id) = runtime_id {
+            line.push_str(&format!(" runtime_id={runtime_id}"));
+        }
+        if let Some(contract) = contract {
+            line.push_str(&format!(" contract_version={contract}"));
+        }
+        return vec![line];
+    }
+    let mut lines = vec!["runtime started".to_string()];
+    if let Some(runtime_id) = runtime_id {
+        lines.push(format!("runtime_id: {runtime_id}"));
+    }
+    if let Some(contract) = contract {
+        lines.push(format!("contract_version: {contract}"));
+    }
+    lines
+}
+
+fn poll_summary_lines(value: &JsonValue, layout: LayoutArg) -> Vec<String> {
+    let count = value.get("events").and_then(JsonValue::as_array).map(Vec::len).unwrap_or(0);
+    let cursor = value.get("next_cursor").and_then(JsonValue::as_str);
+    let dropped = value.get("dropped_count").and_then(JsonValue::as_u64);
+    if layout == LayoutArg::Compact {
+        let mut line = format!("events={count}");
+        if let Some(cursor) = cursor {
+            line.push_str(&format!(" next_cursor={cursor}"));
+        }
+        if let Some(dropped) = dropped.filter(|dropped| *dropped > 0) {
+            line.push_str(&format!(" dropped={dropped}"));
+        }
+        return vec![line];
+    }
+    let mut lines = vec![format!("events: {count}")];
+    if let Some(cursor) = cursor {
+        lines.push(format!("next_cursor: {cursor}"));
+    }
+    if let Some(dropped) = dropped {
+        lines.push(format!("dropped_count: {dropped}"));
+    }
+    lines
+}
+
+/// Summarises backpressure from a runtime snapshot: outbound queue depth and
+/// how full the event buffer is. `None` when the snapshot lacks the gauges.
+fn snapshot_pressure_line(runtime: &JsonValue) -> Option<String> {
+    let queue_depth = runtime.get("outbound_queue_depth").and_then(JsonValue::as_u64)?;
+    let used = runtime.get("event_buffer_used").and_then(JsonValue::as_u64)?;
+    let capacity = runtime.get("event_buffer_capacity").and_then(JsonValue::as_u64)?;
+    let fill_percent = used.saturating_mul(100).checked_div(capacity).unwrap_or(0);
+    Some(format!(
+        "queue depth: {queue_depth}, event buffer: {used}/{capacity} ({fill_percent}% full)"
+    ))
+}
+
 fn completion_shell_name(shell: CompletionShellArg) -> &'static str {
     match shell {
         CompletionShellArg::Bash => "bash",
@@ -552,25 +2120,50 @@ fn emit_json_envelope(value: JsonValue, pretty: bool) {
 }
 
 fn emit_human_output(cli: &Cli, value: &JsonValue) {
+    let layout = select_layout(cli.layout, terminal_rows(), cli.compact_below_rows);
     match &cli.command {
         Command::Start => {
-            println!("runtime started");
-            if let Some(runtime) = value.get("runtime").and_then(JsonValue::as_object) {
-                if let Some(runtime_id) = runtime.get("runtime_id").and_then(JsonValue::as_str) {
-                    println!("runtime_id: {runtime_id}");
-                }
-                if let Some(contract) =
-                    runtime.get("active_contract_version").and_then(JsonValue::as_u64)
-                {
-                    println!("contract_version: {contract}");
-                }
+            for line in start_summary_lines(value, layout) {
+                println!("{line}");
             }
         }
-        Command::Send { .. } => {
-            if let Some(message_id) = value.get("message_id").and_then(JsonValue::as_str) {
-                println!("message queued: {message_id}");
-            } else {
+        Command::Send { dry_run: true, .. } => {
+            let params = value.get("params").unwrap_or(value);
+            println!(
+                "{}",
+                serde_json::to_string_pretty(params).unwrap_or_else(|_| params.to_string())
+            );
+        }
+        Command::Send { id_only, wait, .. } => {
+            let Some(message_id) = value.get("message_id").and_then(JsonValue::as_str) else {
                 println!("{value}");
+                return;
+            };
+            let deduplicated =
+                value.get("deduplicated").and_then(JsonValue::as_bool).unwrap_or(false);
+            if *id_only {
+                println!("{message_id}");
+                return;
+            }
+            if deduplicated {
+                println!("message deduplicated (existing id): {message_id}");
+            }
+            if !*wait {
+                if !deduplicated {
+                    println!("message queued: {message_id}");
+                }
+            } else if value.get("timed_out").and_then(JsonValue::as_bool).unwrap_or(true) {
+                println!("message {message_id}: timed out waiting for delivery");
+            } else {
+                let state = value.pointer("/status/state").and_then(JsonValue::as_str);
+                println!("message {message_id}: {}", state.unwrap_or("unknown"));
+            }
+        }
+        Command::SendCommand { id_only, .. } => {
+            match value.get("message_id").and_then(JsonValue::as_str) {
+                Some(message_id) if *id_only => println!("{message_id}"),
+                Some(message_id) => println!("command message queued: {message_id}"),
+                None => println!("{value}"),
             }
         }
         Command::Cancel { .. } => {
@@ -580,6 +2173,7 @@ fn emit_human_output(cli: &Cli, value: &JsonValue) {
                 println!("{value}");
             }
         }
+        Command::Status { watch: true, .. } => {}
         Command::Status { .. } => {
             if let Some(message) = value.get("message") {
                 println!("message status: {message}");
@@ -588,22 +2182,16 @@ fn emit_human_output(cli: &Cli, value: &JsonValue) {
             }
         }
         Command::Poll { .. } => {
-            let count = value
-                .get("events")
-                .and_then(JsonValue::as_array)
-                .map(|events| events.len())
-                .unwrap_or(0);
-            println!("events: {count}");
-            if let Some(cursor) = value.get("next_cursor").and_then(JsonValue::as_str) {
-                println!("next_cursor: {cursor}");
-            }
-            if let Some(dropped) = value.get("dropped_count").and_then(JsonValue::as_u64) {
-                println!("dropped_count: {dropped}");
+            for line in poll_summary_lines(value, layout) {
+                println!("{line}");
             }
         }
-        Command::Snapshot => {
+        Command::Snapshot { .. } => {
             if let Some(runtime) = value.get("runtime") {
                 println!("runtime snapshot: {runtime}");
+                if let Some(line) = snapshot_pressure_line(runtime) {
+                    println!("{line}");
+                }
             } else {
                 println!("{value}");
             }
@@ -629,6 +2217,150 @@ fn emit_human_output(cli: &Cli, value: &JsonValue) {
                 println!("{value}");
             }
         }
+        Command::Peers { no_color, .. } => {
+            let colorize = !*no_color && colors_enabled(cli.color, std::io::stdout().is_terminal());
+            let peers = value.get("peers").and_then(JsonValue::as_array);
+            let indent = if layout == LayoutArg::Compact {
+                ""
+            } else {
+                println!("peers: {}", peers.map(Vec::len).unwrap_or(0));
+                "  "
+            };
+            for peer in peers.into_iter().flatten() {
+                let freshness = peer
+                    .get("freshness")
+                    .and_then(JsonValue::as_str)
+                    .and_then(|label| PeerFreshness::from_str(label, true).ok());
+                let label = freshness.map(PeerFreshness::as_str).unwrap_or("unknown");
+                let label = match freshness {
+                    Some(freshness) if colorize => {
+                        format!("\x1b[{}m{label:<6}\x1b[0m", freshness.ansi_color())
+                    }
+                    _ => format!("{label:<6}"),
+                };
+                let peer_id = peer.get("peer_id").and_then(JsonValue::as_str).unwrap_or("-");
+                let name = peer
+                    .get("display_name")
+                    .or_else(|| peer.get("name"))
+                    .and_then(JsonValue::as_str)
+                    .unwrap_or("-");
+                let age = peer.get("age_secs").and_then(JsonValue::as_i64).unwrap_or(0);
+                println!("{indent}{label} {peer_id} {name} ({age}s ago)");
+            }
+        }
+        Command::Contacts { .. } => {
+            let field = if value.get("kind").and_then(JsonValue::as_str) == Some("hash") {
+                "aliases"
+            } else {
+                "hashes"
+            };
+            for entry in value.get(field).and_then(JsonValue::as_array).into_iter().flatten() {
+                if let Some(entry) = entry.as_str() {
+                    println!("{entry}");
+                }
+            }
+        }
+        Command::Propagation { .. } => {
+            if let Some(stats) = value.get("stats") {
+                for line in propagation_stats_lines(stats) {
+                    println!("{line}");
+                }
+            }
+            if let Some(sync) = value.get("sync") {
+                for line in propagation_sync_lines(sync) {
+                    println!("{line}");
+                }
+            }
+        }
+        Command::StampStatus => {
+            if let Some(status) = value.get("stamp_status") {
+                for line in stamp_status_lines(status) {
+                    println!("{line}");
+                }
+            }
+        }
+        Command::DumpConfig => {
+            for line in dump_config_lines(value) {
+                println!("{line}");
+            }
+        }
+        Command::Version => {
+            if let Some(version) = value.get("version") {
+                for line in version_lines(version) {
+                    println!("{line}");
+                }
+            }
+        }
+        Command::Whoami => {
+            if let Some(whoami) = value.get("whoami") {
+                for line in whoami_lines(whoami) {
+                    println!("{line}");
+                }
+            }
+        }
+        Command::SetDisplayName { .. } => {
+            if let Some(update) = value.get("display_name") {
+                println!("{}", display_name_line(update));
+            }
+        }
+        Command::Health => {
+            if let Some(Ok(health)) =
+                value.get("health").map(|health| serde_json::from_value(health.clone()))
+            {
+                println!("{}", health_line(&health));
+            }
+        }
+        Command::Logs { .. } => {
+            if let Some(logs) = value.get("logs") {
+                for line in logs_tail_lines(logs) {
+                    println!("{line}");
+                }
+            }
+        }
+        Command::Trace { .. } => {
+            if let Some(trace) = value.get("trace") {
+                for line in trace_lines(trace) {
+                    println!("{line}");
+                }
+            }
+        }
+        Command::Prune { .. } => {
+            let pruned = value.get("pruned").and_then(JsonValue::as_u64).unwrap_or(0);
+            println!("pruned messages: {pruned}");
+        }
+        Command::Search { .. } => {
+            for line in search_result_lines(value) {
+                println!("{line}");
+            }
+        }
+        Command::Export { .. } => {
+            let count = value.get("messages").and_then(JsonValue::as_u64).unwrap_or(0);
+            let out_path = value.get("out_path").and_then(JsonValue::as_str).unwrap_or("-");
+            println!("exported {count} messages to {out_path}");
+        }
+        Command::Attachment { .. } => {
+            let field = |key: &str| value.get(key).and_then(JsonValue::as_str).unwrap_or("-");
+            let size = value.get("size_bytes").and_then(JsonValue::as_u64).unwrap_or(0);
+            println!("wrote {} ({size} bytes) to {}", field("name"), field("out_path"));
+            println!("{}", attachment_type_line(value));
+            println!("sha256: {}", field("checksum_sha256"));
+        }
+        Command::Templates { .. } => {
+            if let Some(templates) = value.get("templates").and_then(JsonValue::as_array) {
+                for template in templates {
+                    let field =
+                        |key: &str| template.get(key).and_then(JsonValue::as_str).unwrap_or("");
+                    println!("{}: [{}] {}", field("name"), field("title"), field("content"));
+                }
+            } else if let Some(saved) = value.get("saved") {
+                let name = saved.get("name").and_then(JsonValue::as_str).unwrap_or("-");
+                println!("template saved: {name}");
+            } else {
+                let name = value.get("name").and_then(JsonValue::as_str).unwrap_or("-");
+                let removed = value.get("removed").and_then(JsonValue::as_bool).unwrap_or(false);
+                println!("template {name}: {}", if removed { "removed" } else { "not found" });
+            }
+        }
         Command::Completions { .. } => {
             if let Some(script) = value.get("script").and_then(JsonValue::as_str) {
                 print!("{script}");
@@ -646,13 +2378,16 @@ fn emit_output(cli: &Cli, value: JsonValue) {
         OutputModeArg::Json => emit_json_envelope(value, false),
         OutputModeArg::JsonPretty => emit_json_envelope(value, true),
         OutputModeArg::Human => emit_human_output(cli, &value),
+        // Poll events were already written one per line while streaming.
+        OutputModeArg::Ndjson if matches!(cli.command, Command::Poll { .. }) => {}
+        OutputModeArg::Ndjson => println!("{value}"),
     }
 }
 
 fn emit_error(cli: &Cli, err: SdkError) {
     match output_mode(cli) {
         OutputModeArg::Human => {}
-        OutputModeArg::Json | OutputModeArg::JsonPretty => {
+        OutputModeArg::Json | OutputModeArg::JsonPretty | OutputModeArg::Ndjson => {
             let machine_code = err.machine_code.clone();
             let message = err.message.clone();
             let envelope = json!({
@@ -660,7 +2395,7 @@ fn emit_error(cli: &Cli, err: SdkError) {
                 "error": err,
             });
             let serialized = match output_mode(cli) {
-                OutputModeArg::Json => serde_json::to_string(&envelope),
+                OutputModeArg::Json | OutputModeArg::Ndjson => serde_json::to_string(&envelope),
                 OutputModeArg::JsonPretty | OutputModeArg::Human => {
                     serde_json::to_string_pretty(&envelope)
                 }
@@ -692,6 +2427,85 @@ mod tests {
         Cli::try_parse_from(args).expect("cli args should parse")
     }
 
+    #[test]
+    fn send_template_fills_placeholders_and_yields_to_explicit_flags() {
+        let path = std::env::temp_dir()
+            .join(format!("lxmf-cli-templates-{}", std::process::id()))
+            .join(TEMPLATES_FILE_NAME);
+        let path_arg = path.to_string_lossy().into_owned();
+        let saved = run_templates_command(
+            &path,
+            &TemplatesCommand::Save {
+                name: "checkin".into(),
+                title: Some("check-in {source}".into()),
+                content: "{source} -> {destination}: all good".into(),
+            },
+        )
+        .expect("save template");
+        assert_eq!(saved["saved"]["name"], "checkin");
+
+        let send = |extra: &[&str]| {
+            let mut args = vec![
+                "lxmf-cli",
+                "--templates-path",
+                path_arg.as_str(),
+                "send",
+                "--source",
+                "aa",
+                "--destination",
+                "bb",
+                "--template",
+                "checkin",
+            ];
+            args.extend_from_slice(extra);
+            build_send_request(&parse_cli(&args))
+                .map(|req| RpcBackendClient::new("127.0.0.1:1").prepare_send_params(req))
+        };
+        let params = send(&[]).expect("templated send");
+        assert_eq!(params["title"], "check-in aa");
+        assert_eq!(params["content"], "aa -> bb: all good");
+
+        let params = send(&["--content", "edited"]).expect("edited send");
+        assert_eq!(params["title"], "check-in aa");
+        assert_eq!(params["content"], "edited");
+
+        let removed =
+            run_templates_command(&path, &TemplatesCommand::Remove { name: "checkin".into() })
+                .expect("remove template");
+        assert_eq!(removed["removed"], true);
+        let err = send(&[]).expect_err("missing template");
+        assert_eq!(err.machine_code, error_code::VALIDATION_INVALID_ARGUMENT);
+        let _ = std::fs::remove_dir_all(path.parent().expect("parent"));
+    }
+
+    #[test]
+    fn profile_path_must_exist_until_start_creates_it() {
+        let profile = std::env::temp_dir()
+            .join(format!("lxmf-cli-profile-{}", std::process::id()))
+            .join("instance-a");
+        let profile_arg = profile.to_string_lossy().into_owned();
+        let _ = std::fs::remove_dir_all(profile.parent().expect("parent"));
+
+        let list = parse_cli(&["lxmf-cli", "--profile-path", &profile_arg, "templates", "list"]);
+        let err = run(&list).expect_err("missing profile path");
+        assert_eq!(err.machine_code, error_code::VALIDATION_INVALID_ARGUMENT);
+        assert!(err.message.contains(&profile_arg));
+
+        let start = parse_cli(&[
+            "lxmf-cli",
+            "--rpc",
+            "127.0.0.1:1",
+            "--profile-path",
+            &profile_arg,
+            "start",
+        ]);
+        let _ = run(&start);
+        assert!(profile.is_dir());
+        assert_eq!(templates_path(&list).expect("path"), profile.join(TEMPLATES_FILE_NAME));
+        assert_eq!(run(&list).expect("list")["templates"], json!([]));
+        let _ = std::fs::remove_dir_all(profile.parent().expect("parent"));
+    }
+
     #[test]
     fn payload_requires_content_when_payload_json_missing() {
         let err = build_payload(None, None, None).expect_err("missing content should fail");
@@ -705,6 +2519,111 @@ mod tests {
         assert_eq!(err.machine_code, error_code::VALIDATION_INVALID_ARGUMENT);
     }
 
+    #[test]
+    fn send_command_collects_command_entries_into_the_commands_field() {
+        let send_command = |extra: &[&str]| {
+            let mut args =
+                vec!["lxmf-cli", "send-command", "--source", "aa", "--destination", "bb"];
+            args.extend_from_slice(extra);
+            build_send_command_request(&parse_cli(&args))
+        };
+
+        let req = send_command(&["--command", "1:ping", "--command", "2:echo a:b"]).expect("req");
+        let params = RpcBackendClient::new("127.0.0.1:1").prepare_send_params(req);
+        let wire = lxmf_core::wire_fields::json_to_rmpv(&params["fields"]).expect("wire fields");
+        let commands = wire
+            .as_map()
+            .and_then(|entries| entries.iter().find(|(key, _)| key.as_i64() == Some(9)))
+            .map(|(_, value)| value.clone())
+            .expect("commands field");
+        let entry = |id: i64, text: &str| {
+            rmpv::Value::Map(vec![(id.into(), rmpv::Value::Binary(text.as_bytes().to_vec()))])
+        };
+        assert_eq!(commands, rmpv::Value::Array(vec![entry(1, "ping"), entry(2, "echo a:b")]));
+        assert_eq!(params["content"], "");
+
+        let err = send_command(&["--command", "256:x"]).expect_err("id out of range");
+        assert_eq!(err.machine_code, error_code::VALIDATION_INVALID_ARGUMENT);
+        let err = send_command(&["--command", "ping"]).expect_err("missing id");
+        assert_eq!(err.machine_code, error_code::VALIDATION_INVALID_ARGUMENT);
+        let err = send_command(&["--command", "1:ping", "--payload-json", r#"{"9":[]}"#])
+            .expect_err("pre-populated commands");
+        assert_eq!(err.machine_code, error_code::VALIDATION_INVALID_ARGUMENT);
+        let req = send_command(&["--command", "1:ping", "--payload-json", r#"{"content":"hi"}"#])
+            .expect("payload without fields");
+        let params = RpcBackendClient::new("127.0.0.1:1").prepare_send_params(req);
+        assert_eq!(params["content"], "hi");
+        assert!(Cli::try_parse_from([
+            "lxmf-cli",
+            "send-command",
+            "--source",
+            "a",
+            "--destination",
+            "b"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn send_dry_run_prepares_params_and_keeps_send_validation() {
+        let cli = parse_cli(&[
+            "lxmf-cli",
+            "send",
+            "--source",
+            "aa",
+            "--destination",
+            " bb ",
+            "--content",
+            "hello",
+            "--dry-run",
+        ]);
+        let req = build_send_request(&cli).expect("valid dry run");
+        let params = RpcBackendClient::new("127.0.0.1:1").prepare_send_params(req);
+        assert_eq!(params["destination"], "bb");
+        assert_eq!(params["content"], "hello");
+
+        let conflicting = parse_cli(&[
+            "lxmf-cli",
+            "send",
+            "--source",
+            "aa",
+            "--destination",
+            "bb",
+            "--content",
+            "hello",
+            "--payload-json",
+            "{}",
+            "--dry-run",
+        ]);
+        let err = build_send_request(&conflicting).expect_err("payload_json + content");
+        assert_eq!(err.machine_code, error_code::VALIDATION_INVALID_ARGUMENT);
+
+        let blank = parse_cli(&[
+            "lxmf-cli",
+            "send",
+            "--source",
+            "aa",
+            "--destination",
+            "  ",
+            "--content",
+            "hello",
+            "--dry-run",
+        ]);
+        let err = build_send_request(&blank).expect_err("blank destination");
+        assert_eq!(err.machine_code, error_code::VALIDATION_INVALID_ARGUMENT);
+        assert!(Cli::try_parse_from([
+            "lxmf-cli",
+            "send",
+            "--source",
+            "a",
+            "--destination",
+            "b",
+            "--dry-run",
+            "--wait"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn start_request_defaults_are_valid() {
         let cli = parse_cli(&["lxmf-cli", "start"]);
@@ -730,18 +2649,734 @@ mod tests {
         assert_eq!(err.machine_code, error_code::VALIDATION_INVALID_ARGUMENT);
     }
 
+    #[test]
+    fn api_key_auth_mode_requires_api_key() {
+        let cli =
+            parse_cli(&["lxmf-cli", "--bind-mode", "remote", "--auth-mode", "api-key", "start"]);
+        let err = build_start_request(&cli).expect_err("missing api key should fail");
+        assert_eq!(err.machine_code, error_code::VALIDATION_INVALID_ARGUMENT);
+
+        let cli = parse_cli(&[
+            "lxmf-cli",
+            "--bind-mode",
+            "remote",
+            "--auth-mode",
+            "api_key",
+            "--api-key",
+            "key-a",
+            "start",
+        ]);
+        let request = build_start_request(&cli).expect("api key start request");
+        assert_eq!(request.config.auth_mode, AuthMode::ApiKey);
+        let backend = request.config.rpc_backend.as_ref().expect("rpc backend");
+        assert_eq!(backend.api_key_auth.as_ref().map(|auth| auth.api_key.as_str()), Some("key-a"));
+    }
+
     #[test]
     fn output_mode_defaults_to_human() {
         let cli = parse_cli(&["lxmf-cli", "start"]);
         assert_eq!(output_mode(&cli), OutputModeArg::Human);
     }
 
+    #[test]
+    fn auto_layout_turns_compact_below_row_threshold() {
+        assert_eq!(select_layout(LayoutArg::Auto, Some(12), 24), LayoutArg::Compact);
+        assert_eq!(select_layout(LayoutArg::Auto, Some(24), 24), LayoutArg::Full);
+        assert_eq!(select_layout(LayoutArg::Auto, Some(60), 24), LayoutArg::Full);
+        assert_eq!(select_layout(LayoutArg::Auto, None, 24), LayoutArg::Full);
+        assert_eq!(select_layout(LayoutArg::Full, Some(12), 24), LayoutArg::Full);
+        assert_eq!(select_layout(LayoutArg::Compact, Some(60), 24), LayoutArg::Compact);
+    }
+
+    #[test]
+    fn color_flag_resolves_against_terminal_detection() {
+        let cli = parse_cli(&["lxmf-cli", "--color", "never", "peers"]);
+        assert_eq!(cli.color, ColorArg::Never);
+        assert_eq!(parse_cli(&["lxmf-cli", "peers"]).color, ColorArg::Auto);
+
+        assert!(colors_enabled(ColorArg::Auto, true));
+        assert!(!colors_enabled(ColorArg::Auto, false));
+        assert!(colors_enabled(ColorArg::Always, false));
+        assert!(!colors_enabled(ColorArg::Never, true));
+    }
+
+    #[test]
+    fn compact_layout_folds_summaries_into_one_line() {
+        let start = json!({ "runtime": { "runtime_id": "rt-1", "active_contract_version": 2 } });
+        assert_eq!(start_summary_lines(&start, LayoutArg::Full).len(), 3);
+        assert_eq!(
+            start_summary_lines(&start, LayoutArg::Compact),
+            vec!["runtime started runtime_id=rt-1 contract_version=2".to_string()]
+        );
+
+        let poll = json!({ "events": [{}, {}], "next_cursor": "c-2", "dropped_count": 0 });
+        assert_eq!(poll_summary_lines(&poll, LayoutArg::Full).len(), 3);
+        assert_eq!(
+            poll_summary_lines(&poll, LayoutArg::Compact),
+            vec!["events=2 next_cursor=c-2".to_string()]
+        );
+    }
+
+    #[test]
+    fn snapshot_pressure_line_reports_queue_depth_and_buffer_fill() {
+        let runtime = json!({
+            "outbound_queue_depth": 4,
+            "event_buffer_used": 256,
+            "event_buffer_capacity": 1024
+        });
+        assert_eq!(
+            snapshot_pressure_line(&runtime).as_deref(),
+            Some("queue depth: 4, event buffer: 256/1024 (25% full)")
+        );
+        assert_eq!(snapshot_pressure_line(&json!({ "config_revision": 2 })), None);
+    }
+
     #[test]
     fn legacy_json_flag_maps_to_json_pretty_output() {
         let cli = parse_cli(&["lxmf-cli", "--json", "start"]);
         assert_eq!(output_mode(&cli), OutputModeArg::JsonPretty);
     }
 
+    fn presence(peer_id: &str, name: Option<&str>, last_seen: i64) -> PresenceRecord {
+        PresenceRecord {
+            peer_id: peer_id.to_owned(),
+            last_seen_ts_ms: last_seen,
+            first_seen_ts_ms: last_seen,
+            seen_count: 1,
+            name: name.map(str::to_owned),
+            name_source: None,
+            trust_level: None,
+            bootstrap: None,
+            extensions: BTreeMap::new(),
+        }
+    }
+
+    const THRESHOLDS: FreshnessThresholds =
+        FreshnessThresholds { fresh_secs: 300, recent_secs: 3_600, stale_secs: 86_400 };
+
+    #[test]
+    fn freshness_buckets_follow_last_seen_age() {
+        let now = 1_700_000_000;
+        for (age, expected) in [
+            (0, PeerFreshness::Fresh),
+            (300, PeerFreshness::Fresh),
+            (301, PeerFreshness::Recent),
+            (3_600, PeerFreshness::Recent),
+            (3_601, PeerFreshness::Stale),
+            (86_400, PeerFreshness::Stale),
+            (86_401, PeerFreshness::Dead),
+            (now, PeerFreshness::Dead),
+        ] {
+            assert_eq!(THRESHOLDS.classify(now - age, now), expected, "age {age}s");
+        }
+    }
+
+    #[test]
+    fn freshness_treats_future_last_seen_as_fresh() {
+        assert_eq!(THRESHOLDS.classify(1_000 + 60, 1_000), PeerFreshness::Fresh);
+    }
+
+    #[test]
+    fn freshness_thresholds_must_be_ordered() {
+        let err = FreshnessThresholds { fresh_secs: 600, recent_secs: 300, stale_secs: 900 }
+            .validate()
+            .expect_err("unordered thresholds should fail");
+        assert_eq!(err.machine_code, error_code::VALIDATION_INVALID_ARGUMENT);
+        THRESHOLDS.validate().expect("default thresholds should be valid");
+    }
+
+    #[test]
+    fn peer_view_filters_and_sorts_by_freshness() {
+        let now = 10_000;
+        let peers = vec![
+            presence("dead-peer", None, now - 90_000),
+            presence("fresh-peer", Some("alpha"), now - 10),
+            presence("stale-peer", None, now - 7_200),
+            presence("recent-peer", None, now - 600),
+        ];
+
+        let view = build_peer_view(peers.clone(), THRESHOLDS, &[], PeerSortArg::Freshness, now);
+        let order = view["peers"]
+            .as_array()
+            .expect("peers array")
+            .iter()
+            .map(|peer| peer["freshness"].as_str().expect("freshness label"))
+            .collect::<Vec<_>>();
+        assert_eq!(order, vec!["fresh", "recent", "stale", "dead"]);
+        assert_eq!(view["counts"]["dead"], 1);
+
+        let only_fresh =
+            build_peer_view(peers, THRESHOLDS, &[PeerFreshness::Fresh], PeerSortArg::LastSeen, now);
+        let filtered = only_fresh["peers"].as_array().expect("peers array");
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0]["peer_id"], "fresh-peer");
+        assert_eq!(filtered[0]["age_secs"], 10);
+        assert_eq!(only_fresh["counts"]["stale"], 1);
+    }
+
+    #[test]
+    fn peers_command_parses_only_filter() {
+        let cli = parse_cli(&["lxmf-cli", "peers", "--only", "fresh", "--sort", "freshness"]);
+        match cli.command {
+            Command::Peers { only, sort, fresh_secs, .. } => {
+                assert_eq!(only, vec![PeerFreshness::Fresh]);
+                assert_eq!(sort, PeerSortArg::Freshness);
+                assert_eq!(fresh_secs, 300);
+            }
+            other => panic!("unexpected command: {other:?}"),
+        }
+    }
+
+    fn contact(hash: &str, alias: Option<&str>) -> ContactRecord {
+        serde_json::from_value(json!({
+            "identity": hash,
+            "display_name": alias,
+            "trust_level": "unknown",
+            "bootstrap": false,
+            "updated_ts_ms": 0,
+        }))
+        .expect("contact record")
+    }
+
+    fn sample_contacts() -> Vec<ContactRecord> {
+        vec![
+            contact("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa", Some("Alice")),
+            contact("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb", Some("Bob")),
+        ]
+    }
+
+    #[test]
+    fn name_preference_orders_contact_alias_and_announced_name() {
+        let aliases = BTreeMap::from([("peer-a".to_string(), "Ops Desk".to_string())]);
+        let peers =
+            vec![presence("PEER-A", Some("announced-a"), 0), presence("peer-b", Some("b"), 0)];
+
+        let mut alias_first = peers.clone();
+        apply_name_preference(
+            &mut alias_first,
+            &aliases,
+            &[NameSourceArg::ContactAlias, NameSourceArg::Announced],
+        );
+        assert_eq!(alias_first[0].name.as_deref(), Some("Ops Desk"));
+        assert_eq!(alias_first[0].name_source.as_deref(), Some("contact_alias"));
+        assert_eq!(alias_first[1].name.as_deref(), Some("b"));
+
+        let mut announced_first = peers.clone();
+        apply_name_preference(
+            &mut announced_first,
+            &aliases,
+            &[NameSourceArg::Announced, NameSourceArg::ContactAlias],
+        );
+        assert_eq!(announced_first[0].name.as_deref(), Some("announced-a"));
+        assert_eq!(announced_first[0].name_source.as_deref(), Some("announced"));
+
+        let mut alias_only = peers;
+        apply_name_preference(&mut alias_only, &aliases, &[NameSourceArg::ContactAlias]);
+        assert_eq!(alias_only[0].name.as_deref(), Some("Ops Desk"));
+        assert_eq!(alias_only[1].name, None);
+
+        let cli = parse_cli(&["lxmf-cli", "peers", "--name-preference", "announced,contact-alias"]);
+        assert!(matches!(
+            cli.command,
+            Command::Peers { ref name_preference, .. }
+                if name_preference == &[NameSourceArg::Announced, NameSourceArg::ContactAlias]
+        ));
+    }
+
+    #[test]
+    fn peer_view_shows_disambiguated_name_unless_alias_wins() {
+        let aliases = BTreeMap::from([("peer-b".to_string(), "Ops Desk".to_string())]);
+        let mut peers =
+            vec![presence("peer-a", Some("Relay"), 0), presence("peer-b", Some("relay"), 0)];
+        for peer in &mut peers {
+            let suffixed = format!("{} ({})", peer.name.as_deref().unwrap(), peer.peer_id);
+            peer.extensions.insert("display_name".to_string(), json!(suffixed));
+        }
+        apply_name_preference(
+            &mut peers,
+            &aliases,
+            &[NameSourceArg::ContactAlias, NameSourceArg::Announced],
+        );
+        let view = build_peer_view(peers, THRESHOLDS, &[], PeerSortArg::Name, 0);
+        let names = view["peers"]
+            .as_array()
+            .expect("peers")
+            .iter()
+            .map(|peer| (peer["name"].clone(), peer["display_name"].clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![(json!("Ops Desk"), json!("Ops Desk")), (json!("Relay"), json!("Relay (peer-a)")),]
+        );
+    }
+
+    #[test]
+    fn contacts_resolve_maps_alias_to_hash() {
+        let resolved = resolve_contact(&sample_contacts(), " @alice ").expect("alias resolves");
+        assert_eq!(resolved["kind"], "alias");
+        assert_eq!(resolved["hashes"], json!(["aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"]));
+    }
+
+    #[test]
+    fn contacts_resolve_maps_hash_to_alias() {
+        let resolved = resolve_contact(&sample_contacts(), "BBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB")
+            .expect("hash resolves");
+        assert_eq!(resolved["kind"], "hash");
+        assert_eq!(resolved["hash"], "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb");
+        assert_eq!(resolved["aliases"], json!(["Bob"]));
+    }
+
+    #[test]
+    fn contacts_resolve_reports_not_found() {
+        let err = resolve_contact(&sample_contacts(), "carol").expect_err("unknown selector");
+        assert_eq!(err.machine_code, error_code::VALIDATION_INVALID_ARGUMENT);
+        assert_eq!(err.details.get("reason"), Some(&json!("not_found")));
+
+        let cli = parse_cli(&["lxmf-cli", "contacts", "resolve", "@bob"]);
+        assert!(matches!(
+            cli.command,
+            Command::Contacts { command: ContactsCommand::Resolve { ref selector } } if selector == "@bob"
+        ));
+    }
+
+    #[test]
+    fn whoami_lines_render_identity_and_missing_display_name() {
+        let cli = parse_cli(&["lxmf-cli", "whoami"]);
+        assert!(matches!(cli.command, Command::Whoami));
+
+        let whoami = json!({
+            "identity_hash": "aa11",
+            "delivery_destination_hash": "bb22",
+            "display_name": null,
+            "profile": "desktop-full",
+        });
+        assert_eq!(
+            whoami_lines(&whoami),
+            vec![
+                "identity: aa11",
+                "delivery destination: bb22",
+                "display name: -",
+                "profile: desktop-full",
+            ]
+        );
+    }
+
+    #[test]
+    fn version_lines_render_contract_and_build() {
+        let cli = parse_cli(&["lxmf-cli", "version"]);
+        assert!(matches!(cli.command, Command::Version));
+
+        let version = json!({
+            "contract_release": "v2.5",
+            "schema_namespace": "v2",
+            "supported_contract_versions": [2],
+            "build_profile": "release",
+            "crate_version": "0.1.0",
+            "git_hash": null,
+            "features": ["debug_assertions"],
+        });
+        assert_eq!(
+            version_lines(&version),
+            vec![
+                "runtime version: 0.1.0 (-)",
+                "contract release: v2.5",
+                "schema namespace: v2",
+                "supported contract versions: 2",
+                "build: release",
+                "features: debug_assertions",
+            ]
+        );
+    }
+
+    #[test]
+    fn set_display_name_reports_published_name() {
+        let cli = parse_cli(&["lxmf-cli", "set-display-name", "Base Camp"]);
+        assert!(matches!(cli.command, Command::SetDisplayName { ref name } if name == "Base Camp"));
+
+        assert_eq!(
+            display_name_line(&json!({ "display_name": "Base Camp", "announced": true })),
+            "name published: Base Camp"
+        );
+        assert_eq!(
+            display_name_line(&json!({ "display_name": "Base Camp", "announced": false })),
+            "name saved: Base Camp"
+        );
+    }
+
+    #[test]
+    fn health_line_summarises_interface_state() {
+        let cli = parse_cli(&["lxmf-cli", "health"]);
+        assert!(matches!(cli.command, Command::Health));
+
+        let mut health = HealthReport {
+            transport_up: true,
+            interfaces_up: 1,
+            interfaces_total: 2,
+            propagation_sync_state: "idle".to_string(),
+            outbound_queue_depth: 3,
+            ..HealthReport::default()
+        };
+        assert_eq!(
+            health_line(&health),
+            "DEGRADED: transport up, 1/2 interfaces up, propagation idle, 3 queued outbound"
+        );
+        health.interfaces_up = 2;
+        assert!(health_line(&health).starts_with("OK: "));
+        health.interfaces_up = 0;
+        assert!(health_line(&health).starts_with("DOWN: "));
+    }
+
+    #[test]
+    fn stamp_status_lines_render_costs_or_disabled_node() {
+        assert_eq!(
+            stamp_status_lines(&json!({ "enabled": false })),
+            vec!["propagation node: disabled"]
+        );
+        let status = json!({
+            "enabled": true,
+            "propagation_stamp_cost": 16,
+            "flexibility": 3,
+            "peering_cost": 18,
+            "accepted_range": [13, 16],
+        });
+        assert_eq!(
+            stamp_status_lines(&status),
+            vec![
+                "propagation node: enabled",
+                "stamp cost: 16 (flexibility 3)",
+                "peering cost: 18",
+                "accepted range: 13..16",
+            ]
+        );
+
+        let adaptive = json!({
+            "enabled": true,
+            "propagation_stamp_cost": 16,
+            "flexibility": 3,
+            "peering_cost": 18,
+            "accepted_range": [15, 18],
+            "mode": "adaptive",
+            "adaptive_cost": 18,
+        });
+        assert_eq!(stamp_status_lines(&adaptive)[2], "adaptive cost: 18");
+        assert_eq!(stamp_status_lines(&adaptive)[4], "accepted range: 15..18");
+    }
+
+    #[test]
+    fn propagation_stats_lines_summarise_counters() {
+        let stats = json!({
+            "enabled": true,
+            "ingested_total": 12,
+            "last_ingest_count": 2,
+            "fetches_served": 7,
+            "fetch_misses": 1,
+            "rejected_stamps": 3,
+            "store_messages": 11,
+            "store_bytes": 4096,
+        });
+        assert_eq!(
+            propagation_stats_lines(&stats),
+            vec![
+                "propagation: enabled",
+                "ingested: 12 (last batch 2)",
+                "fetches served: 7 (misses 1)",
+                "rejected stamps: 3",
+                "store: 11 messages, 4096 bytes",
+            ]
+        );
+
+        let cli = parse_cli(&["lxmf-cli", "propagation", "stats"]);
+        assert!(matches!(cli.command, Command::Propagation { command: PropagationCommand::Stats }));
+    }
+
+    #[test]
+    fn propagation_sync_lines_flag_a_sync_already_running() {
+        let sync = json!({
+            "state": 1,
+            "state_name": "path_requested",
+            "progress": 0.05,
+            "selected_node": "aabbccdd",
+            "already_running": true,
+        });
+        assert_eq!(
+            propagation_sync_lines(&sync),
+            vec![
+                "sync: path_requested (5%)",
+                "propagation node: aabbccdd",
+                "already running; no new sync started",
+            ]
+        );
+
+        let cli = parse_cli(&["lxmf-cli", "propagation", "sync"]);
+        assert!(matches!(cli.command, Command::Propagation { command: PropagationCommand::Sync }));
+    }
+
+    #[test]
+    fn logs_tail_lines_flag_evicted_lines_before_new_ones() {
+        let logs = json!({
+            "lines": ["[daemon] rx data len=12", "[daemon] transport enabled"],
+            "next_offset": 9,
+            "first_offset": 7,
+            "missed_lines": 3,
+        });
+        assert_eq!(
+            logs_tail_lines(&logs),
+            vec![
+                "... 3 earlier lines no longer retained",
+                "[daemon] rx data len=12",
+                "[daemon] transport enabled",
+            ]
+        );
+
+        let cli = parse_cli(&["lxmf-cli", "logs", "--after-offset", "9"]);
+        assert!(matches!(
+            cli.command,
+            Command::Logs { after_offset: Some(9), max_lines: 400, level: None }
+        ));
+        let cli = parse_cli(&["lxmf-cli", "logs", "--level", "warn"]);
+        assert!(matches!(cli.command, Command::Logs { level: Some(LogLevelArg::Warn), .. }));
+    }
+
+    #[test]
+    fn overflow_policy_flag_accepts_drop_newest() {
+        let cli = parse_cli(&["lxmf-cli", "--overflow-policy", "drop_newest", "snapshot"]);
+        let request = build_start_request(&cli).expect("start request");
+        assert_eq!(request.config.overflow_policy, OverflowPolicy::DropNewest);
+    }
+
+    #[test]
+    fn trace_lines_render_stage_and_detail() {
+        let trace = json!([
+            { "status": "queued", "timestamp": 1_770_855_315 },
+            { "status": "sent: link", "timestamp": 1_770_855_316 },
+            { "status": "retrying", "timestamp": 1_770_855_320, "reason_code": "timeout" },
+        ]);
+        assert_eq!(
+            trace_lines(&trace),
+            vec![
+                "1770855315000  queued  -",
+                "1770855316000  sent  link",
+                "1770855320000  retrying  timeout",
+            ]
+        );
+
+        let cli = parse_cli(&["lxmf-cli", "trace", "--message-id", "msg-1", "--follow"]);
+        let Command::Trace { message_id, follow } = cli.command else {
+            panic!("expected trace command");
+        };
+        assert_eq!(message_id, "msg-1");
+        assert!(follow);
+    }
+
+    fn delivery_snapshot(state: &str, terminal: bool) -> DeliverySnapshot {
+        serde_json::from_value(json!({
+            "message_id": "msg-1",
+            "state": state,
+            "terminal": terminal,
+            "last_updated_ms": 0,
+            "attempts": 1,
+            "reason_code": null,
+        }))
+        .expect("delivery snapshot")
+    }
+
+    #[test]
+    fn watch_status_reports_transitions_and_stops_at_terminal_state() {
+        let mut polls = vec![
+            delivery_snapshot("queued", false),
+            delivery_snapshot("queued", false),
+            delivery_snapshot("in_flight", false),
+            delivery_snapshot("delivered", true),
+            delivery_snapshot("delivered", true),
+        ]
+        .into_iter();
+        let mut printed = Vec::new();
+        let (last, transitions) = watch_status(
+            || Ok(polls.next()),
+            Duration::ZERO,
+            |snapshot| printed.push(snapshot.map(|snapshot| snapshot.state.clone())),
+        )
+        .expect("watch");
+        assert_eq!(
+            transitions,
+            vec![DeliveryState::Queued, DeliveryState::InFlight, DeliveryState::Delivered]
+        );
+        assert_eq!(printed.len(), 3);
+        assert_eq!(last.map(|snapshot| snapshot.state), Some(DeliveryState::Delivered));
+        assert_eq!(polls.len(), 1, "watch must stop on the first terminal snapshot");
+
+        let cli = parse_cli(&["lxmf-cli", "status", "--message-id", "msg-1", "--watch"]);
+        assert!(matches!(cli.command, Command::Status { watch: true, interval_ms: 1_000, .. }));
+        assert!(Cli::try_parse_from([
+            "lxmf-cli",
+            "status",
+            "--message-id",
+            "msg-1",
+            "--interval-ms",
+            "10"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn watch_status_prints_unknown_message_once_and_exits() {
+        let mut fetches = 0;
+        let mut printed = Vec::new();
+        let (last, transitions) = watch_status(
+            || {
+                fetches += 1;
+                Ok(None)
+            },
+            Duration::ZERO,
+            |snapshot| printed.push(snapshot.is_none()),
+        )
+        .expect("watch");
+        assert!(last.is_none());
+        assert!(transitions.is_empty());
+        assert_eq!((fetches, printed), (1, vec![true]));
+    }
+
+    #[test]
+    fn search_result_lines_show_peer_direction_and_cursor() {
+        let value = json!({
+            "messages": [
+                {
+                    "id": "m2", "source": "me", "destination": "peer-b", "direction": "out",
+                    "title": "re: lunch", "content": "on my way", "timestamp": 1_770_855_320
+                },
+                {
+                    "id": "m1", "source": "peer-a", "destination": "me", "direction": "in",
+                    "title": "lunch", "content": "noon?", "timestamp": 1_770_855_315
+                },
+            ],
+            "next_cursor": "1770855315:m1",
+        });
+        assert_eq!(
+            search_result_lines(&value),
+            vec![
+                "1770855320000  -> peer-b  re: lunch  on my way  [m2]",
+                "1770855315000  <- peer-a  lunch  noon?  [m1]",
+                "next_cursor: 1770855315:m1",
+            ]
+        );
+
+        let cli = parse_cli(&["lxmf-cli", "search", "--from-ts-ms", "1000", "--to-ts-ms", "2000"]);
+        let Command::Search { query, from_ts_ms, to_ts_ms, limit, .. } = cli.command else {
+            panic!("expected search command");
+        };
+        assert_eq!((query, from_ts_ms, to_ts_ms, limit), (None, Some(1_000), Some(2_000), 50));
+    }
+
+    #[test]
+    fn attachment_command_requires_message_index_and_output_path() {
+        let cli = parse_cli(&[
+            "lxmf-cli",
+            "attachment",
+            "--message-id",
+            "m1",
+            "--index",
+            "2",
+            "--out-path",
+            "/tmp/a.bin",
+        ]);
+        let Command::Attachment { message_id, index, out_path } = cli.command else {
+            panic!("expected attachment command");
+        };
+        assert_eq!((message_id.as_str(), index, out_path.as_str()), ("m1", 2, "/tmp/a.bin"));
+        assert!(Cli::try_parse_from(["lxmf-cli", "attachment", "--message-id", "m1"]).is_err());
+    }
+
+    #[test]
+    fn dump_config_marks_overrides_and_redacts_secrets() {
+        let cli = parse_cli(&[
+            "lxmf-cli",
+            "--profile",
+            "embedded-alloc",
+            "--auth-mode",
+            "api_key",
+            "--api-key",
+            "k3y",
+            "--read-timeout-ms",
+            "9000",
+            "dump-config",
+        ]);
+        let dump = run(&cli).expect("dump without a daemon");
+        let backend = &dump["start_request"]["config"]["rpc_backend"];
+        assert_eq!(backend["api_key_auth"]["api_key"], json!("[redacted]"));
+        assert_eq!(backend["read_timeout_ms"], json!(9000));
+        assert!(!dump.to_string().contains("k3y"));
+
+        let sources = &dump["sources"];
+        assert_eq!(sources["config.profile"], json!("default"));
+        assert_eq!(sources["config.auth_mode"], json!("override"));
+        assert_eq!(sources["config.rpc_backend.read_timeout_ms"], json!("override"));
+        assert_eq!(sources["config.rpc_backend.api_key_auth.api_key"], json!("override"));
+        assert_eq!(sources["supported_contract_versions"], json!("default"));
+
+        let lines = dump_config_lines(&dump);
+        assert!(lines.contains(&"config.rpc_backend.read_timeout_ms = 9000 (override)".to_string()));
+        assert!(lines.contains(&"config.profile = \"embedded-alloc\" (default)".to_string()));
+    }
+
+    #[test]
+    fn attachment_type_line_adds_image_dimensions_when_known() {
+        let image = json!({
+            "content_type": "image/png",
+            "extensions": { "width": 640, "height": 480 },
+        });
+        assert_eq!(attachment_type_line(&image), "type: image/png, 640x480");
+        let blob = json!({ "content_type": "application/octet-stream", "extensions": {} });
+        assert_eq!(attachment_type_line(&blob), "type: application/octet-stream");
+    }
+
+    #[test]
+    fn export_command_takes_optional_filters_and_required_output_path() {
+        let cli = parse_cli(&[
+            "lxmf-cli",
+            "export",
+            "--peer",
+            "peer-a",
+            "--from-ts-ms",
+            "1000",
+            "--out-path",
+            "/tmp/chat.json",
+        ]);
+        let Command::Export { peer, from_ts_ms, to_ts_ms, out_path } = cli.command else {
+            panic!("expected export command");
+        };
+        assert_eq!(peer.as_deref(), Some("peer-a"));
+        assert_eq!((from_ts_ms, to_ts_ms), (Some(1_000), None));
+        assert_eq!(out_path, "/tmp/chat.json");
+        assert!(Cli::try_parse_from(["lxmf-cli", "export", "--peer", "peer-a"]).is_err());
+    }
+
+    #[test]
+    fn prune_command_parses_age_cutoff() {
+        let cli = parse_cli(&["lxmf-cli", "prune", "--older-than-ms", "86400000"]);
+        assert!(matches!(cli.command, Command::Prune { older_than_ms: 86_400_000 }));
+        assert!(Cli::try_parse_from(["lxmf-cli", "prune"]).is_err());
+    }
+
+    #[test]
+    fn shutdown_drain_timeout_applies_only_to_graceful_mode() {
+        let cli = parse_cli(&["lxmf-cli", "shutdown", "--drain-timeout-ms", "2500"]);
+        assert!(matches!(
+            cli.command,
+            Command::Shutdown { mode: ShutdownModeArg::Graceful, drain_timeout_ms: Some(2_500) }
+        ));
+
+        let cli = parse_cli(&[
+            "lxmf-cli",
+            "shutdown",
+            "--mode",
+            "immediate",
+            "--drain-timeout-ms",
+            "2500",
+        ]);
+        let err = run(&cli).expect_err("immediate shutdown must not drain");
+        assert_eq!(err.machine_code, error_code::VALIDATION_INVALID_ARGUMENT);
+    }
+
     #[test]
     fn completions_command_generates_nonempty_script() {
         let cli = parse_cli(&["lxmf-cli", "completions", "--shell", "bash"]);
@@ -753,4 +3388,231 @@ mod tests {
         assert!(script.contains("lxmf"));
         assert!(!script.trim().is_empty());
     }
+
+    #[test]
+    fn bash_completions_cover_subcommands_and_value_names() {
+        let script = generate_completions(CompletionShellArg::Bash);
+        let command = Cli::command();
+        for subcommand in command.get_subcommands() {
+            assert!(
+                script.contains(subcommand.get_name()),
+                "completion script should reference subcommand {}",
+                subcommand.get_name()
+            );
+            for nested in subcommand.get_subcommands() {
+                assert!(
+                    script.contains(&format!(
+                        "lxmf__{}__{}",
+                        subcommand.get_name(),
+                        nested.get_name()
+                    )),
+                    "completion script should reference {} {}",
+                    subcommand.get_name(),
+                    nested.get_name()
+                );
+            }
+        }
+        for value in ProfileArg::value_variants()
+            .iter()
+            .filter_map(ValueEnum::to_possible_value)
+            .chain(OutputModeArg::value_variants().iter().filter_map(ValueEnum::to_possible_value))
+        {
+            assert!(script.contains(value.get_name()), "missing value {}", value.get_name());
+        }
+    }
+
+    fn event_batch(seq_nos: &[u64], next_cursor: &str) -> EventBatch {
+        let events = seq_nos
+            .iter()
+            .map(|seq_no| {
+                json!({
+                    "event_id": format!("evt-{seq_no}"),
+                    "runtime_id": "rt",
+                    "stream_id": "s",
+                    "seq_no": seq_no,
+                    "contract_version": 2,
+                    "ts_ms": 0,
+                    "event_type": "inbound",
+                    "severity": "info",
+                    "source_component": "test",
+                    "operation_id": null,
+                    "message_id": null,
+                    "peer_id": null,
+                    "correlation_id": null,
+                    "trace_id": null,
+                    "payload": {},
+                })
+            })
+            .collect::<Vec<_>>();
+        serde_json::from_value(json!({
+            "events": events,
+            "next_cursor": next_cursor,
+            "dropped_count": 0,
+            "snapshot_high_watermark_seq_no": null,
+        }))
+        .expect("event batch")
+    }
+
+    #[test]
+    fn ndjson_follow_streams_each_event_once_across_cursors() {
+        let mut batches =
+            vec![event_batch(&[1, 2], "c2"), event_batch(&[], "c2"), event_batch(&[3], "c3")]
+                .into_iter();
+        let mut cursors = Vec::new();
+        let mut lines = Vec::new();
+        let err = stream_poll_events(
+            Some(EventCursor("c0".into())),
+            true,
+            Duration::ZERO,
+            |cursor| {
+                cursors.push(cursor.map(|cursor| cursor.0));
+                batches.next().ok_or_else(|| invalid_argument("interrupted"))
+            },
+            |event| lines.push(serde_json::to_string(event).expect("line")),
+        )
+        .expect_err("stream ends when polling fails");
+        assert_eq!(err.message, "interrupted");
+        assert_eq!(
+            cursors,
+            vec![Some("c0".into()), Some("c2".into()), Some("c2".into()), Some("c3".into())]
+        );
+        let seq_nos = lines
+            .iter()
+            .map(|line| serde_json::from_str::<JsonValue>(line).expect("json")["seq_no"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(seq_nos, vec![json!(1), json!(2), json!(3)]);
+        assert!(lines.iter().all(|line| !line.contains('\n')));
+
+        let (streamed, cursor) = stream_poll_events(
+            None,
+            false,
+            Duration::ZERO,
+            |_| Ok(event_batch(&[7], "c7")),
+            |_| {},
+        )
+        .expect("single poll");
+        assert_eq!((streamed, cursor), (1, Some(EventCursor("c7".into()))));
+    }
+
+    #[test]
+    fn export_follows_next_cursor_until_the_last_page() {
+        let page = |ids: &[&str], next_cursor: Option<&str>| {
+            let messages = ids
+                .iter()
+                .map(|id| {
+                    json!({
+                        "id": id,
+                        "source": "a",
+                        "destination": "b",
+                        "title": "",
+                        "content": "",
+                        "timestamp": 0,
+                        "direction": "in",
+                    })
+                })
+                .collect::<Vec<_>>();
+            serde_json::from_value::<MessageExportBundle>(json!({
+                "version": "message_bundle_v1",
+                "exported_at": 1,
+                "peer": "a",
+                "messages": messages,
+                "next_cursor": next_cursor,
+            }))
+            .expect("bundle")
+        };
+        let mut pages = vec![page(&["m1", "m2"], Some("0:m2")), page(&["m3"], None)].into_iter();
+        let mut cursors = Vec::new();
+        let request = MessageExportRequest { peer: Some("a".into()), ..Default::default() };
+        let bundle = export_all_messages(request, |req| {
+            assert_eq!(req.peer.as_deref(), Some("a"));
+            cursors.push(req.cursor);
+            pages.next().ok_or_else(|| invalid_argument("no more pages"))
+        })
+        .expect("export");
+        assert_eq!(cursors, vec![None, Some("0:m2".to_string())]);
+        let ids = bundle.messages.iter().map(|message| message.id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["m1", "m2", "m3"]);
+        assert_eq!(bundle.next_cursor, None);
+        assert!(serde_json::to_value(&bundle).expect("json").get("next_cursor").is_none());
+    }
+
+    #[test]
+    fn poll_follow_requires_ndjson_output() {
+        let cli = parse_cli(&["lxmf-cli", "--output", "ndjson", "poll", "--follow"]);
+        assert_eq!(output_mode(&cli), OutputModeArg::Ndjson);
+        let cli = parse_cli(&["lxmf-cli", "poll", "--follow"]);
+        let err = run(&cli).expect_err("follow without ndjson");
+        assert_eq!(err.machine_code, error_code::VALIDATION_INVALID_ARGUMENT);
+    }
+
+    #[test]
+    fn poll_accepts_repeated_event_type_filters() {
+        let cli = parse_cli(&[
+            "lxmf-cli",
+            "poll",
+            "--event-type",
+            "delivery_update",
+            "--event-type",
+            "inbound",
+        ]);
+        let Command::Poll { event_types, .. } = &cli.command else {
+            panic!("expected poll command");
+        };
+        assert_eq!(event_types, &["delivery_update", "inbound"]);
+    }
+
+    fn waited_send(state: &str, terminal: bool) -> JsonValue {
+        json!({
+            "message_id": "msg-1",
+            "status": {
+                "message_id": "msg-1",
+                "state": state,
+                "terminal": terminal,
+                "last_updated_ms": 1,
+                "attempts": 1,
+                "reason_code": null,
+            },
+            "timed_out": !terminal,
+        })
+    }
+
+    #[test]
+    fn send_wait_exit_code_maps_terminal_statuses() {
+        let cli = parse_cli(&["lxmf-cli", "send", "--source", "a", "--destination", "b", "--wait"]);
+        for (state, expected) in [
+            ("delivered", 0),
+            ("sent", 0),
+            ("failed", EXIT_SEND_FAILED),
+            ("rejected", EXIT_SEND_FAILED),
+            ("expired", EXIT_SEND_EXPIRED),
+            ("cancelled", EXIT_SEND_CANCELLED),
+        ] {
+            assert_eq!(exit_code_for(&cli.command, &waited_send(state, true)), expected, "{state}");
+        }
+        assert_eq!(
+            exit_code_for(&cli.command, &waited_send("in_flight", false)),
+            EXIT_SEND_TIMED_OUT
+        );
+        assert_eq!(
+            exit_code_for(&cli.command, &json!({ "message_id": "msg-1", "status": null })),
+            EXIT_SEND_TIMED_OUT
+        );
+    }
+
+    #[test]
+    fn send_without_wait_always_exits_successfully() {
+        let cli = parse_cli(&["lxmf-cli", "send", "--source", "a", "--destination", "b"]);
+        assert_eq!(exit_code_for(&cli.command, &waited_send("failed", true)), 0);
+        assert!(Cli::try_parse_from([
+            "lxmf-cli",
+            "send",
+            "--source",
+            "a",
+            "--destination",
+            "b",
+            "--wait-timeout-ms",
+            "10",
+        ])
+        .is_err());
+    }
 }
@@ -1,3 +1,8 @@
+use lxmf::propagation::{PEERING_COST, PN_META_NAME, PROPAGATION_LIMIT, SYNC_LIMIT};
+
+/// Longest display name, in characters, carried in delivery announce app-data.
+pub const MAX_DISPLAY_NAME_CHARS: usize = 64;
+
 pub fn encode_delivery_display_name_app_data(display_name: &str) -> Option<Vec<u8>> {
     let normalized = normalize_display_name(display_name)?;
     let peer_data =
@@ -5,6 +10,46 @@ pub fn encode_delivery_display_name_app_data(display_name: &str) -> Option<Vec<u
     rmp_serde::to_vec(&peer_data).ok()
 }
 
+/// Encodes `lxmf.propagation` announce app-data in the layout legacy
+/// `Router::get_propagation_node_app_data` emits: `[false, timestamp,
+/// node_state, per_transfer_limit, per_sync_limit, [stamp_cost, flexibility,
+/// peering_cost], {PN_META_NAME: display_name}]`. `stamp_cost` and
+/// `flexibility` are what the node currently enforces on ingest.
+pub fn encode_propagation_node_app_data(
+    display_name: Option<&str>,
+    node_state: bool,
+    timestamp: u64,
+    stamp_cost: u32,
+    flexibility: u32,
+) -> Option<Vec<u8>> {
+    let mut metadata = Vec::new();
+    if let Some(name) = display_name.and_then(normalize_display_name) {
+        metadata.push((rmpv::Value::from(PN_META_NAME), rmpv::Value::Binary(name.into_bytes())));
+    }
+    let announce_data = rmpv::Value::Array(vec![
+        rmpv::Value::Boolean(false),
+        rmpv::Value::from(timestamp),
+        rmpv::Value::Boolean(node_state),
+        rmpv::Value::from(PROPAGATION_LIMIT),
+        rmpv::Value::from(SYNC_LIMIT),
+        rmpv::Value::Array(vec![
+            rmpv::Value::from(stamp_cost),
+            rmpv::Value::from(flexibility),
+            rmpv::Value::from(PEERING_COST),
+        ]),
+        rmpv::Value::Map(metadata),
+    ]);
+    rmp_serde::to_vec(&announce_data).ok()
+}
+
+/// Reads the node-state flag from `lxmf.propagation` announce app-data.
+pub fn propagation_node_state_from_app_data(app_data: &[u8]) -> Option<bool> {
+    match rmp_serde::from_slice::<rmpv::Value>(app_data).ok()? {
+        rmpv::Value::Array(entries) => entries.get(2)?.as_bool(),
+        _ => None,
+    }
+}
+
 pub fn normalize_display_name(value: &str) -> Option<String> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
@@ -13,7 +58,7 @@ pub fn normalize_display_name(value: &str) -> Option<String> {
     if trimmed.chars().any(char::is_control) {
         return None;
     }
-    let normalized: String = trimmed.chars().take(64).collect();
+    let normalized: String = trimmed.chars().take(MAX_DISPLAY_NAME_CHARS).collect();
     if normalized.is_empty() {
         None
     } else {
@@ -21,6 +66,15 @@ pub fn normalize_display_name(value: &str) -> Option<String> {
     }
 }
 
+/// Like [`normalize_display_name`], but rejects names that would be truncated
+/// on the wire instead of silently shortening them.
+pub fn publishable_display_name(value: &str) -> Option<String> {
+    if value.trim().chars().count() > MAX_DISPLAY_NAME_CHARS {
+        return None;
+    }
+    normalize_display_name(value)
+}
+
 pub fn parse_peer_name_from_app_data(app_data: &[u8]) -> Option<(String, &'static str)> {
     if app_data.is_empty() {
         return None;
@@ -74,8 +128,6 @@ fn display_name_from_app_data(data: &[u8]) -> Option<String> {
 }
 
 fn pn_name_from_app_data(data: &[u8]) -> Option<String> {
-    const PN_META_NAME: u8 = 0x01;
-
     let decoded = rmp_serde::from_slice::<rmpv::Value>(data).ok()?;
     let entries = match decoded {
         rmpv::Value::Array(entries) => entries,
@@ -1,4 +1,5 @@
 use super::bridge::PeerCrypto;
+use super::bridge_helpers::daemon_log;
 use reticulum_daemon::announce_names::parse_peer_name_from_app_data;
 use rns_rpc::RpcDaemon;
 use rns_transport::time::now_epoch_secs_i64;
@@ -26,11 +27,11 @@ pub(super) fn spawn_announce_worker(
                         .unwrap_or((None, None));
                 let _ratchet = event.ratchet;
                 peer_crypto.lock().expect("peer map").insert(peer.clone(), PeerCrypto { identity });
-                if let Some(name) = peer_name.as_ref() {
-                    eprintln!("[daemon] rx announce peer={} name={}", peer, name);
-                } else {
-                    eprintln!("[daemon] rx announce peer={}", peer);
-                }
+                let line = match peer_name.as_ref() {
+                    Some(name) => format!("[daemon] rx announce peer={} name={}", peer, name),
+                    None => format!("[daemon] rx announce peer={}", peer),
+                };
+                daemon_log(&daemon_announce, line);
                 let timestamp = now_epoch_secs_i64();
                 let _ = daemon_announce.accept_announce_with_details(
                     peer,
@@ -1,26 +1,36 @@
 use super::announce_worker::spawn_announce_worker;
 use super::bridge::{PeerCrypto, TransportBridge};
 use super::inbound_worker::spawn_inbound_worker;
-use super::receipt_worker::spawn_receipt_worker;
-use super::Args;
-use reticulum_daemon::announce_names::{
-    encode_delivery_display_name_app_data, normalize_display_name,
+use super::receipt_worker::{
+    spawn_announce_outcome_worker, spawn_ping_worker, spawn_propagation_sync_worker,
+    spawn_receipt_worker, spawn_stamp_worker,
 };
+use super::traffic_worker::{spawn_link_state_worker, spawn_traffic_worker};
+use super::Args;
+use reticulum_daemon::announce_names::normalize_display_name;
 use reticulum_daemon::config::DaemonConfig;
-use reticulum_daemon::identity_store::load_or_create_identity;
+use reticulum_daemon::identity_store::{load_display_name, load_or_create_identity};
+use reticulum_daemon::inbound_rate_limit::InboundRateLimiter;
 use reticulum_daemon::receipt_bridge::ReceiptBridge;
+use rns_rpc::rpc::outbound_file::FileOutboundBridge;
 use rns_rpc::{AnnounceBridge, InterfaceRecord, MessagesStore, OutboundBridge, RpcDaemon};
 use rns_transport::destination::{DestinationName, SingleInputDestination};
 use rns_transport::iface::tcp_client::TcpClient;
 use rns_transport::iface::tcp_server::TcpServer;
+use rns_transport::iface::udp::UdpInterface;
 use rns_transport::transport::{Transport, TransportConfig};
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::sync::mpsc::unbounded_channel;
 
+const SCHEDULED_SEND_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const RECEIPT_TIMEOUT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const INTERFACE_TRAFFIC_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
 #[derive(Clone, Debug)]
 pub(super) struct RpcTlsConfig {
     pub(super) cert_chain_path: PathBuf,
@@ -32,8 +42,11 @@ pub(super) struct BootstrapContext {
     pub(super) rpc_addr: SocketAddr,
     pub(super) daemon: Rc<RpcDaemon>,
     pub(super) rpc_tls: Option<RpcTlsConfig>,
+    pub(super) bridge: Option<Arc<TransportBridge>>,
 }
 
+type SharedDestination = Arc<tokio::sync::Mutex<SingleInputDestination>>;
+
 pub(super) async fn bootstrap(args: Args) -> BootstrapContext {
     let rpc_addr: SocketAddr = args.rpc.parse().expect("invalid rpc address");
     let rpc_tls =
@@ -57,8 +70,15 @@ pub(super) async fn bootstrap(args: Args) -> BootstrapContext {
     });
     let identity = load_or_create_identity(&identity_path).expect("load identity");
     let identity_hash = hex::encode(identity.address_hash().as_slice());
+    // A name set through `set_display_name` outlives LXMF_DISPLAY_NAME.
+    let stored_display_name = load_display_name(&identity_path).unwrap_or_else(|err| {
+        eprintln!("[daemon] failed to read stored display name: {}", err);
+        None
+    });
     let local_display_name =
-        std::env::var("LXMF_DISPLAY_NAME").ok().and_then(|value| normalize_display_name(&value));
+        stored_display_name.and_then(|value| normalize_display_name(&value)).or_else(|| {
+            std::env::var("LXMF_DISPLAY_NAME").ok().and_then(|value| normalize_display_name(&value))
+        });
     let daemon_config = args.config.as_ref().and_then(|path| match DaemonConfig::from_path(path) {
         Ok(config) => Some(config),
         Err(err) => {
@@ -84,17 +104,24 @@ pub(super) async fn bootstrap(args: Args) -> BootstrapContext {
         .unwrap_or_default();
 
     let mut transport: Option<Arc<Transport>> = None;
+    let mut spawned_interfaces = Vec::new();
     let peer_crypto: Arc<Mutex<HashMap<String, PeerCrypto>>> = Arc::new(Mutex::new(HashMap::new()));
-    let mut announce_destination: Option<Arc<tokio::sync::Mutex<SingleInputDestination>>> = None;
+    // Delivery and propagation destinations, registered once transport is up.
+    let mut announce_destinations: Option<(SharedDestination, SharedDestination)> = None;
     let mut delivery_destination_hash_hex: Option<String> = None;
     let mut delivery_source_hash = [0u8; 16];
     let receipt_map: Arc<Mutex<HashMap<String, String>>> = Arc::new(Mutex::new(HashMap::new()));
     let (receipt_tx, receipt_rx) = unbounded_channel();
+    let (stamp_tx, stamp_rx) = unbounded_channel();
+    let (ping_tx, ping_rx) = unbounded_channel();
+    let (announce_tx, announce_rx) = unbounded_channel();
+    let (sync_tx, sync_rx) = unbounded_channel();
 
     if let Some(addr) = args.transport.clone() {
         let transport_identity =
             rns_transport::identity_bridge::to_transport_private_identity(&identity);
-        let config = TransportConfig::new("daemon", &transport_identity, true);
+        let mut config = TransportConfig::new("daemon", &transport_identity, true);
+        config.set_iface_max_in_flight(args.iface_max_in_flight);
         let mut transport_instance = Transport::new(config);
         transport_instance
             .set_receipt_handler(Box::new(ReceiptBridge::new(
@@ -108,15 +135,74 @@ pub(super) async fn bootstrap(args: Args) -> BootstrapContext {
             .await
             .spawn(TcpServer::new(addr.clone(), iface_manager.clone()), TcpServer::spawn);
         eprintln!("[daemon] tcp_server enabled iface={} bind={}", server_iface, addr);
+        spawned_interfaces.push((server_iface, "daemon-transport".to_string()));
         if let Some(config) = daemon_config.as_ref() {
-            for (host, port) in config.tcp_client_endpoints() {
+            for iface in config.enabled_tcp_clients() {
+                let (Some(host), Some(port)) = (iface.host.as_ref(), iface.port) else {
+                    continue;
+                };
+                let name = iface.name.as_deref().unwrap_or("tcp_client");
+                let settings = match iface.tcp_settings() {
+                    Ok(settings) => settings,
+                    Err(err) => {
+                        eprintln!("[daemon] tcp_client disabled name={}: {}", name, err);
+                        continue;
+                    }
+                };
                 let endpoint = format!("{}:{}", host, port);
-                let client_iface =
-                    iface_manager.lock().await.spawn(TcpClient::new(endpoint), TcpClient::spawn);
+                let client_iface = iface_manager
+                    .lock()
+                    .await
+                    .spawn(TcpClient::new(endpoint).with_settings(settings), TcpClient::spawn);
                 eprintln!(
                     "[daemon] tcp_client enabled iface={} name={} host={} port={}",
-                    client_iface, host, host, port
+                    client_iface, name, host, port
                 );
+                spawned_interfaces.push((client_iface, name.to_string()));
+            }
+            for iface in config.enabled_tcp_servers() {
+                let (Some(host), Some(port)) = (iface.host.as_ref(), iface.port) else {
+                    continue;
+                };
+                let name = iface.name.as_deref().unwrap_or("tcp_server");
+                let settings = match iface.tcp_settings() {
+                    Ok(settings) => settings,
+                    Err(err) => {
+                        eprintln!("[daemon] tcp_server disabled name={}: {}", name, err);
+                        continue;
+                    }
+                };
+                let bind = format!("{}:{}", host, port);
+                let listener_iface = iface_manager.lock().await.spawn(
+                    TcpServer::new(bind, iface_manager.clone()).with_settings(settings),
+                    TcpServer::spawn,
+                );
+                eprintln!(
+                    "[daemon] tcp_server enabled iface={} name={} host={} port={}",
+                    listener_iface, name, host, port
+                );
+                spawned_interfaces.push((listener_iface, name.to_string()));
+            }
+            for iface in config.enabled_udp_multicast() {
+                let name = iface.name.as_deref().unwrap_or("udp_multicast");
+                match iface.udp_multicast_settings() {
+                    Ok(settings) => {
+                        let multicast_iface = iface_manager.lock().await.spawn(
+                            UdpInterface::multicast(
+                                settings.group_addr,
+                                settings.port,
+                                settings.ttl,
+                            ),
+                            UdpInterface::spawn,
+                        );
+                        eprintln!(
+                            "[daemon] udp_multicast enabled iface={} name={} group={} port={} ttl={}",
+                            multicast_iface, name, settings.group_addr, settings.port, settings.ttl
+                        );
+                        spawned_interfaces.push((multicast_iface, name.to_string()));
+                    }
+                    Err(err) => eprintln!("[daemon] udp_multicast disabled name={}: {}", name, err),
+                }
             }
         }
         eprintln!("[daemon] transport enabled");
@@ -142,28 +228,58 @@ pub(super) async fn bootstrap(args: Args) -> BootstrapContext {
                 hex::encode(dest.desc.address_hash.as_slice())
             );
         }
-        announce_destination = Some(destination);
+        let propagation_destination = transport_instance
+            .add_destination(
+                transport_identity.clone(),
+                DestinationName::new("lxmf", "propagation"),
+            )
+            .await;
+        announce_destinations = Some((destination, propagation_destination));
         transport = Some(Arc::new(transport_instance));
     }
 
-    let bridge: Option<Arc<TransportBridge>> =
-        transport.as_ref().zip(announce_destination.as_ref()).map(|(transport, destination)| {
+    let bridge: Option<Arc<TransportBridge>> = transport
+        .as_ref()
+        .zip(announce_destinations.as_ref())
+        .map(|(transport, (destination, propagation_destination))| {
             Arc::new(TransportBridge::new(
                 transport.clone(),
+                identity_path.clone(),
                 identity.clone(),
                 delivery_source_hash,
                 destination.clone(),
-                local_display_name
-                    .as_ref()
-                    .and_then(|display_name| encode_delivery_display_name_app_data(display_name)),
+                propagation_destination.clone(),
+                local_display_name.clone(),
                 peer_crypto.clone(),
                 receipt_map.clone(),
                 receipt_tx.clone(),
+                stamp_tx.clone(),
+                ping_tx.clone(),
+                announce_tx.clone(),
+                sync_tx.clone(),
+                spawned_interfaces.clone(),
             ))
         });
 
-    let outbound_bridge: Option<Arc<dyn OutboundBridge>> =
+    if let Some(bridge) = bridge.as_ref() {
+        if let Err(err) = bridge.restore_retired_identities() {
+            eprintln!("[daemon] failed to load retired identities: {}", err);
+        }
+    }
+
+    let mut outbound_bridge: Option<Arc<dyn OutboundBridge>> =
         bridge.as_ref().map(|bridge| bridge.clone() as Arc<dyn OutboundBridge>);
+    if let Some(path) = args.outbound_file.as_ref() {
+        match FileOutboundBridge::new(path) {
+            Ok(file_bridge) => {
+                eprintln!("[daemon] outbound file sink enabled path={}", path.display());
+                outbound_bridge = Some(Arc::new(file_bridge));
+            }
+            Err(err) => {
+                eprintln!("[daemon] outbound file sink disabled path={}: {}", path.display(), err)
+            }
+        }
+    }
     let announce_bridge: Option<Arc<dyn AnnounceBridge>> =
         bridge.as_ref().map(|bridge| bridge.clone() as Arc<dyn AnnounceBridge>);
 
@@ -174,8 +290,34 @@ pub(super) async fn bootstrap(args: Args) -> BootstrapContext {
         announce_bridge,
     ));
     daemon.set_delivery_destination_hash(delivery_destination_hash_hex);
+    daemon.set_local_display_name(local_display_name.clone());
     daemon.replace_interfaces(configured_interfaces);
     daemon.set_propagation_state(transport.is_some(), None, 0);
+    if args.persist_events {
+        let events_path = args.db.with_extension("events.jsonl");
+        if let Err(err) = daemon.enable_event_persistence(&events_path) {
+            eprintln!("[daemon] failed to open event log {}: {}", events_path.display(), err);
+        }
+    }
+    if args.dead_letter_log {
+        let dead_letter_path = args.db.with_extension("dead-letter.jsonl");
+        if let Err(err) =
+            daemon.enable_dead_letter_log(&dead_letter_path, args.dead_letter_max_bytes)
+        {
+            eprintln!(
+                "[daemon] failed to open dead-letter log {}: {}",
+                dead_letter_path.display(),
+                err
+            );
+        }
+    }
+    if args.audit_log {
+        let audit_path = args.db.with_extension("audit.jsonl");
+        if let Err(err) = daemon.enable_security_audit_log(&audit_path, args.audit_include_allowed)
+        {
+            eprintln!("[daemon] failed to open audit log {}: {}", audit_path.display(), err);
+        }
+    }
 
     // Make the local delivery destination visible on startup.
     if let Some(bridge) = bridge.as_ref() {
@@ -184,16 +326,44 @@ pub(super) async fn bootstrap(args: Args) -> BootstrapContext {
 
     if transport.is_some() {
         spawn_receipt_worker(daemon.clone(), receipt_rx);
+        spawn_stamp_worker(daemon.clone(), stamp_rx);
+        spawn_ping_worker(daemon.clone(), ping_rx);
+        spawn_announce_outcome_worker(daemon.clone(), announce_rx);
+        spawn_propagation_sync_worker(daemon.clone(), sync_rx);
     }
-
-    if args.announce_interval_secs > 0 {
-        let _handle = daemon.clone().start_announce_scheduler(args.announce_interval_secs);
+    if let Some(transport) = transport.as_ref() {
+        if let Some(events) = transport.iface_manager().lock().await.take_link_events() {
+            spawn_link_state_worker(daemon.clone(), events, spawned_interfaces.clone());
+        }
+        spawn_traffic_worker(
+            daemon.clone(),
+            transport.iface_manager(),
+            spawned_interfaces,
+            INTERFACE_TRAFFIC_POLL_INTERVAL,
+        );
     }
 
+    let _scheduled_sends = daemon.clone().start_scheduled_send_loop(SCHEDULED_SEND_POLL_INTERVAL);
+    let _receipt_timeouts =
+        daemon.clone().start_receipt_timeout_loop(RECEIPT_TIMEOUT_POLL_INTERVAL);
+
+    // Runs even when the flag is 0 so `announce_interval_secs` can enable
+    // periodic announces through runtime config.
+    let _announce_scheduler = daemon.clone().start_announce_scheduler(args.announce_interval_secs);
+
     if let Some(transport) = transport {
-        spawn_inbound_worker(daemon.clone(), transport.clone());
+        spawn_inbound_worker(
+            daemon.clone(),
+            transport.clone(),
+            args.inbound_decode_workers,
+            args.inbound_decode_queue,
+            args.inbound_rate_per_sec.map(|rate_per_sec| {
+                InboundRateLimiter::new(rate_per_sec, args.inbound_burst)
+                    .with_trusted(&args.inbound_trusted_sources)
+            }),
+        );
         spawn_announce_worker(daemon.clone(), transport, peer_crypto);
     }
 
-    BootstrapContext { rpc_addr, daemon, rpc_tls }
+    BootstrapContext { rpc_addr, daemon, rpc_tls, bridge }
 }
@@ -1,15 +1,31 @@
 use super::bridge_helpers::{
-    diagnostics_enabled, log_delivery_trace, opportunistic_payload, payload_preview,
-    send_trace_detail,
+    diagnostics_enabled, log_delivery_trace, now_epoch_secs, opportunistic_payload,
+    payload_preview, send_trace_detail,
 };
-use reticulum_daemon::lxmf_bridge::build_wire_message;
+use lxmf::propagation::{PROPAGATION_COST, PROPAGATION_COST_FLEX};
+use lxmf::stamper::StampGenerator;
+use reticulum_daemon::announce_names::{
+    encode_delivery_display_name_app_data, encode_propagation_node_app_data,
+    publishable_display_name, MAX_DISPLAY_NAME_CHARS,
+};
+use reticulum_daemon::identity_store::{
+    load_retired_identities, rotate_identity, store_display_name,
+};
+use reticulum_daemon::lxmf_bridge::{build_wire_message, stamp_wire_message};
+use reticulum_daemon::peer_ping::{ping_peer, PeerPingResult};
+use reticulum_daemon::propagation_sync::{sync_from_propagation_node, PropagationSyncUpdate};
 use reticulum_daemon::receipt_bridge::{track_receipt_mapping, ReceiptEvent};
 use rns_core::identity::PrivateIdentity;
-use rns_rpc::{AnnounceBridge, OutboundBridge};
+use rns_rpc::{
+    AnnounceBridge, DisplayNameUpdate, IdentityRotation, OutboundBridge, PeerPingRequest,
+    PropagationSyncRequest, StampTaskHandle,
+};
 use rns_transport::delivery::{
     send_outcome_is_sent, send_outcome_status, send_via_link, LinkSendResult,
 };
-use rns_transport::destination::{DestinationDesc, DestinationName, SingleInputDestination};
+use rns_transport::destination::{
+    DestinationDesc, DestinationName, SingleInputDestination, SingleOutputDestination,
+};
 use rns_transport::destination_hash::parse_destination_hash_required;
 use rns_transport::hash::AddressHash;
 use rns_transport::identity::Identity;
@@ -18,18 +34,89 @@ use rns_transport::packet::{
     PacketDataBuffer, PacketType, PropagationType,
 };
 use rns_transport::transport::Transport;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 
+const PATH_WAIT_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
 pub(super) struct TransportBridge {
     transport: Arc<Transport>,
-    signer: PrivateIdentity,
-    delivery_source_hash: [u8; 16],
-    announce_destination: Arc<tokio::sync::Mutex<SingleInputDestination>>,
-    announce_app_data: Option<Vec<u8>>,
+    identity_path: PathBuf,
+    local: Mutex<LocalIdentity>,
+    /// Identities replaced by [`AnnounceBridge::rotate_identity`]; messages
+    /// stored under their delivery hash keep signing with them.
+    retired: Mutex<Vec<LocalIdentity>>,
+    /// Retired delivery hashes whose key could not be read back from disk.
+    /// Messages queued under them fail instead of being re-signed.
+    unavailable_sources: Mutex<HashSet<String>>,
+    display_name: Mutex<Option<String>>,
+    propagation_node: AtomicBool,
+    /// Delivery tasks still running, so a timed-out or cancelled message
+    /// stops before it reaches the wire.
+    deliveries: Arc<Mutex<HashMap<String, tokio::task::AbortHandle>>>,
+    /// Stamp cost and flexibility advertised in propagation announces, as
+    /// last set by [`AnnounceBridge::set_propagation_stamp_cost`].
+    propagation_stamp_cost: Mutex<(u32, u32)>,
     peer_crypto: Arc<Mutex<HashMap<String, PeerCrypto>>>,
     receipt_map: Arc<Mutex<HashMap<String, String>>>,
     receipt_tx: tokio::sync::mpsc::UnboundedSender<ReceiptEvent>,
+    stamp_tx: tokio::sync::mpsc::UnboundedSender<StampEvent>,
+    ping_tx: tokio::sync::mpsc::UnboundedSender<PingEvent>,
+    announce_tx: tokio::sync::mpsc::UnboundedSender<AnnounceEvent>,
+    sync_tx: tokio::sync::mpsc::UnboundedSender<PropagationSyncUpdate>,
+    interfaces: Vec<(AddressHash, String)>,
+}
+
+pub(super) enum StampEvent {
+    Progress { message_id: String, attempts: u64 },
+    Finished { message_id: String },
+}
+
+pub(super) struct PingEvent {
+    pub(super) ping_id: String,
+    pub(super) result: PeerPingResult,
+}
+
+/// Outcome of an announce started on the transport task, reported back to
+/// the daemon.
+pub(super) enum AnnounceEvent {
+    Directed { target: String, announced: bool },
+    Rotated { delivery_destination_hash: String, result: Result<(), String> },
+}
+
+#[derive(Clone)]
+struct LocalIdentity {
+    signer: PrivateIdentity,
+    delivery_source_hash: [u8; 16],
+    announce_destination: Arc<tokio::sync::Mutex<SingleInputDestination>>,
+    propagation_destination: Arc<tokio::sync::Mutex<SingleInputDestination>>,
+}
+
+impl LocalIdentity {
+    /// Builds the delivery and propagation destinations for `signer`; the
+    /// caller registers them with the transport.
+    fn new(signer: PrivateIdentity) -> Self {
+        let transport_identity =
+            rns_transport::identity_bridge::to_transport_private_identity(&signer);
+        let destination = SingleInputDestination::new(
+            transport_identity.clone(),
+            DestinationName::new("lxmf", "delivery"),
+        );
+        let propagation_destination = SingleInputDestination::new(
+            transport_identity,
+            DestinationName::new("lxmf", "propagation"),
+        );
+        let mut delivery_source_hash = [0u8; 16];
+        delivery_source_hash.copy_from_slice(destination.desc.address_hash.as_slice());
+        Self {
+            signer,
+            delivery_source_hash,
+            announce_destination: Arc::new(tokio::sync::Mutex::new(destination)),
+            propagation_destination: Arc::new(tokio::sync::Mutex::new(propagation_destination)),
+        }
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -41,24 +128,158 @@ impl TransportBridge {
     #[allow(clippy::too_many_arguments)]
     pub(super) fn new(
         transport: Arc<Transport>,
+        identity_path: PathBuf,
         signer: PrivateIdentity,
         delivery_source_hash: [u8; 16],
         announce_destination: Arc<tokio::sync::Mutex<SingleInputDestination>>,
-        announce_app_data: Option<Vec<u8>>,
+        propagation_destination: Arc<tokio::sync::Mutex<SingleInputDestination>>,
+        display_name: Option<String>,
         peer_crypto: Arc<Mutex<HashMap<String, PeerCrypto>>>,
         receipt_map: Arc<Mutex<HashMap<String, String>>>,
         receipt_tx: tokio::sync::mpsc::UnboundedSender<ReceiptEvent>,
+        stamp_tx: tokio::sync::mpsc::UnboundedSender<StampEvent>,
+        ping_tx: tokio::sync::mpsc::UnboundedSender<PingEvent>,
+        announce_tx: tokio::sync::mpsc::UnboundedSender<AnnounceEvent>,
+        sync_tx: tokio::sync::mpsc::UnboundedSender<PropagationSyncUpdate>,
+        interfaces: Vec<(AddressHash, String)>,
     ) -> Self {
         Self {
             transport,
-            signer,
-            delivery_source_hash,
-            announce_destination,
-            announce_app_data,
+            identity_path,
+            local: Mutex::new(LocalIdentity {
+                signer,
+                delivery_source_hash,
+                announce_destination,
+                propagation_destination,
+            }),
+            retired: Mutex::new(Vec::new()),
+            unavailable_sources: Mutex::new(HashSet::new()),
+            display_name: Mutex::new(display_name),
+            propagation_node: AtomicBool::new(false),
+            deliveries: Arc::new(Mutex::new(HashMap::new())),
+            propagation_stamp_cost: Mutex::new((PROPAGATION_COST, PROPAGATION_COST_FLEX)),
             peer_crypto,
             receipt_map,
             receipt_tx,
+            stamp_tx,
+            ping_tx,
+            announce_tx,
+            sync_tx,
+            interfaces,
+        }
+    }
+
+    /// Requests a path to `destination` and waits up to `timeout` for its
+    /// announce to arrive. Runs on the RPC loop ahead of fail-fast sends so
+    /// the synchronous [`OutboundBridge::await_path`] can answer from the peers
+    /// learned meanwhile.
+    pub(super) async fn wait_for_path(&self, destination: &str, timeout: std::time::Duration) {
+        let known = || self.peer_crypto.lock().expect("peer map").contains_key(destination);
+        if known() {
+            return;
+        }
+        let Ok(hash) = parse_destination_hash_required(destination) else {
+            return;
+        };
+        self.transport.request_path(&AddressHash::new(hash), None, None).await;
+        let deadline = tokio::time::Instant::now() + timeout;
+        while !known() && tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(PATH_WAIT_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Loads the keys retired by earlier rotations and registers their
+    /// destinations again, so messages queued before a restart keep their
+    /// original source. Returns how many keys were restored.
+    pub(super) fn restore_retired_identities(&self) -> std::io::Result<usize> {
+        let mut restored = Vec::new();
+        for entry in load_retired_identities(&self.identity_path)? {
+            match entry.identity {
+                Ok(signer) => restored.push(LocalIdentity::new(signer)),
+                Err(err) => {
+                    eprintln!("[daemon] retired identity {} unavailable: {}", entry.name, err);
+                    self.unavailable_sources
+                        .lock()
+                        .expect("unavailable sources")
+                        .insert(entry.name.to_ascii_lowercase());
+                }
+            }
+        }
+        let count = restored.len();
+        let transport = self.transport.clone();
+        let destinations = restored
+            .iter()
+            .map(|identity| {
+                (identity.announce_destination.clone(), identity.propagation_destination.clone())
+            })
+            .collect::<Vec<_>>();
+        tokio::spawn(async move {
+            for (destination, propagation_destination) in destinations {
+                transport.register_destination(destination).await;
+                transport.register_destination(propagation_destination).await;
+            }
+        });
+        self.retired.lock().expect("retired identities").extend(restored);
+        Ok(count)
+    }
+
+    /// Picks the identity a stored message was queued under, falling back to
+    /// the current one. Fails when the message was queued under a retired
+    /// identity whose key is gone, since re-signing it with the current key
+    /// would change its source.
+    fn identity_for_source(&self, source: &str) -> std::io::Result<LocalIdentity> {
+        if self
+            .unavailable_sources
+            .lock()
+            .expect("unavailable sources")
+            .contains(&source.to_ascii_lowercase())
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("key for retired source {source} is unavailable"),
+            ));
         }
+        let retired = self.retired.lock().expect("retired identities");
+        Ok(retired
+            .iter()
+            .find(|identity| {
+                hex::encode(identity.delivery_source_hash).eq_ignore_ascii_case(source)
+            })
+            .cloned()
+            .unwrap_or_else(|| self.local.lock().expect("local identity").clone()))
+    }
+
+    fn announce_app_data(&self) -> Option<Vec<u8>> {
+        let display_name = self.display_name.lock().expect("display name").clone();
+        display_name.as_deref().and_then(encode_delivery_display_name_app_data)
+    }
+
+    fn propagation_app_data(&self, node_state: bool) -> Option<Vec<u8>> {
+        let display_name = self.display_name.lock().expect("display name").clone();
+        let (stamp_cost, flexibility) =
+            *self.propagation_stamp_cost.lock().expect("propagation stamp cost");
+        encode_propagation_node_app_data(
+            display_name.as_deref(),
+            node_state,
+            now_epoch_secs(),
+            stamp_cost,
+            flexibility,
+        )
+    }
+
+    /// Announces the `lxmf.propagation` destination with `node_state`, so
+    /// peers learn both when this node starts and when it stops serving
+    /// propagation.
+    fn announce_propagation_node(&self, node_state: bool) {
+        let Some(app_data) = self.propagation_app_data(node_state) else {
+            return;
+        };
+        let transport = self.transport.clone();
+        let destination =
+            self.local.lock().expect("local identity").propagation_destination.clone();
+        tokio::spawn(async move {
+            transport.send_announce(&destination, Some(&app_data)).await;
+        });
     }
 }
 
@@ -67,12 +288,14 @@ struct DeliveryTask {
     peer_crypto: Arc<Mutex<HashMap<String, PeerCrypto>>>,
     receipt_map: Arc<Mutex<HashMap<String, String>>>,
     receipt_tx: tokio::sync::mpsc::UnboundedSender<ReceiptEvent>,
+    stamp_tx: tokio::sync::mpsc::UnboundedSender<StampEvent>,
     message_id: String,
     destination: [u8; 16],
     destination_hash: AddressHash,
     destination_hex: String,
     payload: Vec<u8>,
     peer_identity: Option<Identity>,
+    stamp_task: Option<StampTaskHandle>,
 }
 
 impl DeliveryTask {
@@ -82,15 +305,35 @@ impl DeliveryTask {
             peer_crypto,
             receipt_map,
             receipt_tx,
+            stamp_tx,
             message_id,
             destination,
             destination_hash,
             destination_hex,
             payload,
             peer_identity,
+            stamp_task,
         } = self;
 
         log_delivery_trace(&message_id, &destination_hex, "start", "delivery requested");
+        let payload = match stamp_task {
+            Some(task) => {
+                let stamped =
+                    stamp_payload(&message_id, &destination_hex, payload, task, &stamp_tx);
+                match stamped.await {
+                    Ok(Some(payload)) => payload,
+                    Ok(None) => return,
+                    Err(err) => {
+                        let _ = receipt_tx.send(ReceiptEvent {
+                            message_id,
+                            status: format!("failed: stamp generation: {err}"),
+                        });
+                        return;
+                    }
+                }
+            }
+            None => payload,
+        };
         let mut identity = peer_identity;
         // Refresh routing for the destination before link setup.
         transport.request_path(&destination_hash, None, None).await;
@@ -249,24 +492,67 @@ impl DeliveryTask {
     }
 }
 
+async fn stamp_payload(
+    message_id: &str,
+    destination_hex: &str,
+    payload: Vec<u8>,
+    task: StampTaskHandle,
+    stamp_tx: &tokio::sync::mpsc::UnboundedSender<StampEvent>,
+) -> Result<Option<Vec<u8>>, String> {
+    let detail = format!("generating cost={} workers={}", task.target_cost(), task.workers());
+    log_delivery_trace(message_id, destination_hex, "stamp", &detail);
+    let progress_id = message_id.to_string();
+    let progress_tx = stamp_tx.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let generator = StampGenerator::new(task.workers());
+        stamp_wire_message(
+            &payload,
+            &generator,
+            task.target_cost(),
+            task.cancel_flag(),
+            |progress| {
+                let _ = progress_tx.send(StampEvent::Progress {
+                    message_id: progress_id.clone(),
+                    attempts: progress.attempts,
+                });
+            },
+        )
+    })
+    .await;
+    let _ = stamp_tx.send(StampEvent::Finished { message_id: message_id.to_string() });
+    let outcome = match result {
+        Ok(Ok(stamped)) => Ok(stamped),
+        Ok(Err(err)) => Err(err.to_string()),
+        Err(err) => Err(err.to_string()),
+    };
+    let detail = match &outcome {
+        Ok(Some(_)) => "generated".to_string(),
+        Ok(None) => "cancelled".to_string(),
+        Err(err) => format!("failed err={err}"),
+    };
+    log_delivery_trace(message_id, destination_hex, "stamp", &detail);
+    outcome
+}
+
 impl OutboundBridge for TransportBridge {
     fn deliver(
         &self,
         record: &rns_rpc::MessageRecord,
-        _options: &rns_rpc::OutboundDeliveryOptions,
+        options: &rns_rpc::OutboundDeliveryOptions,
     ) -> Result<(), std::io::Error> {
         let destination = parse_destination_hash_required(&record.destination)?;
         let peer_info =
             self.peer_crypto.lock().expect("peer map").get(&record.destination).copied();
         let peer_identity = peer_info.map(|info| info.identity);
 
+        let local = self.identity_for_source(&record.source)?;
         let payload = build_wire_message(
-            self.delivery_source_hash,
+            local.delivery_source_hash,
             destination,
             &record.title,
             &record.content,
             record.fields.clone(),
-            &self.signer,
+            &local.signer,
         )
         .map_err(std::io::Error::other)?;
 
@@ -275,26 +561,289 @@ impl OutboundBridge for TransportBridge {
             peer_crypto: self.peer_crypto.clone(),
             receipt_map: self.receipt_map.clone(),
             receipt_tx: self.receipt_tx.clone(),
+            stamp_tx: self.stamp_tx.clone(),
             message_id: record.id.clone(),
             destination,
             destination_hash: AddressHash::new(destination),
             destination_hex: record.destination.clone(),
             payload,
             peer_identity,
+            stamp_task: options.stamp_task.clone(),
+        };
+        // Registering under the lock keeps the task from clearing its entry
+        // before it exists.
+        let mut deliveries = self.deliveries.lock().expect("deliveries");
+        let message_id = record.id.clone();
+        let finished = self.deliveries.clone();
+        let handle = tokio::spawn(async move {
+            let message_id = task.message_id.clone();
+            task.run().await;
+            finished.lock().expect("deliveries").remove(&message_id);
+        });
+        deliveries.insert(message_id, handle.abort_handle());
+        Ok(())
+    }
+
+    fn cancel_delivery(&self, message_id: &str) -> Result<bool, std::io::Error> {
+        let handle = self.deliveries.lock().expect("deliveries").remove(message_id);
+        Ok(handle.map(|handle| handle.abort()).is_some())
+    }
+
+    fn plan(
+        &self,
+        record: &rns_rpc::MessageRecord,
+        options: &rns_rpc::OutboundDeliveryOptions,
+    ) -> Result<rns_rpc::OutboundPlan, std::io::Error> {
+        let destination = parse_destination_hash_required(&record.destination)?;
+        let destination_identity_known =
+            self.peer_crypto.lock().expect("peer map").contains_key(&record.destination);
+        let local = self.identity_for_source(&record.source)?;
+        let payload = build_wire_message(
+            local.delivery_source_hash,
+            destination,
+            &record.title,
+            &record.content,
+            record.fields.clone(),
+            &local.signer,
+        )
+        .map_err(std::io::Error::other)?;
+        Ok(rns_rpc::OutboundPlan {
+            method: options.method.clone().unwrap_or_else(|| "direct".to_string()),
+            wire_size: Some(payload.len()),
+            target_interface: None,
+            destination_identity_known,
+        })
+    }
+
+    fn ping(&self, request: &PeerPingRequest) -> Result<(), std::io::Error> {
+        let destination = AddressHash::new(parse_destination_hash_required(&request.peer)?);
+        let transport = self.transport.clone();
+        let ping_tx = self.ping_tx.clone();
+        let ping_id = request.ping_id.clone();
+        let probe = request.probe;
+        let timeout = std::time::Duration::from_millis(request.timeout_ms);
+        tokio::spawn(async move {
+            let result = ping_peer(transport.as_ref(), &destination, probe, timeout).await;
+            let _ = ping_tx.send(PingEvent { ping_id, result });
+        });
+        Ok(())
+    }
+
+    /// Runs the sync on the transport task; each step reaches the daemon
+    /// through the propagation sync worker.
+    fn sync_propagation(&self, request: &PropagationSyncRequest) -> Result<(), std::io::Error> {
+        let node = AddressHash::new(parse_destination_hash_required(&request.node)?);
+        let transport = self.transport.clone();
+        let signer = self.local.lock().expect("local identity").signer.clone();
+        let sync_tx = self.sync_tx.clone();
+        let max_messages = request.max_messages;
+        tokio::spawn(async move {
+            sync_from_propagation_node(&transport, &signer, &node, max_messages, |update| {
+                let _ = sync_tx.send(update);
+            })
+            .await;
+        });
+        Ok(())
+    }
+
+    /// Answers from announced peers only: the RPC handler shares the runtime
+    /// thread with the transport, so the RPC loop waits out `timeout` through
+    /// [`TransportBridge::wait_for_path`] before dispatching the send. An
+    /// unknown destination still gets a path request so a retry can find it.
+    fn peer_public_key(&self, destination: &str) -> Result<Option<String>, std::io::Error> {
+        let crypto = self.peer_crypto.lock().expect("peer map").get(destination).copied();
+        Ok(crypto.map(|crypto| crypto.identity.to_hex_string()))
+    }
+
+    fn restore_peer_identity(
+        &self,
+        destination: &str,
+        public_key: &str,
+    ) -> Result<(), std::io::Error> {
+        let invalid = |message: &str| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, message.to_string())
         };
-        tokio::spawn(task.run());
+        if public_key.len() != 128 || !public_key.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            return Err(invalid("public identity must be 128 hex characters"));
+        }
+        let identity = Identity::new_from_hex_string(public_key)
+            .map_err(|_| invalid("public identity is not valid hex"))?;
+        let derives_destination = ["delivery", "propagation"].into_iter().any(|aspect| {
+            let derived =
+                SingleOutputDestination::new(identity, DestinationName::new("lxmf", aspect));
+            hex::encode(derived.desc.address_hash.as_slice()) == destination
+        });
+        if !derives_destination {
+            return Err(invalid("public identity does not match the destination hash"));
+        }
+        self.peer_crypto
+            .lock()
+            .expect("peer map")
+            .insert(destination.to_string(), PeerCrypto { identity });
         Ok(())
     }
+
+    fn forget_peers(&self, destinations: &[String]) -> Result<(), std::io::Error> {
+        let mut peers = self.peer_crypto.lock().expect("peer map");
+        for destination in destinations {
+            peers.remove(destination);
+        }
+        Ok(())
+    }
+
+    fn await_path(
+        &self,
+        destination: &str,
+        _timeout: std::time::Duration,
+    ) -> Result<bool, std::io::Error> {
+        let destination_hash = AddressHash::new(parse_destination_hash_required(destination)?);
+        if self.peer_crypto.lock().expect("peer map").contains_key(destination) {
+            return Ok(true);
+        }
+        let transport = self.transport.clone();
+        tokio::spawn(async move {
+            transport.request_path(&destination_hash, None, None).await;
+        });
+        Ok(false)
+    }
+
+    /// Resets every spawned interface carrying `name`. The reset runs on the
+    /// transport task; TCP clients redial immediately and keep queued packets,
+    /// the TCP server drops its accepted clients and UDP interfaces rebind.
+    fn reconnect_interface(&self, name: &str) -> Result<bool, std::io::Error> {
+        let addresses = self
+            .interfaces
+            .iter()
+            .filter(|(_, iface_name)| iface_name == name)
+            .map(|(address, _)| *address)
+            .collect::<Vec<_>>();
+        if addresses.is_empty() {
+            return Ok(false);
+        }
+        let iface_manager = self.transport.iface_manager();
+        tokio::spawn(async move {
+            let manager = iface_manager.lock().await;
+            for address in addresses {
+                manager.reset(&address);
+            }
+        });
+        Ok(true)
+    }
 }
 
 impl AnnounceBridge for TransportBridge {
     fn announce_now(&self) -> Result<(), std::io::Error> {
         let transport = self.transport.clone();
-        let destination = self.announce_destination.clone();
-        let app_data = self.announce_app_data.clone();
+        let destination = self.local.lock().expect("local identity").announce_destination.clone();
+        let app_data = self.announce_app_data();
         tokio::spawn(async move {
             transport.send_announce(&destination, app_data.as_deref()).await;
         });
+        if self.propagation_node.load(Ordering::Relaxed) {
+            self.announce_propagation_node(true);
+        }
+        Ok(())
+    }
+
+    fn announce_to(&self, destination: &str) -> Result<(), std::io::Error> {
+        let target = AddressHash::new(parse_destination_hash_required(destination)?);
+        let target_hex = destination.to_string();
+        let transport = self.transport.clone();
+        let announce_tx = self.announce_tx.clone();
+        let destination = self.local.lock().expect("local identity").announce_destination.clone();
+        let app_data = self.announce_app_data();
+        tokio::spawn(async move {
+            let announced =
+                transport.send_announce_to(&destination, app_data.as_deref(), &target).await;
+            let _ = announce_tx.send(AnnounceEvent::Directed { target: target_hex, announced });
+        });
+        Ok(())
+    }
+
+    /// Persists a new identity, then registers and announces its delivery
+    /// and propagation destinations. The old destinations stay registered so
+    /// replies and receipts addressed to them are still accepted. The announce
+    /// runs on its own task so a failure is reported rather than lost.
+    fn rotate_identity(&self) -> Result<IdentityRotation, std::io::Error> {
+        let retired_name =
+            hex::encode(self.local.lock().expect("local identity").delivery_source_hash);
+        let signer = rotate_identity(&self.identity_path, &retired_name)?;
+        let current = LocalIdentity::new(signer);
+        let rotation = IdentityRotation {
+            identity_hash: hex::encode(current.signer.address_hash().as_slice()),
+            delivery_destination_hash: hex::encode(current.delivery_source_hash),
+        };
+        let destination = current.announce_destination.clone();
+        let propagation_destination = current.propagation_destination.clone();
+
+        let previous = std::mem::replace(&mut *self.local.lock().expect("local identity"), current);
+        self.retired.lock().expect("retired identities").push(previous);
+
+        let transport = self.transport.clone();
+        let app_data = self.announce_app_data();
+        let propagation_app_data = if self.propagation_node.load(Ordering::Relaxed) {
+            self.propagation_app_data(true)
+        } else {
+            None
+        };
+        let announce_tx = self.announce_tx.clone();
+        let delivery_destination_hash = rotation.delivery_destination_hash.clone();
+        tokio::spawn(async move {
+            let announce = tokio::spawn(async move {
+                transport.register_destination(destination.clone()).await;
+                transport.register_destination(propagation_destination.clone()).await;
+                transport.send_announce(&destination, app_data.as_deref()).await;
+                if let Some(app_data) = propagation_app_data {
+                    transport.send_announce(&propagation_destination, Some(&app_data)).await;
+                }
+            });
+            let result = announce.await.map_err(|err| err.to_string());
+            let _ = announce_tx.send(AnnounceEvent::Rotated { delivery_destination_hash, result });
+        });
+        Ok(rotation)
+    }
+
+    /// Persists the name next to the identity before switching to it, so a
+    /// failed write leaves the current name in place. A failed announce
+    /// keeps the new name and reports `announced: false`.
+    fn set_display_name(&self, display_name: &str) -> Result<DisplayNameUpdate, std::io::Error> {
+        let normalized = publishable_display_name(display_name).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "display name must be 1-{MAX_DISPLAY_NAME_CHARS} characters without control characters"
+                ),
+            )
+        })?;
+        store_display_name(&self.identity_path, &normalized)?;
+        *self.display_name.lock().expect("display name") = Some(normalized.clone());
+        let announced = match self.announce_now() {
+            Ok(()) => true,
+            Err(err) => {
+                eprintln!("[daemon] display name announce failed: {err}");
+                false
+            }
+        };
+        Ok(DisplayNameUpdate { display_name: normalized, announced })
+    }
+
+    /// Records the propagation role. Turning it off announces `node_state`
+    /// false once so peers stop offering this node for propagation; turning
+    /// it on is announced by the caller's follow-up [`Self::announce_now`].
+    fn set_propagation_node(&self, enabled: bool) -> Result<(), std::io::Error> {
+        let was_enabled = self.propagation_node.swap(enabled, Ordering::Relaxed);
+        if was_enabled && !enabled {
+            self.announce_propagation_node(false);
+        }
+        Ok(())
+    }
+
+    fn set_propagation_stamp_cost(
+        &self,
+        cost: u32,
+        flexibility: u32,
+    ) -> Result<(), std::io::Error> {
+        *self.propagation_stamp_cost.lock().expect("propagation stamp cost") = (cost, flexibility);
         Ok(())
     }
 }
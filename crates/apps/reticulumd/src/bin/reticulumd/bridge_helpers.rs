@@ -1,3 +1,4 @@
+use rns_rpc::RpcDaemon;
 use rns_transport::delivery::strip_destination_prefix as shared_strip_destination_prefix;
 use rns_transport::transport::SendPacketTrace;
 use std::sync::OnceLock;
@@ -6,6 +7,19 @@ pub(crate) fn opportunistic_payload<'a>(payload: &'a [u8], destination: &[u8; 16
     shared_strip_destination_prefix(payload, destination)
 }
 
+/// Writes a daemon log line to stderr and keeps it for `logs_tail` readers.
+pub(crate) fn daemon_log(daemon: &RpcDaemon, line: String) {
+    eprintln!("{line}");
+    daemon.record_log_line(line);
+}
+
+pub(crate) fn now_epoch_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
 pub(crate) fn log_delivery_trace(message_id: &str, destination: &str, stage: &str, detail: &str) {
     eprintln!(
         "[delivery-trace] msg_id={} dst={} stage={} {}",
@@ -1,12 +1,19 @@
-use super::bridge_helpers::{diagnostics_enabled, payload_preview};
+use super::bridge_helpers::{daemon_log, diagnostics_enabled, payload_preview};
 use lxmf::inbound_decode::InboundPayloadMode;
 use reticulum_daemon::inbound_delivery::{
     decode_inbound_payload, decode_inbound_payload_with_diagnostics,
 };
+use reticulum_daemon::inbound_pool::{
+    DecodedInbound, DropStreak, DropStreakLog, InboundDecodePool, InboundFrame,
+};
+use reticulum_daemon::inbound_rate_limit::{InboundAdmission, InboundRateLimiter};
 use rns_rpc::RpcDaemon;
+use rns_transport::hash::AddressHash;
 use rns_transport::transport::{ReceivedPayloadMode, Transport};
 use std::rc::Rc;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
 
 fn inbound_payload_mode(mode: ReceivedPayloadMode) -> InboundPayloadMode {
     match mode {
@@ -15,54 +22,152 @@ fn inbound_payload_mode(mode: ReceivedPayloadMode) -> InboundPayloadMode {
     }
 }
 
-pub(super) fn spawn_inbound_worker(daemon: Rc<RpcDaemon>, transport: Arc<Transport>) {
+/// Decodes one frame on a pool worker. Worker threads have no daemon handle,
+/// so diagnostics go back over `log_tx` to be written to the daemon log; a run
+/// of decode failures is reported once when it starts and once when it ends.
+fn decode_frame(
+    frame: &InboundFrame,
+    failures: &Mutex<DropStreak>,
+    log_tx: &UnboundedSender<String>,
+) -> Option<rns_rpc::MessageRecord> {
+    if !diagnostics_enabled() {
+        return decode_inbound_payload(frame.destination, &frame.data, frame.mode);
+    }
+    let (record, diagnostics) =
+        decode_inbound_payload_with_diagnostics(frame.destination, &frame.data, frame.mode);
+    let streak = failures.lock().expect("decode failure streak poisoned").record(record.is_none());
+    match streak {
+        Some(DropStreakLog::Started) => {
+            let _ = log_tx.send(format!(
+                "[daemon-rx] decode-failed dst={} attempts={}",
+                hex::encode(frame.destination),
+                diagnostics.summary()
+            ));
+        }
+        Some(DropStreakLog::Ended { dropped }) => {
+            let _ = log_tx
+                .send(format!("[daemon-rx] decoding resumed after {dropped} failed frame(s)"));
+        }
+        None => {}
+    }
+    if let Some(ref decoded) = record {
+        let _ = log_tx.send(format!(
+            "[daemon-rx] decoded msg_id={} src={} dst={} title_len={} content_len={}",
+            decoded.id,
+            decoded.source,
+            decoded.destination,
+            decoded.title.len(),
+            decoded.content.len()
+        ));
+    }
+    record
+}
+
+/// Rate-limit key for inbound data: the link it arrived over, else the
+/// interface that received it.
+fn inbound_peer(link_id: Option<&AddressHash>, received_on: Option<&AddressHash>) -> String {
+    match (link_id, received_on) {
+        (Some(link_id), _) => format!("link:{}", hex::encode(link_id.as_slice())),
+        (None, Some(iface)) => format!("iface:{}", hex::encode(iface.as_slice())),
+        (None, None) => "unknown".to_string(),
+    }
+}
+
+pub(super) fn spawn_inbound_worker(
+    daemon: Rc<RpcDaemon>,
+    transport: Arc<Transport>,
+    decode_workers: usize,
+    decode_queue: usize,
+    mut rate_limiter: Option<InboundRateLimiter>,
+) {
+    let (decoded_tx, mut decoded_rx) = unbounded_channel();
+    let (log_tx, mut log_rx) = unbounded_channel::<String>();
+    let failures = Mutex::new(DropStreak::default());
+    let pool = InboundDecodePool::with_decoder(
+        decode_workers,
+        decode_queue,
+        decoded_tx,
+        Arc::new(move |frame: &InboundFrame| decode_frame(frame, &failures, &log_tx)),
+    );
+    daemon.set_inbound_pending_probe(Arc::new(pool.queue_depth_probe()));
+
+    let daemon_diagnostics = daemon.clone();
+    tokio::task::spawn_local(async move {
+        while let Some(line) = log_rx.recv().await {
+            daemon_log(&daemon_diagnostics, line);
+        }
+    });
+
+    let daemon_accept = daemon.clone();
+    tokio::task::spawn_local(async move {
+        while let Some(DecodedInbound { peer, record }) = decoded_rx.recv().await {
+            let admission = rate_limiter.as_mut().map_or(InboundAdmission::Allow, |limiter| {
+                limiter.admit(&peer, &record.source, Instant::now())
+            });
+            if let InboundAdmission::Drop { consecutive } = admission {
+                daemon_accept.metrics_record_inbound_rate_limited();
+                if consecutive == 1 {
+                    daemon_log(
+                        &daemon_accept,
+                        format!(
+                            "[daemon] inbound rate limit exceeded; throttling peer={} src={}",
+                            peer, record.source
+                        ),
+                    );
+                }
+                continue;
+            }
+            let _ = daemon_accept.accept_inbound(record);
+        }
+    });
+
     let daemon_inbound = daemon;
     let inbound_transport = transport;
     tokio::task::spawn_local(async move {
         let mut rx = inbound_transport.received_data_events();
+        let mut queue_drops = DropStreak::default();
         loop {
             if let Ok(event) = rx.recv().await {
                 let data = event.data.as_slice();
                 let destination_hex = hex::encode(event.destination.as_slice());
-                if diagnostics_enabled() {
-                    eprintln!(
+                let line = if diagnostics_enabled() {
+                    format!(
                         "[daemon-rx] dst={} len={} ratchet_used={} data_prefix={}",
                         destination_hex,
                         data.len(),
                         event.ratchet_used,
                         payload_preview(data, 16)
-                    );
+                    )
                 } else {
-                    eprintln!("[daemon] rx data len={} dst={}", data.len(), destination_hex);
-                }
+                    format!("[daemon] rx data len={} dst={}", data.len(), destination_hex)
+                };
+                daemon_log(&daemon_inbound, line);
                 let mut destination = [0u8; 16];
                 destination.copy_from_slice(event.destination.as_slice());
-                let payload_mode = inbound_payload_mode(event.payload_mode);
-                let record = if diagnostics_enabled() {
-                    let (record, diagnostics) =
-                        decode_inbound_payload_with_diagnostics(destination, data, payload_mode);
-                    if let Some(ref decoded) = record {
-                        eprintln!(
-                            "[daemon-rx] decoded msg_id={} src={} dst={} title_len={} content_len={}",
-                            decoded.id,
-                            decoded.source,
-                            decoded.destination,
-                            decoded.title.len(),
-                            decoded.content.len()
-                        );
-                    } else {
-                        eprintln!(
-                            "[daemon-rx] decode-failed dst={} attempts={}",
-                            destination_hex,
-                            diagnostics.summary()
-                        );
-                    }
-                    record
-                } else {
-                    decode_inbound_payload(destination, data, payload_mode)
+                let frame = InboundFrame {
+                    destination,
+                    data: data.to_vec(),
+                    mode: inbound_payload_mode(event.payload_mode),
+                    peer: inbound_peer(event.link_id.as_ref(), event.received_on.as_ref()),
                 };
-                if let Some(record) = record {
-                    let _ = daemon_inbound.accept_inbound(record);
+                let dropped = pool.submit(frame);
+                if dropped {
+                    daemon_inbound.metrics_record_inbound_decode_dropped();
+                }
+                match queue_drops.record(dropped) {
+                    Some(DropStreakLog::Started) => daemon_log(
+                        &daemon_inbound,
+                        "[daemon] inbound decode queue full; dropping oldest frames".to_string(),
+                    ),
+                    Some(DropStreakLog::Ended { dropped }) => daemon_log(
+                        &daemon_inbound,
+                        format!(
+                            "[daemon] inbound decode queue drained; dropped {} frame(s) (total={})",
+                            dropped,
+                            pool.dropped_count()
+                        ),
+                    ),
+                    None => {}
                 }
             }
         }
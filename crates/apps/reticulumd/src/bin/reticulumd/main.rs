@@ -9,8 +9,15 @@ mod receipt_worker;
 mod rpc_loop;
 #[cfg(test)]
 mod tests;
+mod traffic_worker;
 
 use clap::Parser;
+use reticulum_daemon::inbound_pool::{
+    DEFAULT_INBOUND_DECODE_QUEUE, DEFAULT_INBOUND_DECODE_WORKERS,
+};
+use reticulum_daemon::inbound_rate_limit::DEFAULT_INBOUND_BURST;
+use rns_rpc::rpc::DEFAULT_DEAD_LETTER_MAX_BYTES;
+use rns_transport::iface::DEFAULT_IFACE_MAX_IN_FLIGHT;
 use std::path::PathBuf;
 use tokio::task::LocalSet;
 
@@ -29,12 +36,47 @@ struct Args {
     announce_interval_secs: u64,
     #[arg(long)]
     transport: Option<String>,
+    /// Append outbound messages to this file as JSON lines instead of sending
+    /// them over a transport. Intended for tests and offline demos.
+    #[arg(long, conflicts_with = "transport")]
+    outbound_file: Option<PathBuf>,
     #[arg(long)]
     rpc_tls_cert: Option<PathBuf>,
     #[arg(long)]
     rpc_tls_key: Option<PathBuf>,
     #[arg(long)]
     rpc_tls_client_ca: Option<PathBuf>,
+    #[arg(long, default_value_t = DEFAULT_INBOUND_DECODE_WORKERS)]
+    inbound_decode_workers: usize,
+    #[arg(long, default_value_t = DEFAULT_INBOUND_DECODE_QUEUE)]
+    inbound_decode_queue: usize,
+    /// Inbound messages per second allowed from each link or receiving interface;
+    /// unset disables limiting.
+    #[arg(long)]
+    inbound_rate_per_sec: Option<f64>,
+    #[arg(long, default_value_t = DEFAULT_INBOUND_BURST)]
+    inbound_burst: u32,
+    /// Source hash exempt from inbound rate limiting; may be repeated.
+    #[arg(long = "inbound-trusted-source")]
+    inbound_trusted_sources: Vec<String>,
+    /// Packets each transport interface may have in flight before sends are paced.
+    #[arg(long, default_value_t = DEFAULT_IFACE_MAX_IN_FLIGHT)]
+    iface_max_in_flight: usize,
+    /// Persist the SDK event log next to the database so event cursors survive restarts.
+    #[arg(long)]
+    persist_events: bool,
+    /// Log messages dropped by store-and-forward expiry to a file next to the database.
+    #[arg(long)]
+    dead_letter_log: bool,
+    /// Size in bytes at which the dead-letter log is rotated to `<file>.1`.
+    #[arg(long, default_value_t = DEFAULT_DEAD_LETTER_MAX_BYTES, requires = "dead_letter_log")]
+    dead_letter_max_bytes: u64,
+    /// Record HTTP auth denials to a security audit log next to the database.
+    #[arg(long)]
+    audit_log: bool,
+    /// Also record allowed auth decisions in the security audit log.
+    #[arg(long, requires = "audit_log")]
+    audit_include_allowed: bool,
 }
 
 #[tokio::main(flavor = "current_thread")]
@@ -44,7 +86,13 @@ async fn main() {
         .run_until(async {
             let args = Args::parse();
             let context = bootstrap::bootstrap(args).await;
-            rpc_loop::run_rpc_loop(context.rpc_addr, context.daemon, context.rpc_tls).await;
+            rpc_loop::run_rpc_loop(
+                context.rpc_addr,
+                context.daemon,
+                context.rpc_tls,
+                context.bridge,
+            )
+            .await;
         })
         .await;
 }
@@ -1,4 +1,8 @@
-use super::bridge_helpers::log_delivery_trace;
+use super::bridge::{AnnounceEvent, PingEvent, StampEvent};
+use super::bridge_helpers::{daemon_log, log_delivery_trace, now_epoch_secs};
+use lxmf::inbound_decode::InboundPayloadMode;
+use reticulum_daemon::inbound_delivery::decode_inbound_payload;
+use reticulum_daemon::propagation_sync::PropagationSyncUpdate;
 use reticulum_daemon::receipt_bridge::{handle_receipt_event, ReceiptEvent};
 use rns_rpc::RpcDaemon;
 use std::rc::Rc;
@@ -25,3 +29,90 @@ pub(super) fn spawn_receipt_worker(
         }
     });
 }
+
+pub(super) fn spawn_stamp_worker(
+    daemon: Rc<RpcDaemon>,
+    mut stamp_rx: UnboundedReceiver<StampEvent>,
+) {
+    tokio::task::spawn_local(async move {
+        while let Some(event) = stamp_rx.recv().await {
+            match event {
+                StampEvent::Progress { message_id, attempts } => {
+                    daemon.record_stamp_progress(&message_id, attempts);
+                }
+                StampEvent::Finished { message_id } => daemon.finish_stamp_task(&message_id),
+            }
+        }
+    });
+}
+
+pub(super) fn spawn_ping_worker(daemon: Rc<RpcDaemon>, mut ping_rx: UnboundedReceiver<PingEvent>) {
+    tokio::task::spawn_local(async move {
+        while let Some(PingEvent { ping_id, result }) = ping_rx.recv().await {
+            daemon.record_peer_ping_result(&ping_id, result.outcome, result.rtt_ms);
+        }
+    });
+}
+
+pub(super) fn spawn_announce_outcome_worker(
+    daemon: Rc<RpcDaemon>,
+    mut announce_rx: UnboundedReceiver<AnnounceEvent>,
+) {
+    tokio::task::spawn_local(async move {
+        while let Some(event) = announce_rx.recv().await {
+            match event {
+                AnnounceEvent::Directed { target, announced } => {
+                    daemon.record_directed_announce(&target, announced);
+                }
+                AnnounceEvent::Rotated { delivery_destination_hash, result } => {
+                    daemon.record_identity_announce(&delivery_destination_hash, result);
+                }
+            }
+        }
+    });
+}
+
+pub(super) fn spawn_propagation_sync_worker(
+    daemon: Rc<RpcDaemon>,
+    mut sync_rx: UnboundedReceiver<PropagationSyncUpdate>,
+) {
+    tokio::task::spawn_local(async move {
+        while let Some(update) = sync_rx.recv().await {
+            match update {
+                PropagationSyncUpdate::State { state, state_name, progress } => {
+                    daemon.update_propagation_sync_state(|guard| {
+                        guard.sync_state = state;
+                        guard.state_name = state_name.to_string();
+                        guard.sync_progress = progress;
+                    });
+                }
+                PropagationSyncUpdate::Message(message) => {
+                    let mut destination = [0u8; 16];
+                    destination.copy_from_slice(&message[..16]);
+                    let Some(record) =
+                        decode_inbound_payload(destination, &message, InboundPayloadMode::FullWire)
+                    else {
+                        daemon_log(&daemon, "[daemon] propagated message decode failed".into());
+                        continue;
+                    };
+                    if let Err(err) = daemon.accept_inbound(record) {
+                        daemon_log(
+                            &daemon,
+                            format!("[daemon] propagated message not stored: {err}"),
+                        );
+                    }
+                }
+                PropagationSyncUpdate::Finished { state, state_name, messages_received, error } => {
+                    daemon.update_propagation_sync_state(|guard| {
+                        guard.sync_state = state;
+                        guard.state_name = state_name.to_string();
+                        guard.sync_progress = if error.is_none() { 1.0 } else { 0.0 };
+                        guard.messages_received = messages_received;
+                        guard.last_sync_completed = Some(now_epoch_secs() as i64);
+                        guard.last_sync_error = error;
+                    });
+                }
+            }
+        }
+    });
+}
@@ -1,5 +1,8 @@
 use super::bootstrap::RpcTlsConfig;
-use rns_rpc::rpc::codec;
+use super::bridge::TransportBridge;
+use super::bridge_helpers::daemon_log;
+use rns_rpc::rpc::event_stream::EventStream;
+use rns_rpc::rpc::{codec, fail_fast_path_destination, FAIL_FAST_PATH_TIMEOUT};
 use rns_rpc::{http, RpcDaemon, RpcRequest};
 use rustls::server::WebPkiClientVerifier;
 use rustls::{RootCertStore, ServerConfig};
@@ -10,6 +13,8 @@ use std::io::{self, BufReader};
 use std::net::{IpAddr, SocketAddr};
 use std::path::Path;
 use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use tokio::net::{TcpListener, TcpStream};
 use tokio_rustls::server::TlsStream;
@@ -30,24 +35,40 @@ pub(super) async fn run_rpc_loop(
     addr: SocketAddr,
     daemon: Rc<RpcDaemon>,
     tls: Option<RpcTlsConfig>,
+    bridge: Option<Arc<TransportBridge>>,
 ) {
     match tls {
-        Some(config) => run_tls_rpc_loop(addr, daemon, config).await,
-        None => run_plain_rpc_loop(addr, daemon).await,
+        Some(config) => run_tls_rpc_loop(addr, daemon, config, bridge).await,
+        None => run_plain_rpc_loop(addr, daemon, bridge).await,
     }
 }
 
-async fn run_plain_rpc_loop(addr: SocketAddr, daemon: Rc<RpcDaemon>) {
+async fn run_plain_rpc_loop(
+    addr: SocketAddr,
+    daemon: Rc<RpcDaemon>,
+    bridge: Option<Arc<TransportBridge>>,
+) {
     let listener = TcpListener::bind(addr).await.expect("bind rpc listener");
     println!("reticulumd listening on http://{}", addr);
 
     loop {
         let (stream, peer_addr) = listener.accept().await.expect("accept rpc socket");
-        handle_connection(stream, peer_addr, daemon.as_ref(), None).await;
+        tokio::task::spawn_local(handle_connection(
+            stream,
+            peer_addr,
+            daemon.clone(),
+            bridge.clone(),
+            None,
+        ));
     }
 }
 
-async fn run_tls_rpc_loop(addr: SocketAddr, daemon: Rc<RpcDaemon>, config: RpcTlsConfig) {
+async fn run_tls_rpc_loop(
+    addr: SocketAddr,
+    daemon: Rc<RpcDaemon>,
+    config: RpcTlsConfig,
+    bridge: Option<Arc<TransportBridge>>,
+) {
     let tls_server = build_tls_server_config(&config).expect("build rpc tls server config");
     let acceptor = TlsAcceptor::from(tls_server);
     let listener = TcpListener::bind(addr).await.expect("bind tls rpc listener");
@@ -58,23 +79,34 @@ async fn run_tls_rpc_loop(addr: SocketAddr, daemon: Rc<RpcDaemon>, config: RpcTl
         match acceptor.accept(stream).await {
             Ok(tls_stream) => {
                 let transport_auth = extract_transport_auth(&tls_stream);
-                handle_connection(tls_stream, peer_addr, daemon.as_ref(), Some(transport_auth))
-                    .await;
+                tokio::task::spawn_local(handle_connection(
+                    tls_stream,
+                    peer_addr,
+                    daemon.clone(),
+                    bridge.clone(),
+                    Some(transport_auth),
+                ));
             }
             Err(err) => {
-                eprintln!("[daemon] rpc tls handshake failed peer={} err={}", peer_addr, err);
+                daemon_log(
+                    &daemon,
+                    format!("[daemon] rpc tls handshake failed peer={} err={}", peer_addr, err),
+                );
             }
         }
     }
 }
 
+/// Serves one request. Connections run as local tasks so a fail-fast send
+/// waiting for a path does not hold up other clients.
 async fn handle_connection<S>(
     mut stream: S,
     peer_addr: SocketAddr,
-    daemon: &RpcDaemon,
+    daemon: Rc<RpcDaemon>,
+    bridge: Option<Arc<TransportBridge>>,
     transport_auth: Option<http::TransportAuthContext>,
 ) where
-    S: AsyncRead + AsyncWrite + Unpin,
+    S: AsyncRead + AsyncWrite + Unpin + 'static,
 {
     let mut buffer = Vec::new();
     loop {
@@ -82,7 +114,10 @@ async fn handle_connection<S>(
         let read = match stream.read(&mut chunk).await {
             Ok(read) => read,
             Err(err) => {
-                eprintln!("[daemon] rpc read error peer={} err={}", peer_addr, err);
+                daemon_log(
+                    &daemon,
+                    format!("[daemon] rpc read error peer={} err={}", peer_addr, err),
+                );
                 return;
             }
         };
@@ -109,8 +144,42 @@ async fn handle_connection<S>(
 
     let request_meta = parse_request_log_meta(&buffer);
     let started_at = std::time::Instant::now();
+    if http::is_event_stream_upgrade(&buffer) {
+        let (response, events, error_text) =
+            match http::open_event_stream(&daemon, &buffer, Some(peer_addr), transport_auth) {
+                Ok((response, events)) => (response, events, None),
+                Err(err) => {
+                    let err_text = err.to_string();
+                    let response = http::build_error_response(&format!("rpc error: {err_text}"));
+                    (response, None, Some(err_text))
+                }
+            };
+        let elapsed_ms = started_at.elapsed().as_millis() as u64;
+        emit_rpc_access_log(peer_addr, &request_meta, &response, elapsed_ms, error_text.as_deref());
+        if stream.write_all(&response).await.is_err() {
+            return;
+        }
+        match events {
+            Some(events) => {
+                tokio::task::spawn_local(pump_event_stream(
+                    stream,
+                    events,
+                    EVENT_STREAM_PING_INTERVAL,
+                ));
+            }
+            None => {
+                let _ = stream.shutdown().await;
+            }
+        }
+        return;
+    }
+    if let Some((bridge, destination)) = bridge
+        .zip(decode_rpc_request(&buffer).and_then(|request| fail_fast_path_destination(&request)))
+    {
+        bridge.wait_for_path(&destination, FAIL_FAST_PATH_TIMEOUT).await;
+    }
     let response_result = http::handle_http_request_with_transport_auth(
-        daemon,
+        &daemon,
         &buffer,
         Some(peer_addr),
         transport_auth,
@@ -128,6 +197,92 @@ async fn handle_connection<S>(
     let _ = stream.shutdown().await;
 }
 
+/// How often an idle `/events` connection is pinged. A client that has sent
+/// nothing, not even a pong, for two intervals is disconnected.
+const EVENT_STREAM_PING_INTERVAL: Duration = Duration::from_secs(30);
+/// Largest client frame accepted on `/events`; clients only send control
+/// frames, which are capped at 125 bytes anyway.
+const EVENT_STREAM_MAX_CLIENT_FRAME: usize = 4096;
+
+/// Forwards event batches as WebSocket text frames until the client goes away,
+/// closes the connection, stops answering pings, or the daemon stops
+/// publishing. Client pings are answered with pongs and a client close is
+/// echoed; client data frames are ignored.
+async fn pump_event_stream<S>(stream: S, mut events: EventStream, ping_interval: Duration)
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let (mut reader, mut writer) = tokio::io::split(stream);
+    let mut incoming = [0_u8; 512];
+    let mut pending = Vec::new();
+    let mut last_heard = tokio::time::Instant::now();
+    let mut pings =
+        tokio::time::interval_at(tokio::time::Instant::now() + ping_interval, ping_interval);
+    let close = 'pump: loop {
+        tokio::select! {
+            batch = events.next_batch() => {
+                let Some(batch) = batch else {
+                    break http::encode_websocket_close_frame(1001);
+                };
+                let frame = http::encode_websocket_text_frame(&batch);
+                if writer.write_all(&frame).await.is_err() {
+                    return;
+                }
+            }
+            read = reader.read(&mut incoming) => {
+                match read {
+                    Ok(0) | Err(_) => return,
+                    Ok(read) => pending.extend_from_slice(&incoming[..read]),
+                }
+                last_heard = tokio::time::Instant::now();
+                loop {
+                    let frame = match http::decode_websocket_client_frame(
+                        &pending,
+                        EVENT_STREAM_MAX_CLIENT_FRAME,
+                    ) {
+                        Ok(Some((frame, used))) => {
+                            pending.drain(..used);
+                            frame
+                        }
+                        Ok(None) => break,
+                        Err(error) => {
+                            break 'pump http::encode_websocket_close_frame(error.close_status())
+                        }
+                    };
+                    match frame.opcode {
+                        http::WEBSOCKET_OPCODE_PING => {
+                            let pong =
+                                http::encode_websocket_frame(http::WEBSOCKET_OPCODE_PONG, &frame.payload);
+                            if writer.write_all(&pong).await.is_err() {
+                                return;
+                            }
+                        }
+                        http::WEBSOCKET_OPCODE_CLOSE => {
+                            let status = frame.payload.get(..2).unwrap_or_default();
+                            break 'pump http::encode_websocket_frame(
+                                http::WEBSOCKET_OPCODE_CLOSE,
+                                status,
+                            );
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ = pings.tick() => {
+                if last_heard.elapsed() >= ping_interval * 2 {
+                    break http::encode_websocket_close_frame(1001);
+                }
+                let ping = http::encode_websocket_frame(http::WEBSOCKET_OPCODE_PING, b"");
+                if writer.write_all(&ping).await.is_err() {
+                    return;
+                }
+            }
+        }
+    };
+    let _ = writer.write_all(&close).await;
+    let _ = writer.shutdown().await;
+}
+
 fn parse_request_log_meta(request: &[u8]) -> RpcRequestLogMeta {
     let mut meta = RpcRequestLogMeta::default();
     let Some(header_end) = http::find_header_end(request) else {
@@ -140,18 +295,7 @@ fn parse_request_log_meta(request: &[u8]) -> RpcRequestLogMeta {
     meta.http_method = http_method.to_string();
     meta.path = path.to_string();
 
-    if http_method != "POST" || path != "/rpc" {
-        return meta;
-    }
-    let Some(content_length) = http::parse_content_length(headers) else {
-        return meta;
-    };
-    let body_start = header_end + 4;
-    if request.len() < body_start.saturating_add(content_length) {
-        return meta;
-    }
-    let body = &request[body_start..body_start + content_length];
-    let Ok(rpc_request) = codec::decode_frame::<RpcRequest>(body) else {
+    let Some(rpc_request) = decode_rpc_request(request) else {
         return meta;
     };
     meta.trace_ref = Some(format!("rpc:{}:{:016x}", rpc_request.method, rpc_request.id));
@@ -160,6 +304,18 @@ fn parse_request_log_meta(request: &[u8]) -> RpcRequestLogMeta {
     meta
 }
 
+fn decode_rpc_request(request: &[u8]) -> Option<RpcRequest> {
+    let header_end = http::find_header_end(request)?;
+    let headers = &request[..header_end];
+    if parse_http_request_line(headers)? != ("POST", "/rpc") {
+        return None;
+    }
+    let content_length = http::parse_content_length(headers)?;
+    let body_start = header_end + 4;
+    let body = request.get(body_start..body_start.checked_add(content_length)?)?;
+    codec::decode_frame::<RpcRequest>(body).ok()
+}
+
 fn parse_http_request_line(headers: &[u8]) -> Option<(&str, &str)> {
     let text = std::str::from_utf8(headers).ok()?;
     let line = text.lines().next()?;
@@ -397,9 +553,93 @@ mod rpc_loop_tests {
         assert!(meta.trace_ref.is_none());
     }
 
+    #[test]
+    fn decode_rpc_request_finds_fail_fast_sends() {
+        let rpc_body = codec::encode_frame(&RpcRequest {
+            id: 7,
+            method: "sdk_send_v2".to_string(),
+            params: Some(json!({
+                "source": "src",
+                "destination": "00112233445566778899aabbccddeeff",
+                "content": "hi",
+                "fail_fast_no_path": true
+            })),
+        })
+        .expect("encode rpc body");
+        let mut raw = format!(
+            "POST /rpc HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n",
+            rpc_body.len()
+        )
+        .into_bytes();
+        raw.extend_from_slice(&rpc_body);
+
+        let request = decode_rpc_request(&raw).expect("rpc request");
+        assert_eq!(
+            fail_fast_path_destination(&request).as_deref(),
+            Some("00112233445566778899aabbccddeeff")
+        );
+        assert!(decode_rpc_request(&raw[..raw.len() - 1]).is_none());
+    }
+
     #[test]
     fn parse_status_code_extracts_numeric_status() {
         let response = b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n";
         assert_eq!(parse_status_code(response), Some(200));
     }
+
+    fn masked_client_frame(first: u8, payload: &[u8]) -> Vec<u8> {
+        let mask = [0xA1, 0xB2, 0xC3, 0xD4];
+        let mut frame = vec![first, 0x80 | payload.len() as u8];
+        frame.extend_from_slice(&mask);
+        frame.extend(payload.iter().enumerate().map(|(index, byte)| byte ^ mask[index % 4]));
+        frame
+    }
+
+    #[tokio::test]
+    async fn event_stream_answers_pings_and_echoes_close() {
+        let daemon = RpcDaemon::test_instance();
+        let events = daemon.open_event_stream(None).expect("event stream");
+        let (server, mut client) = tokio::io::duplex(1024);
+        let client_side = async move {
+            client.write_all(&masked_client_frame(0x89, b"hey")).await.expect("ping");
+            client.write_all(&masked_client_frame(0x88, &[0x03, 0xE8])).await.expect("close");
+            let mut received = Vec::new();
+            client.read_to_end(&mut received).await.expect("read");
+            received
+        };
+        let (_, received) =
+            tokio::join!(pump_event_stream(server, events, Duration::from_secs(60)), client_side);
+        assert_eq!(received, [vec![0x8A, 3, b'h', b'e', b'y'], vec![0x88, 2, 0x03, 0xE8]].concat());
+    }
+
+    #[tokio::test]
+    async fn event_stream_closes_connections_that_stop_answering_pings() {
+        let daemon = RpcDaemon::test_instance();
+        let events = daemon.open_event_stream(None).expect("event stream");
+        let (server, mut client) = tokio::io::duplex(1024);
+        let client_side = async move {
+            let mut received = Vec::new();
+            client.read_to_end(&mut received).await.expect("read");
+            received
+        };
+        let (_, received) =
+            tokio::join!(pump_event_stream(server, events, Duration::from_millis(20)), client_side);
+        assert_eq!(received, [0x89, 0x00, 0x88, 0x02, 0x03, 0xE9]);
+    }
+
+    #[tokio::test]
+    async fn event_stream_rejects_unmasked_client_frames() {
+        let daemon = RpcDaemon::test_instance();
+        let events = daemon.open_event_stream(None).expect("event stream");
+        let (server, mut client) = tokio::io::duplex(1024);
+        let client_side = async move {
+            client.write_all(&http::encode_websocket_text_frame(b"hi")).await.expect("write");
+            let mut received = Vec::new();
+            client.read_to_end(&mut received).await.expect("read");
+            received
+        };
+        let (_, received) =
+            tokio::join!(pump_event_stream(server, events, Duration::from_secs(60)), client_side);
+        assert_eq!(received, http::encode_websocket_close_frame(1002));
+    }
 }
@@ -0,0 +1,65 @@
+use rns_rpc::{InterfaceLinkState, InterfaceTraffic, RpcDaemon};
+use rns_transport::hash::AddressHash;
+use rns_transport::iface::{self, InterfaceLinkEvent, InterfaceManager};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::Receiver;
+use tokio::sync::Mutex;
+
+/// Copies per-interface traffic counters from the transport into the daemon
+/// so `list_interfaces` can report them.
+pub(super) fn spawn_traffic_worker(
+    daemon: Rc<RpcDaemon>,
+    iface_manager: Arc<Mutex<InterfaceManager>>,
+    interfaces: Vec<(AddressHash, String)>,
+    interval: Duration,
+) {
+    tokio::task::spawn_local(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let manager = iface_manager.lock().await;
+            for (address, name) in &interfaces {
+                let Some(traffic) = manager.traffic(address) else {
+                    continue;
+                };
+                daemon.set_interface_traffic(
+                    name,
+                    InterfaceTraffic {
+                        bytes_in: traffic.bytes_in,
+                        bytes_out: traffic.bytes_out,
+                        packets_in: traffic.packets_in,
+                        packets_out: traffic.packets_out,
+                        last_activity_secs: traffic.last_activity_secs,
+                    },
+                );
+            }
+        }
+    });
+}
+
+/// Forwards the link transitions interface workers report to the daemon, so
+/// `health` and `interface_link_state` events follow real connects and
+/// disconnects. Transitions of unnamed interfaces, such as clients accepted
+/// by the TCP server, are dropped.
+pub(super) fn spawn_link_state_worker(
+    daemon: Rc<RpcDaemon>,
+    mut events: Receiver<InterfaceLinkEvent>,
+    interfaces: Vec<(AddressHash, String)>,
+) {
+    tokio::task::spawn_local(async move {
+        while let Some(event) = events.recv().await {
+            let Some((_, name)) = interfaces.iter().find(|(address, _)| *address == event.address)
+            else {
+                continue;
+            };
+            let state = match event.state {
+                iface::InterfaceLinkState::Connecting => InterfaceLinkState::Connecting,
+                iface::InterfaceLinkState::Connected => InterfaceLinkState::Connected,
+                iface::InterfaceLinkState::Disconnected => InterfaceLinkState::Disconnected,
+            };
+            daemon.set_interface_link_state(name, state, None);
+        }
+    });
+}
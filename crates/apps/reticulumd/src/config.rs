@@ -1,6 +1,9 @@
+use rns_transport::iface::tcp_client::TcpSettings;
 use serde::Deserialize;
 use std::fs;
+use std::net::IpAddr;
 use std::path::Path;
+use std::time::Duration;
 
 #[derive(Debug, Deserialize)]
 pub struct DaemonConfig {
@@ -8,6 +11,10 @@ pub struct DaemonConfig {
     pub interfaces: Vec<InterfaceConfig>,
 }
 
+pub const DEFAULT_UDP_MULTICAST_PORT: u16 = 29716;
+pub const DEFAULT_UDP_MULTICAST_TTL: u32 = 1;
+pub const MIN_TCP_CONNECT_TIMEOUT_MS: u64 = 100;
+
 #[derive(Debug, Deserialize)]
 pub struct InterfaceConfig {
     #[serde(rename = "type")]
@@ -16,6 +23,70 @@ pub struct InterfaceConfig {
     pub host: Option<String>,
     pub port: Option<u16>,
     pub name: Option<String>,
+    #[serde(default)]
+    pub group_addr: Option<String>,
+    #[serde(default)]
+    pub ttl: Option<u32>,
+    #[serde(default)]
+    pub connect_timeout_ms: Option<u64>,
+    #[serde(default)]
+    pub keepalive_secs: Option<u64>,
+    #[serde(default)]
+    pub reconnect_backoff_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UdpMulticastSettings {
+    pub group_addr: IpAddr,
+    pub port: u16,
+    pub ttl: u32,
+}
+
+impl InterfaceConfig {
+    /// Validated settings for a `udp_multicast` interface. The port defaults to
+    /// `DEFAULT_UDP_MULTICAST_PORT`, the TTL to `DEFAULT_UDP_MULTICAST_TTL` and
+    /// is clamped to 1..=255.
+    pub fn udp_multicast_settings(&self) -> Result<UdpMulticastSettings, String> {
+        let raw = self.group_addr.as_deref().map(str::trim).unwrap_or_default();
+        if raw.is_empty() {
+            return Err("udp_multicast interface requires group_addr".to_string());
+        }
+        let group_addr = raw
+            .parse::<IpAddr>()
+            .map_err(|_| format!("udp_multicast group_addr '{raw}' is not an IP address"))?;
+        if !group_addr.is_multicast() {
+            return Err(format!("udp_multicast group_addr '{raw}' is not a multicast address"));
+        }
+        Ok(UdpMulticastSettings {
+            group_addr,
+            port: self.port.unwrap_or(DEFAULT_UDP_MULTICAST_PORT),
+            ttl: self.ttl.unwrap_or(DEFAULT_UDP_MULTICAST_TTL).clamp(1, 255),
+        })
+    }
+
+    /// Validated socket tuning for a TCP interface. Unset fields stay unset so
+    /// the transport keeps its defaults; set values must be nonzero and the
+    /// connect timeout at least `MIN_TCP_CONNECT_TIMEOUT_MS`.
+    pub fn tcp_settings(&self) -> Result<TcpSettings, String> {
+        if let Some(ms) = self.connect_timeout_ms {
+            if ms < MIN_TCP_CONNECT_TIMEOUT_MS {
+                return Err(format!(
+                    "connect_timeout_ms {ms} is below the {MIN_TCP_CONNECT_TIMEOUT_MS}ms minimum"
+                ));
+            }
+        }
+        if self.keepalive_secs == Some(0) {
+            return Err("keepalive_secs must be greater than zero".to_string());
+        }
+        if self.reconnect_backoff_ms == Some(0) {
+            return Err("reconnect_backoff_ms must be greater than zero".to_string());
+        }
+        Ok(TcpSettings {
+            connect_timeout: self.connect_timeout_ms.map(Duration::from_millis),
+            keepalive: self.keepalive_secs.map(Duration::from_secs),
+            reconnect_backoff: self.reconnect_backoff_ms.map(Duration::from_millis),
+        })
+    }
 }
 
 impl DaemonConfig {
@@ -36,6 +107,22 @@ impl DaemonConfig {
             .collect()
     }
 
+    /// Listeners spawned in addition to the `--transport` server. Their TCP
+    /// settings apply to every client they accept.
+    pub fn enabled_tcp_servers(&self) -> Vec<&InterfaceConfig> {
+        self.interfaces
+            .iter()
+            .filter(|iface| iface.enabled.unwrap_or(false) && iface.kind == "tcp_server")
+            .collect()
+    }
+
+    pub fn enabled_udp_multicast(&self) -> Vec<&InterfaceConfig> {
+        self.interfaces
+            .iter()
+            .filter(|iface| iface.enabled.unwrap_or(false) && iface.kind == "udp_multicast")
+            .collect()
+    }
+
     pub fn tcp_client_endpoints(&self) -> Vec<(String, u16)> {
         self.enabled_tcp_clients()
             .iter()
@@ -1,7 +1,7 @@
 use std::fs;
 use std::io;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use rand_core::OsRng;
@@ -24,6 +24,86 @@ pub fn load_or_create_identity(path: &Path) -> io::Result<PrivateIdentity> {
     Ok(identity)
 }
 
+/// Replaces the identity stored at `path` with a new one. The previous key,
+/// if any, is filed under [`retired_identity_dir`] as `retired_name` so
+/// traffic addressed to the old destination can still be signed and
+/// recovered, however many rotations later.
+pub fn rotate_identity(path: &Path, retired_name: &str) -> io::Result<PrivateIdentity> {
+    match fs::read(path) {
+        Ok(bytes) => write_identity_file(&retired_identity_dir(path).join(retired_name), &bytes)?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+
+    let identity = PrivateIdentity::new_from_rand(OsRng);
+    write_identity_file(path, &identity.to_private_key_bytes())?;
+    Ok(identity)
+}
+
+pub fn retired_identity_dir(path: &Path) -> PathBuf {
+    let mut retired = path.as_os_str().to_owned();
+    retired.push(".retired");
+    PathBuf::from(retired)
+}
+
+/// A key filed by [`rotate_identity`]. `identity` is an error when the file
+/// exists but can no longer be read, so callers still learn the name it was
+/// retired under.
+pub struct RetiredIdentity {
+    pub name: String,
+    pub identity: io::Result<PrivateIdentity>,
+}
+
+/// Lists every key retired next to `path`, ordered by name.
+pub fn load_retired_identities(path: &Path) -> io::Result<Vec<RetiredIdentity>> {
+    let entries = match fs::read_dir(retired_identity_dir(path)) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut retired = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        if name.contains(".tmp-") {
+            continue;
+        }
+        let identity = fs::read(entry.path()).and_then(|bytes| {
+            PrivateIdentity::from_private_key_bytes(&bytes).map_err(|err| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("invalid identity: {err:?}"))
+            })
+        });
+        retired.push(RetiredIdentity { name, identity });
+    }
+    retired.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(retired)
+}
+
+/// Where the display name set at runtime is kept, next to the identity at
+/// `path`.
+pub fn display_name_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".display_name");
+    PathBuf::from(name)
+}
+
+/// Reads the display name stored by [`store_display_name`], if any.
+pub fn load_display_name(path: &Path) -> io::Result<Option<String>> {
+    match fs::read_to_string(display_name_path(path)) {
+        Ok(name) => Ok(Some(name)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Persists `display_name` for the identity at `path`, replacing any
+/// previous one, so it survives a restart.
+pub fn store_display_name(path: &Path, display_name: &str) -> io::Result<()> {
+    write_identity_file(&display_name_path(path), display_name.as_bytes())
+}
+
 fn write_identity_file(path: &Path, key_bytes: &[u8]) -> io::Result<()> {
     if let Some(parent) = path.parent() {
         if !parent.as_os_str().is_empty() {
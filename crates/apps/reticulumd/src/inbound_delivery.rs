@@ -1,5 +1,5 @@
 use lxmf::inbound_decode::{decode_inbound_message, InboundPayloadMode};
-use rns_rpc::MessageRecord;
+use rns_rpc::{reply_to_from_fields, MessageRecord};
 
 use crate::lxmf_bridge::rmpv_to_json;
 
@@ -61,6 +61,7 @@ fn decode_inbound_payload_mode(
     mode: InboundPayloadMode,
 ) -> Result<MessageRecord, lxmf::LxmfError> {
     let message = decode_inbound_message(destination, payload, mode)?;
+    let fields = message.fields.as_ref().and_then(rmpv_to_json);
     Ok(MessageRecord {
         id: message.id,
         source: hex::encode(message.source),
@@ -69,7 +70,8 @@ fn decode_inbound_payload_mode(
         content: message.content,
         timestamp: message.timestamp,
         direction: "in".into(),
-        fields: message.fields.as_ref().and_then(rmpv_to_json),
+        reply_to: reply_to_from_fields(fields.as_ref()),
+        fields,
         receipt_status: None,
     })
 }
@@ -117,4 +119,30 @@ mod tests {
         assert_eq!(record.timestamp, 1_770_000_000_i64);
         assert_eq!(record.direction, "in");
     }
+
+    #[test]
+    fn decode_inbound_payload_reads_reply_to_from_thread_field() {
+        let destination = [0x44; 16];
+        let payload = rmp_serde::to_vec(&rmpv::Value::Array(vec![
+            rmpv::Value::from(1_770_000_000_i64),
+            rmpv::Value::from(""),
+            rmpv::Value::from("a reply"),
+            rmpv::Value::Map(vec![(rmpv::Value::from(8), rmpv::Value::from("parent-id"))]),
+        ]))
+        .expect("payload encoding");
+        let mut wire = Vec::new();
+        wire.extend_from_slice(&destination);
+        wire.extend_from_slice(&[0x55; 16]);
+        wire.extend_from_slice(&[0x66; 64]);
+        wire.extend_from_slice(&payload);
+
+        let (record, _) = decode_inbound_payload_with_diagnostics(
+            destination,
+            &wire,
+            InboundPayloadMode::FullWire,
+        );
+        let record = record.expect("decoded record");
+        assert_eq!(record.reply_to.as_deref(), Some("parent-id"));
+        assert_eq!(record.fields.expect("fields")["8"], "parent-id");
+    }
 }
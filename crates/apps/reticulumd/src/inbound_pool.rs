@@ -0,0 +1,313 @@
+use crate::inbound_delivery::decode_inbound_payload;
+use lxmf::inbound_decode::InboundPayloadMode;
+use rns_rpc::MessageRecord;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::JoinHandle;
+use tokio::sync::mpsc::UnboundedSender;
+
+pub const DEFAULT_INBOUND_DECODE_WORKERS: usize = 2;
+pub const DEFAULT_INBOUND_DECODE_QUEUE: usize = 256;
+
+#[derive(Debug, Clone)]
+pub struct InboundFrame {
+    pub destination: [u8; 16],
+    pub data: Vec<u8>,
+    pub mode: InboundPayloadMode,
+    /// Who handed us the frame, as far as the transport can tell: the link it
+    /// arrived over, or else the interface it was received on. Unlike the
+    /// message's source hash, the sender cannot choose it.
+    pub peer: String,
+}
+
+/// A decoded message together with the [`InboundFrame::peer`] it came from.
+#[derive(Debug, Clone)]
+pub struct DecodedInbound {
+    pub peer: String,
+    pub record: MessageRecord,
+}
+
+/// What a caller should log after feeding one outcome to a [`DropStreak`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropStreakLog {
+    /// The first drop of a new run.
+    Started,
+    /// A run of drops just ended after `dropped` frames.
+    Ended { dropped: u64 },
+}
+
+/// Counts consecutive drops so a busy path logs the first drop of a run and
+/// one summary when it ends, rather than a line per frame.
+#[derive(Debug, Default)]
+pub struct DropStreak {
+    consecutive: u64,
+}
+
+impl DropStreak {
+    pub fn record(&mut self, dropped: bool) -> Option<DropStreakLog> {
+        if dropped {
+            self.consecutive = self.consecutive.saturating_add(1);
+            return (self.consecutive == 1).then_some(DropStreakLog::Started);
+        }
+        let dropped = std::mem::take(&mut self.consecutive);
+        (dropped > 0).then_some(DropStreakLog::Ended { dropped })
+    }
+}
+
+pub type InboundDecoder = Arc<dyn Fn(&InboundFrame) -> Option<MessageRecord> + Send + Sync>;
+
+struct PendingFrames {
+    frames: VecDeque<InboundFrame>,
+    closed: bool,
+}
+
+struct PoolShared {
+    pending: Mutex<PendingFrames>,
+    ready: Condvar,
+    capacity: usize,
+    dropped: AtomicU64,
+    decoded: AtomicU64,
+    failed: AtomicU64,
+}
+
+/// Decodes inbound frames on a fixed set of worker threads so the transport
+/// loop never blocks on decode. When the queue is full the oldest pending
+/// frame is dropped and counted.
+pub struct InboundDecodePool {
+    shared: Arc<PoolShared>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl InboundDecodePool {
+    pub fn new(
+        workers: usize,
+        queue_capacity: usize,
+        output: UnboundedSender<DecodedInbound>,
+    ) -> Self {
+        Self::with_decoder(
+            workers,
+            queue_capacity,
+            output,
+            Arc::new(|frame: &InboundFrame| {
+                decode_inbound_payload(frame.destination, &frame.data, frame.mode)
+            }),
+        )
+    }
+
+    pub fn with_decoder(
+        workers: usize,
+        queue_capacity: usize,
+        output: UnboundedSender<DecodedInbound>,
+        decoder: InboundDecoder,
+    ) -> Self {
+        let shared = Arc::new(PoolShared {
+            pending: Mutex::new(PendingFrames { frames: VecDeque::new(), closed: false }),
+            ready: Condvar::new(),
+            capacity: queue_capacity.max(1),
+            dropped: AtomicU64::new(0),
+            decoded: AtomicU64::new(0),
+            failed: AtomicU64::new(0),
+        });
+        let workers = (0..workers.max(1))
+            .map(|index| {
+                let shared = shared.clone();
+                let output = output.clone();
+                let decoder = decoder.clone();
+                std::thread::Builder::new()
+                    .name(format!("inbound-decode-{index}"))
+                    .spawn(move || run_worker(&shared, &output, decoder.as_ref()))
+                    .expect("spawn inbound decode worker")
+            })
+            .collect();
+        Self { shared, workers }
+    }
+
+    /// Queues a frame for decoding. Returns `true` when the queue was full and
+    /// the oldest pending frame was dropped to make room.
+    pub fn submit(&self, frame: InboundFrame) -> bool {
+        let dropped = {
+            let mut pending = self.shared.pending.lock().expect("inbound decode queue poisoned");
+            let dropped = if pending.frames.len() >= self.shared.capacity {
+                pending.frames.pop_front();
+                true
+            } else {
+                false
+            };
+            pending.frames.push_back(frame);
+            dropped
+        };
+        if dropped {
+            self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        self.shared.ready.notify_one();
+        dropped
+    }
+
+    pub fn queue_depth(&self) -> usize {
+        self.shared.pending.lock().expect("inbound decode queue poisoned").frames.len()
+    }
+
+    /// Returns a closure reporting the live queue depth, for callers that
+    /// outlive a borrow of the pool.
+    pub fn queue_depth_probe(&self) -> impl Fn() -> u64 + Send + Sync + 'static {
+        let shared = self.shared.clone();
+        move || shared.pending.lock().expect("inbound decode queue poisoned").frames.len() as u64
+    }
+
+    pub fn dropped_count(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+
+    pub fn decoded_count(&self) -> u64 {
+        self.shared.decoded.load(Ordering::Relaxed)
+    }
+
+    pub fn failed_count(&self) -> u64 {
+        self.shared.failed.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for InboundDecodePool {
+    fn drop(&mut self) {
+        self.shared.pending.lock().expect("inbound decode queue poisoned").closed = true;
+        self.shared.ready.notify_all();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn run_worker(
+    shared: &PoolShared,
+    output: &UnboundedSender<DecodedInbound>,
+    decoder: &(dyn Fn(&InboundFrame) -> Option<MessageRecord> + Send + Sync),
+) {
+    loop {
+        let frame = {
+            let mut pending = shared.pending.lock().expect("inbound decode queue poisoned");
+            loop {
+                if let Some(frame) = pending.frames.pop_front() {
+                    break frame;
+                }
+                if pending.closed {
+                    return;
+                }
+                pending = shared.ready.wait(pending).expect("inbound decode queue poisoned");
+            }
+        };
+        match decoder(&frame) {
+            Some(record) => {
+                shared.decoded.fetch_add(1, Ordering::Relaxed);
+                if output.send(DecodedInbound { peer: frame.peer, record }).is_err() {
+                    return;
+                }
+            }
+            None => {
+                shared.failed.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DropStreak, DropStreakLog, InboundDecodePool, InboundFrame};
+    use lxmf::inbound_decode::InboundPayloadMode;
+    use rns_rpc::MessageRecord;
+    use std::sync::mpsc;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn frame(marker: u8) -> InboundFrame {
+        InboundFrame {
+            destination: [marker; 16],
+            data: vec![marker],
+            mode: InboundPayloadMode::FullWire,
+            peer: format!("iface:{marker:02x}"),
+        }
+    }
+
+    fn record_for(frame: &InboundFrame) -> MessageRecord {
+        MessageRecord {
+            id: format!("frame-{}", frame.data[0]),
+            source: String::new(),
+            destination: hex::encode(frame.destination),
+            title: String::new(),
+            content: String::new(),
+            timestamp: 0,
+            direction: "in".into(),
+            fields: None,
+            receipt_status: None,
+            reply_to: None,
+        }
+    }
+
+    #[test]
+    fn decode_pool_processes_all_frames_under_light_load() {
+        let (tx, mut rx) = unbounded_channel();
+        let pool =
+            InboundDecodePool::with_decoder(2, 8, tx, Arc::new(|frame| Some(record_for(frame))));
+        for marker in 0..4 {
+            assert!(!pool.submit(frame(marker)));
+        }
+
+        let mut decoded =
+            (0..4).map(|_| rx.blocking_recv().expect("decoded record")).collect::<Vec<_>>();
+        decoded.sort_by(|left, right| left.record.id.cmp(&right.record.id));
+        let ids = decoded.iter().map(|item| item.record.id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["frame-0", "frame-1", "frame-2", "frame-3"]);
+        assert_eq!(decoded[2].peer, "iface:02");
+        assert_eq!(pool.dropped_count(), 0);
+        assert_eq!(pool.decoded_count(), 4);
+    }
+
+    #[test]
+    fn decode_pool_drops_oldest_frames_when_saturated() {
+        let (tx, mut rx) = unbounded_channel();
+        let (started_tx, started_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let started_tx = Mutex::new(started_tx);
+        let release_rx = Mutex::new(release_rx);
+        let pool = InboundDecodePool::with_decoder(
+            1,
+            2,
+            tx,
+            Arc::new(move |frame| {
+                if frame.data[0] == 0 {
+                    let _ = started_tx.lock().expect("started").send(());
+                    let _ = release_rx.lock().expect("release").recv();
+                }
+                Some(record_for(frame))
+            }),
+        );
+
+        pool.submit(frame(0));
+        started_rx.recv_timeout(Duration::from_secs(5)).expect("worker picked up first frame");
+        let dropped = (1..=5).filter(|marker| pool.submit(frame(*marker))).count();
+        assert_eq!(dropped, 3);
+        assert_eq!(pool.dropped_count(), 3);
+        assert_eq!(pool.queue_depth(), 2);
+        let probe = pool.queue_depth_probe();
+        assert_eq!(probe(), 2);
+
+        release_tx.send(()).expect("release worker");
+        let ids = (0..3)
+            .map(|_| rx.blocking_recv().expect("decoded record").record.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["frame-0", "frame-4", "frame-5"]);
+    }
+
+    #[test]
+    fn drop_streak_reports_first_drop_and_summary_only() {
+        let mut streak = DropStreak::default();
+        assert_eq!(streak.record(false), None);
+        assert_eq!(streak.record(true), Some(DropStreakLog::Started));
+        assert_eq!(streak.record(true), None);
+        assert_eq!(streak.record(true), None);
+        assert_eq!(streak.record(false), Some(DropStreakLog::Ended { dropped: 3 }));
+        assert_eq!(streak.record(false), None);
+        assert_eq!(streak.record(true), Some(DropStreakLog::Started));
+    }
+}
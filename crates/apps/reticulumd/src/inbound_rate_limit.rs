@@ -0,0 +1,179 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+pub const DEFAULT_INBOUND_BURST: u32 = 20;
+
+/// Buckets kept before idle (full) ones are evicted.
+const MAX_TRACKED_PEERS: usize = 4_096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InboundAdmission {
+    Allow,
+    /// The peer is over its limit. `consecutive` counts drops since the
+    /// peer last got a message through, so callers can log once per episode.
+    Drop {
+        consecutive: u64,
+    },
+}
+
+#[derive(Debug)]
+struct PeerBucket {
+    tokens: f64,
+    refilled_at: Instant,
+    consecutive_drops: u64,
+}
+
+/// Token-bucket limiter for inbound messages keyed by the peer that delivered
+/// them (see `InboundFrame::peer`), not by the source hash the message claims,
+/// which a sender can vary freely. Each peer refills at `rate_per_sec` up to
+/// `burst` tokens. Messages from trusted source hashes are never limited.
+#[derive(Debug)]
+pub struct InboundRateLimiter {
+    rate_per_sec: f64,
+    burst: f64,
+    trusted: HashSet<String>,
+    buckets: HashMap<String, PeerBucket>,
+    dropped: u64,
+}
+
+impl InboundRateLimiter {
+    pub fn new(rate_per_sec: f64, burst: u32) -> Self {
+        Self {
+            rate_per_sec: rate_per_sec.max(0.0),
+            burst: f64::from(burst.max(1)),
+            trusted: HashSet::new(),
+            buckets: HashMap::new(),
+            dropped: 0,
+        }
+    }
+
+    pub fn with_trusted<I, S>(mut self, sources: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.trusted.extend(sources.into_iter().map(|source| normalize_source(source.as_ref())));
+        self
+    }
+
+    pub fn admit(&mut self, peer: &str, source: &str, now: Instant) -> InboundAdmission {
+        if self.trusted.contains(&normalize_source(source)) {
+            return InboundAdmission::Allow;
+        }
+        if !self.buckets.contains_key(peer) && self.buckets.len() >= MAX_TRACKED_PEERS {
+            self.evict_idle(now);
+        }
+
+        let (rate_per_sec, burst) = (self.rate_per_sec, self.burst);
+        let bucket = self.buckets.entry(peer.to_string()).or_insert_with(|| PeerBucket {
+            tokens: burst,
+            refilled_at: now,
+            consecutive_drops: 0,
+        });
+        refill(bucket, rate_per_sec, burst, now);
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            bucket.consecutive_drops = 0;
+            return InboundAdmission::Allow;
+        }
+        bucket.consecutive_drops = bucket.consecutive_drops.saturating_add(1);
+        self.dropped = self.dropped.saturating_add(1);
+        InboundAdmission::Drop { consecutive: bucket.consecutive_drops }
+    }
+
+    pub fn dropped_count(&self) -> u64 {
+        self.dropped
+    }
+
+    fn evict_idle(&mut self, now: Instant) {
+        let (rate_per_sec, burst) = (self.rate_per_sec, self.burst);
+        self.buckets.retain(|_, bucket| {
+            refill(bucket, rate_per_sec, burst, now);
+            bucket.tokens < burst
+        });
+    }
+}
+
+fn refill(bucket: &mut PeerBucket, rate_per_sec: f64, burst: f64, now: Instant) {
+    let elapsed = now.saturating_duration_since(bucket.refilled_at).as_secs_f64();
+    bucket.tokens = (bucket.tokens + elapsed * rate_per_sec).min(burst);
+    bucket.refilled_at = now;
+}
+
+fn normalize_source(source: &str) -> String {
+    source.trim().to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InboundAdmission, InboundRateLimiter};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn refills_at_configured_rate_after_burst() {
+        let start = Instant::now();
+        let mut limiter = InboundRateLimiter::new(2.0, 3);
+        for _ in 0..3 {
+            assert_eq!(limiter.admit("iface:aa", "aa", start), InboundAdmission::Allow);
+        }
+        assert_eq!(
+            limiter.admit("iface:aa", "aa", start),
+            InboundAdmission::Drop { consecutive: 1 }
+        );
+        assert_eq!(
+            limiter.admit("iface:aa", "aa", start),
+            InboundAdmission::Drop { consecutive: 2 }
+        );
+
+        let later = start + Duration::from_millis(500);
+        assert_eq!(limiter.admit("iface:aa", "aa", later), InboundAdmission::Allow);
+        assert_eq!(
+            limiter.admit("iface:aa", "aa", later),
+            InboundAdmission::Drop { consecutive: 1 }
+        );
+        assert_eq!(limiter.dropped_count(), 3);
+    }
+
+    #[test]
+    fn trusted_sources_match_case_insensitively_and_are_never_limited() {
+        let now = Instant::now();
+        let mut limiter = InboundRateLimiter::new(0.0, 1).with_trusted(["ABCD"]);
+        for _ in 0..10 {
+            assert_eq!(limiter.admit("iface:aa", " abcd ", now), InboundAdmission::Allow);
+        }
+        assert_eq!(limiter.dropped_count(), 0);
+    }
+
+    #[test]
+    fn rotating_claimed_sources_share_the_peer_bucket() {
+        let now = Instant::now();
+        let mut limiter = InboundRateLimiter::new(0.0, 2);
+        let admitted = (0..10)
+            .filter(|index| {
+                limiter.admit("iface:aa", &format!("{index:032x}"), now) == InboundAdmission::Allow
+            })
+            .count();
+        assert_eq!(admitted, 2);
+        assert_eq!(limiter.admit("iface:bb", "00", now), InboundAdmission::Allow);
+    }
+
+    #[test]
+    fn flooding_source_is_throttled_while_normal_source_is_delivered() {
+        let start = Instant::now();
+        let mut limiter = InboundRateLimiter::new(1.0, 5);
+        let mut flood_allowed = 0;
+        let mut normal_allowed = 0;
+        for tick in 0..100u64 {
+            let now = start + Duration::from_millis(tick * 10);
+            if limiter.admit("link:f1", "f100d", now) == InboundAdmission::Allow {
+                flood_allowed += 1;
+            }
+            if tick % 25 == 0 && limiter.admit("link:0a", "0a0a", now) == InboundAdmission::Allow {
+                normal_allowed += 1;
+            }
+        }
+        assert_eq!(flood_allowed, 5);
+        assert_eq!(normal_allowed, 4);
+        assert_eq!(limiter.dropped_count(), 95);
+    }
+}
@@ -2,6 +2,10 @@ pub mod announce_names;
 pub mod config;
 pub mod identity_store;
 pub mod inbound_delivery;
+pub mod inbound_pool;
+pub mod inbound_rate_limit;
 pub mod lxmf_bridge;
+pub mod peer_ping;
+pub mod propagation_sync;
 pub mod receipt_bridge;
 pub mod rns_crypto;
@@ -1,8 +1,10 @@
 use lxmf::identity;
-use lxmf::message::Message;
+use lxmf::message::{Message, WireMessage};
+use lxmf::stamper::{StampGenerator, StampProgress, WORKBLOCK_EXPAND_ROUNDS};
 use lxmf::LxmfError;
 use rns_core::identity::PrivateIdentity;
 use serde_json::Value as JsonValue;
+use std::sync::atomic::AtomicBool;
 
 pub use lxmf::wire_fields::{json_to_rmpv, rmpv_to_json};
 
@@ -29,6 +31,28 @@ pub fn build_wire_message(
     message.to_wire(Some(&lxmf_signer))
 }
 
+/// Returns `Ok(None)` when `cancel` is raised before a stamp is found.
+pub fn stamp_wire_message<F>(
+    bytes: &[u8],
+    generator: &StampGenerator,
+    target_cost: u32,
+    cancel: &AtomicBool,
+    on_progress: F,
+) -> Result<Option<Vec<u8>>, LxmfError>
+where
+    F: Fn(StampProgress) + Sync,
+{
+    let material = WireMessage::unpack(bytes)?.message_id();
+    let Some(stamp) =
+        generator.generate(&material, target_cost, WORKBLOCK_EXPAND_ROUNDS, cancel, on_progress)
+    else {
+        return Ok(None);
+    };
+    let mut message = Message::from_wire(bytes)?;
+    message.set_stamp_from_bytes(&stamp);
+    message.to_wire(None).map(Some)
+}
+
 pub fn decode_wire_message(bytes: &[u8]) -> Result<Message, LxmfError> {
     Message::from_wire(bytes)
 }
@@ -0,0 +1,83 @@
+use rns_rpc::PeerPingOutcome;
+use rns_transport::delivery::await_link_activation;
+use rns_transport::destination::{DestinationDesc, DestinationName};
+use rns_transport::hash::AddressHash;
+use rns_transport::transport::Transport;
+use std::time::Duration;
+use tokio::time::Instant;
+
+const PATH_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerPingResult {
+    pub outcome: PeerPingOutcome,
+    pub rtt_ms: Option<u64>,
+}
+
+/// Transport operations used by [`ping_peer`].
+#[allow(async_fn_in_trait)]
+pub trait PingTransport {
+    async fn request_path(&self, destination: &AddressHash);
+
+    /// Whether the destination has announced and can be routed to.
+    async fn has_path(&self, destination: &AddressHash) -> bool;
+
+    /// Opens (or reuses) a link and returns its round-trip time, or `None`
+    /// when the peer does not answer within `timeout`.
+    async fn link_rtt(&self, destination: &AddressHash, timeout: Duration) -> Option<Duration>;
+}
+
+/// Requests a path to `destination` and, when `probe` is set, measures a link
+/// round trip. Both steps share the same `timeout` budget.
+pub async fn ping_peer<T: PingTransport>(
+    transport: &T,
+    destination: &AddressHash,
+    probe: bool,
+    timeout: Duration,
+) -> PeerPingResult {
+    let deadline = Instant::now() + timeout;
+    transport.request_path(destination).await;
+    while !transport.has_path(destination).await {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return PeerPingResult { outcome: PeerPingOutcome::NoPath, rtt_ms: None };
+        }
+        tokio::time::sleep(remaining.min(PATH_POLL_INTERVAL)).await;
+    }
+    if !probe {
+        return PeerPingResult { outcome: PeerPingOutcome::Reachable, rtt_ms: None };
+    }
+
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    match transport.link_rtt(destination, remaining).await {
+        Some(rtt) => PeerPingResult {
+            outcome: PeerPingOutcome::Reachable,
+            rtt_ms: Some(u64::try_from(rtt.as_millis()).unwrap_or(u64::MAX)),
+        },
+        None => PeerPingResult { outcome: PeerPingOutcome::NoResponse, rtt_ms: None },
+    }
+}
+
+impl PingTransport for Transport {
+    async fn request_path(&self, destination: &AddressHash) {
+        Transport::request_path(self, destination, None, None).await;
+    }
+
+    async fn has_path(&self, destination: &AddressHash) -> bool {
+        self.knows_destination(destination).await
+    }
+
+    async fn link_rtt(&self, destination: &AddressHash, timeout: Duration) -> Option<Duration> {
+        let identity = self.destination_identity(destination).await?;
+        let desc = DestinationDesc {
+            identity,
+            address_hash: *destination,
+            name: DestinationName::new("lxmf", "delivery"),
+        };
+        let started = Instant::now();
+        let link = self.link(desc).await;
+        await_link_activation(self, &link, timeout).await.ok()?;
+        let rtt = link.lock().await.rtt();
+        Some(if rtt.is_zero() { started.elapsed() } else { rtt })
+    }
+}
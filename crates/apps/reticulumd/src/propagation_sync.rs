@@ -0,0 +1,383 @@
+use crate::rns_crypto::decrypt_with_identity;
+use lxmf::propagation::{
+    PR_COMPLETE, PR_LINK_ESTABLISHED, PR_LINK_ESTABLISHING, PR_LINK_FAILED, PR_NO_ACCESS,
+    PR_NO_IDENTITY_RCVD, PR_NO_PATH, PR_RECEIVING, PR_REQUEST_SENT, PR_RESPONSE_RECEIVED,
+    PR_TRANSFER_FAILED,
+};
+use rns_core::identity::PrivateIdentity;
+use rns_transport::delivery::{await_link_activation, send_outcome_is_sent, send_outcome_status};
+use rns_transport::destination::link::{Link, LinkStatus};
+use rns_transport::destination::{DestinationName, SingleOutputDestination};
+use rns_transport::hash::{address_hash, AddressHash, Hash};
+use rns_transport::identity_bridge::to_transport_private_identity;
+use rns_transport::packet::{
+    ContextFlag, DestinationType, Header, HeaderType, IfacFlag, Packet, PacketContext,
+    PacketDataBuffer, PacketType, PropagationType,
+};
+use rns_transport::resource::{ResourceEvent, ResourceEventKind};
+use rns_transport::transport::{ReceivedData, Transport};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::Instant;
+
+const PATH_TIMEOUT: Duration = Duration::from_secs(8);
+const PATH_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const LINK_TIMEOUT: Duration = Duration::from_secs(15);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(45);
+
+/// A step of [`sync_from_propagation_node`], reported as it happens.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropagationSyncUpdate {
+    /// The sync entered `state`, one of the `PR_*` codes.
+    State { state: u32, state_name: &'static str, progress: f64 },
+    /// A downloaded message addressed to this node, decrypted to LXMF wire
+    /// format.
+    Message(Vec<u8>),
+    /// The sync ended in `state`; `error` is set unless it completed.
+    Finished {
+        state: u32,
+        state_name: &'static str,
+        messages_received: usize,
+        error: Option<String>,
+    },
+}
+
+struct SyncFailure {
+    state: u32,
+    state_name: &'static str,
+    error: String,
+}
+
+impl SyncFailure {
+    fn new(state: u32, state_name: &'static str, error: impl Into<String>) -> Self {
+        Self { state, state_name, error: error.into() }
+    }
+
+    fn transfer(error: impl Into<String>) -> Self {
+        Self::new(PR_TRANSFER_FAILED, "transfer_failed", error)
+    }
+}
+
+/// Downloads the messages waiting for `identity` on the propagation node
+/// `node` over a link, the way the LXMF reference router does: list the
+/// node's transient ids, fetch up to `max_messages` of them, then tell the
+/// node which ones arrived. Every step, including the final outcome, goes
+/// through `report`.
+pub async fn sync_from_propagation_node(
+    transport: &Transport,
+    identity: &PrivateIdentity,
+    node: &AddressHash,
+    max_messages: usize,
+    report: impl Fn(PropagationSyncUpdate),
+) {
+    let finished = match run_sync(transport, identity, node, max_messages, &report).await {
+        Ok(messages_received) => PropagationSyncUpdate::Finished {
+            state: PR_COMPLETE,
+            state_name: "complete",
+            messages_received,
+            error: None,
+        },
+        Err(failure) => PropagationSyncUpdate::Finished {
+            state: failure.state,
+            state_name: failure.state_name,
+            messages_received: 0,
+            error: Some(failure.error),
+        },
+    };
+    report(finished);
+}
+
+async fn run_sync(
+    transport: &Transport,
+    identity: &PrivateIdentity,
+    node: &AddressHash,
+    max_messages: usize,
+    report: &impl Fn(PropagationSyncUpdate),
+) -> Result<usize, SyncFailure> {
+    let state = |state, state_name, progress| {
+        report(PropagationSyncUpdate::State { state, state_name, progress });
+    };
+
+    let mut node_identity = transport.destination_identity(node).await;
+    if node_identity.is_none() {
+        transport.request_path(node, None, None).await;
+        let deadline = Instant::now() + PATH_TIMEOUT;
+        while node_identity.is_none() && Instant::now() < deadline {
+            tokio::time::sleep(PATH_POLL_INTERVAL).await;
+            node_identity = transport.destination_identity(node).await;
+        }
+    }
+    let node_identity = node_identity.ok_or_else(|| {
+        SyncFailure::new(PR_NO_PATH, "no_path", "No path known for propagation node")
+    })?;
+
+    state(PR_LINK_ESTABLISHING, "link_establishing", 0.2);
+    let destination =
+        SingleOutputDestination::new(node_identity, DestinationName::new("lxmf", "propagation"));
+    let link = transport.link(destination.desc).await;
+    await_link_activation(transport, &link, LINK_TIMEOUT)
+        .await
+        .map_err(|err| SyncFailure::new(PR_LINK_FAILED, "link_failed", err.to_string()))?;
+    state(PR_LINK_ESTABLISHED, "link_established", 0.35);
+
+    let link_id = *link.lock().await.id();
+    let transport_identity = to_transport_private_identity(identity);
+    let identify = link_identify_payload(&transport_identity, &link_id);
+    send_link_packet(transport, &link, PacketContext::LinkIdentify, &identify).await?;
+
+    let mut data_rx = transport.received_data_events();
+    let mut resource_rx = transport.resource_events();
+    let request = |data: rmpv::Value| {
+        let payload = link_request_payload("/get", data)
+            .map_err(|err| SyncFailure::transfer(err.to_string()))?;
+        Ok::<_, SyncFailure>(payload)
+    };
+
+    let list_request = request(rmpv::Value::Array(vec![rmpv::Value::Nil, rmpv::Value::Nil]))?;
+    let list_id = send_link_packet(transport, &link, PacketContext::Request, &list_request)
+        .await?
+        .ok_or_else(|| SyncFailure::transfer("missing propagation request id"))?;
+    state(PR_REQUEST_SENT, "request_sent", 0.5);
+    let listed = await_link_response(
+        &mut data_rx,
+        &mut resource_rx,
+        destination.desc.address_hash,
+        link_id,
+        list_id,
+    )
+    .await?;
+    let available = parse_binary_array(&listed)
+        .ok_or_else(|| SyncFailure::transfer("invalid propagation list response payload"))?;
+    state(PR_RESPONSE_RECEIVED, "response_received", 0.65);
+
+    let wants = available.into_iter().take(max_messages).collect::<Vec<_>>();
+    if wants.is_empty() {
+        return Ok(0);
+    }
+    let get_request = request(rmpv::Value::Array(vec![
+        rmpv::Value::Array(wants.into_iter().map(rmpv::Value::Binary).collect()),
+        rmpv::Value::Array(Vec::new()),
+        rmpv::Value::Nil,
+    ]))?;
+    let get_id = send_link_packet(transport, &link, PacketContext::Request, &get_request)
+        .await?
+        .ok_or_else(|| SyncFailure::transfer("missing propagation get request id"))?;
+    state(PR_REQUEST_SENT, "request_sent", 0.75);
+    let fetched = await_link_response(
+        &mut data_rx,
+        &mut resource_rx,
+        destination.desc.address_hash,
+        link_id,
+        get_id,
+    )
+    .await?;
+    let messages = parse_binary_array(&fetched)
+        .ok_or_else(|| SyncFailure::transfer("invalid propagation message response payload"))?;
+    state(PR_RECEIVING, "receiving", 0.85);
+
+    let mut haves = Vec::with_capacity(messages.len());
+    for payload in &messages {
+        haves.push(rmpv::Value::Binary(Hash::new_from_slice(payload).to_bytes().to_vec()));
+        if let Some(message) = open_propagated_message(identity, payload) {
+            report(PropagationSyncUpdate::Message(message));
+        }
+    }
+    // Tell the node what arrived so it can drop the messages; a lost
+    // acknowledgement only means they are offered again next time.
+    if let Ok(ack) = request(rmpv::Value::Array(vec![rmpv::Value::Nil, rmpv::Value::Array(haves)]))
+    {
+        let _ = send_link_packet(transport, &link, PacketContext::Request, &ack).await;
+    }
+    Ok(messages.len())
+}
+
+/// Decrypts a propagated message (`destination_hash || ciphertext`) for
+/// `identity`, returning it in LXMF wire format, or `None` when it is not
+/// addressed to `identity`.
+pub fn open_propagated_message(identity: &PrivateIdentity, payload: &[u8]) -> Option<Vec<u8>> {
+    if payload.len() <= 16 {
+        return None;
+    }
+    let (destination, ciphertext) = payload.split_at(16);
+    let salt = identity.address_hash().as_slice();
+    let plaintext = decrypt_with_identity(identity, salt, ciphertext).ok()?;
+    let mut message = Vec::with_capacity(16 + plaintext.len());
+    message.extend_from_slice(destination);
+    message.extend_from_slice(&plaintext);
+    Some(message)
+}
+
+fn link_identify_payload(
+    identity: &rns_transport::identity::PrivateIdentity,
+    link_id: &AddressHash,
+) -> Vec<u8> {
+    let mut public_key = Vec::with_capacity(64);
+    public_key.extend_from_slice(identity.as_identity().public_key.as_bytes());
+    public_key.extend_from_slice(identity.as_identity().verifying_key.as_bytes());
+
+    let mut signed_data = Vec::with_capacity(16 + public_key.len());
+    signed_data.extend_from_slice(link_id.as_slice());
+    signed_data.extend_from_slice(&public_key);
+    let signature = identity.sign(&signed_data);
+
+    let mut payload = public_key;
+    payload.extend_from_slice(&signature.to_bytes());
+    payload
+}
+
+/// Packs a link request for `path` as `[timestamp, path_hash, data]`.
+pub fn link_request_payload(path: &str, data: rmpv::Value) -> Result<Vec<u8>, std::io::Error> {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs_f64();
+    rmp_serde::to_vec(&rmpv::Value::Array(vec![
+        rmpv::Value::F64(timestamp),
+        rmpv::Value::Binary(address_hash(path.as_bytes()).to_vec()),
+        data,
+    ]))
+    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+}
+
+async fn send_link_packet(
+    transport: &Transport,
+    link: &Arc<tokio::sync::Mutex<Link>>,
+    context: PacketContext,
+    payload: &[u8],
+) -> Result<Option<[u8; 16]>, SyncFailure> {
+    let packet = {
+        let guard = link.lock().await;
+        if guard.status() != LinkStatus::Active {
+            return Err(SyncFailure::new(
+                PR_LINK_FAILED,
+                "link_failed",
+                "propagation link is not active",
+            ));
+        }
+        let mut data = PacketDataBuffer::new();
+        let cipher_len = guard
+            .encrypt(payload, data.accuire_buf_max())
+            .map_err(|_| SyncFailure::transfer("failed to encrypt link packet"))?
+            .len();
+        data.resize(cipher_len);
+        Packet {
+            header: Header {
+                ifac_flag: IfacFlag::Open,
+                header_type: HeaderType::Type1,
+                context_flag: ContextFlag::Unset,
+                propagation_type: PropagationType::Broadcast,
+                destination_type: DestinationType::Link,
+                packet_type: PacketType::Data,
+                hops: 0,
+            },
+            ifac: None,
+            destination: *guard.id(),
+            transport: None,
+            context,
+            data,
+        }
+    };
+
+    let request_id = (context == PacketContext::Request).then(|| {
+        let mut request_id = [0u8; 16];
+        request_id.copy_from_slice(&packet.hash().to_bytes()[..16]);
+        request_id
+    });
+    let outcome = transport.send_packet_with_outcome(packet).await;
+    if !send_outcome_is_sent(outcome) {
+        return Err(SyncFailure::transfer(send_outcome_status("propagation request", outcome)));
+    }
+    Ok(request_id)
+}
+
+/// Waits for the response to `request_id`, which arrives either as a link
+/// packet or, when large, as a resource on the link.
+async fn await_link_response(
+    data_rx: &mut broadcast::Receiver<ReceivedData>,
+    resource_rx: &mut broadcast::Receiver<ResourceEvent>,
+    destination: AddressHash,
+    link_id: AddressHash,
+    request_id: [u8; 16],
+) -> Result<rmpv::Value, SyncFailure> {
+    let timed_out = || SyncFailure::transfer("propagation response timed out");
+    let deadline = Instant::now() + REQUEST_TIMEOUT;
+    let response = loop {
+        let frame = tokio::select! {
+            _ = tokio::time::sleep_until(deadline) => return Err(timed_out()),
+            event = data_rx.recv() => match event {
+                Ok(event) if event.destination == destination => {
+                    parse_link_response_frame(event.data.as_slice())
+                }
+                Ok(_) | Err(RecvError::Lagged(_)) => None,
+                Err(RecvError::Closed) => return Err(timed_out()),
+            },
+            event = resource_rx.recv() => match event {
+                Ok(ResourceEvent { link_id: id, kind: ResourceEventKind::Complete(complete), .. })
+                    if id == link_id =>
+                {
+                    parse_link_response_frame(complete.data.as_slice())
+                }
+                Ok(_) | Err(RecvError::Lagged(_)) => None,
+                Err(RecvError::Closed) => return Err(timed_out()),
+            },
+        };
+        if let Some((response_id, response)) = frame {
+            if response_id == request_id {
+                break response;
+            }
+        }
+    };
+    match propagation_response_error(&response) {
+        Some((state, state_name, error)) => Err(SyncFailure::new(state, state_name, error)),
+        None => Ok(response),
+    }
+}
+
+/// Splits a link response frame into its request id and response value.
+pub fn parse_link_response_frame(bytes: &[u8]) -> Option<([u8; 16], rmpv::Value)> {
+    let rmpv::Value::Array(mut entries) = rmp_serde::from_slice::<rmpv::Value>(bytes).ok()? else {
+        return None;
+    };
+    if entries.len() != 2 {
+        return None;
+    }
+    let response = entries.pop()?;
+    let request_id = value_to_bytes(&entries[0])?.try_into().ok()?;
+    Some((request_id, response))
+}
+
+/// Maps the numeric error a propagation node answers with to its sync state,
+/// state name and message.
+pub fn propagation_response_error(
+    value: &rmpv::Value,
+) -> Option<(u32, &'static str, &'static str)> {
+    match u32::try_from(value.as_u64()?).ok()? {
+        PR_NO_PATH => Some((PR_NO_PATH, "no_path", "No path known for propagation node")),
+        PR_LINK_FAILED => Some((PR_LINK_FAILED, "link_failed", "Propagation link failed")),
+        PR_TRANSFER_FAILED => {
+            Some((PR_TRANSFER_FAILED, "transfer_failed", "Propagation transfer failed"))
+        }
+        PR_NO_IDENTITY_RCVD => {
+            Some((PR_NO_IDENTITY_RCVD, "no_identity_rcvd", "Propagation node requires identity"))
+        }
+        PR_NO_ACCESS => Some((PR_NO_ACCESS, "no_access", "Propagation node denied access")),
+        _ => None,
+    }
+}
+
+/// Reads an array of byte strings, accepting hex or raw text entries.
+pub fn parse_binary_array(value: &rmpv::Value) -> Option<Vec<Vec<u8>>> {
+    let rmpv::Value::Array(entries) = value else {
+        return None;
+    };
+    entries.iter().map(value_to_bytes).collect()
+}
+
+fn value_to_bytes(value: &rmpv::Value) -> Option<Vec<u8>> {
+    match value {
+        rmpv::Value::Binary(bytes) => Some(bytes.clone()),
+        rmpv::Value::String(text) => {
+            let text = text.as_str()?;
+            Some(hex::decode(text).unwrap_or_else(|_| text.as_bytes().to_vec()))
+        }
+        _ => None,
+    }
+}
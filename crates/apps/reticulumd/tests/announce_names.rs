@@ -1,5 +1,7 @@
 use reticulum_daemon::announce_names::{
-    encode_delivery_display_name_app_data, normalize_display_name, parse_peer_name_from_app_data,
+    encode_delivery_display_name_app_data, encode_propagation_node_app_data,
+    normalize_display_name, parse_peer_name_from_app_data, propagation_node_state_from_app_data,
+    publishable_display_name, MAX_DISPLAY_NAME_CHARS,
 };
 use rmpv::Value;
 
@@ -71,3 +73,47 @@ fn encode_delivery_display_name_round_trips() {
     assert_eq!(parsed.0, "Alice Router");
     assert_eq!(parsed.1, "delivery_app_data");
 }
+
+#[test]
+fn delivery_app_data_carries_only_name_and_stamp_cost() {
+    let app_data = encode_delivery_display_name_app_data("Relay").expect("encoded");
+    let decoded: Value = rmp_serde::from_slice(&app_data).expect("decode");
+    assert_eq!(decoded, Value::Array(vec![Value::Binary(b"Relay".to_vec()), Value::Nil]));
+}
+
+#[test]
+fn propagation_app_data_matches_legacy_layout() {
+    let app_data = encode_propagation_node_app_data(Some("Relay"), true, 1_700_000_000, 17, 2)
+        .expect("encoded");
+    assert_eq!(propagation_node_state_from_app_data(&app_data), Some(true));
+    let parsed = parse_peer_name_from_app_data(&app_data).expect("parsed");
+    assert_eq!(parsed, ("Relay".to_string(), "pn_meta"));
+
+    let Value::Array(entries) = rmp_serde::from_slice(&app_data).expect("decode") else {
+        panic!("propagation app data must be an array");
+    };
+    assert_eq!(entries.len(), 7);
+    assert_eq!(entries[0], Value::Boolean(false));
+    assert_eq!(entries[1], Value::from(1_700_000_000_u64));
+    assert_eq!(entries[3], Value::from(256_u32));
+    assert_eq!(entries[4], Value::from(256_u32 * 40));
+    assert_eq!(
+        entries[5],
+        Value::Array(vec![Value::from(17_u32), Value::from(2_u32), Value::from(18_u32)])
+    );
+
+    let stopped =
+        encode_propagation_node_app_data(None, false, 1_700_000_000, 16, 3).expect("encoded");
+    assert_eq!(propagation_node_state_from_app_data(&stopped), Some(false));
+    assert!(parse_peer_name_from_app_data(&stopped).is_none());
+    assert_eq!(propagation_node_state_from_app_data(&app_data[..1]), None);
+}
+
+#[test]
+fn publishable_display_name_rejects_names_longer_than_the_wire_limit() {
+    let limit = "n".repeat(MAX_DISPLAY_NAME_CHARS);
+    assert_eq!(publishable_display_name(&format!("  {limit} ")), Some(limit.clone()));
+    assert_eq!(publishable_display_name(&format!("{limit}x")), None);
+    assert_eq!(publishable_display_name("   "), None);
+    assert_eq!(publishable_display_name("bad\nname"), None);
+}
@@ -1,5 +1,9 @@
-use reticulum_daemon::config::{DaemonConfig, InterfaceConfig};
+use reticulum_daemon::config::{
+    DaemonConfig, InterfaceConfig, DEFAULT_UDP_MULTICAST_PORT, DEFAULT_UDP_MULTICAST_TTL,
+};
+use rns_transport::iface::tcp_client::TcpSettings;
 use std::fs;
+use std::time::Duration;
 use tempfile::NamedTempFile;
 
 #[test]
@@ -28,6 +32,11 @@ fn filters_enabled_tcp_clients() {
                 host: Some("rmap.world".into()),
                 port: Some(4242),
                 name: None,
+                group_addr: None,
+                ttl: None,
+                connect_timeout_ms: None,
+                keepalive_secs: None,
+                reconnect_backoff_ms: None,
             },
             InterfaceConfig {
                 kind: "tcp_client".into(),
@@ -35,6 +44,11 @@ fn filters_enabled_tcp_clients() {
                 host: Some("example.com".into()),
                 port: Some(1),
                 name: None,
+                group_addr: None,
+                ttl: None,
+                connect_timeout_ms: None,
+                keepalive_secs: None,
+                reconnect_backoff_ms: None,
             },
         ],
     };
@@ -60,3 +74,77 @@ interfaces = [
     assert_eq!(endpoints[0].0, "rmap.world");
     assert_eq!(endpoints[0].1, 4242);
 }
+
+#[test]
+fn validates_udp_multicast_interfaces() {
+    let input = r#"
+interfaces = [
+  { type = "udp_multicast", enabled = true, group_addr = "239.1.2.3", port = 5000, ttl = 900 },
+  { type = "udp_multicast", enabled = true, group_addr = "ff02::1" },
+  { type = "udp_multicast", enabled = true, group_addr = "10.0.0.1" },
+  { type = "udp_multicast", enabled = true, group_addr = " " },
+  { type = "udp_multicast", enabled = false, group_addr = "239.1.2.3" }
+]
+"#;
+    let cfg = DaemonConfig::from_toml(input).expect("parse");
+    let enabled = cfg.enabled_udp_multicast();
+    assert_eq!(enabled.len(), 4);
+
+    let lan = enabled[0].udp_multicast_settings().expect("ipv4 group");
+    assert_eq!((lan.group_addr.to_string(), lan.port, lan.ttl), ("239.1.2.3".into(), 5000, 255));
+
+    let link_local = enabled[1].udp_multicast_settings().expect("ipv6 group");
+    assert_eq!(link_local.port, DEFAULT_UDP_MULTICAST_PORT);
+    assert_eq!(link_local.ttl, DEFAULT_UDP_MULTICAST_TTL);
+
+    let unicast = enabled[2].udp_multicast_settings().expect_err("unicast group");
+    assert!(unicast.contains("not a multicast address"), "{unicast}");
+    let empty = enabled[3].udp_multicast_settings().expect_err("empty group");
+    assert!(empty.contains("requires group_addr"), "{empty}");
+}
+
+#[test]
+fn validates_tcp_client_socket_settings() {
+    let input = r#"
+interfaces = [
+  { type = "tcp_client", enabled = true, host = "a", port = 1, connect_timeout_ms = 2500, keepalive_secs = 30, reconnect_backoff_ms = 750 },
+  { type = "tcp_client", enabled = true, host = "b", port = 2 },
+  { type = "tcp_client", enabled = true, host = "c", port = 3, connect_timeout_ms = 99 },
+  { type = "tcp_client", enabled = true, host = "d", port = 4, keepalive_secs = 0 },
+  { type = "tcp_client", enabled = true, host = "e", port = 5, reconnect_backoff_ms = 0 }
+]
+"#;
+    let cfg = DaemonConfig::from_toml(input).expect("parse");
+    let clients = cfg.enabled_tcp_clients();
+
+    let tuned = clients[0].tcp_settings().expect("tuned");
+    assert_eq!(tuned.connect_timeout, Some(Duration::from_millis(2500)));
+    assert_eq!(tuned.keepalive, Some(Duration::from_secs(30)));
+    assert_eq!(tuned.reconnect_backoff, Some(Duration::from_millis(750)));
+    assert_eq!(clients[1].tcp_settings().expect("defaults"), TcpSettings::default());
+
+    let short = clients[2].tcp_settings().expect_err("short timeout");
+    assert!(short.contains("100ms minimum"), "{short}");
+    let keepalive = clients[3].tcp_settings().expect_err("zero keepalive");
+    assert!(keepalive.contains("keepalive_secs"), "{keepalive}");
+    let backoff = clients[4].tcp_settings().expect_err("zero backoff");
+    assert!(backoff.contains("reconnect_backoff_ms"), "{backoff}");
+}
+
+#[test]
+fn tcp_server_entries_carry_socket_settings() {
+    let input = r#"
+interfaces = [
+  { type = "tcp_server", enabled = true, host = "0.0.0.0", port = 4243, keepalive_secs = 15 },
+  { type = "tcp_server", enabled = false, host = "0.0.0.0", port = 4244 },
+  { type = "tcp_client", enabled = true, host = "a", port = 1 }
+]
+"#;
+    let cfg = DaemonConfig::from_toml(input).expect("parse");
+    let servers = cfg.enabled_tcp_servers();
+    assert_eq!(servers.len(), 1);
+    assert_eq!(servers[0].port, Some(4243));
+    let settings = servers[0].tcp_settings().expect("settings");
+    assert_eq!(settings.keepalive, Some(Duration::from_secs(15)));
+    assert_eq!(settings.connect_timeout, None);
+}
@@ -1,7 +1,10 @@
 use std::fs;
 use std::io;
 
-use reticulum_daemon::identity_store::load_or_create_identity;
+use reticulum_daemon::identity_store::{
+    display_name_path, load_display_name, load_or_create_identity, load_retired_identities,
+    retired_identity_dir, rotate_identity, store_display_name,
+};
 
 #[test]
 fn identity_persists_across_reloads() {
@@ -19,6 +22,46 @@ fn identity_persists_across_reloads() {
     );
 }
 
+#[test]
+fn identity_rotation_replaces_key_and_keeps_every_retired_key() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("identity.bin");
+
+    let original = load_or_create_identity(&path).expect("create identity");
+    let rotated = rotate_identity(&path, "aa").expect("rotate identity");
+    assert_ne!(original.to_private_key_bytes(), rotated.to_private_key_bytes());
+    let latest = rotate_identity(&path, "bb").expect("rotate again");
+
+    let reloaded = load_or_create_identity(&path).expect("load identity");
+    assert_eq!(reloaded.to_private_key_bytes(), latest.to_private_key_bytes());
+    let retired = load_retired_identities(&path).expect("retired identities");
+    let retired = retired
+        .into_iter()
+        .map(|entry| (entry.name, entry.identity.expect("readable key").to_private_key_bytes()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        retired,
+        vec![
+            ("aa".to_string(), original.to_private_key_bytes()),
+            ("bb".to_string(), rotated.to_private_key_bytes()),
+        ]
+    );
+}
+
+#[test]
+fn unreadable_retired_key_is_reported_by_name() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("identity.bin");
+    load_or_create_identity(&path).expect("create identity");
+    rotate_identity(&path, "aa").expect("rotate identity");
+    fs::write(retired_identity_dir(&path).join("aa"), b"short").expect("corrupt key");
+
+    let retired = load_retired_identities(&path).expect("retired identities");
+    assert_eq!(retired.len(), 1);
+    assert_eq!(retired[0].name, "aa");
+    assert!(retired[0].identity.is_err());
+}
+
 #[test]
 fn identity_load_returns_error_on_unreadable_existing_path() {
     let dir = tempfile::tempdir().expect("tempdir");
@@ -49,3 +92,15 @@ fn identity_file_permissions_are_private() {
     let mode = fs::metadata(&path).expect("metadata").permissions().mode() & 0o777;
     assert_eq!(mode, 0o600, "identity file mode should be 0600");
 }
+
+#[test]
+fn display_name_persists_next_to_the_identity() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("identity.bin");
+
+    assert_eq!(load_display_name(&path).expect("load"), None);
+    store_display_name(&path, "Base Camp").expect("store");
+    store_display_name(&path, "Summit").expect("replace");
+    assert_eq!(load_display_name(&path).expect("load"), Some("Summit".to_string()));
+    assert_eq!(display_name_path(&path), dir.path().join("identity.bin.display_name"));
+}
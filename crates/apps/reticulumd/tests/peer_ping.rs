@@ -0,0 +1,71 @@
+use reticulum_daemon::peer_ping::{ping_peer, PingTransport};
+use rns_rpc::PeerPingOutcome;
+use rns_transport::hash::AddressHash;
+use std::cell::Cell;
+use std::time::Duration;
+
+struct MockTransport {
+    path_after_polls: Option<u32>,
+    link_rtt: Option<Duration>,
+    path_requests: Cell<u32>,
+    polls: Cell<u32>,
+}
+
+impl MockTransport {
+    fn new(path_after_polls: Option<u32>, link_rtt: Option<Duration>) -> Self {
+        Self { path_after_polls, link_rtt, path_requests: Cell::new(0), polls: Cell::new(0) }
+    }
+}
+
+impl PingTransport for MockTransport {
+    async fn request_path(&self, _destination: &AddressHash) {
+        self.path_requests.set(self.path_requests.get() + 1);
+    }
+
+    async fn has_path(&self, _destination: &AddressHash) -> bool {
+        let polls = self.polls.get() + 1;
+        self.polls.set(polls);
+        self.path_after_polls.is_some_and(|after| polls > after)
+    }
+
+    async fn link_rtt(&self, _destination: &AddressHash, _timeout: Duration) -> Option<Duration> {
+        self.link_rtt
+    }
+}
+
+fn destination() -> AddressHash {
+    AddressHash::new([0x11; 16])
+}
+
+#[tokio::test]
+async fn ping_reports_reachable_with_link_rtt() {
+    let transport = MockTransport::new(Some(1), Some(Duration::from_millis(37)));
+    let result = ping_peer(&transport, &destination(), true, Duration::from_secs(2)).await;
+    assert_eq!(result.outcome, PeerPingOutcome::Reachable);
+    assert_eq!(result.rtt_ms, Some(37));
+    assert_eq!(transport.path_requests.get(), 1);
+}
+
+#[tokio::test]
+async fn ping_without_probe_stops_at_path_resolution() {
+    let transport = MockTransport::new(Some(0), None);
+    let result = ping_peer(&transport, &destination(), false, Duration::from_secs(2)).await;
+    assert_eq!(result.outcome, PeerPingOutcome::Reachable);
+    assert_eq!(result.rtt_ms, None);
+}
+
+#[tokio::test]
+async fn ping_reports_no_path_when_path_never_resolves() {
+    let transport = MockTransport::new(None, Some(Duration::from_millis(37)));
+    let result = ping_peer(&transport, &destination(), true, Duration::from_millis(50)).await;
+    assert_eq!(result.outcome, PeerPingOutcome::NoPath);
+    assert_eq!(result.rtt_ms, None);
+}
+
+#[tokio::test]
+async fn ping_reports_no_response_when_probe_is_unanswered() {
+    let transport = MockTransport::new(Some(0), None);
+    let result = ping_peer(&transport, &destination(), true, Duration::from_secs(2)).await;
+    assert_eq!(result.outcome, PeerPingOutcome::NoResponse);
+    assert_eq!(result.rtt_ms, None);
+}
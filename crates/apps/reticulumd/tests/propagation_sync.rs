@@ -0,0 +1,73 @@
+use lxmf::propagation::{PR_NO_ACCESS, PR_NO_IDENTITY_RCVD};
+use rand_core::OsRng;
+use reticulum_daemon::propagation_sync::{
+    link_request_payload, open_propagated_message, parse_binary_array, parse_link_response_frame,
+    propagation_response_error,
+};
+use reticulum_daemon::rns_crypto::encrypt_for_public_key;
+use rmpv::Value;
+use rns_core::identity::PrivateIdentity;
+
+#[test]
+fn open_propagated_message_decrypts_only_for_the_recipient() {
+    let recipient = PrivateIdentity::new_from_name("propagation-sync-recipient");
+    let other = PrivateIdentity::new_from_name("propagation-sync-other");
+    let destination = [0x42u8; 16];
+    let body = b"signed lxmf body";
+    let ciphertext = encrypt_for_public_key(
+        &recipient.as_identity().public_key,
+        recipient.address_hash().as_slice(),
+        body,
+        OsRng,
+    )
+    .expect("encrypt");
+    let mut payload = destination.to_vec();
+    payload.extend_from_slice(&ciphertext);
+
+    let opened = open_propagated_message(&recipient, &payload).expect("opened");
+    assert_eq!(&opened[..16], &destination);
+    assert_eq!(&opened[16..], body);
+    assert!(open_propagated_message(&other, &payload).is_none());
+    assert!(open_propagated_message(&recipient, &destination).is_none());
+}
+
+#[test]
+fn link_response_frames_carry_the_request_id() {
+    let request_id = [7u8; 16];
+    let frame = rmp_serde::to_vec(&Value::Array(vec![
+        Value::Binary(request_id.to_vec()),
+        Value::Array(vec![Value::Binary(vec![1, 2]), Value::from("0304")]),
+    ]))
+    .expect("frame");
+    let (parsed_id, response) = parse_link_response_frame(&frame).expect("parsed");
+    assert_eq!(parsed_id, request_id);
+    assert_eq!(parse_binary_array(&response), Some(vec![vec![1, 2], vec![3, 4]]));
+
+    let short_id = rmp_serde::to_vec(&Value::Array(vec![Value::Binary(vec![7; 8]), Value::Nil]))
+        .expect("frame");
+    assert!(parse_link_response_frame(&short_id).is_none());
+    assert!(parse_binary_array(&Value::Array(vec![Value::Nil])).is_none());
+}
+
+#[test]
+fn numeric_responses_map_to_sync_failures() {
+    let denied = propagation_response_error(&Value::from(PR_NO_ACCESS)).expect("denied");
+    assert_eq!((denied.0, denied.1), (PR_NO_ACCESS, "no_access"));
+    let anonymous =
+        propagation_response_error(&Value::from(PR_NO_IDENTITY_RCVD)).expect("anonymous");
+    assert_eq!(anonymous.1, "no_identity_rcvd");
+    assert!(propagation_response_error(&Value::from(0x07_u32)).is_none());
+    assert!(propagation_response_error(&Value::Array(Vec::new())).is_none());
+}
+
+#[test]
+fn link_requests_address_the_path_hash() {
+    let payload = link_request_payload("/get", Value::Nil).expect("payload");
+    let Value::Array(entries) = rmp_serde::from_slice(&payload).expect("decode") else {
+        panic!("request must be an array");
+    };
+    assert_eq!(entries.len(), 3);
+    assert!(entries[0].as_f64().is_some());
+    assert_eq!(entries[1], Value::Binary(rns_transport::hash::address_hash(b"/get").to_vec()));
+    assert_eq!(entries[2], Value::Nil);
+}
@@ -1,5 +1,6 @@
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
 use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
 
 use crate::cli::commands_contact;
 use crate::cli::commands_daemon;
@@ -12,8 +13,8 @@ use crate::cli::commands_propagation;
 use crate::cli::commands_stamp;
 use crate::cli::output::Output;
 use crate::cli::profile::{
-    load_profile_settings, profile_paths, resolve_runtime_profile_name, ProfilePaths,
-    ProfileSettings,
+    init_profile, load_profile_settings, profile_exists, profile_paths,
+    resolve_runtime_profile_name, ProfilePaths, ProfileSettings, PROFILE_PATH_ENV,
 };
 use crate::cli::rpc_client::RpcClient;
 
@@ -24,6 +25,10 @@ pub struct Cli {
     pub profile: String,
     #[arg(long)]
     pub rpc: Option<String>,
+    /// Directory holding the profile settings, contacts and reticulum config,
+    /// replacing the standard profile directory.
+    #[arg(long)]
+    pub profile_path: Option<PathBuf>,
     #[arg(long)]
     pub json: bool,
     #[arg(long)]
@@ -88,6 +93,8 @@ pub struct ContactUpsertArgs {
     pub hash: String,
     #[arg(long)]
     pub notes: Option<String>,
+    #[arg(long = "group", value_name = "NAME")]
+    pub groups: Vec<String>,
 }
 
 #[derive(Debug, Clone, Args)]
@@ -205,6 +212,12 @@ pub struct IfaceMutationArgs {
     pub host: Option<String>,
     #[arg(long)]
     pub port: Option<u16>,
+    /// Multicast group joined by a `udp_multicast` interface.
+    #[arg(long)]
+    pub group_addr: Option<String>,
+    /// Hop limit for a `udp_multicast` interface (1-255).
+    #[arg(long)]
+    pub ttl: Option<u32>,
     #[arg(long, default_value_t = true)]
     pub enabled: bool,
 }
@@ -431,6 +444,9 @@ impl RuntimeContext {
 }
 
 pub fn run_cli(cli: Cli) -> Result<()> {
+    if let Some(path) = cli.profile_path.clone() {
+        apply_profile_path(&cli, path)?;
+    }
     let output = Output::new(cli.json, cli.quiet);
     let command = cli.command.clone();
     match command {
@@ -478,6 +494,39 @@ pub fn run_cli(cli: Cli) -> Result<()> {
     }
 }
 
+/// Points profile resolution at `path` for this command. `daemon start`,
+/// `daemon restart` and `profile init` create the directory; every other
+/// command requires it to exist already.
+fn apply_profile_path(cli: &Cli, path: PathBuf) -> Result<()> {
+    let starts = matches!(
+        &cli.command,
+        Command::Daemon(DaemonCommand {
+            action: DaemonAction::Start { .. } | DaemonAction::Restart { .. }
+        })
+    );
+    let initializes = matches!(
+        &cli.command,
+        Command::Profile(ProfileCommand { action: ProfileAction::Init { .. } })
+    );
+    if !path.is_dir() {
+        if !starts && !initializes {
+            return Err(anyhow!(
+                "profile path {} does not exist; run `lxmf --profile-path {} daemon start` to create it",
+                path.display(),
+                path.display()
+            ));
+        }
+        std::fs::create_dir_all(&path)
+            .with_context(|| format!("failed to create profile path {}", path.display()))?;
+    }
+    std::env::set_var(PROFILE_PATH_ENV, &path);
+
+    if starts && !profile_exists(&cli.profile)? {
+        init_profile(&cli.profile, false, cli.rpc.clone())?;
+    }
+    Ok(())
+}
+
 fn resolve_profile_name(cli_profile: &str) -> Result<String> {
     resolve_runtime_profile_name(cli_profile)
 }
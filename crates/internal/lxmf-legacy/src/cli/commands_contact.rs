@@ -61,11 +61,17 @@ pub fn run(ctx: &RuntimeContext, command: &ContactCommand) -> Result<()> {
             }
         }
         ContactAction::Import { path, replace } => {
-            let total = import_contacts_json(&ctx.profile_name, Path::new(path), *replace)?;
+            let report = import_contacts_json(&ctx.profile_name, Path::new(path), *replace)?;
+            for skipped in &report.skipped {
+                eprintln!("warning: skipped invalid contact {skipped}");
+            }
             ctx.output.emit_status(&json!({
                 "imported_path": path,
                 "replace": replace,
-                "total_contacts": total,
+                "added": report.added,
+                "updated": report.updated,
+                "skipped": report.skipped,
+                "total_contacts": report.total,
             }))
         }
         ContactAction::Export { path } => {
@@ -84,9 +90,11 @@ fn add_contact(ctx: &RuntimeContext, args: &ContactUpsertArgs) -> Result<()> {
         alias: args.alias.clone(),
         hash: args.hash.clone(),
         notes: args.notes.clone(),
+        groups: args.groups.clone(),
     })?;
     let alias = contact.alias.clone();
     let hash = contact.hash.clone();
+    let groups = contact.groups.clone();
     upsert_contact(&mut contacts, contact);
     save_contacts(&ctx.profile_name, &contacts)?;
 
@@ -94,6 +102,7 @@ fn add_contact(ctx: &RuntimeContext, args: &ContactUpsertArgs) -> Result<()> {
         "contact": {
             "alias": alias,
             "hash": hash,
+            "groups": groups,
         },
         "total_contacts": contacts.len(),
     }))
@@ -206,6 +206,7 @@ mod tests {
             cli: Cli {
                 profile: "daemon-probe-events".into(),
                 rpc: None,
+                profile_path: None,
                 json: true,
                 quiet: true,
                 command: Command::Daemon(DaemonCommand { action: DaemonAction::Probe }),
@@ -276,6 +277,7 @@ mod tests {
             cli: Cli {
                 profile: "daemon-probe-rpc".into(),
                 rpc: None,
+                profile_path: None,
                 json: true,
                 quiet: true,
                 command: Command::Daemon(DaemonCommand { action: DaemonAction::Probe }),
@@ -298,6 +300,34 @@ mod tests {
         std::env::remove_var("LXMF_CONFIG_ROOT");
     }
 
+    #[test]
+    fn profile_path_must_exist_unless_the_command_creates_it() {
+        use crate::cli::app::run_cli;
+        use crate::cli::profile::PROFILE_PATH_ENV;
+        use clap::Parser;
+
+        let _guard = TEST_LOCK.get_or_init(|| Mutex::new(())).lock().unwrap();
+        let temp = tempfile::tempdir().unwrap();
+        let profile_path = temp.path().join("isolated");
+        let cli = |args: &[&str]| {
+            let mut argv = vec!["lxmf", "--quiet", "--profile-path"];
+            argv.push(profile_path.to_str().unwrap());
+            argv.extend_from_slice(args);
+            Cli::try_parse_from(argv).unwrap()
+        };
+
+        let err = run_cli(cli(&["contact", "list"])).expect_err("missing profile path");
+        assert!(err.to_string().contains("does not exist"), "{err}");
+        assert!(!profile_path.exists());
+
+        run_cli(cli(&["profile", "init", "isolated"])).unwrap();
+        assert!(profile_path.join("profile.toml").is_file());
+        assert!(profile_path.join("reticulum.toml").is_file());
+        assert!(profile_path.join("active_profile").is_file());
+        assert_eq!(profile_paths("elsewhere").unwrap().root, profile_path);
+        std::env::remove_var(PROFILE_PATH_ENV);
+    }
+
     #[derive(Debug, Serialize)]
     struct RpcResponse {
         id: u64,
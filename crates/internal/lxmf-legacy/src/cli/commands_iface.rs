@@ -1,8 +1,9 @@
 use crate::cli::app::{IfaceAction, IfaceCommand, RuntimeContext};
 use crate::cli::daemon::DaemonSupervisor;
 use crate::cli::profile::{
-    load_reticulum_config, remove_interface, save_reticulum_config, set_interface_enabled,
-    upsert_interface, InterfaceEntry,
+    conflicting_interface, load_reticulum_config, remove_interface, save_reticulum_config,
+    set_interface_enabled, shared_endpoint_interfaces, upsert_interface, InterfaceEntry,
+    ReticulumConfig,
 };
 use anyhow::{anyhow, Result};
 use serde_json::json;
@@ -15,22 +16,24 @@ pub fn run(ctx: &RuntimeContext, command: &IfaceCommand) -> Result<()> {
             ctx.output.emit_status(&json!({
                 "profile": ctx.profile_name,
                 "config_interfaces": config.interfaces,
+                "shared_endpoint": shared_endpoint_interfaces(&config),
                 "rpc_interfaces": rpc_value,
             }))
         }
         IfaceAction::Add(args) => {
-            validate_kind(&args.kind)?;
             let mut config = load_reticulum_config(&ctx.profile_name)?;
-            upsert_interface(
-                &mut config,
-                InterfaceEntry {
-                    name: args.name.clone(),
-                    kind: args.kind.clone(),
-                    enabled: args.enabled,
-                    host: args.host.clone(),
-                    port: args.port,
-                },
-            );
+            let entry = InterfaceEntry {
+                name: args.name.clone(),
+                kind: args.kind.clone(),
+                enabled: args.enabled,
+                host: args.host.clone(),
+                port: args.port,
+                group_addr: args.group_addr.clone(),
+                ttl: args.ttl,
+            };
+            validate_entry(&entry)?;
+            ensure_endpoint_available(&config, &entry)?;
+            upsert_interface(&mut config, entry);
             save_reticulum_config(&ctx.profile_name, &config)?;
             ctx.output.emit_status(&json!({
                 "updated": args.name,
@@ -49,6 +52,12 @@ pub fn run(ctx: &RuntimeContext, command: &IfaceCommand) -> Result<()> {
         }
         IfaceAction::Enable { name } => {
             let mut config = load_reticulum_config(&ctx.profile_name)?;
+            if let Some(iface) = config.interfaces.iter().find(|iface| iface.name == *name) {
+                ensure_endpoint_available(
+                    &config,
+                    &InterfaceEntry { enabled: true, ..iface.clone() },
+                )?;
+            }
             let updated = set_interface_enabled(&mut config, name, true);
             save_reticulum_config(&ctx.profile_name, &config)?;
             ctx.output.emit_status(&json!({
@@ -102,12 +111,73 @@ fn apply_interfaces(ctx: &RuntimeContext, force_restart: bool) -> Result<()> {
     }))
 }
 
-fn validate_kind(kind: &str) -> Result<()> {
-    match kind {
+fn ensure_endpoint_available(config: &ReticulumConfig, entry: &InterfaceEntry) -> Result<()> {
+    let Some(existing) = conflicting_interface(config, entry) else {
+        return Ok(());
+    };
+    Err(anyhow!(
+        "interface '{}' would bind {}:{}, already used by enabled interface '{}'",
+        entry.name,
+        entry.host.as_deref().unwrap_or_default().trim(),
+        entry.port.unwrap_or_default(),
+        existing.name
+    ))
+}
+
+fn validate_entry(entry: &InterfaceEntry) -> Result<()> {
+    match entry.kind.as_str() {
         "tcp_client" | "tcp_server" => Ok(()),
+        "udp_multicast" => {
+            let group = entry.group_addr.as_deref().map(str::trim).unwrap_or_default();
+            if group.is_empty() || entry.port.is_none() {
+                return Err(anyhow!("udp_multicast interface requires --group-addr and --port"));
+            }
+            let is_multicast =
+                group.parse::<std::net::IpAddr>().map(|addr| addr.is_multicast()).unwrap_or(false);
+            if !is_multicast {
+                return Err(anyhow!(
+                    "udp_multicast group_addr '{}' is not a multicast address",
+                    group
+                ));
+            }
+            if entry.ttl.is_some_and(|ttl| !(1..=255).contains(&ttl)) {
+                return Err(anyhow!("udp_multicast ttl must be between 1 and 255"));
+            }
+            Ok(())
+        }
         other => Err(anyhow!(
-            "unsupported interface type '{}' (supported: tcp_client, tcp_server)",
+            "unsupported interface type '{}' (supported: tcp_client, tcp_server, udp_multicast)",
             other
         )),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::validate_entry;
+    use crate::cli::profile::InterfaceEntry;
+
+    fn multicast(group_addr: Option<&str>, port: Option<u16>) -> InterfaceEntry {
+        InterfaceEntry {
+            name: "lan".to_string(),
+            kind: "udp_multicast".to_string(),
+            enabled: true,
+            host: None,
+            port,
+            group_addr: group_addr.map(ToOwned::to_owned),
+            ttl: None,
+        }
+    }
+
+    #[test]
+    fn udp_multicast_requires_a_multicast_group_and_port() {
+        assert!(validate_entry(&multicast(Some("239.1.2.3"), Some(29716))).is_ok());
+        assert!(validate_entry(&multicast(Some("ff02::1"), Some(29716))).is_ok());
+        assert!(validate_entry(&multicast(None, Some(29716))).is_err());
+        assert!(validate_entry(&multicast(Some("239.1.2.3"), None)).is_err());
+        let err = validate_entry(&multicast(Some("10.0.0.1"), Some(29716))).expect_err("unicast");
+        assert!(err.to_string().contains("not a multicast address"));
+        let ttl = InterfaceEntry { ttl: Some(0), ..multicast(Some("239.1.2.3"), Some(29716)) };
+        assert!(validate_entry(&ttl).is_err());
+    }
+}
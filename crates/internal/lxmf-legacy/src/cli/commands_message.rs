@@ -2,11 +2,15 @@ use crate::cli::app::{
     AnnounceAction, AnnounceCommand, DeliveryMethodArg, EventsAction, EventsCommand, MessageAction,
     MessageCommand, MessageSendArgs, MessageSendCommandArgs, RuntimeContext,
 };
-use crate::cli::contacts::{load_contacts, resolve_contact_hash};
+use crate::cli::commands_peer::{extract_peers, peer_hash};
+use crate::cli::contacts::{
+    group_member_hashes, group_query, hash_prefix, load_contacts, resolve_contact_hash,
+    resolve_hash_prefix, ContactEntry,
+};
+use crate::cli::message_id::generate_message_id;
 use crate::payload_fields::{CommandEntry, WireFields};
 use anyhow::{anyhow, Context, Result};
 use serde_json::{json, Value};
-use std::time::{SystemTime, UNIX_EPOCH};
 
 pub fn run(ctx: &RuntimeContext, command: &MessageCommand) -> Result<()> {
     match &command.action {
@@ -14,7 +18,11 @@ pub fn run(ctx: &RuntimeContext, command: &MessageCommand) -> Result<()> {
         MessageAction::SendCommand(args) => send_command_message(ctx, args),
         MessageAction::List => {
             let messages = ctx.rpc.call("list_messages", None)?;
-            ctx.output.emit_status(&json!({ "messages": messages }))
+            if ctx.output.json {
+                return ctx.output.emit_status(&json!({ "messages": messages }));
+            }
+            ctx.output.emit_lines(&message_list_lines(&messages));
+            Ok(())
         }
         MessageAction::Show { id } => {
             let messages = ctx.rpc.call("list_messages", None)?;
@@ -60,10 +68,67 @@ pub fn run_events(ctx: &RuntimeContext, command: &EventsCommand) -> Result<()> {
 }
 
 fn send_message(ctx: &RuntimeContext, args: &MessageSendArgs) -> Result<()> {
+    if let Some(group) = group_query(&args.destination) {
+        return send_group_message(ctx, args, group);
+    }
     let prepared = prepare_send_params(ctx, args, None)?;
     emit_send_result(ctx, args, prepared)
 }
 
+/// Sends the same content to every contact tagged with `group`. Each member
+/// gets its own message id derived from `--id` (or a generated one). A member
+/// that fails does not stop the others; every outcome is reported and the
+/// command fails afterwards if any member did.
+fn send_group_message(ctx: &RuntimeContext, args: &MessageSendArgs, group: &str) -> Result<()> {
+    let members = group_member_hashes(&load_contacts(&ctx.profile_name)?, group);
+    if members.is_empty() {
+        return Err(anyhow!("contact group '{}' has no members", group));
+    }
+
+    let base_id = args
+        .id
+        .clone()
+        .unwrap_or_else(|| generate_message_id(ctx.profile_settings.message_id_format.as_deref()));
+    let (results, failed) = fan_out(members, |index, destination| {
+        let member_args = MessageSendArgs {
+            id: Some(format!("{base_id}-{index}")),
+            destination: destination.to_string(),
+            ..args.clone()
+        };
+        let prepared = prepare_send_params(ctx, &member_args, None)?;
+        ctx.rpc.call("send_message_v2", Some(prepared.params))
+    });
+    let total = results.len();
+
+    ctx.output.emit_status(&json!({ "group": group, "results": results }))?;
+    if failed > 0 {
+        return Err(anyhow!("{failed} of {total} sends to contact group '{group}' failed"));
+    }
+    Ok(())
+}
+
+/// Runs `send` for every member, recording `{ destination, result }` or
+/// `{ destination, error }` for each. Returns the entries and the number of
+/// failures.
+fn fan_out(
+    members: Vec<String>,
+    mut send: impl FnMut(usize, &str) -> Result<Value>,
+) -> (Vec<Value>, usize) {
+    let mut failed = 0;
+    let results = members
+        .into_iter()
+        .enumerate()
+        .map(|(index, destination)| match send(index, &destination) {
+            Ok(result) => json!({ "destination": destination, "result": result }),
+            Err(err) => {
+                failed += 1;
+                json!({ "destination": destination, "error": format!("{err:#}") })
+            }
+        })
+        .collect();
+    (results, failed)
+}
+
 fn send_command_message(ctx: &RuntimeContext, args: &MessageSendCommandArgs) -> Result<()> {
     if args.message.fields_json.is_some() {
         return Err(anyhow!(
@@ -103,9 +168,11 @@ fn prepare_send_params(
             None => resolve_runtime_identity_hash(&ctx.rpc)?,
         };
     let source = resolve_contact_hash(&contacts, &source_input).unwrap_or(source_input.clone());
-    let destination = resolve_contact_hash(&contacts, &args.destination)
-        .unwrap_or_else(|| args.destination.clone());
-    let id = args.id.clone().unwrap_or_else(generate_message_id);
+    let destination = resolve_destination(ctx, &contacts, &args.destination)?;
+    let id = args
+        .id
+        .clone()
+        .unwrap_or_else(|| generate_message_id(ctx.profile_settings.message_id_format.as_deref()));
     let mut params = json!({
         "id": id,
         "source": source,
@@ -136,6 +203,28 @@ fn prepare_send_params(
     Ok(PreparedSend { params, source, destination, source_changed })
 }
 
+/// Resolves a destination typed as an alias, a full hash, or a unique hash
+/// prefix of a contact or known peer. Exact matches always win; known peers
+/// are only fetched when the input has to be treated as a prefix.
+fn resolve_destination(
+    ctx: &RuntimeContext,
+    contacts: &[ContactEntry],
+    selector: &str,
+) -> Result<String> {
+    if let Some(hash) = resolve_contact_hash(contacts, selector) {
+        return Ok(hash);
+    }
+    let Some(prefix) = hash_prefix(selector) else {
+        return Ok(selector.to_string());
+    };
+    let peer_hashes = extract_peers(ctx.rpc.call("list_peers", None)?)
+        .iter()
+        .filter_map(|peer| peer_hash(peer).map(ToOwned::to_owned))
+        .collect::<Vec<_>>();
+    Ok(resolve_hash_prefix(contacts, &peer_hashes, &prefix)?
+        .unwrap_or_else(|| selector.to_string()))
+}
+
 fn emit_send_result(
     ctx: &RuntimeContext,
     args: &MessageSendArgs,
@@ -171,13 +260,16 @@ fn watch_messages(ctx: &RuntimeContext, interval_secs: u64) -> Result<()> {
     }
 }
 
+fn message_list(messages: &Value) -> &[Value] {
+    messages
+        .as_array()
+        .or_else(|| messages.get("messages").and_then(Value::as_array))
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
 fn find_message(messages: &Value, id: &str) -> Option<Value> {
-    let list = if let Some(list) = messages.as_array() {
-        list
-    } else {
-        messages.get("messages")?.as_array()?
-    };
-    for message in list {
+    for message in message_list(messages) {
         if message.get("id").and_then(Value::as_str) == Some(id) {
             return Some(message.clone());
         }
@@ -185,9 +277,33 @@ fn find_message(messages: &Value, id: &str) -> Option<Value> {
     None
 }
 
-fn generate_message_id() -> String {
-    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
-    format!("lxmf-{now}")
+fn message_list_lines(messages: &Value) -> Vec<String> {
+    message_list(messages)
+        .iter()
+        .map(|message| {
+            let id = message.get("id").and_then(Value::as_str).unwrap_or("-");
+            let content = message.get("content").and_then(Value::as_str).unwrap_or_default();
+            format!("{:<2} {id}  {content}", receipt_glyph(message))
+        })
+        .collect()
+}
+
+/// Outbound receipt marker: `✓✓` read, `✓` delivered, `✗` failed, `…` pending.
+/// Inbound messages get no marker.
+fn receipt_glyph(message: &Value) -> &'static str {
+    if message.get("direction").and_then(Value::as_str) != Some("out") {
+        return "";
+    }
+    let status = message.get("receipt_status").and_then(Value::as_str).unwrap_or_default();
+    if status == "read" || message.get("read_ts_ms").is_some_and(|value| !value.is_null()) {
+        "✓✓"
+    } else if status == "delivered" {
+        "✓"
+    } else if status.starts_with("failed") {
+        "✗"
+    } else {
+        "…"
+    }
 }
 
 fn delivery_method_to_string(method: DeliveryMethodArg) -> String {
@@ -268,9 +384,13 @@ fn trimmed_nonempty(value: &str) -> Option<&str> {
 
 #[cfg(test)]
 mod tests {
-    use super::{find_message, parse_command_entries, source_hash_from_status};
+    use super::{
+        fan_out, find_message, message_list_lines, parse_command_entries, receipt_glyph,
+        source_hash_from_status,
+    };
     use crate::constants::FIELD_COMMANDS;
     use crate::payload_fields::WireFields;
+    use anyhow::anyhow;
     use serde_json::json;
 
     #[test]
@@ -295,6 +415,22 @@ mod tests {
         assert_eq!(found.and_then(|v| v.get("id").cloned()), Some(json!("b")));
     }
 
+    #[test]
+    fn receipt_glyph_distinguishes_read_from_delivered() {
+        let outbound =
+            |status: &str| json!({"id": "m", "direction": "out", "receipt_status": status});
+        assert_eq!(receipt_glyph(&outbound("read")), "✓✓");
+        assert_eq!(receipt_glyph(&outbound("delivered")), "✓");
+        assert_eq!(receipt_glyph(&outbound("failed: receipt timeout")), "✗");
+        assert_eq!(receipt_glyph(&outbound("sent: direct")), "…");
+        assert_eq!(receipt_glyph(&json!({"id": "m", "direction": "in"})), "");
+
+        let lines = message_list_lines(&json!({
+            "messages": [{"id": "a", "direction": "out", "content": "hi", "read_ts_ms": 5}]
+        }));
+        assert_eq!(lines, vec!["✓✓ a  hi".to_string()]);
+    }
+
     #[test]
     fn source_hash_prefers_delivery_destination_hash() {
         let status = json!({
@@ -326,6 +462,25 @@ mod tests {
         assert_eq!(items.len(), 2);
     }
 
+    #[test]
+    fn fan_out_keeps_sending_after_a_member_fails() {
+        let members = vec!["aa".to_string(), "bb".to_string(), "cc".to_string()];
+        let mut attempted = Vec::new();
+        let (results, failed) = fan_out(members, |index, destination| {
+            attempted.push(destination.to_string());
+            if index == 1 {
+                return Err(anyhow!("no path"));
+            }
+            Ok(json!({ "message_id": format!("m-{index}") }))
+        });
+
+        assert_eq!(attempted, ["aa", "bb", "cc"]);
+        assert_eq!(failed, 1);
+        assert_eq!(results[0]["result"]["message_id"], "m-0");
+        assert_eq!(results[1], json!({ "destination": "bb", "error": "no path" }));
+        assert_eq!(results[2]["destination"], "cc");
+    }
+
     #[test]
     fn parse_command_entries_rejects_bad_specs() {
         let err = parse_command_entries(&["oops".into()], &[]).expect_err("invalid spec");
@@ -183,7 +183,7 @@ fn format_peer_candidate(peer: &Value) -> String {
     }
 }
 
-fn peer_hash(peer: &Value) -> Option<&str> {
+pub(crate) fn peer_hash(peer: &Value) -> Option<&str> {
     peer.get("peer").and_then(Value::as_str)
 }
 
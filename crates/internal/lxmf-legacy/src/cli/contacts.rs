@@ -7,6 +7,18 @@ use std::path::Path;
 
 const MAX_ALIAS_CHARS: usize = 64;
 const MAX_NOTES_CHARS: usize = 280;
+const MAX_GROUP_CHARS: usize = 32;
+const GROUP_QUERY_PREFIX: &str = "group:";
+const FULL_HASH_CHARS: usize = 32;
+pub const MIN_HASH_PREFIX_CHARS: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum HashPrefixError {
+    #[error("hash prefix '{prefix}' is too short; use at least {min} hex characters")]
+    TooShort { prefix: String, min: usize },
+    #[error("hash prefix '{prefix}' is ambiguous; candidates: {}", candidates.join(", "))]
+    Ambiguous { prefix: String, candidates: Vec<String> },
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ContactEntry {
@@ -14,6 +26,8 @@ pub struct ContactEntry {
     pub hash: String,
     #[serde(default)]
     pub notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -55,26 +69,29 @@ pub fn save_contacts(profile_name: &str, contacts: &[ContactEntry]) -> Result<()
         .with_context(|| format!("failed to write {}", paths.contacts_toml.display()))
 }
 
-pub fn import_contacts_json(profile_name: &str, src: &Path, replace: bool) -> Result<usize> {
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContactImportReport {
+    pub added: usize,
+    pub updated: usize,
+    /// One message per entry that failed validation and was left out.
+    pub skipped: Vec<String>,
+    pub total: usize,
+}
+
+pub fn import_contacts_json(
+    profile_name: &str,
+    src: &Path,
+    replace: bool,
+) -> Result<ContactImportReport> {
     let raw = fs::read_to_string(src)
         .with_context(|| format!("failed to read contact import {}", src.display()))?;
 
     let imported = parse_contacts_json(&raw)
         .with_context(|| format!("invalid contacts JSON in {}", src.display()))?;
-    let mut imported = imported.into_iter().map(validate_contact).collect::<Result<Vec<_>>>()?;
-
-    if replace {
-        dedupe_contacts(&mut imported);
-        save_contacts(profile_name, &imported)?;
-        return Ok(imported.len());
-    }
-
-    let mut merged = load_contacts(profile_name)?;
-    for contact in imported {
-        upsert_contact(&mut merged, contact);
-    }
+    let existing = if replace { Vec::new() } else { load_contacts(profile_name)? };
+    let (merged, report) = merge_imported_contacts(existing, imported);
     save_contacts(profile_name, &merged)?;
-    Ok(merged.len())
+    Ok(report)
 }
 
 pub fn export_contacts_json(profile_name: &str, dst: &Path) -> Result<usize> {
@@ -84,7 +101,7 @@ pub fn export_contacts_json(profile_name: &str, dst: &Path) -> Result<usize> {
     Ok(contacts.len())
 }
 
-pub fn upsert_contact(contacts: &mut Vec<ContactEntry>, entry: ContactEntry) {
+pub fn upsert_contact(contacts: &mut Vec<ContactEntry>, entry: ContactEntry) -> bool {
     let mut updated = false;
     if let Some(existing) =
         contacts.iter_mut().find(|contact| contact.alias.eq_ignore_ascii_case(&entry.alias))
@@ -103,6 +120,7 @@ pub fn upsert_contact(contacts: &mut Vec<ContactEntry>, entry: ContactEntry) {
     }
     dedupe_contacts(contacts);
     sort_contacts(contacts);
+    updated
 }
 
 pub fn remove_contact_by_alias(contacts: &mut Vec<ContactEntry>, alias: &str) -> bool {
@@ -126,6 +144,50 @@ pub fn resolve_contact_hash(contacts: &[ContactEntry], selector: &str) -> Option
         .map(|entry| entry.hash.clone())
 }
 
+/// Returns the selector lowercased when it is hex but shorter than a full
+/// destination hash, i.e. something that can only be resolved as a prefix.
+pub fn hash_prefix(selector: &str) -> Option<String> {
+    let selector = selector.trim();
+    let selector = selector.strip_prefix('@').unwrap_or(selector);
+    if selector.is_empty()
+        || selector.len() >= FULL_HASH_CHARS
+        || !selector.chars().all(|ch| ch.is_ascii_hexdigit())
+    {
+        return None;
+    }
+    Some(selector.to_ascii_lowercase())
+}
+
+/// Resolves a hash prefix against contact and known peer hashes. Exact alias
+/// and full-hash matches are handled by [`resolve_contact_hash`] and should be
+/// tried first; this only runs for input that cannot match exactly.
+pub fn resolve_hash_prefix(
+    contacts: &[ContactEntry],
+    peer_hashes: &[String],
+    prefix: &str,
+) -> std::result::Result<Option<String>, HashPrefixError> {
+    let prefix = prefix.trim().to_ascii_lowercase();
+    if prefix.len() < MIN_HASH_PREFIX_CHARS {
+        return Err(HashPrefixError::TooShort { prefix, min: MIN_HASH_PREFIX_CHARS });
+    }
+
+    let mut candidates = contacts
+        .iter()
+        .map(|entry| entry.hash.as_str())
+        .chain(peer_hashes.iter().map(String::as_str))
+        .map(str::to_ascii_lowercase)
+        .filter(|hash| hash.starts_with(&prefix))
+        .collect::<Vec<_>>();
+    candidates.sort();
+    candidates.dedup();
+
+    match candidates.len() {
+        0 => Ok(None),
+        1 => Ok(candidates.pop()),
+        _ => Err(HashPrefixError::Ambiguous { prefix, candidates }),
+    }
+}
+
 pub fn find_contact_by_hash<'a>(
     contacts: &'a [ContactEntry],
     hash: &str,
@@ -169,7 +231,10 @@ pub fn filter_contacts(
     query: Option<&str>,
     limit: Option<usize>,
 ) -> Vec<ContactEntry> {
-    let mut result = if let Some(query) = query.and_then(trimmed_nonempty) {
+    let query = query.and_then(trimmed_nonempty);
+    let mut result = if let Some(group) = query.and_then(group_query) {
+        contacts.iter().filter(|entry| in_group(entry, group)).cloned().collect::<Vec<_>>()
+    } else if let Some(query) = query {
         select_contacts(contacts, query, false).into_iter().cloned().collect::<Vec<_>>()
     } else {
         contacts.to_vec()
@@ -181,11 +246,26 @@ pub fn filter_contacts(
     result
 }
 
+/// Returns the group name when `selector` is a `group:<name>` query.
+pub fn group_query(selector: &str) -> Option<&str> {
+    let prefix = selector.trim().get(..GROUP_QUERY_PREFIX.len())?;
+    if !prefix.eq_ignore_ascii_case(GROUP_QUERY_PREFIX) {
+        return None;
+    }
+    trimmed_nonempty(&selector.trim()[GROUP_QUERY_PREFIX.len()..])
+}
+
+/// Destination hashes of every contact tagged with `group`, in contact order.
+pub fn group_member_hashes(contacts: &[ContactEntry], group: &str) -> Vec<String> {
+    contacts.iter().filter(|entry| in_group(entry, group)).map(|entry| entry.hash.clone()).collect()
+}
+
 pub fn validate_contact(entry: ContactEntry) -> Result<ContactEntry> {
     let alias = normalize_alias(&entry.alias)?;
     let hash = normalize_hash(&entry.hash)?;
     let notes = normalize_notes(entry.notes.as_deref())?;
-    Ok(ContactEntry { alias, hash, notes })
+    let groups = normalize_groups(&entry.groups)?;
+    Ok(ContactEntry { alias, hash, notes, groups })
 }
 
 fn normalize_alias(value: &str) -> Result<String> {
@@ -225,6 +305,55 @@ fn normalize_notes(value: Option<&str>) -> Result<Option<String>> {
     Ok(Some(trimmed.chars().take(MAX_NOTES_CHARS).collect()))
 }
 
+fn normalize_groups(values: &[String]) -> Result<Vec<String>> {
+    let mut groups: Vec<String> = Vec::with_capacity(values.len());
+    for value in values {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if trimmed.chars().any(|c| c.is_control() || c.is_whitespace()) {
+            return Err(anyhow!(
+                "contact group '{}' cannot contain whitespace or control characters",
+                trimmed
+            ));
+        }
+        let group = trimmed.chars().take(MAX_GROUP_CHARS).collect::<String>().to_ascii_lowercase();
+        if !groups.contains(&group) {
+            groups.push(group);
+        }
+    }
+    groups.sort();
+    Ok(groups)
+}
+
+fn merge_imported_contacts(
+    mut contacts: Vec<ContactEntry>,
+    imported: Vec<ContactEntry>,
+) -> (Vec<ContactEntry>, ContactImportReport) {
+    let mut report = ContactImportReport::default();
+    for (index, entry) in imported.into_iter().enumerate() {
+        let label = if entry.alias.trim().is_empty() { "<no alias>" } else { entry.alias.trim() };
+        let label = label.to_string();
+        match validate_contact(entry) {
+            Ok(entry) => {
+                if upsert_contact(&mut contacts, entry) {
+                    report.updated += 1;
+                } else {
+                    report.added += 1;
+                }
+            }
+            Err(err) => report.skipped.push(format!("entry {index} ({label}): {err}")),
+        }
+    }
+    report.total = contacts.len();
+    (contacts, report)
+}
+
+fn in_group(entry: &ContactEntry, group: &str) -> bool {
+    entry.groups.iter().any(|candidate| candidate.eq_ignore_ascii_case(group.trim()))
+}
+
 fn parse_contacts_json(raw: &str) -> Result<Vec<ContactEntry>> {
     if raw.trim_start().starts_with('{') {
         #[derive(Deserialize)]
@@ -300,7 +429,11 @@ fn trimmed_nonempty(value: &str) -> Option<&str> {
 
 #[cfg(test)]
 mod tests {
-    use super::{resolve_contact_hash, select_contacts, ContactEntry};
+    use super::{
+        filter_contacts, group_member_hashes, group_query, hash_prefix, merge_imported_contacts,
+        resolve_contact_hash, resolve_hash_prefix, select_contacts, validate_contact, ContactEntry,
+        ContactsFile, HashPrefixError,
+    };
 
     #[test]
     fn resolve_contact_hash_matches_alias_and_hash() {
@@ -309,11 +442,13 @@ mod tests {
                 alias: "Alice".into(),
                 hash: "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".into(),
                 notes: None,
+                groups: Vec::new(),
             },
             ContactEntry {
                 alias: "Bob".into(),
                 hash: "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".into(),
                 notes: None,
+                groups: Vec::new(),
             },
         ];
 
@@ -334,15 +469,184 @@ mod tests {
                 alias: "alice".into(),
                 hash: "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".into(),
                 notes: None,
+                groups: Vec::new(),
             },
             ContactEntry {
                 alias: "alice-remote".into(),
                 hash: "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".into(),
                 notes: None,
+                groups: Vec::new(),
             },
         ];
         let matches = select_contacts(&contacts, "alice", false);
         assert_eq!(matches.len(), 2);
         assert_eq!(matches[0].hash, "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
     }
+
+    #[test]
+    fn contacts_without_groups_load_with_empty_group_list() {
+        let parsed: ContactsFile = toml::from_str(
+            r#"
+[[contacts]]
+alias = "alice"
+hash = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+"#,
+        )
+        .expect("legacy contacts parse");
+        assert_eq!(parsed.contacts.len(), 1);
+        assert!(parsed.contacts[0].groups.is_empty());
+
+        let encoded = toml::to_string_pretty(&parsed).expect("encode");
+        assert!(!encoded.contains("groups"));
+    }
+
+    #[test]
+    fn contact_groups_round_trip_normalized() {
+        let contact = validate_contact(ContactEntry {
+            alias: "relay-1".into(),
+            hash: "cccccccccccccccccccccccccccccccc".into(),
+            notes: None,
+            groups: vec![" Relays ".into(), "field-team".into(), "relays".into(), "".into()],
+        })
+        .expect("valid contact");
+        assert_eq!(contact.groups, vec!["field-team".to_string(), "relays".to_string()]);
+
+        let encoded = toml::to_string_pretty(&ContactsFile { contacts: vec![contact.clone()] })
+            .expect("encode");
+        let decoded: ContactsFile = toml::from_str(&encoded).expect("decode");
+        assert_eq!(decoded.contacts, vec![contact]);
+
+        let err = validate_contact(ContactEntry {
+            alias: "bad".into(),
+            hash: "dddddddddddddddddddddddddddddddd".into(),
+            notes: None,
+            groups: vec!["field team".into()],
+        })
+        .expect_err("group with whitespace");
+        assert!(err.to_string().contains("field team"));
+    }
+
+    #[test]
+    fn group_query_narrows_contacts_to_members() {
+        let contacts = vec![
+            ContactEntry {
+                alias: "alice".into(),
+                hash: "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".into(),
+                notes: None,
+                groups: vec!["field-team".into()],
+            },
+            ContactEntry {
+                alias: "bob".into(),
+                hash: "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".into(),
+                notes: None,
+                groups: vec!["field-team".into(), "relays".into()],
+            },
+            ContactEntry {
+                alias: "group-chat".into(),
+                hash: "cccccccccccccccccccccccccccccccc".into(),
+                notes: None,
+                groups: Vec::new(),
+            },
+        ];
+
+        assert_eq!(group_query("Group:relays"), Some("relays"));
+        assert_eq!(group_query("group:"), None);
+        assert_eq!(group_query("group-chat"), None);
+
+        let relays = filter_contacts(&contacts, Some("group:relays"), None);
+        assert_eq!(relays.iter().map(|entry| entry.alias.as_str()).collect::<Vec<_>>(), ["bob"]);
+        assert_eq!(
+            group_member_hashes(&contacts, "FIELD-TEAM"),
+            vec![
+                "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string(),
+                "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".to_string()
+            ]
+        );
+        assert!(filter_contacts(&contacts, Some("group:unknown"), None).is_empty());
+    }
+
+    #[test]
+    fn import_merge_counts_added_and_updated_and_skips_invalid_entries() {
+        let existing = vec![ContactEntry {
+            alias: "alice".into(),
+            hash: "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".into(),
+            notes: None,
+            groups: Vec::new(),
+        }];
+        let imported = vec![
+            ContactEntry {
+                alias: "Alice".into(),
+                hash: "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".into(),
+                notes: Some("field lead".into()),
+                groups: Vec::new(),
+            },
+            ContactEntry {
+                alias: "bob".into(),
+                hash: "not-a-hash".into(),
+                notes: None,
+                groups: Vec::new(),
+            },
+            ContactEntry {
+                alias: "carol".into(),
+                hash: "cccccccccccccccccccccccccccccccc".into(),
+                notes: None,
+                groups: Vec::new(),
+            },
+        ];
+
+        let (merged, report) = merge_imported_contacts(existing, imported);
+        assert_eq!((report.added, report.updated, report.total), (1, 1, 2));
+        assert_eq!(report.skipped.len(), 1);
+        assert!(report.skipped[0].starts_with("entry 1 (bob)"));
+        assert_eq!(merged[0].notes.as_deref(), Some("field lead"));
+        assert_eq!(merged[1].alias, "carol");
+    }
+
+    #[test]
+    fn hash_prefix_resolves_unique_matches_and_lists_ambiguous_candidates() {
+        let contacts = vec![
+            ContactEntry {
+                alias: "deadbeef".into(),
+                hash: "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".into(),
+                notes: None,
+                groups: Vec::new(),
+            },
+            ContactEntry {
+                alias: "bob".into(),
+                hash: "deadbeef00000000000000000000000b".into(),
+                notes: None,
+                groups: Vec::new(),
+            },
+        ];
+        let peers = vec!["DEADBEEF11111111111111111111111c".to_string()];
+
+        // An alias that happens to look like a prefix wins over prefix matching.
+        assert_eq!(hash_prefix("deadbeef").as_deref(), Some("deadbeef"));
+        assert_eq!(
+            resolve_contact_hash(&contacts, "deadbeef"),
+            Some("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".into())
+        );
+
+        assert_eq!(
+            resolve_hash_prefix(&contacts, &peers, "deadbeef1"),
+            Ok(Some("deadbeef11111111111111111111111c".into()))
+        );
+        assert_eq!(resolve_hash_prefix(&contacts, &peers, "0123456789"), Ok(None));
+        assert_eq!(
+            resolve_hash_prefix(&contacts, &peers, "deadbeef"),
+            Err(HashPrefixError::Ambiguous {
+                prefix: "deadbeef".into(),
+                candidates: vec![
+                    "deadbeef00000000000000000000000b".into(),
+                    "deadbeef11111111111111111111111c".into(),
+                ],
+            })
+        );
+        assert_eq!(
+            resolve_hash_prefix(&contacts, &peers, "dead"),
+            Err(HashPrefixError::TooShort { prefix: "dead".into(), min: 8 })
+        );
+        assert_eq!(hash_prefix("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"), None);
+        assert_eq!(hash_prefix("bob"), None);
+    }
 }
@@ -0,0 +1,83 @@
+//! Message ids minted by the CLI and the embedded runtime. By default they
+//! are ULIDs: a 48-bit millisecond timestamp followed by 80 random bits,
+//! Crockford base32 encoded into 26 characters, sorting in creation order
+//! even within one millisecond. Profiles that set
+//! `message_id_format = "timestamp"` keep the older `lxmf-<millis>` ids.
+
+use rand_core::{OsRng, RngCore};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const CROCKFORD_BASE32: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const TIMESTAMP_MASK: u64 = (1 << 48) - 1;
+const RANDOM_MASK: u128 = (1 << 80) - 1;
+const ENCODED_LEN: usize = 26;
+
+/// Last `(timestamp_ms, random)` pair handed out.
+static LAST_ID: Mutex<(u64, u128)> = Mutex::new((0, 0));
+
+/// Mints an id in the profile's `message_id_format`: `ulid` (default) or
+/// `timestamp`.
+pub fn generate_message_id(format: Option<&str>) -> String {
+    let now_ms =
+        u64::try_from(SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis())
+            .unwrap_or(u64::MAX);
+    match format.map(str::trim) {
+        Some(format) if format.eq_ignore_ascii_case("timestamp") => format!("lxmf-{now_ms}"),
+        _ => {
+            let (timestamp_ms, random) = next_id_parts(now_ms);
+            encode(timestamp_ms, random)
+        }
+    }
+}
+
+fn next_id_parts(now_ms: u64) -> (u64, u128) {
+    let mut last = LAST_ID.lock().expect("message id mutex poisoned");
+    let now_ms = now_ms & TIMESTAMP_MASK;
+    let next = if now_ms > last.0 {
+        (now_ms, random_bits())
+    } else if last.1 < RANDOM_MASK {
+        // Same millisecond, or the clock stepped back: keep the previous
+        // timestamp and bump the random part so ids keep increasing.
+        (last.0, last.1 + 1)
+    } else {
+        (last.0 + 1, random_bits())
+    };
+    *last = next;
+    next
+}
+
+fn random_bits() -> u128 {
+    let mut bytes = [0_u8; 16];
+    OsRng.fill_bytes(&mut bytes[6..]);
+    // Leave headroom so same-millisecond increments rarely carry.
+    (u128::from_be_bytes(bytes) & RANDOM_MASK) >> 1
+}
+
+fn encode(timestamp_ms: u64, random: u128) -> String {
+    let value = (u128::from(timestamp_ms) << 80) | random;
+    (0..ENCODED_LEN)
+        .map(|index| {
+            let shift = 5 * (ENCODED_LEN - 1 - index);
+            char::from(CROCKFORD_BASE32[((value >> shift) & 0x1f) as usize])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_ids_are_ordered_ulids() {
+        let ids = (0..256).map(|_| generate_message_id(None)).collect::<Vec<_>>();
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(ids.iter().all(|id| id.len() == ENCODED_LEN));
+        assert_eq!(generate_message_id(Some("ulid")).len(), ENCODED_LEN);
+    }
+
+    #[test]
+    fn timestamp_format_keeps_legacy_ids() {
+        assert!(generate_message_id(Some("timestamp")).starts_with("lxmf-"));
+    }
+}
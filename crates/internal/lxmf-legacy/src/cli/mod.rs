@@ -10,6 +10,7 @@ pub mod commands_propagation;
 pub mod commands_stamp;
 pub mod contacts;
 pub mod daemon;
+pub mod message_id;
 pub mod output;
 pub mod profile;
 pub mod rpc_client;
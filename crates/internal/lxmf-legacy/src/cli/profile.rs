@@ -6,6 +6,9 @@ use std::path::{Path, PathBuf};
 use crate::helpers::{normalize_display_name as normalize_display_name_shared, DisplayNameError};
 
 const ACTIVE_PROFILE_FILE: &str = "active_profile";
+/// Environment variable holding a directory that replaces the resolved
+/// profile directory; `--profile-path` sets it for the running command.
+pub const PROFILE_PATH_ENV: &str = "LXMF_PROFILE_PATH";
 pub const MAX_DISPLAY_NAME_CHARS: usize = crate::helpers::MAX_DISPLAY_NAME_CHARS;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +22,14 @@ pub struct ProfileSettings {
     pub db_path: Option<String>,
     pub identity_path: Option<String>,
     pub transport: Option<String>,
+    /// Order in which outbound delivery tries propagation relays:
+    /// `selected` (default), `lowest_cost` or `most_recent`.
+    #[serde(default)]
+    pub propagation_selection_strategy: Option<String>,
+    /// Format of generated message ids: `ulid` (default) or `timestamp`
+    /// for the older `lxmf-<millis>` ids.
+    #[serde(default)]
+    pub message_id_format: Option<String>,
 }
 
 impl Default for ProfileSettings {
@@ -32,6 +43,8 @@ impl Default for ProfileSettings {
             db_path: None,
             identity_path: None,
             transport: None,
+            propagation_selection_strategy: None,
+            message_id_format: None,
         }
     }
 }
@@ -56,6 +69,10 @@ pub struct InterfaceEntry {
     pub enabled: bool,
     pub host: Option<String>,
     pub port: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_addr: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -79,11 +96,22 @@ pub fn profiles_root() -> Result<PathBuf> {
 }
 
 pub fn active_profile_path() -> Result<PathBuf> {
+    if let Some(root) = profile_path_override() {
+        return Ok(root.join(ACTIVE_PROFILE_FILE));
+    }
     Ok(config_root()?.join(ACTIVE_PROFILE_FILE))
 }
 
+/// Directory overriding the profile directory, if `LXMF_PROFILE_PATH` is set.
+pub fn profile_path_override() -> Option<PathBuf> {
+    std::env::var(PROFILE_PATH_ENV).ok().filter(|path| !path.trim().is_empty()).map(PathBuf::from)
+}
+
 pub fn profile_paths(name: &str) -> Result<ProfilePaths> {
-    let root = profiles_root()?.join(name);
+    let root = match profile_path_override() {
+        Some(root) => root,
+        None => profiles_root()?.join(name),
+    };
     Ok(ProfilePaths {
         profile_toml: root.join("profile.toml"),
         contacts_toml: root.join("contacts.toml"),
@@ -232,8 +260,9 @@ fn ensure_default_profile_exists() -> Result<()> {
 
 pub fn select_profile(name: &str) -> Result<()> {
     let path = active_profile_path()?;
-    let root = config_root()?;
-    fs::create_dir_all(&root).with_context(|| format!("failed to create {}", root.display()))?;
+    if let Some(root) = path.parent() {
+        fs::create_dir_all(root).with_context(|| format!("failed to create {}", root.display()))?;
+    }
     fs::write(&path, name)
         .with_context(|| format!("failed to write selected profile at {}", path.display()))
 }
@@ -313,6 +342,39 @@ pub fn upsert_interface(config: &mut ReticulumConfig, entry: InterfaceEntry) {
     config.interfaces.sort_by(|a, b| a.name.cmp(&b.name));
 }
 
+/// Returns the other enabled interface already bound to the host:port that
+/// `entry` would use. Entries are matched by name, so an interface keeping its
+/// own endpoint never conflicts with itself.
+pub fn conflicting_interface<'a>(
+    config: &'a ReticulumConfig,
+    entry: &InterfaceEntry,
+) -> Option<&'a InterfaceEntry> {
+    if !entry.enabled {
+        return None;
+    }
+    let endpoint = interface_endpoint(entry)?;
+    config.interfaces.iter().find(|iface| {
+        iface.name != entry.name
+            && iface.enabled
+            && interface_endpoint(iface).as_ref() == Some(&endpoint)
+    })
+}
+
+/// Names of enabled interfaces that share a host:port with another enabled one.
+pub fn shared_endpoint_interfaces(config: &ReticulumConfig) -> Vec<String> {
+    config
+        .interfaces
+        .iter()
+        .filter(|iface| conflicting_interface(config, iface).is_some())
+        .map(|iface| iface.name.clone())
+        .collect()
+}
+
+fn interface_endpoint(entry: &InterfaceEntry) -> Option<(String, u16)> {
+    let host = entry.host.as_deref().map(str::trim).filter(|host| !host.is_empty())?;
+    Some((host.to_ascii_lowercase(), entry.port?))
+}
+
 pub fn set_interface_enabled(config: &mut ReticulumConfig, name: &str, enabled: bool) -> bool {
     if let Some(iface) = config.interfaces.iter_mut().find(|iface| iface.name == name) {
         iface.enabled = enabled;
@@ -339,3 +401,43 @@ pub fn remove_interface(config: &mut ReticulumConfig, name: &str) -> bool {
     config.interfaces.retain(|iface| iface.name != name);
     len_before != config.interfaces.len()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        conflicting_interface, shared_endpoint_interfaces, upsert_interface, InterfaceEntry,
+        ReticulumConfig,
+    };
+
+    fn tcp(name: &str, enabled: bool, host: &str, port: u16) -> InterfaceEntry {
+        InterfaceEntry {
+            name: name.to_string(),
+            kind: "tcp_client".to_string(),
+            enabled,
+            host: Some(host.to_string()),
+            port: Some(port),
+            group_addr: None,
+            ttl: None,
+        }
+    }
+
+    #[test]
+    fn enabled_interfaces_cannot_share_an_endpoint() {
+        let mut config = ReticulumConfig::default();
+        upsert_interface(&mut config, tcp("alpha", true, "10.0.0.1", 4242));
+        upsert_interface(&mut config, tcp("beta", false, "10.0.0.1", 4242));
+
+        let clash = tcp("gamma", true, "10.0.0.1", 4242);
+        assert_eq!(
+            conflicting_interface(&config, &clash).map(|iface| iface.name.as_str()),
+            Some("alpha")
+        );
+        assert!(conflicting_interface(&config, &tcp("gamma", true, "10.0.0.1", 4243)).is_none());
+        assert!(conflicting_interface(&config, &tcp("gamma", false, "10.0.0.1", 4242)).is_none());
+        assert!(conflicting_interface(&config, &tcp("alpha", true, "10.0.0.1", 4242)).is_none());
+        assert!(shared_endpoint_interfaces(&config).is_empty());
+
+        upsert_interface(&mut config, tcp("beta", true, "10.0.0.1", 4242));
+        assert_eq!(shared_endpoint_interfaces(&config), vec!["alpha", "beta"]);
+    }
+}
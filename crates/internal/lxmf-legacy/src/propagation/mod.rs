@@ -1,8 +1,9 @@
 use crate::error::LxmfError;
 use crate::message::WireMessage;
-use crate::storage::{PropagationStore, Store};
+use crate::storage::{lxmf_timestamp_to_system_time, PropagationStore, Store};
 use serde::Deserialize;
 use serde_bytes::ByteBuf;
+use std::time::SystemTime;
 
 #[derive(Debug, Clone, Copy)]
 pub enum VerificationMode {
@@ -46,6 +47,8 @@ pub struct PropagationStamp {
 pub struct IngestedMessage {
     pub transient_id: Vec<u8>,
     pub lxmf_data: Vec<u8>,
+    /// Timestamp of the envelope the message arrived in, in LXMF seconds.
+    pub timestamp: f64,
     pub stamp_value: Option<u32>,
     pub stamp: Option<Vec<u8>>,
 }
@@ -83,6 +86,7 @@ pub fn ingest_envelope(bytes: &[u8], target_cost: u32) -> Result<Vec<IngestedMes
             out.push(IngestedMessage {
                 transient_id: stamped.transient_id,
                 lxmf_data: stamped.lxmf_data,
+                timestamp: envelope.timestamp,
                 stamp_value: Some(stamped.stamp_value),
                 stamp: Some(stamped.stamp),
             });
@@ -91,6 +95,7 @@ pub fn ingest_envelope(bytes: &[u8], target_cost: u32) -> Result<Vec<IngestedMes
             out.push(IngestedMessage {
                 transient_id,
                 lxmf_data: data,
+                timestamp: envelope.timestamp,
                 stamp_value: None,
                 stamp: None,
             });
@@ -110,10 +115,15 @@ impl PropagationService {
         Self { store, target_cost }
     }
 
+    /// Stores the messages of a propagation envelope, each expiring relative
+    /// to the envelope's timestamp, after dropping already expired ones.
     pub fn ingest(&self, bytes: &[u8]) -> Result<usize, LxmfError> {
         let messages = ingest_envelope(bytes, self.target_cost)?;
+        self.store.purge_expired()?;
         for msg in &messages {
-            self.store.save(&msg.transient_id, &msg.lxmf_data)?;
+            let sent_at =
+                lxmf_timestamp_to_system_time(msg.timestamp).unwrap_or_else(SystemTime::now);
+            self.store.save_sent_at(&msg.transient_id, &msg.lxmf_data, sent_at)?;
         }
         Ok(messages.len())
     }
@@ -172,3 +182,35 @@ impl PropagationNode {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ingest_envelope, PropagationService};
+    use crate::storage::{PropagationStore, MESSAGE_EXPIRY};
+    use serde_bytes::ByteBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn envelope(timestamp: f64, message: &[u8]) -> Vec<u8> {
+        rmp_serde::to_vec(&(timestamp, vec![ByteBuf::from(message.to_vec())])).expect("envelope")
+    }
+
+    #[test]
+    fn ingest_expires_messages_from_the_envelope_timestamp() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let service = PropagationService::new(PropagationStore::new(temp.path()), 0);
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).expect("clock").as_secs_f64();
+        let expired = now - MESSAGE_EXPIRY.as_secs_f64() - 60.0;
+
+        let stale = envelope(expired, &[0xAA; 40]);
+        let live = envelope(now, &[0xBB; 40]);
+        assert_eq!(service.ingest(&stale).expect("ingest"), 1);
+        assert_eq!(service.ingest(&live).expect("ingest"), 1);
+
+        let transient_id =
+            |bytes: &[u8]| ingest_envelope(bytes, 0).expect("envelope").remove(0).transient_id;
+        let (stale_id, live_id) = (transient_id(&stale), transient_id(&live));
+        assert!(service.fetch(&stale_id).is_err());
+        assert!(!temp.path().join(hex::encode(&stale_id)).exists(), "purged on ingest");
+        assert!(service.fetch(&live_id).is_ok());
+    }
+}
@@ -90,7 +90,7 @@ pub(super) fn update_peer_announce_meta(
     let app_data_hex = if app_data.is_empty() { None } else { Some(hex::encode(app_data)) };
 
     let mut guard = peer_announce_meta.lock().expect("peer metadata map");
-    guard.insert(peer.to_string(), PeerAnnounceMeta { app_data_hex });
+    guard.insert(peer.to_string(), PeerAnnounceMeta { app_data_hex, last_seen: now_epoch_secs() });
 }
 
 pub(super) fn annotate_peer_records_with_announce_metadata(
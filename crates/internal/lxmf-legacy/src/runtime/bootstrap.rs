@@ -21,11 +21,15 @@ impl WorkerState {
         let peer_identity_cache_path = init.paths.root.join("peer_identities.json");
         let peer_crypto: Arc<Mutex<HashMap<String, PeerCrypto>>> =
             Arc::new(Mutex::new(HashMap::new()));
-        if let Ok(restored) = load_peer_identity_cache(&peer_identity_cache_path) {
-            if let Ok(mut guard) = peer_crypto.lock() {
-                *guard = restored;
+        let peer_identity_cache_error = match load_peer_identity_cache(&peer_identity_cache_path) {
+            Ok(restored) => {
+                if let Ok(mut guard) = peer_crypto.lock() {
+                    *guard = restored;
+                }
+                None
             }
-        }
+            Err(err) => Some(err.to_string()),
+        };
         let peer_announce_meta: Arc<Mutex<HashMap<String, PeerAnnounceMeta>>> =
             Arc::new(Mutex::new(HashMap::new()));
         let selected_propagation_node: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
@@ -129,6 +133,10 @@ impl WorkerState {
                 peer_identity_cache_path.clone(),
                 selected_propagation_node.clone(),
                 known_propagation_nodes.clone(),
+                peer_announce_meta.clone(),
+                PropagationSelectionStrategy::parse(
+                    init.settings.propagation_selection_strategy.as_deref(),
+                ),
                 receipt_map.clone(),
                 outbound_resource_map.clone(),
                 delivered_messages.clone(),
@@ -156,6 +164,16 @@ impl WorkerState {
             event_type: "runtime_started".to_string(),
             payload: json!({ "profile": init.profile }),
         });
+        if let Some(error) = peer_identity_cache_error {
+            // The cache is rebuilt from announces as peers are seen again.
+            daemon.push_event(RpcEvent {
+                event_type: "peer_identity_cache_discarded".to_string(),
+                payload: json!({
+                    "path": peer_identity_cache_path.display().to_string(),
+                    "error": error,
+                }),
+            });
+        }
 
         if let Some(bridge) = bridge.as_ref() {
             let _ = bridge.announce_now();
@@ -170,6 +188,7 @@ impl WorkerState {
                 transport,
                 daemon.clone(),
                 receipt_tx.clone(),
+                delivered_messages.clone(),
                 outbound_resource_map.clone(),
                 peer_crypto.clone(),
                 peer_announce_meta.clone(),
@@ -185,6 +204,9 @@ impl WorkerState {
             None
         };
 
+        let scheduled_send_handle =
+            daemon.clone().start_scheduled_send_loop(SCHEDULED_SEND_POLL_INTERVAL);
+
         if let Some(bridge) = bridge.clone() {
             spawn_startup_announce_burst(bridge);
         }
@@ -211,6 +233,7 @@ impl WorkerState {
             propagation_sync_state: Arc::new(Mutex::new(RuntimePropagationSyncState::default())),
             shutdown_tx,
             scheduler_handle,
+            scheduled_send_handle,
             shutdown: false,
         })
     }
@@ -16,6 +16,8 @@ pub(super) struct EmbeddedTransportBridge {
     pub(super) peer_identity_cache_path: PathBuf,
     pub(super) selected_propagation_node: Arc<Mutex<Option<String>>>,
     pub(super) known_propagation_nodes: Arc<Mutex<HashSet<String>>>,
+    pub(super) peer_announce_meta: Arc<Mutex<HashMap<String, PeerAnnounceMeta>>>,
+    pub(super) propagation_selection: PropagationSelectionStrategy,
     pub(super) receipt_map: Arc<Mutex<HashMap<String, String>>>,
     pub(super) outbound_resource_map: Arc<Mutex<HashMap<String, String>>>,
     pub(super) delivered_messages: Arc<Mutex<HashSet<String>>>,
@@ -34,6 +36,8 @@ impl EmbeddedTransportBridge {
         peer_identity_cache_path: PathBuf,
         selected_propagation_node: Arc<Mutex<Option<String>>>,
         known_propagation_nodes: Arc<Mutex<HashSet<String>>>,
+        peer_announce_meta: Arc<Mutex<HashMap<String, PeerAnnounceMeta>>>,
+        propagation_selection: PropagationSelectionStrategy,
         receipt_map: Arc<Mutex<HashMap<String, String>>>,
         outbound_resource_map: Arc<Mutex<HashMap<String, String>>>,
         delivered_messages: Arc<Mutex<HashSet<String>>>,
@@ -49,6 +53,8 @@ impl EmbeddedTransportBridge {
             peer_identity_cache_path,
             selected_propagation_node,
             known_propagation_nodes,
+            peer_announce_meta,
+            propagation_selection,
             receipt_map,
             outbound_resource_map,
             delivered_messages,
@@ -2,6 +2,10 @@ use super::{OutboundDeliveryOptionsCompat, OUTBOUND_DELIVERY_OPTIONS_FIELD};
 use reticulum::storage::messages::MessageRecord;
 use serde_json::Value;
 
+/// Upper bound on `max_retries`. With the doubling backoff, more retries than
+/// this would keep a delivery task alive for hours.
+pub(super) const MAX_DELIVERY_RETRIES: u32 = 8;
+
 fn parse_u32_field(value: &Value) -> Option<u32> {
     match value {
         Value::Number(number) => number.as_u64().and_then(|value| u32::try_from(value).ok()),
@@ -10,6 +14,14 @@ fn parse_u32_field(value: &Value) -> Option<u32> {
     }
 }
 
+fn parse_u64_field(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(number) => number.as_u64(),
+        Value::String(value) => value.parse().ok(),
+        _ => None,
+    }
+}
+
 fn parse_bool_field(value: &Value) -> Option<bool> {
     match value {
         Value::Bool(value) => Some(*value),
@@ -43,6 +55,13 @@ pub(super) fn merge_outbound_delivery_options(
     if out.source_private_key.is_none() {
         out.source_private_key = api_options.source_private_key.clone();
     }
+    if out.max_retries == 0 {
+        out.max_retries = api_options.max_retries.min(MAX_DELIVERY_RETRIES);
+    }
+    if out.retry_backoff_ms == 0 {
+        out.retry_backoff_ms = api_options.retry_backoff_ms;
+    }
+    out.propagation_first = api_options.propagation_first;
 
     out
 }
@@ -54,7 +73,9 @@ pub(super) fn merge_outbound_delivery_options(
     extract_outbound_delivery_options(record)
 }
 
-fn extract_outbound_delivery_options(record: &MessageRecord) -> OutboundDeliveryOptionsCompat {
+pub(super) fn extract_outbound_delivery_options(
+    record: &MessageRecord,
+) -> OutboundDeliveryOptionsCompat {
     let mut out = OutboundDeliveryOptionsCompat::default();
     let Some(fields) = record.fields.as_ref().and_then(Value::as_object) else {
         return out;
@@ -85,6 +106,16 @@ fn extract_outbound_delivery_options(record: &MessageRecord) -> OutboundDelivery
         {
             out.source_private_key = Some(source_private_key);
         }
+        if let Some(max_retries) =
+            parse_u32_field(options.get("max_retries").unwrap_or(&Value::Null))
+        {
+            out.max_retries = max_retries.min(MAX_DELIVERY_RETRIES);
+        }
+        if let Some(retry_backoff_ms) =
+            parse_u64_field(options.get("retry_backoff_ms").unwrap_or(&Value::Null))
+        {
+            out.retry_backoff_ms = retry_backoff_ms;
+        }
     }
 
     if let Some(lxmf) = fields
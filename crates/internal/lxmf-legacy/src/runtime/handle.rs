@@ -100,11 +100,11 @@ impl RuntimeHandle {
     ) -> Result<SendMessageResponse, LxmfError> {
         let source =
             if let Some(source_private_key) = clean_non_empty(request.source_private_key.clone()) {
-                source_hash_from_private_key_hex(&source_private_key)?
+                verified_source_hash(request.source.as_deref(), &source_private_key)?
             } else {
                 self.resolve_source_for_send(request.source.clone())?
             };
-        let prepared = build_send_params_with_source(request, source)?;
+        let prepared = build_send_params_with_source(request, source, &self.inner.settings)?;
         let PreparedSendMessage { id, source, destination, params } = prepared;
 
         let result = self.call("send_message_v2", Some(params))?;
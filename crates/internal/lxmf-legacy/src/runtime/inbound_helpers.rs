@@ -33,6 +33,7 @@ pub(super) fn decode_inbound_payload(
         direction: "in".into(),
         fields: message.fields.as_ref().and_then(json_fields_with_raw_preserved),
         receipt_status: None,
+        read_ts_ms: None,
     })
 }
 
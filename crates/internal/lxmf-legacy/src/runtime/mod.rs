@@ -40,22 +40,31 @@ use announce_helpers::{
 };
 use announce_rate_limit::trigger_rate_limited_announce;
 use delivery_options::merge_outbound_delivery_options;
+#[cfg(test)]
+use delivery_options::{extract_outbound_delivery_options, MAX_DELIVERY_RETRIES};
 use identity_io::{drop_empty_identity_stub, load_or_create_identity};
 use inbound_helpers::build_propagation_envelope;
 #[cfg(test)]
 use inbound_helpers::decode_inbound_payload;
 use peer_cache::{
-    apply_runtime_identity_restore, load_peer_identity_cache, persist_peer_identity_cache,
+    apply_runtime_identity_restore, forget_peer_identities, load_peer_identity_cache,
+    persist_peer_identity_cache,
+};
+use receipt_flow::{
+    accept_inbound_record, handle_receipt_event, resolve_link_destination, ReceiptBridge,
+    ReceiptEvent,
 };
-use receipt_flow::{handle_receipt_event, resolve_link_destination, ReceiptBridge, ReceiptEvent};
+#[cfg(test)]
+use receipt_flow::{inbound_read_receipt, ReadReceipt};
 use receipt_helpers::{
-    format_relay_request_status, is_message_marked_delivered,
-    parse_alternative_relay_request_status, prune_receipt_mappings_for_message,
-    track_outbound_resource_mapping, track_receipt_mapping,
+    format_delivery_retry_status, format_relay_request_status, is_message_marked_delivered,
+    parse_alternative_relay_request_status, parse_delivery_retry_status,
+    prune_receipt_mappings_for_message, track_outbound_resource_mapping, track_receipt_mapping,
+    DeliveryRetry,
 };
 use relay_helpers::{
-    normalize_relay_destination_hash, propagation_relay_candidates, short_hash_prefix,
-    wait_for_external_relay_selection,
+    normalize_relay_destination_hash, propagation_relay_candidates, require_propagation_relay,
+    short_hash_prefix, wait_for_external_relay_selection, PropagationSelectionStrategy,
 };
 use reticulum::destination::{DestinationName, SingleInputDestination};
 use reticulum::identity::{Identity, PrivateIdentity};
@@ -70,8 +79,8 @@ use rpc_dispatch::handle_runtime_request;
 use rpc_helpers::{annotate_response_meta, build_send_params_with_source, resolve_transport};
 use runtime_loop::runtime_thread;
 use send_helpers::{
-    can_send_opportunistic, opportunistic_payload, parse_delivery_method, send_outcome_is_sent,
-    send_outcome_status, DeliveryMethod,
+    can_send_opportunistic, opportunistic_payload, parse_delivery_method, retry_backoff_delay,
+    send_outcome_is_sent, send_outcome_status, DeliveryMethod,
 };
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -87,8 +96,8 @@ use std::sync::{mpsc as std_mpsc, Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
 use support::{
-    clean_non_empty, extract_identity_hash, generate_message_id, interface_to_rpc, now_epoch_secs,
-    parse_bind_host_port, source_hash_from_private_key_hex,
+    clean_non_empty, extract_identity_hash, interface_to_rpc, now_epoch_secs, parse_bind_host_port,
+    verified_source_hash,
 };
 use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
 use tokio::sync::watch;
@@ -104,6 +113,7 @@ pub use public_types::{
 
 const INFERRED_TRANSPORT_BIND: &str = "127.0.0.1:0";
 const DEFAULT_ANNOUNCE_INTERVAL_SECS: u64 = 60;
+const SCHEDULED_SEND_POLL_INTERVAL: Duration = Duration::from_millis(250);
 const STARTUP_ANNOUNCE_BURST_DELAYS_SECS: &[u64] = &[5, 15, 30];
 const POST_SEND_ANNOUNCE_MIN_INTERVAL_SECS: u64 = 20;
 const MAX_ALTERNATIVE_PROPAGATION_RELAYS: usize = 3;
@@ -6,17 +6,43 @@ use reticulum::destination::{DestinationName, SingleOutputDestination};
 use reticulum::identity::Identity;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
+const PEER_IDENTITY_CACHE_VERSION: u32 = 1;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct PersistedPeerIdentity {
     destination: String,
     identity_hex: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PeerIdentityCacheFile {
+    version: u32,
+    checksum: String,
+    entries: Vec<PersistedPeerIdentity>,
+}
+
+fn peer_identity_cache_checksum(entries: &[PersistedPeerIdentity]) -> String {
+    let mut hasher = Sha256::new();
+    for entry in entries {
+        hasher.update(entry.destination.as_bytes());
+        hasher.update([0u8]);
+        hasher.update(entry.identity_hex.as_bytes());
+        hasher.update([b'\n']);
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Loads the peer identity cache written by [`persist_peer_identity_cache`].
+///
+/// Files with an unknown version or a checksum that does not match their
+/// entries are rejected as a whole so callers can start empty and rebuild the
+/// cache from announces.
 pub(super) fn load_peer_identity_cache(
     path: &Path,
 ) -> Result<HashMap<String, PeerCrypto>, LxmfError> {
@@ -24,8 +50,18 @@ pub(super) fn load_peer_identity_cache(
         return Ok(HashMap::new());
     }
     let raw = fs::read_to_string(path).map_err(|err| LxmfError::Io(err.to_string()))?;
-    let entries: Vec<PersistedPeerIdentity> =
+    let file: PeerIdentityCacheFile =
         serde_json::from_str(&raw).map_err(|err| LxmfError::Decode(err.to_string()))?;
+    if file.version != PEER_IDENTITY_CACHE_VERSION {
+        return Err(LxmfError::Verify(format!(
+            "unsupported peer identity cache version {}",
+            file.version
+        )));
+    }
+    if file.checksum != peer_identity_cache_checksum(&file.entries) {
+        return Err(LxmfError::Verify("peer identity cache checksum mismatch".into()));
+    }
+    let entries = file.entries;
     let mut out = HashMap::new();
     for entry in entries {
         let Some(destination) = normalize_hash_hex_16(&entry.destination) else {
@@ -58,7 +94,12 @@ pub(super) fn persist_peer_identity_cache(
         })
         .unwrap_or_default();
 
-    let encoded = match serde_json::to_string_pretty(&snapshot) {
+    let file = PeerIdentityCacheFile {
+        version: PEER_IDENTITY_CACHE_VERSION,
+        checksum: peer_identity_cache_checksum(&snapshot),
+        entries: snapshot,
+    };
+    let encoded = match serde_json::to_string_pretty(&file) {
         Ok(encoded) => encoded,
         Err(_) => return,
     };
@@ -74,22 +115,49 @@ pub(super) fn persist_peer_identity_cache(
     }
 }
 
+/// Drops the identities cached for `destinations`, as listed by a
+/// `peers_prune` response, and persists the cache. Returns how many were
+/// removed.
+pub(super) fn forget_peer_identities(
+    peer_crypto: &Arc<Mutex<HashMap<String, PeerCrypto>>>,
+    cache_path: &Path,
+    destinations: &[String],
+) -> usize {
+    let removed = match peer_crypto.lock() {
+        Ok(mut guard) => destinations
+            .iter()
+            .filter(|destination| guard.remove(destination.as_str()).is_some())
+            .count(),
+        Err(_) => 0,
+    };
+    if removed > 0 {
+        persist_peer_identity_cache(peer_crypto, cache_path);
+    }
+    removed
+}
+
+/// Registers the peer identities carried by an identity restore or
+/// `peers_import` request and persists the cache. Returns how many were
+/// registered.
 pub(super) fn apply_runtime_identity_restore(
     peer_crypto: &Arc<Mutex<HashMap<String, PeerCrypto>>>,
     cache_path: &Path,
     method: &str,
     params: Option<&Value>,
-) {
+) -> usize {
     let Some(params) = params.and_then(Value::as_object) else {
-        return;
+        return 0;
     };
+    let mut restored = 0;
 
     match method {
         "store_peer_identity" => {
             let identity_hash = params.get("identity_hash").and_then(Value::as_str);
             let public_key = params.get("public_key").and_then(Value::as_str);
             if let (Some(identity_hash), Some(public_key)) = (identity_hash, public_key) {
-                register_peer_identity(peer_crypto, identity_hash, public_key);
+                if register_peer_identity(peer_crypto, identity_hash, public_key) {
+                    restored += 1;
+                }
             }
         }
         "restore_all_peer_identities" | "bulk_restore_peer_identities" => {
@@ -101,7 +169,22 @@ pub(super) fn apply_runtime_identity_restore(
                     let identity_hash = peer.get("identity_hash").and_then(Value::as_str);
                     let public_key = peer.get("public_key").and_then(Value::as_str);
                     if let (Some(identity_hash), Some(public_key)) = (identity_hash, public_key) {
-                        register_peer_identity(peer_crypto, identity_hash, public_key);
+                        if register_peer_identity(peer_crypto, identity_hash, public_key) {
+                            restored += 1;
+                        }
+                    }
+                }
+            }
+        }
+        "peers_import" => {
+            let identities = params
+                .get("bundle")
+                .and_then(|bundle| bundle.get("identities"))
+                .and_then(Value::as_object);
+            for (destination, public_key) in identities.into_iter().flatten() {
+                if let Some(public_key) = public_key.as_str() {
+                    if register_destination_identity(peer_crypto, destination, public_key) {
+                        restored += 1;
                     }
                 }
             }
@@ -115,7 +198,9 @@ pub(super) fn apply_runtime_identity_restore(
                     let destination = announce.get("destination_hash").and_then(Value::as_str);
                     let public_key = announce.get("public_key").and_then(Value::as_str);
                     if let (Some(destination), Some(public_key)) = (destination, public_key) {
-                        register_destination_identity(peer_crypto, destination, public_key);
+                        if register_destination_identity(peer_crypto, destination, public_key) {
+                            restored += 1;
+                        }
                     }
                 }
             }
@@ -124,18 +209,19 @@ pub(super) fn apply_runtime_identity_restore(
     }
 
     persist_peer_identity_cache(peer_crypto, cache_path);
+    restored
 }
 
 fn register_peer_identity(
     peer_crypto: &Arc<Mutex<HashMap<String, PeerCrypto>>>,
     identity_hash_hex: &str,
     public_key_material: &str,
-) {
+) -> bool {
     let Some(identity_hash) = normalize_hash_hex_16(identity_hash_hex) else {
-        return;
+        return false;
     };
     let Some(identity) = identity_from_public_key_material(public_key_material) else {
-        return;
+        return false;
     };
 
     let destination =
@@ -146,8 +232,12 @@ fn register_peer_identity(
         // Public key material is source of truth for identity derivation.
     }
 
-    if let Ok(mut guard) = peer_crypto.lock() {
-        guard.insert(destination_hash, PeerCrypto { identity });
+    match peer_crypto.lock() {
+        Ok(mut guard) => {
+            guard.insert(destination_hash, PeerCrypto { identity });
+            true
+        }
+        Err(_) => false,
     }
 }
 
@@ -155,15 +245,19 @@ fn register_destination_identity(
     peer_crypto: &Arc<Mutex<HashMap<String, PeerCrypto>>>,
     destination_hash: &str,
     public_key_material: &str,
-) {
+) -> bool {
     let Some(destination_hash) = normalize_hash_hex_16(destination_hash) else {
-        return;
+        return false;
     };
     let Some(identity) = identity_from_public_key_material(public_key_material) else {
-        return;
+        return false;
     };
-    if let Ok(mut guard) = peer_crypto.lock() {
-        guard.insert(destination_hash, PeerCrypto { identity });
+    match peer_crypto.lock() {
+        Ok(mut guard) => {
+            guard.insert(destination_hash, PeerCrypto { identity });
+            true
+        }
+        Err(_) => false,
     }
 }
 
@@ -4,6 +4,7 @@ use super::propagation_link::{
     propagation_error_from_response_value, send_link_context_packet,
     wait_for_link_request_response,
 };
+use super::receipt_flow::accept_inbound_record;
 use super::{
     clean_non_empty, normalize_relay_destination_hash, now_epoch_secs,
     RuntimePropagationSyncParams, RuntimePropagationSyncState, WorkerState,
@@ -403,7 +404,7 @@ pub(super) async fn request_messages_from_propagation_node_live(
                 payload.as_slice(),
                 InboundPayloadMode::FullWire,
             ) {
-                state.daemon.accept_inbound(record)?;
+                accept_inbound_record(&state.daemon, record)?;
                 ingested_messages.push(hex::encode(transient_id));
                 continue;
             }
@@ -16,6 +16,7 @@ pub struct SendMessageRequest {
     pub stamp_cost: Option<u32>,
     pub include_ticket: bool,
     pub try_propagation_on_fail: bool,
+    pub scheduled_ts_ms: Option<u64>,
 }
 
 impl SendMessageRequest {
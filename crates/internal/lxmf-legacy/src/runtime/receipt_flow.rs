@@ -1,12 +1,14 @@
-use super::{now_epoch_secs, parse_alternative_relay_request_status};
+use super::{now_epoch_secs, parse_alternative_relay_request_status, parse_delivery_retry_status};
 use reticulum::hash::AddressHash;
 use reticulum::receipt::{
+    record_read_receipt as shared_record_read_receipt,
     record_receipt_status as shared_record_receipt_status,
-    resolve_receipt_message_id as shared_resolve_receipt_message_id,
+    resolve_receipt_message_id as shared_resolve_receipt_message_id, send_auto_read_receipt,
 };
 use reticulum::rpc::{RpcDaemon, RpcEvent};
+use reticulum::storage::messages::MessageRecord;
 use reticulum::transport::{DeliveryReceipt, ReceiptHandler, Transport};
-use serde_json::json;
+use serde_json::{json, Value};
 use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 
@@ -62,9 +64,99 @@ pub(super) fn handle_receipt_event(
             }),
         });
     }
+    if let Some(retry) = parse_delivery_retry_status(status.as_str()) {
+        daemon.push_event(RpcEvent {
+            event_type: "delivery_retry".to_string(),
+            payload: json!({
+                "message_id": message_id,
+                "attempt": retry.attempt,
+                "max_attempts": retry.max_attempts,
+                "backoff_ms": retry.backoff_ms,
+                "error": retry.error,
+                "timestamp_ms": (now_epoch_secs() as i64) * 1000,
+            }),
+        });
+    }
     Ok(())
 }
 
+/// A peer's report that it has read one or more of our messages. Read implies
+/// delivered; peers that never send these leave messages at `delivered`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ReadReceipt {
+    pub(super) message_ids: Vec<String>,
+    pub(super) read_ts_ms: Option<u64>,
+}
+
+/// LXMF itself defines no read receipt, so these travel as ordinary LXMF
+/// messages whose app extensions field (`16`, registered as
+/// `payload.app.read_receipt.v1`) carries `read_of` (a message id or a list of
+/// them) and optionally `read_ts_ms`. The message timestamp stands in for a
+/// missing read time.
+pub(super) fn inbound_read_receipt(record: &MessageRecord) -> Option<ReadReceipt> {
+    let extensions = record.fields.as_ref()?.get("16")?;
+    let message_ids = match extensions.get("read_of")? {
+        Value::String(message_id) => vec![message_id.clone()],
+        Value::Array(entries) => {
+            entries.iter().filter_map(Value::as_str).map(ToOwned::to_owned).collect()
+        }
+        _ => Vec::new(),
+    };
+    let message_ids = message_ids
+        .into_iter()
+        .map(|message_id| message_id.trim().to_string())
+        .filter(|message_id| !message_id.is_empty())
+        .collect::<Vec<_>>();
+    if message_ids.is_empty() {
+        return None;
+    }
+    let read_ts_ms = extensions
+        .get("read_ts_ms")
+        .and_then(Value::as_u64)
+        .or_else(|| u64::try_from(record.timestamp).ok().filter(|ts| *ts > 0).map(|ts| ts * 1000));
+    Some(ReadReceipt { message_ids, read_ts_ms })
+}
+
+/// Stores an inbound message, or applies it when it is a read receipt. Read
+/// receipts are control traffic and do not show up in the message list.
+/// Returns the ids of our messages that were marked read; ids that were not
+/// sent to the receipt's source are ignored, so a peer cannot mark other
+/// conversations read.
+///
+/// When the delivery policy opts in, a newly stored message is answered with
+/// one read receipt. Read receipts and redelivered messages are never
+/// answered, so two auto-answering peers cannot loop, and a failed receipt is
+/// reported as an event without failing the inbound message.
+pub(super) fn accept_inbound_record(
+    daemon: &RpcDaemon,
+    record: MessageRecord,
+) -> Result<Vec<String>, std::io::Error> {
+    let Some(receipt) = inbound_read_receipt(&record) else {
+        let redelivered = daemon.has_message(&record.id)?;
+        daemon.accept_inbound(record.clone())?;
+        if !redelivered {
+            if let Err(err) = send_auto_read_receipt(daemon, &record) {
+                daemon.push_event(RpcEvent {
+                    event_type: "auto_read_receipt_failed".to_string(),
+                    payload: json!({
+                        "message_id": record.id,
+                        "peer": record.source,
+                        "error": err.to_string(),
+                    }),
+                });
+            }
+        }
+        return Ok(Vec::new());
+    };
+    let mut applied = Vec::with_capacity(receipt.message_ids.len());
+    for message_id in receipt.message_ids {
+        if shared_record_read_receipt(daemon, &message_id, &record.source, receipt.read_ts_ms)? {
+            applied.push(message_id);
+        }
+    }
+    Ok(applied)
+}
+
 pub(super) async fn resolve_link_destination(
     transport: &Transport,
     link_id: &AddressHash,
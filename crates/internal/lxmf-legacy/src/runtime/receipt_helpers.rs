@@ -31,6 +31,34 @@ pub(super) fn parse_alternative_relay_request_status(status: &str) -> Option<Vec
     Some(exclude_relays)
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct DeliveryRetry {
+    pub(super) attempt: u32,
+    pub(super) max_attempts: u32,
+    pub(super) backoff_ms: u64,
+    pub(super) error: String,
+}
+
+pub(super) fn format_delivery_retry_status(retry: &DeliveryRetry) -> String {
+    format!(
+        "retrying: link attempt {}/{} in {}ms;error={}",
+        retry.attempt, retry.max_attempts, retry.backoff_ms, retry.error
+    )
+}
+
+pub(super) fn parse_delivery_retry_status(status: &str) -> Option<DeliveryRetry> {
+    let rest = status.strip_prefix("retrying: link attempt ")?;
+    let (progress, rest) = rest.split_once(" in ")?;
+    let (attempt, max_attempts) = progress.split_once('/')?;
+    let (backoff_ms, error) = rest.split_once("ms;error=")?;
+    Some(DeliveryRetry {
+        attempt: attempt.parse().ok()?,
+        max_attempts: max_attempts.parse().ok()?,
+        backoff_ms: backoff_ms.parse().ok()?,
+        error: error.to_string(),
+    })
+}
+
 pub(super) fn track_receipt_mapping(
     map: &Arc<Mutex<HashMap<String, String>>>,
     packet_hash: &str,
@@ -1,25 +1,58 @@
-use super::PeerCrypto;
+use super::{PeerAnnounceMeta, PeerCrypto};
 use reticulum::destination_hash::parse_destination_hash as shared_parse_destination_hash;
+#[cfg(reticulum_api_v2)]
+use reticulum::rpc::{DeliveryPrerequisite, MissingDeliveryPrerequisite};
 use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+/// Order in which outbound delivery tries propagation relays, read from the
+/// profile's `propagation_selection_strategy`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(super) enum PropagationSelectionStrategy {
+    /// The selected node first, then the other known nodes alphabetically.
+    #[default]
+    Selected,
+    /// Known nodes by announced peering cost; nodes without one go last.
+    LowestCost,
+    /// Known nodes by their last announce, newest first.
+    MostRecent,
+}
+
+impl PropagationSelectionStrategy {
+    pub(super) fn parse(value: Option<&str>) -> Self {
+        match value.map(str::trim) {
+            Some("lowest_cost") => Self::LowestCost,
+            Some("most_recent") => Self::MostRecent,
+            _ => Self::Selected,
+        }
+    }
+}
+
+/// Orders the propagation relays to try under `strategy`. Relays in `failed`
+/// were already tried for the current message and are left out.
 pub(super) fn propagation_relay_candidates(
     selected_propagation_node: &Arc<Mutex<Option<String>>>,
     known_propagation_nodes: &Arc<Mutex<HashSet<String>>>,
+    peer_announce_meta: &Arc<Mutex<HashMap<String, PeerAnnounceMeta>>>,
+    strategy: PropagationSelectionStrategy,
+    failed: &[String],
 ) -> Vec<String> {
     let mut candidates = Vec::new();
-    let mut seen = HashSet::new();
+    let mut seen = failed.iter().cloned().collect::<HashSet<_>>();
 
-    let selected = selected_propagation_node
-        .lock()
-        .ok()
-        .and_then(|guard| guard.clone())
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty());
-    if let Some(selected) = selected {
-        seen.insert(selected.clone());
-        candidates.push(selected);
+    if strategy == PropagationSelectionStrategy::Selected {
+        let selected = selected_propagation_node
+            .lock()
+            .ok()
+            .and_then(|guard| guard.clone())
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+        if let Some(selected) = selected {
+            if seen.insert(selected.clone()) {
+                candidates.push(selected);
+            }
+        }
     }
 
     let mut known = known_propagation_nodes
@@ -27,6 +60,19 @@ pub(super) fn propagation_relay_candidates(
         .map(|guard| guard.iter().cloned().collect::<Vec<_>>())
         .unwrap_or_default();
     known.sort();
+    if strategy != PropagationSelectionStrategy::Selected {
+        let meta = peer_announce_meta.lock().map(|guard| guard.clone()).unwrap_or_default();
+        let last_seen = |node: &String| meta.get(node).map(|entry| entry.last_seen).unwrap_or(0);
+        match strategy {
+            PropagationSelectionStrategy::LowestCost => known.sort_by(|a, b| {
+                let cost = |node: &String| {
+                    meta.get(node).and_then(PeerAnnounceMeta::peering_cost).unwrap_or(u32::MAX)
+                };
+                cost(a).cmp(&cost(b)).then_with(|| last_seen(b).cmp(&last_seen(a)))
+            }),
+            _ => known.sort_by_key(|node| std::cmp::Reverse(last_seen(node))),
+        }
+    }
     for candidate in known {
         if seen.insert(candidate.clone()) {
             candidates.push(candidate);
@@ -36,6 +82,39 @@ pub(super) fn propagation_relay_candidates(
     candidates
 }
 
+/// Fails unless a propagation relay is selected or known, so a propagation-first
+/// send is rejected up front instead of quietly going direct.
+pub(super) fn require_propagation_relay(
+    selected_propagation_node: &Arc<Mutex<Option<String>>>,
+    known_propagation_nodes: &Arc<Mutex<HashSet<String>>>,
+) -> Result<(), std::io::Error> {
+    let selected = selected_propagation_node
+        .lock()
+        .map(|guard| guard.as_deref().is_some_and(|value| !value.trim().is_empty()))
+        .unwrap_or(false);
+    let known = known_propagation_nodes.lock().map(|guard| !guard.is_empty()).unwrap_or(false);
+    if selected || known {
+        return Ok(());
+    }
+    Err(missing_propagation_node())
+}
+
+#[cfg(reticulum_api_v2)]
+fn missing_propagation_node() -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        MissingDeliveryPrerequisite(DeliveryPrerequisite::PropagationNode),
+    )
+}
+
+#[cfg(not(reticulum_api_v2))]
+fn missing_propagation_node() -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        "missing delivery prerequisite: propagation_node",
+    )
+}
+
 pub(super) fn short_hash_prefix(value: &str) -> String {
     value.chars().take(12).collect::<String>()
 }
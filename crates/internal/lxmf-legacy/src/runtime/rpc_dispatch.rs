@@ -1,7 +1,8 @@
 use super::propagation_sync::request_messages_from_propagation_node_live;
 use super::{
     annotate_peer_records_with_announce_metadata, annotate_response_meta,
-    apply_runtime_identity_restore, RuntimeCommand, RuntimeResponse, WorkerState,
+    apply_runtime_identity_restore, forget_peer_identities, RuntimeCommand, RuntimeResponse,
+    WorkerState,
 };
 use serde_json::Value;
 
@@ -18,7 +19,25 @@ pub(super) async fn handle_runtime_request(
         RuntimeCommand::Call(request) => {
             let method = request.method.clone();
             let params_snapshot = request.params.clone();
-            let mut result = if method == "request_messages_from_propagation_node"
+            let mut result = if method == "peers_import" {
+                // The embedded daemon keeps no peer table; the bundle's
+                // identities go into the peer identity cache below.
+                let imported = apply_runtime_identity_restore(
+                    &state.peer_crypto,
+                    &state.peer_identity_cache_path,
+                    method.as_str(),
+                    params_snapshot.as_ref(),
+                );
+                let offered = params_snapshot
+                    .as_ref()
+                    .and_then(|params| params.pointer("/bundle/identities"))
+                    .and_then(Value::as_object)
+                    .map_or(0, |identities| identities.len());
+                serde_json::json!({
+                    "identities_imported": imported,
+                    "identities_rejected": offered.saturating_sub(imported),
+                })
+            } else if method == "request_messages_from_propagation_node"
                 && state.transport.is_some()
             {
                 request_messages_from_propagation_node_live(state, params_snapshot.as_ref())
@@ -50,6 +69,26 @@ pub(super) async fn handle_runtime_request(
                     *guard = selected;
                 }
             }
+            if method == "peers_prune" {
+                // Pruned peers must announce again before they can be reached.
+                let pruned = result
+                    .get("peers")
+                    .and_then(Value::as_array)
+                    .map(|peers| {
+                        peers.iter().filter_map(Value::as_str).map(ToOwned::to_owned).collect()
+                    })
+                    .unwrap_or_else(Vec::<String>::new);
+                if let Ok(mut guard) = state.peer_announce_meta.lock() {
+                    for peer in &pruned {
+                        guard.remove(peer);
+                    }
+                }
+                forget_peer_identities(
+                    &state.peer_crypto,
+                    &state.peer_identity_cache_path,
+                    &pruned,
+                );
+            }
             if matches!(
                 method.as_str(),
                 "store_peer_identity"
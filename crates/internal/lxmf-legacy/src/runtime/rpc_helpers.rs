@@ -1,7 +1,8 @@
 use super::{
-    clean_non_empty, generate_message_id, PreparedSendMessage, ProfileSettings, SendMessageRequest,
+    clean_non_empty, PreparedSendMessage, ProfileSettings, SendMessageRequest,
     INFERRED_TRANSPORT_BIND,
 };
+use crate::cli::message_id::generate_message_id;
 use crate::LxmfError;
 use serde_json::{json, Value};
 
@@ -30,10 +31,12 @@ pub(super) fn annotate_response_meta(result: &mut Value, profile: &str, rpc_endp
 pub(super) fn build_send_params_with_source(
     request: SendMessageRequest,
     source: String,
+    settings: &ProfileSettings,
 ) -> Result<PreparedSendMessage, LxmfError> {
     let destination = clean_non_empty(Some(request.destination))
         .ok_or_else(|| LxmfError::Io("destination is required".to_string()))?;
-    let id = clean_non_empty(request.id).unwrap_or_else(generate_message_id);
+    let id = clean_non_empty(request.id)
+        .unwrap_or_else(|| generate_message_id(settings.message_id_format.as_deref()));
 
     let mut params = json!({
         "id": id,
@@ -61,6 +64,9 @@ pub(super) fn build_send_params_with_source(
     if let Some(source_private_key) = clean_non_empty(request.source_private_key) {
         params["source_private_key"] = Value::String(source_private_key);
     }
+    if let Some(scheduled_ts_ms) = request.scheduled_ts_ms {
+        params["scheduled_ts_ms"] = Value::from(scheduled_ts_ms);
+    }
 
     Ok(PreparedSendMessage { id, source, destination, params })
 }
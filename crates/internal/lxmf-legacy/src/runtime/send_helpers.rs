@@ -6,6 +6,7 @@ use reticulum::delivery::{
 };
 use reticulum::transport::SendPacketOutcome;
 use serde_json::Value;
+use std::time::Duration;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(super) enum DeliveryMethod {
@@ -28,6 +29,13 @@ pub(super) fn parse_delivery_method(method: Option<&str>) -> DeliveryMethod {
     }
 }
 
+/// Delay before retry number `retry` (starting at 1): the configured base,
+/// doubled for each earlier retry.
+pub(super) fn retry_backoff_delay(base_ms: u64, retry: u32) -> Duration {
+    let factor = 1u64 << retry.saturating_sub(1).min(16);
+    Duration::from_millis(base_ms.saturating_mul(factor))
+}
+
 pub(super) fn can_send_opportunistic(fields: Option<&Value>, payload_len: usize) -> bool {
     const MAX_OPPORTUNISTIC_BYTES: usize = 295;
     payload_len <= MAX_OPPORTUNISTIC_BYTES && !fields_contain_attachments(fields)
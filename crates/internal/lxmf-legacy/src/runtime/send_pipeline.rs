@@ -1,13 +1,14 @@
 use super::{
     build_propagation_envelope, build_wire_message, can_send_opportunistic, clean_non_empty,
-    format_relay_request_status, is_message_marked_delivered, normalize_relay_destination_hash,
-    opportunistic_payload, parse_delivery_method, persist_peer_identity_cache,
-    propagation_relay_candidates, prune_receipt_mappings_for_message,
-    sanitize_outbound_wire_fields, send_outcome_is_sent, send_outcome_status, short_hash_prefix,
-    track_outbound_resource_mapping, track_receipt_mapping, trigger_rate_limited_announce,
-    wait_for_external_relay_selection, DeliveryMethod, EmbeddedTransportBridge,
-    OutboundDeliveryOptionsCompat, PeerCrypto, ReceiptEvent, MAX_ALTERNATIVE_PROPAGATION_RELAYS,
-    POST_SEND_ANNOUNCE_MIN_INTERVAL_SECS,
+    format_delivery_retry_status, format_relay_request_status, is_message_marked_delivered,
+    normalize_relay_destination_hash, opportunistic_payload, parse_delivery_method,
+    persist_peer_identity_cache, propagation_relay_candidates, prune_receipt_mappings_for_message,
+    require_propagation_relay, retry_backoff_delay, sanitize_outbound_wire_fields,
+    send_outcome_is_sent, send_outcome_status, short_hash_prefix, track_outbound_resource_mapping,
+    track_receipt_mapping, trigger_rate_limited_announce, wait_for_external_relay_selection,
+    AnnounceTarget, DeliveryMethod, DeliveryRetry, EmbeddedTransportBridge,
+    OutboundDeliveryOptionsCompat, PeerAnnounceMeta, PeerCrypto, PropagationSelectionStrategy,
+    ReceiptEvent, MAX_ALTERNATIVE_PROPAGATION_RELAYS, POST_SEND_ANNOUNCE_MIN_INTERVAL_SECS,
 };
 use reticulum::delivery::{send_via_link as shared_send_via_link, LinkSendResult};
 use reticulum::destination::{DestinationDesc, DestinationName};
@@ -16,12 +17,17 @@ use reticulum::destination_hash::{
     parse_destination_hash_required as parse_destination_hex_required,
 };
 use reticulum::hash::AddressHash;
-use reticulum::identity::PrivateIdentity;
+use reticulum::identity::{Identity, PrivateIdentity};
 use reticulum::packet::{
     ContextFlag, DestinationType, Header, HeaderType, IfacFlag, Packet, PacketContext,
     PacketDataBuffer, PacketType, PropagationType,
 };
 use reticulum::storage::messages::MessageRecord;
+use reticulum::transport::Transport;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::AtomicU64;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 #[derive(Clone, Copy)]
@@ -31,6 +37,9 @@ struct DeliveryMethodPlan {
     allow_link: bool,
     allow_opportunistic: bool,
     allow_propagated: bool,
+    /// Try propagation relays before a direct link. Only applies when no
+    /// explicit method was requested.
+    propagation_first: bool,
 }
 
 impl DeliveryMethodPlan {
@@ -38,6 +47,7 @@ impl DeliveryMethodPlan {
         requested: DeliveryMethod,
         opportunistic_supported: bool,
         try_propagation_on_fail: bool,
+        propagation_first: bool,
     ) -> Self {
         let effective =
             if matches!(requested, DeliveryMethod::Opportunistic) && !opportunistic_supported {
@@ -58,6 +68,7 @@ impl DeliveryMethodPlan {
                 effective,
                 DeliveryMethod::Auto | DeliveryMethod::Propagated
             ) || try_propagation_on_fail,
+            propagation_first: propagation_first && matches!(requested, DeliveryMethod::Auto),
         }
     }
 
@@ -86,6 +97,15 @@ fn resolve_signer_and_source_hash(
         })?;
         let mut source_hash = [0u8; 16];
         source_hash.copy_from_slice(signer.address_hash().as_slice());
+        let requested_source = requested_source.trim();
+        if !requested_source.is_empty()
+            && parse_destination_hex_required(requested_source)? != source_hash
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "source hash does not match source_private_key identity",
+            ));
+        }
         return Ok((signer, source_hash));
     }
 
@@ -29,425 +29,467 @@ impl EmbeddedTransportBridge {
             parse_delivery_method(options.method.as_deref()),
             opportunistic_supported,
             options.try_propagation_on_fail,
+            options.propagation_first,
         );
+        if method_plan.propagation_first {
+            require_propagation_relay(
+                &self.selected_propagation_node,
+                &self.known_propagation_nodes,
+            )?;
+        }
 
-        let destination_hash = AddressHash::new(destination);
-        let transport = self.transport.clone();
-        let peer_crypto = self.peer_crypto.clone();
-        let selected_propagation_node = self.selected_propagation_node.clone();
-        let known_propagation_nodes = self.known_propagation_nodes.clone();
-        let receipt_map = self.receipt_map.clone();
-        let outbound_resource_map = self.outbound_resource_map.clone();
-        let delivered_messages = self.delivered_messages.clone();
-        let receipt_tx = self.receipt_tx.clone();
-        let announce_targets = self.announce_targets.clone();
-        let announce_last = self.last_announce_epoch_secs.clone();
-        let peer_identity_cache_path = self.peer_identity_cache_path.clone();
-        let message_id = record.id.clone();
-        let destination_hex = record.destination.clone();
         let ticket_present =
             options.ticket.as_ref().map(|ticket| !ticket.trim().is_empty()).unwrap_or(false);
         let ticket_status =
             ticket_status(options.include_ticket, ticket_present).map(str::to_string);
+        let delivery = OutboundDelivery {
+            transport: self.transport.clone(),
+            peer_crypto: self.peer_crypto.clone(),
+            selected_propagation_node: self.selected_propagation_node.clone(),
+            known_propagation_nodes: self.known_propagation_nodes.clone(),
+            peer_announce_meta: self.peer_announce_meta.clone(),
+            propagation_selection: self.propagation_selection,
+            receipt_map: self.receipt_map.clone(),
+            outbound_resource_map: self.outbound_resource_map.clone(),
+            delivered_messages: self.delivered_messages.clone(),
+            receipt_tx: self.receipt_tx.clone(),
+            announce_targets: self.announce_targets.clone(),
+            announce_last: self.last_announce_epoch_secs.clone(),
+            peer_identity_cache_path: self.peer_identity_cache_path.clone(),
+            message_id: record.id.clone(),
+            destination,
+            destination_hash: AddressHash::new(destination),
+            destination_hex: record.destination.clone(),
+            payload,
+            peer_identity,
+            max_attempts: options.max_retries.saturating_add(1),
+            retry_backoff_ms: options.retry_backoff_ms,
+        };
 
-        tokio::spawn(async move {
-            if let Ok(mut delivered) = delivered_messages.lock() {
-                delivered.remove(&message_id);
-            }
+        tokio::spawn(delivery.run(method_plan, opportunistic_supported, ticket_status));
+
+        Ok(())
+    }
+}
+
+/// One spawned outbound delivery. The link and propagated relay phases are
+/// separate so the delivery policy can choose which one runs first.
+struct OutboundDelivery {
+    transport: Arc<Transport>,
+    peer_crypto: Arc<Mutex<HashMap<String, PeerCrypto>>>,
+    selected_propagation_node: Arc<Mutex<Option<String>>>,
+    known_propagation_nodes: Arc<Mutex<HashSet<String>>>,
+    peer_announce_meta: Arc<Mutex<HashMap<String, PeerAnnounceMeta>>>,
+    propagation_selection: PropagationSelectionStrategy,
+    receipt_map: Arc<Mutex<HashMap<String, String>>>,
+    outbound_resource_map: Arc<Mutex<HashMap<String, String>>>,
+    delivered_messages: Arc<Mutex<HashSet<String>>>,
+    receipt_tx: tokio::sync::mpsc::UnboundedSender<ReceiptEvent>,
+    announce_targets: Vec<AnnounceTarget>,
+    announce_last: Arc<AtomicU64>,
+    peer_identity_cache_path: PathBuf,
+    message_id: String,
+    destination: [u8; 16],
+    destination_hash: AddressHash,
+    destination_hex: String,
+    payload: Vec<u8>,
+    peer_identity: Option<Identity>,
+    max_attempts: u32,
+    retry_backoff_ms: u64,
+}
+
+impl OutboundDelivery {
+    fn report(&self, status: impl Into<String>) {
+        let _ = self
+            .receipt_tx
+            .send(ReceiptEvent { message_id: self.message_id.clone(), status: status.into() });
+    }
+
+    fn fail(&self, status: String) {
+        prune_receipt_mappings_for_message(&self.receipt_map, &self.message_id);
+        self.report(status);
+    }
 
-            if let Some(status) = ticket_status {
-                let _ = receipt_tx.send(ReceiptEvent { message_id: message_id.clone(), status });
+    /// Relays to try next under the profile's selection strategy, skipping
+    /// the ones that already failed for this message.
+    fn relay_candidates(&self, failed: &[String]) -> Vec<String> {
+        propagation_relay_candidates(
+            &self.selected_propagation_node,
+            &self.known_propagation_nodes,
+            &self.peer_announce_meta,
+            self.propagation_selection,
+            failed,
+        )
+    }
+
+    fn announce(&self) {
+        trigger_rate_limited_announce(
+            &self.transport,
+            &self.announce_targets,
+            &self.announce_last,
+            POST_SEND_ANNOUNCE_MIN_INTERVAL_SECS,
+        );
+    }
+
+    async fn run(
+        self,
+        method_plan: DeliveryMethodPlan,
+        opportunistic_supported: bool,
+        ticket_status: Option<String>,
+    ) {
+        if let Ok(mut delivered) = self.delivered_messages.lock() {
+            delivered.remove(&self.message_id);
+        }
+
+        if let Some(status) = ticket_status {
+            self.report(status);
+        }
+
+        if method_plan.downgraded_to_direct() {
+            self.report("retrying: direct fallback due to opportunistic constraints");
+        }
+
+        if method_plan.propagation_first {
+            if self.via_propagation(None).await.is_ok() {
+                return;
+            }
+            self.report("retrying: direct link after propagated relay failure");
+            if let Err(failure) = self.via_link().await {
+                self.fail(failure);
             }
+            return;
+        }
 
-            if method_plan.downgraded_to_direct() {
-                let _ = receipt_tx.send(ReceiptEvent {
-                    message_id: message_id.clone(),
-                    status: "retrying: direct fallback due to opportunistic constraints"
-                        .to_string(),
-                });
+        let mut last_failure: Option<String> = None;
+
+        if method_plan.allow_link {
+            match self.via_link().await {
+                Ok(()) => return,
+                Err(failure) => last_failure = Some(failure),
             }
 
-            let mut last_failure: Option<String> = None;
-
-            if method_plan.allow_link {
-                let _ = receipt_tx.send(ReceiptEvent {
-                    message_id: message_id.clone(),
-                    status: "outbound_attempt: link".to_string(),
-                });
-
-                let mut identity = peer_identity;
-                transport.request_path(&destination_hash, None, None).await;
-
-                if identity.is_none() {
-                    let deadline = tokio::time::Instant::now() + Duration::from_secs(12);
-                    while tokio::time::Instant::now() < deadline {
-                        if let Some(found) = transport.destination_identity(&destination_hash).await
-                        {
-                            identity = Some(found);
-                            break;
-                        }
-                        tokio::time::sleep(Duration::from_millis(250)).await;
-                    }
-                }
+            if !method_plan.allow_opportunistic && !method_plan.allow_propagated {
+                self.fail(
+                    last_failure.unwrap_or_else(|| "failed: link delivery unavailable".to_string()),
+                );
+                return;
+            }
+        }
 
-                if let Some(identity) = identity {
-                    if let Ok(mut peers) = peer_crypto.lock() {
-                        peers.insert(destination_hex.clone(), PeerCrypto { identity });
+        if method_plan.allow_opportunistic {
+            self.report("outbound_attempt: opportunistic");
+            let opportunistic_payload =
+                opportunistic_payload(self.payload.as_slice(), &self.destination);
+            let mut opportunistic_data = PacketDataBuffer::new();
+            if opportunistic_data.write(opportunistic_payload).is_ok() && opportunistic_supported {
+                let opportunistic_packet = Packet {
+                    header: Header {
+                        ifac_flag: IfacFlag::Open,
+                        header_type: HeaderType::Type1,
+                        context_flag: ContextFlag::Unset,
+                        propagation_type: PropagationType::Broadcast,
+                        destination_type: DestinationType::Single,
+                        packet_type: PacketType::Data,
+                        hops: 0,
+                    },
+                    ifac: None,
+                    destination: self.destination_hash,
+                    transport: None,
+                    context: PacketContext::None,
+                    data: opportunistic_data,
+                };
+                let opportunistic_hash = hex::encode(opportunistic_packet.hash().to_bytes());
+                track_receipt_mapping(&self.receipt_map, &opportunistic_hash, &self.message_id);
+                let opportunistic_trace =
+                    self.transport.send_packet_with_trace(opportunistic_packet).await;
+                if !send_outcome_is_sent(opportunistic_trace.outcome) {
+                    if let Ok(mut map) = self.receipt_map.lock() {
+                        map.remove(&opportunistic_hash);
                     }
-                    persist_peer_identity_cache(&peer_crypto, &peer_identity_cache_path);
-
-                    let destination_desc = DestinationDesc {
-                        identity,
-                        address_hash: destination_hash,
-                        name: DestinationName::new("lxmf", "delivery"),
-                    };
-
-                    match shared_send_via_link(
-                        transport.as_ref(),
-                        destination_desc,
-                        payload.as_slice(),
-                        Duration::from_secs(20),
-                    )
-                    .await
-                    {
-                        Ok(LinkSendResult::Packet(packet)) => {
-                            let packet_hash = hex::encode(packet.hash().to_bytes());
-                            track_receipt_mapping(&receipt_map, &packet_hash, &message_id);
-                            trigger_rate_limited_announce(
-                                &transport,
-                                &announce_targets,
-                                &announce_last,
-                                POST_SEND_ANNOUNCE_MIN_INTERVAL_SECS,
-                            );
-                            let _ = receipt_tx.send(ReceiptEvent {
-                                message_id,
-                                status: "sent: link".to_string(),
-                            });
-                            return;
-                        }
-                        Ok(LinkSendResult::Resource(resource_hash)) => {
-                            track_outbound_resource_mapping(
-                                &outbound_resource_map,
-                                &resource_hash,
-                                &message_id,
-                            );
-                            trigger_rate_limited_announce(
-                                &transport,
-                                &announce_targets,
-                                &announce_last,
-                                POST_SEND_ANNOUNCE_MIN_INTERVAL_SECS,
-                            );
-                            let _ = receipt_tx.send(ReceiptEvent {
-                                message_id,
-                                status: "sending: link resource".to_string(),
-                            });
-                            return;
-                        }
-                        Err(err) => {
-                            last_failure = Some(format!("failed: link {err}"));
-                        }
+                    let failed = send_outcome_status("opportunistic", opportunistic_trace.outcome);
+                    last_failure = Some(failed.clone());
+                    if !method_plan.allow_propagated {
+                        self.report(failed);
+                        return;
                     }
                 } else {
-                    last_failure = Some("failed: peer not announced".to_string());
-                }
+                    self.announce();
+                    self.report(send_outcome_status("opportunistic", opportunistic_trace.outcome));
+                    if !method_plan.allow_propagated {
+                        return;
+                    }
 
-                if !method_plan.allow_opportunistic && !method_plan.allow_propagated {
-                    prune_receipt_mappings_for_message(&receipt_map, &message_id);
-                    let _ = receipt_tx.send(ReceiptEvent {
-                        message_id,
-                        status: last_failure
-                            .unwrap_or_else(|| "failed: link delivery unavailable".to_string()),
-                    });
+                    tokio::time::sleep(Duration::from_secs(20)).await;
+                    if is_message_marked_delivered(&self.delivered_messages, &self.message_id) {
+                        return;
+                    }
+                    self.report("retrying: propagated relay after opportunistic timeout");
+                }
+            } else {
+                last_failure =
+                    Some("failed: opportunistic payload too large or unsupported".to_string());
+                if !method_plan.allow_propagated {
+                    self.report(
+                        last_failure
+                            .unwrap_or_else(|| "failed: opportunistic unavailable".to_string()),
+                    );
                     return;
                 }
+                self.report("retrying: propagated relay after opportunistic constraints");
             }
+        }
+
+        if !method_plan.allow_propagated {
+            self.fail(last_failure.unwrap_or_else(|| "failed: delivery unavailable".to_string()));
+            return;
+        }
 
-            if method_plan.allow_opportunistic {
-                let _ = receipt_tx.send(ReceiptEvent {
-                    message_id: message_id.clone(),
-                    status: "outbound_attempt: opportunistic".to_string(),
-                });
-                let opportunistic_payload = opportunistic_payload(payload.as_slice(), &destination);
-                let mut opportunistic_data = PacketDataBuffer::new();
-                if opportunistic_data.write(opportunistic_payload).is_ok()
-                    && opportunistic_supported
+        if let Err(failure) = self.via_propagation(last_failure).await {
+            self.fail(failure);
+        }
+    }
+
+    /// Sends over a link to the destination, retrying link failures up to
+    /// `max_attempts` times. Returns the final failure status otherwise.
+    async fn via_link(&self) -> Result<(), String> {
+        self.report("outbound_attempt: link");
+
+        let mut identity = self.peer_identity;
+        self.transport.request_path(&self.destination_hash, None, None).await;
+
+        if identity.is_none() {
+            let deadline = tokio::time::Instant::now() + Duration::from_secs(12);
+            while tokio::time::Instant::now() < deadline {
+                if let Some(found) = self.transport.destination_identity(&self.destination_hash).await
                 {
-                    let opportunistic_packet = Packet {
-                        header: Header {
-                            ifac_flag: IfacFlag::Open,
-                            header_type: HeaderType::Type1,
-                            context_flag: ContextFlag::Unset,
-                            propagation_type: PropagationType::Broadcast,
-                            destination_type: DestinationType::Single,
-                            packet_type: PacketType::Data,
-                            hops: 0,
-                        },
-                        ifac: None,
-                        destination: destination_hash,
-                        transport: None,
-                        context: PacketContext::None,
-                        data: opportunistic_data,
-                    };
-                    let opportunistic_hash = hex::encode(opportunistic_packet.hash().to_bytes());
-                    track_receipt_mapping(&receipt_map, &opportunistic_hash, &message_id);
-                    let opportunistic_trace =
-                        transport.send_packet_with_trace(opportunistic_packet).await;
-                    if !send_outcome_is_sent(opportunistic_trace.outcome) {
-                        if let Ok(mut map) = receipt_map.lock() {
-                            map.remove(&opportunistic_hash);
-                        }
-                        let failed =
-                            send_outcome_status("opportunistic", opportunistic_trace.outcome);
-                        last_failure = Some(failed.clone());
-                        if !method_plan.allow_propagated {
-                            let _ = receipt_tx.send(ReceiptEvent { message_id, status: failed });
-                            return;
-                        }
-                    } else {
-                        trigger_rate_limited_announce(
-                            &transport,
-                            &announce_targets,
-                            &announce_last,
-                            POST_SEND_ANNOUNCE_MIN_INTERVAL_SECS,
-                        );
-                        let status =
-                            send_outcome_status("opportunistic", opportunistic_trace.outcome);
-                        let _ = receipt_tx
-                            .send(ReceiptEvent { message_id: message_id.clone(), status });
-                        if !method_plan.allow_propagated {
-                            return;
-                        }
-
-                        tokio::time::sleep(Duration::from_secs(20)).await;
-                        if is_message_marked_delivered(&delivered_messages, &message_id) {
-                            return;
-                        }
-                        let _ = receipt_tx.send(ReceiptEvent {
-                            message_id: message_id.clone(),
-                            status: "retrying: propagated relay after opportunistic timeout"
-                                .to_string(),
-                        });
-                    }
-                } else {
-                    last_failure =
-                        Some("failed: opportunistic payload too large or unsupported".to_string());
-                    if !method_plan.allow_propagated {
-                        let _ = receipt_tx.send(ReceiptEvent {
-                            message_id,
-                            status: last_failure
-                                .unwrap_or_else(|| "failed: opportunistic unavailable".to_string()),
-                        });
-                        return;
-                    }
-                    let _ = receipt_tx.send(ReceiptEvent {
-                        message_id: message_id.clone(),
-                        status: "retrying: propagated relay after opportunistic constraints"
-                            .to_string(),
-                    });
+                    identity = Some(found);
+                    break;
                 }
+                tokio::time::sleep(Duration::from_millis(250)).await;
             }
+        }
 
-            if !method_plan.allow_propagated {
-                prune_receipt_mappings_for_message(&receipt_map, &message_id);
-                let _ = receipt_tx.send(ReceiptEvent {
-                    message_id,
-                    status: last_failure
-                        .unwrap_or_else(|| "failed: delivery unavailable".to_string()),
-                });
-                return;
+        let Some(identity) = identity else {
+            return Err("failed: peer not announced".to_string());
+        };
+        if let Ok(mut peers) = self.peer_crypto.lock() {
+            peers.insert(self.destination_hex.clone(), PeerCrypto { identity });
+        }
+        persist_peer_identity_cache(&self.peer_crypto, &self.peer_identity_cache_path);
+
+        let destination_desc = DestinationDesc {
+            identity,
+            address_hash: self.destination_hash,
+            name: DestinationName::new("lxmf", "delivery"),
+        };
+
+        let mut attempt = 1u32;
+        loop {
+            match shared_send_via_link(
+                self.transport.as_ref(),
+                destination_desc,
+                self.payload.as_slice(),
+                Duration::from_secs(20),
+            )
+            .await
+            {
+                Ok(LinkSendResult::Packet(packet)) => {
+                    let packet_hash = hex::encode(packet.hash().to_bytes());
+                    track_receipt_mapping(&self.receipt_map, &packet_hash, &self.message_id);
+                    self.announce();
+                    self.report("sent: link");
+                    return Ok(());
+                }
+                Ok(LinkSendResult::Resource(resource_hash)) => {
+                    track_outbound_resource_mapping(
+                        &self.outbound_resource_map,
+                        &resource_hash,
+                        &self.message_id,
+                    );
+                    self.announce();
+                    self.report("sending: link resource");
+                    return Ok(());
+                }
+                Err(err) => {
+                    if attempt >= self.max_attempts {
+                        return Err(format!("failed: link {err}"));
+                    }
+                    let delay = retry_backoff_delay(self.retry_backoff_ms, attempt);
+                    attempt += 1;
+                    self.report(format_delivery_retry_status(&DeliveryRetry {
+                        attempt,
+                        max_attempts: self.max_attempts,
+                        backoff_ms: u64::try_from(delay.as_millis()).unwrap_or(u64::MAX),
+                        error: err.to_string(),
+                    }));
+                    tokio::time::sleep(delay).await;
+                }
             }
+        }
+    }
 
-            let mut destination_identity = peer_identity;
-            if destination_identity.is_none() {
-                transport.request_path(&destination_hash, None, None).await;
-                let deadline = tokio::time::Instant::now() + Duration::from_secs(8);
-                while tokio::time::Instant::now() < deadline {
-                    if let Some(found) = transport.destination_identity(&destination_hash).await {
-                        destination_identity = Some(found);
-                        if let Ok(mut peers) = peer_crypto.lock() {
-                            peers.insert(destination_hex.clone(), PeerCrypto { identity: found });
-                        }
-                        persist_peer_identity_cache(&peer_crypto, &peer_identity_cache_path);
-                        break;
+    /// Hands the message to the selected or known propagation relays in turn.
+    /// Also succeeds once the message is confirmed delivered some other way.
+    async fn via_propagation(&self, last_failure: Option<String>) -> Result<(), String> {
+        let mut destination_identity = self.peer_identity;
+        if destination_identity.is_none() {
+            self.transport.request_path(&self.destination_hash, None, None).await;
+            let deadline = tokio::time::Instant::now() + Duration::from_secs(8);
+            while tokio::time::Instant::now() < deadline {
+                if let Some(found) = self.transport.destination_identity(&self.destination_hash).await
+                {
+                    destination_identity = Some(found);
+                    if let Ok(mut peers) = self.peer_crypto.lock() {
+                        peers.insert(self.destination_hex.clone(), PeerCrypto { identity: found });
                     }
-                    tokio::time::sleep(Duration::from_millis(250)).await;
+                    persist_peer_identity_cache(&self.peer_crypto, &self.peer_identity_cache_path);
+                    break;
                 }
+                tokio::time::sleep(Duration::from_millis(250)).await;
             }
+        }
 
-            let Some(destination_identity) = destination_identity else {
-                prune_receipt_mappings_for_message(&receipt_map, &message_id);
-                let _ = receipt_tx.send(ReceiptEvent {
-                    message_id,
-                    status: "failed: propagated relay missing destination identity".to_string(),
-                });
-                return;
-            };
+        let Some(destination_identity) = destination_identity else {
+            return Err("failed: propagated relay missing destination identity".to_string());
+        };
 
-            let propagated_payload =
-                match build_propagation_envelope(payload.as_slice(), &destination_identity) {
-                    Ok(encoded) => encoded,
-                    Err(err) => {
-                        prune_receipt_mappings_for_message(&receipt_map, &message_id);
-                        let _ = receipt_tx.send(ReceiptEvent {
-                            message_id,
-                            status: format!("failed: propagated relay encoding error ({err})"),
-                        });
-                        return;
-                    }
-                };
+        let propagated_payload =
+            build_propagation_envelope(self.payload.as_slice(), &destination_identity)
+                .map_err(|err| format!("failed: propagated relay encoding error ({err})"))?;
 
-            let mut relay_candidates =
-                propagation_relay_candidates(&selected_propagation_node, &known_propagation_nodes)
-                    .into_iter()
-                    .take(MAX_ALTERNATIVE_PROPAGATION_RELAYS)
-                    .collect::<Vec<_>>();
-            if relay_candidates.is_empty() {
-                prune_receipt_mappings_for_message(&receipt_map, &message_id);
-                let _ = receipt_tx.send(ReceiptEvent {
-                    message_id,
-                    status: "failed: no propagation relay selected".to_string(),
-                });
-                return;
+        let mut relay_candidates = self
+            .relay_candidates(&[])
+            .into_iter()
+        .take(MAX_ALTERNATIVE_PROPAGATION_RELAYS)
+        .collect::<Vec<_>>();
+        if relay_candidates.is_empty() {
+            return Err("failed: no propagation relay selected".to_string());
+        }
+
+        let mut last_relay_failure =
+            last_failure.unwrap_or_else(|| "failed: propagated relay unavailable".to_string());
+        let mut attempted_relays: Vec<String> = Vec::new();
+        let mut candidate_idx = 0usize;
+        while candidate_idx < relay_candidates.len() {
+            let relay_candidate = relay_candidates[candidate_idx].clone();
+            candidate_idx += 1;
+            let relay_peer = normalize_relay_destination_hash(&self.peer_crypto, &relay_candidate)
+                .unwrap_or(relay_candidate.clone());
+            if !attempted_relays.iter().any(|entry| entry == &relay_peer) {
+                attempted_relays.push(relay_peer.clone());
+            }
+            let Some(relay_destination) = parse_destination_hex(&relay_peer) else {
+                last_relay_failure =
+                    format!("failed: invalid propagation relay hash '{relay_peer}'");
+                continue;
+            };
+            let relay_hash = AddressHash::new(relay_destination);
+            self.transport.request_path(&relay_hash, None, None).await;
+            let relay_known_deadline = tokio::time::Instant::now() + Duration::from_secs(8);
+            let mut relay_known = self.transport.destination_identity(&relay_hash).await.is_some();
+            while !relay_known && tokio::time::Instant::now() < relay_known_deadline {
+                tokio::time::sleep(Duration::from_millis(250)).await;
+                relay_known = self.transport.destination_identity(&relay_hash).await.is_some();
+            }
+            if !relay_known {
+                last_relay_failure = "failed: propagation relay not announced".to_string();
+                if let Some(next_relay) =
+                    self.next_relay(&relay_candidates, candidate_idx, &attempted_relays).await
+                {
+                    relay_candidates.push(next_relay);
+                }
+                continue;
             }
 
-            let mut last_relay_failure =
-                last_failure.unwrap_or_else(|| "failed: propagated relay unavailable".to_string());
-            let mut attempted_relays: Vec<String> = Vec::new();
-            let mut candidate_idx = 0usize;
-            while candidate_idx < relay_candidates.len() {
-                let relay_candidate = relay_candidates[candidate_idx].clone();
-                candidate_idx += 1;
-                let relay_peer = normalize_relay_destination_hash(&peer_crypto, &relay_candidate)
-                    .unwrap_or(relay_candidate.clone());
-                if !attempted_relays.iter().any(|entry| entry == &relay_peer) {
-                    attempted_relays.push(relay_peer.clone());
+            for attempt in 1..=2u8 {
+                if is_message_marked_delivered(&self.delivered_messages, &self.message_id) {
+                    return Ok(());
                 }
-                let Some(relay_destination) = parse_destination_hex(&relay_peer) else {
-                    last_relay_failure =
-                        format!("failed: invalid propagation relay hash '{relay_peer}'");
-                    continue;
-                };
-                let relay_hash = AddressHash::new(relay_destination);
-                transport.request_path(&relay_hash, None, None).await;
-                let relay_known_deadline = tokio::time::Instant::now() + Duration::from_secs(8);
-                let mut relay_known = transport.destination_identity(&relay_hash).await.is_some();
-                while !relay_known && tokio::time::Instant::now() < relay_known_deadline {
-                    tokio::time::sleep(Duration::from_millis(250)).await;
-                    relay_known = transport.destination_identity(&relay_hash).await.is_some();
+                self.report(format!(
+                    "retrying: propagated relay attempt {attempt}/2 via {}",
+                    short_hash_prefix(&relay_peer)
+                ));
+
+                let mut relay_data = PacketDataBuffer::new();
+                if relay_data.write(propagated_payload.as_slice()).is_err() {
+                    return Err("failed: propagated relay payload too large".to_string());
                 }
-                if !relay_known {
-                    last_relay_failure = "failed: propagation relay not announced".to_string();
-                    if candidate_idx < relay_candidates.len() {
-                        let _ = receipt_tx.send(ReceiptEvent {
-                            message_id: message_id.clone(),
-                            status: format_relay_request_status(attempted_relays.as_slice()),
-                        });
-                    } else if let Some(external_relay) = wait_for_external_relay_selection(
-                        &selected_propagation_node,
-                        &peer_crypto,
-                        attempted_relays.as_slice(),
-                        Duration::from_secs(8),
-                    )
-                    .await
-                    {
-                        relay_candidates.push(external_relay);
+                let relay_packet = Packet {
+                    header: Header {
+                        ifac_flag: IfacFlag::Open,
+                        header_type: HeaderType::Type1,
+                        context_flag: ContextFlag::Unset,
+                        propagation_type: PropagationType::Broadcast,
+                        destination_type: DestinationType::Single,
+                        packet_type: PacketType::Data,
+                        hops: 0,
+                    },
+                    ifac: None,
+                    destination: relay_hash,
+                    transport: None,
+                    context: PacketContext::None,
+                    data: relay_data,
+                };
+                let relay_packet_hash = hex::encode(relay_packet.hash().to_bytes());
+                track_receipt_mapping(&self.receipt_map, &relay_packet_hash, &self.message_id);
+                let relay_trace = self.transport.send_packet_with_trace(relay_packet).await;
+                if send_outcome_is_sent(relay_trace.outcome) {
+                    self.announce();
+                    if let Ok(mut selected) = self.selected_propagation_node.lock() {
+                        *selected = Some(relay_peer.clone());
                     }
-                    continue;
+                    self.report(send_outcome_status("propagated relay", relay_trace.outcome));
+                    return Ok(());
                 }
-
-                for attempt in 1..=2u8 {
-                    if is_message_marked_delivered(&delivered_messages, &message_id) {
-                        return;
-                    }
-                    let _ = receipt_tx.send(ReceiptEvent {
-                        message_id: message_id.clone(),
-                        status: format!(
-                            "retrying: propagated relay attempt {attempt}/2 via {}",
-                            short_hash_prefix(&relay_peer)
-                        ),
-                    });
-
-                    let mut relay_data = PacketDataBuffer::new();
-                    if relay_data.write(propagated_payload.as_slice()).is_err() {
-                        prune_receipt_mappings_for_message(&receipt_map, &message_id);
-                        let _ = receipt_tx.send(ReceiptEvent {
-                            message_id,
-                            status: "failed: propagated relay payload too large".to_string(),
-                        });
-                        return;
-                    }
-                    let relay_packet = Packet {
-                        header: Header {
-                            ifac_flag: IfacFlag::Open,
-                            header_type: HeaderType::Type1,
-                            context_flag: ContextFlag::Unset,
-                            propagation_type: PropagationType::Broadcast,
-                            destination_type: DestinationType::Single,
-                            packet_type: PacketType::Data,
-                            hops: 0,
-                        },
-                        ifac: None,
-                        destination: relay_hash,
-                        transport: None,
-                        context: PacketContext::None,
-                        data: relay_data,
-                    };
-                    let relay_packet_hash = hex::encode(relay_packet.hash().to_bytes());
-                    track_receipt_mapping(&receipt_map, &relay_packet_hash, &message_id);
-                    let relay_trace = transport.send_packet_with_trace(relay_packet).await;
-                    if send_outcome_is_sent(relay_trace.outcome) {
-                        trigger_rate_limited_announce(
-                            &transport,
-                            &announce_targets,
-                            &announce_last,
-                            POST_SEND_ANNOUNCE_MIN_INTERVAL_SECS,
-                        );
-                        if let Ok(mut selected) = selected_propagation_node.lock() {
-                            *selected = Some(relay_peer.clone());
-                        }
-                        let _ = receipt_tx.send(ReceiptEvent {
-                            message_id,
-                            status: send_outcome_status("propagated relay", relay_trace.outcome),
-                        });
-                        return;
-                    }
-                    if let Ok(mut map) = receipt_map.lock() {
-                        map.remove(&relay_packet_hash);
-                    }
-                    last_relay_failure =
-                        send_outcome_status("propagated relay", relay_trace.outcome);
-                    if attempt < 2 {
-                        tokio::time::sleep(Duration::from_secs(2)).await;
-                    }
+                if let Ok(mut map) = self.receipt_map.lock() {
+                    map.remove(&relay_packet_hash);
                 }
-
-                if candidate_idx < relay_candidates.len() {
-                    let _ = receipt_tx.send(ReceiptEvent {
-                        message_id: message_id.clone(),
-                        status: format_relay_request_status(attempted_relays.as_slice()),
-                    });
-                } else if let Some(external_relay) = wait_for_external_relay_selection(
-                    &selected_propagation_node,
-                    &peer_crypto,
-                    attempted_relays.as_slice(),
-                    Duration::from_secs(8),
-                )
-                .await
-                {
-                    relay_candidates.push(external_relay);
+                last_relay_failure = send_outcome_status("propagated relay", relay_trace.outcome);
+                if attempt < 2 {
+                    tokio::time::sleep(Duration::from_secs(2)).await;
                 }
             }
 
-            if !attempted_relays.is_empty() {
-                let _ = receipt_tx.send(ReceiptEvent {
-                    message_id: message_id.clone(),
-                    status: format_relay_request_status(attempted_relays.as_slice()),
-                });
+            if let Some(next_relay) =
+                self.next_relay(&relay_candidates, candidate_idx, &attempted_relays).await
+            {
+                relay_candidates.push(next_relay);
             }
-            prune_receipt_mappings_for_message(&receipt_map, &message_id);
-            let _ = receipt_tx.send(ReceiptEvent { message_id, status: last_relay_failure });
-        });
+        }
 
-        Ok(())
+        if !attempted_relays.is_empty() {
+            self.report(format_relay_request_status(attempted_relays.as_slice()));
+        }
+        Err(last_relay_failure)
+    }
+
+    /// Picks the relay to try after a failed one. Remaining candidates go
+    /// first; once they are used up, a node learned since the attempt started
+    /// is taken while under the relay cap, and otherwise an externally
+    /// selected relay is awaited.
+    async fn next_relay(
+        &self,
+        relay_candidates: &[String],
+        candidate_idx: usize,
+        attempted_relays: &[String],
+    ) -> Option<String> {
+        if candidate_idx < relay_candidates.len() {
+            self.report(format_relay_request_status(attempted_relays));
+            return None;
+        }
+        if attempted_relays.len() < MAX_ALTERNATIVE_PROPAGATION_RELAYS {
+            let excluded = [relay_candidates, attempted_relays].concat();
+            if let Some(next_relay) = self.relay_candidates(&excluded).into_iter().next() {
+                self.report(format_relay_request_status(attempted_relays));
+                return Some(next_relay);
+            }
+        }
+        wait_for_external_relay_selection(
+            &self.selected_propagation_node,
+            &self.peer_crypto,
+            attempted_relays,
+            Duration::from_secs(8),
+        )
+        .await
     }
 }
@@ -4,8 +4,9 @@ use super::announce_helpers::{
 use super::inbound_helpers::{annotate_inbound_transport_metadata, decode_inbound_payload};
 use super::peer_cache::persist_peer_identity_cache;
 use super::{
-    handle_receipt_event, resolve_link_destination, AnnounceBridge, EmbeddedTransportBridge,
-    PeerAnnounceMeta, PeerCrypto, ReceiptEvent, RpcDaemon, STARTUP_ANNOUNCE_BURST_DELAYS_SECS,
+    accept_inbound_record, handle_receipt_event, resolve_link_destination, AnnounceBridge,
+    EmbeddedTransportBridge, PeerAnnounceMeta, PeerCrypto, ReceiptEvent, RpcDaemon,
+    STARTUP_ANNOUNCE_BURST_DELAYS_SECS,
 };
 #[cfg(reticulum_api_v2)]
 use crate::helpers::{pn_peering_cost_from_app_data, pn_stamp_cost_flexibility_from_app_data};
@@ -27,6 +28,22 @@ fn inbound_payload_mode(mode: ReceivedPayloadMode) -> InboundPayloadMode {
     }
 }
 
+/// A message the peer has read was delivered even if its delivery receipt has
+/// not arrived yet, so an in-flight send stops waiting for one. `message_ids`
+/// only holds receipts `accept_inbound_record` matched to messages sent to the
+/// reporting peer.
+fn mark_read_messages_delivered(
+    delivered_messages: &Arc<Mutex<HashSet<String>>>,
+    message_ids: Vec<String>,
+) {
+    if message_ids.is_empty() {
+        return;
+    }
+    if let Ok(mut delivered) = delivered_messages.lock() {
+        delivered.extend(message_ids);
+    }
+}
+
 pub(super) fn spawn_receipt_worker(
     daemon: Rc<RpcDaemon>,
     mut receipt_rx: UnboundedReceiver<ReceiptEvent>,
@@ -58,6 +75,7 @@ pub(super) fn spawn_transport_workers(
     transport: Arc<Transport>,
     daemon: Rc<RpcDaemon>,
     receipt_tx: UnboundedSender<ReceiptEvent>,
+    delivered_messages: Arc<Mutex<HashSet<String>>>,
     outbound_resource_map: Arc<Mutex<HashMap<String, String>>>,
     peer_crypto: Arc<Mutex<HashMap<String, PeerCrypto>>>,
     peer_announce_meta: Arc<Mutex<HashMap<String, PeerAnnounceMeta>>>,
@@ -66,6 +84,7 @@ pub(super) fn spawn_transport_workers(
     shutdown_tx: &watch::Sender<bool>,
 ) {
     let daemon_inbound = daemon.clone();
+    let inbound_delivered = delivered_messages.clone();
     let inbound_transport = transport.clone();
     let mut shutdown_rx = shutdown_tx.subscribe();
     tokio::task::spawn_local(async move {
@@ -92,7 +111,9 @@ pub(super) fn spawn_transport_workers(
                                 )
                             {
                                 annotate_inbound_transport_metadata(&mut record, &event);
-                                let _ = daemon_inbound.accept_inbound(record);
+                                if let Ok(read) = accept_inbound_record(&daemon_inbound, record) {
+                                    mark_read_messages_delivered(&inbound_delivered, read);
+                                }
                             }
                         }
                         Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
@@ -128,7 +149,15 @@ pub(super) fn spawn_transport_workers(
                                             &complete.data,
                                             InboundPayloadMode::FullWire,
                                         ) {
-                                            let _ = daemon_resource_inbound.accept_inbound(record);
+                                            if let Ok(read) = accept_inbound_record(
+                                                &daemon_resource_inbound,
+                                                record,
+                                            ) {
+                                                mark_read_messages_delivered(
+                                                    &delivered_messages,
+                                                    read,
+                                                );
+                                            }
                                         }
                                     }
                                 }
@@ -4,7 +4,6 @@ use reticulum::identity::PrivateIdentity;
 use reticulum::rpc::InterfaceRecord;
 use serde_json::Value;
 use std::net::SocketAddr;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 pub(super) fn parse_bind_host_port(bind: &str) -> Option<(String, u16)> {
     if let Ok(addr) = bind.parse::<SocketAddr>() {
@@ -27,9 +26,19 @@ pub(super) fn source_hash_from_private_key_hex(private_key_hex: &str) -> Result<
     Ok(hex::encode(identity.address_hash().as_slice()))
 }
 
-pub(super) fn generate_message_id() -> String {
-    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
-    format!("lxmf-{now}")
+/// Derives the send source from `source_private_key`, rejecting an explicit
+/// `source` that names a different identity instead of silently preferring the key.
+pub(super) fn verified_source_hash(
+    source: Option<&str>,
+    private_key_hex: &str,
+) -> Result<String, LxmfError> {
+    let derived = source_hash_from_private_key_hex(private_key_hex)?;
+    match source.map(str::trim).filter(|source| !source.is_empty()) {
+        Some(source) if !source.eq_ignore_ascii_case(&derived) => Err(LxmfError::Io(format!(
+            "source {source} does not match source_private_key identity {derived}"
+        ))),
+        _ => Ok(derived),
+    }
 }
 
 pub(super) fn now_epoch_secs() -> u64 {
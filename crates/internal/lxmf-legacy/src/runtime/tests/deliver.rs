@@ -0,0 +1,274 @@
+use super::super::{
+    EmbeddedTransportBridge, OutboundDeliveryOptionsCompat, PeerCrypto,
+    PropagationSelectionStrategy, ReceiptEvent,
+};
+use reticulum::destination::{DestinationName, SingleInputDestination};
+use reticulum::identity::PrivateIdentity;
+use reticulum::iface::{
+    Interface, InterfaceChannel, InterfaceRxSender, InterfaceTxReceiver, RxMessage,
+};
+use reticulum::storage::messages::MessageRecord;
+use reticulum::transport::{Transport, TransportConfig};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::AtomicU64;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+
+const SETTLE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// In-memory interface joining two transports without sockets.
+struct MemoryInterface;
+
+impl Interface for MemoryInterface {
+    fn mtu() -> usize {
+        2048
+    }
+}
+
+async fn forward(
+    mut outbound: InterfaceTxReceiver,
+    inbound: InterfaceRxSender,
+    address: reticulum::hash::AddressHash,
+) {
+    while let Some(message) = outbound.recv().await {
+        if inbound.send(RxMessage { address, packet: message.packet }).await.is_err() {
+            return;
+        }
+    }
+}
+
+async fn connect(left: &Transport, right: &Transport) {
+    let left_channel: InterfaceChannel =
+        left.iface_manager().lock().await.new_context(MemoryInterface).channel;
+    let right_channel: InterfaceChannel =
+        right.iface_manager().lock().await.new_context(MemoryInterface).channel;
+    let (left_address, right_address) = (left_channel.address, right_channel.address);
+    let (left_rx, left_tx) = left_channel.split();
+    let (right_rx, right_tx) = right_channel.split();
+    tokio::spawn(forward(left_tx, right_rx, right_address));
+    tokio::spawn(forward(right_tx, left_rx, left_address));
+}
+
+/// A sending node and its peer on the same in-memory segment.
+struct Segment {
+    bridge: EmbeddedTransportBridge,
+    receipts: UnboundedReceiver<ReceiptEvent>,
+    peer: Transport,
+    peer_crypto: Arc<Mutex<HashMap<String, PeerCrypto>>>,
+    known_propagation_nodes: Arc<Mutex<HashSet<String>>>,
+    _cache_dir: tempfile::TempDir,
+}
+
+impl Segment {
+    async fn new() -> Self {
+        let signer = PrivateIdentity::new_from_name("deliver-sender");
+        // Link packets are addressed to the link id, which has no path table
+        // entry, so the sender must be allowed to broadcast them.
+        let transport = Transport::new(TransportConfig::new("sender", &signer, true));
+        let peer_identity = PrivateIdentity::new_from_name("deliver-peer");
+        let peer = Transport::new(TransportConfig::new("peer", &peer_identity, false));
+        connect(&transport, &peer).await;
+
+        let mut source_hash = [0u8; 16];
+        source_hash.copy_from_slice(
+            SingleInputDestination::new(signer.clone(), DestinationName::new("lxmf", "delivery"))
+                .desc
+                .address_hash
+                .as_slice(),
+        );
+        let cache_dir = tempfile::tempdir().expect("tempdir");
+        let peer_crypto = Arc::new(Mutex::new(HashMap::new()));
+        let known_propagation_nodes = Arc::new(Mutex::new(HashSet::new()));
+        let (receipt_tx, receipts) = unbounded_channel();
+        let bridge = EmbeddedTransportBridge::new(
+            Arc::new(transport),
+            signer,
+            source_hash,
+            Vec::new(),
+            Arc::new(AtomicU64::new(0)),
+            peer_crypto.clone(),
+            cache_dir.path().join("peers.json"),
+            Arc::new(Mutex::new(None)),
+            known_propagation_nodes.clone(),
+            Arc::new(Mutex::new(HashMap::new())),
+            PropagationSelectionStrategy::Selected,
+            Arc::new(Mutex::new(HashMap::new())),
+            Arc::new(Mutex::new(HashMap::new())),
+            Arc::new(Mutex::new(HashSet::new())),
+            receipt_tx,
+        );
+        Self { bridge, receipts, peer, peer_crypto, known_propagation_nodes, _cache_dir: cache_dir }
+    }
+
+    /// Registers `aspect` on the peer and announces it to the sender,
+    /// returning the destination hash once the sender has learned it.
+    async fn announce_peer(&mut self, aspect: &str) -> String {
+        let identity = PrivateIdentity::new_from_name("deliver-peer");
+        let destination =
+            self.peer.add_destination(identity, DestinationName::new("lxmf", aspect)).await;
+        let address = destination.lock().await.desc.address_hash;
+        self.peer.send_announce(&destination, None).await;
+        tokio::time::timeout(SETTLE_TIMEOUT, async {
+            while self.bridge.transport.destination_identity(&address).await.is_none() {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .expect("announce reaches the sender");
+        hex::encode(address.as_slice())
+    }
+
+    fn deliver(&self, destination: &str, options: OutboundDeliveryOptionsCompat) {
+        let record = MessageRecord {
+            id: "msg-1".into(),
+            source: hex::encode(self.bridge.delivery_source_hash),
+            destination: destination.to_string(),
+            title: String::new(),
+            content: "hello".into(),
+            timestamp: 0,
+            direction: "out".into(),
+            fields: None,
+            receipt_status: None,
+            read_ts_ms: None,
+        };
+        self.bridge.deliver_with_options(&record, options).expect("delivery accepted");
+    }
+
+    /// Collects reported statuses up to the first sent or failed one.
+    async fn statuses(&mut self) -> Vec<String> {
+        let mut statuses = Vec::new();
+        tokio::time::timeout(SETTLE_TIMEOUT, async {
+            while let Some(event) = self.receipts.recv().await {
+                let terminal =
+                    event.status.starts_with("sent") || event.status.starts_with("failed");
+                statuses.push(event.status);
+                if terminal {
+                    return;
+                }
+            }
+        })
+        .await
+        .unwrap_or_else(|_| panic!("delivery did not settle: {statuses:?}"));
+        statuses
+    }
+}
+
+fn options(method: &str) -> OutboundDeliveryOptionsCompat {
+    OutboundDeliveryOptionsCompat { method: Some(method.to_string()), ..Default::default() }
+}
+
+fn unreachable_peer() -> (String, PeerCrypto) {
+    let identity = *PrivateIdentity::new_from_name("deliver-offline").as_identity();
+    let destination = SingleInputDestination::new(
+        PrivateIdentity::new_from_name("deliver-offline"),
+        DestinationName::new("lxmf", "delivery"),
+    );
+    (hex::encode(destination.desc.address_hash.as_slice()), PeerCrypto { identity })
+}
+
+#[tokio::test]
+async fn direct_delivery_sends_over_a_link() {
+    let mut segment = Segment::new().await;
+    let peer = segment.announce_peer("delivery").await;
+
+    segment.deliver(&peer, options("direct"));
+
+    let statuses = segment.statuses().await;
+    assert_eq!(statuses.first().map(String::as_str), Some("outbound_attempt: link"));
+    assert_eq!(statuses.last().map(String::as_str), Some("sent: link"));
+    assert!(segment.peer_crypto.lock().expect("peers").contains_key(&peer));
+}
+
+#[tokio::test]
+async fn opportunistic_delivery_sends_a_single_packet() {
+    let mut segment = Segment::new().await;
+    let peer = segment.announce_peer("delivery").await;
+
+    segment.deliver(&peer, options("opportunistic"));
+
+    let statuses = segment.statuses().await;
+    assert_eq!(statuses.first().map(String::as_str), Some("outbound_attempt: opportunistic"));
+    let last = statuses.last().expect("terminal status");
+    assert!(last.starts_with("sent: opportunistic"), "{statuses:?}");
+}
+
+#[tokio::test]
+async fn failed_opportunistic_delivery_falls_back_to_a_propagation_relay() {
+    let mut segment = Segment::new().await;
+    let relay = segment.announce_peer("propagation").await;
+    segment.known_propagation_nodes.lock().expect("nodes").insert(relay.clone());
+    // Known from the identity cache but never announced, so the transport
+    // cannot encrypt an opportunistic packet to it.
+    let (peer, crypto) = unreachable_peer();
+    segment.peer_crypto.lock().expect("peers").insert(peer.clone(), crypto);
+
+    segment.deliver(
+        &peer,
+        OutboundDeliveryOptionsCompat { try_propagation_on_fail: true, ..options("opportunistic") },
+    );
+
+    let statuses = segment.statuses().await;
+    assert!(
+        statuses.iter().any(|status| status.starts_with("retrying: propagated relay attempt 1/2")),
+        "{statuses:?}"
+    );
+    let last = statuses.last().expect("terminal status");
+    assert!(last.starts_with("sent: propagated relay"), "{statuses:?}");
+    assert_eq!(
+        segment.bridge.selected_propagation_node.lock().expect("selected").as_deref(),
+        Some(relay.as_str())
+    );
+}
+
+#[tokio::test]
+async fn delivery_without_a_route_or_relay_fails() {
+    let mut segment = Segment::new().await;
+    let (peer, crypto) = unreachable_peer();
+    segment.peer_crypto.lock().expect("peers").insert(peer.clone(), crypto);
+
+    segment.deliver(&peer, options("opportunistic"));
+    let statuses = segment.statuses().await;
+    let last = statuses.last().expect("terminal status");
+    assert!(last.starts_with("failed: opportunistic"), "{statuses:?}");
+
+    segment.deliver(&peer, options("propagated"));
+    let statuses = segment.statuses().await;
+    assert_eq!(statuses.last().map(String::as_str), Some("failed: no propagation relay selected"));
+}
+
+#[tokio::test]
+async fn delivery_with_source_private_key_rejects_unparseable_source() {
+    let segment = Segment::new().await;
+    let (destination, _) = unreachable_peer();
+    let signer = PrivateIdentity::new_from_name("deliver-sender");
+    let mut record = MessageRecord {
+        id: "msg-source".into(),
+        source: "not-a-destination-hash".into(),
+        destination,
+        title: String::new(),
+        content: "hello".into(),
+        timestamp: 0,
+        direction: "out".into(),
+        fields: None,
+        receipt_status: None,
+        read_ts_ms: None,
+    };
+    let keyed = || OutboundDeliveryOptionsCompat {
+        source_private_key: Some(hex::encode(signer.to_private_key_bytes())),
+        ..options("opportunistic")
+    };
+
+    let err = segment
+        .bridge
+        .deliver_with_options(&record, keyed())
+        .expect_err("unparseable source rejected");
+    assert!(err.to_string().contains("invalid destination hash"), "{err}");
+
+    record.source = "00112233445566778899aabbccddeeff".into();
+    let err = segment
+        .bridge
+        .deliver_with_options(&record, keyed())
+        .expect_err("mismatched source rejected");
+    assert!(err.to_string().contains("does not match source_private_key"), "{err}");
+}
@@ -1,12 +1,16 @@
 use super::super::{
-    build_send_params_with_source, build_wire_message, can_send_opportunistic,
-    decode_inbound_payload, rmpv_to_json, sanitize_outbound_wire_fields, InboundPayloadMode,
-    SendMessageRequest,
+    accept_inbound_record, build_send_params_with_source, build_wire_message,
+    can_send_opportunistic, decode_inbound_payload, extract_outbound_delivery_options,
+    inbound_read_receipt, rmpv_to_json, sanitize_outbound_wire_fields, verified_source_hash,
+    InboundPayloadMode, ProfileSettings, ReadReceipt, SendMessageRequest, MAX_DELIVERY_RETRIES,
 };
 use crate::constants::FIELD_COMMANDS;
 use crate::message::Message;
 use crate::payload_fields::{CommandEntry, WireFields};
 use reticulum::identity::PrivateIdentity;
+use reticulum::receipt::record_receipt_status;
+use reticulum::rpc::{RpcDaemon, RpcRequest};
+use reticulum::storage::messages::{MessageRecord, MessagesStore};
 use serde_json::{json, Value};
 
 #[test]
@@ -78,11 +82,15 @@ fn build_send_params_includes_expected_rpc_keys() {
         stamp_cost: Some(7),
         include_ticket: true,
         try_propagation_on_fail: true,
+        scheduled_ts_ms: Some(1_700_000_000_000),
     };
 
-    let prepared =
-        build_send_params_with_source(request, "00112233445566778899aabbccddeeff".to_string())
-            .expect("prepared");
+    let prepared = build_send_params_with_source(
+        request,
+        "00112233445566778899aabbccddeeff".to_string(),
+        &ProfileSettings::default(),
+    )
+    .expect("prepared");
     assert_eq!(prepared.id, "msg-123");
     assert_eq!(prepared.source, "00112233445566778899aabbccddeeff");
     assert_eq!(prepared.destination, "ffeeddccbbaa99887766554433221100");
@@ -97,6 +105,36 @@ fn build_send_params_includes_expected_rpc_keys() {
         )
     );
     assert_eq!(prepared.params["fields"]["k"], Value::String("v".to_string()));
+    assert_eq!(prepared.params["scheduled_ts_ms"], Value::from(1_700_000_000_000_u64));
+}
+
+#[test]
+fn delivery_options_parse_retry_schedule_and_default_to_single_attempt() {
+    let record = |fields: Option<Value>| MessageRecord {
+        id: "msg-retry".to_string(),
+        source: "src".to_string(),
+        destination: "dst".to_string(),
+        title: String::new(),
+        content: "hello".to_string(),
+        timestamp: 0,
+        direction: "out".to_string(),
+        fields,
+        receipt_status: None,
+        read_ts_ms: None,
+    };
+
+    let defaults = extract_outbound_delivery_options(&record(None));
+    assert_eq!((defaults.max_retries, defaults.retry_backoff_ms), (0, 0));
+
+    let options = extract_outbound_delivery_options(&record(Some(json!({
+        "__delivery_options": { "max_retries": 3, "retry_backoff_ms": "1500" }
+    }))));
+    assert_eq!((options.max_retries, options.retry_backoff_ms), (3, 1_500));
+
+    let excessive = extract_outbound_delivery_options(&record(Some(json!({
+        "__delivery_options": { "max_retries": 1_000_000 }
+    }))));
+    assert_eq!(excessive.max_retries, MAX_DELIVERY_RETRIES);
 }
 
 #[test]
@@ -155,6 +193,23 @@ fn sanitize_outbound_wire_fields_preserves_canonical_attachments() {
     assert_eq!(sanitized.get("attachments"), fields.get("attachments"));
 }
 
+#[test]
+fn thread_field_survives_sanitize_and_wire_roundtrip() {
+    let signer = PrivateIdentity::new_from_name("runtime-thread-field");
+    let fields = json!({
+        "__delivery_options": { "method": "direct" },
+        "8": "parent-message-id",
+    });
+    let sanitized = sanitize_outbound_wire_fields(Some(&fields)).expect("sanitized");
+    assert_eq!(sanitized, json!({ "8": "parent-message-id" }));
+
+    let wire = build_wire_message([0x11; 16], [0x22; 16], "", "reply", Some(sanitized), &signer)
+        .expect("wire");
+    let decoded = Message::from_wire(&wire).expect("decode");
+    let parsed = decoded.fields.as_ref().and_then(rmpv_to_json).expect("fields");
+    assert_eq!(parsed.get("8"), Some(&json!("parent-message-id")));
+}
+
 #[test]
 fn build_wire_message_rejects_ambiguous_attachment_text_data() {
     let signer = PrivateIdentity::new_from_name("runtime-ambiguous-attachment");
@@ -271,6 +326,197 @@ fn build_send_params_rejects_empty_destination() {
         content: "body".to_string(),
         ..SendMessageRequest::default()
     };
-    let err = build_send_params_with_source(request, "source".to_string()).expect_err("err");
+    let err =
+        build_send_params_with_source(request, "source".to_string(), &ProfileSettings::default())
+            .expect_err("err");
     assert!(err.to_string().contains("destination is required"));
 }
+
+#[test]
+fn verified_source_hash_accepts_matching_source_and_key() {
+    let signer = PrivateIdentity::new_from_name("source-verify");
+    let key_hex = hex::encode(signer.to_private_key_bytes());
+    let expected = hex::encode(signer.address_hash().as_slice());
+
+    assert_eq!(verified_source_hash(Some(&expected), &key_hex).expect("matching"), expected);
+    assert_eq!(
+        verified_source_hash(Some(&expected.to_ascii_uppercase()), &key_hex).expect("case"),
+        expected
+    );
+    assert_eq!(verified_source_hash(None, &key_hex).expect("key only"), expected);
+}
+
+#[test]
+fn verified_source_hash_rejects_mismatched_source() {
+    let signer = PrivateIdentity::new_from_name("source-verify");
+    let key_hex = hex::encode(signer.to_private_key_bytes());
+
+    let err = verified_source_hash(Some("00112233445566778899aabbccddeeff"), &key_hex)
+        .expect_err("mismatched source should be rejected");
+    assert!(err.to_string().contains("does not match source_private_key"));
+}
+
+#[test]
+fn read_receipts_upgrade_delivered_messages_without_being_stored() {
+    let record = |id: &str, direction: &str, fields: Option<Value>| MessageRecord {
+        id: id.to_string(),
+        source: "src".to_string(),
+        destination: "dst".to_string(),
+        title: String::new(),
+        content: String::new(),
+        timestamp: 1_700_000_000,
+        direction: direction.to_string(),
+        fields,
+        receipt_status: None,
+        read_ts_ms: None,
+    };
+    let stored = |daemon: &RpcDaemon| {
+        let response = daemon
+            .handle_rpc(RpcRequest { id: 1, method: "list_messages".into(), params: None })
+            .expect("list_messages");
+        response.result.expect("result")["messages"].as_array().cloned().unwrap_or_default()
+    };
+
+    let daemon = RpcDaemon::test_instance();
+    daemon.accept_inbound(record("msg-1", "out", None)).expect("seed outbound");
+    record_receipt_status(&daemon, "msg-1", "delivered").expect("delivered");
+    let messages = stored(&daemon);
+    assert_eq!(messages[0]["receipt_status"], "delivered");
+    assert!(messages[0].get("read_ts_ms").is_none());
+
+    assert_eq!(inbound_read_receipt(&record("plain", "in", Some(json!({"16": {}})))), None);
+    let by_timestamp = record("r0", "in", Some(json!({"16": {"read_of": "msg-1"}})));
+    assert_eq!(
+        inbound_read_receipt(&by_timestamp),
+        Some(ReadReceipt {
+            message_ids: vec!["msg-1".to_string()],
+            read_ts_ms: Some(1_700_000_000_000),
+        })
+    );
+
+    let forged = record("r1", "in", Some(json!({"16": {"read_of": ["msg-1"], "read_ts_ms": 7}})));
+    assert!(accept_inbound_record(&daemon, forged).expect("forged").is_empty());
+    assert_eq!(stored(&daemon)[0]["receipt_status"], "delivered");
+
+    let mut receipt =
+        record("r2", "in", Some(json!({"16": {"read_of": ["msg-1"], "read_ts_ms": 42}})));
+    receipt.source = "dst".to_string();
+    assert_eq!(accept_inbound_record(&daemon, receipt).expect("read"), vec!["msg-1".to_string()]);
+    record_receipt_status(&daemon, "msg-1", "delivered").expect("late delivered");
+
+    let messages = stored(&daemon);
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0]["receipt_status"], "read");
+    assert_eq!(messages[0]["read_ts_ms"], 42);
+}
+
+#[test]
+fn auto_read_receipts_answer_new_messages_only_when_opted_in() {
+    let inbound = |id: &str, source: &str, fields: Option<Value>| MessageRecord {
+        id: id.to_string(),
+        source: source.to_string(),
+        destination: "local".to_string(),
+        title: String::new(),
+        content: "hello".to_string(),
+        timestamp: 1_700_000_000,
+        direction: "in".to_string(),
+        fields,
+        receipt_status: None,
+        read_ts_ms: None,
+    };
+    let receipts = |daemon: &RpcDaemon| {
+        let response = daemon
+            .handle_rpc(RpcRequest { id: 1, method: "list_messages".into(), params: None })
+            .expect("list_messages");
+        response.result.expect("result")["messages"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|message| message["direction"] == "out")
+            .collect::<Vec<_>>()
+    };
+    let set_policy = |daemon: &RpcDaemon, policy: Value| {
+        daemon
+            .handle_rpc(RpcRequest {
+                id: 2,
+                method: "set_delivery_policy".into(),
+                params: Some(policy),
+            })
+            .expect("set_delivery_policy");
+    };
+
+    let daemon = RpcDaemon::test_instance();
+    accept_inbound_record(&daemon, inbound("m-off", "peer-a", None)).expect("accept");
+    assert!(receipts(&daemon).is_empty(), "auto read receipts are off by default");
+
+    set_policy(&daemon, json!({ "auto_read_receipt_sources": ["PEER-A"] }));
+    accept_inbound_record(&daemon, inbound("m-other", "peer-b", None)).expect("accept");
+    assert!(receipts(&daemon).is_empty());
+    accept_inbound_record(&daemon, inbound("m-1", "peer-a", None)).expect("accept");
+    accept_inbound_record(&daemon, inbound("m-1", "peer-a", None)).expect("redelivery");
+    let sent = receipts(&daemon);
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0]["source"], "local");
+    assert_eq!(sent[0]["destination"], "peer-a");
+    assert_eq!(sent[0]["fields"]["16"]["read_of"], json!(["m-1"]));
+
+    let peer_receipt = inbound("r-1", "peer-a", Some(json!({"16": {"read_of": ["read-m-1"]}})));
+    accept_inbound_record(&daemon, peer_receipt).expect("peer read receipt");
+    assert_eq!(receipts(&daemon).len(), 1, "read receipts are never answered");
+
+    set_policy(&daemon, json!({ "auto_read_receipts": true }));
+    accept_inbound_record(&daemon, inbound("m-2", "peer-b", None)).expect("accept");
+    assert_eq!(receipts(&daemon).len(), 2);
+}
+
+#[test]
+fn scheduled_sends_are_held_and_survive_a_restart() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let db_path = temp.path().join("reticulum.db");
+    let now_ms = reticulum::time::now_epoch_secs_u64() * 1_000;
+    let send = |daemon: &RpcDaemon, id: &str, scheduled_ts_ms: u64| {
+        daemon
+            .handle_rpc(RpcRequest {
+                id: 1,
+                method: "send_message_v2".into(),
+                params: Some(json!({
+                    "id": id,
+                    "source": "src",
+                    "destination": "dst",
+                    "content": "later",
+                    "scheduled_ts_ms": scheduled_ts_ms,
+                })),
+            })
+            .expect("send")
+            .result
+            .expect("result")
+    };
+    let status = |daemon: &RpcDaemon, id: &str| {
+        let response = daemon
+            .handle_rpc(RpcRequest { id: 2, method: "list_messages".into(), params: None })
+            .expect("list_messages");
+        response.result.expect("result")["messages"]
+            .as_array()
+            .and_then(|messages| messages.iter().find(|message| message["id"] == id).cloned())
+            .map(|message| message["receipt_status"].clone())
+            .unwrap_or(Value::Null)
+    };
+
+    {
+        let store = MessagesStore::open(&db_path).expect("open store");
+        let daemon = RpcDaemon::with_store(store, "sched-node".into());
+        let held = send(&daemon, "sched-later", now_ms + 3_600_000);
+        assert_eq!(held["scheduled_ts_ms"], now_ms + 3_600_000);
+        send(&daemon, "sched-soon", now_ms + 1_500);
+        assert_eq!(status(&daemon, "sched-soon"), "scheduled");
+        assert_eq!(daemon.dispatch_due_scheduled_sends(), 0);
+    }
+
+    std::thread::sleep(std::time::Duration::from_millis(2_600));
+    let store = MessagesStore::open(&db_path).expect("reopen store");
+    let daemon = RpcDaemon::with_store(store, "sched-node".into());
+    assert_eq!(daemon.dispatch_due_scheduled_sends(), 1);
+    assert_eq!(status(&daemon, "sched-soon"), "sent: direct");
+    assert_eq!(status(&daemon, "sched-later"), "scheduled");
+}
@@ -13,8 +13,10 @@ fn annotate_list_peers_result_with_app_data_hex() {
         ]
     });
     let mut metadata = HashMap::new();
-    metadata
-        .insert("aa11".to_string(), PeerAnnounceMeta { app_data_hex: Some("cafe".to_string()) });
+    metadata.insert(
+        "aa11".to_string(),
+        PeerAnnounceMeta { app_data_hex: Some("cafe".to_string()), last_seen: 1 },
+    );
 
     annotate_peer_records_with_announce_metadata(&mut result, &metadata);
     assert_eq!(result["peers"][0]["app_data_hex"], Value::String("cafe".to_string()));
@@ -1,4 +1,6 @@
 mod codec;
+mod deliver;
 mod inbound_send;
 mod metadata;
+mod peer_cache;
 mod relay;
@@ -0,0 +1,102 @@
+use super::super::{
+    apply_runtime_identity_restore, forget_peer_identities, load_peer_identity_cache,
+    persist_peer_identity_cache, PeerCrypto,
+};
+use crate::LxmfError;
+use reticulum::identity::PrivateIdentity;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+fn sample_cache() -> Arc<Mutex<HashMap<String, PeerCrypto>>> {
+    let mut peers = HashMap::new();
+    for (index, name) in ["peer-cache-a", "peer-cache-b"].into_iter().enumerate() {
+        let identity = *PrivateIdentity::new_from_name(name).as_identity();
+        peers.insert(format!("{:032x}", index + 1), PeerCrypto { identity });
+    }
+    Arc::new(Mutex::new(peers))
+}
+
+#[test]
+fn peer_identity_cache_round_trips_with_checksum_header() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("peer_identities.json");
+    persist_peer_identity_cache(&sample_cache(), &path);
+
+    let raw: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&path).expect("read")).expect("json");
+    assert_eq!(raw["version"], 1);
+    assert!(raw["checksum"].as_str().is_some_and(|checksum| checksum.len() == 64));
+
+    let loaded = load_peer_identity_cache(&path).expect("valid cache loads");
+    assert_eq!(loaded.len(), 2);
+    assert!(loaded.contains_key(&format!("{:032x}", 1)));
+}
+
+#[test]
+fn peer_identity_cache_rejects_tampered_entries() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("peer_identities.json");
+    persist_peer_identity_cache(&sample_cache(), &path);
+
+    let raw = fs::read_to_string(&path).expect("read");
+    let tampered = raw.replacen(&format!("{:032x}", 1), &format!("{:032x}", 9), 1);
+    assert_ne!(raw, tampered);
+    fs::write(&path, tampered).expect("write");
+
+    let result = load_peer_identity_cache(&path);
+    assert!(matches!(result, Err(LxmfError::Verify(_))));
+}
+
+#[test]
+fn peer_identity_cache_rejects_truncated_file() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("peer_identities.json");
+    persist_peer_identity_cache(&sample_cache(), &path);
+
+    let raw = fs::read(&path).expect("read");
+    fs::write(&path, &raw[..raw.len() / 2]).expect("write");
+
+    let result = load_peer_identity_cache(&path);
+    assert!(matches!(result, Err(LxmfError::Decode(_))));
+}
+
+#[test]
+fn peers_import_persists_bundle_identities() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("peer_identities.json");
+    let peer_crypto = Arc::new(Mutex::new(HashMap::new()));
+    let identity = *PrivateIdentity::new_from_name("peer-cache-import").as_identity();
+    let params = serde_json::json!({
+        "bundle": {
+            "version": "peer_bundle_v1",
+            "identities": {
+                format!("{:032x}", 7): identity.to_hex_string(),
+                format!("{:032x}", 8): "not-a-key",
+            },
+        },
+    });
+
+    let imported =
+        apply_runtime_identity_restore(&peer_crypto, &path, "peers_import", Some(&params));
+
+    assert_eq!(imported, 1);
+    let loaded = load_peer_identity_cache(&path).expect("valid cache loads");
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[&format!("{:032x}", 7)].identity.to_hex_string(), identity.to_hex_string());
+}
+
+#[test]
+fn forgotten_peers_are_removed_from_the_persisted_cache() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("peer_identities.json");
+    let cache = sample_cache();
+    persist_peer_identity_cache(&cache, &path);
+
+    let pruned = vec![format!("{:032x}", 1), format!("{:032x}", 7)];
+    assert_eq!(forget_peer_identities(&cache, &path, &pruned), 1);
+
+    let loaded = load_peer_identity_cache(&path).expect("valid cache loads");
+    assert_eq!(loaded.len(), 1);
+    assert!(loaded.contains_key(&format!("{:032x}", 2)));
+}
@@ -1,12 +1,16 @@
 use super::super::{
-    build_propagation_envelope, build_wire_message, format_relay_request_status,
-    normalize_relay_destination_hash, parse_alternative_relay_request_status,
-    propagation_relay_candidates, PeerCrypto,
+    build_propagation_envelope, build_wire_message, format_delivery_retry_status,
+    format_relay_request_status, normalize_relay_destination_hash,
+    parse_alternative_relay_request_status, parse_delivery_retry_status,
+    propagation_relay_candidates, require_propagation_relay, retry_backoff_delay, DeliveryRetry,
+    PeerAnnounceMeta, PeerCrypto, PropagationSelectionStrategy,
 };
 use crate::propagation::unpack_envelope;
 use reticulum::identity::PrivateIdentity;
+use reticulum::rpc::{DeliveryPrerequisite, MissingDeliveryPrerequisite};
 use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 #[test]
 fn normalize_relay_destination_hash_preserves_destination_hash_input() {
@@ -46,13 +50,127 @@ fn propagation_relay_candidates_prefer_selected_then_known_nodes() {
         "cccccccccccccccccccccccccccccccc".to_string(),
     ])));
 
-    let candidates = propagation_relay_candidates(&selected, &known_nodes);
+    let candidates = propagation_relay_candidates(
+        &selected,
+        &known_nodes,
+        &Arc::new(Mutex::new(HashMap::new())),
+        PropagationSelectionStrategy::Selected,
+        &[],
+    );
     assert_eq!(candidates[0], "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
     assert!(candidates.contains(&"bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".to_string()));
     assert!(candidates.contains(&"cccccccccccccccccccccccccccccccc".to_string()));
     assert_eq!(candidates.len(), 3);
 }
 
+fn propagation_announce_app_data(peering_cost: Option<u32>) -> String {
+    let costs = match peering_cost {
+        Some(cost) => rmpv::Value::Array(vec![16.into(), 3.into(), cost.into()]),
+        None => rmpv::Value::Nil,
+    };
+    let app_data = rmpv::Value::Array(vec![
+        false.into(),
+        1.into(),
+        true.into(),
+        256.into(),
+        10240.into(),
+        costs,
+        rmpv::Value::Map(Vec::new()),
+    ]);
+    hex::encode(rmp_serde::to_vec(&app_data).expect("encode app data"))
+}
+
+#[test]
+fn propagation_relay_candidates_follow_selection_strategy() {
+    let selected = Arc::new(Mutex::new(Some("cccccccccccccccccccccccccccccccc".to_string())));
+    let known_nodes = Arc::new(Mutex::new(HashSet::from([
+        "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string(),
+        "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".to_string(),
+        "cccccccccccccccccccccccccccccccc".to_string(),
+        "dddddddddddddddddddddddddddddddd".to_string(),
+    ])));
+    let meta = Arc::new(Mutex::new(HashMap::from([
+        (
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string(),
+            PeerAnnounceMeta {
+                app_data_hex: Some(propagation_announce_app_data(Some(20))),
+                last_seen: 100,
+            },
+        ),
+        (
+            "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".to_string(),
+            PeerAnnounceMeta {
+                app_data_hex: Some(propagation_announce_app_data(Some(18))),
+                last_seen: 300,
+            },
+        ),
+        (
+            "cccccccccccccccccccccccccccccccc".to_string(),
+            PeerAnnounceMeta {
+                app_data_hex: Some(propagation_announce_app_data(None)),
+                last_seen: 200,
+            },
+        ),
+    ])));
+    let order = |strategy, failed: &[String]| {
+        propagation_relay_candidates(&selected, &known_nodes, &meta, strategy, failed)
+            .into_iter()
+            .map(|node| node[..1].to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(order(PropagationSelectionStrategy::Selected, &[]), ["c", "a", "b", "d"]);
+    assert_eq!(order(PropagationSelectionStrategy::LowestCost, &[]), ["b", "a", "c", "d"]);
+    assert_eq!(order(PropagationSelectionStrategy::MostRecent, &[]), ["b", "c", "a", "d"]);
+    assert_eq!(
+        order(
+            PropagationSelectionStrategy::Selected,
+            &["cccccccccccccccccccccccccccccccc".to_string()]
+        ),
+        ["a", "b", "d"]
+    );
+    assert_eq!(
+        order(
+            PropagationSelectionStrategy::LowestCost,
+            &["bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".to_string()]
+        ),
+        ["a", "c", "d"]
+    );
+}
+
+#[test]
+fn propagation_selection_strategy_defaults_to_selected() {
+    assert_eq!(PropagationSelectionStrategy::parse(None), PropagationSelectionStrategy::Selected);
+    assert_eq!(
+        PropagationSelectionStrategy::parse(Some("random")),
+        PropagationSelectionStrategy::Selected
+    );
+    assert_eq!(
+        PropagationSelectionStrategy::parse(Some("lowest_cost")),
+        PropagationSelectionStrategy::LowestCost
+    );
+    assert_eq!(
+        PropagationSelectionStrategy::parse(Some("most_recent")),
+        PropagationSelectionStrategy::MostRecent
+    );
+}
+
+#[test]
+fn propagation_first_requires_a_selected_or_known_relay() {
+    let selected = Arc::new(Mutex::new(None));
+    let known_nodes = Arc::new(Mutex::new(HashSet::new()));
+
+    let err = require_propagation_relay(&selected, &known_nodes).expect_err("no relay known");
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    assert_eq!(
+        err.get_ref().and_then(|inner| inner.downcast_ref::<MissingDeliveryPrerequisite>()),
+        Some(&MissingDeliveryPrerequisite(DeliveryPrerequisite::PropagationNode))
+    );
+
+    known_nodes.lock().unwrap().insert("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string());
+    assert!(require_propagation_relay(&selected, &known_nodes).is_ok());
+}
+
 #[test]
 fn relay_request_status_roundtrips_exclusions() {
     let status = format_relay_request_status(&[
@@ -70,6 +188,23 @@ fn relay_request_status_roundtrips_exclusions() {
     );
 }
 
+#[test]
+fn delivery_retry_status_roundtrips_and_backoff_doubles() {
+    let retry = DeliveryRetry {
+        attempt: 2,
+        max_attempts: 4,
+        backoff_ms: 500,
+        error: "link activation timed out".to_string(),
+    };
+    let status = format_delivery_retry_status(&retry);
+    assert_eq!(parse_delivery_retry_status(status.as_str()), Some(retry));
+    assert_eq!(parse_delivery_retry_status("retrying: propagated relay attempt 1/2 via aa"), None);
+
+    assert_eq!(retry_backoff_delay(500, 1), Duration::from_millis(500));
+    assert_eq!(retry_backoff_delay(500, 3), Duration::from_millis(2_000));
+    assert_eq!(retry_backoff_delay(0, 5), Duration::ZERO);
+}
+
 #[test]
 fn build_propagation_envelope_wraps_wire_payload() {
     let signer = PrivateIdentity::new_from_name("propagation-envelope-signer");
@@ -49,6 +49,7 @@ pub(super) struct WorkerState {
     pub(super) propagation_sync_state: Arc<Mutex<RuntimePropagationSyncState>>,
     pub(super) shutdown_tx: watch::Sender<bool>,
     pub(super) scheduler_handle: Option<tokio::task::JoinHandle<()>>,
+    pub(super) scheduled_send_handle: tokio::task::JoinHandle<()>,
     pub(super) shutdown: bool,
 }
 
@@ -89,6 +90,9 @@ pub(super) struct OutboundDeliveryOptionsCompat {
     pub(super) try_propagation_on_fail: bool,
     pub(super) source_private_key: Option<String>,
     pub(super) ticket: Option<String>,
+    pub(super) max_retries: u32,
+    pub(super) retry_backoff_ms: u64,
+    pub(super) propagation_first: bool,
 }
 
 #[derive(Clone, Copy)]
@@ -99,6 +103,15 @@ pub(super) struct PeerCrypto {
 #[derive(Clone, Debug, Default)]
 pub(super) struct PeerAnnounceMeta {
     pub(super) app_data_hex: Option<String>,
+    pub(super) last_seen: u64,
+}
+
+impl PeerAnnounceMeta {
+    /// Peering cost a propagation node advertised in its last announce.
+    pub(super) fn peering_cost(&self) -> Option<u32> {
+        let app_data = hex::decode(self.app_data_hex.as_deref()?).ok()?;
+        crate::helpers::pn_peering_cost_from_app_data(&app_data)
+    }
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -118,6 +131,7 @@ impl WorkerState {
         if let Some(handle) = self.scheduler_handle.take() {
             handle.abort();
         }
+        self.scheduled_send_handle.abort();
         let _ = self.shutdown_tx.send(true);
         self.daemon.push_event(RpcEvent {
             event_type: "runtime_stopped".to_string(),
@@ -3,7 +3,8 @@ mod propagation_store;
 
 use crate::message::WireMessage;
 pub use file_store::FileStore;
-pub use propagation_store::PropagationStore;
+pub(crate) use propagation_store::lxmf_timestamp_to_system_time;
+pub use propagation_store::{PropagationStore, MESSAGE_EXPIRY};
 
 pub trait Store {
     fn save(&self, msg: &WireMessage) -> Result<(), crate::error::LxmfError>;
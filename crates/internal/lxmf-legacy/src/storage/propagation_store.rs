@@ -1,30 +1,68 @@
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::error::LxmfError;
 
+/// How long a propagation node holds a message, counted from its send time.
+/// Matches the LXMF router's `MESSAGE_EXPIRY` of 30 days.
+pub const MESSAGE_EXPIRY: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
 pub struct PropagationStore {
     root: PathBuf,
+    expiry: Option<Duration>,
 }
 
 impl PropagationStore {
     pub fn new(root: &Path) -> Self {
-        Self { root: root.to_path_buf() }
+        Self { root: root.to_path_buf(), expiry: Some(MESSAGE_EXPIRY) }
+    }
+
+    /// Replaces the message lifetime; `None` keeps messages until removed.
+    pub fn with_expiry(mut self, expiry: Option<Duration>) -> Self {
+        self.expiry = expiry;
+        self
     }
 
     pub fn save(&self, transient_id: &[u8], data: &[u8]) -> Result<(), LxmfError> {
+        self.save_sent_at(transient_id, data, SystemTime::now())
+    }
+
+    /// Stores a message whose lifetime starts at `sent_at`, typically the
+    /// timestamp of the propagation envelope it arrived in. Send times in the
+    /// future are clamped to now, so clock skew cannot extend the lifetime.
+    /// The send time is kept as the file's modification time.
+    pub fn save_sent_at(
+        &self,
+        transient_id: &[u8],
+        data: &[u8],
+        sent_at: SystemTime,
+    ) -> Result<(), LxmfError> {
         fs::create_dir_all(&self.root).map_err(|e| LxmfError::Io(e.to_string()))?;
-        let name = hex::encode(transient_id);
-        let path = self.root.join(name);
-        fs::write(path, data).map_err(|e| LxmfError::Io(e.to_string()))
+        let path = self.path(transient_id);
+        fs::write(&path, data).map_err(|e| LxmfError::Io(e.to_string()))?;
+        let file = fs::File::options()
+            .write(true)
+            .open(&path)
+            .map_err(|e| LxmfError::Io(e.to_string()))?;
+        file.set_modified(sent_at.min(SystemTime::now())).map_err(|e| LxmfError::Io(e.to_string()))
     }
 
+    /// Reads a stored message. An expired message is removed and reported as
+    /// an error, the same as one that was never stored.
     pub fn get(&self, transient_id: &[u8]) -> Result<Vec<u8>, LxmfError> {
-        let name = hex::encode(transient_id);
-        let path = self.root.join(name);
+        let path = self.path(transient_id);
+        if self.is_expired(&path) {
+            let _ = fs::remove_file(&path);
+            return Err(LxmfError::Io(format!(
+                "propagated message {} expired",
+                hex::encode(transient_id)
+            )));
+        }
         fs::read(path).map_err(|e| LxmfError::Io(e.to_string()))
     }
 
+    /// Ids of the stored messages that have not expired.
     pub fn list_ids(&self) -> Result<Vec<Vec<u8>>, LxmfError> {
         let mut out = Vec::new();
         if !self.root.exists() {
@@ -34,7 +72,7 @@ impl PropagationStore {
             let entry = entry.map_err(|e| LxmfError::Io(e.to_string()))?;
             let name = entry.file_name();
             let name = name.to_string_lossy();
-            if name.len() == 64 {
+            if name.len() == 64 && !self.is_expired(&entry.path()) {
                 if let Ok(bytes) = hex::decode(name.as_ref()) {
                     out.push(bytes);
                 }
@@ -42,4 +80,85 @@ impl PropagationStore {
         }
         Ok(out)
     }
+
+    /// Deletes every expired message and returns how many were removed.
+    pub fn purge_expired(&self) -> Result<usize, LxmfError> {
+        if self.expiry.is_none() || !self.root.exists() {
+            return Ok(0);
+        }
+        let mut purged = 0;
+        for entry in fs::read_dir(&self.root).map_err(|e| LxmfError::Io(e.to_string()))? {
+            let path = entry.map_err(|e| LxmfError::Io(e.to_string()))?.path();
+            if self.is_expired(&path) && fs::remove_file(&path).is_ok() {
+                purged += 1;
+            }
+        }
+        Ok(purged)
+    }
+
+    fn path(&self, transient_id: &[u8]) -> PathBuf {
+        self.root.join(hex::encode(transient_id))
+    }
+
+    fn is_expired(&self, path: &Path) -> bool {
+        let Some(expiry) = self.expiry else {
+            return false;
+        };
+        fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|sent_at| SystemTime::now().duration_since(sent_at).ok())
+            .is_some_and(|age| age >= expiry)
+    }
+}
+
+/// Converts an LXMF timestamp (float seconds since the epoch) to a send time,
+/// or `None` when it is not a usable point in time.
+pub(crate) fn lxmf_timestamp_to_system_time(timestamp: f64) -> Option<SystemTime> {
+    if !timestamp.is_finite() || timestamp < 0.0 {
+        return None;
+    }
+    UNIX_EPOCH.checked_add(Duration::try_from_secs_f64(timestamp).ok()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PropagationStore, MESSAGE_EXPIRY};
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn messages_expire_relative_to_their_send_time() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let store = PropagationStore::new(temp.path());
+        let now = SystemTime::now();
+        let stale = [0x11; 32];
+        let fresh = [0x22; 32];
+        let skewed = [0x33; 32];
+        store
+            .save_sent_at(&stale, b"stale", now - MESSAGE_EXPIRY - Duration::from_secs(1))
+            .expect("save");
+        store.save_sent_at(&fresh, b"fresh", now - Duration::from_secs(60)).expect("save");
+        store.save_sent_at(&skewed, b"skewed", now + MESSAGE_EXPIRY * 2).expect("save");
+
+        let mut ids = store.list_ids().expect("list");
+        ids.sort();
+        assert_eq!(ids, vec![fresh.to_vec(), skewed.to_vec()]);
+        assert_eq!(store.get(&fresh).expect("fresh"), b"fresh");
+        assert!(store.get(&stale).is_err());
+        assert!(!temp.path().join(hex::encode(stale)).exists());
+
+        // A future send time is clamped to now, so it expires on schedule.
+        let short = PropagationStore::new(temp.path()).with_expiry(Some(Duration::ZERO));
+        assert_eq!(short.purge_expired().expect("purge"), 2);
+        assert!(short.list_ids().expect("list").is_empty());
+    }
+
+    #[test]
+    fn disabled_expiry_keeps_messages() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let store = PropagationStore::new(temp.path()).with_expiry(None);
+        store.save_sent_at(&[0x44; 32], b"old", SystemTime::UNIX_EPOCH).expect("save");
+        assert_eq!(store.purge_expired().expect("purge"), 0);
+        assert_eq!(store.get(&[0x44; 32]).expect("kept"), b"old");
+    }
 }
@@ -429,6 +429,11 @@ fn enrich_app_extension_fields(object: &mut JsonMap<String, JsonValue>) {
     if let Some(reply_to) = app_extensions.get("reply_to").and_then(JsonValue::as_str) {
         object.insert("reply_to".to_string(), JsonValue::String(reply_to.to_string()));
     }
+
+    if let Some(read_of) = app_extensions.get("read_of") {
+        object.insert("is_read_receipt".to_string(), JsonValue::Bool(true));
+        object.insert("read_of".to_string(), read_of.clone());
+    }
 }
 
 fn decode_binary_bytes(value: &Value) -> Option<&[u8]> {
@@ -1,4 +1,5 @@
-use crate::rpc::{RpcDaemon, RpcRequest};
+use crate::rpc::{DeliveryPolicy, RpcDaemon, RpcRequest};
+use crate::storage::messages::MessageRecord;
 use crate::transport::DeliveryReceipt;
 use serde_json::json;
 use std::collections::HashMap;
@@ -46,3 +47,69 @@ pub fn record_receipt_status(
     })?;
     Ok(())
 }
+
+/// Applies a read receipt from `reader`. Returns false when `message_id` is
+/// not one of our messages to that peer, in which case nothing changes.
+pub fn record_read_receipt(
+    daemon: &RpcDaemon,
+    message_id: &str,
+    reader: &str,
+    read_ts_ms: Option<u64>,
+) -> Result<bool, std::io::Error> {
+    let response = daemon.handle_rpc(RpcRequest {
+        id: 0,
+        method: "record_receipt".into(),
+        params: Some(json!({
+            "message_id": message_id,
+            "status": "read",
+            "read_ts_ms": read_ts_ms,
+            "reader": reader,
+        })),
+    })?;
+    Ok(response
+        .result
+        .and_then(|result| result.get("applied").and_then(serde_json::Value::as_bool))
+        .unwrap_or(true))
+}
+
+/// Answers an inbound message with a `payload.app.read_receipt.v1` message
+/// when the delivery policy opts in for its source. The receipt is sent from
+/// the address the message was delivered to and carries no `read_ts_ms`, so
+/// the peer takes the receipt's own timestamp as the read time. Returns
+/// whether a receipt was queued.
+pub fn send_auto_read_receipt(
+    daemon: &RpcDaemon,
+    record: &MessageRecord,
+) -> Result<bool, std::io::Error> {
+    let response = daemon.handle_rpc(RpcRequest {
+        id: 0,
+        method: "get_delivery_policy".into(),
+        params: None,
+    })?;
+    let policy = response
+        .result
+        .and_then(|result| result.get("policy").cloned())
+        .map(serde_json::from_value::<DeliveryPolicy>)
+        .transpose()
+        .map_err(std::io::Error::other)?
+        .unwrap_or_default();
+    if !policy.auto_read_receipt_for(&record.source) {
+        return Ok(false);
+    }
+    let response = daemon.handle_rpc(RpcRequest {
+        id: 0,
+        method: "send_message_v2".into(),
+        params: Some(json!({
+            "id": format!("read-{}", record.id),
+            "source": record.destination,
+            "destination": record.source,
+            "title": "",
+            "content": "",
+            "fields": { "16": { "read_of": [record.id] } },
+        })),
+    })?;
+    if let Some(err) = response.error {
+        return Err(std::io::Error::other(format!("{}: {}", err.code, err.message)));
+    }
+    Ok(true)
+}
@@ -1,4 +1,36 @@
 impl RpcDaemon {
+    /// Forgets peers last seen before `cutoff_ts` (seconds), in memory and in
+    /// the persisted announces. The selected propagation node is always kept.
+    fn prune_peers_before(&self, cutoff_ts: i64) -> Result<Vec<String>, std::io::Error> {
+        let protected = [
+            self.outbound_propagation_node.lock().expect("propagation node mutex poisoned").clone(),
+            self.propagation_state
+                .lock()
+                .expect("propagation mutex poisoned")
+                .selected_node
+                .clone(),
+        ];
+        let is_protected = |peer: &str| protected.iter().flatten().any(|selected| selected == peer);
+
+        let mut stale =
+            self.store.stale_announce_peers(cutoff_ts).map_err(std::io::Error::other)?;
+        let mut peers = self.peers.lock().expect("peers mutex poisoned");
+        stale.extend(
+            peers
+                .values()
+                .filter(|record| record.last_seen < cutoff_ts)
+                .map(|record| record.peer.clone()),
+        );
+        stale.sort_unstable();
+        stale.dedup();
+        stale.retain(|peer| !is_protected(peer));
+        for peer in &stale {
+            peers.remove(peer);
+            self.store.delete_peer_announces(peer).map_err(std::io::Error::other)?;
+        }
+        Ok(stale)
+    }
+
     fn handle_rpc_clear(&self, request: RpcRequest) -> Result<RpcResponse, std::io::Error> {
         match request.method.as_str() {
             "clear_messages" => {
@@ -26,6 +58,25 @@ impl RpcDaemon {
                     error: None,
                 })
             }
+            "peers_prune" => {
+                let params = request.params.ok_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::InvalidInput, "missing params")
+                })?;
+                let parsed: PeersPruneParams = serde_json::from_value(params)
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+                let cutoff_ts = now_i64()
+                    .saturating_sub(i64::try_from(parsed.max_age_secs).unwrap_or(i64::MAX));
+                let pruned = self.prune_peers_before(cutoff_ts)?;
+                Ok(RpcResponse {
+                    id: request.id,
+                    result: Some(json!({
+                        "pruned": pruned.len(),
+                        "peers": pruned,
+                        "cutoff_ts": cutoff_ts,
+                    })),
+                    error: None,
+                })
+            }
             "clear_all" => {
                 self.store.clear_messages().map_err(std::io::Error::other)?;
                 self.store.clear_announces().map_err(std::io::Error::other)?;
@@ -224,6 +224,7 @@ impl RpcDaemon {
                     direction: "in".into(),
                     fields: parsed.fields,
                     receipt_status: None,
+                    read_ts_ms: None,
                 };
                 self.store_inbound_record(record)?;
                 Ok(RpcResponse {
@@ -238,32 +239,53 @@ impl RpcDaemon {
                 })?;
                 let parsed: RecordReceiptParams = serde_json::from_value(params)
                     .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
-                self.store
-                    .update_receipt_status(&parsed.message_id, &parsed.status)
-                    .map_err(std::io::Error::other)?;
+                let read_ts_ms = if parsed.status == "read" {
+                    let read_ts_ms = parsed.read_ts_ms.unwrap_or_else(now_ms_u64);
+                    let applied = match parsed.reader.as_deref() {
+                        Some(reader) => self
+                            .store
+                            .mark_read_by(&parsed.message_id, reader, read_ts_ms)
+                            .map_err(std::io::Error::other)?,
+                        None => {
+                            self.store
+                                .mark_read(&parsed.message_id, read_ts_ms)
+                                .map_err(std::io::Error::other)?;
+                            true
+                        }
+                    };
+                    if !applied {
+                        return Ok(RpcResponse {
+                            id: request.id,
+                            result: Some(json!({
+                                "message_id": parsed.message_id,
+                                "applied": false,
+                            })),
+                            error: None,
+                        });
+                    }
+                    Some(read_ts_ms)
+                } else {
+                    self.store
+                        .update_receipt_status(&parsed.message_id, &parsed.status)
+                        .map_err(std::io::Error::other)?;
+                    None
+                };
                 let message_id = parsed.message_id;
                 let status = parsed.status;
                 self.append_delivery_trace(&message_id, status.clone());
                 let reason_code = delivery_reason_code(&status);
-                let event = RpcEvent {
-                    event_type: "receipt".into(),
-                    payload: json!({
-                        "message_id": message_id,
-                        "status": status,
-                        "reason_code": reason_code,
-                    }),
-                };
+                let mut payload = json!({
+                    "message_id": message_id,
+                    "status": status,
+                    "reason_code": reason_code,
+                });
+                if let Some(read_ts_ms) = read_ts_ms {
+                    payload["read_ts_ms"] = json!(read_ts_ms);
+                }
+                let event = RpcEvent { event_type: "receipt".into(), payload: payload.clone() };
                 self.push_event(event.clone());
                 let _ = self.events.send(event);
-                Ok(RpcResponse {
-                    id: request.id,
-                    result: Some(json!({
-                        "message_id": message_id,
-                        "status": status,
-                        "reason_code": reason_code,
-                    })),
-                    error: None,
-                })
+                Ok(RpcResponse { id: request.id, result: Some(payload), error: None })
             }
             "message_delivery_trace" => {
                 let params = request.params.ok_or_else(|| {
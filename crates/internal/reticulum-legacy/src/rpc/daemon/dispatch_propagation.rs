@@ -33,6 +33,15 @@ impl RpcDaemon {
                     if let Some(value) = parsed.prioritised_destinations {
                         guard.prioritised_destinations = value;
                     }
+                    if let Some(value) = parsed.delivery_mode {
+                        guard.delivery_mode = value;
+                    }
+                    if let Some(value) = parsed.auto_read_receipts {
+                        guard.auto_read_receipts = value;
+                    }
+                    if let Some(value) = parsed.auto_read_receipt_sources {
+                        guard.auto_read_receipt_sources = value;
+                    }
                     guard.clone()
                 };
 
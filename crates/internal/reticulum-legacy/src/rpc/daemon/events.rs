@@ -38,6 +38,21 @@ impl RpcDaemon {
         let _ = self.events.send(event);
     }
 
+    /// Polls the scheduled send queue so messages queued with a future
+    /// `scheduled_ts_ms` are dispatched once their send time passes.
+    pub fn start_scheduled_send_loop(
+        self: std::rc::Rc<Self>,
+        poll_interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::task::spawn_local(async move {
+            let mut interval = tokio::time::interval(poll_interval);
+            loop {
+                interval.tick().await;
+                self.dispatch_due_scheduled_sends();
+            }
+        })
+    }
+
     pub fn start_announce_scheduler(
         self: std::rc::Rc<Self>,
         interval_secs: u64,
@@ -83,6 +98,7 @@ impl RpcDaemon {
             direction: "in".into(),
             fields: None,
             receipt_status: None,
+            read_ts_ms: None,
         };
         let _ = self.store.insert_message(&record);
         let event =
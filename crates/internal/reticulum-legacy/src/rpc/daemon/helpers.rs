@@ -61,12 +61,15 @@ impl RpcDaemon {
         fields: Option<JsonValue>,
         method: Option<String>,
         stamp_cost: Option<u32>,
-        options: OutboundDeliveryOptions,
+        mut options: OutboundDeliveryOptions,
         include_ticket: Option<bool>,
     ) -> Result<RpcResponse, std::io::Error> {
+        let delivery_mode =
+            self.delivery_policy.lock().expect("policy mutex poisoned").delivery_mode;
+        options.propagation_first = delivery_mode == DeliveryMode::PropagationFirst;
         let timestamp = now_i64();
         self.append_delivery_trace(&id, "queued".to_string());
-        let mut record = MessageRecord {
+        let record = MessageRecord {
             id: id.clone(),
             source,
             destination,
@@ -76,9 +79,26 @@ impl RpcDaemon {
             direction: "out".into(),
             fields: merge_fields_with_options(fields, method.clone(), stamp_cost, include_ticket),
             receipt_status: None,
+            read_ts_ms: None,
         };
 
         self.store.insert_message(&record).map_err(std::io::Error::other)?;
+        if let Some(due_ms) = options.scheduled_ts_ms.filter(|due_ms| *due_ms > now_ms_u64()) {
+            return self.schedule_outbound(request_id, record, method, options, due_ms);
+        }
+        self.dispatch_outbound(request_id, record, method, options)
+    }
+
+    /// Hands a stored outbound record to the bridge and records the resulting
+    /// status. Scheduled messages reach this once their send time has passed.
+    fn dispatch_outbound(
+        &self,
+        request_id: u64,
+        mut record: MessageRecord,
+        method: Option<String>,
+        options: OutboundDeliveryOptions,
+    ) -> Result<RpcResponse, std::io::Error> {
+        let id = record.id.clone();
         self.append_delivery_trace(&id, "sending".to_string());
         let deliver_result = if let Some(bridge) = &self.outbound_bridge {
             bridge.deliver(&record, &options)
@@ -104,13 +124,26 @@ impl RpcDaemon {
             };
             self.push_event(event.clone());
             let _ = self.events.send(event);
+            let code = if err
+                .get_ref()
+                .is_some_and(|inner| inner.is::<MissingDeliveryPrerequisite>())
+            {
+                "DELIVERY_PREREQUISITE_MISSING"
+            } else {
+                "DELIVERY_FAILED"
+            };
             return Ok(RpcResponse {
                 id: request_id,
                 result: None,
-                error: Some(RpcError { code: "DELIVERY_FAILED".into(), message: err.to_string() }),
+                error: Some(RpcError { code: code.into(), message: err.to_string() }),
             });
         }
         let sent_status = format!("sent: {}", method.as_deref().unwrap_or("direct"));
+        if record.receipt_status.as_deref() == Some("scheduled") {
+            // Replace the held status; immediate sends leave it to receipts.
+            self.store.update_receipt_status(&id, &sent_status).map_err(std::io::Error::other)?;
+            record.receipt_status = Some(sent_status.clone());
+        }
         self.append_delivery_trace(&id, sent_status.clone());
         let event = RpcEvent {
             event_type: "outbound".into(),
@@ -126,6 +159,146 @@ impl RpcDaemon {
         Ok(RpcResponse { id: request_id, result: Some(json!({ "message_id": id })), error: None })
     }
 
+    fn schedule_outbound(
+        &self,
+        request_id: u64,
+        mut record: MessageRecord,
+        method: Option<String>,
+        options: OutboundDeliveryOptions,
+        due_ms: u64,
+    ) -> Result<RpcResponse, std::io::Error> {
+        let id = record.id.clone();
+        self.store
+            .put_scheduled_send(&ScheduledSendRecord {
+                message_id: id.clone(),
+                due_ms,
+                method: method.clone(),
+                options: serde_json::to_value(&options).map_err(std::io::Error::other)?,
+            })
+            .map_err(std::io::Error::other)?;
+        self.store.update_receipt_status(&id, "scheduled").map_err(std::io::Error::other)?;
+        self.append_delivery_trace(&id, "scheduled".to_string());
+        record.receipt_status = Some("scheduled".to_string());
+        let event = RpcEvent {
+            event_type: "outbound".into(),
+            payload: json!({
+                "message": record,
+                "method": method,
+                "scheduled_ts_ms": due_ms,
+            }),
+        };
+        self.push_event(event.clone());
+        let _ = self.events.send(event);
+        self.scheduled_sends.lock().expect("scheduled sends mutex poisoned").push(ScheduledSend {
+            due_ms,
+            record,
+            method,
+            options,
+        });
+        Ok(RpcResponse {
+            id: request_id,
+            result: Some(json!({ "message_id": id, "scheduled_ts_ms": due_ms })),
+            error: None,
+        })
+    }
+
+    /// Dispatches every scheduled message whose send time has passed and
+    /// returns how many were handed to the delivery pipeline.
+    pub fn dispatch_due_scheduled_sends(&self) -> usize {
+        let now_ms = now_ms_u64();
+        let due = {
+            let mut guard = self.scheduled_sends.lock().expect("scheduled sends mutex poisoned");
+            let (due, pending): (Vec<_>, Vec<_>) =
+                guard.drain(..).partition(|scheduled| scheduled.due_ms <= now_ms);
+            *guard = pending;
+            due
+        };
+        let count = due.len();
+        for scheduled in due {
+            let id = scheduled.record.id.clone();
+            if let Err(err) = self.store.delete_scheduled_send(&id) {
+                log::warn!("scheduled send {id} not cleared: {err}");
+            }
+            let record = scheduled.record.clone();
+            let method = scheduled.method.clone();
+            if let Err(err) =
+                self.dispatch_outbound(0, scheduled.record, scheduled.method, scheduled.options)
+            {
+                self.fail_scheduled_send(record, method, &err);
+            }
+        }
+        count
+    }
+
+    /// Marks a scheduled send failed when it could not be dispatched, so it
+    /// does not sit in `scheduled` forever.
+    fn fail_scheduled_send(
+        &self,
+        mut record: MessageRecord,
+        method: Option<String>,
+        err: &std::io::Error,
+    ) {
+        let status = format!("failed: {err}");
+        if let Err(store_err) = self.store.update_receipt_status(&record.id, &status) {
+            log::warn!("scheduled send {} failed ({err}) and was not marked: {store_err}", record.id);
+        }
+        self.append_delivery_trace(&record.id, status.clone());
+        record.receipt_status = Some(status.clone());
+        let event = RpcEvent {
+            event_type: "outbound".into(),
+            payload: json!({
+                "message": record,
+                "method": method,
+                "error": err.to_string(),
+                "reason_code": delivery_reason_code(&status),
+            }),
+        };
+        self.push_event(event.clone());
+        let _ = self.events.send(event);
+    }
+
+    /// Reloads the sends persisted with a future send time so a restart keeps
+    /// them scheduled. Ones that fell due while the daemon was down go out on
+    /// the next tick of the scheduled send loop.
+    fn restore_scheduled_sends(&self) -> Result<usize, std::io::Error> {
+        let persisted = self.store.list_scheduled_sends().map_err(std::io::Error::other)?;
+        let mut restored = Vec::with_capacity(persisted.len());
+        for entry in persisted {
+            let record = self
+                .store
+                .get_message(&entry.message_id)
+                .map_err(std::io::Error::other)?
+                .filter(|record| record.receipt_status.as_deref() == Some("scheduled"));
+            let Some(record) = record else {
+                self.store
+                    .delete_scheduled_send(&entry.message_id)
+                    .map_err(std::io::Error::other)?;
+                continue;
+            };
+            match serde_json::from_value::<OutboundDeliveryOptions>(entry.options) {
+                Ok(options) => restored.push(ScheduledSend {
+                    due_ms: entry.due_ms,
+                    record,
+                    method: entry.method,
+                    options,
+                }),
+                Err(err) => {
+                    self.store
+                        .delete_scheduled_send(&entry.message_id)
+                        .map_err(std::io::Error::other)?;
+                    let err = std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("stored delivery options unreadable: {err}"),
+                    );
+                    self.fail_scheduled_send(record, entry.method, &err);
+                }
+            }
+        }
+        let count = restored.len();
+        self.scheduled_sends.lock().expect("scheduled sends mutex poisoned").extend(restored);
+        Ok(count)
+    }
+
     fn local_delivery_hash(&self) -> String {
         self.delivery_destination_hash
             .lock()
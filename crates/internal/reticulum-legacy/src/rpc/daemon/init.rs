@@ -1,25 +1,6 @@
 impl RpcDaemon {
     pub fn with_store(store: MessagesStore, identity_hash: String) -> Self {
-        let (events, _rx) = broadcast::channel(64);
-        Self {
-            store,
-            identity_hash,
-            delivery_destination_hash: Mutex::new(None),
-            events,
-            event_queue: Mutex::new(VecDeque::new()),
-            peers: Mutex::new(HashMap::new()),
-            interfaces: Mutex::new(Vec::new()),
-            delivery_policy: Mutex::new(DeliveryPolicy::default()),
-            propagation_state: Mutex::new(PropagationState::default()),
-            propagation_payloads: Mutex::new(HashMap::new()),
-            outbound_propagation_node: Mutex::new(None),
-            paper_ingest_seen: Mutex::new(HashSet::new()),
-            stamp_policy: Mutex::new(StampPolicy::default()),
-            ticket_cache: Mutex::new(HashMap::new()),
-            delivery_traces: Mutex::new(HashMap::new()),
-            outbound_bridge: None,
-            announce_bridge: None,
-        }
+        Self::with_store_and_bridges(store, identity_hash, None, None)
     }
 
     pub fn with_store_and_bridge(
@@ -27,26 +8,7 @@ impl RpcDaemon {
         identity_hash: String,
         outbound_bridge: Arc<dyn OutboundBridge>,
     ) -> Self {
-        let (events, _rx) = broadcast::channel(64);
-        Self {
-            store,
-            identity_hash,
-            delivery_destination_hash: Mutex::new(None),
-            events,
-            event_queue: Mutex::new(VecDeque::new()),
-            peers: Mutex::new(HashMap::new()),
-            interfaces: Mutex::new(Vec::new()),
-            delivery_policy: Mutex::new(DeliveryPolicy::default()),
-            propagation_state: Mutex::new(PropagationState::default()),
-            propagation_payloads: Mutex::new(HashMap::new()),
-            outbound_propagation_node: Mutex::new(None),
-            paper_ingest_seen: Mutex::new(HashSet::new()),
-            stamp_policy: Mutex::new(StampPolicy::default()),
-            ticket_cache: Mutex::new(HashMap::new()),
-            delivery_traces: Mutex::new(HashMap::new()),
-            outbound_bridge: Some(outbound_bridge),
-            announce_bridge: None,
-        }
+        Self::with_store_and_bridges(store, identity_hash, Some(outbound_bridge), None)
     }
 
     pub fn with_store_and_bridges(
@@ -56,7 +18,7 @@ impl RpcDaemon {
         announce_bridge: Option<Arc<dyn AnnounceBridge>>,
     ) -> Self {
         let (events, _rx) = broadcast::channel(64);
-        Self {
+        let daemon = Self {
             store,
             identity_hash,
             delivery_destination_hash: Mutex::new(None),
@@ -72,9 +34,14 @@ impl RpcDaemon {
             stamp_policy: Mutex::new(StampPolicy::default()),
             ticket_cache: Mutex::new(HashMap::new()),
             delivery_traces: Mutex::new(HashMap::new()),
+            scheduled_sends: Mutex::new(Vec::new()),
             outbound_bridge,
             announce_bridge,
+        };
+        if let Err(err) = daemon.restore_scheduled_sends() {
+            log::warn!("scheduled sends not restored: {err}");
         }
+        daemon
     }
 
     pub fn test_instance() -> Self {
@@ -141,6 +108,12 @@ impl RpcDaemon {
         self.store_inbound_record(record)
     }
 
+    /// Whether a message with `message_id` is already stored, in either
+    /// direction.
+    pub fn has_message(&self, message_id: &str) -> Result<bool, std::io::Error> {
+        Ok(self.store.get_message(message_id).map_err(std::io::Error::other)?.is_some())
+    }
+
     pub fn accept_announce(&self, peer: String, timestamp: i64) -> Result<(), std::io::Error> {
         self.accept_announce_with_metadata(
             peer, timestamp, None, None, None, None, None, None, None, None, None, None, None,
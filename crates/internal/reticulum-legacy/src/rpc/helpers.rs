@@ -43,6 +43,13 @@ fn now_i64() -> i64 {
         .unwrap_or(0)
 }
 
+fn now_ms_u64() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|value| value.as_millis() as u64)
+        .unwrap_or(0)
+}
+
 fn first_n_chars(input: &str, n: usize) -> Option<String> {
     if n == 0 {
         return Some(String::new());
@@ -6,7 +6,7 @@ use rmpv::Value as MsgPackValue;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Map as JsonMap, Value as JsonValue};
 
-use crate::storage::messages::{AnnounceRecord, MessageRecord, MessagesStore};
+use crate::storage::messages::{AnnounceRecord, MessageRecord, MessagesStore, ScheduledSendRecord};
 use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::{Arc, Mutex};
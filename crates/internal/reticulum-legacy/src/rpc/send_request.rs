@@ -35,6 +35,12 @@ struct SendMessageV2Params {
     try_propagation_on_fail: Option<bool>,
     #[serde(default)]
     source_private_key: Option<String>,
+    #[serde(default)]
+    max_retries: Option<u32>,
+    #[serde(default)]
+    retry_backoff_ms: Option<u64>,
+    #[serde(default)]
+    scheduled_ts_ms: Option<u64>,
 }
 
 #[derive(Debug)]
@@ -99,6 +105,10 @@ pub(super) fn parse_outbound_send_request(
                     try_propagation_on_fail: parsed.try_propagation_on_fail.unwrap_or_default(),
                     ticket: None,
                     source_private_key: parsed.source_private_key,
+                    max_retries: parsed.max_retries.unwrap_or_default(),
+                    retry_backoff_ms: parsed.retry_backoff_ms.unwrap_or_default(),
+                    propagation_first: false,
+                    scheduled_ts_ms: parsed.scheduled_ts_ms,
                 },
                 include_ticket,
             })
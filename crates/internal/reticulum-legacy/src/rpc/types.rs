@@ -35,8 +35,69 @@ pub struct DeliveryPolicy {
     pub denied_destinations: Vec<String>,
     pub ignored_destinations: Vec<String>,
     pub prioritised_destinations: Vec<String>,
+    #[serde(default)]
+    pub delivery_mode: DeliveryMode,
+    /// Answer every inbound message with a read receipt. Off by default, since
+    /// a read receipt tells the sender the message was opened.
+    #[serde(default)]
+    pub auto_read_receipts: bool,
+    /// Sources answered with a read receipt even while `auto_read_receipts` is
+    /// off.
+    #[serde(default)]
+    pub auto_read_receipt_sources: Vec<String>,
+}
+
+impl DeliveryPolicy {
+    /// Whether an inbound message from `source` is answered with a read
+    /// receipt without user action.
+    pub fn auto_read_receipt_for(&self, source: &str) -> bool {
+        self.auto_read_receipts
+            || self
+                .auto_read_receipt_sources
+                .iter()
+                .any(|entry| entry.trim().eq_ignore_ascii_case(source.trim()))
+    }
+}
+
+/// Which path an outbound message with no explicit method tries first.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryMode {
+    /// Direct link first, with propagation as the fallback.
+    #[default]
+    DirectFirst,
+    /// A propagation node first, with a direct link as the fallback. Sends fail
+    /// with [`MissingDeliveryPrerequisite`] while no propagation node is known.
+    PropagationFirst,
 }
 
+/// Something a delivery policy needs before a message can be handed off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryPrerequisite {
+    PropagationNode,
+}
+
+impl DeliveryPrerequisite {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PropagationNode => "propagation_node",
+        }
+    }
+}
+
+/// Returned by outbound bridges, wrapped in an `std::io::Error`, when the
+/// active delivery policy cannot be honored yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissingDeliveryPrerequisite(pub DeliveryPrerequisite);
+
+impl std::fmt::Display for MissingDeliveryPrerequisite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "missing delivery prerequisite: {}", self.0.as_str())
+    }
+}
+
+impl std::error::Error for MissingDeliveryPrerequisite {}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct PropagationState {
     pub enabled: bool,
@@ -76,6 +137,16 @@ pub struct DeliveryTraceEntry {
     pub reason_code: Option<String>,
 }
 
+/// An outbound message stored with a future `scheduled_ts_ms`, waiting for
+/// [`RpcDaemon::dispatch_due_scheduled_sends`].
+#[derive(Debug)]
+struct ScheduledSend {
+    due_ms: u64,
+    record: MessageRecord,
+    method: Option<String>,
+    options: OutboundDeliveryOptions,
+}
+
 pub struct RpcDaemon {
     store: MessagesStore,
     identity_hash: String,
@@ -92,6 +163,7 @@ pub struct RpcDaemon {
     stamp_policy: Mutex<StampPolicy>,
     ticket_cache: Mutex<HashMap<String, TicketRecord>>,
     delivery_traces: Mutex<HashMap<String, Vec<DeliveryTraceEntry>>>,
+    scheduled_sends: Mutex<Vec<ScheduledSend>>,
     outbound_bridge: Option<Arc<dyn OutboundBridge>>,
     announce_bridge: Option<Arc<dyn AnnounceBridge>>,
 }
@@ -122,6 +194,20 @@ pub struct OutboundDeliveryOptions {
     pub ticket: Option<String>,
     #[serde(default)]
     pub source_private_key: Option<String>,
+    /// Extra direct-link attempts after the first one fails. Zero keeps the
+    /// single-attempt behavior.
+    #[serde(default)]
+    pub max_retries: u32,
+    /// Delay before the first retry; each further retry doubles it.
+    #[serde(default)]
+    pub retry_backoff_ms: u64,
+    /// Set by the daemon from [`DeliveryMode::PropagationFirst`].
+    #[serde(default)]
+    pub propagation_first: bool,
+    /// Wall-clock send time in unix milliseconds. The daemon holds the message
+    /// until then; a missing or past time sends immediately.
+    #[serde(default)]
+    pub scheduled_ts_ms: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -148,6 +234,11 @@ pub struct PeerRecord {
 struct RecordReceiptParams {
     message_id: String,
     status: String,
+    #[serde(default)]
+    read_ts_ms: Option<u64>,
+    /// Peer reporting a read; when set, only a message sent to it is marked.
+    #[serde(default)]
+    reader: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -207,6 +298,12 @@ struct DeliveryPolicyParams {
     ignored_destinations: Option<Vec<String>>,
     #[serde(default)]
     prioritised_destinations: Option<Vec<String>>,
+    #[serde(default)]
+    delivery_mode: Option<DeliveryMode>,
+    #[serde(default)]
+    auto_read_receipts: Option<bool>,
+    #[serde(default)]
+    auto_read_receipt_sources: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -261,6 +358,11 @@ struct ListAnnouncesParams {
     cursor: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct PeersPruneParams {
+    max_age_secs: u64,
+}
+
 #[derive(Debug, Deserialize)]
 struct SetOutboundPropagationNodeParams {
     #[serde(default)]
@@ -1,4 +1,4 @@
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
 use serde_json::Value as JsonValue;
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize)]
@@ -12,6 +12,8 @@ pub struct MessageRecord {
     pub direction: String,
     pub fields: Option<JsonValue>,
     pub receipt_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_ts_ms: Option<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -33,6 +35,16 @@ pub struct AnnounceRecord {
     pub peering_cost: Option<u32>,
 }
 
+/// An outbound message held until `due_ms`, persisted so a restart keeps it
+/// scheduled. `options` is the serialized delivery options of the send.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledSendRecord {
+    pub message_id: String,
+    pub due_ms: u64,
+    pub method: Option<String>,
+    pub options: JsonValue,
+}
+
 pub struct MessagesStore {
     conn: Connection,
 }
@@ -56,7 +68,7 @@ impl MessagesStore {
         let fields_json =
             record.fields.as_ref().map(|value| serde_json::to_string(value).unwrap_or_default());
         self.conn.execute(
-            "INSERT OR REPLACE INTO messages (id, source, destination, title, content, timestamp, direction, fields, receipt_status) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            "INSERT OR REPLACE INTO messages (id, source, destination, title, content, timestamp, direction, fields, receipt_status, read_ts_ms) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
             params![
                 &record.id,
                 &record.source,
@@ -67,6 +79,7 @@ impl MessagesStore {
                 &record.direction,
                 fields_json,
                 &record.receipt_status,
+                record.read_ts_ms.map(|value| value as i64),
             ],
         )?;
         Ok(())
@@ -80,7 +93,7 @@ impl MessagesStore {
         let mut records = Vec::new();
         if let Some(ts) = before_ts {
             let mut stmt = self.conn.prepare(
-                "SELECT id, source, destination, title, content, timestamp, direction, fields, receipt_status FROM messages WHERE timestamp < ?1 ORDER BY timestamp DESC LIMIT ?2",
+                "SELECT id, source, destination, title, content, timestamp, direction, fields, receipt_status, read_ts_ms FROM messages WHERE timestamp < ?1 ORDER BY timestamp DESC LIMIT ?2",
             )?;
             let mut rows = stmt.query(params![ts, limit as i64])?;
             while let Some(row) = rows.next()? {
@@ -88,6 +101,7 @@ impl MessagesStore {
                 let fields =
                     fields_json.as_ref().and_then(|value| serde_json::from_str(value).ok());
                 let receipt_status: Option<String> = row.get(8)?;
+                let read_ts_ms: Option<i64> = row.get(9)?;
                 records.push(MessageRecord {
                     id: row.get(0)?,
                     source: row.get(1)?,
@@ -98,11 +112,12 @@ impl MessagesStore {
                     direction: row.get(6)?,
                     fields,
                     receipt_status,
+                    read_ts_ms: read_ts_ms.map(|value| value.max(0) as u64),
                 });
             }
         } else {
             let mut stmt = self.conn.prepare(
-                "SELECT id, source, destination, title, content, timestamp, direction, fields, receipt_status FROM messages ORDER BY timestamp DESC LIMIT ?1",
+                "SELECT id, source, destination, title, content, timestamp, direction, fields, receipt_status, read_ts_ms FROM messages ORDER BY timestamp DESC LIMIT ?1",
             )?;
             let mut rows = stmt.query(params![limit as i64])?;
             while let Some(row) = rows.next()? {
@@ -110,6 +125,7 @@ impl MessagesStore {
                 let fields =
                     fields_json.as_ref().and_then(|value| serde_json::from_str(value).ok());
                 let receipt_status: Option<String> = row.get(8)?;
+                let read_ts_ms: Option<i64> = row.get(9)?;
                 records.push(MessageRecord {
                     id: row.get(0)?,
                     source: row.get(1)?,
@@ -120,20 +136,125 @@ impl MessagesStore {
                     direction: row.get(6)?,
                     fields,
                     receipt_status,
+                    read_ts_ms: read_ts_ms.map(|value| value.max(0) as u64),
                 });
             }
         }
         Ok(records)
     }
 
+    pub fn get_message(&self, message_id: &str) -> rusqlite::Result<Option<MessageRecord>> {
+        self.conn
+            .query_row(
+                "SELECT id, source, destination, title, content, timestamp, direction, fields, receipt_status, read_ts_ms FROM messages WHERE id = ?1",
+                params![message_id],
+                |row| {
+                    let fields_json: Option<String> = row.get(7)?;
+                    Ok(MessageRecord {
+                        id: row.get(0)?,
+                        source: row.get(1)?,
+                        destination: row.get(2)?,
+                        title: row.get(3)?,
+                        content: row.get(4)?,
+                        timestamp: row.get(5)?,
+                        direction: row.get(6)?,
+                        fields: fields_json.and_then(|value| serde_json::from_str(&value).ok()),
+                        receipt_status: row.get(8)?,
+                        read_ts_ms: row.get::<_, Option<i64>>(9)?.map(|value| value as u64),
+                    })
+                },
+            )
+            .optional()
+    }
+
+    /// Read is the final receipt state: later statuses for a message that has
+    /// already been read are ignored.
     pub fn update_receipt_status(&self, message_id: &str, status: &str) -> rusqlite::Result<()> {
         self.conn.execute(
-            "UPDATE messages SET receipt_status = ?1 WHERE id = ?2",
+            "UPDATE messages SET receipt_status = ?1 WHERE id = ?2 AND read_ts_ms IS NULL",
             params![status, message_id],
         )?;
         Ok(())
     }
 
+    /// Marks a message read, keeping the first read time if it was already set.
+    pub fn mark_read(&self, message_id: &str, read_ts_ms: u64) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "UPDATE messages SET receipt_status = 'read', read_ts_ms = COALESCE(read_ts_ms, ?1) WHERE id = ?2",
+            params![read_ts_ms as i64, message_id],
+        )?;
+        Ok(())
+    }
+
+    /// Like [`Self::mark_read`], but only for an outbound message addressed to
+    /// `reader`, so a peer can only mark read what was sent to it. Returns
+    /// whether a message matched.
+    pub fn mark_read_by(
+        &self,
+        message_id: &str,
+        reader: &str,
+        read_ts_ms: u64,
+    ) -> rusqlite::Result<bool> {
+        let changed = self.conn.execute(
+            "UPDATE messages SET receipt_status = 'read', read_ts_ms = COALESCE(read_ts_ms, ?1) WHERE id = ?2 AND direction = 'out' AND destination = ?3",
+            params![read_ts_ms as i64, message_id, reader],
+        )?;
+        Ok(changed > 0)
+    }
+
+    pub fn put_scheduled_send(&self, record: &ScheduledSendRecord) -> rusqlite::Result<()> {
+        let options_json = serde_json::to_string(&record.options)
+            .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+        self.conn.execute(
+            "INSERT INTO scheduled_sends (message_id, due_ms, method, options)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(message_id) DO UPDATE SET
+                due_ms = excluded.due_ms,
+                method = excluded.method,
+                options = excluded.options",
+            params![
+                &record.message_id,
+                i64::try_from(record.due_ms).unwrap_or(i64::MAX),
+                &record.method,
+                options_json
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_scheduled_send(&self, message_id: &str) -> rusqlite::Result<bool> {
+        let deleted = self
+            .conn
+            .execute("DELETE FROM scheduled_sends WHERE message_id = ?1", params![message_id])?;
+        Ok(deleted > 0)
+    }
+
+    /// Returns every persisted scheduled send, earliest due first.
+    pub fn list_scheduled_sends(&self) -> rusqlite::Result<Vec<ScheduledSendRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT message_id, due_ms, method, options
+             FROM scheduled_sends
+             ORDER BY due_ms ASC, message_id ASC",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let options_json: String = row.get(3)?;
+            let options = serde_json::from_str(&options_json).map_err(|err| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    3,
+                    rusqlite::types::Type::Text,
+                    Box::new(err),
+                )
+            })?;
+            Ok(ScheduledSendRecord {
+                message_id: row.get(0)?,
+                due_ms: u64::try_from(row.get::<_, i64>(1)?).unwrap_or(0),
+                method: row.get(2)?,
+                options,
+            })
+        })?;
+        rows.collect()
+    }
+
     pub fn clear_messages(&self) -> rusqlite::Result<()> {
         self.conn.execute("DELETE FROM messages", [])?;
         Ok(())
@@ -222,6 +343,19 @@ impl MessagesStore {
         Ok(records)
     }
 
+    /// Peers whose newest announce is older than `cutoff_ts` (seconds).
+    pub fn stale_announce_peers(&self, cutoff_ts: i64) -> rusqlite::Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT peer FROM announces GROUP BY peer HAVING MAX(timestamp) < ?1")?;
+        let rows = stmt.query_map(params![cutoff_ts], |row| row.get(0))?;
+        rows.collect()
+    }
+
+    pub fn delete_peer_announces(&self, peer: &str) -> rusqlite::Result<usize> {
+        self.conn.execute("DELETE FROM announces WHERE peer = ?1", params![peer])
+    }
+
     pub fn clear_announces(&self) -> rusqlite::Result<()> {
         self.conn.execute("DELETE FROM announces", [])?;
         Ok(())
@@ -238,7 +372,8 @@ impl MessagesStore {
                 timestamp INTEGER NOT NULL,
                 direction TEXT NOT NULL,
                 fields TEXT,
-                receipt_status TEXT
+                receipt_status TEXT,
+                read_ts_ms INTEGER
             );
             CREATE TABLE IF NOT EXISTS announces (
                 id TEXT PRIMARY KEY,
@@ -255,12 +390,19 @@ impl MessagesStore {
                 q REAL,
                 stamp_cost_flexibility INTEGER,
                 peering_cost INTEGER
+            );
+            CREATE TABLE IF NOT EXISTS scheduled_sends (
+                message_id TEXT PRIMARY KEY,
+                due_ms INTEGER NOT NULL,
+                method TEXT,
+                options TEXT NOT NULL
             );",
         )?;
         let _ = self.conn.execute("ALTER TABLE messages ADD COLUMN title TEXT", []);
         let _ = self.conn.execute("UPDATE messages SET title = '' WHERE title IS NULL", []);
         let _ = self.conn.execute("ALTER TABLE messages ADD COLUMN fields TEXT", []);
         let _ = self.conn.execute("ALTER TABLE messages ADD COLUMN receipt_status TEXT", []);
+        let _ = self.conn.execute("ALTER TABLE messages ADD COLUMN read_ts_ms INTEGER", []);
         let _ = self.conn.execute("ALTER TABLE announces ADD COLUMN name TEXT", []);
         let _ = self.conn.execute("ALTER TABLE announces ADD COLUMN name_source TEXT", []);
         let _ = self.conn.execute("ALTER TABLE announces ADD COLUMN first_seen INTEGER", []);
@@ -97,6 +97,7 @@ pub mod test_bridge {
             direction: "in".into(),
             fields: record.fields.clone(),
             receipt_status: None,
+            read_ts_ms: None,
         };
         let _ = daemon.accept_inbound_for_test(inbound);
         true
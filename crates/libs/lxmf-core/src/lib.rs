@@ -10,6 +10,9 @@ pub mod identity;
 pub mod inbound_decode;
 pub mod message;
 pub mod payload_fields;
+pub mod propagation;
+#[cfg(feature = "std")]
+pub mod stamper;
 #[cfg(feature = "std")]
 pub mod wire_fields;
 
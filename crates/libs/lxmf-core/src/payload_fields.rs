@@ -5,6 +5,10 @@ use alloc::string::ToString;
 use alloc::vec;
 use alloc::vec::Vec;
 
+/// JSON key carrying a base64 msgpack map of wire fields, for values such as
+/// binary payloads that plain JSON fields cannot express.
+pub const TRANSPORT_FIELDS_MSGPACK_B64_KEY: &str = "_lxmf_fields_msgpack_b64";
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CommandEntry {
     pub command_id: u8,
@@ -63,6 +67,51 @@ impl WireFields {
     pub fn encode_msgpack(&self) -> Result<Vec<u8>, LxmfError> {
         rmp_serde::to_vec(&self.to_rmpv()).map_err(|err| LxmfError::Encode(err.to_string()))
     }
+
+    #[cfg(feature = "std")]
+    pub fn to_transport_json(&self) -> Result<serde_json::Value, LxmfError> {
+        use base64::Engine as _;
+        let encoded = self.encode_msgpack()?;
+        let b64 = base64::engine::general_purpose::STANDARD.encode(encoded);
+        Ok(serde_json::json!({
+            TRANSPORT_FIELDS_MSGPACK_B64_KEY: b64
+        }))
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn decode_transport_fields_json(
+    fields: &serde_json::Value,
+) -> Result<Option<rmpv::Value>, LxmfError> {
+    let Some(object) = fields.as_object() else {
+        return Ok(None);
+    };
+    let Some(encoded) =
+        object.get(TRANSPORT_FIELDS_MSGPACK_B64_KEY).and_then(serde_json::Value::as_str)
+    else {
+        return Ok(None);
+    };
+
+    let bytes = decode_b64_msgpack(encoded)?;
+    let mut cursor = std::io::Cursor::new(bytes);
+    let decoded =
+        rmpv::decode::read_value(&mut cursor).map_err(|err| LxmfError::Decode(err.to_string()))?;
+    Ok(Some(decoded))
+}
+
+#[cfg(feature = "std")]
+fn decode_b64_msgpack(encoded: &str) -> Result<Vec<u8>, LxmfError> {
+    use alloc::format;
+    use base64::Engine as _;
+    base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .or_else(|_| base64::engine::general_purpose::URL_SAFE.decode(encoded))
+        .map_err(|err| {
+            LxmfError::Decode(format!(
+                "invalid {} payload: {err}",
+                TRANSPORT_FIELDS_MSGPACK_B64_KEY
+            ))
+        })
 }
 
 #[cfg(test)]
@@ -86,4 +135,31 @@ mod tests {
         };
         assert_eq!(cmds.len(), 2);
     }
+
+    #[test]
+    fn transport_fields_keep_binary_commands_through_json() {
+        let mut fields = WireFields::new();
+        fields.set_commands(vec![CommandEntry::from_text(0x01, "ping")]);
+        let mut json = fields.to_transport_json().expect("transport json");
+        json["8"] = serde_json::json!("thread");
+        json["9"] = serde_json::json!([{ "1": "shadowed" }]);
+
+        let rmpv::Value::Map(entries) = crate::wire_fields::json_to_rmpv(&json).expect("wire")
+        else {
+            panic!("expected map")
+        };
+        assert_eq!(entries.len(), 2);
+        let commands = entries
+            .iter()
+            .find(|(key, _)| key.as_i64() == Some(FIELD_COMMANDS as i64))
+            .map(|(_, value)| value.clone())
+            .expect("commands field");
+        assert_eq!(
+            commands,
+            rmpv::Value::Array(vec![rmpv::Value::Map(vec![(
+                rmpv::Value::Integer(1.into()),
+                rmpv::Value::Binary(b"ping".to_vec()),
+            )])])
+        );
+    }
 }
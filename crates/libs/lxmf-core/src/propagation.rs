@@ -0,0 +1,32 @@
+//! Propagation node parameters advertised in `lxmf.propagation` announces.
+
+/// Lowest stamp cost a propagation node may require.
+pub const PROPAGATION_COST_MIN: u32 = 13;
+/// Stamp cost shortfall a propagation node tolerates below its advertised cost.
+pub const PROPAGATION_COST_FLEX: u32 = 3;
+/// Default stamp cost for messages handed to a propagation node.
+pub const PROPAGATION_COST: u32 = 16;
+/// Largest single propagation transfer, in kilobytes.
+pub const PROPAGATION_LIMIT: u32 = 256;
+/// Largest propagation sync, in kilobytes.
+pub const SYNC_LIMIT: u32 = PROPAGATION_LIMIT * 40;
+/// Default stamp cost for peering with another propagation node.
+pub const PEERING_COST: u32 = 18;
+
+/// Announce metadata key carrying the node's display name.
+pub const PN_META_NAME: u8 = 0x01;
+
+/// Propagation sync states, numbered as in the LXMF reference router.
+pub const PR_IDLE: u32 = 0x00;
+pub const PR_PATH_REQUESTED: u32 = 0x01;
+pub const PR_LINK_ESTABLISHING: u32 = 0x02;
+pub const PR_LINK_ESTABLISHED: u32 = 0x03;
+pub const PR_REQUEST_SENT: u32 = 0x04;
+pub const PR_RECEIVING: u32 = 0x05;
+pub const PR_RESPONSE_RECEIVED: u32 = 0x06;
+pub const PR_COMPLETE: u32 = 0x07;
+pub const PR_NO_PATH: u32 = 0xF0;
+pub const PR_LINK_FAILED: u32 = 0xF1;
+pub const PR_TRANSFER_FAILED: u32 = 0xF2;
+pub const PR_NO_IDENTITY_RCVD: u32 = 0xF3;
+pub const PR_NO_ACCESS: u32 = 0xF4;
@@ -0,0 +1,235 @@
+use hkdf::Hkdf;
+use rns_core::hash::{Hash, HASH_SIZE};
+use sha2::Sha256;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::vec::Vec;
+
+pub const WORKBLOCK_EXPAND_ROUNDS: usize = 3000;
+pub const WORKBLOCK_EXPAND_ROUNDS_PN: usize = 1000;
+
+const DEFAULT_PROGRESS_INTERVAL: u64 = 4096;
+
+/// `(transient_id, lxmf_data, stamp_value, stamp)` of a propagated message
+/// whose stamp met the required cost.
+pub type PnStampValidation = (Vec<u8>, Vec<u8>, u32, Vec<u8>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StampProgress {
+    pub attempts: u64,
+    pub target_cost: u32,
+}
+
+pub fn stamp_workblock(material: &[u8], expand_rounds: usize) -> Vec<u8> {
+    let mut workblock = Vec::with_capacity(expand_rounds * 256);
+    for n in 0..expand_rounds {
+        let mut salt_data = Vec::with_capacity(material.len() + 9);
+        salt_data.extend_from_slice(material);
+        salt_data.extend_from_slice(&rmp_serde::to_vec(&n).unwrap_or_default());
+        let salt = Hash::new_from_slice(&salt_data);
+
+        let hk = Hkdf::<Sha256>::new(Some(salt.as_slice()), material);
+        let mut okm = [0u8; 256];
+        if hk.expand(&[], &mut okm).is_ok() {
+            workblock.extend_from_slice(&okm);
+        }
+    }
+    workblock
+}
+
+pub fn stamp_value(workblock: &[u8], stamp: &[u8]) -> u32 {
+    let mut data = Vec::with_capacity(workblock.len() + stamp.len());
+    data.extend_from_slice(workblock);
+    data.extend_from_slice(stamp);
+    let hash = Hash::new_from_slice(&data);
+
+    let mut value = 0u32;
+    for byte in hash.as_slice() {
+        if *byte == 0 {
+            value += 8;
+        } else {
+            value += byte.leading_zeros();
+            break;
+        }
+    }
+    value
+}
+
+pub fn stamp_valid(stamp: &[u8], target_cost: u32, workblock: &[u8]) -> bool {
+    stamp_value(workblock, stamp) >= target_cost
+}
+
+/// Splits propagated transient data into the LXMF data and its trailing
+/// propagation stamp, as legacy `validate_pn_stamp` does. Returns `None` when
+/// the data is too short to carry a stamp or the stamp is below `target_cost`.
+pub fn validate_pn_stamp(transient_data: &[u8], target_cost: u32) -> Option<PnStampValidation> {
+    if transient_data.len() <= HASH_SIZE {
+        return None;
+    }
+    let (lxmf_data, stamp) = transient_data.split_at(transient_data.len() - HASH_SIZE);
+    let transient_id = Hash::new_from_slice(lxmf_data).to_bytes().to_vec();
+    let workblock = stamp_workblock(&transient_id, WORKBLOCK_EXPAND_ROUNDS_PN);
+    let value = stamp_value(&workblock, stamp);
+    if value < target_cost {
+        return None;
+    }
+    Some((transient_id, lxmf_data.to_vec(), value, stamp.to_vec()))
+}
+
+/// Brute-forces stamps across a fixed pool of worker threads.
+///
+/// Workers interleave the nonce space, stop as soon as any of them finds a
+/// valid stamp, and poll the caller's cancel flag between attempts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StampGenerator {
+    workers: usize,
+    progress_interval: u64,
+}
+
+impl Default for StampGenerator {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl StampGenerator {
+    pub fn new(workers: usize) -> Self {
+        Self { workers: workers.max(1), progress_interval: DEFAULT_PROGRESS_INTERVAL }
+    }
+
+    pub fn with_progress_interval(mut self, attempts: u64) -> Self {
+        self.progress_interval = attempts.max(1);
+        self
+    }
+
+    pub fn workers(&self) -> usize {
+        self.workers
+    }
+
+    /// Returns `None` when `cancel` is raised before a valid stamp is found.
+    pub fn generate<F>(
+        &self,
+        material: &[u8],
+        target_cost: u32,
+        expand_rounds: usize,
+        cancel: &AtomicBool,
+        on_progress: F,
+    ) -> Option<Vec<u8>>
+    where
+        F: Fn(StampProgress) + Sync,
+    {
+        let workblock = stamp_workblock(material, expand_rounds);
+        let attempts = AtomicU64::new(0);
+        let done = AtomicBool::new(false);
+        let found: Mutex<Option<Vec<u8>>> = Mutex::new(None);
+        let stride = self.workers as u64;
+
+        std::thread::scope(|scope| {
+            for worker in 0..stride {
+                let workblock = workblock.as_slice();
+                let attempts = &attempts;
+                let done = &done;
+                let found = &found;
+                let on_progress = &on_progress;
+                let interval = self.progress_interval;
+                scope.spawn(move || {
+                    let mut nonce = worker;
+                    let mut pending = 0u64;
+                    while !done.load(Ordering::Relaxed) && !cancel.load(Ordering::Relaxed) {
+                        let stamp = nonce.to_le_bytes();
+                        pending += 1;
+                        if stamp_valid(&stamp, target_cost, workblock) {
+                            done.store(true, Ordering::Relaxed);
+                            if let Ok(mut slot) = found.lock() {
+                                slot.get_or_insert_with(|| stamp.to_vec());
+                            }
+                            break;
+                        }
+                        if pending >= interval {
+                            let total = attempts.fetch_add(pending, Ordering::Relaxed) + pending;
+                            pending = 0;
+                            on_progress(StampProgress { attempts: total, target_cost });
+                        }
+                        nonce = match nonce.checked_add(stride) {
+                            Some(next) => next,
+                            None => break,
+                        };
+                    }
+                    attempts.fetch_add(pending, Ordering::Relaxed);
+                });
+            }
+        });
+
+        if cancel.load(Ordering::Relaxed) && !done.load(Ordering::Relaxed) {
+            return None;
+        }
+        found.into_inner().ok().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        stamp_valid, stamp_value, stamp_workblock, validate_pn_stamp, StampGenerator,
+        WORKBLOCK_EXPAND_ROUNDS_PN,
+    };
+    use rns_core::hash::{Hash, HASH_SIZE};
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+    #[test]
+    fn pn_stamp_validation_splits_data_and_checks_cost() {
+        let lxmf_data = b"propagated lxmf data".to_vec();
+        let transient_id = Hash::new_from_slice(&lxmf_data).to_bytes();
+        let workblock = stamp_workblock(&transient_id, WORKBLOCK_EXPAND_ROUNDS_PN);
+        let stamp = (0u64..)
+            .map(|nonce| {
+                let mut stamp = [0u8; HASH_SIZE];
+                stamp[..8].copy_from_slice(&nonce.to_le_bytes());
+                stamp
+            })
+            .find(|stamp| stamp_valid(stamp, 4, &workblock))
+            .expect("stamp");
+        let transient_data = [lxmf_data.as_slice(), stamp.as_slice()].concat();
+
+        let (id, data, value, found) = validate_pn_stamp(&transient_data, 4).expect("valid");
+        assert_eq!(id, transient_id.to_vec());
+        assert_eq!(data, lxmf_data);
+        assert_eq!(value, stamp_value(&workblock, &stamp));
+        assert_eq!(found, stamp.to_vec());
+        assert!(validate_pn_stamp(&transient_data, value + 1).is_none());
+        assert!(validate_pn_stamp(&stamp, 0).is_none());
+    }
+
+    #[test]
+    fn generator_reports_progress_and_returns_valid_stamp() {
+        let material = [0x42u8; 32];
+        let reports = AtomicU64::new(0);
+        let cancel = AtomicBool::new(false);
+        let generator = StampGenerator::new(2).with_progress_interval(1);
+
+        let stamp = generator
+            .generate(&material, 6, 2, &cancel, |progress| {
+                assert_eq!(progress.target_cost, 6);
+                reports.fetch_add(1, Ordering::Relaxed);
+            })
+            .expect("stamp");
+
+        assert!(stamp_valid(&stamp, 6, &stamp_workblock(&material, 2)));
+        assert!(reports.load(Ordering::Relaxed) > 0);
+    }
+
+    #[test]
+    fn generator_stops_when_cancelled_mid_flight() {
+        let material = [0x17u8; 32];
+        let cancel = AtomicBool::new(false);
+        let generator = StampGenerator::new(2).with_progress_interval(8);
+
+        let stamp = generator.generate(&material, 255, 1, &cancel, |progress| {
+            if progress.attempts >= 64 {
+                cancel.store(true, Ordering::Relaxed);
+            }
+        });
+
+        assert!(stamp.is_none());
+    }
+}
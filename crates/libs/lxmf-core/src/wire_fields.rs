@@ -1,3 +1,4 @@
+use crate::payload_fields::{decode_transport_fields_json, TRANSPORT_FIELDS_MSGPACK_B64_KEY};
 use crate::LxmfError;
 use alloc::format;
 use alloc::string::String;
@@ -71,12 +72,31 @@ pub fn normalize_attachment_fields_for_wire(
     Ok(())
 }
 
+/// Converts JSON fields to their wire form. Entries carried under
+/// `TRANSPORT_FIELDS_MSGPACK_B64_KEY` are decoded as-is and take precedence
+/// over JSON entries with the same field id, so binary values survive.
 pub fn json_to_rmpv(value: &JsonValue) -> Result<Value, LxmfError> {
+    let transport = decode_transport_fields_json(value)?;
     let mut normalized = value.clone();
     if let JsonValue::Object(map) = &mut normalized {
+        map.remove(TRANSPORT_FIELDS_MSGPACK_B64_KEY);
         normalize_attachment_fields_for_wire(map)?;
     }
-    json_to_rmpv_lossless(&normalized)
+    let converted = json_to_rmpv_lossless(&normalized)?;
+    match (converted, transport) {
+        (converted, None) => Ok(converted),
+        (Value::Map(mut entries), Some(Value::Map(transport))) => {
+            for (key, value) in transport {
+                entries.retain(|(existing, _)| existing != &key);
+                entries.push((key, value));
+            }
+            Ok(Value::Map(entries))
+        }
+        (_, Some(_)) => Err(LxmfError::Encode(format!(
+            "field '{}' must encode a msgpack map",
+            TRANSPORT_FIELDS_MSGPACK_B64_KEY
+        ))),
+    }
 }
 
 pub fn rmpv_to_json(value: &Value) -> Option<JsonValue> {
@@ -3,13 +3,16 @@ use crate::domain::{
     AttachmentListResult, AttachmentMeta, AttachmentStoreRequest, AttachmentUploadChunkAck,
     AttachmentUploadChunkRequest, AttachmentUploadCommitRequest, AttachmentUploadSession,
     AttachmentUploadStartRequest, ContactListRequest, ContactListResult, ContactRecord,
-    ContactUpdateRequest, IdentityBootstrapRequest, IdentityBundle, IdentityImportRequest,
-    IdentityRef, IdentityResolveRequest, MarkerCreateRequest, MarkerDeleteRequest,
-    MarkerListRequest, MarkerListResult, MarkerRecord, MarkerUpdatePositionRequest,
-    PaperMessageEnvelope, PresenceListRequest, PresenceListResult, RemoteCommandRequest,
-    RemoteCommandResponse, TelemetryPoint, TelemetryQuery, TopicCreateRequest, TopicId,
-    TopicListRequest, TopicListResult, TopicPublishRequest, TopicRecord, TopicSubscriptionRequest,
-    VoiceSessionId, VoiceSessionOpenRequest, VoiceSessionState, VoiceSessionUpdateRequest,
+    ContactUpdateRequest, DeliveryTrace, DisplayNameUpdate, HealthReport, IdentityBootstrapRequest,
+    IdentityBundle, IdentityImportRequest, IdentityRef, IdentityResolveRequest, LogLevel, LogsTail,
+    MarkerCreateRequest, MarkerDeleteRequest, MarkerListRequest, MarkerListResult, MarkerRecord,
+    MarkerUpdatePositionRequest, MessageAttachment, MessageExportBundle, MessageExportRequest,
+    MessagePruneResult, MessageSearchRequest, MessageSearchResult, PaperMessageEnvelope,
+    PresenceListRequest, PresenceListResult, PropagationStats, PropagationSyncStatus,
+    RemoteCommandRequest, RemoteCommandResponse, StampStatus, TelemetryPoint, TelemetryQuery,
+    TopicCreateRequest, TopicId, TopicListRequest, TopicListResult, TopicPublishRequest,
+    TopicRecord, TopicSubscriptionRequest, VoiceSessionId, VoiceSessionOpenRequest,
+    VoiceSessionState, VoiceSessionUpdateRequest, Whoami,
 };
 use crate::error::SdkError;
 use crate::event::{EventBatch, EventCursor};
@@ -17,8 +20,8 @@ use crate::event::{EventBatch, EventCursor};
 use crate::event::{EventSubscription, SubscriptionStart};
 use crate::types::{
     Ack, CancelResult, ClientHandle, ConfigPatch, DeliverySnapshot, GroupSendRequest,
-    GroupSendResult, MessageId, RuntimeSnapshot, SendRequest, ShutdownMode, StartRequest,
-    TickBudget, TickResult,
+    GroupSendResult, MessageId, RuntimeSnapshot, RuntimeSnapshotDelta, SendRequest, ShutdownMode,
+    StartRequest, TickBudget, TickResult, VersionInfo,
 };
 
 pub trait LxmfSdk {
@@ -30,6 +33,50 @@ pub trait LxmfSdk {
     fn poll_events(&self, cursor: Option<EventCursor>, max: usize) -> Result<EventBatch, SdkError>;
     fn snapshot(&self) -> Result<RuntimeSnapshot, SdkError>;
     fn shutdown(&self, mode: ShutdownMode) -> Result<Ack, SdkError>;
+
+    /// Stops accepting sends and waits up to `drain_timeout_ms` for queued
+    /// messages to settle before stopping. The returned `Ack` reports how many
+    /// were still pending.
+    fn shutdown_graceful(&self, _drain_timeout_ms: u64) -> Result<Ack, SdkError> {
+        self.shutdown(ShutdownMode::Graceful)
+    }
+
+    /// Returns only the snapshot fields changed since `since_revision`.
+    /// Revision 0 always yields the full snapshot.
+    fn snapshot_since(&self, _since_revision: u64) -> Result<RuntimeSnapshotDelta, SdkError> {
+        self.snapshot().map(|snapshot| RuntimeSnapshotDelta::full(&snapshot))
+    }
+
+    /// Reports the runtime's contract release and build. Needs no started
+    /// session, so tooling can probe compatibility before `start`, but the
+    /// runtime authorizes the call like any other.
+    fn version(&self) -> Result<VersionInfo, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.version"))
+    }
+
+    /// Polls only events whose `event_type` is in `event_types`; an empty
+    /// list returns every type. The cursor still advances past the rest.
+    fn poll_events_filtered(
+        &self,
+        cursor: Option<EventCursor>,
+        max: usize,
+        event_types: &[String],
+    ) -> Result<EventBatch, SdkError> {
+        let mut batch = self.poll_events(cursor, max)?;
+        if !event_types.is_empty() {
+            batch.events.retain(|event| event_types.contains(&event.event_type));
+        }
+        Ok(batch)
+    }
+
+    /// Submits several messages in one round-trip. The outer error covers the
+    /// whole batch; each item carries its own result in request order.
+    fn send_batch(
+        &self,
+        _reqs: Vec<SendRequest>,
+    ) -> Result<Vec<Result<MessageId, SdkError>>, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.send_batch"))
+    }
 }
 
 pub trait LxmfSdkManualTick {
@@ -92,6 +139,14 @@ pub trait LxmfSdkAttachments {
         Err(SdkError::capability_disabled("sdk.capability.attachments"))
     }
 
+    fn message_attachment_get(
+        &self,
+        _message_id: MessageId,
+        _index: usize,
+    ) -> Result<MessageAttachment, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.attachments"))
+    }
+
     fn attachment_list(
         &self,
         _req: AttachmentListRequest,
@@ -174,6 +229,14 @@ pub trait LxmfSdkIdentity {
         Err(SdkError::capability_disabled("sdk.capability.identity_discovery"))
     }
 
+    fn whoami(&self) -> Result<Whoami, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.whoami"))
+    }
+
+    fn set_display_name(&self, _display_name: &str) -> Result<DisplayNameUpdate, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.display_name"))
+    }
+
     fn identity_presence_list(
         &self,
         _req: PresenceListRequest,
@@ -274,3 +337,57 @@ pub trait LxmfSdkGroupDelivery {
         Err(SdkError::capability_disabled("sdk.capability.group_delivery"))
     }
 }
+
+pub trait LxmfSdkLogs {
+    fn logs_tail(
+        &self,
+        _after_offset: Option<u64>,
+        _max_lines: usize,
+    ) -> Result<LogsTail, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.logs_tail"))
+    }
+
+    /// Like [`Self::logs_tail`], returning only lines at `min_level` or above.
+    fn logs_tail_at_level(
+        &self,
+        _after_offset: Option<u64>,
+        _max_lines: usize,
+        _min_level: LogLevel,
+    ) -> Result<LogsTail, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.logs_tail"))
+    }
+
+    fn delivery_trace(&self, _id: MessageId) -> Result<DeliveryTrace, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.delivery_trace"))
+    }
+
+    fn prune_messages(&self, _older_than_ms: u64) -> Result<MessagePruneResult, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.prune_messages"))
+    }
+
+    fn messages_search(&self, _req: MessageSearchRequest) -> Result<MessageSearchResult, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.messages_search"))
+    }
+
+    fn messages_export(&self, _req: MessageExportRequest) -> Result<MessageExportBundle, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.messages_export"))
+    }
+
+    fn health(&self) -> Result<HealthReport, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.health"))
+    }
+}
+
+pub trait LxmfSdkPropagation {
+    fn propagation_stats(&self) -> Result<PropagationStats, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.propagation_stats"))
+    }
+
+    fn propagation_sync_now(&self) -> Result<PropagationSyncStatus, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.sync_now"))
+    }
+
+    fn stamp_status(&self) -> Result<StampStatus, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.stamp_status"))
+    }
+}
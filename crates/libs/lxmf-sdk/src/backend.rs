@@ -4,21 +4,24 @@ use crate::domain::{
     AttachmentListResult, AttachmentMeta, AttachmentStoreRequest, AttachmentUploadChunkAck,
     AttachmentUploadChunkRequest, AttachmentUploadCommitRequest, AttachmentUploadSession,
     AttachmentUploadStartRequest, ContactListRequest, ContactListResult, ContactRecord,
-    ContactUpdateRequest, IdentityBootstrapRequest, IdentityBundle, IdentityImportRequest,
-    IdentityRef, IdentityResolveRequest, MarkerCreateRequest, MarkerDeleteRequest,
-    MarkerListRequest, MarkerListResult, MarkerRecord, MarkerUpdatePositionRequest,
-    PaperMessageEnvelope, PresenceListRequest, PresenceListResult, RemoteCommandRequest,
-    RemoteCommandResponse, TelemetryPoint, TelemetryQuery, TopicCreateRequest, TopicId,
-    TopicListRequest, TopicListResult, TopicPublishRequest, TopicRecord, TopicSubscriptionRequest,
-    VoiceSessionId, VoiceSessionOpenRequest, VoiceSessionState, VoiceSessionUpdateRequest,
+    ContactUpdateRequest, DeliveryTrace, DisplayNameUpdate, HealthReport, IdentityBootstrapRequest,
+    IdentityBundle, IdentityImportRequest, IdentityRef, IdentityResolveRequest, LogLevel, LogsTail,
+    MarkerCreateRequest, MarkerDeleteRequest, MarkerListRequest, MarkerListResult, MarkerRecord,
+    MarkerUpdatePositionRequest, MessageAttachment, MessageExportBundle, MessageExportRequest,
+    MessagePruneResult, MessageSearchRequest, MessageSearchResult, PaperMessageEnvelope,
+    PresenceListRequest, PresenceListResult, PropagationStats, PropagationSyncStatus,
+    RemoteCommandRequest, RemoteCommandResponse, StampStatus, TelemetryPoint, TelemetryQuery,
+    TopicCreateRequest, TopicId, TopicListRequest, TopicListResult, TopicPublishRequest,
+    TopicRecord, TopicSubscriptionRequest, VoiceSessionId, VoiceSessionOpenRequest,
+    VoiceSessionState, VoiceSessionUpdateRequest, Whoami,
 };
 use crate::error::{code, ErrorCategory, SdkError};
 use crate::event::{EventBatch, EventCursor};
 #[cfg(feature = "sdk-async")]
 use crate::event::{EventSubscription, SubscriptionStart};
 use crate::types::{
-    Ack, CancelResult, ConfigPatch, DeliverySnapshot, MessageId, RuntimeSnapshot, SendRequest,
-    ShutdownMode, TickBudget, TickResult,
+    Ack, CancelResult, ConfigPatch, DeliverySnapshot, MessageId, RuntimeSnapshot,
+    RuntimeSnapshotDelta, SendRequest, ShutdownMode, TickBudget, TickResult, VersionInfo,
 };
 use serde::{Deserialize, Serialize};
 
@@ -63,10 +66,48 @@ pub trait SdkBackend: Send + Sync {
 
     fn poll_events(&self, cursor: Option<EventCursor>, max: usize) -> Result<EventBatch, SdkError>;
 
+    fn poll_events_filtered(
+        &self,
+        cursor: Option<EventCursor>,
+        max: usize,
+        event_types: &[String],
+    ) -> Result<EventBatch, SdkError> {
+        let mut batch = self.poll_events(cursor, max)?;
+        if !event_types.is_empty() {
+            batch.events.retain(|event| event_types.contains(&event.event_type));
+        }
+        Ok(batch)
+    }
+
     fn snapshot(&self) -> Result<RuntimeSnapshot, SdkError>;
 
     fn shutdown(&self, mode: ShutdownMode) -> Result<Ack, SdkError>;
 
+    fn shutdown_graceful(&self, _drain_timeout_ms: u64) -> Result<Ack, SdkError> {
+        self.shutdown(ShutdownMode::Graceful)
+    }
+
+    fn snapshot_since(&self, _since_revision: u64) -> Result<RuntimeSnapshotDelta, SdkError> {
+        self.snapshot().map(|snapshot| RuntimeSnapshotDelta::full(&snapshot))
+    }
+
+    fn version(&self) -> Result<VersionInfo, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.version"))
+    }
+
+    fn send_batch(
+        &self,
+        _reqs: Vec<SendRequest>,
+    ) -> Result<Vec<Result<MessageId, SdkError>>, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.send_batch"))
+    }
+
+    /// Whether the runtime answered the send that returned `id` with the
+    /// message an earlier send under the same idempotency key created.
+    fn send_deduplicated(&self, _id: &MessageId) -> bool {
+        false
+    }
+
     fn tick(&self, _budget: TickBudget) -> Result<TickResult, SdkError> {
         Err(SdkError::new(
             code::CAPABILITY_DISABLED,
@@ -118,6 +159,14 @@ pub trait SdkBackend: Send + Sync {
         Err(SdkError::capability_disabled("sdk.capability.attachments"))
     }
 
+    fn message_attachment_get(
+        &self,
+        _message_id: MessageId,
+        _index: usize,
+    ) -> Result<MessageAttachment, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.attachments"))
+    }
+
     fn attachment_list(
         &self,
         _req: AttachmentListRequest,
@@ -196,6 +245,14 @@ pub trait SdkBackend: Send + Sync {
         Err(SdkError::capability_disabled("sdk.capability.identity_discovery"))
     }
 
+    fn whoami(&self) -> Result<Whoami, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.whoami"))
+    }
+
+    fn set_display_name(&self, _display_name: &str) -> Result<DisplayNameUpdate, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.display_name"))
+    }
+
     fn identity_presence_list(
         &self,
         _req: PresenceListRequest,
@@ -283,6 +340,55 @@ pub trait SdkBackend: Send + Sync {
     fn voice_session_close(&self, _session_id: VoiceSessionId) -> Result<Ack, SdkError> {
         Err(SdkError::capability_disabled("sdk.capability.voice_signaling"))
     }
+
+    fn propagation_stats(&self) -> Result<PropagationStats, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.propagation_stats"))
+    }
+
+    fn propagation_sync_now(&self) -> Result<PropagationSyncStatus, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.sync_now"))
+    }
+
+    fn stamp_status(&self) -> Result<StampStatus, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.stamp_status"))
+    }
+
+    fn logs_tail(
+        &self,
+        _after_offset: Option<u64>,
+        _max_lines: usize,
+    ) -> Result<LogsTail, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.logs_tail"))
+    }
+
+    fn logs_tail_at_level(
+        &self,
+        _after_offset: Option<u64>,
+        _max_lines: usize,
+        _min_level: LogLevel,
+    ) -> Result<LogsTail, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.logs_tail"))
+    }
+
+    fn delivery_trace(&self, _id: MessageId) -> Result<DeliveryTrace, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.delivery_trace"))
+    }
+
+    fn prune_messages(&self, _older_than_ms: u64) -> Result<MessagePruneResult, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.prune_messages"))
+    }
+
+    fn messages_search(&self, _req: MessageSearchRequest) -> Result<MessageSearchResult, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.messages_search"))
+    }
+
+    fn messages_export(&self, _req: MessageExportRequest) -> Result<MessageExportBundle, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.messages_export"))
+    }
+
+    fn health(&self) -> Result<HealthReport, SdkError> {
+        Err(SdkError::capability_disabled("sdk.capability.health"))
+    }
 }
 
 pub trait SdkBackendKeyManagement: SdkBackend {
@@ -365,7 +471,7 @@ mod tests {
         }
 
         fn configure(&self, _expected_revision: u64, _patch: ConfigPatch) -> Result<Ack, SdkError> {
-            Ok(Ack { accepted: true, revision: Some(1) })
+            Ok(Ack { accepted: true, revision: Some(1), pending_messages: None })
         }
 
         fn poll_events(
@@ -391,11 +497,18 @@ mod tests {
                 config_revision: 1,
                 queued_messages: 0,
                 in_flight_messages: 0,
+                announce_interval_secs: None,
+                outbound_queue_depth: 0,
+                inbound_pending: 0,
+                event_buffer_used: 0,
+                event_buffer_capacity: 0,
+                messages_stored: 0,
+                propagation_node: false,
             })
         }
 
         fn shutdown(&self, _mode: ShutdownMode) -> Result<Ack, SdkError> {
-            Ok(Ack { accepted: true, revision: Some(2) })
+            Ok(Ack { accepted: true, revision: Some(2), pending_messages: None })
         }
 
         fn tick(&self, _budget: TickBudget) -> Result<crate::types::TickResult, SdkError> {
@@ -7,13 +7,16 @@ use crate::domain::{
     AttachmentListResult, AttachmentMeta, AttachmentStoreRequest, AttachmentUploadChunkAck,
     AttachmentUploadChunkRequest, AttachmentUploadCommitRequest, AttachmentUploadSession,
     AttachmentUploadStartRequest, ContactListRequest, ContactListResult, ContactRecord,
-    ContactUpdateRequest, IdentityBootstrapRequest, IdentityBundle, IdentityImportRequest,
-    IdentityRef, IdentityResolveRequest, MarkerCreateRequest, MarkerDeleteRequest,
-    MarkerListRequest, MarkerListResult, MarkerRecord, MarkerUpdatePositionRequest,
-    PaperMessageEnvelope, PresenceListRequest, PresenceListResult, RemoteCommandRequest,
-    RemoteCommandResponse, TelemetryPoint, TelemetryQuery, TopicCreateRequest, TopicId,
-    TopicListRequest, TopicListResult, TopicPublishRequest, TopicRecord, TopicSubscriptionRequest,
-    VoiceSessionId, VoiceSessionOpenRequest, VoiceSessionState, VoiceSessionUpdateRequest,
+    ContactUpdateRequest, DeliveryTrace, DisplayNameUpdate, HealthReport, IdentityBootstrapRequest,
+    IdentityBundle, IdentityImportRequest, IdentityRef, IdentityResolveRequest, LogLevel, LogsTail,
+    MarkerCreateRequest, MarkerDeleteRequest, MarkerListRequest, MarkerListResult, MarkerRecord,
+    MarkerUpdatePositionRequest, MessageAttachment, MessageExportBundle, MessageExportRequest,
+    MessagePruneResult, MessageSearchRequest, MessageSearchResult, PaperMessageEnvelope,
+    PresenceListRequest, PresenceListResult, PropagationStats, PropagationSyncStatus,
+    RemoteCommandRequest, RemoteCommandResponse, StampStatus, TelemetryPoint, TelemetryQuery,
+    TopicCreateRequest, TopicId, TopicListRequest, TopicListResult, TopicPublishRequest,
+    TopicRecord, TopicSubscriptionRequest, VoiceSessionId, VoiceSessionOpenRequest,
+    VoiceSessionState, VoiceSessionUpdateRequest, Whoami,
 };
 use crate::error::{code, ErrorCategory, SdkError};
 use crate::event::{EventBatch, EventCursor, SdkEvent, Severity};
@@ -21,16 +24,23 @@ use crate::event::{EventBatch, EventCursor, SdkEvent, Severity};
 use crate::event::{EventSubscription, SubscriptionStart};
 use crate::types::{
     Ack, AuthMode, CancelResult, ConfigPatch, DeliverySnapshot, DeliveryState, MessageId,
-    RuntimeSnapshot, RuntimeState, SendRequest, ShutdownMode, TickBudget, TickResult,
+    MessageIdFormat, RuntimeSnapshot, RuntimeSnapshotDelta, RuntimeState, SendRequest,
+    ShutdownMode, TickBudget, TickResult, VersionInfo,
 };
 use serde::de::DeserializeOwned;
 use serde_json::{Map as JsonMap, Value as JsonValue};
-use std::collections::BTreeMap;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::RwLock;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use zeroize::Zeroizing;
 
+/// Response body limit used until the caller or negotiation supplies
+/// `max_body_bytes`.
+const DEFAULT_MAX_BODY_BYTES: usize = 1_048_576;
+const SHUTDOWN_DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(50);
+const DEDUPLICATED_IDS_CAPACITY: usize = 256;
+
 #[path = "rpc/core_impl.rs"]
 mod core_impl;
 #[path = "rpc/domains_impl.rs"]
@@ -47,6 +57,11 @@ pub struct RpcBackendClient {
     negotiated_limits: RwLock<Option<EffectiveLimits>>,
     manual_tick_cursor: RwLock<Option<EventCursor>>,
     session_auth: RwLock<SessionAuth>,
+    max_body_bytes: AtomicUsize,
+    /// Set by negotiation when `rpc_backend.message_id_format` is `sequential`.
+    sequential_message_ids: AtomicBool,
+    /// Recent sends the daemon answered from an earlier idempotent send.
+    deduplicated_ids: RwLock<VecDeque<MessageId>>,
 }
 
 enum SessionAuth {
@@ -62,6 +77,9 @@ enum SessionAuth {
         client_cert_path: Option<String>,
         client_key_path: Option<String>,
     },
+    ApiKey {
+        api_key: Zeroizing<String>,
+    },
 }
 
 #[derive(Clone)]
@@ -80,9 +98,26 @@ impl RpcBackendClient {
             negotiated_limits: RwLock::new(None),
             manual_tick_cursor: RwLock::new(None),
             session_auth: RwLock::new(SessionAuth::LocalTrusted),
+            max_body_bytes: AtomicUsize::new(DEFAULT_MAX_BODY_BYTES),
+            sequential_message_ids: AtomicBool::new(false),
+            deduplicated_ids: RwLock::new(VecDeque::new()),
         }
     }
 
+    /// Sets the largest response body, after decoding, the client accepts.
+    /// Negotiation and `rpc_backend.max_body_bytes` patches replace it with
+    /// the configured value.
+    pub fn with_max_body_bytes(self, max_body_bytes: usize) -> Self {
+        self.max_body_bytes.store(max_body_bytes, Ordering::Relaxed);
+        self
+    }
+
+    /// Returns the `sdk_send_v2` params that `send` would post for `req`,
+    /// without contacting the daemon.
+    pub fn prepare_send_params(&self, req: SendRequest) -> JsonValue {
+        self.send_params(req)
+    }
+
     fn next_request_id(&self) -> u64 {
         self.next_request_id.fetch_add(1, Ordering::Relaxed)
     }
@@ -118,10 +153,21 @@ impl SdkBackend for RpcBackendClient {
         self.send_impl(req)
     }
 
+    fn send_batch(
+        &self,
+        reqs: Vec<SendRequest>,
+    ) -> Result<Vec<Result<MessageId, SdkError>>, SdkError> {
+        self.send_batch_impl(reqs)
+    }
+
     fn cancel(&self, id: MessageId) -> Result<CancelResult, SdkError> {
         self.cancel_impl(id)
     }
 
+    fn send_deduplicated(&self, id: &MessageId) -> bool {
+        self.deduplicated_ids.read().expect("deduplicated_ids rwlock poisoned").contains(id)
+    }
+
     fn status(&self, id: MessageId) -> Result<Option<DeliverySnapshot>, SdkError> {
         self.status_impl(id)
     }
@@ -131,15 +177,36 @@ impl SdkBackend for RpcBackendClient {
     }
 
     fn poll_events(&self, cursor: Option<EventCursor>, max: usize) -> Result<EventBatch, SdkError> {
-        self.poll_events_impl(cursor, max)
+        self.poll_events_impl(cursor, max, &[])
+    }
+
+    fn poll_events_filtered(
+        &self,
+        cursor: Option<EventCursor>,
+        max: usize,
+        event_types: &[String],
+    ) -> Result<EventBatch, SdkError> {
+        self.poll_events_impl(cursor, max, event_types)
     }
 
     fn snapshot(&self) -> Result<RuntimeSnapshot, SdkError> {
         self.snapshot_impl()
     }
 
+    fn snapshot_since(&self, since_revision: u64) -> Result<RuntimeSnapshotDelta, SdkError> {
+        self.snapshot_since_impl(since_revision)
+    }
+
+    fn version(&self) -> Result<VersionInfo, SdkError> {
+        self.version_impl()
+    }
+
     fn shutdown(&self, mode: ShutdownMode) -> Result<Ack, SdkError> {
-        self.shutdown_impl(mode)
+        self.shutdown_impl(mode, None)
+    }
+
+    fn shutdown_graceful(&self, drain_timeout_ms: u64) -> Result<Ack, SdkError> {
+        self.shutdown_impl(ShutdownMode::Graceful, Some(drain_timeout_ms))
     }
 
     fn topic_create(&self, req: TopicCreateRequest) -> Result<TopicRecord, SdkError> {
@@ -185,6 +252,14 @@ impl SdkBackend for RpcBackendClient {
         self.attachment_get_impl(attachment_id)
     }
 
+    fn message_attachment_get(
+        &self,
+        message_id: MessageId,
+        index: usize,
+    ) -> Result<MessageAttachment, SdkError> {
+        self.message_attachment_get_impl(message_id, index)
+    }
+
     fn attachment_list(
         &self,
         req: AttachmentListRequest,
@@ -263,6 +338,14 @@ impl SdkBackend for RpcBackendClient {
         self.identity_announce_now_impl()
     }
 
+    fn whoami(&self) -> Result<Whoami, SdkError> {
+        self.whoami_impl()
+    }
+
+    fn set_display_name(&self, display_name: &str) -> Result<DisplayNameUpdate, SdkError> {
+        self.set_display_name_impl(display_name)
+    }
+
     fn identity_presence_list(
         &self,
         req: PresenceListRequest,
@@ -342,6 +425,51 @@ impl SdkBackend for RpcBackendClient {
         self.voice_session_close_impl(session_id)
     }
 
+    fn propagation_stats(&self) -> Result<PropagationStats, SdkError> {
+        self.propagation_stats_impl()
+    }
+
+    fn propagation_sync_now(&self) -> Result<PropagationSyncStatus, SdkError> {
+        self.propagation_sync_now_impl()
+    }
+
+    fn stamp_status(&self) -> Result<StampStatus, SdkError> {
+        self.stamp_status_impl()
+    }
+
+    fn logs_tail(&self, after_offset: Option<u64>, max_lines: usize) -> Result<LogsTail, SdkError> {
+        self.logs_tail_impl(after_offset, max_lines, None)
+    }
+
+    fn logs_tail_at_level(
+        &self,
+        after_offset: Option<u64>,
+        max_lines: usize,
+        min_level: LogLevel,
+    ) -> Result<LogsTail, SdkError> {
+        self.logs_tail_impl(after_offset, max_lines, Some(min_level))
+    }
+
+    fn delivery_trace(&self, id: MessageId) -> Result<DeliveryTrace, SdkError> {
+        self.delivery_trace_impl(id)
+    }
+
+    fn prune_messages(&self, older_than_ms: u64) -> Result<MessagePruneResult, SdkError> {
+        self.prune_messages_impl(older_than_ms)
+    }
+
+    fn messages_search(&self, req: MessageSearchRequest) -> Result<MessageSearchResult, SdkError> {
+        self.messages_search_impl(req)
+    }
+
+    fn messages_export(&self, req: MessageExportRequest) -> Result<MessageExportBundle, SdkError> {
+        self.messages_export_impl(req)
+    }
+
+    fn health(&self) -> Result<HealthReport, SdkError> {
+        self.health_impl()
+    }
+
     fn tick(&self, budget: TickBudget) -> Result<TickResult, SdkError> {
         self.tick_impl(budget)
     }
@@ -3,6 +3,8 @@ use serde_json::json;
 
 impl RpcBackendClient {
     const DEFAULT_IDLE_TICK_DELAY_MS: u64 = 25;
+    /// LXMF `FIELD_THREAD` (0x08); numeric JSON keys become integer keys on the wire.
+    const FIELD_THREAD_KEY: &'static str = "8";
 
     fn run_manual_tick_loop<F>(
         start_cursor: Option<EventCursor>,
@@ -54,6 +56,7 @@ impl RpcBackendClient {
                 "write_timeout_ms": config.write_timeout_ms,
                 "max_header_bytes": config.max_header_bytes,
                 "max_body_bytes": config.max_body_bytes,
+                "min_compress_bytes": config.min_compress_bytes,
                 "token_auth": config.token_auth.as_ref().map(|token| json!({
                     "issuer": token.issuer,
                     "audience": token.audience,
@@ -68,6 +71,9 @@ impl RpcBackendClient {
                     "client_cert_path": mtls.client_cert_path,
                     "client_key_path": mtls.client_key_path,
                 })),
+                "api_key_auth": config.api_key_auth.as_ref().map(|auth| json!({
+                    "api_key": auth.api_key,
+                })),
             })
         });
         let result = self.call_rpc_with_headers(
@@ -123,6 +129,11 @@ impl RpcBackendClient {
             let mut guard = self.session_auth.write().expect("session_auth rwlock poisoned");
             *guard = session_auth;
         }
+        if let Some(config) = req.rpc_backend.as_ref() {
+            self.max_body_bytes.store(config.max_body_bytes, Ordering::Relaxed);
+            self.sequential_message_ids
+                .store(config.message_id_format == MessageIdFormat::Sequential, Ordering::Relaxed);
+        }
         {
             let mut guard =
                 self.manual_tick_cursor.write().expect("manual_tick_cursor rwlock poisoned");
@@ -139,7 +150,7 @@ impl RpcBackendClient {
         })
     }
 
-    pub(super) fn send_impl(&self, req: SendRequest) -> Result<MessageId, SdkError> {
+    pub(super) fn send_params(&self, req: SendRequest) -> JsonValue {
         let SendRequest {
             source,
             destination,
@@ -147,9 +158,16 @@ impl RpcBackendClient {
             idempotency_key,
             ttl_ms,
             correlation_id,
+            scheduled_ts_ms,
+            fail_fast_no_path,
+            reply_to,
             extensions,
         } = req;
-        let rpc_message_id = format!("sdk-{}", self.next_request_id());
+        let rpc_message_id = if self.sequential_message_ids.load(Ordering::Relaxed) {
+            format!("sdk-{}", self.next_request_id())
+        } else {
+            rns_rpc::generate_message_id()
+        };
         let content = payload
             .get("content")
             .and_then(JsonValue::as_str)
@@ -163,6 +181,9 @@ impl RpcBackendClient {
             other => json!({ "payload": other }),
         };
         if let JsonValue::Object(map) = &mut fields {
+            if let Some(reply_to) = reply_to.filter(|id| !id.trim().is_empty()) {
+                map.insert(Self::FIELD_THREAD_KEY.to_string(), JsonValue::String(reply_to));
+            }
             let mut sdk_meta = JsonMap::new();
             if let Some(idempotency_key) = idempotency_key {
                 sdk_meta.insert("idempotency_key".to_string(), JsonValue::String(idempotency_key));
@@ -182,17 +203,83 @@ impl RpcBackendClient {
             }
         }
 
-        let params = Some(json!({
+        let mut params = json!({
             "id": rpc_message_id,
             "source": source,
             "destination": destination,
             "title": title,
             "content": content,
             "fields": fields,
-        }));
-        let result = self.call_rpc("sdk_send_v2", params)?;
-        let message_id = Self::parse_required_string(&result, "message_id")?;
-        Ok(MessageId(message_id))
+        });
+        if let Some(scheduled_ts_ms) = scheduled_ts_ms {
+            params["scheduled_ts_ms"] = JsonValue::from(scheduled_ts_ms);
+        }
+        if fail_fast_no_path {
+            params["fail_fast_no_path"] = JsonValue::Bool(true);
+        }
+        params
+    }
+
+    pub(super) fn send_impl(&self, req: SendRequest) -> Result<MessageId, SdkError> {
+        let params = self.send_params(req);
+        let result = self.call_rpc("sdk_send_v2", Some(params))?;
+        let message_id = MessageId(Self::parse_required_string(&result, "message_id")?);
+        self.note_deduplicated(&result, &message_id);
+        Ok(message_id)
+    }
+
+    /// Remembers a send the daemon answered from an earlier send under the
+    /// same idempotency key, so `send_deduplicated` can report it.
+    fn note_deduplicated(&self, result: &JsonValue, message_id: &MessageId) {
+        if !result.get("deduplicated").and_then(JsonValue::as_bool).unwrap_or(false) {
+            return;
+        }
+        let mut ids = self.deduplicated_ids.write().expect("deduplicated_ids rwlock poisoned");
+        if !ids.contains(message_id) {
+            if ids.len() == DEDUPLICATED_IDS_CAPACITY {
+                ids.pop_front();
+            }
+            ids.push_back(message_id.clone());
+        }
+    }
+
+    pub(super) fn send_batch_impl(
+        &self,
+        reqs: Vec<SendRequest>,
+    ) -> Result<Vec<Result<MessageId, SdkError>>, SdkError> {
+        let expected = reqs.len();
+        let messages = reqs.into_iter().map(|req| self.send_params(req)).collect::<Vec<_>>();
+        let result = self.call_rpc("sdk_send_batch_v2", Some(json!({ "messages": messages })))?;
+        let rows = result.get("results").and_then(JsonValue::as_array).ok_or_else(|| {
+            SdkError::new(
+                code::INTERNAL,
+                ErrorCategory::Internal,
+                "rpc response missing array field 'results'",
+            )
+        })?;
+        if rows.len() != expected {
+            return Err(SdkError::new(
+                code::INTERNAL,
+                ErrorCategory::Internal,
+                format!("send batch returned {} results for {expected} messages", rows.len()),
+            ));
+        }
+        rows.iter()
+            .map(|row| {
+                if let Some(error) = row.get("error") {
+                    let error: rns_rpc::RpcError =
+                        serde_json::from_value(error.clone()).map_err(|err| {
+                            SdkError::new(code::INTERNAL, ErrorCategory::Internal, err.to_string())
+                        })?;
+                    return Ok(Err(Self::map_rpc_error(error)));
+                }
+                Ok(Self::parse_required_string(row, "message_id").map(|message_id| {
+                    let message_id = MessageId(message_id);
+                    self.note_deduplicated(row, &message_id);
+                    message_id
+                }))
+            })
+            .collect()
     }
 
     pub(super) fn cancel_impl(&self, id: MessageId) -> Result<CancelResult, SdkError> {
@@ -246,11 +333,13 @@ impl RpcBackendClient {
             | DeliveryState::Cancelled
             | DeliveryState::Expired
             | DeliveryState::Rejected => true,
-            DeliveryState::Queued
+            DeliveryState::Scheduled
+            | DeliveryState::Queued
             | DeliveryState::Dispatching
             | DeliveryState::InFlight
             | DeliveryState::Unknown => false,
         };
+        let deduplicated = result.get("deduplicated").and_then(JsonValue::as_bool).unwrap_or(false);
         let timestamp = record.get("timestamp").and_then(JsonValue::as_i64).unwrap_or(0_i64);
         let last_updated_ms = u64::try_from(timestamp.max(0)).unwrap_or(0).saturating_mul(1000);
 
@@ -261,6 +350,7 @@ impl RpcBackendClient {
             last_updated_ms,
             attempts: 0,
             reason_code: None,
+            deduplicated,
         }))
     }
 
@@ -269,6 +359,13 @@ impl RpcBackendClient {
         expected_revision: u64,
         patch: ConfigPatch,
     ) -> Result<Ack, SdkError> {
+        let max_body_bytes = match &patch.rpc_backend {
+            Some(Some(backend)) => {
+                backend.max_body_bytes.map(|value| value.unwrap_or(DEFAULT_MAX_BODY_BYTES))
+            }
+            Some(None) => Some(DEFAULT_MAX_BODY_BYTES),
+            None => None,
+        };
         let patch = serde_json::to_value(patch).map_err(|err| {
             SdkError::new(code::INTERNAL, ErrorCategory::Internal, err.to_string())
         })?;
@@ -279,9 +376,14 @@ impl RpcBackendClient {
                 "patch": patch,
             })),
         )?;
+        let accepted = result.get("accepted").and_then(JsonValue::as_bool).unwrap_or(false);
+        if let (true, Some(max_body_bytes)) = (accepted, max_body_bytes) {
+            self.max_body_bytes.store(max_body_bytes, Ordering::Relaxed);
+        }
         Ok(Ack {
-            accepted: result.get("accepted").and_then(JsonValue::as_bool).unwrap_or(false),
+            accepted,
             revision: result.get("revision").and_then(JsonValue::as_u64),
+            pending_messages: None,
         })
     }
 
@@ -289,14 +391,16 @@ impl RpcBackendClient {
         &self,
         cursor: Option<EventCursor>,
         max: usize,
+        event_types: &[String],
     ) -> Result<EventBatch, SdkError> {
-        let result = self.call_rpc(
-            "sdk_poll_events_v2",
-            Some(json!({
-                "cursor": cursor.map(|cursor| cursor.0),
-                "max": max,
-            })),
-        )?;
+        let mut params = json!({
+            "cursor": cursor.map(|cursor| cursor.0),
+            "max": max,
+        });
+        if !event_types.is_empty() {
+            params["event_types"] = json!(event_types);
+        }
+        let result = self.call_rpc("sdk_poll_events_v2", Some(params))?;
 
         let mut events = Vec::new();
         if let Some(rows) = result.get("events").and_then(JsonValue::as_array) {
@@ -355,13 +459,21 @@ impl RpcBackendClient {
         let dropped_count = result.get("dropped_count").and_then(JsonValue::as_u64).unwrap_or(0);
         let snapshot_high_watermark_seq_no =
             result.get("snapshot_high_watermark_seq_no").and_then(JsonValue::as_u64);
+        let mut extensions = BTreeMap::new();
+        if let Some(batch_ids) = result
+            .get("batch_ids")
+            .and_then(JsonValue::as_array)
+            .filter(|batch_ids| !batch_ids.is_empty())
+        {
+            extensions.insert("batch_ids".to_string(), JsonValue::Array(batch_ids.clone()));
+        }
 
         Ok(EventBatch {
             events,
             next_cursor,
             dropped_count,
             snapshot_high_watermark_seq_no,
-            extensions: BTreeMap::new(),
+            extensions,
         })
     }
 
@@ -382,23 +494,88 @@ impl RpcBackendClient {
                 .get("in_flight_messages")
                 .and_then(JsonValue::as_u64)
                 .unwrap_or(0),
+            announce_interval_secs: result
+                .get("announce_interval_secs")
+                .and_then(JsonValue::as_u64),
+            outbound_queue_depth: result
+                .get("outbound_queue_depth")
+                .and_then(JsonValue::as_u64)
+                .unwrap_or(0),
+            inbound_pending: result.get("inbound_pending").and_then(JsonValue::as_u64).unwrap_or(0),
+            event_buffer_used: result
+                .get("event_buffer_used")
+                .and_then(JsonValue::as_u64)
+                .unwrap_or(0),
+            event_buffer_capacity: result
+                .get("event_buffer_capacity")
+                .and_then(JsonValue::as_u64)
+                .unwrap_or(0),
+            messages_stored: result.get("messages_stored").and_then(JsonValue::as_u64).unwrap_or(0),
+            propagation_node: result
+                .get("propagation_node")
+                .and_then(JsonValue::as_bool)
+                .unwrap_or(false),
         })
     }
 
-    pub(super) fn shutdown_impl(&self, mode: ShutdownMode) -> Result<Ack, SdkError> {
+    pub(super) fn version_impl(&self) -> Result<VersionInfo, SdkError> {
+        let result = self.call_rpc("version", None)?;
+        Self::decode_value(result, "version response")
+    }
+
+    pub(super) fn snapshot_since_impl(
+        &self,
+        since_revision: u64,
+    ) -> Result<RuntimeSnapshotDelta, SdkError> {
+        let result = self.call_rpc(
+            "sdk_snapshot_v2",
+            Some(json!({ "include_counts": true, "since_revision": since_revision })),
+        )?;
+        let changed = result
+            .get("changed")
+            .and_then(JsonValue::as_object)
+            .map(|fields| fields.clone().into_iter().collect())
+            .unwrap_or_default();
+        Ok(RuntimeSnapshotDelta {
+            snapshot_revision: Self::parse_required_u64(&result, "snapshot_revision")?,
+            unchanged: result.get("unchanged").and_then(JsonValue::as_bool).unwrap_or(false),
+            changed,
+        })
+    }
+
+    pub(super) fn shutdown_impl(
+        &self,
+        mode: ShutdownMode,
+        drain_timeout_ms: Option<u64>,
+    ) -> Result<Ack, SdkError> {
         let mode = match mode {
             ShutdownMode::Graceful => "graceful",
             ShutdownMode::Immediate => "immediate",
         };
-        let result = self.call_rpc(
-            "sdk_shutdown_v2",
-            Some(json!({
-                "mode": mode,
-            })),
-        )?;
+        let mut params = json!({ "mode": mode });
+        if let Some(drain_timeout_ms) = drain_timeout_ms {
+            params["drain_timeout_ms"] = json!(drain_timeout_ms);
+        }
+        let mut result = self.call_rpc("sdk_shutdown_v2", Some(params.clone()))?;
+        // The daemon answers at once, so a graceful drain is polled from here
+        // rather than parking the runtime that has to deliver the pending sends.
+        if mode == "graceful" {
+            let drain_timeout = Duration::from_millis(
+                result.get("drain_timeout_ms").and_then(JsonValue::as_u64).unwrap_or(0),
+            );
+            let started = Instant::now();
+            while result.get("pending_messages").and_then(JsonValue::as_u64).unwrap_or(0) > 0 {
+                let Some(remaining) = drain_timeout.checked_sub(started.elapsed()) else {
+                    break;
+                };
+                std::thread::sleep(SHUTDOWN_DRAIN_POLL_INTERVAL.min(remaining));
+                result = self.call_rpc("sdk_shutdown_v2", Some(params.clone()))?;
+            }
+        }
         let ack = Ack {
             accepted: result.get("accepted").and_then(JsonValue::as_bool).unwrap_or(false),
             revision: None,
+            pending_messages: result.get("pending_messages").and_then(JsonValue::as_u64),
         };
         if ack.accepted {
             let mut guard =
@@ -427,7 +604,7 @@ impl RpcBackendClient {
             start_cursor,
             budget.max_work_items,
             self.negotiated_max_poll_events(),
-            |cursor, max| self.poll_events_impl(cursor, max),
+            |cursor, max| self.poll_events_impl(cursor, max, &[]),
         )?;
         {
             let mut guard =
@@ -455,7 +632,7 @@ impl RpcBackendClient {
 
         // Prevent unbounded loops if the backend keeps returning the same cursor.
         for _ in 0..1024 {
-            let batch = self.poll_events_impl(cursor.clone(), poll_max)?;
+            let batch = self.poll_events_impl(cursor.clone(), poll_max, &[])?;
             let next_cursor = batch.next_cursor.clone();
             let reached_target =
                 batch.events.last().map(|event| event.seq_no >= target_seq_no).unwrap_or(true);
@@ -481,8 +658,9 @@ impl RpcBackendClient {
             return Err(SdkError::capability_disabled("sdk.capability.async_events"));
         }
 
-        let cursor = match start {
+        let cursor = match &start {
             SubscriptionStart::Head | SubscriptionStart::Snapshot => None,
+            SubscriptionStart::Cursor(cursor) => Some(cursor.clone()),
             SubscriptionStart::Tail => {
                 let snapshot = self.snapshot_impl()?;
                 self.fast_forward_tail_cursor(snapshot.event_stream_position)?
@@ -538,6 +716,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn send_params_carries_reply_to_in_thread_field() {
+        let client = RpcBackendClient::new("127.0.0.1:1");
+        let req = crate::types::SendRequest::new("src", "dst", json!({ "content": "re" }))
+            .with_reply_to("parent-id");
+        let params = client.send_params(req);
+        assert_eq!(params["fields"]["8"], "parent-id");
+
+        let plain = client.send_params(crate::types::SendRequest::new("src", "dst", json!({})));
+        assert!(plain["fields"].get("8").is_none());
+    }
+
+    #[test]
+    fn send_params_use_ulids_unless_sequential_ids_are_configured() {
+        let client = RpcBackendClient::new("127.0.0.1:1");
+        let first = client.send_params(crate::types::SendRequest::new("src", "dst", json!({})));
+        let second = client.send_params(crate::types::SendRequest::new("src", "dst", json!({})));
+        let first_id = first["id"].as_str().expect("id");
+        assert_eq!(first_id.len(), 26);
+        assert!(first_id < second["id"].as_str().expect("id"));
+
+        client.sequential_message_ids.store(true, std::sync::atomic::Ordering::Relaxed);
+        let sequential =
+            client.send_params(crate::types::SendRequest::new("src", "dst", json!({})));
+        assert!(sequential["id"].as_str().expect("id").starts_with("sdk-"));
+    }
+
+    #[test]
+    fn max_body_bytes_can_be_raised_before_negotiation() {
+        let client = RpcBackendClient::new("127.0.0.1:1").with_max_body_bytes(8 * 1_048_576);
+        assert_eq!(client.max_body_bytes.load(std::sync::atomic::Ordering::Relaxed), 8 * 1_048_576);
+    }
+
     #[test]
     fn parse_cancel_result_accepts_contract_variants() {
         assert!(matches!(
@@ -558,6 +769,22 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn rpc_error_details_map_into_sdk_error_details() {
+        let mut details = serde_json::Map::new();
+        details.insert("field".to_owned(), json!("destination"));
+        details.insert("reason".to_owned(), json!("missing"));
+        let rpc_error = rns_rpc::RpcError::new(
+            "SDK_VALIDATION_INVALID_ARGUMENT",
+            "missing field `destination`",
+        )
+        .with_details(details);
+        let err = RpcBackendClient::map_rpc_error(rpc_error);
+        assert_eq!(err.category, ErrorCategory::Validation);
+        assert_eq!(err.details.get("field"), Some(&json!("destination")));
+        assert_eq!(err.details.get("reason"), Some(&json!("missing")));
+    }
+
     #[test]
     fn parse_cancel_result_rejects_unknown_variant() {
         let err = RpcBackendClient::parse_cancel_result("LegacyUnsupported")
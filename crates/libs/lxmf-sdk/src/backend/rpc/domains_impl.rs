@@ -279,6 +279,20 @@ impl RpcBackendClient {
         Ok(Self::parse_ack(&result))
     }
 
+    pub(super) fn whoami_impl(&self) -> Result<Whoami, SdkError> {
+        let result = self.call_rpc("whoami", Some(json!({})))?;
+        Self::decode_value(result, "whoami response")
+    }
+
+    pub(super) fn set_display_name_impl(
+        &self,
+        display_name: &str,
+    ) -> Result<DisplayNameUpdate, SdkError> {
+        let result =
+            self.call_rpc("set_display_name", Some(json!({ "display_name": display_name })))?;
+        Self::decode_value(result, "set_display_name response")
+    }
+
     pub(super) fn identity_presence_list_impl(
         &self,
         req: PresenceListRequest,
@@ -470,4 +484,96 @@ impl RpcBackendClient {
         )?;
         Ok(Self::parse_ack(&result))
     }
+
+    pub(super) fn propagation_stats_impl(&self) -> Result<PropagationStats, SdkError> {
+        let result = self.call_rpc("propagation_stats", Some(json!({})))?;
+        Self::decode_field_or_root(&result, "stats", "propagation_stats response")
+    }
+
+    pub(super) fn propagation_sync_now_impl(&self) -> Result<PropagationSyncStatus, SdkError> {
+        let result = self.call_rpc("sync_now", Some(json!({})))?;
+        let mut status: PropagationSyncStatus =
+            Self::decode_field_or_root(&result, "sync", "sync_now response")?;
+        status.already_running =
+            result.get("already_running").and_then(JsonValue::as_bool).unwrap_or(false);
+        Ok(status)
+    }
+
+    pub(super) fn stamp_status_impl(&self) -> Result<StampStatus, SdkError> {
+        let result = self.call_rpc("stamp_status", Some(json!({})))?;
+        Self::decode_field_or_root(&result, "stamp_status", "stamp_status response")
+    }
+
+    pub(super) fn health_impl(&self) -> Result<HealthReport, SdkError> {
+        let result = self.call_rpc("health", Some(json!({})))?;
+        Self::decode_value(result, "health response")
+    }
+
+    pub(super) fn logs_tail_impl(
+        &self,
+        after_offset: Option<u64>,
+        max_lines: usize,
+        min_level: Option<LogLevel>,
+    ) -> Result<LogsTail, SdkError> {
+        let mut params = json!({
+            "after_offset": after_offset,
+            "max_lines": max_lines,
+        });
+        if let Some(min_level) = min_level {
+            params["log_level"] = json!(min_level);
+        }
+        let result = self.call_rpc("logs_tail", Some(params))?;
+        Self::decode_value(result, "logs_tail response")
+    }
+
+    pub(super) fn delivery_trace_impl(&self, id: MessageId) -> Result<DeliveryTrace, SdkError> {
+        let result = self.call_rpc("delivery_trace", Some(json!({ "message_id": id.0 })))?;
+        Self::decode_value(result, "delivery_trace response")
+    }
+
+    pub(super) fn prune_messages_impl(
+        &self,
+        older_than_ms: u64,
+    ) -> Result<MessagePruneResult, SdkError> {
+        let result =
+            self.call_rpc("prune_messages", Some(json!({ "older_than_ms": older_than_ms })))?;
+        Self::decode_value(result, "prune_messages response")
+    }
+
+    pub(super) fn messages_search_impl(
+        &self,
+        req: MessageSearchRequest,
+    ) -> Result<MessageSearchResult, SdkError> {
+        let params = serde_json::to_value(req).map_err(|err| {
+            SdkError::new(code::INTERNAL, ErrorCategory::Internal, err.to_string())
+        })?;
+        let result = self.call_rpc("messages_search", Some(params))?;
+        Self::decode_value(result, "messages_search response")
+    }
+
+    pub(super) fn messages_export_impl(
+        &self,
+        req: MessageExportRequest,
+    ) -> Result<MessageExportBundle, SdkError> {
+        let params = serde_json::to_value(req).map_err(|err| {
+            SdkError::new(code::INTERNAL, ErrorCategory::Internal, err.to_string())
+        })?;
+        let result = self.call_rpc("messages_export", Some(params))?;
+        Self::decode_field_or_root(&result, "bundle", "messages_export response")
+    }
+
+    pub(super) fn message_attachment_get_impl(
+        &self,
+        message_id: MessageId,
+        index: usize,
+    ) -> Result<MessageAttachment, SdkError> {
+        let result = self.call_rpc(
+            "attachment_get",
+            Some(json!({
+                "message_id": message_id.0,
+                "index": index,
+            })),
+        )?;
+        Self::decode_field_or_root(&result, "attachment", "attachment_get response")
+    }
 }
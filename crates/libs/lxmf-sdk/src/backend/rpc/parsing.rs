@@ -164,7 +164,11 @@ impl RpcBackendClient {
                 })
             })
             .unwrap_or(true);
-        Ack { accepted, revision: result.get("revision").and_then(JsonValue::as_u64) }
+        Ack {
+            accepted,
+            revision: result.get("revision").and_then(JsonValue::as_u64),
+            pending_messages: None,
+        }
     }
 
     pub(super) fn parse_delivery_state(receipt_status: Option<&str>) -> DeliveryState {
@@ -178,6 +182,9 @@ impl RpcBackendClient {
         if starts_with_ignore_ascii_case(normalized, "failed") {
             return DeliveryState::Failed;
         }
+        if normalized.eq_ignore_ascii_case("scheduled") {
+            return DeliveryState::Scheduled;
+        }
         if normalized.eq_ignore_ascii_case("queued") {
             return DeliveryState::Queued;
         }
@@ -248,6 +255,10 @@ mod tests {
 
     #[test]
     fn parse_delivery_state_transient_states_map_to_enum_variants() {
+        assert_eq!(
+            RpcBackendClient::parse_delivery_state(Some("scheduled")),
+            crate::types::DeliveryState::Scheduled
+        );
         assert_eq!(
             RpcBackendClient::parse_delivery_state(Some("queued")),
             crate::types::DeliveryState::Queued
@@ -1,6 +1,6 @@
 use super::*;
 use hmac::{Hmac, Mac};
-use rns_rpc::e2e_harness::{build_rpc_frame, parse_http_response_body, parse_rpc_frame};
+use rns_rpc::e2e_harness::{build_rpc_frame, parse_http_response_body_with_limit, parse_rpc_frame};
 use rns_rpc::RpcError;
 use rustls::pki_types::ServerName;
 use rustls::{ClientConfig, RootCertStore};
@@ -53,9 +53,11 @@ impl RpcBackendClient {
         request.zeroize();
         Self::zeroize_header_values(headers.as_mut_slice());
         let mut response = response_result?;
-        let body = parse_http_response_body(response.as_mut_slice()).map_err(|err| {
-            SdkError::new(code::INTERNAL, ErrorCategory::Transport, err.to_string())
-        })?;
+        let max_body_bytes = self.max_body_bytes.load(Ordering::Relaxed);
+        let body = parse_http_response_body_with_limit(response.as_mut_slice(), max_body_bytes)
+            .map_err(|err| {
+                SdkError::new(code::INTERNAL, ErrorCategory::Transport, err.to_string())
+            })?;
         let rpc_response = parse_rpc_frame(&body).map_err(|err| {
             SdkError::new(code::INTERNAL, ErrorCategory::Transport, err.to_string())
         })?;
@@ -278,6 +280,7 @@ impl RpcBackendClient {
         request.extend_from_slice(format!("POST {path} HTTP/1.1\r\n").as_bytes());
         request.extend_from_slice(format!("Host: {host}\r\n").as_bytes());
         request.extend_from_slice(b"Content-Type: application/msgpack\r\n");
+        request.extend_from_slice(b"Accept-Encoding: gzip\r\n");
         for (name, value) in headers {
             request.extend_from_slice(format!("{name}: {value}\r\n").as_bytes());
         }
@@ -394,6 +397,7 @@ impl RpcBackendClient {
             crate::types::AuthMode::LocalTrusted => "local_trusted",
             crate::types::AuthMode::Token => "token",
             crate::types::AuthMode::Mtls => "mtls",
+            crate::types::AuthMode::ApiKey => "api_key",
         }
     }
 
@@ -403,6 +407,7 @@ impl RpcBackendClient {
         match overflow_policy {
             crate::types::OverflowPolicy::Reject => "reject",
             crate::types::OverflowPolicy::DropOldest => "drop_oldest",
+            crate::types::OverflowPolicy::DropNewest => "drop_newest",
             crate::types::OverflowPolicy::Block => "block",
         }
     }
@@ -492,6 +497,22 @@ impl RpcBackendClient {
                     ttl_secs: (token_auth.jti_cache_ttl_ms / 1000).max(1),
                 })
             }
+            AuthMode::ApiKey => {
+                let api_key = req
+                    .rpc_backend
+                    .as_ref()
+                    .and_then(|config| config.api_key_auth.as_ref())
+                    .map(|auth| auth.api_key.trim())
+                    .filter(|api_key| !api_key.is_empty())
+                    .ok_or_else(|| {
+                        SdkError::new(
+                            code::SECURITY_AUTH_REQUIRED,
+                            ErrorCategory::Security,
+                            "api_key auth mode requires rpc_backend.api_key_auth.api_key",
+                        )
+                    })?;
+                Ok(SessionAuth::ApiKey { api_key: Zeroizing::new(api_key.to_owned()) })
+            }
         }
     }
 
@@ -518,6 +539,9 @@ impl RpcBackendClient {
                 let token = Zeroizing::new(format!("{};sig={}", payload.as_str(), sig.as_str()));
                 vec![("Authorization".to_owned(), format!("Bearer {}", token.as_str()))]
             }
+            SessionAuth::ApiKey { api_key } => {
+                vec![("X-Api-Key".to_owned(), api_key.as_str().to_owned())]
+            }
         }
     }
 
@@ -530,7 +554,9 @@ impl RpcBackendClient {
                     client_key_path: client_key_path.clone(),
                 })
             }
-            SessionAuth::LocalTrusted | SessionAuth::Token { .. } => None,
+            SessionAuth::LocalTrusted | SessionAuth::Token { .. } | SessionAuth::ApiKey { .. } => {
+                None
+            }
         }
     }
 
@@ -557,6 +583,15 @@ mod tests {
         assert!(headers.iter().all(|(_, value)| value.is_empty()));
     }
 
+    #[test]
+    fn http_post_advertises_gzip_response_encoding() {
+        let request =
+            RpcBackendClient::build_http_post_with_headers("/rpc", "127.0.0.1:4242", b"{}", &[]);
+        let text = String::from_utf8(request).expect("utf8 request");
+        assert!(text.contains("\r\nAccept-Encoding: gzip\r\n"));
+        assert!(text.ends_with("Content-Length: 2\r\n\r\n{}"));
+    }
+
     #[test]
     fn mtls_for_session_auth_returns_mtls_paths_only() {
         let mtls_auth = SessionAuth::Mtls {
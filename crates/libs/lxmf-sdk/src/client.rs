@@ -1,9 +1,9 @@
 #[cfg(feature = "sdk-async")]
 use crate::api::LxmfSdkAsync;
 use crate::api::{
-    LxmfSdk, LxmfSdkAttachments, LxmfSdkGroupDelivery, LxmfSdkIdentity, LxmfSdkManualTick,
-    LxmfSdkMarkers, LxmfSdkPaper, LxmfSdkRemoteCommands, LxmfSdkTelemetry, LxmfSdkTopics,
-    LxmfSdkVoiceSignaling,
+    LxmfSdk, LxmfSdkAttachments, LxmfSdkGroupDelivery, LxmfSdkIdentity, LxmfSdkLogs,
+    LxmfSdkManualTick, LxmfSdkMarkers, LxmfSdkPaper, LxmfSdkPropagation, LxmfSdkRemoteCommands,
+    LxmfSdkTelemetry, LxmfSdkTopics, LxmfSdkVoiceSignaling,
 };
 use crate::backend::SdkBackend;
 #[cfg(feature = "sdk-async")]
@@ -18,7 +18,8 @@ use crate::profiles::{required_capabilities, supports_capability};
 use crate::types::{
     Ack, CancelResult, ClientHandle, ConfigPatch, DeliverySnapshot, GroupRecipientState,
     GroupSendOutcome, GroupSendRequest, GroupSendResult, MessageId, Profile, RuntimeSnapshot,
-    RuntimeState, SendRequest, ShutdownMode, StartRequest, TickBudget, TickResult,
+    RuntimeSnapshotDelta, RuntimeState, SendRequest, ShutdownMode, StartRequest, TickBudget,
+    TickResult, VersionInfo,
 };
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
@@ -29,6 +30,7 @@ struct IdempotencyRecord {
     payload_hash: u64,
     message_id: MessageId,
     seen_at: Instant,
+    replayed: bool,
 }
 
 pub struct Client<B: SdkBackend> {
@@ -55,6 +57,43 @@ impl<B: SdkBackend> Client<B> {
         &self.backend
     }
 
+    /// Returns true when a send returning `id` was answered from an earlier
+    /// send under the same idempotency key, by this client's cache within
+    /// the current TTL window or by the runtime.
+    pub fn is_deduplicated(&self, id: &MessageId) -> bool {
+        self.idempotency_cache
+            .lock()
+            .expect("idempotency_cache mutex poisoned")
+            .values()
+            .any(|record| record.replayed && record.message_id == *id)
+            || self.backend.send_deduplicated(id)
+    }
+
+    /// Runs a backend shutdown request unless the runtime is already stopped,
+    /// then moves the lifecycle through draining to stopped.
+    fn shutdown_with(
+        &self,
+        request: impl FnOnce(&B) -> Result<Ack, SdkError>,
+    ) -> Result<Ack, SdkError> {
+        let current_state = {
+            let lifecycle = self.lifecycle.lock().expect("lifecycle mutex poisoned");
+            lifecycle.ensure_method_legal(SdkMethod::Shutdown)?;
+            lifecycle.state()
+        };
+        if current_state == RuntimeState::Stopped {
+            return Ok(Ack { accepted: true, revision: None, pending_messages: None });
+        }
+        let ack = request(&self.backend)?;
+        {
+            let mut lifecycle = self.lifecycle.lock().expect("lifecycle mutex poisoned");
+            if lifecycle.state() != RuntimeState::Stopped {
+                let _ = lifecycle.mark_draining();
+                lifecycle.mark_stopped();
+            }
+        }
+        Ok(ack)
+    }
+
     fn ensure_capabilities(
         profile: Profile,
         requested_capabilities: &[String],
@@ -113,6 +152,15 @@ impl<B: SdkBackend> Client<B> {
         Ok(hasher.finish())
     }
 
+    fn idempotency_conflict() -> SdkError {
+        SdkError::new(
+            code::VALIDATION_IDEMPOTENCY_CONFLICT,
+            ErrorCategory::Validation,
+            "idempotency key already used for different payload",
+        )
+        .with_user_actionable(true)
+    }
+
     fn current_limits(&self) -> Option<crate::capability::EffectiveLimits> {
         self.handle
             .lock()
@@ -223,26 +271,135 @@ impl<B: SdkBackend> LxmfSdk for Client<B> {
         cache.retain(|_, record| {
             now.duration_since(record.seen_at).as_millis() <= u128::from(ttl_ms)
         });
-        if let Some(existing) = cache.get(&cache_key) {
+        if let Some(existing) = cache.get_mut(&cache_key) {
             if existing.payload_hash == payload_hash {
+                existing.replayed = true;
                 return Ok(existing.message_id.clone());
             }
-            return Err(SdkError::new(
-                code::VALIDATION_IDEMPOTENCY_CONFLICT,
-                ErrorCategory::Validation,
-                "idempotency key already used for different payload",
-            )
-            .with_user_actionable(true));
+            return Err(Self::idempotency_conflict());
         }
 
         let message_id = self.backend.send(req)?;
         cache.insert(
             cache_key,
-            IdempotencyRecord { payload_hash, message_id: message_id.clone(), seen_at: now },
+            IdempotencyRecord {
+                payload_hash,
+                message_id: message_id.clone(),
+                seen_at: now,
+                replayed: false,
+            },
         );
         Ok(message_id)
     }
 
+    fn send_batch(
+        &self,
+        reqs: Vec<SendRequest>,
+    ) -> Result<Vec<Result<MessageId, SdkError>>, SdkError> {
+        {
+            let lifecycle = self.lifecycle.lock().expect("lifecycle mutex poisoned");
+            lifecycle.ensure_method_legal(SdkMethod::Send)?;
+        }
+        let negotiated = self
+            .handle
+            .lock()
+            .expect("client handle mutex poisoned")
+            .as_ref()
+            .map(|handle| handle.effective_capabilities.clone())
+            .unwrap_or_default();
+        if !negotiated.iter().any(|capability| capability == "sdk.capability.send_batch") {
+            return Err(SdkError::capability_disabled("sdk.capability.send_batch"));
+        }
+        if reqs.is_empty() {
+            return Err(SdkError::new(
+                code::VALIDATION_INVALID_ARGUMENT,
+                ErrorCategory::Validation,
+                "send batch requires at least one message",
+            )
+            .with_user_actionable(true));
+        }
+
+        let ttl_ms =
+            self.current_limits().map(|limits| limits.idempotency_ttl_ms).unwrap_or(86_400_000);
+        let now = Instant::now();
+        let mut cache = self.idempotency_cache.lock().expect("idempotency_cache mutex poisoned");
+        cache.retain(|_, record| {
+            now.duration_since(record.seen_at).as_millis() <= u128::from(ttl_ms)
+        });
+
+        // Items answered from the idempotency cache never reach the backend; the
+        // rest are submitted together and keep their position in the result list.
+        let mut results: Vec<Option<Result<MessageId, SdkError>>> = vec![None; reqs.len()];
+        let mut submitted = Vec::new();
+        let mut submitted_keys = Vec::new();
+        let mut duplicates = Vec::new();
+        for (index, req) in reqs.into_iter().enumerate() {
+            let Some(idempotency_key) = req.idempotency_key.clone() else {
+                submitted_keys.push((index, None));
+                submitted.push(req);
+                continue;
+            };
+            let cache_key = (req.source.clone(), req.destination.clone(), idempotency_key);
+            let payload_hash = Self::payload_hash(&req.payload)?;
+            if let Some(existing) = cache.get_mut(&cache_key) {
+                results[index] = Some(if existing.payload_hash == payload_hash {
+                    existing.replayed = true;
+                    Ok(existing.message_id.clone())
+                } else {
+                    Err(Self::idempotency_conflict())
+                });
+                continue;
+            }
+            let earlier = submitted_keys
+                .iter()
+                .position(|(_, key)| matches!(key, Some((key, _)) if *key == cache_key));
+            if let Some(position) = earlier {
+                let same_payload =
+                    matches!(&submitted_keys[position].1, Some((_, hash)) if *hash == payload_hash);
+                if same_payload {
+                    duplicates.push((index, position));
+                } else {
+                    results[index] = Some(Err(Self::idempotency_conflict()));
+                }
+                continue;
+            }
+            submitted_keys.push((index, Some((cache_key, payload_hash))));
+            submitted.push(req);
+        }
+
+        let outcomes =
+            if submitted.is_empty() { Vec::new() } else { self.backend.send_batch(submitted)? };
+        if outcomes.len() != submitted_keys.len() {
+            return Err(SdkError::new(
+                code::INTERNAL,
+                ErrorCategory::Internal,
+                "backend returned a result count that does not match the batch",
+            ));
+        }
+        let mut replayed_positions = Vec::with_capacity(duplicates.len());
+        for (index, position) in duplicates {
+            results[index] = Some(outcomes[position].clone());
+            replayed_positions.push(position);
+        }
+        for (position, ((index, key), outcome)) in
+            submitted_keys.into_iter().zip(outcomes).enumerate()
+        {
+            if let (Some((cache_key, payload_hash)), Ok(message_id)) = (key, &outcome) {
+                cache.insert(
+                    cache_key,
+                    IdempotencyRecord {
+                        payload_hash,
+                        message_id: message_id.clone(),
+                        seen_at: now,
+                        replayed: replayed_positions.contains(&position),
+                    },
+                );
+            }
+            results[index] = Some(outcome);
+        }
+        Ok(results.into_iter().map(|result| result.expect("every batch item resolved")).collect())
+    }
+
     fn cancel(&self, id: MessageId) -> Result<CancelResult, SdkError> {
         {
             let lifecycle = self.lifecycle.lock().expect("lifecycle mutex poisoned");
@@ -256,7 +413,12 @@ impl<B: SdkBackend> LxmfSdk for Client<B> {
             let lifecycle = self.lifecycle.lock().expect("lifecycle mutex poisoned");
             lifecycle.ensure_method_legal(SdkMethod::Status)?;
         }
-        self.backend.status(id)
+        let deduplicated = self.is_deduplicated(&id);
+        let mut snapshot = self.backend.status(id)?;
+        if let Some(snapshot) = snapshot.as_mut() {
+            snapshot.deduplicated |= deduplicated;
+        }
+        Ok(snapshot)
     }
 
     fn configure(&self, expected_revision: u64, patch: ConfigPatch) -> Result<Ack, SdkError> {
@@ -276,6 +438,15 @@ impl<B: SdkBackend> LxmfSdk for Client<B> {
     }
 
     fn poll_events(&self, cursor: Option<EventCursor>, max: usize) -> Result<EventBatch, SdkError> {
+        self.poll_events_filtered(cursor, max, &[])
+    }
+
+    fn poll_events_filtered(
+        &self,
+        cursor: Option<EventCursor>,
+        max: usize,
+        event_types: &[String],
+    ) -> Result<EventBatch, SdkError> {
         {
             let lifecycle = self.lifecycle.lock().expect("lifecycle mutex poisoned");
             lifecycle.ensure_method_legal(SdkMethod::PollEvents)?;
@@ -298,7 +469,7 @@ impl<B: SdkBackend> LxmfSdk for Client<B> {
                 .with_user_actionable(true));
             }
         }
-        self.backend.poll_events(cursor, max)
+        self.backend.poll_events_filtered(cursor, max, event_types)
     }
 
     fn snapshot(&self) -> Result<RuntimeSnapshot, SdkError> {
@@ -309,24 +480,25 @@ impl<B: SdkBackend> LxmfSdk for Client<B> {
         self.backend.snapshot()
     }
 
-    fn shutdown(&self, mode: ShutdownMode) -> Result<Ack, SdkError> {
-        let current_state = {
-            let lifecycle = self.lifecycle.lock().expect("lifecycle mutex poisoned");
-            lifecycle.ensure_method_legal(SdkMethod::Shutdown)?;
-            lifecycle.state()
-        };
-        if current_state == RuntimeState::Stopped {
-            return Ok(Ack { accepted: true, revision: None });
-        }
-        let ack = self.backend.shutdown(mode)?;
+    fn snapshot_since(&self, since_revision: u64) -> Result<RuntimeSnapshotDelta, SdkError> {
         {
-            let mut lifecycle = self.lifecycle.lock().expect("lifecycle mutex poisoned");
-            if lifecycle.state() != RuntimeState::Stopped {
-                let _ = lifecycle.mark_draining();
-                lifecycle.mark_stopped();
-            }
+            let lifecycle = self.lifecycle.lock().expect("lifecycle mutex poisoned");
+            lifecycle.ensure_method_legal(SdkMethod::Snapshot)?;
         }
-        Ok(ack)
+        self.backend.snapshot_since(since_revision)
+    }
+
+    /// Not lifecycle-gated: probing the runtime before `start` is the point.
+    fn version(&self) -> Result<VersionInfo, SdkError> {
+        self.backend.version()
+    }
+
+    fn shutdown(&self, mode: ShutdownMode) -> Result<Ack, SdkError> {
+        self.shutdown_with(|backend| backend.shutdown(mode))
+    }
+
+    fn shutdown_graceful(&self, drain_timeout_ms: u64) -> Result<Ack, SdkError> {
+        self.shutdown_with(|backend| backend.shutdown_graceful(drain_timeout_ms))
     }
 }
 
@@ -386,6 +558,9 @@ impl<B: SdkBackend> LxmfSdkGroupDelivery for Client<B> {
                 idempotency_key: req.idempotency_key.clone(),
                 ttl_ms: req.ttl_ms,
                 correlation_id: req.correlation_id.clone(),
+                scheduled_ts_ms: None,
+                fail_fast_no_path: false,
+                reply_to: None,
                 extensions: req.extensions.clone(),
             };
             match self.send(send_request) {
@@ -66,6 +66,14 @@ impl<B: SdkBackend> LxmfSdkAttachments for Client<B> {
         self.backend.attachment_get(attachment_id)
     }
 
+    fn message_attachment_get(
+        &self,
+        message_id: crate::types::MessageId,
+        index: usize,
+    ) -> Result<crate::domain::MessageAttachment, SdkError> {
+        self.backend.message_attachment_get(message_id, index)
+    }
+
     fn attachment_list(
         &self,
         req: crate::domain::AttachmentListRequest,
@@ -160,6 +168,17 @@ impl<B: SdkBackend> LxmfSdkIdentity for Client<B> {
         self.backend.identity_announce_now()
     }
 
+    fn whoami(&self) -> Result<crate::domain::Whoami, SdkError> {
+        self.backend.whoami()
+    }
+
+    fn set_display_name(
+        &self,
+        display_name: &str,
+    ) -> Result<crate::domain::DisplayNameUpdate, SdkError> {
+        self.backend.set_display_name(display_name)
+    }
+
     fn identity_presence_list(
         &self,
         req: crate::domain::PresenceListRequest,
@@ -266,3 +285,65 @@ impl<B: SdkBackend> LxmfSdkVoiceSignaling for Client<B> {
         self.backend.voice_session_close(session_id)
     }
 }
+
+impl<B: SdkBackend> LxmfSdkLogs for Client<B> {
+    fn logs_tail(
+        &self,
+        after_offset: Option<u64>,
+        max_lines: usize,
+    ) -> Result<crate::domain::LogsTail, SdkError> {
+        self.backend.logs_tail(after_offset, max_lines)
+    }
+
+    fn logs_tail_at_level(
+        &self,
+        after_offset: Option<u64>,
+        max_lines: usize,
+        min_level: crate::domain::LogLevel,
+    ) -> Result<crate::domain::LogsTail, SdkError> {
+        self.backend.logs_tail_at_level(after_offset, max_lines, min_level)
+    }
+
+    fn delivery_trace(&self, id: MessageId) -> Result<crate::domain::DeliveryTrace, SdkError> {
+        self.backend.delivery_trace(id)
+    }
+
+    fn prune_messages(
+        &self,
+        older_than_ms: u64,
+    ) -> Result<crate::domain::MessagePruneResult, SdkError> {
+        self.backend.prune_messages(older_than_ms)
+    }
+
+    fn messages_search(
+        &self,
+        req: crate::domain::MessageSearchRequest,
+    ) -> Result<crate::domain::MessageSearchResult, SdkError> {
+        self.backend.messages_search(req)
+    }
+
+    fn messages_export(
+        &self,
+        req: crate::domain::MessageExportRequest,
+    ) -> Result<crate::domain::MessageExportBundle, SdkError> {
+        self.backend.messages_export(req)
+    }
+
+    fn health(&self) -> Result<crate::domain::HealthReport, SdkError> {
+        self.backend.health()
+    }
+}
+
+impl<B: SdkBackend> LxmfSdkPropagation for Client<B> {
+    fn propagation_stats(&self) -> Result<crate::domain::PropagationStats, SdkError> {
+        self.backend.propagation_stats()
+    }
+
+    fn propagation_sync_now(&self) -> Result<crate::domain::PropagationSyncStatus, SdkError> {
+        self.backend.propagation_sync_now()
+    }
+
+    fn stamp_status(&self) -> Result<crate::domain::StampStatus, SdkError> {
+        self.backend.stamp_status()
+    }
+}
@@ -17,7 +17,10 @@ struct MockBackend {
     shutdown_results: Mutex<VecDeque<Result<Ack, SdkError>>>,
     send_results: Mutex<VecDeque<Result<MessageId, SdkError>>>,
     send_calls: AtomicUsize,
+    send_batch_calls: AtomicUsize,
     shutdown_calls: AtomicUsize,
+    /// Sends the runtime reports as answered by an earlier idempotent send.
+    runtime_replays: Mutex<Vec<MessageId>>,
 }
 
 impl MockBackend {
@@ -27,10 +30,13 @@ impl MockBackend {
             shutdown_results: Mutex::new(VecDeque::from(vec![Ok(Ack {
                 accepted: true,
                 revision: None,
+                pending_messages: None,
             })])),
             send_results: Mutex::new(VecDeque::new()),
             send_calls: AtomicUsize::new(0),
+            send_batch_calls: AtomicUsize::new(0),
             shutdown_calls: AtomicUsize::new(0),
+            runtime_replays: Mutex::new(Vec::new()),
         }
     }
 
@@ -70,6 +76,18 @@ impl SdkBackend for MockBackend {
         Ok(MessageId(format!("m-{sequence}")))
     }
 
+    fn send_batch(
+        &self,
+        reqs: Vec<SendRequest>,
+    ) -> Result<Vec<Result<MessageId, SdkError>>, SdkError> {
+        self.send_batch_calls.fetch_add(1, Ordering::Relaxed);
+        Ok(reqs.into_iter().map(|req| self.send(req)).collect())
+    }
+
+    fn send_deduplicated(&self, id: &MessageId) -> bool {
+        self.runtime_replays.lock().expect("runtime_replays mutex poisoned").contains(id)
+    }
+
     fn cancel(&self, _id: MessageId) -> Result<CancelResult, SdkError> {
         Ok(CancelResult::Accepted)
     }
@@ -82,11 +100,12 @@ impl SdkBackend for MockBackend {
             last_updated_ms: 0,
             attempts: 0,
             reason_code: None,
+            deduplicated: false,
         }))
     }
 
     fn configure(&self, _expected_revision: u64, _patch: ConfigPatch) -> Result<Ack, SdkError> {
-        Ok(Ack { accepted: true, revision: Some(1) })
+        Ok(Ack { accepted: true, revision: Some(1), pending_messages: None })
     }
 
     fn poll_events(
@@ -129,6 +148,13 @@ impl SdkBackend for MockBackend {
             config_revision: 0,
             queued_messages: 0,
             in_flight_messages: 0,
+            announce_interval_secs: None,
+            outbound_queue_depth: 0,
+            inbound_pending: 0,
+            event_buffer_used: 0,
+            event_buffer_capacity: 0,
+            messages_stored: 0,
+            propagation_node: false,
         })
     }
 
@@ -268,7 +294,7 @@ fn start_ignores_unknown_requested_capabilities_when_known_overlap_exists() {
 #[test]
 fn shutdown_is_noop_once_stopped() {
     let backend = MockBackend::new(vec![successful_negotiation()]).with_shutdown_results(vec![
-        Ok(Ack { accepted: true, revision: None }),
+        Ok(Ack { accepted: true, revision: None, pending_messages: None }),
         Err(SdkError::new(
             code::INTERNAL,
             ErrorCategory::Transport,
@@ -314,6 +340,41 @@ fn race_idempotent_send_parallel_calls_dedupe_to_single_backend_send() {
     );
 }
 
+#[test]
+fn idempotent_replay_returns_original_id_and_flags_snapshot() {
+    let backend = MockBackend::new(vec![successful_negotiation()]);
+    let client = Client::new(backend);
+    client.start(sample_start_request()).expect("start");
+
+    let first = client.send(sample_send_request("payload", Some("idem-replay"))).expect("send");
+    let fresh = client.status(first.clone()).expect("status").expect("snapshot");
+    assert!(!fresh.deduplicated, "first send is not a replay");
+
+    let second = client.send(sample_send_request("payload", Some("idem-replay"))).expect("send");
+    assert_eq!(second, first, "replay must return the original id");
+    assert!(client.is_deduplicated(&second));
+    let replayed = client.status(second).expect("status").expect("snapshot");
+    assert!(replayed.deduplicated, "replayed send must be flagged");
+    assert_eq!(client.backend().send_calls.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn runtime_idempotent_replay_is_flagged_by_a_fresh_client() {
+    let original = MessageId("m-original".to_owned());
+    let backend = MockBackend::new(vec![successful_negotiation()])
+        .with_send_results(vec![Ok(original.clone())]);
+    backend.runtime_replays.lock().expect("runtime_replays mutex poisoned").push(original.clone());
+    let client = Client::new(backend);
+    client.start(sample_start_request()).expect("start");
+
+    let replayed = client.send(sample_send_request("payload", Some("idem-daemon"))).expect("send");
+    assert_eq!(replayed, original);
+    assert!(
+        client.is_deduplicated(&replayed),
+        "runtime replays must be flagged without a cache hit"
+    );
+}
+
 #[test]
 fn race_idempotency_conflict_parallel_payloads_return_conflict() {
     let backend = MockBackend::new(vec![successful_negotiation()]);
@@ -399,3 +460,55 @@ fn group_send_rejects_empty_destination_list() {
         .expect_err("group send without destinations must fail");
     assert_eq!(err.machine_code, code::VALIDATION_INVALID_ARGUMENT);
 }
+
+fn send_batch_negotiation() -> Result<NegotiationResponse, SdkError> {
+    let mut negotiation = successful_negotiation()?;
+    negotiation.effective_capabilities.push("sdk.capability.send_batch".to_owned());
+    Ok(negotiation)
+}
+
+#[test]
+fn send_batch_honours_idempotency_per_item_in_one_backend_call() {
+    let backend = MockBackend::new(vec![send_batch_negotiation()]);
+    let client = Client::new(backend);
+    client.start(sample_start_request()).expect("start");
+    let cached = client.send(sample_send_request("first", Some("idem-1"))).expect("send");
+
+    let results = client
+        .send_batch(vec![
+            sample_send_request("first", Some("idem-1")),
+            sample_send_request("changed", Some("idem-1")),
+            sample_send_request("fresh", None),
+            sample_send_request("keyed", Some("idem-2")),
+            sample_send_request("keyed", Some("idem-2")),
+        ])
+        .expect("batch should return per-item results");
+
+    assert_eq!(results.len(), 5);
+    assert_eq!(results[0].as_ref().expect("cached item"), &cached);
+    let conflict = results[1].as_ref().expect_err("changed payload must conflict");
+    assert_eq!(conflict.machine_code, code::VALIDATION_IDEMPOTENCY_CONFLICT);
+    assert!(results[2].is_ok());
+    let keyed = results[3].as_ref().expect("keyed item");
+    assert_eq!(results[4].as_ref().expect("duplicate keyed item"), keyed);
+    assert_eq!(client.backend().send_batch_calls.load(Ordering::Relaxed), 1);
+    assert_eq!(client.backend().send_calls.load(Ordering::Relaxed), 3);
+
+    let replay = client
+        .send(sample_send_request("keyed", Some("idem-2")))
+        .expect("batched idempotency key should be cached");
+    assert_eq!(&replay, keyed);
+}
+
+#[test]
+fn send_batch_requires_negotiated_capability() {
+    let backend = MockBackend::new(vec![successful_negotiation()]);
+    let client = Client::new(backend);
+    client.start(sample_start_request()).expect("start");
+
+    let err = client
+        .send_batch(vec![sample_send_request("payload", None)])
+        .expect_err("batch send without capability must fail");
+    assert_eq!(err.machine_code, code::CAPABILITY_DISABLED);
+    assert_eq!(client.backend().send_batch_calls.load(Ordering::Relaxed), 0);
+}
@@ -1,5 +1,9 @@
+use crate::error::{code, ErrorCategory, SdkError};
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine as _;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
+use sha2::{Digest, Sha256};
 use std::collections::BTreeMap;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -194,6 +198,55 @@ pub struct AttachmentDownloadChunk {
     pub bytes_base64: String,
 }
 
+/// A file attachment decoded from a stored message's fields.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MessageAttachment {
+    pub message_id: String,
+    pub index: usize,
+    pub name: String,
+    /// Detected from the payload's leading bytes; `application/octet-stream`
+    /// when the format is not recognised. Image `width` / `height` are carried
+    /// in `extensions`.
+    #[serde(default = "default_attachment_content_type")]
+    pub content_type: String,
+    pub size_bytes: u64,
+    pub checksum_sha256: String,
+    pub bytes_base64: String,
+    #[serde(default)]
+    pub extensions: BTreeMap<String, JsonValue>,
+}
+
+fn default_attachment_content_type() -> String {
+    "application/octet-stream".to_string()
+}
+
+impl MessageAttachment {
+    /// Decodes the payload and checks it against `checksum_sha256`.
+    pub fn decode_verified(&self) -> Result<Vec<u8>, SdkError> {
+        let bytes = BASE64_STANDARD.decode(self.bytes_base64.as_bytes()).map_err(|err| {
+            SdkError::new(
+                code::VALIDATION_INVALID_ARGUMENT,
+                ErrorCategory::Validation,
+                format!("attachment payload is not valid base64: {err}"),
+            )
+        })?;
+        let actual = hex::encode(Sha256::digest(bytes.as_slice()));
+        let expected = self.checksum_sha256.trim();
+        if !actual.eq_ignore_ascii_case(expected) {
+            let (index, message_id) = (self.index, &self.message_id);
+            return Err(SdkError::new(
+                code::VALIDATION_CHECKSUM_MISMATCH,
+                ErrorCategory::Validation,
+                format!(
+                    "attachment {index} of message {message_id} failed integrity check: \
+                     expected sha256 {expected}, got {actual}"
+                ),
+            ));
+        }
+        Ok(bytes)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct MarkerId(pub String);
 
@@ -434,9 +487,266 @@ pub struct VoiceSessionUpdateRequest {
     pub extensions: BTreeMap<String, JsonValue>,
 }
 
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PropagationStats {
+    pub enabled: bool,
+    pub ingested_total: u64,
+    pub last_ingest_count: u64,
+    pub fetches_served: u64,
+    pub fetch_misses: u64,
+    pub rejected_stamps: u64,
+    pub store_messages: u64,
+    pub store_bytes: u64,
+    #[serde(default)]
+    pub extensions: BTreeMap<String, JsonValue>,
+}
+
+/// Outcome of `propagation_sync_now`: the sync state the node is in after
+/// the request. `already_running` is set when an in-flight sync was reported
+/// instead of starting a new one.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct PropagationSyncStatus {
+    pub state: u32,
+    pub state_name: String,
+    pub progress: f64,
+    #[serde(default)]
+    pub selected_node: Option<String>,
+    #[serde(default)]
+    pub messages_received: u64,
+    #[serde(default)]
+    pub last_sync_started: Option<i64>,
+    #[serde(default)]
+    pub last_sync_error: Option<String>,
+    #[serde(default)]
+    pub already_running: bool,
+    #[serde(default)]
+    pub extensions: BTreeMap<String, JsonValue>,
+}
+
+/// The node's own addressing details, as reported by `whoami`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Whoami {
+    pub identity_hash: String,
+    pub delivery_destination_hash: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    pub profile: String,
+    #[serde(default)]
+    pub extensions: BTreeMap<String, JsonValue>,
+}
+
+/// Outcome of `set_display_name`: the normalized name now carried in
+/// delivery announces.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DisplayNameUpdate {
+    pub display_name: String,
+    pub announced: bool,
+    #[serde(default)]
+    pub extensions: BTreeMap<String, JsonValue>,
+}
+
+/// Propagation stamp-cost settings. Only `enabled` is reported when the node
+/// is not acting as a propagation node.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StampStatus {
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub propagation_stamp_cost: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flexibility: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peering_cost: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accepted_range: Option<[u32; 2]>,
+    /// `adaptive` when the required cost follows the propagation store load.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adaptive_cost: Option<u32>,
+    #[serde(default)]
+    pub extensions: BTreeMap<String, JsonValue>,
+}
+
+/// Daemon subsystem summary returned by the `health` RPC. Only enabled
+/// interfaces are counted.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HealthReport {
+    pub transport_up: bool,
+    pub interfaces_up: u32,
+    pub interfaces_total: u32,
+    pub propagation_sync_state: String,
+    pub outbound_queue_depth: u64,
+    #[serde(default)]
+    pub last_announce_ts_ms: Option<u64>,
+    #[serde(default)]
+    pub extensions: BTreeMap<String, JsonValue>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Ok,
+    Degraded,
+    Down,
+}
+
+impl HealthReport {
+    /// `Down` when no interface is up, `Degraded` when some enabled interface
+    /// is down while at least one is up.
+    pub fn status(&self) -> HealthStatus {
+        if self.interfaces_up == 0 {
+            HealthStatus::Down
+        } else if self.interfaces_up < self.interfaces_total {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Ok
+        }
+    }
+}
+
+/// Minimum severity for [`LogsTail`] requests. Daemon lines without a
+/// recognised level prefix are treated as `Info`.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum LogLevel {
+    /// Returns every line unchanged.
+    #[default]
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LogsTail {
+    pub lines: Vec<String>,
+    pub next_offset: u64,
+    #[serde(default)]
+    pub first_offset: u64,
+    #[serde(default)]
+    pub missed_lines: u64,
+    #[serde(default)]
+    pub extensions: BTreeMap<String, JsonValue>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MessagePruneResult {
+    pub pruned: u64,
+    pub cutoff_ts_ms: u64,
+    #[serde(default)]
+    pub extensions: BTreeMap<String, JsonValue>,
+}
+
+/// Server-side message filter. `query` matches title or content
+/// case-insensitively; time bounds are inclusive unix milliseconds.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MessageSearchRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_ts_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_ts_ms: Option<u64>,
+    /// Restricts results to this message and the replies that link to it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thread: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct StoredMessage {
+    pub id: String,
+    pub source: String,
+    pub destination: String,
+    pub title: String,
+    pub content: String,
+    pub timestamp: i64,
+    pub direction: String,
+    #[serde(default)]
+    pub fields: Option<JsonValue>,
+    #[serde(default)]
+    pub receipt_status: Option<String>,
+    #[serde(default)]
+    pub reply_to: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct MessageSearchResult {
+    pub messages: Vec<StoredMessage>,
+    pub next_cursor: Option<String>,
+    #[serde(default)]
+    pub extensions: BTreeMap<String, JsonValue>,
+}
+
+/// Selects the messages `messages_export` archives: those exchanged with
+/// `peer`, within inclusive unix-millisecond bounds. Unset fields match all.
+/// `limit` caps one page and `cursor` resumes from a bundle's `next_cursor`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MessageExportRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_ts_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_ts_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// Versioned conversation archive, oldest message first. A page returned by
+/// `messages_export` sets `next_cursor` while more messages remain.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct MessageExportBundle {
+    pub version: String,
+    pub exported_at: i64,
+    #[serde(default)]
+    pub peer: Option<String>,
+    #[serde(default)]
+    pub from_ts_ms: Option<u64>,
+    #[serde(default)]
+    pub to_ts_ms: Option<u64>,
+    #[serde(default)]
+    pub messages: Vec<StoredMessage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeliveryTraceEntry {
+    pub status: String,
+    pub timestamp: i64,
+    #[serde(default)]
+    pub reason_code: Option<String>,
+    #[serde(default)]
+    pub detail: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeliveryTrace {
+    pub message_id: String,
+    pub trace: Vec<DeliveryTraceEntry>,
+    #[serde(default)]
+    pub receipt_status: Option<String>,
+    #[serde(default)]
+    pub terminal: bool,
+    #[serde(default)]
+    pub extensions: BTreeMap<String, JsonValue>,
+}
+
 #[cfg(test)]
 mod tests {
-    use super::VoiceSessionState;
+    use super::{HealthReport, HealthStatus, MessageAttachment, VoiceSessionState};
+    use crate::error::code;
 
     #[test]
     fn voice_session_state_deserializes_unknown_variant() {
@@ -445,4 +755,38 @@ mod tests {
             serde_json::from_value(value).expect("unknown voice state should map to Unknown");
         assert_eq!(state, VoiceSessionState::Unknown);
     }
+
+    #[test]
+    fn message_attachment_decode_verified_rejects_checksum_mismatch() {
+        let mut attachment = MessageAttachment {
+            message_id: "msg-1".to_string(),
+            index: 0,
+            name: "a.bin".to_string(),
+            content_type: "application/octet-stream".to_string(),
+            size_bytes: 3,
+            checksum_sha256: "039058c6f2c0cb492c533b0a4d14ef77cc0f78abccced5287d84a1a2011cfb81"
+                .to_string(),
+            bytes_base64: "AQID".to_string(),
+            extensions: Default::default(),
+        };
+        assert_eq!(attachment.decode_verified().expect("checksum matches"), vec![1, 2, 3]);
+
+        attachment.bytes_base64 = "AQIE".to_string();
+        let err = attachment.decode_verified().expect_err("tampered payload");
+        assert_eq!(err.machine_code, code::VALIDATION_CHECKSUM_MISMATCH);
+    }
+
+    #[test]
+    fn health_status_distinguishes_degraded_from_down() {
+        let report = |interfaces_up, interfaces_total| HealthReport {
+            transport_up: true,
+            interfaces_up,
+            interfaces_total,
+            ..HealthReport::default()
+        };
+        assert_eq!(report(2, 2).status(), HealthStatus::Ok);
+        assert_eq!(report(1, 2).status(), HealthStatus::Degraded);
+        assert_eq!(report(0, 2).status(), HealthStatus::Down);
+        assert_eq!(report(0, 0).status(), HealthStatus::Down);
+    }
 }
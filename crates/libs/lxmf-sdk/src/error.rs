@@ -13,6 +13,10 @@ pub mod code {
     pub const RUNTIME_INVALID_CURSOR: &str = "SDK_RUNTIME_INVALID_CURSOR";
     pub const RUNTIME_CURSOR_EXPIRED: &str = "SDK_RUNTIME_CURSOR_EXPIRED";
     pub const RUNTIME_STREAM_DEGRADED: &str = "SDK_RUNTIME_STREAM_DEGRADED";
+    pub const RUNTIME_NO_PATH: &str = "SDK_RUNTIME_NO_PATH";
+    pub const RUNTIME_RATE_LIMITED: &str = "SDK_RUNTIME_RATE_LIMITED";
+    pub const RUNTIME_MESSAGE_EXPIRED: &str = "SDK_RUNTIME_MESSAGE_EXPIRED";
+    pub const RUNTIME_NO_PROPAGATION_NODE: &str = "SDK_RUNTIME_NO_PROPAGATION_NODE";
     pub const VALIDATION_IDEMPOTENCY_CONFLICT: &str = "SDK_VALIDATION_IDEMPOTENCY_CONFLICT";
     pub const VALIDATION_INVALID_ARGUMENT: &str = "SDK_VALIDATION_INVALID_ARGUMENT";
     pub const VALIDATION_CHECKSUM_MISMATCH: &str = "SDK_VALIDATION_CHECKSUM_MISMATCH";
@@ -20,8 +24,12 @@ pub mod code {
     pub const VALIDATION_MAX_POLL_EVENTS_EXCEEDED: &str = "SDK_VALIDATION_MAX_POLL_EVENTS_EXCEEDED";
     pub const VALIDATION_EVENT_TOO_LARGE: &str = "SDK_VALIDATION_EVENT_TOO_LARGE";
     pub const VALIDATION_BATCH_TOO_LARGE: &str = "SDK_VALIDATION_BATCH_TOO_LARGE";
+    pub const VALIDATION_ATTACHMENT_LIMIT_EXCEEDED: &str =
+        "SDK_VALIDATION_ATTACHMENT_LIMIT_EXCEEDED";
+    pub const VALIDATION_CONTENT_TOO_LARGE: &str = "SDK_VALIDATION_CONTENT_TOO_LARGE";
     pub const VALIDATION_MAX_EXTENSION_KEYS_EXCEEDED: &str =
         "SDK_VALIDATION_MAX_EXTENSION_KEYS_EXCEEDED";
+    pub const VALIDATION_INVALID_STAMP: &str = "SDK_VALIDATION_INVALID_STAMP";
     pub const CONFIG_CONFLICT: &str = "SDK_CONFIG_CONFLICT";
     pub const CONFIG_UNKNOWN_KEY: &str = "SDK_CONFIG_UNKNOWN_KEY";
     pub const SECURITY_AUTH_REQUIRED: &str = "SDK_SECURITY_AUTH_REQUIRED";
@@ -31,6 +39,7 @@ pub mod code {
     pub const SECURITY_RATE_LIMITED: &str = "SDK_SECURITY_RATE_LIMITED";
     pub const SECURITY_REMOTE_BIND_DISALLOWED: &str = "SDK_SECURITY_REMOTE_BIND_DISALLOWED";
     pub const SECURITY_REDACTION_REQUIRED: &str = "SDK_SECURITY_REDACTION_REQUIRED";
+    pub const SECURITY_TICKET_REVOKED: &str = "SDK_SECURITY_TICKET_REVOKED";
     pub const INTERNAL: &str = "SDK_INTERNAL_ERROR";
 }
 
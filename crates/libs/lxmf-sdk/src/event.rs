@@ -76,6 +76,8 @@ pub enum SubscriptionStart {
     Head,
     Tail,
     Snapshot,
+    /// Resume after a previously observed cursor, replaying retained events.
+    Cursor(EventCursor),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -85,9 +87,20 @@ pub struct EventSubscription {
     pub cursor: Option<EventCursor>,
 }
 
+impl EventSubscription {
+    /// Request target for the daemon's WebSocket `/events` stream, resuming
+    /// after `cursor` when one is set.
+    pub fn websocket_path(&self) -> String {
+        match &self.cursor {
+            Some(cursor) => format!("/events?cursor={}", cursor.0),
+            None => "/events".to_owned(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Severity;
+    use super::{EventCursor, EventSubscription, Severity, SubscriptionStart};
 
     #[test]
     fn severity_deserializes_unknown_variant() {
@@ -96,4 +109,16 @@ mod tests {
             serde_json::from_value(value).expect("unknown severity should map to Unknown");
         assert_eq!(severity, Severity::Unknown);
     }
+
+    #[test]
+    fn cursor_subscription_resumes_websocket_stream_after_cursor() {
+        let cursor = EventCursor("v2:rt-1:stream-1:7".to_owned());
+        let subscription = EventSubscription {
+            start: SubscriptionStart::Cursor(cursor.clone()),
+            cursor: Some(cursor),
+        };
+        assert_eq!(subscription.websocket_path(), "/events?cursor=v2:rt-1:stream-1:7");
+        let live = EventSubscription { start: SubscriptionStart::Head, cursor: None };
+        assert_eq!(live.websocket_path(), "/events");
+    }
 }
@@ -15,8 +15,9 @@ pub mod types;
 pub use api::{LxmfSdk, LxmfSdkAsync, LxmfSdkManualTick};
 // Stability class: experimental (capability-gated extension traits)
 pub use api::{
-    LxmfSdkAttachments, LxmfSdkGroupDelivery, LxmfSdkIdentity, LxmfSdkMarkers, LxmfSdkPaper,
-    LxmfSdkRemoteCommands, LxmfSdkTelemetry, LxmfSdkTopics, LxmfSdkVoiceSignaling,
+    LxmfSdkAttachments, LxmfSdkGroupDelivery, LxmfSdkIdentity, LxmfSdkLogs, LxmfSdkMarkers,
+    LxmfSdkPaper, LxmfSdkPropagation, LxmfSdkRemoteCommands, LxmfSdkTelemetry, LxmfSdkTopics,
+    LxmfSdkVoiceSignaling,
 };
 // Stability class: internal (backend composition surface)
 #[cfg(all(feature = "rpc-backend", feature = "std"))]
@@ -39,14 +40,18 @@ pub use domain::{
     AttachmentListResult, AttachmentMeta, AttachmentStoreRequest, AttachmentUploadChunkAck,
     AttachmentUploadChunkRequest, AttachmentUploadCommitRequest, AttachmentUploadId,
     AttachmentUploadSession, AttachmentUploadStartRequest, ContactListRequest, ContactListResult,
-    ContactRecord, ContactUpdateRequest, GeoPoint, IdentityBootstrapRequest, IdentityBundle,
-    IdentityImportRequest, IdentityRef, IdentityResolveRequest, MarkerCreateRequest,
-    MarkerDeleteRequest, MarkerId, MarkerListRequest, MarkerListResult, MarkerRecord,
-    MarkerUpdatePositionRequest, PaperMessageEnvelope, PresenceListRequest, PresenceListResult,
-    PresenceRecord, RemoteCommandRequest, RemoteCommandResponse, TelemetryPoint, TelemetryQuery,
-    TopicCreateRequest, TopicId, TopicListRequest, TopicListResult, TopicPath, TopicPublishRequest,
-    TopicRecord, TopicSubscriptionRequest, TrustLevel, VoiceSessionId, VoiceSessionOpenRequest,
-    VoiceSessionState, VoiceSessionUpdateRequest,
+    ContactRecord, ContactUpdateRequest, DeliveryTrace, DeliveryTraceEntry, DisplayNameUpdate,
+    GeoPoint, HealthReport, HealthStatus, IdentityBootstrapRequest, IdentityBundle,
+    IdentityImportRequest, IdentityRef, IdentityResolveRequest, LogLevel, LogsTail,
+    MarkerCreateRequest, MarkerDeleteRequest, MarkerId, MarkerListRequest, MarkerListResult,
+    MarkerRecord, MarkerUpdatePositionRequest, MessageAttachment, MessageExportBundle,
+    MessageExportRequest, MessagePruneResult, MessageSearchRequest, MessageSearchResult,
+    PaperMessageEnvelope, PresenceListRequest, PresenceListResult, PresenceRecord,
+    PropagationStats, PropagationSyncStatus, RemoteCommandRequest, RemoteCommandResponse,
+    StampStatus, StoredMessage, TelemetryPoint, TelemetryQuery, TopicCreateRequest, TopicId,
+    TopicListRequest, TopicListResult, TopicPath, TopicPublishRequest, TopicRecord,
+    TopicSubscriptionRequest, TrustLevel, VoiceSessionId, VoiceSessionOpenRequest,
+    VoiceSessionState, VoiceSessionUpdateRequest, Whoami,
 };
 pub use error::{code as error_code, ErrorCategory, ErrorDetails, SdkError};
 // Stability class: stable
@@ -64,10 +69,11 @@ pub use types::{
     Ack, AuthMode, BindMode, CancelResult, ClientHandle, ConfigPatch, DeliverySnapshot,
     DeliveryState, EventSinkConfig, EventSinkKind, EventSinkPatch, EventStreamConfig,
     GroupRecipientState, GroupSendOutcome, GroupSendRequest, GroupSendResult, MessageId,
-    OverflowPolicy, Profile, RedactionConfig, RedactionTransform, RpcBackendConfig,
-    RuntimeSnapshot, RuntimeState, SdkConfig, SendRequest, ShutdownMode, StartRequest,
-    StoreForwardCapacityPolicy, StoreForwardConfig, StoreForwardEvictionPriority,
-    StoreForwardPatch, TickBudget, TickResult,
+    MessageIdFormat, OverflowPolicy, Profile, PropagationSelectionStrategy, RedactionConfig,
+    RedactionTransform, RpcBackendConfig, RuntimeSnapshot, RuntimeSnapshotDelta, RuntimeState,
+    SdkConfig, SendRequest, ShutdownMode, StartRequest, StoreForwardCapacityPolicy,
+    StoreForwardConfig, StoreForwardEvictionPriority, StoreForwardPatch, TickBudget, TickResult,
+    VersionInfo,
 };
 
 pub const CONTRACT_RELEASE: &str = "v2.5";
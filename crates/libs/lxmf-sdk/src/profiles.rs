@@ -27,6 +27,7 @@ const CAP_PAPER_MESSAGES: &str = "sdk.capability.paper_messages";
 const CAP_REMOTE_COMMANDS: &str = "sdk.capability.remote_commands";
 const CAP_VOICE_SIGNALING: &str = "sdk.capability.voice_signaling";
 const CAP_GROUP_DELIVERY: &str = "sdk.capability.group_delivery";
+const CAP_SEND_BATCH: &str = "sdk.capability.send_batch";
 const CAP_EVENT_SINK_BRIDGE: &str = "sdk.capability.event_sink_bridge";
 const CAP_SHARED_INSTANCE_RPC_AUTH: &str = "sdk.capability.shared_instance_rpc_auth";
 
@@ -78,6 +79,7 @@ const DESKTOP_FULL_SUPPORTED: &[&str] = &[
     CAP_REMOTE_COMMANDS,
     CAP_VOICE_SIGNALING,
     CAP_GROUP_DELIVERY,
+    CAP_SEND_BATCH,
     CAP_EVENT_SINK_BRIDGE,
     CAP_SHARED_INSTANCE_RPC_AUTH,
 ];
@@ -109,6 +111,7 @@ const DESKTOP_LOCAL_RUNTIME_SUPPORTED: &[&str] = &[
     CAP_REMOTE_COMMANDS,
     CAP_VOICE_SIGNALING,
     CAP_GROUP_DELIVERY,
+    CAP_SEND_BATCH,
     CAP_EVENT_SINK_BRIDGE,
     CAP_SHARED_INSTANCE_RPC_AUTH,
 ];
@@ -207,11 +210,13 @@ pub fn supports_capability(profile: Profile, capability_id: &str) -> bool {
 
 pub fn is_profile_method_required(profile: Profile, method: &str) -> bool {
     match profile {
-        Profile::DesktopFull => !matches!(method, "tick" | "send_group"),
+        Profile::DesktopFull => !matches!(method, "tick" | "send_group" | "send_batch"),
         Profile::DesktopLocalRuntime => {
-            !matches!(method, "tick" | "subscribe_events" | "send_group")
+            !matches!(method, "tick" | "subscribe_events" | "send_group" | "send_batch")
+        }
+        Profile::EmbeddedAlloc => {
+            !matches!(method, "subscribe_events" | "send_group" | "send_batch")
         }
-        Profile::EmbeddedAlloc => !matches!(method, "subscribe_events" | "send_group"),
     }
 }
 
@@ -219,7 +224,7 @@ pub fn is_profile_method_supported(profile: Profile, method: &str) -> bool {
     match profile {
         Profile::DesktopFull => true,
         Profile::DesktopLocalRuntime => true,
-        Profile::EmbeddedAlloc => method != "subscribe_events",
+        Profile::EmbeddedAlloc => !matches!(method, "subscribe_events" | "send_batch"),
     }
 }
 
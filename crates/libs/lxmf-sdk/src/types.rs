@@ -5,19 +5,23 @@ mod runtime;
 mod session;
 
 pub use config::{
-    AuthMode, BindMode, EventSinkConfig, EventSinkKind, EventStreamConfig, MtlsAuthConfig,
-    OverflowPolicy, Profile, RedactionConfig, RedactionTransform, RpcBackendConfig, SdkConfig,
-    StoreForwardCapacityPolicy, StoreForwardConfig, StoreForwardEvictionPriority, TokenAuthConfig,
+    ApiKeyAuthConfig, AuthMode, BindMode, EventSinkConfig, EventSinkKind, EventStreamConfig,
+    MessageIdFormat, MtlsAuthConfig, OverflowPolicy, Profile, PropagationSelectionStrategy,
+    RedactionConfig, RedactionTransform, RpcBackendConfig, SdkConfig, StoreForwardCapacityPolicy,
+    StoreForwardConfig, StoreForwardEvictionPriority, TokenAuthConfig,
 };
 pub use delivery::{
     Ack, CancelResult, DeliverySnapshot, DeliveryState, GroupRecipientState, GroupSendOutcome,
     GroupSendRequest, GroupSendResult, MessageId, SendRequest,
 };
 pub use patch::{
-    ConfigPatch, EventSinkPatch, EventStreamPatch, MtlsAuthPatch, RedactionPatch, RpcBackendPatch,
-    StoreForwardPatch, TokenAuthPatch,
+    ApiKeyAuthPatch, ConfigPatch, EventSinkPatch, EventStreamPatch, MtlsAuthPatch, RedactionPatch,
+    RpcBackendPatch, StoreForwardPatch, TokenAuthPatch,
+};
+pub use runtime::{
+    RuntimeSnapshot, RuntimeSnapshotDelta, RuntimeState, ShutdownMode, TickBudget, TickResult,
+    VersionInfo,
 };
-pub use runtime::{RuntimeSnapshot, RuntimeState, ShutdownMode, TickBudget, TickResult};
 pub use session::{ClientHandle, StartRequest};
 
 #[cfg(test)]
@@ -27,6 +27,7 @@ pub enum AuthMode {
     LocalTrusted,
     Token,
     Mtls,
+    ApiKey,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -35,6 +36,7 @@ pub enum AuthMode {
 pub enum OverflowPolicy {
     Reject,
     DropOldest,
+    DropNewest,
     Block,
 }
 
@@ -54,6 +56,34 @@ pub enum StoreForwardEvictionPriority {
     TerminalFirst,
 }
 
+/// Order in which known propagation nodes are tried as relays.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum PropagationSelectionStrategy {
+    /// The selected node first, then the other known nodes.
+    #[default]
+    Selected,
+    /// Known nodes by ascending announced peering cost.
+    LowestCost,
+    /// Known nodes by most recent announce.
+    MostRecent,
+}
+
+/// How the RPC backend names the messages it sends.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum MessageIdFormat {
+    /// 26-character ULIDs that sort by creation time and stay unique across
+    /// client processes.
+    #[default]
+    Ulid,
+    /// `sdk-<n>` from the client's request counter, which restarts with
+    /// every client.
+    Sequential,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[non_exhaustive]
 pub struct StoreForwardConfig {
@@ -119,6 +149,13 @@ pub struct TokenAuthConfig {
     pub shared_secret: String,
 }
 
+/// Static key presented by clients in the `X-Api-Key` header.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ApiKeyAuthConfig {
+    pub api_key: String,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[non_exhaustive]
 pub struct MtlsAuthConfig {
@@ -137,8 +174,15 @@ pub struct RpcBackendConfig {
     pub write_timeout_ms: u64,
     pub max_header_bytes: usize,
     pub max_body_bytes: usize,
+    /// Response bodies at least this large are gzip-compressed by the daemon.
+    #[serde(default = "default_min_compress_bytes")]
+    pub min_compress_bytes: usize,
+    #[serde(default)]
+    pub message_id_format: MessageIdFormat,
     pub token_auth: Option<TokenAuthConfig>,
     pub mtls_auth: Option<MtlsAuthConfig>,
+    #[serde(default)]
+    pub api_key_auth: Option<ApiKeyAuthConfig>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -162,6 +206,7 @@ pub struct SdkConfig {
 }
 
 const DEFAULT_RPC_LISTEN_ADDR: &str = "127.0.0.1:4242";
+const DEFAULT_MIN_COMPRESS_BYTES: usize = 1_024;
 
 fn default_event_stream(profile: &Profile) -> EventStreamConfig {
     match profile {
@@ -202,11 +247,18 @@ fn default_rpc_backend(listen_addr: impl Into<String>) -> RpcBackendConfig {
         write_timeout_ms: 5_000,
         max_header_bytes: 16_384,
         max_body_bytes: 1_048_576,
+        min_compress_bytes: DEFAULT_MIN_COMPRESS_BYTES,
+        message_id_format: MessageIdFormat::Ulid,
         token_auth: None,
         mtls_auth: None,
+        api_key_auth: None,
     }
 }
 
+fn default_min_compress_bytes() -> usize {
+    DEFAULT_MIN_COMPRESS_BYTES
+}
+
 fn default_store_forward(profile: &Profile) -> StoreForwardConfig {
     match profile {
         Profile::DesktopFull | Profile::DesktopLocalRuntime => StoreForwardConfig {
@@ -299,8 +351,11 @@ impl SdkConfig {
                 write_timeout_ms: 2_000,
                 max_header_bytes: 8_192,
                 max_body_bytes: 65_536,
+                min_compress_bytes: DEFAULT_MIN_COMPRESS_BYTES,
+                message_id_format: MessageIdFormat::Ulid,
                 token_auth: None,
                 mtls_auth: None,
+                api_key_auth: None,
             }),
             extensions: BTreeMap::new(),
         }
@@ -326,6 +381,7 @@ impl SdkConfig {
         let backend =
             self.rpc_backend.get_or_insert_with(|| default_rpc_backend(DEFAULT_RPC_LISTEN_ADDR));
         backend.mtls_auth = None;
+        backend.api_key_auth = None;
         backend.token_auth = Some(TokenAuthConfig {
             issuer: issuer.into(),
             audience: audience.into(),
@@ -342,6 +398,7 @@ impl SdkConfig {
         let backend =
             self.rpc_backend.get_or_insert_with(|| default_rpc_backend(DEFAULT_RPC_LISTEN_ADDR));
         backend.token_auth = None;
+        backend.api_key_auth = None;
         backend.mtls_auth = Some(MtlsAuthConfig {
             ca_bundle_path: ca_bundle_path.into(),
             require_client_cert: false,
@@ -352,6 +409,19 @@ impl SdkConfig {
         self
     }
 
+    /// Requires clients to send `api_key` in the `X-Api-Key` header. The bind
+    /// mode is left as is, so a local_only daemon still rejects non-loopback
+    /// peers before the key is checked.
+    pub fn with_api_key_auth(mut self, api_key: impl Into<String>) -> Self {
+        self.auth_mode = AuthMode::ApiKey;
+        let backend =
+            self.rpc_backend.get_or_insert_with(|| default_rpc_backend(DEFAULT_RPC_LISTEN_ADDR));
+        backend.token_auth = None;
+        backend.mtls_auth = None;
+        backend.api_key_auth = Some(ApiKeyAuthConfig { api_key: api_key.into() });
+        self
+    }
+
     pub fn with_mtls_client_credentials(
         mut self,
         client_cert_path: impl Into<String>,
@@ -362,6 +432,7 @@ impl SdkConfig {
         let mtls =
             self.rpc_backend.get_or_insert_with(|| default_rpc_backend(DEFAULT_RPC_LISTEN_ADDR));
         mtls.token_auth = None;
+        mtls.api_key_auth = None;
         if mtls.mtls_auth.is_none() {
             mtls.mtls_auth = Some(MtlsAuthConfig {
                 ca_bundle_path: "ca.pem".to_owned(),
@@ -487,21 +558,21 @@ impl SdkConfig {
 
         match self.bind_mode {
             BindMode::LocalOnly => {
-                if self.auth_mode != AuthMode::LocalTrusted {
+                if !matches!(self.auth_mode, AuthMode::LocalTrusted | AuthMode::ApiKey) {
                     return Err(SdkError::new(
                         code::SECURITY_AUTH_REQUIRED,
                         ErrorCategory::Security,
-                        "local_only bind mode requires local_trusted auth mode",
+                        "local_only bind mode requires local_trusted or api_key auth mode",
                     )
                     .with_user_actionable(true));
                 }
             }
             BindMode::Remote => {
-                if !matches!(self.auth_mode, AuthMode::Token | AuthMode::Mtls) {
+                if !matches!(self.auth_mode, AuthMode::Token | AuthMode::Mtls | AuthMode::ApiKey) {
                     return Err(SdkError::new(
                         code::SECURITY_REMOTE_BIND_DISALLOWED,
                         ErrorCategory::Security,
-                        "remote bind mode requires token or mtls auth mode",
+                        "remote bind mode requires token, mtls, or api_key auth mode",
                     )
                     .with_user_actionable(true));
                 }
@@ -546,6 +617,22 @@ impl SdkConfig {
                     .with_user_actionable(true));
                 }
             }
+            AuthMode::ApiKey => {
+                let api_key = self
+                    .rpc_backend
+                    .as_ref()
+                    .and_then(|backend| backend.api_key_auth.as_ref())
+                    .map(|auth| auth.api_key.trim())
+                    .unwrap_or_default();
+                if api_key.is_empty() {
+                    return Err(SdkError::new(
+                        code::SECURITY_AUTH_REQUIRED,
+                        ErrorCategory::Security,
+                        "api_key auth mode requires a non-empty rpc_backend.api_key_auth.api_key",
+                    )
+                    .with_user_actionable(true));
+                }
+            }
             AuthMode::Mtls => {
                 if self.profile == Profile::EmbeddedAlloc {
                     return Err(SdkError::new(
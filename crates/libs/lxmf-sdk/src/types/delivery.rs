@@ -12,6 +12,17 @@ pub struct SendRequest {
     pub idempotency_key: Option<String>,
     pub ttl_ms: Option<u64>,
     pub correlation_id: Option<String>,
+    /// Unix time in milliseconds before which the daemon holds the message.
+    #[serde(default)]
+    pub scheduled_ts_ms: Option<u64>,
+    /// Fail with `SDK_RUNTIME_NO_PATH` instead of queueing when the
+    /// destination has no path.
+    #[serde(default)]
+    pub fail_fast_no_path: bool,
+    /// Parent message id sent in the LXMF thread field. The link is
+    /// advisory; the parent does not need to be known locally.
+    #[serde(default)]
+    pub reply_to: Option<String>,
     #[serde(default)]
     pub extensions: BTreeMap<String, JsonValue>,
 }
@@ -29,6 +40,9 @@ impl SendRequest {
             idempotency_key: None,
             ttl_ms: None,
             correlation_id: None,
+            scheduled_ts_ms: None,
+            fail_fast_no_path: false,
+            reply_to: None,
             extensions: BTreeMap::new(),
         }
     }
@@ -48,6 +62,21 @@ impl SendRequest {
         self
     }
 
+    pub fn with_scheduled_ts_ms(mut self, scheduled_ts_ms: u64) -> Self {
+        self.scheduled_ts_ms = Some(scheduled_ts_ms);
+        self
+    }
+
+    pub fn with_fail_fast_no_path(mut self) -> Self {
+        self.fail_fast_no_path = true;
+        self
+    }
+
+    pub fn with_reply_to(mut self, message_id: impl Into<String>) -> Self {
+        self.reply_to = Some(message_id.into());
+        self
+    }
+
     pub fn with_extension(mut self, key: impl Into<String>, value: JsonValue) -> Self {
         self.extensions.insert(key.into(), value);
         self
@@ -158,6 +187,7 @@ impl Display for MessageId {
 #[serde(rename_all = "snake_case")]
 #[non_exhaustive]
 pub enum DeliveryState {
+    Scheduled,
     Queued,
     Dispatching,
     InFlight,
@@ -180,6 +210,10 @@ pub struct DeliverySnapshot {
     pub last_updated_ms: u64,
     pub attempts: u32,
     pub reason_code: Option<String>,
+    /// Set when the send that produced this id was answered from the
+    /// idempotency cache instead of submitting a new message.
+    #[serde(default)]
+    pub deduplicated: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -187,6 +221,9 @@ pub struct DeliverySnapshot {
 pub struct Ack {
     pub accepted: bool,
     pub revision: Option<u64>,
+    /// Outbound messages still queued or in flight when a shutdown returned.
+    #[serde(default)]
+    pub pending_messages: Option<u64>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
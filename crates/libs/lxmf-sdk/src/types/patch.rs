@@ -1,6 +1,6 @@
 use super::config::{
-    EventSinkKind, OverflowPolicy, RedactionTransform, StoreForwardCapacityPolicy,
-    StoreForwardEvictionPriority,
+    EventSinkKind, OverflowPolicy, PropagationSelectionStrategy, RedactionTransform,
+    StoreForwardCapacityPolicy, StoreForwardEvictionPriority,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
@@ -62,6 +62,13 @@ pub struct MtlsAuthPatch {
     pub client_key_path: Option<Option<String>>,
 }
 
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[non_exhaustive]
+pub struct ApiKeyAuthPatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<Option<String>>,
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
 #[non_exhaustive]
 pub struct RpcBackendPatch {
@@ -76,9 +83,13 @@ pub struct RpcBackendPatch {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_body_bytes: Option<Option<usize>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_compress_bytes: Option<Option<usize>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub token_auth: Option<Option<TokenAuthPatch>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mtls_auth: Option<Option<MtlsAuthPatch>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_auth: Option<Option<ApiKeyAuthPatch>>,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
@@ -122,10 +133,52 @@ pub struct ConfigPatch {
     pub event_stream: Option<Option<EventStreamPatch>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub idempotency_ttl_ms: Option<Option<u64>>,
+    /// Maximum sends per minute to any one destination; 0 disables the limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_destination_rate_limit: Option<Option<u32>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub redaction: Option<Option<RedactionPatch>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rpc_backend: Option<Option<RpcBackendPatch>>,
+    /// Telemetry points kept per peer before the oldest are dropped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub telemetry_max_points_per_peer: Option<Option<u32>>,
+    /// Seconds between periodic announces; values below 10 are clamped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub announce_interval_secs: Option<Option<u64>>,
+    /// Largest outbound content in UTF-8 bytes; 0 disables the check.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_content_bytes: Option<Option<u64>>,
+    /// Largest outbound title in UTF-8 bytes while the delivery policy sets
+    /// no `max_title_bytes`, which wins when set; 0 disables the check.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_title_wire_bytes: Option<Option<u64>>,
+    /// Milliseconds an outbound message may go without progress before it
+    /// fails with a `timeout` reason; 0 disables.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt_timeout_ms: Option<Option<u64>>,
+    /// Receipt timeout for propagated sends, which usually settle slower.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub propagation_receipt_timeout_ms: Option<Option<u64>>,
+    /// Lifetime of propagated messages stored here that carry no TTL of their own.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub propagation_message_ttl_ms: Option<Option<u64>>,
+    /// How propagation relay candidates are ordered.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub propagation_selection_strategy: Option<Option<PropagationSelectionStrategy>>,
+    /// Serves as a propagation node; switching it applies live and enabling
+    /// it announces the new role.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub propagation_node: Option<Option<bool>>,
+    /// Source hashes accepted inbound; when non-empty everything else is dropped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inbound_allowlist: Option<Option<Vec<String>>>,
+    /// Source hashes whose inbound messages are dropped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inbound_blocklist: Option<Option<Vec<String>>>,
+    /// Let messages from non-blocked contacts bypass the inbound lists.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inbound_contacts_bypass: Option<Option<bool>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extensions: Option<Option<BTreeMap<String, JsonValue>>>,
 }
@@ -160,6 +213,74 @@ impl ConfigPatch {
         self
     }
 
+    pub fn with_per_destination_rate_limit(mut self, sends_per_minute: u32) -> Self {
+        self.per_destination_rate_limit = Some(Some(sends_per_minute));
+        self
+    }
+
+    pub fn with_telemetry_max_points_per_peer(mut self, max_points: u32) -> Self {
+        self.telemetry_max_points_per_peer = Some(Some(max_points));
+        self
+    }
+
+    pub fn with_announce_interval_secs(mut self, interval_secs: u64) -> Self {
+        self.announce_interval_secs = Some(Some(interval_secs));
+        self
+    }
+
+    pub fn with_max_content_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_content_bytes = Some(Some(max_bytes));
+        self
+    }
+
+    pub fn with_max_title_wire_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_title_wire_bytes = Some(Some(max_bytes));
+        self
+    }
+
+    pub fn with_receipt_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.receipt_timeout_ms = Some(Some(timeout_ms));
+        self
+    }
+
+    pub fn with_propagation_receipt_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.propagation_receipt_timeout_ms = Some(Some(timeout_ms));
+        self
+    }
+
+    pub fn with_propagation_message_ttl_ms(mut self, ttl_ms: u64) -> Self {
+        self.propagation_message_ttl_ms = Some(Some(ttl_ms));
+        self
+    }
+
+    pub fn with_propagation_selection_strategy(
+        mut self,
+        strategy: PropagationSelectionStrategy,
+    ) -> Self {
+        self.propagation_selection_strategy = Some(Some(strategy));
+        self
+    }
+
+    pub fn with_propagation_node(mut self, enabled: bool) -> Self {
+        self.propagation_node = Some(Some(enabled));
+        self
+    }
+
+    pub fn with_inbound_allowlist(mut self, hashes: Vec<String>) -> Self {
+        self.inbound_allowlist = Some(Some(hashes));
+        self
+    }
+
+    pub fn with_inbound_blocklist(mut self, hashes: Vec<String>) -> Self {
+        self.inbound_blocklist = Some(Some(hashes));
+        self
+    }
+
+    pub fn with_inbound_contacts_bypass(mut self, enabled: bool) -> Self {
+        self.inbound_contacts_bypass = Some(Some(enabled));
+        self
+    }
+
     pub fn with_extension(mut self, key: impl Into<String>, value: JsonValue) -> Self {
         let mut extensions = self.extensions.unwrap_or(Some(BTreeMap::new())).unwrap_or_default();
         extensions.insert(key.into(), value);
@@ -174,8 +295,21 @@ impl ConfigPatch {
             && self.event_sink.is_none()
             && self.event_stream.is_none()
             && self.idempotency_ttl_ms.is_none()
+            && self.per_destination_rate_limit.is_none()
             && self.redaction.is_none()
             && self.rpc_backend.is_none()
+            && self.telemetry_max_points_per_peer.is_none()
+            && self.announce_interval_secs.is_none()
+            && self.max_content_bytes.is_none()
+            && self.max_title_wire_bytes.is_none()
+            && self.receipt_timeout_ms.is_none()
+            && self.propagation_receipt_timeout_ms.is_none()
+            && self.propagation_message_ttl_ms.is_none()
+            && self.propagation_selection_strategy.is_none()
+            && self.propagation_node.is_none()
+            && self.inbound_allowlist.is_none()
+            && self.inbound_blocklist.is_none()
+            && self.inbound_contacts_bypass.is_none()
             && self.extensions.is_none()
     }
 }
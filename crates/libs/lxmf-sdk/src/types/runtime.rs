@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -24,6 +26,71 @@ pub struct RuntimeSnapshot {
     pub config_revision: u64,
     pub queued_messages: u64,
     pub in_flight_messages: u64,
+    /// Effective seconds between periodic announces; `None` when the runtime
+    /// is not announcing periodically.
+    #[serde(default)]
+    pub announce_interval_secs: Option<u64>,
+    /// Outbound messages not yet sent or terminal.
+    #[serde(default)]
+    pub outbound_queue_depth: u64,
+    /// Inbound frames waiting to be decoded.
+    #[serde(default)]
+    pub inbound_pending: u64,
+    /// Events held in the runtime's replay buffer, out of
+    /// `event_buffer_capacity`.
+    #[serde(default)]
+    pub event_buffer_used: u64,
+    #[serde(default)]
+    pub event_buffer_capacity: u64,
+    #[serde(default)]
+    pub messages_stored: u64,
+    /// Whether the runtime currently serves as a propagation node.
+    #[serde(default)]
+    pub propagation_node: bool,
+}
+
+/// Snapshot fields that changed after a prior `snapshot_revision`. Asking
+/// for revision 0, or for one the runtime no longer knows, yields every field.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[non_exhaustive]
+pub struct RuntimeSnapshotDelta {
+    pub snapshot_revision: u64,
+    pub unchanged: bool,
+    #[serde(default)]
+    pub changed: BTreeMap<String, JsonValue>,
+}
+
+/// Contract and build details a runtime reports through `version`, usable
+/// before a session is negotiated.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VersionInfo {
+    pub contract_release: String,
+    pub schema_namespace: String,
+    #[serde(default)]
+    pub supported_contract_versions: Vec<u16>,
+    /// `debug` or `release`.
+    pub build_profile: String,
+    #[serde(default)]
+    pub crate_version: String,
+    /// Commit the runtime was built from, when the build could determine it.
+    #[serde(default)]
+    pub git_hash: Option<String>,
+    /// Cargo features enabled in the runtime build.
+    #[serde(default)]
+    pub features: Vec<String>,
+    #[serde(default)]
+    pub extensions: BTreeMap<String, JsonValue>,
+}
+
+impl RuntimeSnapshotDelta {
+    /// Reports a full snapshot as a delta for backends that cannot diff.
+    pub fn full(snapshot: &RuntimeSnapshot) -> Self {
+        let changed = match serde_json::to_value(snapshot) {
+            Ok(JsonValue::Object(fields)) => fields.into_iter().collect(),
+            _ => BTreeMap::new(),
+        };
+        Self { snapshot_revision: 0, unchanged: false, changed }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
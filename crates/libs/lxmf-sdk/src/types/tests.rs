@@ -91,6 +91,8 @@ fn config_rejects_embedded_profile_with_mtls_auth_mode() {
         write_timeout_ms: 1_000,
         max_header_bytes: 8_192,
         max_body_bytes: 65_536,
+        min_compress_bytes: 1_024,
+        message_id_format: MessageIdFormat::Ulid,
         token_auth: None,
         mtls_auth: Some(MtlsAuthConfig {
             ca_bundle_path: "/tmp/ca.pem".to_string(),
@@ -99,6 +101,7 @@ fn config_rejects_embedded_profile_with_mtls_auth_mode() {
             client_cert_path: Some("/tmp/client.pem".to_string()),
             client_key_path: Some("/tmp/client.key".to_string()),
         }),
+        api_key_auth: None,
     });
     let err = config.validate().expect_err("embedded profile must reject mtls");
     assert_eq!(err.machine_code, crate::error::code::VALIDATION_INVALID_ARGUMENT);
@@ -115,6 +118,8 @@ fn config_rejects_mtls_without_client_cert_material_when_required() {
         write_timeout_ms: 1_000,
         max_header_bytes: 8_192,
         max_body_bytes: 65_536,
+        min_compress_bytes: 1_024,
+        message_id_format: MessageIdFormat::Ulid,
         token_auth: None,
         mtls_auth: Some(MtlsAuthConfig {
             ca_bundle_path: "/tmp/ca.pem".to_string(),
@@ -123,6 +128,7 @@ fn config_rejects_mtls_without_client_cert_material_when_required() {
             client_cert_path: None,
             client_key_path: None,
         }),
+        api_key_auth: None,
     });
     let err = config.validate().expect_err("required mtls client cert paths must be provided");
     assert_eq!(err.machine_code, crate::error::code::SECURITY_AUTH_REQUIRED);
@@ -137,8 +143,21 @@ fn config_patch_serialization_preserves_absent_vs_null() {
         event_sink: None,
         event_stream: None,
         idempotency_ttl_ms: None,
+        per_destination_rate_limit: None,
         redaction: None,
         rpc_backend: None,
+        telemetry_max_points_per_peer: None,
+        announce_interval_secs: None,
+        max_content_bytes: None,
+        max_title_wire_bytes: None,
+        receipt_timeout_ms: None,
+        propagation_receipt_timeout_ms: None,
+        propagation_message_ttl_ms: None,
+        propagation_selection_strategy: None,
+        propagation_node: None,
+        inbound_allowlist: None,
+        inbound_blocklist: None,
+        inbound_contacts_bypass: None,
         extensions: None,
     };
     let absent_json = serde_json::to_value(&absent_patch).expect("serialize absent patch");
@@ -151,8 +170,21 @@ fn config_patch_serialization_preserves_absent_vs_null() {
         event_sink: None,
         event_stream: None,
         idempotency_ttl_ms: None,
+        per_destination_rate_limit: None,
         redaction: None,
         rpc_backend: None,
+        telemetry_max_points_per_peer: None,
+        announce_interval_secs: None,
+        max_content_bytes: None,
+        max_title_wire_bytes: None,
+        receipt_timeout_ms: None,
+        propagation_receipt_timeout_ms: None,
+        propagation_message_ttl_ms: None,
+        propagation_selection_strategy: None,
+        propagation_node: None,
+        inbound_allowlist: None,
+        inbound_blocklist: None,
+        inbound_contacts_bypass: None,
         extensions: None,
     };
     let clear_json = serde_json::to_value(&clear_patch).expect("serialize clear patch");
@@ -227,6 +259,22 @@ fn sdk_config_remote_auth_helpers_apply_valid_security_modes() {
     assert_eq!(mtls.auth_mode, AuthMode::Mtls);
 }
 
+#[test]
+fn sdk_config_api_key_auth_validates_for_both_bind_modes_and_requires_key() {
+    let local = SdkConfig::desktop_full_default().with_api_key_auth("key-a");
+    assert!(local.validate().is_ok());
+    assert_eq!(local.bind_mode, BindMode::LocalOnly);
+    assert_eq!(local.auth_mode, AuthMode::ApiKey);
+
+    let mut remote = local.clone();
+    remote.bind_mode = BindMode::Remote;
+    assert!(remote.validate().is_ok());
+
+    let blank = SdkConfig::desktop_full_default().with_api_key_auth("  ");
+    let err = blank.validate().expect_err("blank api key must fail");
+    assert_eq!(err.machine_code, crate::error::code::SECURITY_AUTH_REQUIRED);
+}
+
 #[test]
 fn sdk_config_store_forward_helpers_apply_policy_mutations() {
     let config = SdkConfig::desktop_full_default()
@@ -278,9 +326,27 @@ fn config_patch_builder_accumulates_mutations() {
         .with_overflow_policy(OverflowPolicy::Block)
         .with_block_timeout_ms(250)
         .with_idempotency_ttl_ms(5_000)
+        .with_per_destination_rate_limit(30)
+        .with_telemetry_max_points_per_peer(64)
+        .with_announce_interval_secs(120)
+        .with_max_content_bytes(4_096)
+        .with_max_title_wire_bytes(128)
+        .with_receipt_timeout_ms(30_000)
+        .with_propagation_receipt_timeout_ms(600_000)
+        .with_propagation_message_ttl_ms(86_400_000)
+        .with_propagation_node(true)
         .with_extension("sdk.ext.sample", serde_json::json!("on"));
     assert!(!patch.is_empty());
     assert_eq!(patch.block_timeout_ms, Some(Some(250)));
     assert_eq!(patch.idempotency_ttl_ms, Some(Some(5_000)));
+    assert_eq!(patch.per_destination_rate_limit, Some(Some(30)));
+    assert_eq!(patch.telemetry_max_points_per_peer, Some(Some(64)));
+    assert_eq!(patch.announce_interval_secs, Some(Some(120)));
+    assert_eq!(patch.max_content_bytes, Some(Some(4_096)));
+    assert_eq!(patch.max_title_wire_bytes, Some(Some(128)));
+    assert_eq!(patch.receipt_timeout_ms, Some(Some(30_000)));
+    assert_eq!(patch.propagation_receipt_timeout_ms, Some(Some(600_000)));
+    assert_eq!(patch.propagation_message_ttl_ms, Some(Some(86_400_000)));
+    assert_eq!(patch.propagation_node, Some(Some(true)));
     assert!(patch.extensions.as_ref().and_then(Option::as_ref).is_some());
 }
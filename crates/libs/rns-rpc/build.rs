@@ -0,0 +1,59 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=LXMF_GIT_HASH");
+
+    println!("cargo:rustc-env=RNS_RPC_FEATURES={}", enabled_features().join(","));
+
+    if let Some(hash) = git_hash() {
+        println!("cargo:rustc-env=LXMF_GIT_HASH={hash}");
+    }
+}
+
+// Cargo exposes every enabled feature of this crate as `CARGO_FEATURE_<NAME>`,
+// so the reported list follows the manifest instead of a hand-kept copy.
+fn enabled_features() -> Vec<String> {
+    let mut features = env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|name| name.to_ascii_lowercase().replace('_', "-"))
+        })
+        .collect::<Vec<_>>();
+    features.sort();
+    features
+}
+
+fn git_hash() -> Option<String> {
+    // Packagers building from a tarball can pin the hash explicitly.
+    if let Ok(hash) = env::var("LXMF_GIT_HASH") {
+        let hash = hash.trim().to_string();
+        return (!hash.is_empty()).then_some(hash);
+    }
+
+    if let Some(git_dir) = git_dir() {
+        println!("cargo:rerun-if-changed={}", git_dir.join("HEAD").display());
+        if let Some(reference) = head_reference(&git_dir) {
+            println!("cargo:rerun-if-changed={}", git_dir.join(reference).display());
+        }
+    }
+
+    let output = Command::new("git").args(["rev-parse", "--short=12", "HEAD"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let hash = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!hash.is_empty()).then_some(hash)
+}
+
+fn git_dir() -> Option<PathBuf> {
+    let manifest_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR")?);
+    manifest_dir.ancestors().map(|dir| dir.join(".git")).find(|dir| dir.join("HEAD").is_file())
+}
+
+fn head_reference(git_dir: &Path) -> Option<String> {
+    let head = std::fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    head.trim().strip_prefix("ref: ").map(str::to_string)
+}
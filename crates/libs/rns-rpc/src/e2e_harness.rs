@@ -30,6 +30,15 @@ pub fn parse_rpc_frame(bytes: &[u8]) -> io::Result<crate::rpc::RpcResponse> {
     crate::rpc::codec::decode_frame(bytes)
 }
 
+/// Frames several requests as one `POST /rpc` batch body.
+pub fn build_rpc_batch_frame(requests: &[crate::rpc::RpcRequest]) -> io::Result<Vec<u8>> {
+    crate::rpc::codec::encode_frame(&requests)
+}
+
+pub fn parse_rpc_batch_frame(bytes: &[u8]) -> io::Result<Vec<crate::rpc::RpcResponse>> {
+    crate::rpc::codec::decode_frame(bytes)
+}
+
 pub fn build_http_post(path: &str, host: &str, body: &[u8]) -> Vec<u8> {
     let mut request = Vec::new();
     request.extend_from_slice(format!("POST {} HTTP/1.1\r\n", path).as_bytes());
@@ -42,6 +51,15 @@ pub fn build_http_post(path: &str, host: &str, body: &[u8]) -> Vec<u8> {
 }
 
 pub fn parse_http_response_body(response: &[u8]) -> io::Result<Vec<u8>> {
+    parse_http_response_body_with_limit(response, usize::MAX)
+}
+
+/// Extracts the response body and undoes a gzip or deflate `Content-Encoding`,
+/// rejecting bodies that decode to more than `max_body_bytes`.
+pub fn parse_http_response_body_with_limit(
+    response: &[u8],
+    max_body_bytes: usize,
+) -> io::Result<Vec<u8>> {
     let header_end = crate::rpc::http::find_header_end(response)
         .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing headers"))?;
     let headers = &response[..header_end];
@@ -51,7 +69,8 @@ pub fn parse_http_response_body(response: &[u8]) -> io::Result<Vec<u8>> {
     if response.len() < body_start + content_length {
         return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "response body incomplete"));
     }
-    Ok(response[body_start..body_start + content_length].to_vec())
+    let body = &response[body_start..body_start + content_length];
+    crate::rpc::http::decode_response_body(headers, body, max_body_bytes)
 }
 
 pub fn build_daemon_args(
@@ -133,3 +152,252 @@ pub fn peer_present(response: &crate::rpc::RpcResponse, peer: &str) -> bool {
     };
     peers.iter().any(|entry| entry.get("peer").and_then(|value| value.as_str()) == Some(peer))
 }
+
+/// Outbound bridge that queues delivered records for a [`TwoNodeHarness`]
+/// to hand over to the peer node on the next pump.
+struct LoopbackBridge {
+    outbox: std::sync::Arc<std::sync::Mutex<std::collections::VecDeque<crate::MessageRecord>>>,
+}
+
+impl crate::rpc::OutboundBridge for LoopbackBridge {
+    fn deliver(
+        &self,
+        record: &crate::MessageRecord,
+        _options: &crate::rpc::OutboundDeliveryOptions,
+    ) -> io::Result<()> {
+        self.outbox.lock().expect("loopback outbox mutex poisoned").push_back(record.clone());
+        Ok(())
+    }
+}
+
+/// Two in-memory runtimes wired to each other through a loopback transport.
+///
+/// Outbound records are queued by each node's bridge and handed to the other
+/// node by [`TwoNodeHarness::pump`], which the `wait_for_*` helpers call while
+/// polling. Once the peer has accepted a record, the loopback marks it
+/// `delivered` on the sending node, standing in for the transport's LXMF
+/// delivery proof.
+pub struct TwoNodeHarness {
+    node_a: crate::rpc::RpcDaemon,
+    node_b: crate::rpc::RpcDaemon,
+    outbox_a: std::sync::Arc<std::sync::Mutex<std::collections::VecDeque<crate::MessageRecord>>>,
+    outbox_b: std::sync::Arc<std::sync::Mutex<std::collections::VecDeque<crate::MessageRecord>>>,
+    identity_a: String,
+    identity_b: String,
+    next_request_id: std::cell::Cell<u64>,
+}
+
+impl TwoNodeHarness {
+    pub fn new() -> io::Result<Self> {
+        Self::with_identities("node-a", "node-b")
+    }
+
+    pub fn with_identities(identity_a: &str, identity_b: &str) -> io::Result<Self> {
+        let outbox_a = std::sync::Arc::default();
+        let outbox_b = std::sync::Arc::default();
+        let node_a = crate::rpc::RpcDaemon::with_store_and_bridge(
+            crate::MessagesStore::in_memory().map_err(io::Error::other)?,
+            identity_a.to_string(),
+            std::sync::Arc::new(LoopbackBridge { outbox: std::sync::Arc::clone(&outbox_a) }),
+        );
+        let node_b = crate::rpc::RpcDaemon::with_store_and_bridge(
+            crate::MessagesStore::in_memory().map_err(io::Error::other)?,
+            identity_b.to_string(),
+            std::sync::Arc::new(LoopbackBridge { outbox: std::sync::Arc::clone(&outbox_b) }),
+        );
+        let harness = Self {
+            node_a,
+            node_b,
+            outbox_a,
+            outbox_b,
+            identity_a: identity_a.to_string(),
+            identity_b: identity_b.to_string(),
+            next_request_id: std::cell::Cell::new(1),
+        };
+        Ok(harness)
+    }
+
+    pub fn node_a(&self) -> &crate::rpc::RpcDaemon {
+        &self.node_a
+    }
+
+    pub fn node_b(&self) -> &crate::rpc::RpcDaemon {
+        &self.node_b
+    }
+
+    pub fn identity_a(&self) -> &str {
+        &self.identity_a
+    }
+
+    pub fn identity_b(&self) -> &str {
+        &self.identity_b
+    }
+
+    /// Sends `content` from node A to node B.
+    pub fn send_from_a(
+        &self,
+        message_id: &str,
+        content: &str,
+    ) -> io::Result<crate::rpc::RpcResponse> {
+        self.send(&self.node_a, &self.identity_a, &self.identity_b, message_id, content)
+    }
+
+    /// Sends `content` from node B to node A.
+    pub fn send_from_b(
+        &self,
+        message_id: &str,
+        content: &str,
+    ) -> io::Result<crate::rpc::RpcResponse> {
+        self.send(&self.node_b, &self.identity_b, &self.identity_a, message_id, content)
+    }
+
+    /// Hands queued outbound records to the peer node until both outboxes are
+    /// empty, returning how many records were transferred.
+    pub fn pump(&self) -> io::Result<usize> {
+        let mut transferred = 0;
+        loop {
+            let from_a = self.outbox_a.lock().expect("loopback outbox mutex poisoned").pop_front();
+            let from_b = self.outbox_b.lock().expect("loopback outbox mutex poisoned").pop_front();
+            if from_a.is_none() && from_b.is_none() {
+                return Ok(transferred);
+            }
+            if let Some(record) = from_a {
+                self.transfer(&self.node_a, &self.node_b, record)?;
+                transferred += 1;
+            }
+            if let Some(record) = from_b {
+                self.transfer(&self.node_b, &self.node_a, record)?;
+                transferred += 1;
+            }
+        }
+    }
+
+    /// Waits until `node` holds an inbound message with `message_id`.
+    pub fn wait_for_inbound(
+        &self,
+        node: &crate::rpc::RpcDaemon,
+        message_id: &str,
+        timeout: std::time::Duration,
+    ) -> io::Result<bool> {
+        self.wait_for_message(node, message_id, timeout, |message| {
+            message.get("direction").and_then(serde_json::Value::as_str) == Some("in")
+        })
+    }
+
+    /// Waits until the message with `message_id` on `node` reaches `delivered`.
+    pub fn wait_for_delivered(
+        &self,
+        node: &crate::rpc::RpcDaemon,
+        message_id: &str,
+        timeout: std::time::Duration,
+    ) -> io::Result<bool> {
+        self.wait_for_message(node, message_id, timeout, |message| {
+            message.get("receipt_status").and_then(serde_json::Value::as_str) == Some("delivered")
+        })
+    }
+
+    fn wait_for_message(
+        &self,
+        node: &crate::rpc::RpcDaemon,
+        message_id: &str,
+        timeout: std::time::Duration,
+        ready: impl Fn(&serde_json::Value) -> bool,
+    ) -> io::Result<bool> {
+        let deadline = std::time::Instant::now() + timeout;
+        loop {
+            self.pump()?;
+            let response = self.call(node, "list_messages", serde_json::Value::Null)?;
+            let found = response
+                .result
+                .as_ref()
+                .and_then(|result| result.get("messages"))
+                .and_then(serde_json::Value::as_array)
+                .is_some_and(|messages| {
+                    messages
+                        .iter()
+                        .filter(|message| {
+                            message.get("id").and_then(serde_json::Value::as_str)
+                                == Some(message_id)
+                        })
+                        .any(&ready)
+                });
+            if found {
+                return Ok(true);
+            }
+            if std::time::Instant::now() >= deadline {
+                return Ok(false);
+            }
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+    }
+
+    fn transfer(
+        &self,
+        sender: &crate::rpc::RpcDaemon,
+        peer: &crate::rpc::RpcDaemon,
+        record: crate::MessageRecord,
+    ) -> io::Result<()> {
+        let message_id = record.id.clone();
+        let mut inbound = record;
+        inbound.direction = "in".into();
+        inbound.receipt_status = None;
+        peer.accept_inbound(inbound)?;
+        self.call(
+            sender,
+            "record_receipt",
+            serde_json::json!({ "message_id": message_id, "status": "delivered" }),
+        )?;
+        Ok(())
+    }
+
+    fn send(
+        &self,
+        node: &crate::rpc::RpcDaemon,
+        source: &str,
+        destination: &str,
+        message_id: &str,
+        content: &str,
+    ) -> io::Result<crate::rpc::RpcResponse> {
+        self.call(node, "send_message", build_send_params(message_id, source, destination, content))
+    }
+
+    fn call(
+        &self,
+        node: &crate::rpc::RpcDaemon,
+        method: &str,
+        params: serde_json::Value,
+    ) -> io::Result<crate::rpc::RpcResponse> {
+        let id = self.next_request_id.get();
+        self.next_request_id.set(id + 1);
+        node.handle_rpc(crate::rpc::RpcRequest { id, method: method.into(), params: Some(params) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn two_node_harness_delivers_and_proves_in_both_directions() {
+        let harness = TwoNodeHarness::new().expect("harness");
+
+        let sent = harness.send_from_a("a-to-b", "hello b").expect("send from a");
+        assert!(sent.error.is_none());
+        assert!(harness
+            .wait_for_inbound(harness.node_b(), "a-to-b", Duration::from_secs(1))
+            .expect("wait for inbound"));
+        assert!(harness
+            .wait_for_delivered(harness.node_a(), "a-to-b", Duration::from_secs(1))
+            .expect("wait for receipt"));
+
+        harness.send_from_b("b-to-a", "hello a").expect("send from b");
+        assert!(harness
+            .wait_for_inbound(harness.node_a(), "b-to-a", Duration::from_secs(1))
+            .expect("wait for inbound"));
+        assert!(harness
+            .wait_for_delivered(harness.node_b(), "b-to-a", Duration::from_secs(1))
+            .expect("wait for receipt"));
+        assert_eq!(harness.pump().expect("pump"), 0);
+    }
+}
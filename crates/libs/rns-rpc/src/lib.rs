@@ -7,8 +7,14 @@ mod transport;
 
 pub use rpc::http;
 pub use rpc::{
-    AnnounceBridge, DeliveryPolicy, DeliveryTraceEntry, InterfaceRecord, OutboundBridge,
-    OutboundDeliveryOptions, PeerRecord, PropagationState, RpcDaemon, RpcError, RpcEvent,
-    RpcRequest, RpcResponse, StampPolicy, TicketRecord,
+    generate_message_id, AnnounceBridge, DeliveryPolicy, DisplayNameUpdate, IdentityRotation,
+    InboundPendingProbe, InterfaceLinkState, InterfaceRecord, InterfaceTraffic, LengthLimitMode,
+    MessageBundle, OutboundBridge, OutboundDeliveryOptions, OutboundPlan, PeerBundle,
+    PeerPingOutcome, PeerPingRequest, PropagationState, PropagationSyncRequest, RpcDaemon,
+    RpcError, RpcEvent, RpcRequest, RpcResponse, StampCostMode, StampPolicy, StampTaskHandle,
+    TicketRecord,
+};
+pub use storage::messages::{
+    reply_to_from_fields, AnnounceRecord, DeliveryTraceEntry, IdempotencyRecord, MessageRecord,
+    MessageSearchFilter, MessagesStore, PeerIdentityRecord, PeerRecord, ScheduledSendRecord,
 };
-pub use storage::messages::{AnnounceRecord, MessageRecord, MessagesStore};
@@ -68,6 +68,99 @@ fn delivery_reason_code(status: &str) -> Option<&'static str> {
     None
 }
 
+/// Aggregates delivery outcomes for traces whose latest transition falls at or after
+/// `window_start_ms`. Latencies are measured between millisecond trace timestamps.
+fn aggregate_delivery_metrics<'a>(
+    traces: impl IntoIterator<Item = &'a [DeliveryTraceEntry]>,
+    window_start_ms: i64,
+) -> JsonValue {
+    #[derive(Default)]
+    struct MethodCounts {
+        total: u64,
+        delivered: u64,
+        failed: u64,
+    }
+
+    let mut total = 0_u64;
+    let mut delivered = 0_u64;
+    let mut failed = 0_u64;
+    let mut cancelled = 0_u64;
+    let mut latencies_ms = Vec::new();
+    let mut by_method: BTreeMap<String, MethodCounts> = BTreeMap::new();
+
+    for trace in traces {
+        let (Some(first), Some(last)) = (trace.first(), trace.last()) else {
+            continue;
+        };
+        if last.timestamp_ms < window_start_ms {
+            continue;
+        }
+        let method = trace
+            .iter()
+            .find_map(|entry| entry.status.trim().strip_prefix("sent:"))
+            .map(|method| method.trim().to_ascii_lowercase())
+            .filter(|method| !method.is_empty())
+            .unwrap_or_else(|| "unknown".to_string());
+        let counts = by_method.entry(method).or_default();
+        total += 1;
+        counts.total += 1;
+
+        let outcome = last.status.trim().to_ascii_lowercase();
+        if outcome == "delivered" {
+            delivered += 1;
+            counts.delivered += 1;
+            let latency_ms = last.timestamp_ms.saturating_sub(first.timestamp_ms).max(0);
+            latencies_ms.push(latency_ms as u64);
+        } else if outcome.starts_with("failed")
+            || outcome.starts_with("rejected")
+            || outcome == "expired"
+        {
+            failed += 1;
+            counts.failed += 1;
+        } else if outcome == "cancelled" {
+            cancelled += 1;
+        }
+    }
+
+    let settled = delivered + failed;
+    let success_rate = (settled > 0).then(|| delivered as f64 / settled as f64);
+    latencies_ms.sort_unstable();
+    let avg_latency_ms = (!latencies_ms.is_empty())
+        .then(|| latencies_ms.iter().sum::<u64>() as f64 / latencies_ms.len() as f64);
+    let p95_latency_ms = (!latencies_ms.is_empty()).then(|| {
+        let rank = (latencies_ms.len() * 95).div_ceil(100);
+        latencies_ms[rank.saturating_sub(1)]
+    });
+    let by_method = by_method
+        .into_iter()
+        .map(|(method, counts)| {
+            (
+                method,
+                json!({
+                    "total": counts.total,
+                    "delivered": counts.delivered,
+                    "failed": counts.failed,
+                }),
+            )
+        })
+        .collect::<JsonMap<String, JsonValue>>();
+
+    json!({
+        "total": total,
+        "delivered": delivered,
+        "failed": failed,
+        "cancelled": cancelled,
+        "pending": total - delivered - failed - cancelled,
+        "success_rate": success_rate,
+        "latency_ms": {
+            "avg": avg_latency_ms,
+            "p95": p95_latency_ms,
+            "samples": latencies_ms.len(),
+        },
+        "by_method": by_method,
+    })
+}
+
 fn merge_json_patch(target: &mut JsonValue, patch: &JsonValue) {
     let JsonValue::Object(patch_map) = patch else {
         *target = patch.clone();
@@ -24,12 +24,35 @@ impl RpcDaemon {
             "status" => Ok(RpcResponse {
                 id: request.id,
                 result: Some(json!({
-                    "identity_hash": self.identity_hash,
+                    "identity_hash": self.current_identity_hash(),
                     "delivery_destination_hash": self.local_delivery_hash(),
                     "running": true
                 })),
                 error: None,
             }),
+            "whoami" if self.identity_hash.trim().is_empty() => Ok(self.sdk_error_response(
+                request.id,
+                "SDK_RUNTIME_INVALID_STATE",
+                "runtime identity is not ready; start the runtime first",
+            )),
+            "whoami" => {
+                let display_name = self
+                    .local_display_name
+                    .lock()
+                    .expect("local_display_name mutex poisoned")
+                    .clone();
+                let profile = self.sdk_profile.lock().expect("sdk_profile mutex poisoned").clone();
+                Ok(RpcResponse {
+                    id: request.id,
+                    result: Some(json!({
+                        "identity_hash": self.current_identity_hash(),
+                        "delivery_destination_hash": self.local_delivery_hash(),
+                        "display_name": display_name,
+                        "profile": profile,
+                    })),
+                    error: None,
+                })
+            }
             "sdk_negotiate_v2" => self.handle_sdk_negotiate_v2(request),
             "daemon_status_ex" => {
                 let peer_count = self.peers.lock().expect("peers mutex poisoned").len();
@@ -45,7 +68,7 @@ impl RpcDaemon {
                 Ok(RpcResponse {
                     id: request.id,
                     result: Some(json!({
-                        "identity_hash": self.identity_hash,
+                        "identity_hash": self.current_identity_hash(),
                         "delivery_destination_hash": self.local_delivery_hash(),
                         "running": true,
                         "peer_count": peer_count,
@@ -60,6 +83,20 @@ impl RpcDaemon {
                     error: None,
                 })
             }
+            "version" | "version_info" => Ok(RpcResponse {
+                id: request.id,
+                result: Some(json!({
+                    "contract_release": CONTRACT_RELEASE,
+                    "schema_namespace": SCHEMA_NAMESPACE,
+                    "supported_contract_versions": SUPPORTED_CONTRACT_VERSIONS,
+                    "build_profile": if cfg!(debug_assertions) { "debug" } else { "release" },
+                    "crate_version": env!("CARGO_PKG_VERSION"),
+                    "git_hash": option_env!("LXMF_GIT_HASH"),
+                    "features": Self::compiled_features(),
+                })),
+                error: None,
+            }),
+            "sdk_send_batch_v2" => self.handle_sdk_send_batch_v2(request),
             "sdk_snapshot_v2" => self.handle_sdk_snapshot_v2(request),
             "sdk_status_v2" => self.handle_sdk_status_v2(request),
             "sdk_configure_v2" => self.handle_sdk_configure_v2(request),
@@ -131,6 +168,7 @@ impl RpcDaemon {
                 Ok(response)
             }
             Err(error) if is_sdk_method && error.kind() == std::io::ErrorKind::InvalidInput => {
+                let details = validation_error_details(&error);
                 let message = error.to_string();
                 let normalized = message.to_ascii_lowercase();
                 let (code, message) = if normalized.contains("unknown field") {
@@ -138,7 +176,10 @@ impl RpcDaemon {
                 } else {
                     ("SDK_VALIDATION_INVALID_ARGUMENT", message.as_str())
                 };
-                let mapped = self.sdk_error_response(request_id, code, message);
+                let mut mapped = self.sdk_error_response(request_id, code, message);
+                if let (Some(error), Some(details)) = (mapped.error.as_mut(), details) {
+                    error.details = Some(Box::new(details));
+                }
                 let elapsed_ms = metrics_started.elapsed().as_millis() as u64;
                 self.metrics_record_rpc_response(method.as_str(), elapsed_ms, &mapped);
                 if let Some(trace_id) = lifecycle_trace_id.as_deref() {
@@ -179,15 +220,42 @@ impl RpcDaemon {
             }
         }
     }
+    /// Returns the trace for `message_id`, falling back to the persisted rows
+    /// once the in-memory copy has been evicted or lost to a restart.
+    fn delivery_trace_entries(&self, message_id: &str) -> Result<Vec<DeliveryTraceEntry>, std::io::Error> {
+        let cached = self
+            .delivery_traces
+            .lock()
+            .expect("delivery traces mutex poisoned")
+            .get(message_id)
+            .cloned();
+        match cached {
+            Some(trace) => Ok(trace),
+            None => self.store.get_delivery_trace(message_id).map_err(std::io::Error::other),
+        }
+    }
+
     fn append_delivery_trace(&self, message_id: &str, status: String) {
-        const MAX_DELIVERY_TRACE_ENTRIES: usize = 32;
         const MAX_TRACKED_MESSAGE_TRACES: usize = 2048;
 
-        let timestamp = now_i64();
+        let timestamp_ms = i64::try_from(now_millis_u64()).unwrap_or(i64::MAX);
         let reason_code = delivery_reason_code(&status).map(ToOwned::to_owned);
+        let detail = delivery_trace_detail(&status);
+        let trace_entry = DeliveryTraceEntry {
+            status,
+            timestamp: timestamp_ms / 1000,
+            timestamp_ms,
+            reason_code,
+            detail,
+        };
+        let _ = self.store.append_delivery_trace(
+            message_id,
+            &trace_entry,
+            MAX_DELIVERY_TRACE_ENTRIES,
+        );
         let mut guard = self.delivery_traces.lock().expect("delivery traces mutex poisoned");
         let entry = guard.entry(message_id.to_string()).or_default();
-        entry.push(DeliveryTraceEntry { status, timestamp, reason_code });
+        entry.push(trace_entry);
         if entry.len() > MAX_DELIVERY_TRACE_ENTRIES {
             let drain_count = entry.len().saturating_sub(MAX_DELIVERY_TRACE_ENTRIES);
             entry.drain(0..drain_count);
@@ -1,14 +1,86 @@
 impl RpcDaemon {
+    /// Forgets peers last seen before `cutoff_ts` (seconds), in memory, in the
+    /// persisted announces, in the imported peer identities and in the
+    /// transport's identity cache. Selected propagation nodes are always kept.
+    fn prune_peers_before(&self, cutoff_ts: i64) -> Result<Vec<String>, std::io::Error> {
+        let mut protected = vec![self
+            .outbound_propagation_node
+            .lock()
+            .expect("propagation node mutex poisoned")
+            .clone()];
+        protected.push(
+            self.propagation_state
+                .lock()
+                .expect("propagation mutex poisoned")
+                .selected_node
+                .clone(),
+        );
+        let is_protected = |peer: &str| protected.iter().flatten().any(|selected| selected == peer);
+
+        let mut stale =
+            self.store.stale_announce_peers(cutoff_ts).map_err(std::io::Error::other)?;
+        let mut peers = self.peers.lock().expect("peers mutex poisoned");
+        stale.extend(
+            peers
+                .values()
+                .filter(|record| record.last_seen < cutoff_ts)
+                .map(|record| record.peer.clone()),
+        );
+        stale.sort_unstable();
+        stale.dedup();
+        stale.retain(|peer| !is_protected(peer));
+        for peer in &stale {
+            peers.remove(peer);
+            self.store.delete_peer_announces(peer).map_err(std::io::Error::other)?;
+            self.store.delete_peer_identity(peer).map_err(std::io::Error::other)?;
+        }
+        drop(peers);
+        if let (Some(bridge), false) = (self.outbound_bridge.as_ref(), stale.is_empty()) {
+            bridge.forget_peers(&stale)?;
+        }
+        Ok(stale)
+    }
+
     fn handle_rpc_legacy_clear(&self, request: RpcRequest) -> Result<RpcResponse, std::io::Error> {
         match request.method.as_str() {
             "clear_messages" => {
                 self.store.clear_messages().map_err(std::io::Error::other)?;
+                *self.inbound_dedup.lock().expect("inbound_dedup mutex poisoned") =
+                    InboundDedupWindow::default();
                 Ok(RpcResponse {
                     id: request.id,
                     result: Some(json!({ "cleared": "messages" })),
                     error: None,
                 })
             }
+            "prune_messages" => {
+                let params = request.params.ok_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::InvalidInput, "missing params")
+                })?;
+                let parsed: PruneMessagesParams = serde_json::from_value(params)
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+                let cutoff_ts_ms = now_millis_u64().saturating_sub(parsed.older_than_ms);
+                let pruned = self
+                    .store
+                    .prune_delivered_before(cutoff_ts_ms)
+                    .map_err(std::io::Error::other)?;
+                {
+                    let mut traces =
+                        self.delivery_traces.lock().expect("delivery traces mutex poisoned");
+                    for message_id in &pruned {
+                        traces.remove(message_id);
+                    }
+                }
+                Ok(RpcResponse {
+                    id: request.id,
+                    result: Some(json!({
+                        "pruned": pruned.len(),
+                        "cutoff_ts_ms": cutoff_ts_ms,
+                        "meta": self.response_meta(),
+                    })),
+                    error: None,
+                })
+            }
             "clear_resources" => {
                 let _domain_state_guard = self.lock_and_restore_sdk_domain_snapshot()?;
                 self.sdk_attachments.lock().expect("sdk_attachments mutex poisoned").clear();
@@ -51,16 +123,40 @@ impl RpcDaemon {
                     guard.clear();
                 }
                 self.store.clear_announces().map_err(std::io::Error::other)?;
+                self.store.clear_peer_identities().map_err(std::io::Error::other)?;
                 Ok(RpcResponse {
                     id: request.id,
                     result: Some(json!({ "cleared": "peers" })),
                     error: None,
                 })
             }
+            "peers_prune" => {
+                let params = request.params.ok_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::InvalidInput, "missing params")
+                })?;
+                let parsed: PeersPruneParams = serde_json::from_value(params)
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+                let cutoff_ts = now_i64()
+                    .saturating_sub(i64::try_from(parsed.max_age_secs).unwrap_or(i64::MAX));
+                let pruned = self.prune_peers_before(cutoff_ts)?;
+                Ok(RpcResponse {
+                    id: request.id,
+                    result: Some(json!({
+                        "pruned": pruned.len(),
+                        "peers": pruned,
+                        "cutoff_ts": cutoff_ts,
+                        "meta": self.response_meta(),
+                    })),
+                    error: None,
+                })
+            }
             "clear_all" => {
                 let _domain_state_guard = self.lock_and_restore_sdk_domain_snapshot()?;
                 self.store.clear_messages().map_err(std::io::Error::other)?;
+                *self.inbound_dedup.lock().expect("inbound_dedup mutex poisoned") =
+                    InboundDedupWindow::default();
                 self.store.clear_announces().map_err(std::io::Error::other)?;
+                self.store.clear_peer_identities().map_err(std::io::Error::other)?;
                 {
                     let mut guard = self.peers.lock().expect("peers mutex poisoned");
                     guard.clear();
@@ -101,6 +197,7 @@ impl RpcDaemon {
                     .lock()
                     .expect("sdk_telemetry_points mutex poisoned")
                     .clear();
+                self.store.clear_inbound_telemetry().map_err(std::io::Error::other)?;
                 self.sdk_remote_commands
                     .lock()
                     .expect("sdk_remote_commands mutex poisoned")
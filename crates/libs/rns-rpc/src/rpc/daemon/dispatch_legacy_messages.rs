@@ -12,6 +12,167 @@ impl RpcDaemon {
                     error: None,
                 })
             }
+            "messages_search" => {
+                let parsed = request
+                    .params
+                    .map(serde_json::from_value::<MessagesSearchParams>)
+                    .transpose()
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?
+                    .unwrap_or_default();
+                let limit = parsed.limit.unwrap_or(100).clamp(1, 1000);
+                let before = match parse_announce_cursor(parsed.cursor.as_deref()) {
+                    Some((Some(timestamp), Some(id))) => Some((timestamp, id)),
+                    Some((Some(timestamp), None)) => Some((timestamp, String::new())),
+                    _ => None,
+                };
+                let filter = MessageSearchFilter {
+                    query: parsed.query,
+                    source: parsed.source,
+                    destination: parsed.destination,
+                    from_ts_ms: parsed.from_ts_ms,
+                    to_ts_ms: parsed.to_ts_ms,
+                    thread: parsed.thread,
+                    peer: None,
+                };
+                let items = self
+                    .store
+                    .search_messages(
+                        &filter,
+                        before.as_ref().map(|(timestamp, id)| (*timestamp, id.as_str())),
+                        limit,
+                    )
+                    .map_err(std::io::Error::other)?;
+                let next_cursor = if items.len() >= limit {
+                    items.last().map(|record| format!("{}:{}", record.timestamp, record.id))
+                } else {
+                    None
+                };
+                Ok(RpcResponse {
+                    id: request.id,
+                    result: Some(json!({
+                        "messages": items,
+                        "next_cursor": next_cursor,
+                        "meta": self.response_meta(),
+                    })),
+                    error: None,
+                })
+            }
+            "attachment_get" => {
+                let params = request.params.ok_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::InvalidInput, "missing params")
+                })?;
+                let parsed: AttachmentGetParams = serde_json::from_value(params)
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+                let message_id = parsed.message_id.trim();
+                let message = self.store.get_message(message_id).map_err(std::io::Error::other)?;
+                let Some(message) = message else {
+                    return Ok(self.sdk_error_response(
+                        request.id,
+                        "SDK_VALIDATION_INVALID_ARGUMENT",
+                        &format!("unknown message_id '{message_id}'"),
+                    ));
+                };
+                let mut attachments = message_attachment_refs(message.fields.as_ref());
+                if parsed.index >= attachments.len() {
+                    let (index, count) = (parsed.index, attachments.len());
+                    let mut error = RpcError::new(
+                        "SDK_VALIDATION_INVALID_ARGUMENT",
+                        format!(
+                            "attachment index {index} is out of range; \
+                             message has {count} attachments"
+                        ),
+                    );
+                    let mut details = JsonMap::new();
+                    details.insert("message_id".to_string(), json!(message_id));
+                    details.insert("index".to_string(), json!(parsed.index));
+                    details.insert("attachment_count".to_string(), json!(count));
+                    error.details = Some(Box::new(details));
+                    return Ok(RpcResponse { id: request.id, result: None, error: Some(error) });
+                }
+                let attachment = match attachments.swap_remove(parsed.index) {
+                    MessageAttachmentRef::Inline { name, bytes } => {
+                        let media = message
+                            .fields
+                            .as_ref()
+                            .and_then(|fields| fields.get(FIELD_ATTACHMENT_MEDIA))
+                            .and_then(JsonValue::as_array)
+                            .and_then(|media| {
+                                media.iter().find(|entry| {
+                                    entry.get("index").and_then(JsonValue::as_u64)
+                                        == Some(parsed.index as u64)
+                                })
+                            })
+                            .cloned()
+                            .unwrap_or_else(|| attachment_media_json(parsed.index, &name, &bytes));
+                        // The checksum recorded when the message was stored,
+                        // so a payload altered since then fails verification.
+                        let checksum = media
+                            .get("checksum_sha256")
+                            .and_then(JsonValue::as_str)
+                            .map(str::to_string)
+                            .unwrap_or_else(|| encode_hex(Sha256::digest(bytes.as_slice())));
+                        json!({
+                            "message_id": message_id,
+                            "index": parsed.index,
+                            "name": name,
+                            "content_type": media
+                                .get("content_type")
+                                .and_then(JsonValue::as_str)
+                                .unwrap_or(OCTET_STREAM),
+                            "size_bytes": bytes.len() as u64,
+                            "checksum_sha256": checksum,
+                            "bytes_base64": BASE64_STANDARD.encode(bytes.as_slice()),
+                            "extensions": media
+                                .get("extensions")
+                                .cloned()
+                                .unwrap_or_else(|| json!({})),
+                        })
+                    }
+                    MessageAttachmentRef::Stored { name, attachment_id } => {
+                        let _domain_state_guard = self.lock_and_restore_sdk_domain_snapshot()?;
+                        let record = self
+                            .sdk_attachments
+                            .lock()
+                            .expect("sdk_attachments mutex poisoned")
+                            .get(attachment_id.as_str())
+                            .cloned();
+                        let payload = self
+                            .sdk_attachment_payloads
+                            .lock()
+                            .expect("sdk_attachment_payloads mutex poisoned")
+                            .get(attachment_id.as_str())
+                            .cloned();
+                        let (Some(record), Some(payload)) = (record, payload) else {
+                            return Ok(self.sdk_error_response(
+                                request.id,
+                                "SDK_RUNTIME_NOT_FOUND",
+                                &format!(
+                                    "attachment '{attachment_id}' referenced by message \
+                                     '{message_id}' is not in the attachment store"
+                                ),
+                            ));
+                        };
+                        json!({
+                            "message_id": message_id,
+                            "index": parsed.index,
+                            "name": name.unwrap_or(record.name),
+                            "content_type": record.content_type,
+                            "size_bytes": record.byte_len,
+                            "checksum_sha256": record.checksum_sha256,
+                            "bytes_base64": payload,
+                            "extensions": record.extensions,
+                        })
+                    }
+                };
+                Ok(RpcResponse {
+                    id: request.id,
+                    result: Some(json!({
+                        "attachment": attachment,
+                        "meta": self.response_meta(),
+                    })),
+                    error: None,
+                })
+            }
             "sdk_poll_events_v2" => self.handle_sdk_poll_events_v2(request),
             "list_announces" => {
                 let parsed = request
@@ -45,6 +206,12 @@ impl RpcDaemon {
                 })
             }
             "list_peers" => {
+                let parsed = request
+                    .params
+                    .map(serde_json::from_value::<ListPeersParams>)
+                    .transpose()
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?
+                    .unwrap_or_default();
                 let mut peers = self
                     .peers
                     .lock()
@@ -55,6 +222,14 @@ impl RpcDaemon {
                 peers.sort_by(|a, b| {
                     b.last_seen.cmp(&a.last_seen).then_with(|| a.peer.cmp(&b.peer))
                 });
+                let display_names = disambiguated_peer_names(
+                    &peers,
+                    parsed.name_suffix_len.unwrap_or(DEFAULT_PEER_NAME_SUFFIX_LEN),
+                );
+                let peers = peers
+                    .iter()
+                    .map(|peer| peer_display_view(peer, &display_names))
+                    .collect::<Vec<_>>();
                 Ok(RpcResponse {
                     id: request.id,
                     result: Some(json!({
@@ -64,8 +239,243 @@ impl RpcDaemon {
                     error: None,
                 })
             }
+            "messages_export" => {
+                let parsed = request
+                    .params
+                    .map(serde_json::from_value::<MessagesExportParams>)
+                    .transpose()
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?
+                    .unwrap_or_default();
+                if let (Some(from_ts_ms), Some(to_ts_ms)) = (parsed.from_ts_ms, parsed.to_ts_ms) {
+                    if from_ts_ms > to_ts_ms {
+                        return Ok(self.sdk_error_response(
+                            request.id,
+                            "SDK_VALIDATION_INVALID_ARGUMENT",
+                            "from_ts_ms must not be after to_ts_ms",
+                        ));
+                    }
+                }
+                let peer =
+                    parsed.peer.map(|peer| peer.trim().to_string()).filter(|peer| !peer.is_empty());
+                let filter = MessageSearchFilter {
+                    peer: peer.clone(),
+                    from_ts_ms: parsed.from_ts_ms,
+                    to_ts_ms: parsed.to_ts_ms,
+                    ..Default::default()
+                };
+                let limit = parsed.limit.unwrap_or(500).clamp(1, 5000);
+                let after = match parse_announce_cursor(parsed.cursor.as_deref()) {
+                    Some((Some(timestamp), Some(id))) => Some((timestamp, id)),
+                    Some((Some(timestamp), None)) => Some((timestamp, String::new())),
+                    _ => None,
+                };
+                let messages = self
+                    .store
+                    .export_messages(
+                        &filter,
+                        after.as_ref().map(|(timestamp, id)| (*timestamp, id.as_str())),
+                        limit,
+                    )
+                    .map_err(std::io::Error::other)?;
+                let next_cursor = if messages.len() >= limit {
+                    messages.last().map(|record| format!("{}:{}", record.timestamp, record.id))
+                } else {
+                    None
+                };
+                let bundle = MessageBundle {
+                    version: MESSAGE_BUNDLE_VERSION.to_string(),
+                    exported_at: now_i64(),
+                    peer,
+                    from_ts_ms: parsed.from_ts_ms,
+                    to_ts_ms: parsed.to_ts_ms,
+                    messages,
+                    next_cursor,
+                };
+                Ok(RpcResponse {
+                    id: request.id,
+                    result: Some(json!({ "bundle": bundle })),
+                    error: None,
+                })
+            }
+            "peers_export" => {
+                let bundle = self.export_peer_bundle()?;
+                Ok(RpcResponse {
+                    id: request.id,
+                    result: Some(json!({ "bundle": bundle })),
+                    error: None,
+                })
+            }
+            "peers_import" => {
+                let params = request.params.ok_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::InvalidInput, "missing params")
+                })?;
+                let parsed: PeersImportParams = serde_json::from_value(params)
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+                if parsed.bundle.version != PEER_BUNDLE_VERSION {
+                    return Ok(self.sdk_error_response(
+                        request.id,
+                        "SDK_VALIDATION_INVALID_ARGUMENT",
+                        &format!(
+                            "unsupported peer bundle version '{}' (expected '{PEER_BUNDLE_VERSION}')",
+                            parsed.bundle.version
+                        ),
+                    ));
+                }
+                let summary = self.import_peer_bundle(parsed.bundle)?;
+                Ok(RpcResponse { id: request.id, result: Some(summary), error: None })
+            }
+            "peer_describe" => {
+                let params = request.params.ok_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::InvalidInput, "missing params")
+                })?;
+                let parsed: PeerDescribeParams = serde_json::from_value(params)
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+                let peers = self.peers.lock().expect("peers mutex poisoned").clone();
+                let Some(peer) = peers.get(parsed.peer.trim()) else {
+                    return Ok(self.sdk_error_response(
+                        request.id,
+                        "SDK_RUNTIME_NOT_FOUND",
+                        &format!("peer '{}' not found", parsed.peer.trim()),
+                    ));
+                };
+                let display_names = disambiguated_peer_names(
+                    peers.values(),
+                    parsed.name_suffix_len.unwrap_or(DEFAULT_PEER_NAME_SUFFIX_LEN),
+                );
+                let mut view = peer_display_view(peer, &display_names);
+                let name_key = peer.name.as_deref().map(|name| name.trim().to_lowercase());
+                let mut conflicting_peers = peers
+                    .values()
+                    .filter(|other| other.peer != peer.peer)
+                    .filter(|other| {
+                        name_key.as_ref().is_some_and(|key| {
+                            other.name.as_deref().map(|name| name.trim().to_lowercase()).as_ref()
+                                == Some(key)
+                        })
+                    })
+                    .map(|other| other.peer.clone())
+                    .collect::<Vec<_>>();
+                conflicting_peers.sort();
+                view["conflicting_peers"] = json!(conflicting_peers);
+                Ok(RpcResponse {
+                    id: request.id,
+                    result: Some(json!({
+                        "peer": view,
+                        "meta": self.response_meta(),
+                    })),
+                    error: None,
+                })
+            }
+            "peer_ping" => {
+                let parsed = request
+                    .params
+                    .map(serde_json::from_value::<PeerPingParams>)
+                    .transpose()
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?
+                    .unwrap_or_default();
+                if let Some(ping_id) = parsed.ping_id {
+                    let Some(record) = self.peer_ping_record(ping_id.trim()) else {
+                        return Ok(self.sdk_error_response(
+                            request.id,
+                            "SDK_VALIDATION_INVALID_ARGUMENT",
+                            &format!("unknown ping_id '{}'", ping_id.trim()),
+                        ));
+                    };
+                    let mut result = json!(record);
+                    result["meta"] = self.response_meta();
+                    return Ok(RpcResponse { id: request.id, result: Some(result), error: None });
+                }
+                let peer = parsed.peer.as_deref().map(str::trim).unwrap_or_default();
+                if peer.is_empty() {
+                    return Ok(self.sdk_error_response(
+                        request.id,
+                        "SDK_VALIDATION_INVALID_ARGUMENT",
+                        "peer_ping requires peer or ping_id",
+                    ));
+                }
+                let Some(bridge) = self.outbound_bridge.as_ref() else {
+                    return Ok(self.sdk_error_response(
+                        request.id,
+                        "SDK_CAPABILITY_DISABLED",
+                        "peer_ping requires a transport bridge",
+                    ));
+                };
+                let ping = PeerPingRequest {
+                    ping_id: self.next_sdk_domain_id("ping"),
+                    peer: peer.to_string(),
+                    probe: parsed.probe,
+                    timeout_ms: parsed.timeout_ms.unwrap_or(PEER_PING_DEFAULT_TIMEOUT_MS).max(1),
+                };
+                self.start_peer_ping(&ping);
+                if let Err(err) = bridge.ping(&ping) {
+                    self.forget_peer_ping(&ping.ping_id);
+                    let code = if err.kind() == std::io::ErrorKind::Unsupported {
+                        "SDK_CAPABILITY_DISABLED"
+                    } else {
+                        "SDK_VALIDATION_INVALID_ARGUMENT"
+                    };
+                    return Ok(self.sdk_error_response(request.id, code, &err.to_string()));
+                }
+                let mut result = json!(self.peer_ping_record(&ping.ping_id));
+                result["meta"] = self.response_meta();
+                Ok(RpcResponse { id: request.id, result: Some(result), error: None })
+            }
+            "peer_rtt" => {
+                let parsed = request
+                    .params
+                    .map(serde_json::from_value::<PeerRttParams>)
+                    .transpose()
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?
+                    .unwrap_or_default();
+                let mut result = match parsed.peer {
+                    Some(peer) => self.peer_rtt_summary(peer.trim()),
+                    None => {
+                        let mut peers = self
+                            .peers
+                            .lock()
+                            .expect("peers mutex poisoned")
+                            .keys()
+                            .cloned()
+                            .collect::<BTreeSet<_>>();
+                        peers.extend(
+                            self.peer_rtt
+                                .lock()
+                                .expect("peer rtt mutex poisoned")
+                                .samples
+                                .keys()
+                                .cloned(),
+                        );
+                        let rows: Vec<JsonValue> =
+                            peers.iter().map(|peer| self.peer_rtt_summary(peer)).collect();
+                        json!({ "peers": rows })
+                    }
+                };
+                result["meta"] = self.response_meta();
+                Ok(RpcResponse { id: request.id, result: Some(result), error: None })
+            }
             "list_interfaces" => {
                 let interfaces = self.interfaces.lock().expect("interfaces mutex poisoned").clone();
+                let traffic =
+                    self.interface_traffic.lock().expect("interface_traffic mutex poisoned").clone();
+                let now = now_i64().max(0) as u64;
+                let interfaces = interfaces
+                    .into_iter()
+                    .map(|iface| {
+                        let counters = traffic
+                            .get(iface.name.as_deref().unwrap_or(&iface.kind))
+                            .copied()
+                            .unwrap_or_default();
+                        let mut row = json!(iface);
+                        row["bytes_in"] = json!(counters.bytes_in);
+                        row["bytes_out"] = json!(counters.bytes_out);
+                        row["packets_in"] = json!(counters.packets_in);
+                        row["packets_out"] = json!(counters.packets_out);
+                        row["idle_secs"] = json!(counters
+                            .last_activity_secs
+                            .map(|last| now.saturating_sub(last)));
+                        row
+                    })
+                    .collect::<Vec<_>>();
                 Ok(RpcResponse {
                     id: request.id,
                     result: Some(json!({
@@ -75,6 +485,69 @@ impl RpcDaemon {
                     error: None,
                 })
             }
+            "reconnect" => {
+                let parsed = request
+                    .params
+                    .map(serde_json::from_value::<ReconnectParams>)
+                    .transpose()
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?
+                    .unwrap_or_default();
+                let known = self
+                    .interfaces
+                    .lock()
+                    .expect("interfaces mutex poisoned")
+                    .iter()
+                    .filter(|iface| iface.enabled)
+                    .map(|iface| iface.name.clone().unwrap_or_else(|| iface.kind.clone()))
+                    .collect::<Vec<_>>();
+                let targets = match parsed.interface_name.as_deref().map(str::trim) {
+                    Some(name) if !known.iter().any(|known| known == name) => {
+                        return Ok(self.sdk_error_response(
+                            request.id,
+                            "SDK_RUNTIME_NOT_FOUND",
+                            &format!("unknown interface '{name}'"),
+                        ));
+                    }
+                    Some(name) => vec![name.to_string()],
+                    None => known,
+                };
+                let Some(bridge) = self.outbound_bridge.as_ref() else {
+                    return Ok(self.sdk_error_response(
+                        request.id,
+                        "SDK_CAPABILITY_DISABLED",
+                        "reconnect requires a transport bridge",
+                    ));
+                };
+                let mut reconnected = Vec::new();
+                for name in targets {
+                    match bridge.reconnect_interface(&name) {
+                        // The interface reports its own transitions as it
+                        // drops the connection and comes back up.
+                        Ok(true) => reconnected.push(name),
+                        Ok(false) => self.set_interface_link_state(
+                            &name,
+                            InterfaceLinkState::Disconnected,
+                            Some("reconnect: not running"),
+                        ),
+                        Err(err) if err.kind() == std::io::ErrorKind::Unsupported => {
+                            return Ok(self.sdk_error_response(
+                                request.id,
+                                "SDK_CAPABILITY_DISABLED",
+                                &err.to_string(),
+                            ));
+                        }
+                        Err(err) => return Err(err),
+                    }
+                }
+                Ok(RpcResponse {
+                    id: request.id,
+                    result: Some(json!({
+                        "reconnected": reconnected,
+                        "meta": self.response_meta(),
+                    })),
+                    error: None,
+                })
+            }
             "set_interfaces" => {
                 let params = request.params.ok_or_else(|| {
                     std::io::Error::new(std::io::ErrorKind::InvalidInput, "missing params")
@@ -189,6 +662,9 @@ impl RpcDaemon {
                     std::io::Error::new(std::io::ErrorKind::InvalidInput, "missing params")
                 })?;
                 let parsed = parse_outbound_send_request(request.method.as_str(), params)?;
+                if parsed.dry_run {
+                    return self.plan_outbound(request.id, parsed);
+                }
 
                 self.store_outbound(
                     request.id,
@@ -221,6 +697,7 @@ impl RpcDaemon {
                     direction: "in".into(),
                     fields: parsed.fields,
                     receipt_status: None,
+                    reply_to: None,
                 };
                 self.store_inbound_record(record)?;
                 Ok(RpcResponse {
@@ -263,6 +740,17 @@ impl RpcDaemon {
                 };
                 if updated {
                     self.append_delivery_trace(&message_id, status.clone());
+                    self.note_receipt_progress(&message_id, &status);
+                    if Self::is_terminal_receipt_status(&status) {
+                        self.finish_rtt_timer(&message_id, status == "delivered");
+                    }
+                    if status == "delivered" {
+                        if let Err(err) = self.auto_create_contact_for_delivery(&message_id) {
+                            self.record_log_line(format!(
+                                "auto contact for {message_id} not created: {err}"
+                            ));
+                        }
+                    }
                 }
                 let reason_code = delivery_reason_code(&status);
                 let event = RpcEvent {
@@ -293,13 +781,7 @@ impl RpcDaemon {
                 })?;
                 let parsed: MessageDeliveryTraceParams = serde_json::from_value(params)
                     .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
-                let traces = self
-                    .delivery_traces
-                    .lock()
-                    .expect("delivery traces mutex poisoned")
-                    .get(parsed.message_id.as_str())
-                    .cloned()
-                    .unwrap_or_default();
+                let traces = self.delivery_trace_entries(parsed.message_id.as_str())?;
                 Ok(RpcResponse {
                     id: request.id,
                     result: Some(json!({
@@ -310,8 +792,407 @@ impl RpcDaemon {
                     error: None,
                 })
             }
+            "delivery_trace" | "trace_get" => {
+                let params = request.params.ok_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::InvalidInput, "missing params")
+                })?;
+                let parsed: MessageDeliveryTraceParams = serde_json::from_value(params)
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+                let message_id = parsed.message_id.trim();
+                let message = self.store.get_message(message_id).map_err(std::io::Error::other)?;
+                let Some(message) = message else {
+                    return Ok(self.sdk_error_response(
+                        request.id,
+                        "SDK_VALIDATION_INVALID_ARGUMENT",
+                        &format!("unknown message_id '{message_id}'"),
+                    ));
+                };
+                let trace = self.delivery_trace_entries(message_id)?;
+                let terminal = message
+                    .receipt_status
+                    .as_deref()
+                    .is_some_and(Self::is_terminal_receipt_status);
+                Ok(RpcResponse {
+                    id: request.id,
+                    result: Some(json!({
+                        "message_id": message_id,
+                        "trace": trace,
+                        "receipt_status": message.receipt_status,
+                        "terminal": terminal,
+                        "meta": self.response_meta(),
+                    })),
+                    error: None,
+                })
+            }
+            "delivery_metrics" => {
+                let params = request.params.ok_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::InvalidInput, "missing params")
+                })?;
+                let parsed: DeliveryMetricsParams = serde_json::from_value(params)
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+                if parsed.window_ms == 0 {
+                    return Ok(self.sdk_error_response(
+                        request.id,
+                        "SDK_VALIDATION_INVALID_ARGUMENT",
+                        "window_ms must be greater than zero",
+                    ));
+                }
+                let window_ms = i64::try_from(parsed.window_ms).unwrap_or(i64::MAX);
+                let window_start_ms = i64::try_from(now_millis_u64())
+                    .unwrap_or(i64::MAX)
+                    .saturating_sub(window_ms);
+                let traces = self
+                    .store
+                    .list_delivery_traces_since(window_start_ms)
+                    .map_err(std::io::Error::other)?;
+                let mut metrics =
+                    aggregate_delivery_metrics(traces.iter().map(Vec::as_slice), window_start_ms);
+                metrics["window_ms"] = json!(parsed.window_ms);
+                metrics["window_start_ms"] = json!(window_start_ms);
+                metrics["meta"] = self.response_meta();
+                Ok(RpcResponse { id: request.id, result: Some(metrics), error: None })
+            }
             _ => unreachable!("legacy message route: {}", request.method),
         }
     }
 
+    /// Reads every stored announce, newest first, a page at a time.
+    fn all_announces(&self) -> Result<Vec<AnnounceRecord>, std::io::Error> {
+        let mut announces: Vec<AnnounceRecord> = Vec::new();
+        loop {
+            let cursor = announces.last().map(|last| (last.timestamp, last.id.clone()));
+            let page = self
+                .store
+                .list_announces(
+                    ANNOUNCE_SCAN_PAGE,
+                    cursor.as_ref().map(|(ts, _)| *ts),
+                    cursor.as_ref().map(|(_, id)| id.as_str()),
+                )
+                .map_err(std::io::Error::other)?;
+            let done = page.len() < ANNOUNCE_SCAN_PAGE;
+            announces.extend(page);
+            if done {
+                return Ok(announces);
+            }
+        }
+    }
+
+    /// The public identity known for `destination`: the transport's view
+    /// first, then whatever an earlier import stored.
+    fn known_peer_public_key(
+        &self,
+        destination: &str,
+        stored: &HashMap<String, String>,
+    ) -> Option<String> {
+        let from_bridge = self
+            .outbound_bridge
+            .as_ref()
+            .and_then(|bridge| bridge.peer_public_key(destination).ok().flatten());
+        from_bridge.or_else(|| stored.get(destination).cloned())
+    }
+
+    fn export_peer_bundle(&self) -> Result<PeerBundle, std::io::Error> {
+        let mut peers =
+            self.peers.lock().expect("peers mutex poisoned").values().cloned().collect::<Vec<_>>();
+        peers.sort_by(|a, b| a.peer.cmp(&b.peer));
+        let mut latest: HashMap<String, AnnounceRecord> = HashMap::new();
+        for announce in self.all_announces()? {
+            if !announce.capabilities.iter().any(|cap| cap == "propagation") {
+                continue;
+            }
+            match latest.get(&announce.peer) {
+                Some(existing) if existing.timestamp >= announce.timestamp => {}
+                _ => {
+                    latest.insert(announce.peer.clone(), announce);
+                }
+            }
+        }
+        let mut propagation_nodes = latest.into_values().collect::<Vec<_>>();
+        propagation_nodes.sort_by(|a, b| a.peer.cmp(&b.peer));
+        let stored = self
+            .store
+            .list_peer_identities()
+            .map_err(std::io::Error::other)?
+            .into_iter()
+            .filter_map(|stored| Some((stored.peer, stored.public_key?)))
+            .collect::<HashMap<_, _>>();
+        let identities = peers
+            .iter()
+            .map(|peer| peer.peer.as_str())
+            .chain(propagation_nodes.iter().map(|node| node.peer.as_str()))
+            .filter_map(|destination| {
+                self.known_peer_public_key(destination, &stored)
+                    .map(|public_key| (destination.to_string(), public_key))
+            })
+            .collect();
+        Ok(PeerBundle {
+            version: PEER_BUNDLE_VERSION.to_string(),
+            exported_at: now_i64(),
+            peers,
+            propagation_nodes,
+            identities,
+        })
+    }
+
+    /// Merges an exported bundle into the peer table and announce store. Entries
+    /// that are not newer than what this node already knows are skipped.
+    /// Imported peers and their public identities are persisted and handed to
+    /// the transport, so they can be messaged right away and after a restart.
+    fn import_peer_bundle(&self, bundle: PeerBundle) -> Result<JsonValue, std::io::Error> {
+        let (mut peers_imported, mut peers_skipped) = (0_u64, 0_u64);
+        let mut imported_records = Vec::new();
+        {
+            let mut guard = self.peers.lock().expect("peers mutex poisoned");
+            for imported in bundle.peers {
+                match guard.get_mut(&imported.peer) {
+                    Some(existing) if existing.last_seen >= imported.last_seen => {
+                        peers_skipped += 1;
+                        continue;
+                    }
+                    Some(existing) => {
+                        existing.last_seen = imported.last_seen;
+                        existing.first_seen = existing.first_seen.min(imported.first_seen);
+                        existing.seen_count = existing.seen_count.max(imported.seen_count);
+                        if imported.name.is_some() {
+                            existing.name = imported.name;
+                            existing.name_source = imported.name_source;
+                        }
+                        imported_records.push(existing.clone());
+                    }
+                    None => {
+                        guard.insert(imported.peer.clone(), imported.clone());
+                        imported_records.push(imported);
+                    }
+                }
+                peers_imported += 1;
+            }
+        }
+
+        let mut newest_announce: HashMap<String, i64> = HashMap::new();
+        for announce in self.all_announces()? {
+            let newest = newest_announce.entry(announce.peer).or_insert(announce.timestamp);
+            *newest = (*newest).max(announce.timestamp);
+        }
+        let (mut nodes_imported, mut nodes_skipped) = (0_u64, 0_u64);
+        for mut node in bundle.propagation_nodes {
+            if newest_announce.get(&node.peer).is_some_and(|newest| *newest >= node.timestamp) {
+                nodes_skipped += 1;
+                continue;
+            }
+            node.id = format!("announce-import-{}-{}", node.timestamp, node.peer);
+            self.store.insert_announce(&node).map_err(std::io::Error::other)?;
+            self.propagation_candidates
+                .lock()
+                .expect("propagation candidates mutex poisoned")
+                .observe_announce(&node, None);
+            nodes_imported += 1;
+        }
+
+        let mut accepted: HashMap<&str, &str> = HashMap::new();
+        let mut identities_rejected = 0_u64;
+        for (destination, public_key) in &bundle.identities {
+            if self.restore_bundle_identity(destination, public_key) {
+                accepted.insert(destination, public_key);
+            } else {
+                identities_rejected += 1;
+            }
+        }
+        let identities_imported = accepted.len() as u64;
+        for record in &imported_records {
+            let public_key = accepted.remove(record.peer.as_str());
+            self.store
+                .put_peer_identity(&record.peer, Some(record), public_key)
+                .map_err(std::io::Error::other)?;
+        }
+        for (destination, public_key) in accepted {
+            self.store
+                .put_peer_identity(destination, None, Some(public_key))
+                .map_err(std::io::Error::other)?;
+        }
+
+        Ok(json!({
+            "peers_imported": peers_imported,
+            "peers_skipped": peers_skipped,
+            "propagation_nodes_imported": nodes_imported,
+            "propagation_nodes_skipped": nodes_skipped,
+            "identities_imported": identities_imported,
+            "identities_rejected": identities_rejected,
+        }))
+    }
+
+    fn is_public_identity_hex(public_key: &str) -> bool {
+        public_key.len() == PUBLIC_IDENTITY_HEX_LEN
+            && public_key.chars().all(|ch| ch.is_ascii_hexdigit())
+    }
+
+    /// Hands an imported public identity to the transport. Bridges without
+    /// identity support still keep the key in the store for a later export;
+    /// keys that are malformed or do not derive `destination` are rejected.
+    fn restore_bundle_identity(&self, destination: &str, public_key: &str) -> bool {
+        if !Self::is_public_identity_hex(public_key) {
+            return false;
+        }
+        let Some(bridge) = self.outbound_bridge.as_ref() else {
+            return true;
+        };
+        match bridge.restore_peer_identity(destination, public_key) {
+            Ok(()) => true,
+            Err(err) if err.kind() == std::io::ErrorKind::Unsupported => true,
+            Err(err) => {
+                self.record_log_line(format!(
+                    "peer identity for {destination} not imported: {err}"
+                ));
+                false
+            }
+        }
+    }
+
+    /// Re-registers the public identities of imported peers with the
+    /// transport and restores their peer records after a restart.
+    fn restore_peer_identities(&self) -> Result<usize, std::io::Error> {
+        let stored = self.store.list_peer_identities().map_err(std::io::Error::other)?;
+        let count = stored.len();
+        for PeerIdentityRecord { peer, record, public_key } in stored {
+            if let (Some(bridge), Some(public_key)) = (self.outbound_bridge.as_ref(), public_key) {
+                match bridge.restore_peer_identity(&peer, &public_key) {
+                    Ok(()) => {}
+                    Err(err) if err.kind() == std::io::ErrorKind::Unsupported => {}
+                    Err(err) => {
+                        self.record_log_line(format!("peer identity for {peer} not restored: {err}"))
+                    }
+                }
+            }
+            let Some(record) = record else {
+                continue;
+            };
+            let mut guard = self.peers.lock().expect("peers mutex poisoned");
+            match guard.get(&peer) {
+                Some(existing) if existing.last_seen >= record.last_seen => {}
+                _ => {
+                    guard.insert(peer, record);
+                }
+            }
+        }
+        Ok(count)
+    }
+
+}
+
+/// One file attachment in stored message fields: inline bytes, or an
+/// `attachments` object naming an attachment-store entry by `attachment_id`.
+enum MessageAttachmentRef {
+    Inline { name: String, bytes: Vec<u8> },
+    Stored { name: Option<String>, attachment_id: String },
+}
+
+/// Reads the file attachments carried in stored message fields, in order.
+/// Both the canonical `attachments` objects and the wire `"5"` pairs are read;
+/// entries whose data cannot be decoded are skipped.
+fn message_attachment_refs(fields: Option<&JsonValue>) -> Vec<MessageAttachmentRef> {
+    let Some(fields) = fields.and_then(JsonValue::as_object) else {
+        return Vec::new();
+    };
+    if let Some(entries) = fields.get("attachments").and_then(JsonValue::as_array) {
+        return entries
+            .iter()
+            .filter_map(|entry| {
+                let name = entry.get("name").and_then(JsonValue::as_str).map(str::to_string);
+                if let Some(data) = entry.get("data") {
+                    return Some(MessageAttachmentRef::Inline {
+                        name: name?,
+                        bytes: decode_attachment_bytes(data)?,
+                    });
+                }
+                let attachment_id = entry.get("attachment_id")?.as_str()?.trim();
+                (!attachment_id.is_empty()).then(|| MessageAttachmentRef::Stored {
+                    name,
+                    attachment_id: attachment_id.to_string(),
+                })
+            })
+            .collect();
+    }
+    let Some(entries) = fields.get("5").and_then(JsonValue::as_array) else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let pair = entry.as_array()?;
+            let name = match pair.first()? {
+                JsonValue::String(name) => name.clone(),
+                other => String::from_utf8_lossy(&decode_attachment_bytes(other)?).into_owned(),
+            };
+            Some(MessageAttachmentRef::Inline { name, bytes: decode_attachment_bytes(pair.get(1)?)? })
+        })
+        .collect()
+}
+
+/// Decodes the inline file attachments carried in stored message fields.
+fn message_attachment_entries(fields: Option<&JsonValue>) -> Vec<(String, Vec<u8>)> {
+    message_attachment_refs(fields)
+        .into_iter()
+        .filter_map(|attachment| match attachment {
+            MessageAttachmentRef::Inline { name, bytes } => Some((name, bytes)),
+            MessageAttachmentRef::Stored { .. } => None,
+        })
+        .collect()
+}
+
+/// Describes one attachment for the `_attachments` field: its detected
+/// `content_type` and, for images, `width` / `height` under `extensions`.
+fn attachment_media_json(index: usize, name: &str, bytes: &[u8]) -> JsonValue {
+    let media = sniff_media(bytes);
+    let mut extensions = JsonMap::new();
+    if let Some((width, height)) = media.dimensions {
+        extensions.insert("width".to_string(), json!(width));
+        extensions.insert("height".to_string(), json!(height));
+    }
+    json!({
+        "index": index,
+        "name": name,
+        "content_type": media.content_type,
+        "size_bytes": bytes.len() as u64,
+        "checksum_sha256": encode_hex(Sha256::digest(bytes)),
+        "extensions": extensions,
+    })
+}
+
+/// Stores the media description of every attachment alongside the message,
+/// so clients can show type and size without fetching the payloads.
+fn annotate_attachment_media(fields: &mut Option<JsonValue>) {
+    let media = message_attachment_refs(fields.as_ref())
+        .iter()
+        .enumerate()
+        .filter_map(|(index, attachment)| match attachment {
+            MessageAttachmentRef::Inline { name, bytes } => {
+                Some(attachment_media_json(index, name, bytes))
+            }
+            MessageAttachmentRef::Stored { .. } => None,
+        })
+        .collect::<Vec<_>>();
+    if media.is_empty() {
+        return;
+    }
+    if let Some(JsonValue::Object(fields)) = fields.as_mut() {
+        fields.insert(FIELD_ATTACHMENT_MEDIA.to_string(), JsonValue::Array(media));
+    }
+}
+
+fn decode_attachment_bytes(value: &JsonValue) -> Option<Vec<u8>> {
+    match value {
+        JsonValue::Array(items) => items
+            .iter()
+            .map(|item| item.as_u64().and_then(|byte| u8::try_from(byte).ok()))
+            .collect(),
+        JsonValue::String(text) => {
+            let text = text.trim();
+            if let Some(payload) = text.strip_prefix("hex:").or_else(|| text.strip_prefix("HEX:"))
+            {
+                return hex::decode(payload.trim()).ok();
+            }
+            let payload = text.strip_prefix("base64:").or_else(|| text.strip_prefix("BASE64:"))?;
+            BASE64_STANDARD.decode(payload.trim()).ok()
+        }
+        _ => None,
+    }
 }
@@ -1,6 +1,129 @@
 impl RpcDaemon {
     fn handle_rpc_legacy_misc(&self, request: RpcRequest) -> Result<RpcResponse, std::io::Error> {
         match request.method.as_str() {
+            "health" => {
+                let interfaces = self.interfaces.lock().expect("interfaces mutex poisoned").clone();
+                let down =
+                    self.interfaces_down.lock().expect("interfaces_down mutex poisoned").clone();
+                let transport_up = self.outbound_bridge.is_some();
+                let enabled = interfaces.iter().filter(|iface| iface.enabled).collect::<Vec<_>>();
+                let interfaces_up = if transport_up {
+                    enabled
+                        .iter()
+                        .filter(|iface| {
+                            !down.contains(iface.name.as_deref().unwrap_or(&iface.kind))
+                        })
+                        .count()
+                } else {
+                    0
+                };
+                let mut propagation_sync_state = self
+                    .propagation_state
+                    .lock()
+                    .expect("propagation mutex poisoned")
+                    .state_name
+                    .clone();
+                if propagation_sync_state.is_empty() {
+                    propagation_sync_state = "idle".to_string();
+                }
+                let outbound_queue_depth = self
+                    .store
+                    .count_pending_outbound_messages()
+                    .map_err(std::io::Error::other)?;
+                let last_announce_ts_ms =
+                    *self.last_announce_ts_ms.lock().expect("last_announce mutex poisoned");
+                Ok(RpcResponse {
+                    id: request.id,
+                    result: Some(json!({
+                        "transport_up": transport_up,
+                        "interfaces_up": interfaces_up as u32,
+                        "interfaces_total": enabled.len() as u32,
+                        "propagation_sync_state": propagation_sync_state,
+                        "outbound_queue_depth": outbound_queue_depth,
+                        "last_announce_ts_ms": last_announce_ts_ms,
+                        "meta": self.response_meta(),
+                    })),
+                    error: None,
+                })
+            }
+            "logs_tail" => {
+                let parsed = request
+                    .params
+                    .map(serde_json::from_value::<LogsTailParams>)
+                    .transpose()
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?
+                    .unwrap_or_default();
+                let max_lines = parsed
+                    .max_lines
+                    .unwrap_or(DAEMON_LOG_DEFAULT_TAIL)
+                    .clamp(1, DAEMON_LOG_CAPACITY);
+                let min_level = match parsed.log_level.as_deref() {
+                    None => DaemonLogLevel::Trace,
+                    Some(value) => match parse_daemon_log_level(value) {
+                        Some(level) => level,
+                        None => {
+                            return Ok(self.sdk_error_response(
+                                request.id,
+                                "SDK_VALIDATION_INVALID_ARGUMENT",
+                                &format!(
+                                    "unknown log_level '{value}'; expected TRACE, DEBUG, INFO, \
+                                     WARN or ERROR"
+                                ),
+                            ))
+                        }
+                    },
+                };
+                let matches = |line: &String| daemon_log_line_level(line) >= min_level;
+                let guard = self.daemon_log.lock().expect("daemon log mutex poisoned");
+                let end_offset = guard.first_offset + guard.lines.len() as u64;
+                let missed_lines = parsed
+                    .after_offset
+                    .map_or(0, |offset| guard.first_offset.saturating_sub(offset));
+                // Without an offset the caller gets the most recent matching lines; with
+                // one it gets matching lines recorded since, starting at the oldest line
+                // still held. Skipped lines still advance `next_offset`.
+                let (lines, next_offset) = match parsed.after_offset {
+                    None => {
+                        let mut lines = guard
+                            .lines
+                            .iter()
+                            .rev()
+                            .filter(|line| matches(line))
+                            .take(max_lines)
+                            .cloned()
+                            .collect::<Vec<_>>();
+                        lines.reverse();
+                        (lines, end_offset)
+                    }
+                    Some(offset) => {
+                        let start_offset = offset.clamp(guard.first_offset, end_offset);
+                        let mut next_offset = start_offset;
+                        let mut lines = Vec::new();
+                        for line in guard.lines.iter().skip((start_offset - guard.first_offset) as usize)
+                        {
+                            if lines.len() == max_lines {
+                                break;
+                            }
+                            next_offset += 1;
+                            if matches(line) {
+                                lines.push(line.clone());
+                            }
+                        }
+                        (lines, next_offset)
+                    }
+                };
+                Ok(RpcResponse {
+                    id: request.id,
+                    result: Some(json!({
+                        "lines": lines,
+                        "next_offset": next_offset,
+                        "first_offset": guard.first_offset,
+                        "missed_lines": missed_lines,
+                        "meta": self.response_meta(),
+                    })),
+                    error: None,
+                })
+            }
             "paper_ingest_uri" => {
                 let params = request.params.ok_or_else(|| {
                     std::io::Error::new(std::io::ErrorKind::InvalidInput, "missing params")
@@ -69,8 +192,15 @@ impl RpcDaemon {
                     if let Some(value) = parsed.flexibility {
                         guard.flexibility = value;
                     }
+                    if let Some(value) = parsed.generation_workers {
+                        guard.generation_workers = value;
+                    }
+                    if let Some(value) = parsed.mode {
+                        guard.mode = value;
+                    }
                     guard.clone()
                 };
+                self.advertise_propagation_stamp_cost();
 
                 Ok(RpcResponse {
                     id: request.id,
@@ -102,7 +232,8 @@ impl RpcDaemon {
                 let mut hasher = Sha256::new();
                 hasher.update(parsed.destination.as_bytes());
                 hasher.update(now.to_be_bytes());
-                let ticket = encode_hex(hasher.finalize());
+                hasher.update(expires_at.to_be_bytes());
+                let ticket = normalize_ticket(&encode_hex(hasher.finalize()));
                 let record = TicketRecord {
                     destination: parsed.destination.clone(),
                     ticket,
@@ -112,7 +243,7 @@ impl RpcDaemon {
                 self.ticket_cache
                     .lock()
                     .expect("ticket mutex poisoned")
-                    .insert(parsed.destination, record.clone());
+                    .insert(record.ticket.clone(), record.clone());
 
                 Ok(RpcResponse {
                     id: request.id,
@@ -125,6 +256,69 @@ impl RpcDaemon {
                     error: None,
                 })
             }
+            "tickets_list" => {
+                let now = now_i64();
+                let issuer = self.local_delivery_hash();
+                let mut tickets = self
+                    .ticket_cache
+                    .lock()
+                    .expect("ticket mutex poisoned")
+                    .values()
+                    .cloned()
+                    .collect::<Vec<_>>();
+                tickets.sort_by(|a, b| {
+                    (&a.destination, a.expires_at, &a.ticket).cmp(&(
+                        &b.destination,
+                        b.expires_at,
+                        &b.ticket,
+                    ))
+                });
+                let tickets = tickets
+                    .into_iter()
+                    .map(|record| {
+                        json!({
+                            "ticket": record.ticket,
+                            "issuer": issuer,
+                            "audience": record.destination,
+                            "expires_at": record.expires_at,
+                            "expired": record.expires_at <= now,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                Ok(RpcResponse {
+                    id: request.id,
+                    result: Some(json!({ "tickets": tickets })),
+                    error: None,
+                })
+            }
+            "ticket_revoke" => {
+                let params = request.params.ok_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::InvalidInput, "missing params")
+                })?;
+                let parsed: TicketRevokeParams = serde_json::from_value(params)
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+                let ticket = normalize_ticket(&parsed.ticket);
+                let revoked = self
+                    .ticket_cache
+                    .lock()
+                    .expect("ticket mutex poisoned")
+                    .remove(&ticket)
+                    .is_some();
+                if revoked {
+                    self.store
+                        .put_revoked_ticket(&ticket, now_i64())
+                        .map_err(std::io::Error::other)?;
+                    self.revoked_tickets
+                        .lock()
+                        .expect("revoked_tickets mutex poisoned")
+                        .insert(ticket.clone());
+                }
+                Ok(RpcResponse {
+                    id: request.id,
+                    result: Some(json!({ "ticket": ticket, "revoked": revoked })),
+                    error: None,
+                })
+            }
             "announce_now" => {
                 let timestamp = now_i64();
                 if let Some(bridge) = &self.announce_bridge {
@@ -141,6 +335,158 @@ impl RpcDaemon {
                     error: None,
                 })
             }
+            "announce_once_to" => {
+                let parsed = request
+                    .params
+                    .map(serde_json::from_value::<AnnounceOnceToParams>)
+                    .transpose()
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?
+                    .unwrap_or_default();
+                let peer = parsed.peer.as_deref().map(str::trim).unwrap_or_default();
+                if !is_destination_hash(peer) {
+                    return Ok(self.sdk_error_response(
+                        request.id,
+                        "SDK_VALIDATION_INVALID_ARGUMENT",
+                        "announce_once_to requires peer as a 32-character hex destination hash",
+                    ));
+                }
+                let peer = peer.to_ascii_lowercase();
+                let Some(bridge) = self.announce_bridge.as_ref() else {
+                    return Ok(self.sdk_error_response(
+                        request.id,
+                        "SDK_CAPABILITY_DISABLED",
+                        "announce_once_to requires a transport bridge",
+                    ));
+                };
+                match bridge.announce_to(&peer) {
+                    Ok(()) => {}
+                    Err(err) if err.kind() == std::io::ErrorKind::Unsupported => {
+                        return Ok(self.sdk_error_response(
+                            request.id,
+                            "SDK_CAPABILITY_DISABLED",
+                            &err.to_string(),
+                        ));
+                    }
+                    Err(err) => return Err(err),
+                }
+                Ok(RpcResponse {
+                    id: request.id,
+                    result: Some(json!({ "announce_id": request.id, "target": peer })),
+                    error: None,
+                })
+            }
+            "identity_rotate" => {
+                let parsed = request
+                    .params
+                    .map(serde_json::from_value::<IdentityRotateParams>)
+                    .transpose()
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?
+                    .unwrap_or_default();
+                if !parsed.confirm {
+                    return Ok(self.sdk_error_response(
+                        request.id,
+                        "SDK_VALIDATION_INVALID_ARGUMENT",
+                        "identity_rotate changes the node's address; pass confirm=true",
+                    ));
+                }
+                let Some(bridge) = self.announce_bridge.as_ref() else {
+                    return Ok(self.sdk_error_response(
+                        request.id,
+                        "SDK_CAPABILITY_DISABLED",
+                        "identity_rotate requires a transport bridge",
+                    ));
+                };
+                let rotation = match bridge.rotate_identity() {
+                    Ok(rotation) => rotation,
+                    Err(err) if err.kind() == std::io::ErrorKind::Unsupported => {
+                        return Ok(self.sdk_error_response(
+                            request.id,
+                            "SDK_CAPABILITY_DISABLED",
+                            &err.to_string(),
+                        ));
+                    }
+                    Err(err) => return Err(err),
+                };
+                let previous_delivery_hash = self.local_delivery_hash();
+                let previous_identity_hash = self.current_identity_hash();
+                *self
+                    .rotated_identity_hash
+                    .lock()
+                    .expect("rotated_identity_hash mutex poisoned") =
+                    Some(rotation.identity_hash.clone());
+                self.set_delivery_destination_hash(Some(
+                    rotation.delivery_destination_hash.clone(),
+                ));
+                let timestamp = now_i64();
+                self.publish_event(RpcEvent {
+                    event_type: "identity_rotated".into(),
+                    payload: json!({
+                        "previous_identity_hash": previous_identity_hash,
+                        "previous_delivery_destination_hash": previous_delivery_hash,
+                        "delivery_destination_hash": rotation.delivery_destination_hash,
+                        "identity_hash": rotation.identity_hash,
+                        "timestamp": timestamp,
+                    }),
+                });
+                Ok(RpcResponse {
+                    id: request.id,
+                    result: Some(json!({
+                        "previous_identity_hash": previous_identity_hash,
+                        "identity_hash": rotation.identity_hash,
+                        "previous_delivery_destination_hash": previous_delivery_hash,
+                        "delivery_destination_hash": rotation.delivery_destination_hash,
+                        "meta": self.response_meta(),
+                    })),
+                    error: None,
+                })
+            }
+            "set_display_name" => {
+                let params = request.params.ok_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::InvalidInput, "missing params")
+                })?;
+                let parsed: SetDisplayNameParams = serde_json::from_value(params)
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+                let Some(bridge) = self.announce_bridge.as_ref() else {
+                    return Ok(self.sdk_error_response(
+                        request.id,
+                        "SDK_CAPABILITY_DISABLED",
+                        "set_display_name requires a transport bridge",
+                    ));
+                };
+                let update = match bridge.set_display_name(&parsed.display_name) {
+                    Ok(update) => update,
+                    Err(err) if err.kind() == std::io::ErrorKind::InvalidInput => {
+                        return Ok(self.sdk_error_response(
+                            request.id,
+                            "SDK_VALIDATION_INVALID_ARGUMENT",
+                            &err.to_string(),
+                        ));
+                    }
+                    Err(err) if err.kind() == std::io::ErrorKind::Unsupported => {
+                        return Ok(self.sdk_error_response(
+                            request.id,
+                            "SDK_CAPABILITY_DISABLED",
+                            &err.to_string(),
+                        ));
+                    }
+                    Err(err) => return Err(err),
+                };
+                self.set_local_display_name(Some(update.display_name.clone()));
+                if update.announced {
+                    self.publish_event(RpcEvent {
+                        event_type: "announce_sent".into(),
+                        payload: json!({ "timestamp": now_i64() }),
+                    });
+                }
+                Ok(RpcResponse {
+                    id: request.id,
+                    result: Some(json!({
+                        "display_name": update.display_name,
+                        "announced": update.announced,
+                    })),
+                    error: None,
+                })
+            }
             "announce_received" => {
                 let params = request.params.ok_or_else(|| {
                     std::io::Error::new(std::io::ErrorKind::InvalidInput, "missing params")
@@ -164,7 +510,7 @@ impl RpcDaemon {
                     parsed.rssi,
                     parsed.snr,
                     parsed.q,
-                    None,
+                    parsed.stamp_cost,
                     Some(stamp_cost_flexibility),
                     Some(peering_cost),
                     None,
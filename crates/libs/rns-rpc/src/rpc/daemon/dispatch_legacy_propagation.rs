@@ -33,6 +33,21 @@ impl RpcDaemon {
                     if let Some(value) = parsed.prioritised_destinations {
                         guard.prioritised_destinations = value;
                     }
+                    if let Some(value) = parsed.auto_contacts {
+                        guard.auto_contacts = value;
+                    }
+                    if let Some(value) = parsed.max_title_bytes {
+                        guard.max_title_bytes = value;
+                    }
+                    if let Some(value) = parsed.title_limit_mode {
+                        guard.title_limit_mode = value;
+                    }
+                    if let Some(value) = parsed.max_attachment_bytes {
+                        guard.max_attachment_bytes = value;
+                    }
+                    if let Some(value) = parsed.max_attachments {
+                        guard.max_attachments = value;
+                    }
                     guard.clone()
                 };
 
@@ -70,6 +85,7 @@ impl RpcDaemon {
                     }
                     guard.clone()
                 };
+                self.advertise_propagation_stamp_cost();
                 Ok(RpcResponse {
                     id: request.id,
                     result: Some(json!({ "propagation": state })),
@@ -82,19 +98,60 @@ impl RpcDaemon {
                 })?;
                 let parsed: PropagationIngestParams = serde_json::from_value(params)
                     .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+                let enabled =
+                    self.propagation_state.lock().expect("propagation mutex poisoned").enabled;
+                if !enabled {
+                    return Ok(self.sdk_error_response(
+                        request.id,
+                        "SDK_CAPABILITY_DISABLED",
+                        "propagation node mode is disabled",
+                    ));
+                }
 
-                let payload_hex = parsed.payload_hex.unwrap_or_default();
-                let transient_id = parsed.transient_id.unwrap_or_else(|| {
+                let mut payload_hex = parsed.payload_hex.unwrap_or_default();
+                let mut stamped_id = None;
+                if let Some(accepted) = self.accepted_propagation_stamp_value() {
+                    let Some((transient_id, lxmf_data, _, _)) = hex::decode(&payload_hex)
+                        .ok()
+                        .and_then(|data| validate_pn_stamp(&data, accepted))
+                    else {
+                        self.record_propagation_stamp_rejected();
+                        return Ok(self.sdk_error_response(
+                            request.id,
+                            "SDK_VALIDATION_INVALID_STAMP",
+                            &format!("propagation stamp below accepted cost {accepted}"),
+                        ));
+                    };
+                    payload_hex = encode_hex(lxmf_data);
+                    stamped_id = Some(encode_hex(transient_id));
+                }
+                let transient_id = parsed.transient_id.or(stamped_id).unwrap_or_else(|| {
                     let mut hasher = Sha256::new();
                     hasher.update(payload_hex.as_bytes());
                     encode_hex(hasher.finalize())
                 });
 
+                let now_ms = now_millis_u64();
+                self.purge_expired_propagation_payloads(now_ms);
                 if !payload_hex.is_empty() {
+                    let ttl_ms = self.propagation_message_ttl_ms();
+                    let expires_at_ms = (ttl_ms > 0).then(|| {
+                        propagated_message_timestamp_ms(&payload_hex)
+                            .map_or(now_ms, |sent_ms| sent_ms.min(now_ms))
+                            .saturating_add(ttl_ms)
+                    });
                     self.propagation_payloads
                         .lock()
                         .expect("propagation payload mutex poisoned")
-                        .insert(transient_id.clone(), payload_hex);
+                        .insert(
+                            transient_id.clone(),
+                            PropagationPayload { payload_hex, expires_at_ms },
+                        );
+                    self.propagation_expired_ids
+                        .lock()
+                        .expect("propagation expired ids mutex poisoned")
+                        .retain(|id| id != &transient_id);
+                    self.advertise_propagation_stamp_cost();
                 }
 
                 let state = {
@@ -122,15 +179,50 @@ impl RpcDaemon {
                 let parsed: PropagationFetchParams = serde_json::from_value(params)
                     .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
 
-                let payload = self
+                let now_ms = now_millis_u64();
+                let entry = self
                     .propagation_payloads
                     .lock()
                     .expect("propagation payload mutex poisoned")
                     .get(&parsed.transient_id)
-                    .cloned()
-                    .ok_or_else(|| {
-                        std::io::Error::new(std::io::ErrorKind::NotFound, "transient_id not found")
-                    })?;
+                    .cloned();
+                let expired = match &entry {
+                    Some(entry) => entry.expires_at_ms.is_some_and(|at| at <= now_ms),
+                    None => self
+                        .propagation_expired_ids
+                        .lock()
+                        .expect("propagation expired ids mutex poisoned")
+                        .contains(&parsed.transient_id),
+                };
+                let payload = entry.filter(|_| !expired).map(|entry| entry.payload_hex);
+                {
+                    let mut guard =
+                        self.propagation_state.lock().expect("propagation mutex poisoned");
+                    if payload.is_some() {
+                        guard.total_fetched += 1;
+                    } else {
+                        guard.fetch_misses += 1;
+                    }
+                }
+                if expired {
+                    let mut response = self.sdk_error_response(
+                        request.id,
+                        "SDK_RUNTIME_MESSAGE_EXPIRED",
+                        "propagated message has expired",
+                    );
+                    if let Some(error) = response.error.as_mut() {
+                        let mut details = JsonMap::new();
+                        details.insert(
+                            "transient_id".to_string(),
+                            JsonValue::String(parsed.transient_id),
+                        );
+                        error.details = Some(Box::new(details));
+                    }
+                    return Ok(response);
+                }
+                let payload = payload.ok_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::NotFound, "transient_id not found")
+                })?;
 
                 Ok(RpcResponse {
                     id: request.id,
@@ -141,7 +233,165 @@ impl RpcDaemon {
                     error: None,
                 })
             }
+            "propagation_stats" => {
+                let state =
+                    self.propagation_state.lock().expect("propagation mutex poisoned").clone();
+                let (store_messages, store_bytes) = self.propagation_store_usage();
+                Ok(RpcResponse {
+                    id: request.id,
+                    result: Some(json!({
+                        "stats": {
+                            "enabled": state.enabled,
+                            "ingested_total": state.total_ingested,
+                            "last_ingest_count": state.last_ingest_count,
+                            "fetches_served": state.total_fetched,
+                            "fetch_misses": state.fetch_misses,
+                            "rejected_stamps": state.rejected_stamps,
+                            "store_messages": store_messages,
+                            "store_bytes": store_bytes,
+                        },
+                        "meta": self.response_meta(),
+                    })),
+                    error: None,
+                })
+            }
+            "sync_now" => {
+                let now = now_i64();
+                self.reselect_stale_propagation_node(now)?;
+                let Some(node) = self
+                    .outbound_propagation_node
+                    .lock()
+                    .expect("propagation node mutex poisoned")
+                    .clone()
+                else {
+                    let mut response = self.sdk_error_response(
+                        request.id,
+                        "SDK_RUNTIME_NO_PROPAGATION_NODE",
+                        "sync_now requires a selected propagation node",
+                    );
+                    if let Some(error) = response.error.as_mut() {
+                        let mut details = JsonMap::new();
+                        details.insert(
+                            "prerequisite".to_string(),
+                            JsonValue::String("outbound_propagation_node".to_string()),
+                        );
+                        error.details = Some(Box::new(details));
+                    }
+                    return Ok(response);
+                };
+
+                let already_running = {
+                    let guard = self.propagation_state.lock().expect("propagation mutex poisoned");
+                    let in_progress = (PR_PATH_REQUESTED..PR_COMPLETE).contains(&guard.sync_state);
+                    let fresh = guard.last_sync_started.is_some_and(|started| {
+                        now.saturating_sub(started) < PROPAGATION_SYNC_STALE_SECS
+                    });
+                    in_progress && fresh
+                };
+                if !already_running {
+                    let sync = PropagationSyncRequest {
+                        node: node.clone(),
+                        max_messages: PROPAGATION_SYNC_MAX_MESSAGES,
+                    };
+                    let started = match &self.outbound_bridge {
+                        Some(bridge) => bridge.sync_propagation(&sync),
+                        None => Err(std::io::Error::new(
+                            std::io::ErrorKind::Unsupported,
+                            "propagation sync not supported",
+                        )),
+                    };
+                    if let Err(err) = &started {
+                        if err.kind() == std::io::ErrorKind::Unsupported {
+                            return Ok(self.sdk_error_response(
+                                request.id,
+                                "SDK_CAPABILITY_DISABLED",
+                                &err.to_string(),
+                            ));
+                        }
+                    }
+                    // The bridge reports later steps through the runtime
+                    // thread, so they always land after this transition.
+                    self.update_propagation_sync_state(|guard| {
+                        guard.messages_received = 0;
+                        guard.max_messages = sync.max_messages;
+                        guard.selected_node = Some(node.clone());
+                        guard.last_sync_started = Some(now);
+                        match started {
+                            Ok(()) => {
+                                guard.sync_state = PR_PATH_REQUESTED;
+                                guard.state_name = "path_requested".to_string();
+                                guard.sync_progress = 0.05;
+                                guard.last_sync_completed = None;
+                                guard.last_sync_error = None;
+                            }
+                            Err(err) => {
+                                guard.sync_state = PR_NO_PATH;
+                                guard.state_name = "no_path".to_string();
+                                guard.sync_progress = 0.0;
+                                guard.last_sync_completed = Some(now);
+                                guard.last_sync_error = Some(err.to_string());
+                            }
+                        }
+                    });
+                }
+
+                let state =
+                    self.propagation_state.lock().expect("propagation mutex poisoned").clone();
+                Ok(RpcResponse {
+                    id: request.id,
+                    result: Some(json!({
+                        "sync": Self::propagation_sync_json(&state),
+                        "already_running": already_running,
+                        "meta": self.response_meta(),
+                    })),
+                    error: None,
+                })
+            }
+            "stamp_status" => {
+                let state =
+                    self.propagation_state.lock().expect("propagation mutex poisoned").clone();
+                if !state.enabled {
+                    return Ok(RpcResponse {
+                        id: request.id,
+                        result: Some(json!({
+                            "stamp_status": { "enabled": false },
+                            "meta": self.response_meta(),
+                        })),
+                        error: None,
+                    });
+                }
+                let policy = self.stamp_policy.lock().expect("stamp mutex poisoned").clone();
+                let flexibility = policy.flexibility;
+                let cost = state.target_cost.max(PROPAGATION_COST_MIN);
+                let mut status = json!({
+                    "enabled": true,
+                    "propagation_stamp_cost": cost,
+                    "flexibility": flexibility,
+                    "peering_cost": PEERING_COST,
+                    "accepted_range": [cost.saturating_sub(flexibility), cost],
+                });
+                if policy.mode == StampCostMode::Adaptive {
+                    let required = self.required_propagation_stamp_cost();
+                    status["mode"] = json!(policy.mode);
+                    status["adaptive_cost"] = json!(required);
+                    status["accepted_range"] =
+                        json!([required.saturating_sub(flexibility), required]);
+                }
+                Ok(RpcResponse {
+                    id: request.id,
+                    result: Some(json!({
+                        "stamp_status": status,
+                        "meta": self.response_meta(),
+                    })),
+                    error: None,
+                })
+            }
             "get_outbound_propagation_node" => {
+                self.reselect_stale_propagation_node(now_i64())?;
+                let pinned = *self
+                    .outbound_propagation_node_pinned
+                    .lock()
+                    .expect("propagation pin mutex poisoned");
                 let selected = self
                     .outbound_propagation_node
                     .lock()
@@ -151,6 +401,7 @@ impl RpcDaemon {
                     id: request.id,
                     result: Some(json!({
                         "peer": selected,
+                        "pinned": pinned,
                         "meta": self.response_meta(),
                     })),
                     error: None,
@@ -173,6 +424,11 @@ impl RpcDaemon {
                         .expect("propagation node mutex poisoned");
                     *guard = peer.clone();
                 }
+                // A manual selection pins the node until it is cleared again.
+                *self
+                    .outbound_propagation_node_pinned
+                    .lock()
+                    .expect("propagation pin mutex poisoned") = peer.is_some();
                 let event = RpcEvent {
                     event_type: "propagation_node_selected".into(),
                     payload: json!({ "peer": peer }),
@@ -188,46 +444,20 @@ impl RpcDaemon {
                 })
             }
             "list_propagation_nodes" => {
-                let selected = self
-                    .outbound_propagation_node
-                    .lock()
-                    .expect("propagation node mutex poisoned")
-                    .clone();
-                let announces =
-                    self.store.list_announces(500, None, None).map_err(std::io::Error::other)?;
-                let mut by_peer: HashMap<String, PropagationNodeRecord> = HashMap::new();
-                for announce in announces {
-                    if !announce.capabilities.iter().any(|cap| cap == "propagation") {
-                        continue;
-                    }
-
-                    let key = announce.peer.clone();
-                    let entry =
-                        by_peer.entry(key.clone()).or_insert_with(|| PropagationNodeRecord {
-                            peer: key.clone(),
-                            name: announce.name.clone(),
-                            last_seen: announce.timestamp,
-                            capabilities: announce.capabilities.clone(),
-                            selected: selected.as_deref() == Some(key.as_str()),
-                        });
-                    if announce.timestamp > entry.last_seen {
-                        entry.last_seen = announce.timestamp;
-                        entry.name = announce.name.clone();
-                        entry.capabilities = announce.capabilities.clone();
-                    }
-                    if selected.as_deref() == Some(key.as_str()) {
-                        entry.selected = true;
-                    }
-                }
-
-                let mut nodes = by_peer.into_values().collect::<Vec<_>>();
-                nodes.sort_by(|a, b| {
-                    b.last_seen.cmp(&a.last_seen).then_with(|| a.peer.cmp(&b.peer))
-                });
+                let parsed = request
+                    .params
+                    .map(serde_json::from_value::<ListPropagationNodesParams>)
+                    .transpose()
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?
+                    .unwrap_or_default();
+                let nodes = self.known_propagation_nodes()?;
+                let relay_candidates = self.propagation_relay_candidates(&parsed.exclude)?;
                 Ok(RpcResponse {
                     id: request.id,
                     result: Some(json!({
                         "nodes": nodes,
+                        "selection_strategy": self.sdk_propagation_selection_strategy(),
+                        "relay_candidates": relay_candidates,
                         "meta": self.response_meta(),
                     })),
                     error: None,
@@ -237,4 +467,266 @@ impl RpcDaemon {
         }
     }
 
+    /// Message count and decoded byte size of the propagation store.
+    fn propagation_store_usage(&self) -> (usize, usize) {
+        let payloads =
+            self.propagation_payloads.lock().expect("propagation payload mutex poisoned");
+        let now_ms = now_millis_u64();
+        let live = payloads
+            .values()
+            .filter(|payload| payload.expires_at_ms.map_or(true, |at| at > now_ms))
+            .collect::<Vec<_>>();
+        let bytes = live.iter().map(|payload| payload.payload_hex.len() / 2).sum::<usize>();
+        (live.len(), bytes)
+    }
+
+    /// Sync progress as reported by `sync_now` and `propagation_sync_progress`
+    /// events.
+    fn propagation_sync_json(state: &PropagationState) -> JsonValue {
+        json!({
+            "state": state.sync_state,
+            "state_name": if state.state_name.is_empty() { "idle" } else { &state.state_name },
+            "progress": state.sync_progress,
+            "selected_node": state.selected_node,
+            "messages_received": state.messages_received,
+            "last_sync_started": state.last_sync_started,
+            "last_sync_error": state.last_sync_error,
+        })
+    }
+
+    /// Drops propagated messages whose TTL has passed, remembering their ids
+    /// so a later fetch can report them as expired rather than unknown.
+    fn purge_expired_propagation_payloads(&self, now_ms: u64) {
+        let mut payloads =
+            self.propagation_payloads.lock().expect("propagation payload mutex poisoned");
+        let expired = payloads
+            .iter()
+            .filter(|(_, payload)| payload.expires_at_ms.is_some_and(|at| at <= now_ms))
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        if expired.is_empty() {
+            return;
+        }
+        let mut expired_ids =
+            self.propagation_expired_ids.lock().expect("propagation expired ids mutex poisoned");
+        for id in expired {
+            payloads.remove(&id);
+            expired_ids.push_back(id);
+        }
+        while expired_ids.len() > PROPAGATION_EXPIRED_ID_CAPACITY {
+            expired_ids.pop_front();
+        }
+    }
+
+    /// Stamp cost this node currently asks of propagated messages: the
+    /// configured cost, or under `StampCostMode::Adaptive` a cost that follows
+    /// the propagation store's fill level.
+    pub fn required_propagation_stamp_cost(&self) -> u32 {
+        let cost = self
+            .propagation_state
+            .lock()
+            .expect("propagation mutex poisoned")
+            .target_cost
+            .max(PROPAGATION_COST_MIN);
+        let policy = self.stamp_policy.lock().expect("stamp mutex poisoned").clone();
+        match policy.mode {
+            StampCostMode::Static => cost,
+            StampCostMode::Adaptive => {
+                adaptive_stamp_cost(cost, policy.flexibility, self.propagation_store_usage().1)
+            }
+        }
+    }
+
+    /// Lowest stamp value `propagation_ingest` accepts: the required cost less
+    /// the policy flexibility, the bottom of `stamp_status`'s `accepted_range`.
+    /// `None` while no stamp cost is configured and stamps are not required.
+    fn accepted_propagation_stamp_value(&self) -> Option<u32> {
+        let configured =
+            self.propagation_state.lock().expect("propagation mutex poisoned").target_cost;
+        if configured == 0 {
+            return None;
+        }
+        let flexibility = self.stamp_policy.lock().expect("stamp mutex poisoned").flexibility;
+        Some(self.required_propagation_stamp_cost().saturating_sub(flexibility))
+    }
+
+    /// Hands the cost `propagation_ingest` enforces to the announce bridge so
+    /// propagation announces advertise it.
+    pub(super) fn advertise_propagation_stamp_cost(&self) {
+        let Some(bridge) = &self.announce_bridge else {
+            return;
+        };
+        let flexibility = self.stamp_policy.lock().expect("stamp mutex poisoned").flexibility;
+        let _ =
+            bridge.set_propagation_stamp_cost(self.required_propagation_stamp_cost(), flexibility);
+    }
+
+    fn known_propagation_nodes(&self) -> Result<Vec<PropagationNodeRecord>, std::io::Error> {
+        let selected =
+            self.outbound_propagation_node.lock().expect("propagation node mutex poisoned").clone();
+        let announces =
+            self.store.list_announces(500, None, None).map_err(std::io::Error::other)?;
+        let mut by_peer: HashMap<String, PropagationNodeRecord> = HashMap::new();
+        for announce in announces {
+            if !announce.capabilities.iter().any(|cap| cap == "propagation") {
+                continue;
+            }
+
+            let key = announce.peer.clone();
+            let entry = by_peer.entry(key.clone()).or_insert_with(|| PropagationNodeRecord {
+                peer: key.clone(),
+                name: announce.name.clone(),
+                last_seen: announce.timestamp,
+                capabilities: announce.capabilities.clone(),
+                peering_cost: announce.peering_cost,
+                selected: selected.as_deref() == Some(key.as_str()),
+            });
+            if announce.timestamp > entry.last_seen {
+                entry.last_seen = announce.timestamp;
+                entry.name = announce.name.clone();
+                entry.capabilities = announce.capabilities.clone();
+                entry.peering_cost = announce.peering_cost;
+            }
+            if selected.as_deref() == Some(key.as_str()) {
+                entry.selected = true;
+            }
+        }
+
+        let mut nodes = by_peer.into_values().collect::<Vec<_>>();
+        nodes.sort_by(|a, b| b.last_seen.cmp(&a.last_seen).then_with(|| a.peer.cmp(&b.peer)));
+        Ok(nodes)
+    }
+
+    /// Orders propagation relays under the configured
+    /// `propagation_selection_strategy`: `selected` tries the selected node
+    /// and then the other known nodes by recency, `lowest_cost` sorts by
+    /// announced peering cost and `most_recent` by last announce. Nodes in
+    /// `failed` were already tried in the current attempt and are left out.
+    fn propagation_relay_candidates(&self, failed: &[String]) -> Result<Vec<String>, std::io::Error> {
+        let mut nodes = self.known_propagation_nodes()?;
+        let mut candidates = Vec::with_capacity(nodes.len() + 1);
+        match self.sdk_propagation_selection_strategy().as_str() {
+            "lowest_cost" => nodes.sort_by(|a, b| {
+                a.peering_cost
+                    .unwrap_or(u32::MAX)
+                    .cmp(&b.peering_cost.unwrap_or(u32::MAX))
+                    .then_with(|| b.last_seen.cmp(&a.last_seen))
+                    .then_with(|| a.peer.cmp(&b.peer))
+            }),
+            "most_recent" => {}
+            _ => {
+                if let Some(selected) = self
+                    .outbound_propagation_node
+                    .lock()
+                    .expect("propagation node mutex poisoned")
+                    .clone()
+                {
+                    candidates.push(selected);
+                }
+            }
+        }
+        for node in nodes {
+            if !candidates.contains(&node.peer) {
+                candidates.push(node.peer);
+            }
+        }
+        candidates.retain(|peer| !failed.contains(peer));
+        Ok(candidates)
+    }
+
+    /// Staleness window for the selected propagation node when auto-reselect is
+    /// enabled via `extensions.propagation_reselect`.
+    fn propagation_reselect_stale_after_secs(&self) -> Option<i64> {
+        let config =
+            self.sdk_runtime_config.lock().expect("sdk_runtime_config mutex poisoned").clone();
+        let reselect =
+            config.get("extensions").and_then(|value| value.get("propagation_reselect"))?;
+        if !reselect.get("enabled").and_then(JsonValue::as_bool).unwrap_or(false) {
+            return None;
+        }
+        let stale_after_secs = reselect
+            .get("stale_after_secs")
+            .and_then(JsonValue::as_u64)
+            .and_then(|value| i64::try_from(value).ok())
+            .unwrap_or(3_600);
+        Some(stale_after_secs)
+    }
+
+    /// Replaces a selected propagation node that has not been seen within the
+    /// staleness window with the fresh node announcing the lowest stamp cost,
+    /// preferring the most recently seen on a tie.
+    fn reselect_stale_propagation_node(&self, now: i64) -> Result<Option<String>, std::io::Error> {
+        let Some(stale_after_secs) = self.propagation_reselect_stale_after_secs() else {
+            return Ok(None);
+        };
+        if *self.outbound_propagation_node_pinned.lock().expect("propagation pin mutex poisoned") {
+            return Ok(None);
+        }
+        let Some(previous) =
+            self.outbound_propagation_node.lock().expect("propagation node mutex poisoned").clone()
+        else {
+            return Ok(None);
+        };
+
+        self.load_propagation_candidates()?;
+        let cutoff = now.saturating_sub(stale_after_secs);
+        let (previous_last_seen, replacement) = {
+            let candidates = self
+                .propagation_candidates
+                .lock()
+                .expect("propagation candidates mutex poisoned");
+            let previous_last_seen = candidates.last_seen(&previous);
+            if previous_last_seen.is_some_and(|last_seen| last_seen >= cutoff) {
+                return Ok(None);
+            }
+            (previous_last_seen, candidates.replacement(&previous, cutoff))
+        };
+        let Some((peer, replacement)) = replacement else {
+            return Ok(None);
+        };
+
+        *self.outbound_propagation_node.lock().expect("propagation node mutex poisoned") =
+            Some(peer.clone());
+        self.publish_event(RpcEvent {
+            event_type: "propagation_node_reselected".into(),
+            payload: json!({
+                "previous": previous,
+                "previous_last_seen": previous_last_seen,
+                "peer": peer,
+                "peer_last_seen": replacement.last_seen,
+                "stamp_cost": replacement.stamp_cost,
+                "stale_after_secs": stale_after_secs,
+            }),
+        });
+        Ok(Some(peer))
+    }
+
+    /// Seeds the reselection candidates from stored announces the first time
+    /// they are needed, so nodes announced before a restart are considered.
+    /// Nodes already observed since startup keep their live entry.
+    fn load_propagation_candidates(&self) -> Result<(), std::io::Error> {
+        if self.propagation_candidates.lock().expect("propagation candidates mutex poisoned").loaded
+        {
+            return Ok(());
+        }
+        let announces =
+            self.store.list_announces(500, None, None).map_err(std::io::Error::other)?;
+        let mut candidates =
+            self.propagation_candidates.lock().expect("propagation candidates mutex poisoned");
+        let seen = candidates.nodes.keys().cloned().collect::<HashSet<_>>();
+        for announce in announces.iter().filter(|announce| !seen.contains(&announce.peer)) {
+            candidates.observe_announce(announce, None);
+        }
+        candidates.loaded = true;
+        Ok(())
+    }
+}
+
+/// Send time of a propagated payload that is a readable LXMF message, taken
+/// from its own timestamp. Encrypted payloads are opaque to the node and
+/// yield `None`, so their lifetime starts on receipt.
+fn propagated_message_timestamp_ms(payload_hex: &str) -> Option<u64> {
+    let payload = hex::decode(payload_hex).ok()?;
+    let timestamp = WireMessage::unpack(&payload).ok()?.payload.timestamp;
+    (timestamp.is_finite() && timestamp >= 0.0).then_some((timestamp * 1000.0) as u64)
 }
@@ -1,10 +1,10 @@
 impl RpcDaemon {
     fn handle_rpc_legacy(&self, request: RpcRequest) -> Result<RpcResponse, std::io::Error> {
         match request.method.as_str() {
-            "list_messages" | "sdk_poll_events_v2" | "list_announces" | "list_peers" | "list_interfaces" | "set_interfaces" | "reload_config" | "peer_sync" | "peer_unpeer" | "send_message" | "send_message_v2" | "sdk_send_v2" | "receive_message" | "record_receipt" | "sdk_cancel_message_v2" | "message_delivery_trace" => self.handle_rpc_legacy_messages(request),
-            "get_delivery_policy" | "set_delivery_policy" | "propagation_status" | "propagation_enable" | "propagation_ingest" | "propagation_fetch" | "get_outbound_propagation_node" | "set_outbound_propagation_node" | "list_propagation_nodes" => self.handle_rpc_legacy_propagation(request),
-            "paper_ingest_uri" | "stamp_policy_get" | "stamp_policy_set" | "ticket_generate" | "announce_now" | "announce_received" => self.handle_rpc_legacy_misc(request),
-            "clear_messages" | "clear_resources" | "clear_peers" | "clear_all" => self.handle_rpc_legacy_clear(request),
+            "list_messages" | "messages_search" | "messages_export" | "attachment_get" | "sdk_poll_events_v2" | "list_announces" | "list_peers" | "peers_export" | "peers_import" | "peer_describe" | "peer_rtt" | "peer_ping" | "list_interfaces" | "set_interfaces" | "reconnect" | "reload_config" | "peer_sync" | "peer_unpeer" | "send_message" | "send_message_v2" | "sdk_send_v2" | "receive_message" | "record_receipt" | "sdk_cancel_message_v2" | "message_delivery_trace" | "delivery_trace" | "trace_get" | "delivery_metrics" => self.handle_rpc_legacy_messages(request),
+            "get_delivery_policy" | "set_delivery_policy" | "propagation_status" | "propagation_enable" | "propagation_ingest" | "propagation_fetch" | "propagation_stats" | "sync_now" | "stamp_status" | "get_outbound_propagation_node" | "set_outbound_propagation_node" | "list_propagation_nodes" => self.handle_rpc_legacy_propagation(request),
+            "paper_ingest_uri" | "stamp_policy_get" | "stamp_policy_set" | "ticket_generate" | "tickets_list" | "ticket_revoke" | "announce_now" | "announce_once_to" | "identity_rotate" | "set_display_name" | "announce_received" | "logs_tail" | "health" => self.handle_rpc_legacy_misc(request),
+            "clear_messages" | "prune_messages" | "clear_resources" | "clear_peers" | "peers_prune" | "clear_all" => self.handle_rpc_legacy_clear(request),
             _ => Ok(RpcResponse {
                 id: request.id,
                 result: None,
@@ -9,7 +9,7 @@ impl RpcDaemon {
             .unwrap_or("drop_oldest")
             .trim()
             .to_ascii_lowercase();
-        if matches!(configured.as_str(), "reject" | "drop_oldest" | "block") {
+        if matches!(configured.as_str(), "reject" | "drop_oldest" | "drop_newest" | "block") {
             configured
         } else {
             "drop_oldest".to_string()
@@ -32,7 +32,7 @@ impl RpcDaemon {
         block_timeout_ms: u64,
     ) -> bool {
         match policy {
-            "reject" => {
+            "reject" | "drop_newest" => {
                 let mut guard = self.event_queue.lock().expect("event_queue mutex poisoned");
                 if guard.len() >= LEGACY_EVENT_QUEUE_CAPACITY {
                     return false;
@@ -77,7 +77,7 @@ impl RpcDaemon {
         block_timeout_ms: u64,
     ) -> bool {
         match policy {
-            "reject" => {
+            "reject" | "drop_newest" => {
                 let mut log_guard = self.sdk_event_log.lock().expect("sdk_event_log mutex poisoned");
                 if log_guard.len() >= SDK_EVENT_LOG_CAPACITY {
                     return false;
@@ -121,6 +121,118 @@ impl RpcDaemon {
         }
     }
 
+    fn sdk_event_persist_limits(&self) -> EventPersistLimits {
+        let config = self.sdk_runtime_config.lock().expect("sdk_runtime_config mutex poisoned");
+        let event_stream = config.get("event_stream");
+        let limit = |key: &str| event_stream.and_then(|value| value.get(key)?.as_u64());
+        EventPersistLimits {
+            max_bytes: limit("persist_max_bytes").unwrap_or(DEFAULT_EVENT_PERSIST_MAX_BYTES),
+            max_age_secs: limit("persist_max_age_secs")
+                .unwrap_or(DEFAULT_EVENT_PERSIST_MAX_AGE_SECS),
+        }
+    }
+
+    fn read_persisted_sdk_events(
+        log: &JsonlLog,
+        limits: EventPersistLimits,
+        now: i64,
+    ) -> Vec<PersistedRpcEvent> {
+        let Ok(raw) = log.read_to_string() else {
+            return Vec::new();
+        };
+        let cutoff = now.saturating_sub(i64::try_from(limits.max_age_secs).unwrap_or(i64::MAX));
+        let mut records = raw
+            .lines()
+            .filter_map(|line| serde_json::from_str::<PersistedRpcEvent>(line).ok())
+            .filter(|record| record.persisted_at >= cutoff)
+            .collect::<Vec<_>>();
+        records.sort_by_key(|record| record.seq_no);
+        records.dedup_by_key(|record| record.seq_no);
+        records
+    }
+
+    /// Rewrites the on-disk log keeping only unexpired events, newest first, within
+    /// half of `max_bytes` so compaction does not run again on the next append.
+    fn compact_persisted_sdk_events(
+        log: &mut JsonlLog,
+        limits: EventPersistLimits,
+        now: i64,
+    ) -> Result<(), std::io::Error> {
+        let lines = Self::read_persisted_sdk_events(log, limits, now)
+            .iter()
+            .filter_map(|record| serde_json::to_string(record).ok())
+            .collect::<Vec<_>>();
+        let budget = limits.max_bytes / 2;
+        let mut retained_bytes = 0_u64;
+        let mut keep_from = lines.len();
+        for (index, line) in lines.iter().enumerate().rev() {
+            let next_bytes = retained_bytes.saturating_add(line.len() as u64 + 1);
+            if next_bytes > budget {
+                break;
+            }
+            retained_bytes = next_bytes;
+            keep_from = index;
+        }
+        let mut body = String::new();
+        for line in &lines[keep_from..] {
+            body.push_str(line);
+            body.push('\n');
+        }
+        log.replace(body.as_bytes())
+    }
+
+    fn persist_sdk_event(&self, seq_no: u64, event: &RpcEvent) {
+        if event.event_type == "sdk_lifecycle_trace" {
+            return;
+        }
+        let limits = self.sdk_event_persist_limits();
+        let mut persist_guard =
+            self.sdk_event_persist.lock().expect("sdk_event_persist mutex poisoned");
+        let Some(log) = persist_guard.as_mut() else {
+            return;
+        };
+        let now = now_i64();
+        let record = PersistedRpcEvent { seq_no, persisted_at: now, event: event.clone() };
+        let Ok(mut line) = serde_json::to_string(&record) else {
+            return;
+        };
+        line.push('\n');
+        if log.append(line.as_bytes()).is_err() {
+            return;
+        }
+        if log.len() > limits.max_bytes {
+            let _ = Self::compact_persisted_sdk_events(log, limits, now);
+        }
+    }
+
+    /// Reloads persisted events into an empty event log at startup and resumes
+    /// sequence numbering after them, so cursors issued before a restart stay valid.
+    fn restore_persisted_sdk_events(&self, log: &JsonlLog) {
+        let records =
+            Self::read_persisted_sdk_events(log, self.sdk_event_persist_limits(), now_i64());
+        let Some(last_seq) = records.last().map(|record| record.seq_no) else {
+            return;
+        };
+
+        let mut log_guard = self.sdk_event_log.lock().expect("sdk_event_log mutex poisoned");
+        if !log_guard.is_empty() {
+            return;
+        }
+        let skip = records.len().saturating_sub(SDK_EVENT_LOG_CAPACITY);
+        for record in records.into_iter().skip(skip) {
+            log_guard.push_back(SequencedRpcEvent { seq_no: record.seq_no, event: record.event });
+        }
+        // Everything before the restored tail was compacted away or expired.
+        let lost = log_guard.front().map_or(0, |entry| entry.seq_no.saturating_sub(1));
+        let mut dropped =
+            self.sdk_dropped_event_count.lock().expect("sdk_dropped_event_count mutex poisoned");
+        *dropped = (*dropped).max(lost);
+        drop(dropped);
+        let mut seq_guard =
+            self.sdk_next_event_seq.lock().expect("sdk_next_event_seq mutex poisoned");
+        *seq_guard = (*seq_guard).max(last_seq);
+    }
+
     fn redaction_enabled(&self) -> bool {
         self.sdk_runtime_config
             .lock()
@@ -202,7 +314,7 @@ impl RpcDaemon {
         }
 
         let envelope = RpcEventSinkEnvelope {
-            contract_release: "v2.5".to_string(),
+            contract_release: CONTRACT_RELEASE.to_string(),
             runtime_id: self.identity_hash.clone(),
             stream_id: SDK_STREAM_ID.to_string(),
             seq_no,
@@ -244,6 +356,8 @@ impl RpcDaemon {
                 | "shared_secret"
                 | "authorization"
                 | "token"
+                | "api_key"
+                | "x-api-key"
                 | "passphrase"
         )
     }
@@ -316,6 +430,72 @@ impl RpcDaemon {
         self.events.subscribe()
     }
 
+    /// Opens a live event stream. With a cursor, retained events after it are
+    /// replayed before live ones; the receiver is subscribed while the event log
+    /// is locked so nothing published in between is missed.
+    #[allow(clippy::result_large_err)]
+    pub fn open_event_stream(
+        &self,
+        cursor: Option<&str>,
+    ) -> Result<event_stream::EventStream, RpcError> {
+        let cursor_seq = self
+            .sdk_decode_cursor(cursor)
+            .map_err(|error| RpcError::new(error.code, error.message))?;
+        let log_guard = self.sdk_event_log.lock().expect("sdk_event_log mutex poisoned");
+        let oldest_seq = log_guard.front().map(|entry| entry.seq_no);
+        if let Some(error) = self.sdk_cursor_expired_error(cursor_seq, oldest_seq) {
+            return Err(error);
+        }
+        let receiver = self.events.subscribe();
+        let replay = match cursor_seq {
+            Some(cursor_seq) => log_guard
+                .iter()
+                .filter(|entry| entry.seq_no > cursor_seq)
+                .map(|entry| entry.event.clone())
+                .collect(),
+            None => VecDeque::new(),
+        };
+        drop(log_guard);
+        Ok(event_stream::EventStream::new(
+            receiver,
+            replay,
+            self.sdk_max_event_bytes(),
+            self.sdk_max_batch_bytes(),
+        ))
+    }
+
+    /// Returns `SDK_RUNTIME_CURSOR_EXPIRED` when the retained event window no
+    /// longer covers `cursor_seq`: it is older than the oldest retained event,
+    /// or ahead of every sequence number issued since startup because the
+    /// events it pointed at were not persisted across a restart. Details carry
+    /// the current `snapshot_high_watermark_seq_no` to resume from and, for an
+    /// old cursor, the `dropped_count` of events it can no longer see.
+    fn sdk_cursor_expired_error(
+        &self,
+        cursor_seq: Option<u64>,
+        oldest_seq: Option<u64>,
+    ) -> Option<RpcError> {
+        let cursor_seq = cursor_seq?;
+        let latest_seq =
+            *self.sdk_next_event_seq.lock().expect("sdk_next_event_seq mutex poisoned");
+        let dropped_count = if cursor_is_expired(Some(cursor_seq), oldest_seq) {
+            oldest_seq.map(|oldest| oldest.saturating_sub(cursor_seq.saturating_add(1)))
+        } else if cursor_seq > latest_seq {
+            None
+        } else {
+            return None;
+        };
+        let mut error =
+            RpcError::new("SDK_RUNTIME_CURSOR_EXPIRED", "cursor is outside retained event window");
+        let mut details = JsonMap::new();
+        details.insert("cursor_seq_no".to_string(), json!(cursor_seq));
+        details.insert("oldest_seq_no".to_string(), json!(oldest_seq));
+        details.insert("snapshot_high_watermark_seq_no".to_string(), json!(latest_seq));
+        details.insert("dropped_count".to_string(), json!(dropped_count));
+        error.details = Some(Box::new(details));
+        Some(error)
+    }
+
     pub fn take_event(&self) -> Option<RpcEvent> {
         let mut guard = self.event_queue.lock().expect("event_queue mutex poisoned");
         guard.pop_front()
@@ -339,7 +519,9 @@ impl RpcDaemon {
             policy.as_str(),
             block_timeout_ms,
         );
-        if !inserted {
+        if inserted {
+            self.persist_sdk_event(seq_no, &event);
+        } else {
             let mut dropped = self
                 .sdk_dropped_event_count
                 .lock()
@@ -352,6 +534,10 @@ impl RpcDaemon {
     }
 
     pub fn publish_event(&self, event: RpcEvent) {
+        if event.event_type == "announce_sent" {
+            *self.last_announce_ts_ms.lock().expect("last_announce mutex poisoned") =
+                Some(now_millis_u64());
+        }
         let event = self.push_event(event);
         let _ = self.events.send(event);
     }
@@ -369,19 +555,78 @@ impl RpcDaemon {
         self.publish_event(event);
     }
 
-    pub fn start_announce_scheduler(
+    /// Polls the scheduled send queue so messages queued with a future
+    /// `scheduled_ts_ms` are dispatched once their send time passes.
+    pub fn start_scheduled_send_loop(
         self: std::rc::Rc<Self>,
-        interval_secs: u64,
+        poll_interval: Duration,
     ) -> tokio::task::JoinHandle<()> {
         tokio::task::spawn_local(async move {
-            if interval_secs == 0 {
-                return;
+            let mut interval = tokio::time::interval(poll_interval);
+            loop {
+                interval.tick().await;
+                self.dispatch_due_scheduled_sends();
             }
+        })
+    }
 
-            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+    /// Polls the receipt watches so messages that stop making progress are
+    /// failed with a `timeout` reason instead of staying pending forever.
+    pub fn start_receipt_timeout_loop(
+        self: std::rc::Rc<Self>,
+        poll_interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::task::spawn_local(async move {
+            let mut interval = tokio::time::interval(poll_interval);
             loop {
-                // First tick is immediate, so we announce once at scheduler start.
                 interval.tick().await;
+                self.expire_stale_receipts();
+            }
+        })
+    }
+
+    /// Announces every `interval_secs` unless `announce_interval_secs` is
+    /// configured, re-arming when that config changes. With no effective
+    /// interval the scheduler idles until one is configured.
+    pub fn start_announce_scheduler(
+        self: std::rc::Rc<Self>,
+        interval_secs: u64,
+    ) -> tokio::task::JoinHandle<()> {
+        *self
+            .announce_interval_default_secs
+            .lock()
+            .expect("announce_interval_default_secs mutex poisoned") = interval_secs;
+        tokio::task::spawn_local(async move {
+            let mut interval_secs = self.effective_announce_interval_secs();
+            // First tick is immediate, so we announce once at scheduler start.
+            let mut interval = (interval_secs > 0)
+                .then(|| tokio::time::interval(Duration::from_secs(interval_secs)));
+            loop {
+                let tick = async {
+                    match interval.as_mut() {
+                        Some(interval) => {
+                            interval.tick().await;
+                        }
+                        None => std::future::pending().await,
+                    }
+                };
+                tokio::select! {
+                    _ = tick => {}
+                    _ = self.announce_rearm.notified() => {
+                        let next_secs = self.effective_announce_interval_secs();
+                        if next_secs != interval_secs {
+                            // Start the new period from now rather than
+                            // announcing immediately on every change.
+                            interval_secs = next_secs;
+                            interval = (interval_secs > 0).then(|| {
+                                let period = Duration::from_secs(interval_secs);
+                                tokio::time::interval_at(tokio::time::Instant::now() + period, period)
+                            });
+                        }
+                        continue;
+                    }
+                }
+
                 let id = std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH)
                     .map(|value| value.as_secs())
@@ -413,6 +658,7 @@ impl RpcDaemon {
             direction: "in".into(),
             fields: None,
             receipt_status: None,
+            reply_to: None,
         };
         let _ = self.store.insert_message(&record);
         let event =
@@ -47,23 +47,31 @@ impl RpcDaemon {
         let daemon = Self {
             store,
             identity_hash,
+            rotated_identity_hash: Mutex::new(None),
             delivery_destination_hash: Mutex::new(None),
+            local_display_name: Mutex::new(None),
             events,
             event_queue: Mutex::new(VecDeque::new()),
             sdk_event_log: Mutex::new(VecDeque::new()),
             sdk_next_event_seq: Mutex::new(0),
             sdk_dropped_event_count: Mutex::new(0),
+            sdk_event_persist: Mutex::new(None),
+            dead_letter_log: Mutex::new(None),
+            sdk_audit_log: Mutex::new(None),
             sdk_active_contract_version: Mutex::new(2),
             sdk_profile: Mutex::new("desktop-full".to_string()),
             sdk_config_revision: Mutex::new(0),
+            sdk_snapshot_revisions: Mutex::new(SnapshotRevisions::default()),
             sdk_runtime_config: Mutex::new(JsonValue::Object(JsonMap::new())),
             sdk_config_apply_lock: Mutex::new(()),
             sdk_effective_capabilities: Mutex::new(Self::sdk_supported_capabilities()),
             sdk_stream_degraded: Mutex::new(false),
+            sdk_draining: Mutex::new(false),
             sdk_seen_jti: Mutex::new(HashMap::new()),
             sdk_rate_window_started_ms: Mutex::new(0),
             sdk_rate_ip_counts: Mutex::new(HashMap::new()),
             sdk_rate_principal_counts: Mutex::new(HashMap::new()),
+            sdk_destination_send_times: Mutex::new(HashMap::new()),
             sdk_domain_state_lock: Mutex::new(()),
             sdk_next_domain_seq: Mutex::new(0),
             sdk_topics: Mutex::new(HashMap::new()),
@@ -84,21 +92,51 @@ impl RpcDaemon {
             sdk_voice_sessions: Mutex::new(HashMap::new()),
             peers: Mutex::new(HashMap::new()),
             interfaces: Mutex::new(Vec::new()),
+            interfaces_down: Mutex::new(HashSet::new()),
+            interface_link_states: Mutex::new(HashMap::new()),
+            interface_traffic: Mutex::new(HashMap::new()),
+            last_announce_ts_ms: Mutex::new(None),
+            announce_interval_default_secs: Mutex::new(0),
+            announce_rearm: tokio::sync::Notify::new(),
             delivery_policy: Mutex::new(DeliveryPolicy::default()),
             propagation_state: Mutex::new(PropagationState::default()),
             propagation_payloads: Mutex::new(HashMap::new()),
+            propagation_expired_ids: Mutex::new(VecDeque::new()),
             outbound_propagation_node: Mutex::new(None),
+            outbound_propagation_node_pinned: Mutex::new(false),
+            propagation_candidates: Mutex::new(PropagationCandidates::default()),
             paper_ingest_seen: Mutex::new(HashSet::new()),
             stamp_policy: Mutex::new(StampPolicy::default()),
+            stamp_tasks: Mutex::new(HashMap::new()),
             ticket_cache: Mutex::new(HashMap::new()),
+            revoked_tickets: Mutex::new(HashSet::new()),
             delivery_traces: Mutex::new(HashMap::new()),
             delivery_status_lock: Mutex::new(()),
             sdk_metrics: Mutex::new(RpcMetrics::default()),
+            daemon_log: Mutex::new(DaemonLogBuffer::default()),
+            inbound_dedup: Mutex::new(InboundDedupWindow::default()),
+            peer_rtt: Mutex::new(PeerRttState::default()),
+            peer_pings: Mutex::new(VecDeque::new()),
+            scheduled_sends: Mutex::new(Vec::new()),
+            receipt_watches: Mutex::new(HashMap::new()),
+            inbound_pending_probe: Mutex::new(None),
             outbound_bridge,
             announce_bridge,
             event_sink_bridges,
         };
         let _ = daemon.restore_sdk_domain_snapshot();
+        if let Err(err) = daemon.restore_scheduled_sends() {
+            daemon.record_log_line(format!("scheduled sends not restored: {err}"));
+        }
+        if let Err(err) = daemon.restore_receipt_watches() {
+            daemon.record_log_line(format!("receipt watches not restored: {err}"));
+        }
+        if let Err(err) = daemon.restore_peer_identities() {
+            daemon.record_log_line(format!("peer identities not restored: {err}"));
+        }
+        if let Err(err) = daemon.restore_revoked_tickets() {
+            daemon.record_log_line(format!("revoked tickets not restored: {err}"));
+        }
         daemon
     }
 
@@ -113,6 +151,54 @@ impl RpcDaemon {
         Self::with_store(store, identity.into())
     }
 
+    /// Persists the SDK event log to `path` so cursors survive restarts, after
+    /// replaying whatever an earlier run left there. The location is fixed by
+    /// the host at startup; runtime configuration only tunes retention.
+    pub fn enable_event_persistence(
+        &self,
+        path: impl Into<std::path::PathBuf>,
+    ) -> Result<(), std::io::Error> {
+        let log = JsonlLog::open(path)?;
+        self.restore_persisted_sdk_events(&log);
+        *self.sdk_event_persist.lock().expect("sdk_event_persist mutex poisoned") = Some(log);
+        Ok(())
+    }
+
+    /// Appends messages dropped by store-and-forward expiry to `path`. Like the
+    /// event log, the location is the host's choice and not a runtime setting.
+    /// Once the file passes `max_bytes` it is moved to `<path>.1`, replacing the
+    /// previous rotation, so at most two files' worth is kept on disk.
+    pub fn enable_dead_letter_log(
+        &self,
+        path: impl Into<std::path::PathBuf>,
+        max_bytes: u64,
+    ) -> Result<(), std::io::Error> {
+        let log = JsonlLog::open(path)?;
+        *self.dead_letter_log.lock().expect("dead_letter_log mutex poisoned") =
+            Some(DeadLetterSink { log, max_bytes });
+        Ok(())
+    }
+
+    /// Records HTTP auth denials, and allowed decisions when `include_allowed`
+    /// is set, to `path`. Clients cannot redirect or disable the audit sink, so
+    /// it is only configurable here at startup.
+    pub fn enable_security_audit_log(
+        &self,
+        path: impl Into<std::path::PathBuf>,
+        include_allowed: bool,
+    ) -> Result<(), std::io::Error> {
+        let log = JsonlLog::open(path)?;
+        *self.sdk_audit_log.lock().expect("sdk_audit_log mutex poisoned") =
+            Some(SecurityAuditSink { log, include_allowed });
+        Ok(())
+    }
+
+    /// Lets `sdk_snapshot_v2` report the host's inbound decode backlog.
+    pub fn set_inbound_pending_probe(&self, probe: InboundPendingProbe) {
+        *self.inbound_pending_probe.lock().expect("inbound_pending_probe mutex poisoned") =
+            Some(probe);
+    }
+
     pub fn set_delivery_destination_hash(&self, hash: Option<String>) {
         let mut guard = self
             .delivery_destination_hash
@@ -128,36 +214,412 @@ impl RpcDaemon {
         });
     }
 
+    /// Display name this node announces, as reported by `whoami`.
+    pub fn set_local_display_name(&self, name: Option<String>) {
+        *self.local_display_name.lock().expect("local_display_name mutex poisoned") =
+            name.map(|value| value.trim().to_string()).filter(|value| !value.is_empty());
+    }
+
     pub fn replace_interfaces(&self, interfaces: Vec<InterfaceRecord>) {
         let mut guard = self.interfaces.lock().expect("interfaces mutex poisoned");
         *guard = interfaces;
     }
 
+    /// Reports whether an interface currently has a working link. Interfaces
+    /// are keyed by name, falling back to their type, and enabled interfaces
+    /// count as up until a transport reports otherwise.
+    pub fn set_interface_link_up(&self, name: &str, up: bool) {
+        let state =
+            if up { InterfaceLinkState::Connected } else { InterfaceLinkState::Disconnected };
+        self.set_interface_link_state(name, state, None);
+    }
+
+    /// Records an interface connection transition and publishes an
+    /// `interface_link_state` event. Repeating the current state, as a
+    /// reconnect loop does on every backoff tick, emits nothing.
+    pub fn set_interface_link_state(
+        &self,
+        name: &str,
+        state: InterfaceLinkState,
+        detail: Option<&str>,
+    ) {
+        {
+            let mut states =
+                self.interface_link_states.lock().expect("interface_link_states mutex poisoned");
+            if states.insert(name.to_string(), state) == Some(state) {
+                return;
+            }
+            let mut down = self.interfaces_down.lock().expect("interfaces_down mutex poisoned");
+            if state == InterfaceLinkState::Connected {
+                down.remove(name);
+            } else {
+                down.insert(name.to_string());
+            }
+        }
+        self.publish_event(RpcEvent {
+            event_type: "interface_link_state".into(),
+            payload: json!({
+                "interface_name": name,
+                "state": state,
+                "detail": detail,
+            }),
+        });
+    }
+
+    /// Stores the latest traffic counters for an interface, keyed like
+    /// [`RpcDaemon::set_interface_link_up`]. Counters are cumulative, so each
+    /// report replaces the previous one.
+    pub fn set_interface_traffic(&self, name: &str, traffic: InterfaceTraffic) {
+        self.interface_traffic
+            .lock()
+            .expect("interface_traffic mutex poisoned")
+            .insert(name.to_string(), traffic);
+    }
+
     pub fn set_propagation_state(
         &self,
         enabled: bool,
         store_root: Option<String>,
         target_cost: u32,
     ) {
-        let mut guard = self.propagation_state.lock().expect("propagation mutex poisoned");
-        guard.enabled = enabled;
-        guard.store_root = store_root;
-        guard.target_cost = target_cost;
+        {
+            let mut guard = self.propagation_state.lock().expect("propagation mutex poisoned");
+            guard.enabled = enabled;
+            guard.store_root = store_root;
+            guard.target_cost = target_cost;
+        }
+        self.advertise_propagation_stamp_cost();
+        if let Some(bridge) = &self.announce_bridge {
+            let _ = bridge.set_propagation_node(enabled);
+        }
+    }
+
+    /// Switches propagation-node mode live. Already stored propagation
+    /// payloads are kept; enabling announces at once so peers learn the
+    /// new role promptly.
+    fn set_propagation_node_role(&self, enabled: bool) {
+        let was_enabled = std::mem::replace(
+            &mut self.propagation_state.lock().expect("propagation mutex poisoned").enabled,
+            enabled,
+        );
+        if let Some(bridge) = &self.announce_bridge {
+            let _ = bridge.set_propagation_node(enabled);
+        }
+        if enabled && !was_enabled {
+            self.advertise_propagation_stamp_cost();
+            if let Some(bridge) = &self.announce_bridge {
+                let _ = bridge.announce_now();
+            }
+            self.publish_event(RpcEvent {
+                event_type: "announce_sent".into(),
+                payload: json!({ "timestamp": now_i64(), "propagation_node": true }),
+            });
+        }
     }
 
+    /// Applies a propagation sync transition, emitting a
+    /// `propagation_sync_progress` event when the state or progress moved.
     pub fn update_propagation_sync_state<F>(&self, update: F)
     where
         F: FnOnce(&mut PropagationState),
     {
+        let (before, after) = {
+            let mut guard = self.propagation_state.lock().expect("propagation mutex poisoned");
+            let before = (guard.sync_state, guard.sync_progress, guard.state_name.clone());
+            update(&mut guard);
+            (before, guard.clone())
+        };
+        if before != (after.sync_state, after.sync_progress, after.state_name.clone()) {
+            self.publish_event(RpcEvent {
+                event_type: "propagation_sync_progress".into(),
+                payload: Self::propagation_sync_json(&after),
+            });
+        }
+    }
+
+    /// Counts a propagated message refused for a missing or too cheap stamp.
+    pub fn record_propagation_stamp_rejected(&self) {
         let mut guard = self.propagation_state.lock().expect("propagation mutex poisoned");
-        update(&mut guard);
+        guard.rejected_stamps += 1;
+    }
+
+    pub fn record_log_line(&self, line: impl Into<String>) {
+        let mut guard = self.daemon_log.lock().expect("daemon log mutex poisoned");
+        guard.lines.push_back(line.into());
+        while guard.lines.len() > DAEMON_LOG_CAPACITY {
+            guard.lines.pop_front();
+            guard.first_offset += 1;
+        }
+    }
+
+    pub fn record_peer_rtt_sample(&self, peer: &str, rtt_ms: u64) {
+        let mut guard = self.peer_rtt.lock().expect("peer rtt mutex poisoned");
+        let samples = guard.samples.entry(peer.to_string()).or_default();
+        samples.push_back(rtt_ms);
+        while samples.len() > PEER_RTT_WINDOW {
+            samples.pop_front();
+        }
     }
 
-    fn store_inbound_record(&self, record: MessageRecord) -> Result<(), std::io::Error> {
+    fn start_rtt_timer(&self, message_id: &str, peer: &str) {
+        self.start_rtt_timer_at(message_id, peer, now_millis_u64());
+    }
+
+    /// Timers for sends that never settle are dropped once they pass
+    /// `PEER_RTT_PENDING_MAX_AGE_MS`; if the table is still full, the oldest
+    /// one makes room, so new sends are always measured.
+    fn start_rtt_timer_at(&self, message_id: &str, peer: &str, now_ms: u64) {
+        let mut guard = self.peer_rtt.lock().expect("peer rtt mutex poisoned");
+        if guard.pending.len() >= PEER_RTT_PENDING_CAPACITY {
+            guard.pending.retain(|_, (_, sent_at_ms)| {
+                now_ms.saturating_sub(*sent_at_ms) < PEER_RTT_PENDING_MAX_AGE_MS
+            });
+        }
+        if guard.pending.len() >= PEER_RTT_PENDING_CAPACITY {
+            let oldest = guard
+                .pending
+                .iter()
+                .min_by_key(|(_, (_, sent_at_ms))| *sent_at_ms)
+                .map(|(id, _)| id.clone());
+            if let Some(oldest) = oldest {
+                guard.pending.remove(&oldest);
+            }
+        }
+        guard.pending.insert(message_id.to_string(), (peer.to_string(), now_ms));
+    }
+
+    fn finish_rtt_timer(&self, message_id: &str, delivered: bool) {
+        let pending =
+            self.peer_rtt.lock().expect("peer rtt mutex poisoned").pending.remove(message_id);
+        if let Some((peer, sent_at_ms)) = pending.filter(|_| delivered) {
+            self.record_peer_rtt_sample(&peer, now_millis_u64().saturating_sub(sent_at_ms));
+        }
+    }
+
+    /// Starts the receipt timeout for a message the bridge has accepted.
+    fn watch_receipt(&self, message_id: &str, propagated: bool) {
+        self.receipt_watches.lock().expect("receipt watches mutex poisoned").insert(
+            message_id.to_string(),
+            ReceiptWatch { last_progress_ms: now_millis_u64(), propagated },
+        );
+    }
+
+    /// Watches every message an earlier run left waiting for a receipt, so
+    /// it still times out. The timeout counts from this start.
+    fn restore_receipt_watches(&self) -> Result<usize, std::io::Error> {
+        let unsettled = self.store.list_unsettled_outbound().map_err(std::io::Error::other)?;
+        let count = unsettled.len();
+        for (message_id, status) in unsettled {
+            let propagated =
+                status.is_some_and(|status| status.to_ascii_lowercase().contains("propagated"));
+            self.watch_receipt(&message_id, propagated);
+        }
+        Ok(count)
+    }
+
+    /// Restarts the receipt timeout on forward progress, or stops watching
+    /// once the message is terminal.
+    fn note_receipt_progress(&self, message_id: &str, status: &str) {
+        let mut guard = self.receipt_watches.lock().expect("receipt watches mutex poisoned");
+        if Self::is_terminal_receipt_status(status) {
+            guard.remove(message_id);
+        } else if let Some(watch) = guard.get_mut(message_id) {
+            watch.last_progress_ms = now_millis_u64();
+        }
+    }
+
+    fn peer_rtt_summary(&self, peer: &str) -> JsonValue {
+        let mut samples = self
+            .peer_rtt
+            .lock()
+            .expect("peer rtt mutex poisoned")
+            .samples
+            .get(peer)
+            .map(|samples| samples.iter().copied().collect::<Vec<_>>())
+            .unwrap_or_default();
+        samples.sort_unstable();
+        json!({
+            "peer": peer,
+            "rtt_ms_p50": nearest_rank_percentile(&samples, 50),
+            "rtt_ms_p95": nearest_rank_percentile(&samples, 95),
+            "samples": samples.len() as u32,
+        })
+    }
+
+    fn start_peer_ping(&self, request: &PeerPingRequest) {
+        let mut guard = self.peer_pings.lock().expect("peer pings mutex poisoned");
+        guard.push_back(PeerPingRecord {
+            ping_id: request.ping_id.clone(),
+            peer: request.peer.clone(),
+            probe: request.probe,
+            outcome: None,
+            rtt_ms: None,
+            started_at_ms: now_millis_u64(),
+            finished_at_ms: None,
+        });
+        while guard.len() > PEER_PING_HISTORY {
+            guard.pop_front();
+        }
+    }
+
+    fn peer_ping_record(&self, ping_id: &str) -> Option<PeerPingRecord> {
+        let guard = self.peer_pings.lock().expect("peer pings mutex poisoned");
+        guard.iter().find(|record| record.ping_id == ping_id).cloned()
+    }
+
+    fn forget_peer_ping(&self, ping_id: &str) {
+        let mut guard = self.peer_pings.lock().expect("peer pings mutex poisoned");
+        guard.retain(|record| record.ping_id != ping_id);
+    }
+
+    /// Reports the outcome of an `announce_once_to` started on the bridge:
+    /// `announce_sent` when the announce went out, or `announce_skipped`
+    /// when no path to `target` was known and only a path request was sent.
+    pub fn record_directed_announce(&self, target: &str, announced: bool) {
+        let event = if announced {
+            RpcEvent {
+                event_type: "announce_sent".into(),
+                payload: json!({ "timestamp": now_i64(), "target": target }),
+            }
+        } else {
+            RpcEvent {
+                event_type: "announce_skipped".into(),
+                payload: json!({ "timestamp": now_i64(), "target": target, "reason": "no_path" }),
+            }
+        };
+        self.publish_event(event);
+    }
+
+    /// Reports the outcome of the announce `identity_rotate` started for the
+    /// new delivery destination: `announce_sent` once it went out, or
+    /// `announce_failed` with the bridge's error.
+    pub fn record_identity_announce(
+        &self,
+        delivery_destination_hash: &str,
+        result: Result<(), String>,
+    ) {
+        let event = match result {
+            Ok(()) => RpcEvent {
+                event_type: "announce_sent".into(),
+                payload: json!({
+                    "timestamp": now_i64(),
+                    "delivery_destination_hash": delivery_destination_hash,
+                }),
+            },
+            Err(error) => RpcEvent {
+                event_type: "announce_failed".into(),
+                payload: json!({
+                    "timestamp": now_i64(),
+                    "delivery_destination_hash": delivery_destination_hash,
+                    "error": error,
+                }),
+            },
+        };
+        self.publish_event(event);
+    }
+
+    pub fn record_peer_ping_result(
+        &self,
+        ping_id: &str,
+        outcome: PeerPingOutcome,
+        rtt_ms: Option<u64>,
+    ) {
+        let record = {
+            let mut guard = self.peer_pings.lock().expect("peer pings mutex poisoned");
+            let Some(record) = guard.iter_mut().find(|record| record.ping_id == ping_id) else {
+                return;
+            };
+            record.outcome = Some(outcome);
+            record.rtt_ms = rtt_ms.filter(|_| outcome == PeerPingOutcome::Reachable);
+            record.finished_at_ms = Some(now_millis_u64());
+            record.clone()
+        };
+        self.publish_event(RpcEvent {
+            event_type: "peer_ping".into(),
+            payload: json!(record),
+        });
+    }
+
+    pub fn begin_stamp_task(&self, message_id: &str, target_cost: u32) -> StampTaskHandle {
+        let configured =
+            self.stamp_policy.lock().expect("stamp mutex poisoned").generation_workers;
+        let workers = if configured == 0 {
+            std::thread::available_parallelism().map(usize::from).unwrap_or(1)
+        } else {
+            configured
+        };
+        let task = StampTaskHandle { target_cost, workers, cancel: Arc::default() };
+        self.stamp_tasks
+            .lock()
+            .expect("stamp tasks mutex poisoned")
+            .insert(message_id.to_string(), task.clone());
+        task
+    }
+
+    pub fn record_stamp_progress(&self, message_id: &str, attempts: u64) {
+        let task =
+            self.stamp_tasks.lock().expect("stamp tasks mutex poisoned").get(message_id).cloned();
+        let Some(task) = task else {
+            return;
+        };
+        let event = RpcEvent {
+            event_type: "stamp_progress".into(),
+            payload: json!({
+                "message_id": message_id,
+                "attempts": attempts,
+                "target_cost": task.target_cost,
+                "workers": task.workers,
+            }),
+        };
+        self.publish_event(event);
+    }
+
+    pub fn finish_stamp_task(&self, message_id: &str) {
+        self.stamp_tasks.lock().expect("stamp tasks mutex poisoned").remove(message_id);
+    }
+
+    fn cancel_stamp_task(&self, message_id: &str) -> bool {
+        let task =
+            self.stamp_tasks.lock().expect("stamp tasks mutex poisoned").remove(message_id);
+        match task {
+            Some(task) => {
+                task.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn store_inbound_record(&self, mut record: MessageRecord) -> Result<(), std::io::Error> {
+        if self.inbound_source_filtered(&record.source) {
+            self.metrics_record_inbound_blocked();
+            return Ok(());
+        }
+        let transient_hash = inbound_transient_hash(&record);
+        // Held until the message is stored, so a concurrent copy waits and
+        // is then caught as a duplicate, while a failed store claims nothing.
+        let mut window = self.inbound_dedup.lock().expect("inbound_dedup mutex poisoned");
+        if window.seen.contains(&transient_hash)
+            || self.store.has_inbound_message(&record.id).map_err(std::io::Error::other)?
+        {
+            self.metrics_record_duplicate_suppressed();
+            return Ok(());
+        }
+        if record.reply_to.is_none() {
+            record.reply_to = reply_to_from_fields(record.fields.as_ref());
+        }
+        annotate_attachment_media(&mut record.fields);
         self.store.insert_message(&record).map_err(std::io::Error::other)?;
+        window.claim(transient_hash);
+        drop(window);
         let event =
-            RpcEvent { event_type: "inbound".into(), payload: json!({ "message": record }) };
+            RpcEvent { event_type: "inbound".into(), payload: json!({ "message": &record }) };
         self.publish_event(event);
+        // Telemetry is derived data: failing to record it must not fail a
+        // message that is already stored and announced.
+        if let Err(err) = self.record_inbound_telemetry(&record) {
+            self.record_log_line(format!("telemetry from {} not recorded: {err}", record.id));
+        }
         Ok(())
     }
 
@@ -223,7 +685,6 @@ impl RpcDaemon {
         source_identity: Option<String>,
         source_node: Option<String>,
     ) -> Result<(), std::io::Error> {
-        let _ = stamp_cost;
         let stamp_cost_flexibility = stamp_cost_flexibility.flatten();
         let peering_cost = peering_cost.flatten();
         let record = self.upsert_peer(peer, timestamp, name, name_source);
@@ -250,6 +711,10 @@ impl RpcDaemon {
             peering_cost,
         };
         self.store.insert_announce(&announce_record).map_err(std::io::Error::other)?;
+        self.propagation_candidates
+            .lock()
+            .expect("propagation candidates mutex poisoned")
+            .observe_announce(&announce_record, stamp_cost);
 
         let event = RpcEvent {
             event_type: "announce_received".into(),
@@ -277,6 +742,7 @@ impl RpcDaemon {
             }),
         };
         self.publish_event(event);
+        self.reselect_stale_propagation_node(now_i64())?;
         Ok(())
     }
 
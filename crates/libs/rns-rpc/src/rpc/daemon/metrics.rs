@@ -143,6 +143,32 @@ impl RpcDaemon {
         metrics.sdk_event_sink_skipped_total = metrics.sdk_event_sink_skipped_total.saturating_add(1);
     }
 
+    fn metrics_record_audit_log_error(&self) {
+        let mut metrics = self.sdk_metrics.lock().expect("sdk_metrics mutex poisoned");
+        metrics.sdk_audit_log_errors_total = metrics.sdk_audit_log_errors_total.saturating_add(1);
+    }
+
+    pub fn metrics_record_inbound_decode_dropped(&self) {
+        let mut metrics = self.sdk_metrics.lock().expect("sdk_metrics mutex poisoned");
+        metrics.inbound_decode_dropped_total =
+            metrics.inbound_decode_dropped_total.saturating_add(1);
+    }
+
+    pub fn metrics_record_inbound_rate_limited(&self) {
+        let mut metrics = self.sdk_metrics.lock().expect("sdk_metrics mutex poisoned");
+        metrics.inbound_rate_limited_total = metrics.inbound_rate_limited_total.saturating_add(1);
+    }
+
+    fn metrics_record_inbound_blocked(&self) {
+        let mut metrics = self.sdk_metrics.lock().expect("sdk_metrics mutex poisoned");
+        metrics.inbound_blocked_total = metrics.inbound_blocked_total.saturating_add(1);
+    }
+
+    fn metrics_record_duplicate_suppressed(&self) {
+        let mut metrics = self.sdk_metrics.lock().expect("sdk_metrics mutex poisoned");
+        metrics.duplicate_suppressed_total = metrics.duplicate_suppressed_total.saturating_add(1);
+    }
+
     pub fn metrics_snapshot(&self) -> JsonValue {
         let metrics = self.sdk_metrics.lock().expect("sdk_metrics mutex poisoned").clone();
         let event_queue_depth = self.event_queue.lock().expect("event_queue mutex poisoned").len();
@@ -176,6 +202,11 @@ impl RpcDaemon {
                 "sdk_event_sink_error_total": metrics.sdk_event_sink_error_total,
                 "sdk_event_sink_skipped_total": metrics.sdk_event_sink_skipped_total,
                 "sdk_auth_failures_total": metrics.sdk_auth_failures_total,
+                "sdk_audit_log_errors_total": metrics.sdk_audit_log_errors_total,
+                "inbound_decode_dropped_total": metrics.inbound_decode_dropped_total,
+                "inbound_rate_limited_total": metrics.inbound_rate_limited_total,
+                "inbound_blocked_total": metrics.inbound_blocked_total,
+                "duplicate_suppressed_total": metrics.duplicate_suppressed_total,
                 "sdk_event_dropped_count": dropped_count,
             },
             "depth": {
@@ -39,9 +39,9 @@ impl RpcDaemon {
         let name = match Self::normalize_non_empty(parsed.name.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "name",
                     "attachment name must not be empty",
                 ))
             }
@@ -49,9 +49,9 @@ impl RpcDaemon {
         let content_type = match Self::normalize_non_empty(parsed.content_type.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "content_type",
                     "attachment content_type must not be empty",
                 ))
             }
@@ -133,9 +133,9 @@ impl RpcDaemon {
         let attachment_id = match Self::normalize_non_empty(parsed.attachment_id.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "attachment_id",
                     "attachment_id must not be empty",
                 ))
             }
@@ -241,9 +241,9 @@ impl RpcDaemon {
         let attachment_id = match Self::normalize_non_empty(parsed.attachment_id.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "attachment_id",
                     "attachment_id must not be empty",
                 ))
             }
@@ -290,9 +290,9 @@ impl RpcDaemon {
         let attachment_id = match Self::normalize_non_empty(parsed.attachment_id.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "attachment_id",
                     "attachment_id must not be empty",
                 ))
             }
@@ -341,9 +341,9 @@ impl RpcDaemon {
         let name = match Self::normalize_non_empty(parsed.name.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "name",
                     "attachment name must not be empty",
                 ))
             }
@@ -351,9 +351,9 @@ impl RpcDaemon {
         let content_type = match Self::normalize_non_empty(parsed.content_type.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "content_type",
                     "attachment content_type must not be empty",
                 ))
             }
@@ -435,9 +435,9 @@ impl RpcDaemon {
         let upload_id = match Self::normalize_non_empty(parsed.upload_id.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "upload_id",
                     "upload_id must not be empty",
                 ))
             }
@@ -450,9 +450,9 @@ impl RpcDaemon {
                 )
             })?;
         if decoded_bytes.is_empty() {
-            return Ok(self.sdk_error_response(
+            return Ok(self.sdk_empty_field_response(
                 request.id,
-                "SDK_VALIDATION_INVALID_ARGUMENT",
+                "bytes_base64",
                 "attachment upload chunk must not be empty",
             ));
         }
@@ -518,9 +518,9 @@ impl RpcDaemon {
         let upload_id = match Self::normalize_non_empty(parsed.upload_id.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "upload_id",
                     "upload_id must not be empty",
                 ))
             }
@@ -621,9 +621,9 @@ impl RpcDaemon {
         let attachment_id = match Self::normalize_non_empty(parsed.attachment_id.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "attachment_id",
                     "attachment_id must not be empty",
                 ))
             }
@@ -711,9 +711,9 @@ impl RpcDaemon {
         let attachment_id = match Self::normalize_non_empty(parsed.attachment_id.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "attachment_id",
                     "attachment_id must not be empty",
                 ))
             }
@@ -721,9 +721,9 @@ impl RpcDaemon {
         let topic_id = match Self::normalize_non_empty(parsed.topic_id.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "topic_id",
                     "topic_id must not be empty",
                 ))
             }
@@ -8,6 +8,31 @@ impl RpcDaemon {
         })
     }
 
+    /// Smallest HTTP response body that is compressed for clients accepting gzip
+    /// or deflate, from `rpc_backend.min_compress_bytes`.
+    pub(crate) fn http_min_compress_bytes(&self) -> usize {
+        self.sdk_runtime_config
+            .lock()
+            .expect("sdk_runtime_config mutex poisoned")
+            .get("rpc_backend")
+            .and_then(|value| value.get("min_compress_bytes"))
+            .and_then(JsonValue::as_u64)
+            .and_then(|value| usize::try_from(value).ok())
+            .unwrap_or(DEFAULT_MIN_COMPRESS_BYTES)
+    }
+
+    /// Largest accepted `POST /rpc` batch body, from `rpc_backend.max_body_bytes`.
+    pub(crate) fn http_max_body_bytes(&self) -> usize {
+        self.sdk_runtime_config
+            .lock()
+            .expect("sdk_runtime_config mutex poisoned")
+            .get("rpc_backend")
+            .and_then(|value| value.get("max_body_bytes"))
+            .and_then(JsonValue::as_u64)
+            .and_then(|value| usize::try_from(value).ok())
+            .unwrap_or(DEFAULT_MAX_BODY_BYTES)
+    }
+
     #[allow(clippy::result_large_err)]
     pub fn authorize_http_request(
         &self,
@@ -25,6 +50,9 @@ impl RpcDaemon {
         transport_auth: Option<&crate::rpc::http::TransportAuthContext>,
     ) -> Result<(), RpcError> {
         let auth_started = std::time::Instant::now();
+        let mut audit_source = None;
+        let mut audit_principal = None;
+        let mut audit_auth_mode = None;
         let result: Result<(), RpcError> = (|| {
             let (trust_forwarded, trusted_proxy_ips, bind_mode, auth_mode) = {
                 let config_guard =
@@ -77,6 +105,8 @@ impl RpcDaemon {
             }
             .filter(|value| !value.is_empty())
             .unwrap_or_else(|| "unknown".to_string());
+            audit_source = Some(source_ip.clone());
+            audit_auth_mode = Some(auth_mode.clone());
 
             if bind_mode == "local_only" && !Self::is_loopback_source(source_ip.as_str()) {
                 return Err(RpcError::new(
@@ -218,6 +248,30 @@ impl RpcDaemon {
                     }
                     replay_cache.insert(jti, now.saturating_add(jti_ttl_ms.max(1)));
                 }
+                "api_key" => {
+                    let presented = Self::header_value(headers, "x-api-key")
+                        .map(str::trim)
+                        .filter(|value| !value.is_empty())
+                        .ok_or_else(|| {
+                            RpcError::new(
+                                "SDK_SECURITY_AUTH_REQUIRED".to_string(),
+                                "x-api-key header is required".to_string(),
+                            )
+                        })?;
+                    let expected = self.sdk_api_key_auth_config().ok_or_else(|| {
+                        RpcError::new(
+                            "SDK_SECURITY_AUTH_REQUIRED".to_string(),
+                            "api_key auth mode requires api key configuration".to_string(),
+                        )
+                    })?;
+                    if !Self::api_key_matches(presented, expected.as_str()) {
+                        return Err(RpcError::new(
+                            "SDK_SECURITY_AUTHZ_DENIED".to_string(),
+                            "api key does not match runtime policy".to_string(),
+                        ));
+                    }
+                    principal = "api-key-client".to_string();
+                }
                 "mtls" => {
                     let transport_auth = transport_auth.ok_or_else(|| {
                         RpcError::new(
@@ -268,14 +322,48 @@ impl RpcDaemon {
                 }
             }
 
+            audit_principal = Some(principal.clone());
             self.enforce_rate_limits(source_ip.as_str(), principal.as_str())
         })();
 
         let elapsed_ms = auth_started.elapsed().as_millis() as u64;
         self.metrics_record_auth_result(elapsed_ms, result.is_ok());
+        self.record_security_audit(SecurityAuditRecord {
+            ts_ms: now_millis_u64(),
+            decision: "http_auth".to_string(),
+            outcome: if result.is_ok() { "allow" } else { "deny" }.to_string(),
+            reason_code: result
+                .as_ref()
+                .err()
+                .map_or_else(|| "ok".to_string(), |error| error.code.clone()),
+            source: audit_source.unwrap_or_else(|| "unknown".to_string()),
+            principal: audit_principal,
+            auth_mode: audit_auth_mode.unwrap_or_default(),
+        });
         result
     }
 
+    /// Appends a security decision to the audit log as one JSON line. The audit
+    /// log is a separate file from the event stream so it is not subject to event
+    /// redaction or retention; write failures are counted but never fail the request.
+    fn record_security_audit(&self, record: SecurityAuditRecord) {
+        let mut audit_guard = self.sdk_audit_log.lock().expect("sdk_audit_log mutex poisoned");
+        let Some(sink) = audit_guard.as_mut() else {
+            return;
+        };
+        if record.outcome == "allow" && !sink.include_allowed {
+            return;
+        }
+        let Ok(mut line) = serde_json::to_string(&record) else {
+            return;
+        };
+        line.push('\n');
+        if sink.log.append(line.as_bytes()).is_err() {
+            drop(audit_guard);
+            self.metrics_record_audit_log_error();
+        }
+    }
+
     #[allow(clippy::result_large_err)]
     fn enforce_rate_limits(&self, source_ip: &str, principal: &str) -> Result<(), RpcError> {
         let (per_ip_limit, per_principal_limit) = self.sdk_rate_limits();
@@ -19,6 +19,178 @@ impl RpcDaemon {
         (per_ip, per_principal)
     }
 
+    /// Sends allowed per destination per minute; 0 (the default) disables it.
+    fn sdk_per_destination_rate_limit(&self) -> u32 {
+        self.sdk_runtime_config
+            .lock()
+            .expect("sdk_runtime_config mutex poisoned")
+            .get("per_destination_rate_limit")
+            .and_then(JsonValue::as_u64)
+            .and_then(|value| u32::try_from(value).ok())
+            .unwrap_or(0)
+    }
+
+    /// How long an idempotency key keeps answering with its first message:
+    /// `idempotency_ttl_ms`, else the profile limit.
+    fn sdk_idempotency_ttl_ms(&self) -> u64 {
+        let configured = self
+            .sdk_runtime_config
+            .lock()
+            .expect("sdk_runtime_config mutex poisoned")
+            .get("idempotency_ttl_ms")
+            .and_then(JsonValue::as_u64);
+        configured.unwrap_or_else(|| {
+            let profile = self.sdk_profile.lock().expect("sdk_profile mutex poisoned").clone();
+            Self::sdk_effective_limits_for_profile(&profile)["idempotency_ttl_ms"]
+                .as_u64()
+                .unwrap_or(86_400_000)
+        })
+    }
+
+    /// Configured `propagation_selection_strategy`, `selected` when unset.
+    fn sdk_propagation_selection_strategy(&self) -> String {
+        self.sdk_runtime_config
+            .lock()
+            .expect("sdk_runtime_config mutex poisoned")
+            .get("propagation_selection_strategy")
+            .and_then(JsonValue::as_str)
+            .unwrap_or("selected")
+            .to_string()
+    }
+
+    /// Whether the configured `inbound_allowlist`/`inbound_blocklist` reject
+    /// messages from `source`. Blocklisted sources are always dropped. A
+    /// non-empty allowlist drops every other source not on it, except
+    /// non-blocked contacts when `inbound_contacts_bypass` is set.
+    fn inbound_source_filtered(&self, source: &str) -> bool {
+        let (allowlist, blocklist, contacts_bypass) = {
+            let config = self.sdk_runtime_config.lock().expect("sdk_runtime_config mutex poisoned");
+            let hashes = |key: &str| -> Vec<String> {
+                config
+                    .get(key)
+                    .and_then(JsonValue::as_array)
+                    .map(|entries| {
+                        entries.iter().filter_map(JsonValue::as_str).map(str::to_string).collect()
+                    })
+                    .unwrap_or_default()
+            };
+            (
+                hashes("inbound_allowlist"),
+                hashes("inbound_blocklist"),
+                config.get("inbound_contacts_bypass").and_then(JsonValue::as_bool).unwrap_or(false),
+            )
+        };
+        let listed = |list: &[String]| list.iter().any(|hash| hash.eq_ignore_ascii_case(source));
+        if listed(&blocklist) {
+            return true;
+        }
+        if allowlist.is_empty() || listed(&allowlist) {
+            return false;
+        }
+        if contacts_bypass {
+            let contacts = self.sdk_contacts.lock().expect("sdk_contacts mutex poisoned");
+            if contacts.values().any(|contact| {
+                contact.identity.eq_ignore_ascii_case(source) && contact.trust_level != "blocked"
+            }) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Telemetry points kept per peer before the oldest are dropped.
+    fn sdk_telemetry_max_points_per_peer(&self) -> usize {
+        self.sdk_runtime_config
+            .lock()
+            .expect("sdk_runtime_config mutex poisoned")
+            .get("telemetry_max_points_per_peer")
+            .and_then(JsonValue::as_u64)
+            .and_then(|value| usize::try_from(value).ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(DEFAULT_TELEMETRY_POINTS_PER_PEER)
+    }
+
+    /// Largest message content, in UTF-8 bytes, accepted for sending: the
+    /// configured `max_content_bytes`, else the profile default. 0 disables.
+    fn sdk_max_content_bytes(&self) -> usize {
+        if let Some(value) = self.sdk_runtime_config_usize("max_content_bytes") {
+            return value;
+        }
+        match self.sdk_profile.lock().expect("sdk_profile mutex poisoned").as_str() {
+            "embedded-alloc" => 65_536,
+            _ => 1_048_576,
+        }
+    }
+
+    /// Largest message title, in UTF-8 bytes, accepted for sending while the
+    /// delivery policy sets no `max_title_bytes`; a policy limit replaces it.
+    /// 0 disables.
+    fn sdk_max_title_wire_bytes(&self) -> usize {
+        if let Some(value) = self.sdk_runtime_config_usize("max_title_wire_bytes") {
+            return value;
+        }
+        match self.sdk_profile.lock().expect("sdk_profile mutex poisoned").as_str() {
+            "embedded-alloc" => 256,
+            _ => 1_024,
+        }
+    }
+
+    /// Milliseconds an outbound message may go without forward progress
+    /// before it is failed: `propagation_receipt_timeout_ms` for propagated
+    /// sends, else `receipt_timeout_ms`. 0 disables.
+    fn sdk_receipt_timeout_ms(&self, propagated: bool) -> u64 {
+        let (key, default) = if propagated {
+            ("propagation_receipt_timeout_ms", DEFAULT_PROPAGATION_RECEIPT_TIMEOUT_MS)
+        } else {
+            ("receipt_timeout_ms", DEFAULT_RECEIPT_TIMEOUT_MS)
+        };
+        self.sdk_runtime_config
+            .lock()
+            .expect("sdk_runtime_config mutex poisoned")
+            .get(key)
+            .and_then(JsonValue::as_u64)
+            .unwrap_or(default)
+    }
+
+    /// Lifetime of a propagated message, counted from its LXMF timestamp:
+    /// `propagation_message_ttl_ms`, else 30 days. 0 keeps messages forever.
+    fn propagation_message_ttl_ms(&self) -> u64 {
+        self.sdk_runtime_config
+            .lock()
+            .expect("sdk_runtime_config mutex poisoned")
+            .get("propagation_message_ttl_ms")
+            .and_then(JsonValue::as_u64)
+            .unwrap_or(DEFAULT_PROPAGATION_MESSAGE_TTL_MS)
+    }
+
+    fn sdk_runtime_config_usize(&self, key: &str) -> Option<usize> {
+        self.sdk_runtime_config
+            .lock()
+            .expect("sdk_runtime_config mutex poisoned")
+            .get(key)
+            .and_then(JsonValue::as_u64)
+            .and_then(|value| usize::try_from(value).ok())
+    }
+
+    /// Seconds between periodic announces: the configured
+    /// `announce_interval_secs` clamped to the minimum, else the interval the
+    /// scheduler was started with. 0 means periodic announces are off.
+    fn effective_announce_interval_secs(&self) -> u64 {
+        let configured = self
+            .sdk_runtime_config
+            .lock()
+            .expect("sdk_runtime_config mutex poisoned")
+            .get("announce_interval_secs")
+            .and_then(JsonValue::as_u64);
+        match configured {
+            Some(interval_secs) => interval_secs.max(MIN_ANNOUNCE_INTERVAL_SECS),
+            None => *self
+                .announce_interval_default_secs
+                .lock()
+                .expect("announce_interval_default_secs mutex poisoned"),
+        }
+    }
+
     fn sdk_token_auth_config(
         &self,
     ) -> Option<(String, String, u64, u64, zeroize::Zeroizing<String>)> {
@@ -35,6 +207,13 @@ impl RpcDaemon {
         Some((issuer, audience, jti_ttl_ms, clock_skew_secs, shared_secret))
     }
 
+    fn sdk_api_key_auth_config(&self) -> Option<zeroize::Zeroizing<String>> {
+        let config_guard =
+            self.sdk_runtime_config.lock().expect("sdk_runtime_config mutex poisoned");
+        let api_key = config_guard.get("rpc_backend")?.get("api_key_auth")?.get("api_key")?;
+        Some(zeroize::Zeroizing::new(api_key.as_str()?.to_string()))
+    }
+
     fn sdk_mtls_auth_config(&self) -> Option<(bool, Option<String>)> {
         let config =
             self.sdk_runtime_config.lock().expect("sdk_runtime_config mutex poisoned").clone();
@@ -79,6 +258,14 @@ impl RpcDaemon {
         Some(hex::encode(mac.finalize().into_bytes()))
     }
 
+    /// Compares SHA-256 digests of the two keys without short-circuiting, so the
+    /// time taken does not reveal how much of a guessed key was correct.
+    fn api_key_matches(presented: &str, expected: &str) -> bool {
+        let presented = Sha256::digest(presented.as_bytes());
+        let expected = Sha256::digest(expected.as_bytes());
+        presented.iter().zip(expected.iter()).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+    }
+
     fn is_loopback_source(source: &str) -> bool {
         let normalized = source.trim().to_ascii_lowercase();
         normalized == "127.0.0.1"
@@ -129,6 +316,7 @@ impl RpcDaemon {
             "sdk.capability.remote_commands".to_string(),
             "sdk.capability.voice_signaling".to_string(),
             "sdk.capability.group_delivery".to_string(),
+            "sdk.capability.send_batch".to_string(),
             "sdk.capability.event_sink_bridge".to_string(),
             "sdk.capability.shared_instance_rpc_auth".to_string(),
         ]
@@ -137,7 +325,10 @@ impl RpcDaemon {
     fn sdk_supported_capabilities_for_profile(profile: &str) -> Vec<String> {
         let mut caps = Self::sdk_supported_capabilities();
         if profile == "embedded-alloc" {
-            caps.retain(|capability| capability != "sdk.capability.async_events");
+            caps.retain(|capability| {
+                capability != "sdk.capability.async_events"
+                    && capability != "sdk.capability.send_batch"
+            });
         }
         caps
     }
@@ -229,6 +420,18 @@ impl RpcDaemon {
         }
     }
 
+    fn sdk_event_oversize_policy(&self) -> String {
+        self.sdk_runtime_config
+            .lock()
+            .expect("sdk_runtime_config mutex poisoned")
+            .get("event_stream")
+            .and_then(|value| value.get("oversize_policy"))
+            .and_then(JsonValue::as_str)
+            .map(|value| value.trim().to_ascii_lowercase())
+            .filter(|value| matches!(value.as_str(), "reject" | "skip" | "truncate"))
+            .unwrap_or_else(|| "reject".to_string())
+    }
+
     fn sdk_max_batch_bytes(&self) -> usize {
         if let Some(value) = self
             .sdk_runtime_config
@@ -266,6 +469,13 @@ impl RpcDaemon {
         RpcResponse { id, result: None, error: Some(RpcError::new(code, message)) }
     }
 
+    /// `SDK_VALIDATION_INVALID_ARGUMENT` for a required `field` left empty.
+    fn sdk_empty_field_response(&self, id: u64, field: &str, message: &str) -> RpcResponse {
+        let error = RpcError::new("SDK_VALIDATION_INVALID_ARGUMENT", message)
+            .with_details(field_error_details(field, "empty"));
+        RpcResponse { id, result: None, error: Some(error) }
+    }
+
     fn sdk_capability_disabled_response(
         &self,
         id: u64,
@@ -51,22 +51,23 @@ impl RpcDaemon {
             .unwrap_or("local_trusted")
             .trim()
             .to_ascii_lowercase();
-        if !matches!(auth_mode.as_str(), "local_trusted" | "token" | "mtls") {
+        if !matches!(auth_mode.as_str(), "local_trusted" | "token" | "mtls" | "api_key") {
             return Err(Self::sdk_config_error(
                 "SDK_VALIDATION_INVALID_ARGUMENT",
-                "auth_mode must be local_trusted, token, or mtls",
+                "auth_mode must be local_trusted, token, mtls, or api_key",
             ));
         }
-        if bind_mode == "remote" && !matches!(auth_mode.as_str(), "token" | "mtls") {
+        if bind_mode == "remote" && !matches!(auth_mode.as_str(), "token" | "mtls" | "api_key") {
             return Err(Self::sdk_config_error(
                 "SDK_SECURITY_REMOTE_BIND_DISALLOWED",
-                "remote bind mode requires token or mtls auth mode",
+                "remote bind mode requires token, mtls, or api_key auth mode",
             ));
         }
-        if bind_mode == "local_only" && auth_mode != "local_trusted" {
+        if bind_mode == "local_only" && !matches!(auth_mode.as_str(), "local_trusted" | "api_key")
+        {
             return Err(Self::sdk_config_error(
                 "SDK_SECURITY_AUTH_REQUIRED",
-                "local_only bind mode requires local_trusted auth mode",
+                "local_only bind mode requires local_trusted or api_key auth mode",
             ));
         }
         if profile == "embedded-alloc" && auth_mode == "mtls" {
@@ -82,10 +83,10 @@ impl RpcDaemon {
             .unwrap_or("reject")
             .trim()
             .to_ascii_lowercase();
-        if !matches!(overflow_policy.as_str(), "reject" | "drop_oldest" | "block") {
+        if !matches!(overflow_policy.as_str(), "reject" | "drop_oldest" | "drop_newest" | "block") {
             return Err(Self::sdk_config_error(
                 "SDK_VALIDATION_INVALID_ARGUMENT",
-                "overflow_policy must be reject, drop_oldest, or block",
+                "overflow_policy must be reject, drop_oldest, drop_newest, or block",
             ));
         }
         if overflow_policy == "block"
@@ -97,6 +98,96 @@ impl RpcDaemon {
             ));
         }
 
+        if let Some(limit) = config.get("per_destination_rate_limit") {
+            if limit.as_u64().and_then(|value| u32::try_from(value).ok()).is_none() {
+                return Err(Self::sdk_config_error(
+                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "per_destination_rate_limit must be an unsigned 32-bit integer",
+                ));
+            }
+        }
+
+        if let Some(max_points) = config.get("telemetry_max_points_per_peer") {
+            if max_points.as_u64().filter(|value| *value > 0).is_none() {
+                return Err(Self::sdk_config_error(
+                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "telemetry_max_points_per_peer must be a positive integer",
+                ));
+            }
+        }
+
+        for key in [
+            "max_content_bytes",
+            "max_title_wire_bytes",
+            "receipt_timeout_ms",
+            "propagation_receipt_timeout_ms",
+            "propagation_message_ttl_ms",
+        ] {
+            if config.get(key).is_some_and(|value| value.as_u64().is_none()) {
+                return Err(Self::sdk_config_error(
+                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    &format!("{key} must be an unsigned integer"),
+                ));
+            }
+        }
+
+        if let Some(interval_secs) = config.get("announce_interval_secs") {
+            if interval_secs.as_u64().is_none() {
+                return Err(Self::sdk_config_error(
+                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "announce_interval_secs must be an unsigned integer",
+                ));
+            }
+        }
+
+        if let Some(strategy) = config.get("propagation_selection_strategy") {
+            if !strategy.is_null()
+                && !matches!(
+                    strategy.as_str(),
+                    Some("selected" | "lowest_cost" | "most_recent")
+                )
+            {
+                return Err(Self::sdk_config_error(
+                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "propagation_selection_strategy must be selected, lowest_cost, or most_recent",
+                ));
+            }
+        }
+
+        if config.get("propagation_node").is_some_and(|value| !value.is_boolean()) {
+            return Err(Self::sdk_config_error(
+                "SDK_VALIDATION_INVALID_ARGUMENT",
+                "propagation_node must be a boolean",
+            ));
+        }
+
+        for key in ["inbound_allowlist", "inbound_blocklist"] {
+            let Some(list) = config.get(key) else {
+                continue;
+            };
+            if list.is_null() {
+                continue;
+            }
+            let valid = list.as_array().is_some_and(|entries| {
+                entries.iter().all(|entry| entry.as_str().is_some_and(is_destination_hash))
+            });
+            if !valid {
+                return Err(Self::sdk_config_error(
+                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    &format!("{key} must be an array of 32-character hex hashes"),
+                ));
+            }
+        }
+
+        if let Some(bypass) = config.get("inbound_contacts_bypass") {
+            if !bypass.is_boolean() && !bypass.is_null() {
+                return Err(Self::sdk_config_error(
+                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "inbound_contacts_bypass must be a boolean",
+                ));
+            }
+        }
+
         if let Some(store_forward) = config.get("store_forward") {
             if !store_forward.is_object() && !store_forward.is_null() {
                 return Err(Self::sdk_config_error(
@@ -112,6 +203,12 @@ impl RpcDaemon {
                 "capacity_policy",
                 "eviction_priority",
             ];
+            if store_forward.contains_key("dead_letter_path") {
+                return Err(Self::sdk_config_error(
+                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "store_forward.dead_letter_path is fixed at daemon startup",
+                ));
+            }
             if let Some(key) = store_forward
                 .keys()
                 .find(|key| !ALLOWED_STORE_FORWARD_KEYS.contains(&key.as_str()))
@@ -191,7 +288,16 @@ impl RpcDaemon {
                 "max_event_bytes",
                 "max_batch_bytes",
                 "max_extension_keys",
+                "persist_max_bytes",
+                "persist_max_age_secs",
+                "oversize_policy",
             ];
+            if event_stream.contains_key("persist_path") {
+                return Err(Self::sdk_config_error(
+                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "event_stream.persist_path is fixed at daemon startup",
+                ));
+            }
             if let Some(key) = event_stream
                 .keys()
                 .find(|key| !ALLOWED_EVENT_STREAM_KEYS.contains(&key.as_str()))
@@ -217,6 +323,8 @@ impl RpcDaemon {
             let max_event_bytes = parse_u64_field("max_event_bytes")?;
             let max_batch_bytes = parse_u64_field("max_batch_bytes")?;
             let max_extension_keys = parse_u64_field("max_extension_keys")?;
+            let persist_max_bytes = parse_u64_field("persist_max_bytes")?;
+            let persist_max_age_secs = parse_u64_field("persist_max_age_secs")?;
 
             if max_poll_events.is_some_and(|value| value == 0 || value > 10_000) {
                 return Err(Self::sdk_config_error(
@@ -242,6 +350,32 @@ impl RpcDaemon {
                     "event_stream.max_extension_keys must be in the range 0..=32",
                 ));
             }
+            if let Some(oversize_policy) = event_stream.get("oversize_policy") {
+                let valid = oversize_policy.as_str().is_some_and(|value| {
+                    matches!(
+                        value.trim().to_ascii_lowercase().as_str(),
+                        "reject" | "skip" | "truncate"
+                    )
+                });
+                if !valid {
+                    return Err(Self::sdk_config_error(
+                        "SDK_VALIDATION_INVALID_ARGUMENT",
+                        "event_stream.oversize_policy must be reject, skip, or truncate",
+                    ));
+                }
+            }
+            if persist_max_bytes.is_some_and(|value| value < 1_024) {
+                return Err(Self::sdk_config_error(
+                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "event_stream.persist_max_bytes must be at least 1024",
+                ));
+            }
+            if persist_max_age_secs.is_some_and(|value| value == 0) {
+                return Err(Self::sdk_config_error(
+                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "event_stream.persist_max_age_secs must be greater than zero",
+                ));
+            }
             if let (Some(max_event_bytes), Some(max_batch_bytes)) = (max_event_bytes, max_batch_bytes)
             {
                 if max_batch_bytes < max_event_bytes {
@@ -342,7 +476,28 @@ impl RpcDaemon {
             }
         }
 
+        if config.get("audit_log").is_some_and(|value| !value.is_null()) {
+            return Err(Self::sdk_config_error(
+                "SDK_VALIDATION_INVALID_ARGUMENT",
+                "audit_log is fixed at daemon startup",
+            ));
+        }
+
         match auth_mode.as_str() {
+            "api_key" => {
+                let api_key = config
+                    .get("rpc_backend")
+                    .and_then(|value| value.get("api_key_auth"))
+                    .and_then(|value| value.get("api_key"))
+                    .and_then(JsonValue::as_str)
+                    .unwrap_or("");
+                if api_key.trim().is_empty() {
+                    return Err(Self::sdk_config_error(
+                        "SDK_SECURITY_AUTH_REQUIRED",
+                        "api_key auth mode requires rpc_backend.api_key_auth.api_key",
+                    ));
+                }
+            }
             "token" => {
                 let Some(token_auth) = config
                     .get("rpc_backend")
@@ -536,6 +691,7 @@ impl RpcDaemon {
                     "max_message_age_ms": policy.max_message_age_ms,
                 }),
             });
+            self.publish_expired_batch(&expired_ids, now_ts);
         }
 
         let outbound_count =
@@ -583,6 +739,48 @@ impl RpcDaemon {
         Ok(remaining >= policy.max_messages)
     }
 
+    /// Rolls up one expiry sweep into a `messages_expired_batch` event and, when
+    /// the host enabled it at startup, appends the same entries to the dead-letter
+    /// log as JSON lines, rotating the log once it outgrows its cap.
+    fn publish_expired_batch(&self, expired_ids: &[String], now_ts: i64) {
+        let messages = expired_ids
+            .iter()
+            .map(|message_id| {
+                let destination = self
+                    .store
+                    .get_message(message_id)
+                    .ok()
+                    .flatten()
+                    .map(|record| record.destination);
+                json!({ "message_id": message_id, "destination": destination })
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(sink) =
+            self.dead_letter_log.lock().expect("dead_letter_log mutex poisoned").as_mut()
+        {
+            let mut lines = String::new();
+            for message in &messages {
+                let mut entry = message.clone();
+                entry["expired_at"] = json!(now_ts);
+                lines.push_str(&entry.to_string());
+                lines.push('\n');
+            }
+            if sink.log.append(lines.as_bytes()).is_ok() && sink.log.len() > sink.max_bytes {
+                let _ = sink.log.rotate();
+            }
+        }
+
+        self.publish_event(RpcEvent {
+            event_type: "messages_expired_batch".to_string(),
+            payload: json!({
+                "expired_count": messages.len(),
+                "messages": messages,
+                "expired_at": now_ts,
+            }),
+        });
+    }
+
     fn default_sdk_identity(identity_hash: &str) -> SdkIdentityBundle {
         SdkIdentityBundle {
             identity: identity_hash.to_string(),
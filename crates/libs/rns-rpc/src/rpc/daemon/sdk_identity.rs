@@ -125,6 +125,7 @@ impl RpcDaemon {
             .lock()
             .expect("sdk_contacts mutex poisoned")
             .clone();
+        let display_names = disambiguated_peer_names(&peer_rows, DEFAULT_PEER_NAME_SUFFIX_LEN);
         let mut next_index = start_index;
         let mut peers = Vec::new();
         for peer in peer_rows.iter().skip(start_index) {
@@ -133,6 +134,10 @@ impl RpcDaemon {
                 .get(peer.peer.as_str())
                 .map(|contact| (Some(contact.trust_level.clone()), Some(contact.bootstrap)))
                 .unwrap_or((None, None));
+            let mut extensions = JsonMap::new();
+            if let Some(display_name) = display_names.get(&peer.peer) {
+                extensions.insert("display_name".to_string(), json!(display_name));
+            }
             peers.push(SdkPresenceRecord {
                 peer_id: peer.peer.clone(),
                 last_seen_ts_ms: peer.last_seen,
@@ -142,7 +147,7 @@ impl RpcDaemon {
                 name_source: peer.name_source.clone(),
                 trust_level,
                 bootstrap,
-                extensions: JsonMap::new(),
+                extensions,
             });
             if peers.len() >= limit {
                 break;
@@ -182,9 +187,9 @@ impl RpcDaemon {
         let identity = match Self::normalize_non_empty(parsed.identity.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "identity",
                     "identity must not be empty",
                 ))
             }
@@ -233,9 +238,9 @@ impl RpcDaemon {
         let bundle_base64 = match Self::normalize_non_empty(parsed.bundle_base64.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "bundle_base64",
                     "bundle_base64 must not be empty",
                 ))
             }
@@ -290,9 +295,9 @@ impl RpcDaemon {
         let identity = match Self::normalize_non_empty(parsed.identity.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "identity",
                     "identity must not be empty",
                 ))
             }
@@ -346,9 +351,9 @@ impl RpcDaemon {
         let query = match Self::normalize_non_empty(parsed.hash.as_str()) {
             Some(value) => value.to_ascii_lowercase(),
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "hash",
                     "hash must not be empty",
                 ))
             }
@@ -390,9 +395,9 @@ impl RpcDaemon {
         let identity = match Self::normalize_non_empty(parsed.identity.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "identity",
                     "identity must not be empty",
                 ))
             }
@@ -572,9 +577,9 @@ impl RpcDaemon {
         let identity = match Self::normalize_non_empty(parsed.identity.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "identity",
                     "identity must not be empty",
                 ))
             }
@@ -628,4 +633,68 @@ impl RpcDaemon {
         })
     }
 
+    /// Adds the destination of a delivered outbound message to the contact
+    /// list when the delivery policy opts in and the peer announced a verified
+    /// display name. Existing contacts are left untouched.
+    fn auto_create_contact_for_delivery(&self, message_id: &str) -> Result<(), std::io::Error> {
+        if !self.delivery_policy.lock().expect("policy mutex poisoned").auto_contacts {
+            return Ok(());
+        }
+        let Some(message) = self.store.get_message(message_id).map_err(std::io::Error::other)?
+        else {
+            return Ok(());
+        };
+        if message.direction != "out" {
+            return Ok(());
+        }
+        let identity = message.destination;
+        let display_name = {
+            let peers = self.peers.lock().expect("peers mutex poisoned");
+            let Some(peer) = peers.get(&identity) else {
+                return Ok(());
+            };
+            let verified = peer
+                .name_source
+                .as_deref()
+                .is_some_and(|source| VERIFIED_PEER_NAME_SOURCES.contains(&source));
+            match peer.name.clone() {
+                Some(name) if verified => name,
+                _ => return Ok(()),
+            }
+        };
+
+        let _domain_state_guard = self.lock_and_restore_sdk_domain_snapshot()?;
+        let contact = {
+            let mut contacts = self.sdk_contacts.lock().expect("sdk_contacts mutex poisoned");
+            if contacts.contains_key(&identity) {
+                return Ok(());
+            }
+            let mut metadata = JsonMap::new();
+            metadata.insert("created_by".to_string(), json!("auto_contacts"));
+            let record = SdkContactRecord {
+                identity: identity.clone(),
+                display_name: Some(display_name),
+                trust_level: "unknown".to_string(),
+                bootstrap: false,
+                updated_ts_ms: now_millis_u64(),
+                metadata,
+                extensions: JsonMap::new(),
+            };
+            contacts.insert(identity.clone(), record.clone());
+            record
+        };
+        {
+            let mut order =
+                self.sdk_contact_order.lock().expect("sdk_contact_order mutex poisoned");
+            if !order.contains(&identity) {
+                order.push(identity);
+            }
+        }
+        self.persist_sdk_domain_snapshot()?;
+        self.publish_event(RpcEvent {
+            event_type: "contact_auto_created".to_string(),
+            payload: json!({ "contact": contact, "message_id": message_id }),
+        });
+        Ok(())
+    }
 }
@@ -22,6 +22,47 @@ impl RpcDaemon {
         RpcResponse { id: request_id, result: None, error: Some(error) }
     }
 
+    /// Rejects positions outside -90..=90 latitude or -180..=180 longitude,
+    /// naming the offending field and its bounds in the error details.
+    fn marker_position_invalid_response(
+        &self,
+        request_id: u64,
+        position: &SdkGeoPoint,
+    ) -> Option<RpcResponse> {
+        let (field, value, bound) = if !(-90.0..=90.0).contains(&position.lat) {
+            ("lat", position.lat, 90.0)
+        } else if !(-180.0..=180.0).contains(&position.lon) {
+            ("lon", position.lon, 180.0)
+        } else {
+            return None;
+        };
+        let mut error = RpcError::new(
+            "SDK_VALIDATION_INVALID_ARGUMENT",
+            format!("marker {field} must be within -{bound}..={bound}"),
+        );
+        let mut details = JsonMap::new();
+        details.insert("field".to_string(), json!(format!("position.{field}")));
+        details.insert("value".to_string(), json!(value));
+        details.insert("min".to_string(), json!(-bound));
+        details.insert("max".to_string(), json!(bound));
+        error.details = Some(Box::new(details));
+        Some(RpcResponse { id: request_id, result: None, error: Some(error) })
+    }
+
+    fn publish_marker_updated(&self, change: &str, marker: &SdkMarkerRecord) {
+        self.publish_event(RpcEvent {
+            event_type: "marker_updated".to_string(),
+            payload: json!({
+                "change": change,
+                "marker_id": marker.marker_id,
+                "topic_id": marker.topic_id,
+                "revision": marker.revision,
+                "position": marker.position,
+                "updated_ts_ms": marker.updated_ts_ms,
+            }),
+        });
+    }
+
     fn handle_sdk_marker_create_v2(
         &self,
         request: RpcRequest,
@@ -42,21 +83,16 @@ impl RpcDaemon {
         let label = match Self::normalize_non_empty(parsed.label.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "label",
                     "marker label must not be empty",
                 ))
             }
         };
-        if !((-90.0..=90.0).contains(&parsed.position.lat)
-            && (-180.0..=180.0).contains(&parsed.position.lon))
+        if let Some(response) = self.marker_position_invalid_response(request.id, &parsed.position)
         {
-            return Ok(self.sdk_error_response(
-                request.id,
-                "SDK_VALIDATION_INVALID_ARGUMENT",
-                "marker coordinates are out of range",
-            ));
+            return Ok(response);
         }
         if let Some(topic_id) = parsed.topic_id.as_deref() {
             if !self.sdk_topics.lock().expect("sdk_topics mutex poisoned").contains_key(topic_id) {
@@ -83,6 +119,7 @@ impl RpcDaemon {
             .insert(marker_id.clone(), record.clone());
         self.sdk_marker_order.lock().expect("sdk_marker_order mutex poisoned").push(marker_id);
         self.persist_sdk_domain_snapshot()?;
+        self.publish_marker_updated("created", &record);
         Ok(RpcResponse { id: request.id, result: Some(json!({ "marker": record })), error: None })
     }
 
@@ -167,9 +204,9 @@ impl RpcDaemon {
         let marker_id = match Self::normalize_non_empty(parsed.marker_id.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "marker_id",
                     "marker_id must not be empty",
                 ))
             }
@@ -181,14 +218,9 @@ impl RpcDaemon {
                 "expected_revision must be greater than 0",
             ));
         }
-        if !((-90.0..=90.0).contains(&parsed.position.lat)
-            && (-180.0..=180.0).contains(&parsed.position.lon))
+        if let Some(response) = self.marker_position_invalid_response(request.id, &parsed.position)
         {
-            return Ok(self.sdk_error_response(
-                request.id,
-                "SDK_VALIDATION_INVALID_ARGUMENT",
-                "marker coordinates are out of range",
-            ));
+            return Ok(response);
         }
         let marker = {
             let mut markers = self.sdk_markers.lock().expect("sdk_markers mutex poisoned");
@@ -214,6 +246,7 @@ impl RpcDaemon {
             record.clone()
         };
         self.persist_sdk_domain_snapshot()?;
+        self.publish_marker_updated("position_updated", &marker);
         Ok(RpcResponse {
             id: request.id,
             result: Some(json!({ "marker": marker })),
@@ -242,9 +275,9 @@ impl RpcDaemon {
         let marker_id = match Self::normalize_non_empty(parsed.marker_id.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "marker_id",
                     "marker_id must not be empty",
                 ))
             }
@@ -268,22 +301,22 @@ impl RpcDaemon {
                             existing.revision,
                         ));
                     }
-                    markers.remove(marker_id.as_str());
-                    true
+                    markers.remove(marker_id.as_str())
                 }
-                None => false,
+                None => None,
             }
         };
-        if removed {
+        if let Some(removed) = removed.as_ref() {
             self.sdk_marker_order
                 .lock()
                 .expect("sdk_marker_order mutex poisoned")
                 .retain(|current| current != marker_id.as_str());
             self.persist_sdk_domain_snapshot()?;
+            self.publish_marker_updated("deleted", removed);
         }
         Ok(RpcResponse {
             id: request.id,
-            result: Some(json!({ "accepted": removed, "marker_id": marker_id })),
+            result: Some(json!({ "accepted": removed.is_some(), "marker_id": marker_id })),
             error: None,
         })
     }
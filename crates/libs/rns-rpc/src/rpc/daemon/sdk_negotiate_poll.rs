@@ -10,7 +10,7 @@ impl RpcDaemon {
             .supported_contract_versions
             .iter()
             .copied()
-            .filter(|version| *version == 2)
+            .filter(|version| SUPPORTED_CONTRACT_VERSIONS.contains(version))
             .max();
 
         let Some(active_contract_version) = active_contract_version else {
@@ -50,25 +50,26 @@ impl RpcDaemon {
             .unwrap_or("local_trusted")
             .trim()
             .to_ascii_lowercase();
-        if !matches!(auth_mode.as_str(), "local_trusted" | "token" | "mtls") {
+        if !matches!(auth_mode.as_str(), "local_trusted" | "token" | "mtls" | "api_key") {
             return Ok(self.sdk_error_response(
                 request.id,
                 "SDK_VALIDATION_INVALID_ARGUMENT",
-                "auth_mode must be local_trusted, token, or mtls",
+                "auth_mode must be local_trusted, token, mtls, or api_key",
             ));
         }
-        if bind_mode == "remote" && !matches!(auth_mode.as_str(), "token" | "mtls") {
+        if bind_mode == "remote" && !matches!(auth_mode.as_str(), "token" | "mtls" | "api_key") {
             return Ok(self.sdk_error_response(
                 request.id,
                 "SDK_SECURITY_REMOTE_BIND_DISALLOWED",
-                "remote bind mode requires token or mtls auth mode",
+                "remote bind mode requires token, mtls, or api_key auth mode",
             ));
         }
-        if bind_mode == "local_only" && auth_mode != "local_trusted" {
+        if bind_mode == "local_only" && !matches!(auth_mode.as_str(), "local_trusted" | "api_key")
+        {
             return Ok(self.sdk_error_response(
                 request.id,
                 "SDK_SECURITY_AUTH_REQUIRED",
-                "local_only bind mode requires local_trusted auth mode",
+                "local_only bind mode requires local_trusted or api_key auth mode",
             ));
         }
         if profile == "embedded-alloc" && auth_mode == "mtls" {
@@ -86,11 +87,11 @@ impl RpcDaemon {
             .unwrap_or("reject")
             .trim()
             .to_ascii_lowercase();
-        if !matches!(overflow_policy.as_str(), "reject" | "drop_oldest" | "block") {
+        if !matches!(overflow_policy.as_str(), "reject" | "drop_oldest" | "drop_newest" | "block") {
             return Ok(self.sdk_error_response(
                 request.id,
                 "SDK_VALIDATION_INVALID_ARGUMENT",
-                "overflow_policy must be reject, drop_oldest, or block",
+                "overflow_policy must be reject, drop_oldest, drop_newest, or block",
             ));
         }
         if overflow_policy == "block" && parsed.config.block_timeout_ms.is_none() {
@@ -149,6 +150,21 @@ impl RpcDaemon {
         }
 
         match auth_mode.as_str() {
+            "api_key" => {
+                let api_key = parsed
+                    .config
+                    .rpc_backend
+                    .as_ref()
+                    .and_then(|backend| backend.api_key_auth.as_ref())
+                    .map_or("", |auth| auth.api_key.as_str());
+                if api_key.trim().is_empty() {
+                    return Ok(self.sdk_error_response(
+                        request.id,
+                        "SDK_SECURITY_AUTH_REQUIRED",
+                        "api_key auth mode requires rpc_backend.api_key_auth.api_key",
+                    ));
+                }
+            }
             "token" => {
                 let Some(token_auth) = parsed
                     .config
@@ -290,6 +306,7 @@ impl RpcDaemon {
                         "write_timeout_ms": backend.write_timeout_ms,
                         "max_header_bytes": backend.max_header_bytes,
                         "max_body_bytes": backend.max_body_bytes,
+                        "min_compress_bytes": backend.min_compress_bytes,
                         "token_auth": backend.token_auth.as_ref().map(|token| json!({
                             "issuer": token.issuer,
                             "audience": token.audience,
@@ -304,6 +321,9 @@ impl RpcDaemon {
                             "client_cert_path": mtls.client_cert_path,
                             "client_key_path": mtls.client_key_path,
                         })),
+                        "api_key_auth": backend.api_key_auth.as_ref().map(|auth| json!({
+                            "api_key": auth.api_key,
+                        })),
                     })
                 });
             let event_sink = parsed.config.event_sink.as_ref().map_or_else(
@@ -365,6 +385,7 @@ impl RpcDaemon {
                 self.sdk_stream_degraded.lock().expect("sdk_stream_degraded mutex poisoned");
             *guard = false;
         }
+        *self.sdk_draining.lock().expect("sdk_draining mutex poisoned") = false;
         {
             self.sdk_seen_jti.lock().expect("sdk_seen_jti mutex poisoned").clear();
             *self
@@ -385,8 +406,8 @@ impl RpcDaemon {
                 "active_contract_version": active_contract_version,
                 "effective_capabilities": effective_capabilities,
                 "effective_limits": limits,
-                "contract_release": "v2.5",
-                "schema_namespace": "v2",
+                "contract_release": CONTRACT_RELEASE,
+                "schema_namespace": SCHEMA_NAMESPACE,
                 "meta": self.response_meta(),
             })),
             error: None,
@@ -435,6 +456,7 @@ impl RpcDaemon {
         let max_event_bytes = self.sdk_max_event_bytes();
         let max_batch_bytes = self.sdk_max_batch_bytes();
         let max_extension_keys = self.sdk_max_extension_keys();
+        let oversize_policy = self.sdk_event_oversize_policy();
 
         let cursor_seq = match self.sdk_decode_cursor(parsed.cursor.as_deref()) {
             Ok(value) => value,
@@ -449,21 +471,18 @@ impl RpcDaemon {
         let oldest_seq = log_guard.front().map(|entry| entry.seq_no);
         let latest_seq = log_guard.back().map(|entry| entry.seq_no);
 
-        if cursor_is_expired(cursor_seq, oldest_seq) {
+        if let Some(error) = self.sdk_cursor_expired_error(cursor_seq, oldest_seq) {
             let mut degraded =
                 self.sdk_stream_degraded.lock().expect("sdk_stream_degraded mutex poisoned");
             *degraded = true;
-            return Ok(self.sdk_error_response(
-                request.id,
-                "SDK_RUNTIME_CURSOR_EXPIRED",
-                "cursor is outside retained event window",
-            ));
+            return Ok(RpcResponse { id: request.id, result: None, error: Some(error) });
         }
 
         let start_seq = cursor_seq.map(|value| value.saturating_add(1)).or(oldest_seq).unwrap_or(0);
         let mut event_rows = Vec::new();
         let mut batch_bytes = 0_usize;
 
+        #[allow(clippy::result_large_err)]
         let append_event_row =
             |row: JsonValue, event_rows: &mut Vec<JsonValue>, batch_bytes: &mut usize| {
                 let payload_bytes =
@@ -527,12 +546,45 @@ impl RpcDaemon {
         }
 
         let remaining_slots = parsed.max.saturating_sub(event_rows.len());
+        let mut oversize_events = Vec::new();
+        let mut skipped_seq = None;
+        // Filtered-out events never fill a slot, but the cursor still moves
+        // past them so they are not scanned again on the next poll.
+        let mut filtered_seq = None;
+        let wanted_type = |event_type: &str| {
+            parsed.event_types.is_empty()
+                || parsed.event_types.iter().any(|wanted| wanted == event_type)
+        };
+        let mut scanned = 0_usize;
         for entry in log_guard
             .iter()
             .filter(|entry| entry.seq_no >= start_seq)
             .filter(|entry| entry.event.event_type != "sdk_lifecycle_trace")
-            .take(remaining_slots)
         {
+            if scanned == remaining_slots {
+                break;
+            }
+            if !wanted_type(entry.event.event_type.as_str()) {
+                filtered_seq = Some(entry.seq_no);
+                continue;
+            }
+            scanned += 1;
+            let mut payload = entry.event.payload.clone();
+            let payload_bytes = payload.to_string().len();
+            if payload_bytes > max_event_bytes && oversize_policy != "reject" {
+                let marker = json!({
+                    "seq_no": entry.seq_no,
+                    "event_type": entry.event.event_type,
+                    "payload_bytes": payload_bytes,
+                    "max_event_bytes": max_event_bytes,
+                });
+                if oversize_policy == "skip" {
+                    oversize_events.push(marker);
+                    skipped_seq = Some(entry.seq_no);
+                    continue;
+                }
+                payload = json!({ "oversize_event": marker });
+            }
             let event_row = json!({
                 "event_id": format!("evt-{}", entry.seq_no),
                 "runtime_id": self.identity_hash,
@@ -543,7 +595,7 @@ impl RpcDaemon {
                 "event_type": entry.event.event_type.clone(),
                 "severity": Self::event_severity(entry.event.event_type.as_str()),
                 "source_component": "rns-rpc",
-                "payload": entry.event.payload.clone(),
+                "payload": payload,
             });
             if let Err(response) = append_event_row(event_row, &mut event_rows, &mut batch_bytes) {
                 return Ok(response);
@@ -554,11 +606,31 @@ impl RpcDaemon {
             .iter()
             .rev()
             .find_map(|event| event.get("seq_no").and_then(JsonValue::as_u64))
+            .max(skipped_seq)
+            .max(filtered_seq)
             .or(cursor_seq)
             .or(latest_seq)
             .unwrap_or(0);
         let next_cursor = self.sdk_encode_cursor(next_seq);
 
+        let mut batch_ids = Vec::new();
+        for event in &event_rows {
+            let Some(message_id) =
+                event.pointer("/payload/message/id").and_then(JsonValue::as_str)
+            else {
+                continue;
+            };
+            let batch_id = self.store.message_batch(message_id).map_err(std::io::Error::other)?;
+            if let Some(batch_id) = batch_id {
+                if !batch_ids.contains(&batch_id) {
+                    batch_ids.push(batch_id);
+                }
+            }
+        }
+
+        let gap_dropped_count = if parsed.cursor.is_none() { dropped_count } else { 0 };
+        let reported_dropped_count = gap_dropped_count + oversize_events.len() as u64;
+
         if clear_degraded_on_success {
             let mut degraded =
                 self.sdk_stream_degraded.lock().expect("sdk_stream_degraded mutex poisoned");
@@ -572,7 +644,10 @@ impl RpcDaemon {
                 "stream_id": SDK_STREAM_ID,
                 "events": event_rows,
                 "next_cursor": next_cursor,
-                "dropped_count": if parsed.cursor.is_none() { dropped_count } else { 0 },
+                "dropped_count": reported_dropped_count,
+                "snapshot_high_watermark_seq_no": latest_seq,
+                "oversize_events": oversize_events,
+                "batch_ids": batch_ids,
                 "meta": self.response_meta(),
             })),
             error: None,
@@ -14,6 +14,49 @@ impl RpcDaemon {
         options: OutboundDeliveryOptions,
         include_ticket: Option<bool>,
     ) -> Result<RpcResponse, std::io::Error> {
+        let idempotency = idempotency_claim(&title, &content, fields.as_ref());
+        if let Some((key, payload_hash)) = &idempotency {
+            if let Some(response) =
+                self.replay_idempotent_send(request_id, &source, &destination, key, payload_hash)?
+            {
+                return Ok(response);
+            }
+        }
+        let title = match self.check_outbound_admission(
+            &source,
+            &destination,
+            &title,
+            &content,
+            fields.as_ref(),
+            &options,
+        ) {
+            Ok(title) => title,
+            Err(error) => {
+                return Ok(RpcResponse { id: request_id, result: None, error: Some(error) })
+            }
+        };
+        if options.fail_fast_no_path {
+            if let Some(bridge) = &self.outbound_bridge {
+                match bridge.await_path(&destination, FAIL_FAST_PATH_TIMEOUT) {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        return Ok(self.sdk_error_response(
+                            request_id,
+                            "SDK_RUNTIME_NO_PATH",
+                            &format!("no path to destination '{destination}'"),
+                        ));
+                    }
+                    Err(err) if err.kind() == std::io::ErrorKind::Unsupported => {
+                        return Ok(self.sdk_error_response(
+                            request_id,
+                            "SDK_CAPABILITY_DISABLED",
+                            &err.to_string(),
+                        ));
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+        }
         let timestamp = now_i64();
         if self.enforce_store_forward_retention(timestamp)? {
             return Ok(self.sdk_error_response(
@@ -22,8 +65,8 @@ impl RpcDaemon {
                 "store-forward capacity reached and policy rejected new outbound message",
             ));
         }
-        self.append_delivery_trace(&id, "queued".to_string());
-        let mut record = MessageRecord {
+        let fields = merge_fields_with_options(fields, method.clone(), stamp_cost, include_ticket);
+        let record = MessageRecord {
             id: id.clone(),
             source,
             destination,
@@ -31,19 +74,269 @@ impl RpcDaemon {
             content,
             timestamp,
             direction: "out".into(),
-            fields: merge_fields_with_options(fields, method.clone(), stamp_cost, include_ticket),
+            reply_to: reply_to_from_fields(fields.as_ref()),
+            fields,
             receipt_status: None,
         };
 
         self.store.insert_message(&record).map_err(std::io::Error::other)?;
+        if let Some((key, payload_hash)) = idempotency {
+            self.store
+                .put_idempotency_record(&IdempotencyRecord {
+                    source: record.source.trim().to_string(),
+                    destination: record.destination.trim().to_string(),
+                    key,
+                    payload_hash,
+                    message_id: id.clone(),
+                    created_ms: now_millis_u64(),
+                    replayed: false,
+                })
+                .map_err(std::io::Error::other)?;
+        }
+        self.append_delivery_trace(&id, "queued".to_string());
+        let rate_key = record.destination.clone();
+        let mut response = match options.scheduled_ts_ms.filter(|due_ms| *due_ms > now_millis_u64())
+        {
+            Some(due_ms) => {
+                self.schedule_outbound(request_id, record, method, stamp_cost, options, due_ms)?
+            }
+            None => self.dispatch_outbound(request_id, record, method, stamp_cost, options)?,
+        };
+        if response.error.is_none() {
+            self.charge_destination_rate_limit(&rate_key);
+        }
+        if let Some(result) = response.result.as_mut().and_then(JsonValue::as_object_mut) {
+            result.insert("deduplicated".to_string(), JsonValue::Bool(false));
+        }
+        Ok(response)
+    }
+
+    /// Answers a send whose idempotency key was already used within
+    /// `idempotency_ttl_ms`: with the original message id when the payload
+    /// matches, else with `SDK_VALIDATION_IDEMPOTENCY_CONFLICT`. Keys are
+    /// persisted, so a new client process or a restart still deduplicates.
+    fn replay_idempotent_send(
+        &self,
+        request_id: u64,
+        source: &str,
+        destination: &str,
+        key: &str,
+        payload_hash: &str,
+    ) -> Result<Option<RpcResponse>, std::io::Error> {
+        let cutoff_ms = now_millis_u64().saturating_sub(self.sdk_idempotency_ttl_ms());
+        self.store.prune_idempotency_records(cutoff_ms).map_err(std::io::Error::other)?;
+        let Some(mut existing) = self
+            .store
+            .get_idempotency_record(source.trim(), destination.trim(), key)
+            .map_err(std::io::Error::other)?
+        else {
+            return Ok(None);
+        };
+        if existing.payload_hash != payload_hash {
+            return Ok(Some(self.sdk_error_response(
+                request_id,
+                "SDK_VALIDATION_IDEMPOTENCY_CONFLICT",
+                "idempotency key already used for different payload",
+            )));
+        }
+        if !existing.replayed {
+            existing.replayed = true;
+            self.store.put_idempotency_record(&existing).map_err(std::io::Error::other)?;
+        }
+        Ok(Some(RpcResponse {
+            id: request_id,
+            result: Some(json!({ "message_id": existing.message_id, "deduplicated": true })),
+            error: None,
+        }))
+    }
+
+    /// Checks a send must pass before its message record is created, shared
+    /// by real sends and dry runs so a plan is only reported for a send that
+    /// would be accepted. Returns the title after the title policy.
+    #[allow(clippy::result_large_err)]
+    fn check_outbound_admission(
+        &self,
+        source: &str,
+        destination: &str,
+        title: &str,
+        content: &str,
+        fields: Option<&JsonValue>,
+        options: &OutboundDeliveryOptions,
+    ) -> Result<String, RpcError> {
+        check_source_private_key(source, options.source_private_key.as_deref())?;
+        let title = self.apply_title_policy(title)?;
+        self.check_content_limit(content)?;
+        self.check_attachment_limits(fields)?;
+        if *self.sdk_draining.lock().expect("sdk_draining mutex poisoned") {
+            return Err(RpcError::new(
+                "SDK_RUNTIME_INVALID_STATE",
+                "runtime is draining for shutdown and does not accept new sends",
+            ));
+        }
+        if let Some(ticket) = options.ticket.as_deref().filter(|ticket| self.is_ticket_revoked(ticket))
+        {
+            return Err(RpcError::new(
+                "SDK_SECURITY_TICKET_REVOKED",
+                format!("ticket '{ticket}' has been revoked"),
+            ));
+        }
+        self.check_destination_rate_limit(destination)?;
+        Ok(title)
+    }
+
+    /// Rejects content longer, in UTF-8 bytes, than the runtime config allows,
+    /// before anything is stored or encoded. Titles are limited by
+    /// [`Self::apply_title_policy`].
+    #[allow(clippy::result_large_err)]
+    fn check_content_limit(&self, content: &str) -> Result<(), RpcError> {
+        let limit = self.sdk_max_content_bytes();
+        if limit > 0 && content.len() > limit {
+            return Err(content_too_large_error("content", "max_content_bytes", limit, content.len()));
+        }
+        Ok(())
+    }
+
+    /// Rejects sends whose attachments exceed the delivery policy's count or
+    /// total decoded size, naming the attachment that crossed the limit.
+    #[allow(clippy::result_large_err)]
+    fn check_attachment_limits(&self, fields: Option<&JsonValue>) -> Result<(), RpcError> {
+        let (max_bytes, max_count) = {
+            let policy = self.delivery_policy.lock().expect("policy mutex poisoned");
+            (policy.max_attachment_bytes, policy.max_attachments)
+        };
+        let attachments = message_attachment_entries(fields);
+        if max_count > 0 && attachments.len() > max_count {
+            let (name, _) = &attachments[max_count];
+            return Err(attachment_limit_error(
+                format!(
+                    "attachment '{name}' exceeds max_attachments {max_count} ({} attachments)",
+                    attachments.len()
+                ),
+                name,
+                max_count,
+                "max_attachments",
+                max_count,
+                attachments.len(),
+            ));
+        }
+        if max_bytes == 0 {
+            return Ok(());
+        }
+        let mut total = 0usize;
+        for (index, (name, data)) in attachments.iter().enumerate() {
+            total = total.saturating_add(data.len());
+            if total > max_bytes {
+                return Err(attachment_limit_error(
+                    format!(
+                        "attachment '{name}' brings attachments to {total} bytes which exceeds max_attachment_bytes {max_bytes}"
+                    ),
+                    name,
+                    index,
+                    "max_attachment_bytes",
+                    max_bytes,
+                    total,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reloads the tickets revoked in earlier runs, so they stay unusable.
+    fn restore_revoked_tickets(&self) -> Result<usize, std::io::Error> {
+        let stored = self.store.list_revoked_tickets().map_err(std::io::Error::other)?;
+        let count = stored.len();
+        self.revoked_tickets
+            .lock()
+            .expect("revoked_tickets mutex poisoned")
+            .extend(stored.iter().map(|ticket| normalize_ticket(ticket)));
+        Ok(count)
+    }
+
+    fn is_ticket_revoked(&self, ticket: &str) -> bool {
+        self.revoked_tickets
+            .lock()
+            .expect("revoked_tickets mutex poisoned")
+            .contains(&normalize_ticket(ticket))
+    }
+
+    /// Refuses a send with `SDK_RUNTIME_RATE_LIMITED` once the destination's
+    /// one-minute window is full; `retry_after_ms` says when the oldest send in
+    /// the window expires. Only accepted sends fill the window, see
+    /// [`Self::charge_destination_rate_limit`].
+    #[allow(clippy::result_large_err)]
+    fn check_destination_rate_limit(&self, destination: &str) -> Result<(), RpcError> {
+        let limit = self.sdk_per_destination_rate_limit();
+        if limit == 0 {
+            return Ok(());
+        }
+        let now = now_millis_u64();
+        let mut send_times = self
+            .sdk_destination_send_times
+            .lock()
+            .expect("sdk_destination_send_times mutex poisoned");
+        send_times.retain(|_, sent| {
+            while sent.front().is_some_and(|sent_ms| now.saturating_sub(*sent_ms) >= 60_000) {
+                sent.pop_front();
+            }
+            !sent.is_empty()
+        });
+        let Some(sent) = send_times
+            .get(&destination.trim().to_ascii_lowercase())
+            .filter(|sent| sent.len() >= limit as usize)
+        else {
+            return Ok(());
+        };
+        let retry_after_ms =
+            sent.front().map_or(0, |oldest| oldest.saturating_add(60_000).saturating_sub(now));
+        let mut error = RpcError::new(
+            "SDK_RUNTIME_RATE_LIMITED",
+            format!("destination '{destination}' exceeded {limit} sends per minute"),
+        );
+        error.retryable = Some(true);
+        let mut details = JsonMap::new();
+        details.insert("destination".to_string(), json!(destination));
+        details.insert("limit".to_string(), json!(limit));
+        details.insert("retry_after_ms".to_string(), json!(retry_after_ms));
+        error.details = Some(Box::new(details));
+        Err(error)
+    }
+
+    /// Counts an accepted send against the destination's one-minute window.
+    fn charge_destination_rate_limit(&self, destination: &str) {
+        if self.sdk_per_destination_rate_limit() == 0 {
+            return;
+        }
+        self.sdk_destination_send_times
+            .lock()
+            .expect("sdk_destination_send_times mutex poisoned")
+            .entry(destination.trim().to_ascii_lowercase())
+            .or_default()
+            .push_back(now_millis_u64());
+    }
+
+    /// Hands a stored outbound record to the bridge and records the resulting
+    /// status. Scheduled messages reach this once their send time has passed.
+    fn dispatch_outbound(
+        &self,
+        request_id: u64,
+        mut record: MessageRecord,
+        method: Option<String>,
+        stamp_cost: Option<u32>,
+        mut options: OutboundDeliveryOptions,
+    ) -> Result<RpcResponse, std::io::Error> {
+        let id = record.id.clone();
         self.append_delivery_trace(&id, "sending".to_string());
         let deliver_result = if let Some(bridge) = &self.outbound_bridge {
+            if let Some(cost) = stamp_cost.or(options.stamp_cost).filter(|cost| *cost > 0) {
+                options.stamp_task = Some(self.begin_stamp_task(&id, cost));
+            }
             bridge.deliver(&record, &options)
         } else {
             let _delivered = crate::transport::test_bridge::deliver_outbound(&record);
             Ok(())
         };
         if let Err(err) = deliver_result {
+            self.finish_stamp_task(&id);
             let status = format!("failed: {err}");
             let resolved_status = {
                 let _status_guard =
@@ -89,6 +382,7 @@ impl RpcDaemon {
                 error: Some(RpcError::new("DELIVERY_FAILED", err.to_string())),
             });
         }
+        self.start_rtt_timer(&id, &record.destination);
         let sent_status = format!("sent: {}", method.as_deref().unwrap_or("direct"));
         let resolved_status = {
             let _status_guard =
@@ -116,6 +410,9 @@ impl RpcDaemon {
                 sent_status
             }
         };
+        if !Self::is_terminal_receipt_status(&resolved_status) {
+            self.watch_receipt(&id, method.as_deref() == Some("propagated"));
+        }
         record.receipt_status = Some(resolved_status.clone());
         let event = RpcEvent {
             event_type: "outbound".into(),
@@ -130,6 +427,438 @@ impl RpcDaemon {
         Ok(RpcResponse { id: request_id, result: Some(json!({ "message_id": id })), error: None })
     }
 
+    fn schedule_outbound(
+        &self,
+        request_id: u64,
+        mut record: MessageRecord,
+        method: Option<String>,
+        stamp_cost: Option<u32>,
+        options: OutboundDeliveryOptions,
+        due_ms: u64,
+    ) -> Result<RpcResponse, std::io::Error> {
+        let id = record.id.clone();
+        self.store
+            .put_scheduled_send(&ScheduledSendRecord {
+                message_id: id.clone(),
+                due_ms,
+                method: method.clone(),
+                stamp_cost,
+                options: serde_json::to_value(&options).map_err(std::io::Error::other)?,
+            })
+            .map_err(std::io::Error::other)?;
+        self.store.update_receipt_status(&id, "scheduled").map_err(std::io::Error::other)?;
+        self.append_delivery_trace(&id, "scheduled".to_string());
+        record.receipt_status = Some("scheduled".to_string());
+        self.publish_event(RpcEvent {
+            event_type: "outbound".into(),
+            payload: json!({
+                "message": record,
+                "method": method,
+                "scheduled_ts_ms": due_ms,
+            }),
+        });
+        self.scheduled_sends.lock().expect("scheduled sends mutex poisoned").push(ScheduledSend {
+            due_ms,
+            record,
+            method,
+            stamp_cost,
+            options,
+        });
+        Ok(RpcResponse {
+            id: request_id,
+            result: Some(json!({ "message_id": id, "scheduled_ts_ms": due_ms })),
+            error: None,
+        })
+    }
+
+    /// Dispatches every scheduled message whose send time has passed and
+    /// returns how many were handed to the delivery pipeline.
+    pub fn dispatch_due_scheduled_sends(&self) -> usize {
+        let now_ms = now_millis_u64();
+        let due = {
+            let mut guard = self.scheduled_sends.lock().expect("scheduled sends mutex poisoned");
+            let (due, pending): (Vec<_>, Vec<_>) =
+                guard.drain(..).partition(|scheduled| scheduled.due_ms <= now_ms);
+            *guard = pending;
+            due
+        };
+        let count = due.len();
+        for scheduled in due {
+            let id = scheduled.record.id.clone();
+            if let Err(err) = self.store.delete_scheduled_send(&id) {
+                self.record_log_line(format!("scheduled send {id} not cleared: {err}"));
+            }
+            let record = scheduled.record.clone();
+            let method = scheduled.method.clone();
+            if let Err(err) = self.dispatch_outbound(
+                0,
+                scheduled.record,
+                scheduled.method,
+                scheduled.stamp_cost,
+                scheduled.options,
+            ) {
+                self.fail_scheduled_send(record, method, &err);
+            }
+        }
+        count
+    }
+
+    /// Marks a scheduled send failed when it could not be dispatched, so it
+    /// does not sit in `scheduled` forever.
+    fn fail_scheduled_send(
+        &self,
+        mut record: MessageRecord,
+        method: Option<String>,
+        err: &std::io::Error,
+    ) {
+        let status = format!("failed: {err}");
+        {
+            let _status_guard =
+                self.delivery_status_lock.lock().expect("delivery_status_lock mutex poisoned");
+            let existing_status = self
+                .store
+                .get_message(&record.id)
+                .ok()
+                .flatten()
+                .and_then(|message| message.receipt_status);
+            if existing_status.as_deref().is_some_and(Self::is_terminal_receipt_status) {
+                return;
+            }
+            if let Err(store_err) = self.store.update_receipt_status(&record.id, &status) {
+                self.record_log_line(format!(
+                    "scheduled send {} failed ({err}) and was not marked: {store_err}",
+                    record.id
+                ));
+            }
+        }
+        self.finish_stamp_task(&record.id);
+        self.append_delivery_trace(&record.id, status.clone());
+        record.receipt_status = Some(status.clone());
+        self.publish_event(RpcEvent {
+            event_type: "outbound".into(),
+            payload: json!({
+                "message": record,
+                "method": method,
+                "error": err.to_string(),
+                "reason_code": delivery_reason_code(&status),
+            }),
+        });
+    }
+
+    /// Reloads the sends persisted with a future send time so a restart keeps
+    /// them scheduled. Ones that fell due while the daemon was down go out on
+    /// the next tick of the scheduled send loop.
+    fn restore_scheduled_sends(&self) -> Result<usize, std::io::Error> {
+        let persisted = self.store.list_scheduled_sends().map_err(std::io::Error::other)?;
+        let mut restored = Vec::with_capacity(persisted.len());
+        for entry in persisted {
+            let record = self
+                .store
+                .get_message(&entry.message_id)
+                .map_err(std::io::Error::other)?
+                .filter(|record| record.receipt_status.as_deref() == Some("scheduled"));
+            let Some(record) = record else {
+                self.store
+                    .delete_scheduled_send(&entry.message_id)
+                    .map_err(std::io::Error::other)?;
+                continue;
+            };
+            match serde_json::from_value::<OutboundDeliveryOptions>(entry.options) {
+                Ok(options) => restored.push(ScheduledSend {
+                    due_ms: entry.due_ms,
+                    record,
+                    method: entry.method,
+                    stamp_cost: entry.stamp_cost,
+                    options,
+                }),
+                Err(err) => {
+                    self.store
+                        .delete_scheduled_send(&entry.message_id)
+                        .map_err(std::io::Error::other)?;
+                    let err = std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("stored delivery options unreadable: {err}"),
+                    );
+                    self.fail_scheduled_send(record, entry.method, &err);
+                }
+            }
+        }
+        let count = restored.len();
+        self.scheduled_sends.lock().expect("scheduled sends mutex poisoned").extend(restored);
+        Ok(count)
+    }
+
+    /// Fails every watched message that has gone longer than its receipt
+    /// timeout without progress and returns how many were failed.
+    pub fn expire_stale_receipts(&self) -> usize {
+        self.expire_stale_receipts_at(now_millis_u64())
+    }
+
+    fn expire_stale_receipts_at(&self, now_ms: u64) -> usize {
+        let is_stale = |watch: &ReceiptWatch| {
+            let timeout_ms = self.sdk_receipt_timeout_ms(watch.propagated);
+            timeout_ms > 0 && now_ms.saturating_sub(watch.last_progress_ms) >= timeout_ms
+        };
+        let stale = {
+            let mut guard = self.receipt_watches.lock().expect("receipt watches mutex poisoned");
+            let ids = guard
+                .iter()
+                .filter(|(_, watch)| is_stale(watch))
+                .map(|(id, _)| id.clone())
+                .collect::<Vec<_>>();
+            ids.into_iter()
+                .filter_map(|id| guard.remove(&id).map(|watch| (id, watch)))
+                .collect::<Vec<_>>()
+        };
+
+        let mut expired = 0;
+        for (message_id, watch) in stale {
+            let status = "failed: timeout waiting for receipt".to_string();
+            {
+                let _status_guard =
+                    self.delivery_status_lock.lock().expect("delivery_status_lock mutex poisoned");
+                let existing_status = match self.store.get_message(&message_id) {
+                    Ok(Some(message)) => message.receipt_status,
+                    _ => continue,
+                };
+                if existing_status.as_deref().is_some_and(Self::is_terminal_receipt_status) {
+                    continue;
+                }
+                if self.store.update_receipt_status(&message_id, &status).is_err() {
+                    continue;
+                }
+            }
+            self.append_delivery_trace(&message_id, status.clone());
+            self.finish_rtt_timer(&message_id, false);
+            self.cancel_bridge_delivery(&message_id);
+            self.publish_event(RpcEvent {
+                event_type: "delivery_failed".into(),
+                payload: json!({
+                    "message_id": message_id,
+                    "status": status,
+                    "reason_code": "timeout",
+                    "timeout_ms": self.sdk_receipt_timeout_ms(watch.propagated),
+                }),
+            });
+            expired += 1;
+        }
+        expired
+    }
+
+    /// Asks the bridge to stop delivering `message_id`. Bridges without
+    /// cancellable deliveries are skipped.
+    fn cancel_bridge_delivery(&self, message_id: &str) {
+        let Some(bridge) = &self.outbound_bridge else {
+            return;
+        };
+        match bridge.cancel_delivery(message_id) {
+            Ok(_) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::Unsupported => {}
+            Err(err) => {
+                self.record_log_line(format!("delivery of {message_id} not cancelled: {err}"));
+            }
+        }
+    }
+
+    fn cancel_scheduled_send(&self, message_id: &str) -> bool {
+        if let Err(err) = self.store.delete_scheduled_send(message_id) {
+            self.record_log_line(format!("scheduled send {message_id} not cleared: {err}"));
+        }
+        let mut guard = self.scheduled_sends.lock().expect("scheduled sends mutex poisoned");
+        let before = guard.len();
+        guard.retain(|scheduled| scheduled.record.id != message_id);
+        guard.len() != before
+    }
+
+    /// Submits several `sdk_send_v2` messages in one round-trip. Items are
+    /// validated and stored independently so one bad message does not fail the
+    /// batch; the store records which batch each accepted message came from.
+    fn handle_sdk_send_batch_v2(&self, request: RpcRequest) -> Result<RpcResponse, std::io::Error> {
+        if !self.sdk_has_capability("sdk.capability.send_batch") {
+            return Ok(self.sdk_capability_disabled_response(
+                request.id,
+                "sdk_send_batch_v2",
+                "sdk.capability.send_batch",
+            ));
+        }
+        let params = request.params.ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "missing params")
+        })?;
+        let parsed: SdkSendBatchV2Params = serde_json::from_value(params)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+        if parsed.messages.is_empty() {
+            return Ok(self.sdk_error_response(
+                request.id,
+                "SDK_VALIDATION_INVALID_ARGUMENT",
+                "send batch requires at least one message",
+            ));
+        }
+
+        let batch_id = self.next_sdk_domain_id("batch");
+        let mut results = Vec::with_capacity(parsed.messages.len());
+        for message in parsed.messages {
+            let item = match parse_outbound_send_request("sdk_send_v2", message) {
+                Ok(item) if item.dry_run => {
+                    results.push(json!({
+                        "error": RpcError::new(
+                            "SDK_VALIDATION_INVALID_ARGUMENT",
+                            "dry_run is not supported in a send batch",
+                        ),
+                    }));
+                    continue;
+                }
+                Ok(item) => item,
+                Err(err) => {
+                    results.push(json!({
+                        "error": RpcError::new("SDK_VALIDATION_INVALID_ARGUMENT", err.to_string()),
+                    }));
+                    continue;
+                }
+            };
+            let response = self.store_outbound(
+                request.id,
+                item.id,
+                item.source,
+                item.destination,
+                item.title,
+                item.content,
+                item.fields,
+                item.method,
+                item.stamp_cost,
+                item.options,
+                item.include_ticket,
+            )?;
+            match response.error {
+                Some(error) => results.push(json!({ "error": error })),
+                None => {
+                    let result = response.result.as_ref();
+                    let message_id = result
+                        .and_then(|result| result.get("message_id"))
+                        .and_then(JsonValue::as_str);
+                    let deduplicated = result
+                        .and_then(|result| result.get("deduplicated"))
+                        .and_then(JsonValue::as_bool)
+                        .unwrap_or(false);
+                    // A replayed message stays in the batch that first sent it.
+                    if let Some(message_id) = message_id.filter(|_| !deduplicated) {
+                        self.store
+                            .set_message_batch(message_id, &batch_id)
+                            .map_err(std::io::Error::other)?;
+                    }
+                    results.push(json!({ "message_id": message_id, "deduplicated": deduplicated }));
+                }
+            }
+        }
+
+        Ok(RpcResponse {
+            id: request.id,
+            result: Some(json!({
+                "batch_id": batch_id,
+                "results": results,
+                "meta": self.response_meta(),
+            })),
+            error: None,
+        })
+    }
+
+    /// Sanitizes `title` and applies the single title limit: the delivery
+    /// policy's `max_title_bytes` with its `title_limit_mode` when non-zero,
+    /// else the runtime config's `max_title_wire_bytes`, which always rejects.
+    #[allow(clippy::result_large_err)]
+    fn apply_title_policy(&self, title: &str) -> Result<String, RpcError> {
+        let (policy_max_bytes, policy_mode) = {
+            let policy = self.delivery_policy.lock().expect("policy mutex poisoned");
+            (policy.max_title_bytes, policy.title_limit_mode)
+        };
+        let (limit_name, max_bytes, mode) = if policy_max_bytes > 0 {
+            ("max_title_bytes", policy_max_bytes, policy_mode)
+        } else {
+            ("max_title_wire_bytes", self.sdk_max_title_wire_bytes(), LengthLimitMode::Reject)
+        };
+        let mut title = sanitize_title(title);
+        if max_bytes == 0 || title.len() <= max_bytes {
+            return Ok(title);
+        }
+        match mode {
+            LengthLimitMode::Reject => {
+                Err(content_too_large_error("title", limit_name, max_bytes, title.len()))
+            }
+            LengthLimitMode::Truncate => {
+                truncate_at_char_boundary(&mut title, max_bytes);
+                Ok(title)
+            }
+        }
+    }
+
+    /// Validates and plans a send without creating a message record, tracing
+    /// delivery, or handing anything to the outbound bridge for transmission.
+    fn plan_outbound(
+        &self,
+        request_id: u64,
+        request: NormalizedSendRequest,
+    ) -> Result<RpcResponse, std::io::Error> {
+        let source = request.source.trim().to_string();
+        let destination = request.destination.trim().to_string();
+        if source.is_empty() || destination.is_empty() {
+            let field = if source.is_empty() { "source" } else { "destination" };
+            return Err(invalid_field_error(
+                field,
+                "empty",
+                "source and destination must not be empty",
+            ));
+        }
+        let title = match self.check_outbound_admission(
+            &source,
+            &destination,
+            &request.title,
+            &request.content,
+            request.fields.as_ref(),
+            &request.options,
+        ) {
+            Ok(title) => title,
+            Err(error) => {
+                return Ok(RpcResponse { id: request_id, result: None, error: Some(error) })
+            }
+        };
+        let record = MessageRecord {
+            id: request.id.clone(),
+            source,
+            destination,
+            title,
+            content: request.content,
+            timestamp: now_i64(),
+            direction: "out".into(),
+            fields: merge_fields_with_options(
+                request.fields,
+                request.method.clone(),
+                request.stamp_cost,
+                request.include_ticket,
+            ),
+            receipt_status: None,
+            reply_to: None,
+        };
+        let plan = match &self.outbound_bridge {
+            Some(bridge) => bridge.plan(&record, &request.options)?,
+            None => estimate_outbound_plan(&record, &request.options),
+        };
+        let propagation_node = if plan.method == "propagated" {
+            self.propagation_relay_candidates(&[])?.into_iter().next()
+        } else {
+            None
+        };
+        Ok(RpcResponse {
+            id: request_id,
+            result: Some(json!({
+                "message_id": request.id,
+                "dry_run": true,
+                "plan": plan,
+                "propagation_node": propagation_node,
+                "meta": self.response_meta(),
+            })),
+            error: None,
+        })
+    }
+
     fn local_delivery_hash(&self) -> String {
         self.delivery_destination_hash
             .lock()
@@ -138,16 +867,32 @@ impl RpcDaemon {
             .unwrap_or_else(|| self.identity_hash.clone())
     }
 
+    fn current_identity_hash(&self) -> String {
+        self.rotated_identity_hash
+            .lock()
+            .expect("rotated_identity_hash mutex poisoned")
+            .clone()
+            .unwrap_or_else(|| self.identity_hash.clone())
+    }
+
     fn capabilities() -> Vec<&'static str> {
         vec![
             "status",
+            "whoami",
             "daemon_status_ex",
+            "version",
+            "version_info",
+            "health",
             "list_messages",
             "list_announces",
             "list_peers",
+            "peer_describe",
+            "peer_rtt",
+            "peer_ping",
             "send_message",
             "send_message_v2",
             "sdk_send_v2",
+            "sdk_send_batch_v2",
             "sdk_negotiate_v2",
             "sdk_status_v2",
             "sdk_configure_v2",
@@ -195,6 +940,9 @@ impl RpcDaemon {
             "sdk_voice_session_update_v2",
             "sdk_voice_session_close_v2",
             "announce_now",
+            "announce_once_to",
+            "identity_rotate",
+            "set_display_name",
             "list_interfaces",
             "set_interfaces",
             "reload_config",
@@ -206,6 +954,9 @@ impl RpcDaemon {
             "propagation_enable",
             "propagation_ingest",
             "propagation_fetch",
+            "propagation_stats",
+            "sync_now",
+            "stamp_status",
             "get_outbound_propagation_node",
             "set_outbound_propagation_node",
             "list_propagation_nodes",
@@ -213,8 +964,107 @@ impl RpcDaemon {
             "stamp_policy_get",
             "stamp_policy_set",
             "ticket_generate",
+            "tickets_list",
+            "ticket_revoke",
+            "peers_export",
+            "peers_import",
+            "peers_prune",
+            "reconnect",
             "message_delivery_trace",
+            "delivery_trace",
+            "trace_get",
+            "delivery_metrics",
+            "logs_tail",
+            "prune_messages",
+            "messages_search",
+            "messages_export",
+            "attachment_get",
         ]
     }
 
+    /// Cargo features enabled for this build (collected by `build.rs`), plus
+    /// `debug_assertions` in debug builds, as reported by `version`.
+    fn compiled_features() -> Vec<&'static str> {
+        let mut features =
+            env!("RNS_RPC_FEATURES").split(',').filter(|name| !name.is_empty()).collect::<Vec<_>>();
+        if cfg!(debug_assertions) {
+            features.push("debug_assertions");
+        }
+        features
+    }
+
+}
+
+fn attachment_limit_error(
+    message: String,
+    name: &str,
+    index: usize,
+    limit_name: &str,
+    limit: usize,
+    observed: usize,
+) -> RpcError {
+    let mut error = RpcError::new("SDK_VALIDATION_ATTACHMENT_LIMIT_EXCEEDED", message);
+    let mut details = JsonMap::new();
+    details.insert("attachment".to_string(), json!(name));
+    details.insert("index".to_string(), json!(index));
+    details.insert("limit_name".to_string(), json!(limit_name));
+    details.insert("limit".to_string(), json!(limit));
+    details.insert("observed".to_string(), json!(observed));
+    error.details = Some(Box::new(details));
+    error
+}
+
+/// Rejects a `source_private_key` that is not a valid identity key, and a
+/// `source` that is not the identity hash derived from it. A source that is
+/// not a 32-character hex hash is rejected rather than skipped.
+#[allow(clippy::result_large_err)]
+fn check_source_private_key(source: &str, source_private_key: Option<&str>) -> Result<(), RpcError> {
+    let Some(private_key) = source_private_key.map(str::trim).filter(|key| !key.is_empty()) else {
+        return Ok(());
+    };
+    let invalid = |field: &str, message: String| {
+        let mut error = RpcError::new("SDK_VALIDATION_INVALID_ARGUMENT", message);
+        let mut details = JsonMap::new();
+        details.insert("field".to_string(), json!(field));
+        error.details = Some(Box::new(details));
+        error
+    };
+    let Some(signer) = hex::decode(private_key)
+        .ok()
+        .and_then(|bytes| PrivateIdentity::from_private_key_bytes(&bytes).ok())
+    else {
+        return Err(invalid(
+            "source_private_key",
+            "source_private_key must be a hex-encoded identity private key".to_string(),
+        ));
+    };
+    let derived = hex::encode(signer.address_hash().as_slice());
+    let source = source.trim();
+    if !is_destination_hash(source) {
+        return Err(invalid(
+            "source",
+            format!("source '{source}' must be a 32-character hex hash when source_private_key is set"),
+        ));
+    }
+    if !source.eq_ignore_ascii_case(&derived) {
+        return Err(invalid(
+            "source",
+            format!("source {source} does not match source_private_key identity {derived}"),
+        ));
+    }
+    Ok(())
+}
+
+fn content_too_large_error(field: &str, limit_name: &str, limit: usize, observed: usize) -> RpcError {
+    let mut error = RpcError::new(
+        "SDK_VALIDATION_CONTENT_TOO_LARGE",
+        format!("{field} is {observed} bytes which exceeds {limit_name} {limit}"),
+    );
+    let mut details = JsonMap::new();
+    details.insert("field".to_string(), json!(field));
+    details.insert("limit_name".to_string(), json!(limit_name));
+    details.insert("limit".to_string(), json!(limit));
+    details.insert("observed".to_string(), json!(observed));
+    error.details = Some(Box::new(details));
+    error
 }
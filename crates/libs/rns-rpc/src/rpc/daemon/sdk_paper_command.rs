@@ -20,9 +20,9 @@ impl RpcDaemon {
         let message_id = match Self::normalize_non_empty(parsed.message_id.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "message_id",
                     "message_id must not be empty",
                 ))
             }
@@ -120,9 +120,9 @@ impl RpcDaemon {
         let command = match Self::normalize_non_empty(parsed.command.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "command",
                     "command must not be empty",
                 ))
             }
@@ -172,9 +172,9 @@ impl RpcDaemon {
         let correlation_id = match Self::normalize_non_empty(parsed.correlation_id.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "correlation_id",
                     "correlation_id must not be empty",
                 ))
             }
@@ -10,9 +10,9 @@ impl RpcDaemon {
             .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
         let message_id = parsed.message_id.trim();
         if message_id.is_empty() {
-            return Ok(self.sdk_error_response(
+            return Ok(self.sdk_empty_field_response(
                 request.id,
-                "SDK_VALIDATION_INVALID_ARGUMENT",
+                "message_id",
                 "message_id must not be empty",
             ));
         }
@@ -72,7 +72,17 @@ impl RpcDaemon {
             }
         }
 
+        // Bridges report "sent" once delivery is handed off, but a message still
+        // waiting on its stamp has not left the node yet.
+        if cancel_result == "TooLateToCancel" && self.cancel_stamp_task(message_id) {
+            cancel_result = "Accepted";
+        }
+
         if cancel_result == "Accepted" {
+            self.cancel_scheduled_send(message_id);
+            self.cancel_stamp_task(message_id);
+            self.cancel_bridge_delivery(message_id);
+            self.finish_rtt_timer(message_id, false);
             self.store
                 .update_receipt_status(message_id, "cancelled")
                 .map_err(std::io::Error::other)?;
@@ -102,17 +112,20 @@ impl RpcDaemon {
             .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
         let message_id = parsed.message_id.trim();
         if message_id.is_empty() {
-            return Ok(self.sdk_error_response(
+            return Ok(self.sdk_empty_field_response(
                 request.id,
-                "SDK_VALIDATION_INVALID_ARGUMENT",
+                "message_id",
                 "message_id must not be empty",
             ));
         }
         let message = self.store.get_message(message_id).map_err(std::io::Error::other)?;
+        let deduplicated =
+            self.store.message_replayed(message_id).map_err(std::io::Error::other)?;
         Ok(RpcResponse {
             id: request.id,
             result: Some(json!({
                 "message": message,
+                "deduplicated": deduplicated,
                 "meta": self.response_meta(),
             })),
             error: None,
@@ -135,7 +148,21 @@ impl RpcDaemon {
             "store_forward",
             "event_stream",
             "event_sink",
+            "audit_log",
             "idempotency_ttl_ms",
+            "per_destination_rate_limit",
+            "telemetry_max_points_per_peer",
+            "announce_interval_secs",
+            "max_content_bytes",
+            "max_title_wire_bytes",
+            "receipt_timeout_ms",
+            "propagation_receipt_timeout_ms",
+            "propagation_message_ttl_ms",
+            "propagation_selection_strategy",
+            "propagation_node",
+            "inbound_allowlist",
+            "inbound_blocklist",
+            "inbound_contacts_bypass",
             "redaction",
             "rpc_backend",
             "extensions",
@@ -150,6 +177,9 @@ impl RpcDaemon {
 
         let _apply_guard =
             self.sdk_config_apply_lock.lock().expect("sdk_config_apply_lock mutex poisoned");
+        // Sync with the persisted state before applying; restoring after the
+        // apply would roll the patch back to the previously persisted config.
+        let _domain_guard = self.lock_and_restore_sdk_domain_snapshot()?;
         let mut revision_guard =
             self.sdk_config_revision.lock().expect("sdk_config_revision mutex poisoned");
         if parsed.expected_revision != *revision_guard {
@@ -177,9 +207,21 @@ impl RpcDaemon {
         let revision = *revision_guard;
         drop(revision_guard);
 
-        {
-            let _domain_guard = self.lock_and_restore_sdk_domain_snapshot()?;
-            self.persist_sdk_domain_snapshot()?;
+        self.persist_sdk_domain_snapshot()?;
+        drop(_domain_guard);
+
+        if patch_map.contains_key("announce_interval_secs") {
+            self.announce_rearm.notify_one();
+        }
+        if patch_map.contains_key("per_destination_rate_limit") {
+            // A new limit (or `null`, which clears it) starts every window afresh.
+            self.sdk_destination_send_times
+                .lock()
+                .expect("sdk_destination_send_times mutex poisoned")
+                .clear();
+        }
+        if let Some(enabled) = patch_map.get("propagation_node").and_then(JsonValue::as_bool) {
+            self.set_propagation_node_role(enabled);
         }
 
         let event = RpcEvent {
@@ -216,20 +258,48 @@ impl RpcDaemon {
             ));
         }
 
-        let event = RpcEvent {
-            event_type: "runtime_shutdown_requested".into(),
-            payload: json!({
-                "mode": mode,
-                "flush_timeout_ms": parsed.flush_timeout_ms,
-            }),
-        };
-        self.publish_event(event);
+        let drain_timeout_ms = (mode == "graceful").then(|| {
+            parsed
+                .drain_timeout_ms
+                .or(parsed.flush_timeout_ms)
+                .unwrap_or(DEFAULT_SHUTDOWN_DRAIN_TIMEOUT_MS)
+                .min(MAX_SHUTDOWN_DRAIN_TIMEOUT_MS)
+        });
+        let graceful = mode == "graceful";
+        // Only a graceful shutdown drains. An immediate one leaves the runtime
+        // state alone and reports the outbound messages it abandons instead.
+        let already_draining = graceful
+            && std::mem::replace(
+                &mut *self.sdk_draining.lock().expect("sdk_draining mutex poisoned"),
+                true,
+            );
+        // The drain itself is never awaited here: blocking would park the RPC
+        // loop that delivers the very sends being drained. Callers repeat the
+        // request to poll `pending_messages` until it reaches zero or their
+        // `drain_timeout_ms` runs out.
+        let pending_messages =
+            self.store.count_pending_outbound_messages().map_err(std::io::Error::other)?;
+        let abandoned_messages = (!graceful).then_some(pending_messages);
+        if !already_draining {
+            self.publish_event(RpcEvent {
+                event_type: "runtime_shutdown_requested".into(),
+                payload: json!({
+                    "mode": mode,
+                    "flush_timeout_ms": parsed.flush_timeout_ms,
+                    "drain_timeout_ms": drain_timeout_ms,
+                    "abandoned_messages": abandoned_messages,
+                }),
+            });
+        }
 
         Ok(RpcResponse {
             id: request.id,
             result: Some(json!({
                 "accepted": true,
                 "mode": mode,
+                "drain_timeout_ms": drain_timeout_ms,
+                "pending_messages": pending_messages,
+                "abandoned_messages": abandoned_messages,
             })),
             error: None,
         })
@@ -259,26 +329,102 @@ impl RpcDaemon {
             .expect("sdk_effective_capabilities mutex poisoned")
             .clone();
 
+        let draining = *self.sdk_draining.lock().expect("sdk_draining mutex poisoned");
         let (queued_messages, in_flight_messages) =
             self.store.count_message_buckets().map_err(std::io::Error::other)?;
+        let announce_interval_secs =
+            Some(self.effective_announce_interval_secs()).filter(|secs| *secs > 0);
+        let propagation_node =
+            self.propagation_state.lock().expect("propagation mutex poisoned").enabled;
+        let event_buffer_used =
+            self.sdk_event_log.lock().expect("sdk_event_log mutex poisoned").len();
+        let inbound_pending = self
+            .inbound_pending_probe
+            .lock()
+            .expect("inbound_pending_probe mutex poisoned")
+            .as_ref()
+            .map_or(0, |probe| probe());
 
-        Ok(RpcResponse {
-            id: request.id,
-            result: Some(json!({
-                "runtime_id": self.identity_hash,
-                "state": "running",
-                "active_contract_version": active_contract_version,
-                "event_stream_position": event_stream_position,
-                "config_revision": config_revision,
-                "profile": profile,
-                "effective_capabilities": effective_capabilities,
-                "queued_messages": queued_messages,
-                "in_flight_messages": in_flight_messages,
-                "counts_included": params.include_counts,
-                "meta": self.response_meta(),
-            })),
-            error: None,
-        })
+        let mut snapshot = [
+            ("runtime_id", json!(self.identity_hash)),
+            ("state", json!(if draining { "draining" } else { "running" })),
+            ("active_contract_version", json!(active_contract_version)),
+            ("event_stream_position", json!(event_stream_position)),
+            ("config_revision", json!(config_revision)),
+            ("profile", json!(profile)),
+            ("effective_capabilities", json!(effective_capabilities)),
+            ("queued_messages", json!(queued_messages)),
+            ("in_flight_messages", json!(in_flight_messages)),
+            ("announce_interval_secs", json!(announce_interval_secs)),
+            ("propagation_node", json!(propagation_node)),
+            ("outbound_queue_depth", json!(self.store.pending_outbound_count())),
+            ("inbound_pending", json!(inbound_pending)),
+            ("event_buffer_used", json!(event_buffer_used)),
+            ("event_buffer_capacity", json!(SDK_EVENT_LOG_CAPACITY)),
+            ("messages_stored", json!(self.store.stored_message_count())),
+        ]
+        .into_iter()
+        .map(|(field, value)| (field.to_string(), value))
+        .collect::<JsonMap<String, JsonValue>>();
+        let (snapshot_revision, changed) =
+            self.track_snapshot_revision(&snapshot, params.since_revision.unwrap_or(0));
+        if params.since_revision.is_some() {
+            return Ok(RpcResponse {
+                id: request.id,
+                result: Some(json!({
+                    "snapshot_revision": snapshot_revision,
+                    "unchanged": changed.is_empty(),
+                    "changed": changed,
+                    "counts_included": params.include_counts,
+                    "meta": self.response_meta(),
+                })),
+                error: None,
+            });
+        }
+        snapshot.insert("snapshot_revision".into(), json!(snapshot_revision));
+        snapshot.insert("counts_included".into(), json!(params.include_counts));
+        snapshot.insert("meta".into(), self.response_meta());
+        Ok(RpcResponse { id: request.id, result: Some(JsonValue::Object(snapshot)), error: None })
+    }
+
+    /// Folds the current snapshot into the per-field revision log, bumping the
+    /// snapshot revision when any field changed, and returns that revision with
+    /// the fields changed after `since_revision`. Revision 0, or one this daemon
+    /// has not reached (for example after a restart), yields every field.
+    fn track_snapshot_revision(
+        &self,
+        snapshot: &JsonMap<String, JsonValue>,
+        since_revision: u64,
+    ) -> (u64, JsonMap<String, JsonValue>) {
+        let mut revisions =
+            self.sdk_snapshot_revisions.lock().expect("sdk_snapshot_revisions mutex poisoned");
+        let next_revision = revisions.revision + 1;
+        let mut bumped = false;
+        for (field, value) in snapshot {
+            match revisions.fields.get_mut(field) {
+                Some((previous, _)) if previous == value => {}
+                Some(entry) => {
+                    *entry = (value.clone(), next_revision);
+                    bumped = true;
+                }
+                None => {
+                    revisions.fields.insert(field.clone(), (value.clone(), next_revision));
+                    bumped = true;
+                }
+            }
+        }
+        if bumped {
+            revisions.revision = next_revision;
+        }
+        let since_revision =
+            if since_revision > revisions.revision { 0 } else { since_revision };
+        let changed = revisions
+            .fields
+            .iter()
+            .filter(|(_, (_, changed_at))| *changed_at > since_revision)
+            .map(|(field, (value, _))| (field.clone(), value.clone()))
+            .collect();
+        (revisions.revision, changed)
     }
 
 }
@@ -18,9 +18,9 @@ impl RpcDaemon {
             Some(value) => {
                 let normalized = Self::normalize_non_empty(value.as_str());
                 if normalized.is_none() {
-                    return Ok(self.sdk_error_response(
+                    return Ok(self.sdk_empty_field_response(
                         request.id,
-                        "SDK_VALIDATION_INVALID_ARGUMENT",
+                        "topic_path",
                         "topic_path must not be empty when provided",
                     ));
                 }
@@ -72,9 +72,9 @@ impl RpcDaemon {
         let topic_id = match Self::normalize_non_empty(parsed.topic_id.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "topic_id",
                     "topic_id must not be empty",
                 ))
             }
@@ -161,9 +161,9 @@ impl RpcDaemon {
         let topic_id = match Self::normalize_non_empty(parsed.topic_id.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "topic_id",
                     "topic_id must not be empty",
                 ))
             }
@@ -212,9 +212,9 @@ impl RpcDaemon {
         let topic_id = match Self::normalize_non_empty(parsed.topic_id.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "topic_id",
                     "topic_id must not be empty",
                 ))
             }
@@ -252,9 +252,9 @@ impl RpcDaemon {
         let topic_id = match Self::normalize_non_empty(parsed.topic_id.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "topic_id",
                     "topic_id must not be empty",
                 ))
             }
@@ -283,10 +283,7 @@ impl RpcDaemon {
             tags,
             extensions: parsed.extensions.clone(),
         };
-        self.sdk_telemetry_points
-            .lock()
-            .expect("sdk_telemetry_points mutex poisoned")
-            .push(telemetry);
+        self.push_sdk_telemetry_point(telemetry);
         self.persist_sdk_domain_snapshot()?;
 
         let event = RpcEvent {
@@ -302,6 +299,65 @@ impl RpcDaemon {
         Ok(RpcResponse { id: request.id, result: Some(json!({ "accepted": true })), error: None })
     }
 
+    /// Appends a telemetry point. Each peer's points form a ring buffer: once a
+    /// peer holds more than the configured bound, its earliest entries go.
+    fn push_sdk_telemetry_point(&self, point: SdkTelemetryPoint) {
+        let max_points = self.sdk_telemetry_max_points_per_peer();
+        let mut points =
+            self.sdk_telemetry_points.lock().expect("sdk_telemetry_points mutex poisoned");
+        let peer_id = point.tags.get("peer_id").cloned();
+        points.push(point);
+        let same_peer =
+            |candidate: &SdkTelemetryPoint| candidate.tags.get("peer_id") == peer_id.as_ref();
+        let held = points.iter().filter(|candidate| same_peer(candidate)).count();
+        let mut excess = held.saturating_sub(max_points);
+        points.retain(|candidate| {
+            if excess > 0 && same_peer(candidate) {
+                excess -= 1;
+                return false;
+            }
+            true
+        });
+    }
+
+    /// Stores the location sensor decoded from an inbound message's field `2`
+    /// as a telemetry point tagged with the sending peer. Points are kept in
+    /// their own table, one row per message, outside the domain snapshot.
+    fn record_inbound_telemetry(&self, record: &MessageRecord) -> Result<(), std::io::Error> {
+        let Some(location) = record
+            .fields
+            .as_ref()
+            .and_then(|fields| fields.get("2"))
+            .filter(|location| location.get("lat").is_some() && location.get("lon").is_some())
+        else {
+            return Ok(());
+        };
+        let reported_secs = location
+            .get("updated")
+            .and_then(JsonValue::as_u64)
+            .or_else(|| u64::try_from(record.timestamp).ok());
+        let mut tags = HashMap::new();
+        tags.insert("peer_id".to_string(), record.source.clone());
+        tags.insert("message_id".to_string(), record.id.clone());
+        let point = SdkTelemetryPoint {
+            ts_ms: reported_secs.map_or_else(now_millis_u64, |secs| secs.saturating_mul(1000)),
+            key: "location".to_string(),
+            value: location.clone(),
+            unit: None,
+            tags,
+            extensions: JsonMap::new(),
+        };
+        let point = serde_json::to_value(point).map_err(std::io::Error::other)?;
+        self.store
+            .insert_inbound_telemetry(
+                &record.id,
+                &record.source,
+                &point,
+                self.sdk_telemetry_max_points_per_peer(),
+            )
+            .map_err(std::io::Error::other)
+    }
+
     fn handle_sdk_telemetry_query_v2(
         &self,
         request: RpcRequest,
@@ -320,6 +376,9 @@ impl RpcDaemon {
         let _ = parsed.extensions.len();
         let mut points =
             self.sdk_telemetry_points.lock().expect("sdk_telemetry_points mutex poisoned").clone();
+        for point in self.store.list_inbound_telemetry().map_err(std::io::Error::other)? {
+            points.push(serde_json::from_value(point).map_err(std::io::Error::other)?);
+        }
 
         if let Some(from_ts_ms) = parsed.from_ts_ms {
             points.retain(|point| point.ts_ms >= from_ts_ms);
@@ -337,6 +396,7 @@ impl RpcDaemon {
                 point.tags.get("peer_id").is_some_and(|current| current == peer_id.as_str())
             });
         }
+        points.sort_by_key(|point| point.ts_ms);
         let limit = parsed.limit.unwrap_or(128).clamp(1, 2048);
         if points.len() > limit {
             points.truncate(limit);
@@ -19,9 +19,9 @@ impl RpcDaemon {
         let peer_id = match Self::normalize_non_empty(parsed.peer_id.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "peer_id",
                     "peer_id must not be empty",
                 ))
             }
@@ -66,9 +66,9 @@ impl RpcDaemon {
         let session_id = match Self::normalize_non_empty(parsed.session_id.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "session_id",
                     "session_id must not be empty",
                 ))
             }
@@ -139,9 +139,9 @@ impl RpcDaemon {
         let session_id = match Self::normalize_non_empty(parsed.session_id.as_str()) {
             Some(value) => value,
             None => {
-                return Ok(self.sdk_error_response(
+                return Ok(self.sdk_empty_field_response(
                     request.id,
-                    "SDK_VALIDATION_INVALID_ARGUMENT",
+                    "session_id",
                     "session_id must not be empty",
                 ))
             }
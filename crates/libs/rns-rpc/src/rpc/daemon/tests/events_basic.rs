@@ -144,6 +144,154 @@
         assert_eq!(response.result.expect("result")["message_id"], json!("sdk-send-1"));
     }
 
+    #[test]
+    fn sdk_send_v2_generates_sortable_id_when_omitted() {
+        let daemon = RpcDaemon::test_instance();
+        let send = |id: u64| {
+            daemon
+                .handle_rpc(rpc_request(
+                    id,
+                    "sdk_send_v2",
+                    json!({ "source": "src", "destination": "dst", "content": "hello" }),
+                ))
+                .expect("sdk_send_v2")
+                .result
+                .expect("result")["message_id"]
+                .as_str()
+                .expect("message_id")
+                .to_string()
+        };
+        let first = send(6);
+        let second = send(7);
+        assert_eq!(first.len(), 26);
+        assert!(first < second);
+        assert!(daemon.store.get_message(&second).expect("get message").is_some());
+    }
+
+    #[test]
+    fn sdk_send_v2_enforces_per_destination_rate_limit() {
+        let daemon = RpcDaemon::test_instance();
+        let send = |id: u64, destination: &str| {
+            daemon
+                .handle_rpc(rpc_request(
+                    id,
+                    "sdk_send_v2",
+                    json!({
+                        "id": format!("rate-{id}"),
+                        "source": "src",
+                        "destination": destination,
+                        "title": "",
+                        "content": "hello"
+                    }),
+                ))
+                .expect("sdk_send_v2")
+        };
+        let configure = |id: u64, expected_revision: u64, limit: JsonValue| {
+            daemon
+                .handle_rpc(rpc_request(
+                    id,
+                    "sdk_configure_v2",
+                    json!({
+                        "expected_revision": expected_revision,
+                        "patch": { "per_destination_rate_limit": limit }
+                    }),
+                ))
+                .expect("sdk_configure_v2")
+        };
+
+        let invalid = configure(10, 0, json!(-1));
+        assert_eq!(invalid.error.expect("error").code, "SDK_VALIDATION_INVALID_ARGUMENT");
+        assert!(configure(11, 0, json!(2)).error.is_none());
+
+        assert!(send(12, "dst-a").error.is_none());
+        assert!(send(13, "dst-a").error.is_none());
+        let limited = send(14, "dst-a").error.expect("rate limited");
+        assert_eq!(limited.code, "SDK_RUNTIME_RATE_LIMITED");
+        assert_eq!(limited.retryable, Some(true));
+        let details = limited.details.expect("details");
+        assert_eq!(details["limit"], json!(2));
+        let retry_after_ms = details["retry_after_ms"].as_u64().expect("retry_after_ms");
+        assert!(retry_after_ms > 0 && retry_after_ms <= 60_000);
+        assert!(daemon.store.get_message("rate-14").expect("get").is_none());
+
+        assert!(send(15, "dst-b").error.is_none(), "other destinations keep their own window");
+
+        assert!(configure(16, 1, json!(0)).error.is_none());
+        assert!(send(17, "dst-a").error.is_none(), "a limit of 0 disables rate limiting");
+
+        assert!(configure(18, 2, json!(1)).error.is_none());
+        assert!(send(19, "dst-a").error.is_none(), "a new limit starts a fresh window");
+        assert!(send(20, "dst-a").error.is_some());
+        assert!(configure(21, 3, JsonValue::Null).error.is_none(), "null clears the limit");
+        assert!(send(22, "dst-a").error.is_none());
+        assert!(send(23, "dst-a").error.is_none());
+    }
+
+    #[test]
+    fn sdk_send_batch_v2_reports_per_item_results_and_batch_id() {
+        let daemon = RpcDaemon::test_instance();
+        let response = daemon
+            .handle_rpc(rpc_request(
+                6,
+                "sdk_send_batch_v2",
+                json!({
+                    "messages": [
+                        {
+                            "id": "batch-1",
+                            "source": "src",
+                            "destination": "dst-a",
+                            "title": "",
+                            "content": "one"
+                        },
+                        { "id": "batch-bad", "source": "src" },
+                        {
+                            "id": "batch-2",
+                            "source": "src",
+                            "destination": "dst-b",
+                            "title": "",
+                            "content": "two",
+                            "fields": { "_sdk": { "correlation_id": "corr-2" } }
+                        }
+                    ]
+                }),
+            ))
+            .expect("sdk_send_batch_v2");
+        assert!(response.error.is_none());
+        let result = response.result.expect("result");
+        let batch_id = result["batch_id"].as_str().expect("batch id").to_string();
+        assert_eq!(result["results"][0]["message_id"], json!("batch-1"));
+        assert_eq!(result["results"][1]["error"]["code"], json!("SDK_VALIDATION_INVALID_ARGUMENT"));
+        assert_eq!(result["results"][2]["message_id"], json!("batch-2"));
+
+        let stored = daemon.store.get_message("batch-2").expect("load").expect("stored message");
+        let sdk_meta = &stored.fields.expect("fields")["_sdk"];
+        assert!(sdk_meta.get("batch_id").is_none(), "batch membership stays off the wire");
+        assert_eq!(sdk_meta["correlation_id"], json!("corr-2"));
+        let first = daemon.store.get_message("batch-1").expect("load").expect("stored message");
+        assert!(first.fields.is_none());
+        assert_eq!(daemon.store.message_batch("batch-2").expect("batch"), Some(batch_id.clone()));
+
+        let poll = daemon
+            .handle_rpc(rpc_request(7, "sdk_poll_events_v2", json!({ "cursor": null, "max": 16 })))
+            .expect("poll");
+        assert_eq!(poll.result.expect("result")["batch_ids"], json!([batch_id]));
+    }
+
+    #[test]
+    fn sdk_send_batch_v2_is_disabled_without_capability() {
+        let daemon = RpcDaemon::test_instance();
+        daemon
+            .sdk_effective_capabilities
+            .lock()
+            .expect("sdk_effective_capabilities mutex poisoned")
+            .retain(|capability| capability != "sdk.capability.send_batch");
+        let response = daemon
+            .handle_rpc(rpc_request(8, "sdk_send_batch_v2", json!({ "messages": [] })))
+            .expect("rpc response");
+        let error = response.error.expect("expected capability error");
+        assert_eq!(error.code, "SDK_CAPABILITY_DISABLED");
+    }
+
     #[test]
     fn sdk_poll_events_v2_rejects_oversized_event_payload() {
         let daemon = RpcDaemon::test_instance();
@@ -192,6 +340,129 @@
         assert_eq!(response.error.expect("error").code, "SDK_VALIDATION_EVENT_TOO_LARGE");
     }
 
+    fn poll_past_oversize_event(policy: &str) -> (JsonValue, JsonValue) {
+        let daemon = RpcDaemon::test_instance();
+        let configure = daemon
+            .handle_rpc(rpc_request(
+                1,
+                "sdk_configure_v2",
+                json!({
+                    "expected_revision": 0,
+                    "patch": {
+                        "event_stream": { "max_event_bytes": 16_384, "oversize_policy": policy }
+                    }
+                }),
+            ))
+            .expect("configure");
+        assert!(configure.error.is_none());
+        let first_poll = daemon
+            .handle_rpc(rpc_request(2, "sdk_poll_events_v2", json!({ "cursor": null, "max": 8 })))
+            .expect("poll");
+        let cursor = first_poll.result.expect("result")["next_cursor"].clone();
+
+        daemon.emit_event(RpcEvent {
+            event_type: "inbound".to_string(),
+            payload: json!({ "message_id": "too-large", "blob": "x".repeat(17_000) }),
+        });
+        daemon.emit_event(RpcEvent {
+            event_type: "inbound".to_string(),
+            payload: json!({ "message_id": "after" }),
+        });
+
+        let blocked = daemon
+            .handle_rpc(rpc_request(3, "sdk_poll_events_v2", json!({ "cursor": cursor, "max": 1 })))
+            .expect("poll")
+            .result
+            .expect("oversize event should not stall the poll");
+        let next = daemon
+            .handle_rpc(rpc_request(
+                4,
+                "sdk_poll_events_v2",
+                json!({ "cursor": blocked["next_cursor"], "max": 8 }),
+            ))
+            .expect("poll")
+            .result
+            .expect("result");
+        (blocked, next)
+    }
+
+    #[test]
+    fn sdk_poll_events_v2_skips_oversize_event_and_advances_cursor() {
+        let (skipped, next) = poll_past_oversize_event("skip");
+        assert!(skipped["events"].as_array().expect("events").is_empty());
+        assert_eq!(skipped["dropped_count"], 1);
+        assert_eq!(skipped["oversize_events"][0]["event_type"], "inbound");
+        assert!(skipped["oversize_events"][0]["payload_bytes"].as_u64().expect("bytes") > 16_384);
+        assert_eq!(next["events"][0]["payload"]["message_id"], "after");
+        assert_eq!(next["dropped_count"], 0);
+    }
+
+    #[test]
+    fn sdk_poll_events_v2_truncates_oversize_event_to_summary() {
+        let (truncated, next) = poll_past_oversize_event("truncate");
+        let event = &truncated["events"][0];
+        assert_eq!(event["event_type"], "inbound");
+        assert_eq!(event["payload"]["oversize_event"]["max_event_bytes"], 16_384);
+        assert!(event["payload"].get("blob").is_none());
+        assert_eq!(truncated["dropped_count"], 0);
+        assert_eq!(next["events"][0]["payload"]["message_id"], "after");
+    }
+
+    #[test]
+    fn sdk_poll_events_v2_filters_event_types_and_advances_cursor_past_others() {
+        let daemon = RpcDaemon::test_instance();
+        let baseline = daemon
+            .handle_rpc(rpc_request(1, "sdk_poll_events_v2", json!({ "cursor": null, "max": 8 })))
+            .expect("poll")
+            .result
+            .expect("result")["next_cursor"]
+            .clone();
+        for (event_type, id) in [
+            ("announce_received", "a-1"),
+            ("delivery_update", "m-1"),
+            ("peer_seen", "p-1"),
+            ("delivery_update", "m-2"),
+            ("announce_received", "a-2"),
+        ] {
+            daemon.emit_event(RpcEvent {
+                event_type: event_type.to_string(),
+                payload: json!({ "message_id": id }),
+            });
+        }
+        let poll = |id: u64, params: JsonValue| {
+            daemon
+                .handle_rpc(rpc_request(id, "sdk_poll_events_v2", params))
+                .expect("poll")
+                .result
+                .expect("result")
+        };
+        let ids = |batch: &JsonValue| {
+            batch["events"]
+                .as_array()
+                .expect("events")
+                .iter()
+                .map(|event| event["payload"]["message_id"].as_str().expect("id").to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let unfiltered = poll(2, json!({ "cursor": baseline, "max": 8, "event_types": [] }));
+        assert_eq!(ids(&unfiltered), vec!["a-1", "m-1", "p-1", "m-2", "a-2"]);
+
+        let first = poll(
+            3,
+            json!({ "cursor": baseline, "max": 1, "event_types": ["delivery_update"] }),
+        );
+        assert_eq!(ids(&first), vec!["m-1"]);
+        let second = poll(
+            4,
+            json!({ "cursor": first["next_cursor"], "max": 8, "event_types": ["delivery_update"] }),
+        );
+        assert_eq!(ids(&second), vec!["m-2"]);
+        assert_eq!(second["dropped_count"], 0);
+        let after = poll(5, json!({ "cursor": second["next_cursor"], "max": 8 }));
+        assert!(ids(&after).is_empty());
+    }
+
     #[test]
     fn sdk_poll_events_v2_rejects_oversized_batch() {
         let daemon = RpcDaemon::test_instance();
@@ -442,6 +713,57 @@
         );
     }
 
+    #[test]
+    fn sdk_overflow_policy_drop_newest_keeps_head_entries() {
+        let daemon = RpcDaemon::test_instance();
+        let configure = daemon
+            .handle_rpc(rpc_request(
+                92,
+                "sdk_configure_v2",
+                json!({
+                    "expected_revision": 0,
+                    "patch": {
+                        "overflow_policy": "drop_newest",
+                        "event_stream": { "max_poll_events": 2048 }
+                    }
+                }),
+            ))
+            .expect("configure");
+        assert!(configure.error.is_none());
+
+        for idx in 0..(SDK_EVENT_LOG_CAPACITY * 2) {
+            daemon.emit_event(RpcEvent {
+                event_type: "telemetry".to_string(),
+                payload: json!({ "idx": idx }),
+            });
+        }
+
+        let response = daemon
+            .handle_rpc(rpc_request(
+                93,
+                "sdk_poll_events_v2",
+                json!({ "cursor": null, "max": 2048 }),
+            ))
+            .expect("poll");
+        let result = response.result.expect("result");
+        let payload_indices = result["events"]
+            .as_array()
+            .expect("events array")
+            .iter()
+            .filter_map(|row| row["payload"]["idx"].as_u64())
+            .collect::<Vec<_>>();
+
+        assert!(result["dropped_count"].as_u64().unwrap_or(0) >= SDK_EVENT_LOG_CAPACITY as u64);
+        assert!(
+            payload_indices.contains(&0),
+            "drop_newest policy should keep the oldest buffered event"
+        );
+        assert!(
+            !payload_indices.contains(&(SDK_EVENT_LOG_CAPACITY as u64)),
+            "drop_newest policy should discard arrivals once capacity is reached"
+        );
+    }
+
     #[test]
     fn sdk_event_queues_remain_bounded_under_sustained_load() {
         let daemon = RpcDaemon::test_instance();
@@ -629,3 +951,222 @@
             "cursored polls must not inject StreamGap events"
         );
     }
+
+    fn event_persist_paths(label: &str) -> (std::path::PathBuf, std::path::PathBuf) {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        let run_id = SystemTime::now().duration_since(UNIX_EPOCH).expect("unix epoch").as_nanos();
+        let prefix = format!("lxmf-rs-{label}-{run_id}-{}", std::process::id());
+        let temp_dir = std::env::temp_dir();
+        (temp_dir.join(format!("{prefix}.sqlite")), temp_dir.join(format!("{prefix}.events.jsonl")))
+    }
+
+    #[test]
+    fn sdk_persisted_events_replay_from_cursor_after_restart() {
+        let (db_path, events_path) = event_persist_paths("event-persist");
+        let cursor: String;
+
+        {
+            let store = MessagesStore::open(db_path.as_path()).expect("open sqlite store");
+            let daemon = RpcDaemon::with_store(store, "persist-node".to_string());
+            daemon.enable_event_persistence(&events_path).expect("enable event persistence");
+
+            let drained = daemon
+                .handle_rpc(rpc_request(
+                    2,
+                    "sdk_poll_events_v2",
+                    json!({ "cursor": null, "max": 16 }),
+                ))
+                .expect("poll");
+            cursor = drained.result.expect("result")["next_cursor"]
+                .as_str()
+                .expect("cursor")
+                .to_string();
+
+            daemon.emit_event(RpcEvent {
+                event_type: "inbound".to_string(),
+                payload: json!({ "message_id": "before-restart" }),
+            });
+        }
+
+        let store = MessagesStore::open(db_path.as_path()).expect("reopen sqlite store");
+        let daemon = RpcDaemon::with_store(store, "persist-node".to_string());
+        daemon.enable_event_persistence(&events_path).expect("enable event persistence");
+        let replayed = daemon
+            .handle_rpc(rpc_request(
+                3,
+                "sdk_poll_events_v2",
+                json!({ "cursor": cursor, "max": 16 }),
+            ))
+            .expect("poll after restart");
+        assert!(replayed.error.is_none(), "cursor should stay valid after restart");
+        let events =
+            replayed.result.expect("result")["events"].as_array().cloned().expect("events");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["event_type"], "inbound");
+        assert_eq!(events[0]["payload"]["message_id"], "before-restart");
+
+        daemon.emit_event(RpcEvent {
+            event_type: "inbound".to_string(),
+            payload: json!({ "message_id": "after-restart" }),
+        });
+        let seq_before = events[0]["seq_no"].as_u64().expect("seq");
+        let log = daemon.sdk_event_log.lock().expect("sdk_event_log mutex poisoned");
+        assert_eq!(log.back().expect("latest event").seq_no, seq_before + 1);
+        drop(log);
+
+        let _ = std::fs::remove_file(&db_path);
+        let _ = std::fs::remove_file(&events_path);
+    }
+
+    #[test]
+    fn sdk_cursor_outside_persisted_tail_expires_after_restart() {
+        let (db_path, events_path) = event_persist_paths("event-persist-tail");
+        let persist_config = json!({
+            "expected_revision": 0,
+            "patch": {
+                "event_stream": { "persist_max_bytes": 4_096 }
+            }
+        });
+        let poll = |daemon: &RpcDaemon, id: u64, cursor: JsonValue| {
+            daemon
+                .handle_rpc(rpc_request(id, "sdk_poll_events_v2", json!({ "cursor": cursor, "max": 4 })))
+                .expect("poll")
+        };
+        let stale_cursor: String;
+        let surviving_cursor: String;
+
+        {
+            let store = MessagesStore::open(db_path.as_path()).expect("open sqlite store");
+            let daemon = RpcDaemon::with_store(store, "tail-node".to_string());
+            let configured = daemon
+                .handle_rpc(rpc_request(1, "sdk_configure_v2", persist_config.clone()))
+                .expect("configure");
+            assert!(configured.error.is_none());
+            daemon.enable_event_persistence(&events_path).expect("enable event persistence");
+            let drained = poll(&daemon, 2, JsonValue::Null);
+            stale_cursor =
+                drained.result.expect("result")["next_cursor"].as_str().expect("cursor").into();
+
+            for index in 0..200 {
+                daemon.emit_event(RpcEvent {
+                    event_type: "inbound".to_string(),
+                    payload: json!({ "message_id": format!("tail-{index}") }),
+                });
+            }
+            let latest = daemon.sdk_event_log.lock().expect("log").back().expect("event").seq_no;
+            surviving_cursor = daemon.sdk_encode_cursor(latest - 1);
+        }
+
+        let store = MessagesStore::open(db_path.as_path()).expect("reopen sqlite store");
+        let daemon = RpcDaemon::with_store(store, "tail-node".to_string());
+        daemon.enable_event_persistence(&events_path).expect("enable event persistence");
+        let oldest = daemon.sdk_event_log.lock().expect("log").front().expect("event").seq_no;
+
+        let resumed = poll(&daemon, 3, json!(surviving_cursor));
+        assert!(resumed.error.is_none(), "cursor inside the persisted tail must survive");
+        let result = resumed.result.expect("result");
+        assert_eq!(result["events"][0]["payload"]["message_id"], "tail-199");
+        let high_watermark = result["snapshot_high_watermark_seq_no"].as_u64().expect("watermark");
+
+        let expired = poll(&daemon, 4, json!(stale_cursor));
+        let error = expired.error.expect("stale cursor must expire");
+        assert_eq!(error.code, "SDK_RUNTIME_CURSOR_EXPIRED");
+        let details = error.details.expect("details");
+        assert_eq!(details["snapshot_high_watermark_seq_no"], json!(high_watermark));
+        let stale_seq = details["cursor_seq_no"].as_u64().expect("cursor seq");
+        assert_eq!(details["dropped_count"], json!(oldest - stale_seq - 1));
+
+        let fresh = poll(&daemon, 5, JsonValue::Null);
+        assert!(fresh.error.is_none());
+        assert_eq!(fresh.result.expect("result")["dropped_count"], json!(oldest - 1));
+
+        let _ = std::fs::remove_file(&db_path);
+        let _ = std::fs::remove_file(&events_path);
+    }
+
+    #[test]
+    fn sdk_cursor_ahead_of_unpersisted_restart_expires() {
+        let cursor = {
+            let daemon = RpcDaemon::test_instance();
+            for index in 0..3 {
+                daemon.emit_event(RpcEvent {
+                    event_type: "inbound".to_string(),
+                    payload: json!({ "message_id": format!("gone-{index}") }),
+                });
+            }
+            let latest = daemon.sdk_event_log.lock().expect("log").back().expect("event").seq_no;
+            daemon.sdk_encode_cursor(latest)
+        };
+
+        let daemon = RpcDaemon::test_instance();
+        daemon.emit_event(RpcEvent {
+            event_type: "inbound".to_string(),
+            payload: json!({ "message_id": "fresh" }),
+        });
+        let response = daemon
+            .handle_rpc(rpc_request(1, "sdk_poll_events_v2", json!({ "cursor": cursor, "max": 4 })))
+            .expect("poll");
+        let error = response.error.expect("cursor from a previous run must expire");
+        assert_eq!(error.code, "SDK_RUNTIME_CURSOR_EXPIRED");
+        let details = error.details.expect("details");
+        assert_eq!(details["dropped_count"], JsonValue::Null);
+        assert!(details["snapshot_high_watermark_seq_no"].as_u64().is_some());
+    }
+
+    #[test]
+    fn sdk_persisted_event_log_stays_within_size_bound() {
+        let (_, events_path) = event_persist_paths("event-persist-bound");
+        let daemon = RpcDaemon::test_instance();
+        let configured = daemon
+            .handle_rpc(rpc_request(
+                1,
+                "sdk_configure_v2",
+                json!({
+                    "expected_revision": 0,
+                    "patch": { "event_stream": { "persist_max_bytes": 4_096 } }
+                }),
+            ))
+            .expect("configure");
+        assert!(configured.error.is_none());
+        daemon.enable_event_persistence(&events_path).expect("enable event persistence");
+
+        for index in 0..200 {
+            daemon.emit_event(RpcEvent {
+                event_type: "inbound".to_string(),
+                payload: json!({ "message_id": format!("bounded-{index}") }),
+            });
+        }
+
+        let persisted = std::fs::read_to_string(&events_path).expect("persisted log");
+        assert!(persisted.len() <= 4_096, "persisted log exceeded size bound");
+        assert!(persisted.contains("bounded-199"), "newest event should be retained");
+        assert!(!persisted.contains("\"bounded-0\""), "oldest event should be evicted");
+
+        let invalid = daemon
+            .handle_rpc(rpc_request(
+                2,
+                "sdk_configure_v2",
+                json!({
+                    "expected_revision": 1,
+                    "patch": { "event_stream": { "persist_max_age_secs": 0 } }
+                }),
+            ))
+            .expect("configure");
+        assert_eq!(invalid.error.expect("error").code, "SDK_VALIDATION_INVALID_ARGUMENT");
+
+        let redirected = daemon
+            .handle_rpc(rpc_request(
+                3,
+                "sdk_configure_v2",
+                json!({
+                    "expected_revision": 1,
+                    "patch": { "event_stream": { "persist_path": "/etc/passwd" } }
+                }),
+            ))
+            .expect("configure");
+        let error = redirected.error.expect("persist_path must not be remotely configurable");
+        assert_eq!(error.code, "SDK_VALIDATION_INVALID_ARGUMENT");
+
+        let _ = std::fs::remove_file(&events_path);
+    }
@@ -375,6 +375,226 @@
         assert_eq!(tampered.code, "SDK_SECURITY_TOKEN_INVALID");
     }
 
+    #[test]
+    fn sdk_security_authorize_http_request_checks_api_key_header() {
+        let daemon = RpcDaemon::test_instance();
+        let response = daemon
+            .handle_rpc(rpc_request(
+                25,
+                "sdk_negotiate_v2",
+                json!({
+                    "supported_contract_versions": [2],
+                    "requested_capabilities": [],
+                    "config": {
+                        "profile": "desktop-full",
+                        "bind_mode": "remote",
+                        "auth_mode": "api_key",
+                        "rpc_backend": {
+                            "api_key_auth": { "api_key": "test-api-key" }
+                        }
+                    }
+                }),
+            ))
+            .expect("negotiate");
+        assert!(response.error.is_none(), "api_key negotiate should succeed: {response:?}");
+
+        let missing = daemon
+            .authorize_http_request(&[], Some("10.5.6.7"))
+            .expect_err("missing api key should be rejected");
+        assert_eq!(missing.code, "SDK_SECURITY_AUTH_REQUIRED");
+
+        let wrong = vec![("X-Api-Key".to_string(), "test-api-kex".to_string())];
+        let mismatched = daemon
+            .authorize_http_request(&wrong, Some("10.5.6.7"))
+            .expect_err("mismatched api key should be rejected");
+        assert_eq!(mismatched.code, "SDK_SECURITY_AUTHZ_DENIED");
+
+        let valid = vec![("X-Api-Key".to_string(), "test-api-key".to_string())];
+        daemon.authorize_http_request(&valid, Some("10.5.6.7")).expect("matching api key passes");
+    }
+
+    #[test]
+    fn sdk_security_api_key_mode_keeps_local_only_bind_restriction() {
+        let daemon = RpcDaemon::test_instance();
+        let response = daemon
+            .handle_rpc(rpc_request(
+                26,
+                "sdk_negotiate_v2",
+                json!({
+                    "supported_contract_versions": [2],
+                    "requested_capabilities": [],
+                    "config": {
+                        "profile": "desktop-full",
+                        "bind_mode": "local_only",
+                        "auth_mode": "api_key",
+                        "rpc_backend": {
+                            "api_key_auth": { "api_key": "test-api-key" }
+                        }
+                    }
+                }),
+            ))
+            .expect("negotiate");
+        assert!(response.error.is_none(), "api_key negotiate should succeed: {response:?}");
+
+        let headers = vec![("x-api-key".to_string(), "test-api-key".to_string())];
+        let remote = daemon
+            .authorize_http_request(&headers, Some("10.1.2.3"))
+            .expect_err("remote source should be rejected even with a valid key");
+        assert_eq!(remote.code, "SDK_SECURITY_REMOTE_BIND_DISALLOWED");
+        let keyless = daemon
+            .authorize_http_request(&[], Some("127.0.0.1"))
+            .expect_err("loopback source still needs the key");
+        assert_eq!(keyless.code, "SDK_SECURITY_AUTH_REQUIRED");
+        daemon.authorize_http_request(&headers, Some("127.0.0.1")).expect("loopback with key");
+    }
+
+    #[test]
+    fn sdk_negotiate_v2_rejects_api_key_mode_without_key() {
+        let daemon = RpcDaemon::test_instance();
+        let response = daemon
+            .handle_rpc(rpc_request(
+                27,
+                "sdk_negotiate_v2",
+                json!({
+                    "supported_contract_versions": [2],
+                    "requested_capabilities": [],
+                    "config": {
+                        "profile": "desktop-full",
+                        "bind_mode": "remote",
+                        "auth_mode": "api_key",
+                        "rpc_backend": { "api_key_auth": { "api_key": "  " } }
+                    }
+                }),
+            ))
+            .expect("negotiate");
+        let error = response.error.expect("blank api key should be rejected");
+        assert_eq!(error.code, "SDK_SECURITY_AUTH_REQUIRED");
+    }
+
+    fn audit_log_path(label: &str) -> std::path::PathBuf {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        let run_id = SystemTime::now().duration_since(UNIX_EPOCH).expect("unix epoch").as_nanos();
+        std::env::temp_dir()
+            .join(format!("lxmf-rs-{label}-{run_id}-{}.audit.jsonl", std::process::id()))
+    }
+
+    fn enable_audit_log(daemon: &RpcDaemon, path: &std::path::Path) {
+        daemon.enable_security_audit_log(path, false).expect("enable audit log");
+        let response = daemon
+            .handle_rpc(rpc_request(
+                30,
+                "sdk_configure_v2",
+                json!({
+                    "expected_revision": 0,
+                    "patch": {
+                        "audit_log": { "enabled": false, "path": "/tmp/elsewhere.jsonl" }
+                    }
+                }),
+            ))
+            .expect("configure audit log");
+        let error = response.error.expect("audit_log must not be remotely configurable");
+        assert_eq!(error.code, "SDK_VALIDATION_INVALID_ARGUMENT");
+    }
+
+    fn read_audit_records(path: &std::path::Path) -> Vec<SecurityAuditRecord> {
+        std::fs::read_to_string(path)
+            .expect("audit log")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("audit record"))
+            .collect()
+    }
+
+    #[test]
+    fn sdk_security_audit_log_records_rejected_token() {
+        let path = audit_log_path("audit-token");
+        let daemon = RpcDaemon::test_instance();
+        let response = daemon
+            .handle_rpc(rpc_request(
+                24,
+                "sdk_negotiate_v2",
+                json!({
+                    "supported_contract_versions": [2],
+                    "requested_capabilities": [],
+                    "config": {
+                        "profile": "desktop-full",
+                        "bind_mode": "remote",
+                        "auth_mode": "token",
+                        "rpc_backend": {
+                            "token_auth": {
+                                "issuer": "test-issuer",
+                                "audience": "test-audience",
+                                "jti_cache_ttl_ms": 30_000,
+                                "clock_skew_ms": 0,
+                                "shared_secret": "test-secret"
+                            }
+                        }
+                    }
+                }),
+            ))
+            .expect("negotiate");
+        assert!(response.error.is_none());
+        enable_audit_log(&daemon, &path);
+
+        let now = now_seconds_u64();
+        let payload = format!(
+            "iss=test-issuer;aud=test-audience;jti=audit-1;sub=cli;iat={now};exp={}",
+            now.saturating_add(60)
+        );
+        let headers = vec![("authorization".to_string(), format!("Bearer {payload};sig=deadbeef"))];
+        let error = daemon
+            .authorize_http_request(&headers, Some("10.5.6.7"))
+            .expect_err("tampered token should be rejected");
+        assert_eq!(error.code, "SDK_SECURITY_TOKEN_INVALID");
+
+        let records = read_audit_records(&path);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record.decision, "http_auth");
+        assert_eq!(record.outcome, "deny");
+        assert_eq!(record.reason_code, "SDK_SECURITY_TOKEN_INVALID");
+        assert_eq!(record.source, "10.5.6.7");
+        assert_eq!(record.auth_mode, "token");
+        assert!(record.principal.is_none());
+        assert!(record.ts_ms > 0);
+    }
+
+    #[test]
+    fn sdk_security_audit_log_records_remote_bind_disallowed() {
+        let path = audit_log_path("audit-remote-bind");
+        let daemon = RpcDaemon::test_instance();
+        let _ = daemon.handle_rpc(rpc_request(
+            25,
+            "sdk_negotiate_v2",
+            json!({
+                "supported_contract_versions": [2],
+                "requested_capabilities": [],
+                "config": {
+                    "profile": "desktop-full",
+                    "bind_mode": "local_only",
+                    "auth_mode": "local_trusted"
+                }
+            }),
+        ));
+        enable_audit_log(&daemon, &path);
+
+        daemon.authorize_http_request(&[], Some("127.0.0.1")).expect("loopback is allowed");
+        let error = daemon
+            .authorize_http_request(&[], Some("10.1.2.3"))
+            .expect_err("remote source should be rejected in local_only mode");
+        assert_eq!(error.code, "SDK_SECURITY_REMOTE_BIND_DISALLOWED");
+
+        let records = read_audit_records(&path);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(records.len(), 1, "allowed decisions are not recorded by default");
+        let record = &records[0];
+        assert_eq!(record.outcome, "deny");
+        assert_eq!(record.reason_code, "SDK_SECURITY_REMOTE_BIND_DISALLOWED");
+        assert_eq!(record.source, "10.1.2.3");
+        assert_eq!(record.auth_mode, "local_trusted");
+    }
+
     #[test]
     fn sdk_negotiate_v2_accepts_mtls_auth_mode_with_backend_config() {
         let daemon = RpcDaemon::test_instance();
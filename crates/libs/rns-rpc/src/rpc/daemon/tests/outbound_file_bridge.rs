@@ -0,0 +1,42 @@
+#[test]
+fn file_outbound_bridge_appends_delivered_records_as_json_lines() {
+    use crate::rpc::outbound_file::FileOutboundBridge;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let run_id = SystemTime::now().duration_since(UNIX_EPOCH).expect("unix epoch").as_nanos();
+    let path = std::env::temp_dir()
+        .join(format!("lxmf-rs-outbound-{run_id}-{}.jsonl", std::process::id()));
+    let bridge = Arc::new(FileOutboundBridge::new(&path).expect("open outbound file"));
+    assert_eq!(bridge.path(), path.as_path());
+    let daemon = RpcDaemon::with_store_and_bridge(
+        MessagesStore::in_memory().expect("in-memory store"),
+        "file-node".to_string(),
+        bridge,
+    );
+
+    let send = daemon
+        .handle_rpc(rpc_request(
+            1,
+            "send_message",
+            json!({
+                "id": "file-sink-1",
+                "source": "source.file",
+                "destination": "destination.file",
+                "title": "hello",
+                "content": "written to disk"
+            }),
+        ))
+        .expect("send");
+    assert!(send.error.is_none(), "send failed: {:?}", send.error);
+
+    let contents = std::fs::read_to_string(&path).expect("read outbound file");
+    let _ = std::fs::remove_file(&path);
+    let lines = contents.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 1);
+    let record: JsonValue = serde_json::from_str(lines[0]).expect("json line");
+    assert_eq!(record["id"], json!("file-sink-1"));
+    assert_eq!(record["source"], json!("source.file"));
+    assert_eq!(record["destination"], json!("destination.file"));
+    assert_eq!(record["content"], json!("written to disk"));
+    assert_eq!(record["direction"], json!("out"));
+}
@@ -225,6 +225,286 @@
         assert_eq!(marker_result["next_cursor"], JsonValue::Null);
     }
 
+    #[test]
+    fn sdk_release_b_marker_changes_publish_marker_updated_events() {
+        let daemon = RpcDaemon::test_instance();
+        let rejected = daemon
+            .handle_rpc(rpc_request(
+                120,
+                "sdk_marker_create_v2",
+                json!({ "label": "Off", "position": { "lat": 10.0, "lon": 180.5 } }),
+            ))
+            .expect("marker create");
+        let error = rejected.error.expect("out of range longitude");
+        assert_eq!(error.code, "SDK_VALIDATION_INVALID_ARGUMENT");
+        let details = error.details.expect("details");
+        assert_eq!(details["field"], json!("position.lon"));
+        assert_eq!(details["max"], json!(180.0));
+
+        while daemon.take_event().is_some() {}
+        let created = daemon
+            .handle_rpc(rpc_request(
+                121,
+                "sdk_marker_create_v2",
+                json!({ "label": "Bravo", "position": { "lat": 10.0, "lon": 20.0 } }),
+            ))
+            .expect("marker create")
+            .result
+            .expect("result");
+        let marker_id = created["marker"]["marker_id"].as_str().expect("marker id").to_string();
+        let updated = daemon
+            .handle_rpc(rpc_request(
+                122,
+                "sdk_marker_update_position_v2",
+                json!({
+                    "marker_id": marker_id.clone(),
+                    "expected_revision": 1,
+                    "position": { "lat": -90.0, "lon": -180.0 }
+                }),
+            ))
+            .expect("marker update");
+        assert!(updated.error.is_none());
+        let deleted = daemon
+            .handle_rpc(rpc_request(
+                123,
+                "sdk_marker_delete_v2",
+                json!({ "marker_id": marker_id.clone(), "expected_revision": 2 }),
+            ))
+            .expect("marker delete");
+        assert_eq!(deleted.result.expect("result")["accepted"], json!(true));
+
+        let mut changes = Vec::new();
+        while let Some(event) = daemon.take_event() {
+            if event.event_type == "marker_updated" {
+                assert_eq!(event.payload["marker_id"], json!(marker_id));
+                changes.push((
+                    event.payload["change"].as_str().expect("change").to_string(),
+                    event.payload["revision"].as_u64().expect("revision"),
+                ));
+            }
+        }
+        assert_eq!(
+            changes,
+            vec![
+                ("created".to_string(), 1),
+                ("position_updated".to_string(), 2),
+                ("deleted".to_string(), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn sdk_telemetry_query_returns_inbound_locations_bounded_per_peer() {
+        let daemon = RpcDaemon::test_instance();
+        let configured = daemon
+            .handle_rpc(rpc_request(
+                130,
+                "sdk_configure_v2",
+                json!({ "expected_revision": 0, "patch": { "telemetry_max_points_per_peer": 2 } }),
+            ))
+            .expect("sdk_configure_v2");
+        assert!(configured.error.is_none());
+
+        for (index, (source, updated)) in
+            [("peer-a", 300), ("peer-a", 100), ("peer-b", 150), ("peer-a", 200)]
+                .into_iter()
+                .enumerate()
+        {
+            daemon
+                .accept_inbound(MessageRecord {
+                    id: format!("telemetry-{index}"),
+                    source: source.to_string(),
+                    destination: "local".to_string(),
+                    title: String::new(),
+                    content: String::new(),
+                    timestamp: 1_000,
+                    direction: "in".to_string(),
+                    fields: Some(json!({
+                        "2": { "lat": 35.0, "lon": -115.0, "alt": 10.0, "updated": updated }
+                    })),
+                    receipt_status: None,
+                    reply_to: None,
+                })
+                .expect("accept inbound");
+        }
+
+        let query = |id: u64, params: JsonValue| {
+            daemon
+                .handle_rpc(rpc_request(id, "sdk_telemetry_query_v2", params))
+                .expect("telemetry query")
+                .result
+                .expect("result")["points"]
+                .as_array()
+                .expect("points")
+                .iter()
+                .map(|point| point["ts_ms"].as_u64().expect("ts_ms"))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(query(131, json!({ "peer_id": "peer-a" })), vec![100_000, 200_000]);
+        assert_eq!(query(132, json!({})), vec![100_000, 150_000, 200_000]);
+        assert_eq!(query(133, json!({ "from_ts_ms": 150_000, "limit": 1 })), vec![150_000]);
+    }
+
+    #[test]
+    fn inbound_duplicates_are_suppressed_from_window_and_store() {
+        let daemon = RpcDaemon::test_instance();
+        let message = |id: &str, source: &str, timestamp: i64| MessageRecord {
+            id: id.to_string(),
+            source: source.to_string(),
+            destination: "local".to_string(),
+            title: "status".to_string(),
+            content: "all good".to_string(),
+            timestamp,
+            direction: "in".to_string(),
+            fields: None,
+            receipt_status: None,
+            reply_to: None,
+        };
+        daemon.accept_inbound(message("m-1", "peer-a", 1_000)).expect("accept");
+        daemon.accept_inbound(message("m-1", "peer-a", 1_000)).expect("accept duplicate");
+        daemon.accept_inbound(message("m-1-copy", "peer-a", 1_000)).expect("accept same content");
+        daemon.accept_inbound(message("m-2", "peer-a", 2_000)).expect("accept later");
+        daemon.accept_inbound(message("m-3", "peer-b", 1_000)).expect("accept other source");
+
+        // Past the in-memory window the stored history still catches repeats.
+        *daemon.inbound_dedup.lock().expect("inbound_dedup") = InboundDedupWindow::default();
+        daemon.accept_inbound(message("m-1", "peer-a", 1_000)).expect("accept after eviction");
+
+        let stored = daemon.store.list_messages(16, None).expect("list messages");
+        let mut ids = stored.iter().map(|record| record.id.as_str()).collect::<Vec<_>>();
+        ids.sort_unstable();
+        assert_eq!(ids, vec!["m-1", "m-1-copy", "m-2", "m-3"]);
+        assert_eq!(daemon.metrics_snapshot()["counters"]["duplicate_suppressed_total"], json!(2));
+        let inbound_events = std::iter::from_fn(|| daemon.take_event())
+            .filter(|event| event.event_type == "inbound")
+            .count();
+        assert_eq!(inbound_events, 4);
+    }
+
+    #[test]
+    fn inbound_message_that_fails_to_store_is_accepted_on_redelivery() {
+        let (db_path, _) = event_persist_paths("inbound-store-failure");
+        let daemon = RpcDaemon::with_store(
+            MessagesStore::open(db_path.as_path()).expect("open sqlite store"),
+            "local".into(),
+        );
+        let record = MessageRecord {
+            id: "m-retry".to_string(),
+            source: "peer-a".to_string(),
+            destination: "local".to_string(),
+            title: String::new(),
+            content: "hello".to_string(),
+            timestamp: 1_000,
+            direction: "in".to_string(),
+            fields: None,
+            receipt_status: None,
+            reply_to: None,
+        };
+        let sqlite = rusqlite::Connection::open(db_path.as_path()).expect("open sqlite");
+        sqlite
+            .execute_batch(
+                "CREATE TRIGGER reject_messages BEFORE INSERT ON messages
+                 BEGIN SELECT RAISE(ABORT, 'disk full'); END;",
+            )
+            .expect("install failing trigger");
+        assert!(daemon.accept_inbound(record.clone()).is_err());
+
+        sqlite.execute_batch("DROP TRIGGER reject_messages").expect("drop trigger");
+        daemon.accept_inbound(record).expect("accept redelivery");
+        let stored = daemon.store.list_messages(16, None).expect("list messages");
+        assert_eq!(stored.len(), 1);
+        assert_eq!(daemon.metrics_snapshot()["counters"]["duplicate_suppressed_total"], json!(0));
+        let _ = std::fs::remove_file(db_path);
+    }
+
+    #[test]
+    fn inbound_filter_drops_listed_sources_and_lets_contacts_bypass() {
+        let daemon = RpcDaemon::test_instance();
+        let allowed = "aa".repeat(16);
+        let blocked = "bb".repeat(16);
+        let contact = "cc".repeat(16);
+        let configured = daemon
+            .handle_rpc(rpc_request(
+                140,
+                "sdk_configure_v2",
+                json!({
+                    "expected_revision": 0,
+                    "patch": {
+                        "inbound_allowlist": [allowed.to_uppercase()],
+                        "inbound_blocklist": [blocked]
+                    }
+                }),
+            ))
+            .expect("sdk_configure_v2");
+        assert!(configured.error.is_none());
+        let invalid = daemon
+            .handle_rpc(rpc_request(
+                141,
+                "sdk_configure_v2",
+                json!({ "expected_revision": 1, "patch": { "inbound_blocklist": ["peer"] } }),
+            ))
+            .expect("sdk_configure_v2");
+        assert_eq!(invalid.error.expect("error").code, "SDK_VALIDATION_INVALID_ARGUMENT");
+
+        let inbound = |id: &str, source: &str| {
+            daemon
+                .accept_inbound(MessageRecord {
+                    id: id.to_string(),
+                    source: source.to_string(),
+                    destination: "local".to_string(),
+                    title: String::new(),
+                    content: "hello".to_string(),
+                    timestamp: 1_000,
+                    direction: "in".to_string(),
+                    fields: None,
+                    receipt_status: None,
+                    reply_to: None,
+                })
+                .expect("accept inbound");
+        };
+        inbound("from-allowed", &allowed);
+        inbound("from-blocked", &blocked);
+        inbound("from-contact", &contact);
+        let stored = |id: &str| daemon.store.get_message(id).expect("get message").is_some();
+        assert!(stored("from-allowed"));
+        assert!(!stored("from-blocked"));
+        assert!(!stored("from-contact"));
+        assert_eq!(daemon.metrics_snapshot()["counters"]["inbound_blocked_total"], json!(2));
+
+        let contact_update = daemon
+            .handle_rpc(rpc_request(
+                142,
+                "sdk_identity_contact_update_v2",
+                json!({ "identity": contact, "trust_level": "trusted" }),
+            ))
+            .expect("contact update");
+        assert!(contact_update.error.is_none());
+        let bypass = daemon
+            .handle_rpc(rpc_request(
+                143,
+                "sdk_configure_v2",
+                json!({ "expected_revision": 1, "patch": { "inbound_contacts_bypass": true } }),
+            ))
+            .expect("sdk_configure_v2");
+        assert!(bypass.error.is_none());
+        inbound("from-contact-again", &contact);
+        assert!(stored("from-contact-again"));
+        assert_eq!(daemon.metrics_snapshot()["counters"]["inbound_blocked_total"], json!(2));
+
+        // The bypass never overrides the blocklist.
+        let blocked_contact = daemon
+            .handle_rpc(rpc_request(
+                144,
+                "sdk_identity_contact_update_v2",
+                json!({ "identity": blocked, "trust_level": "trusted" }),
+            ))
+            .expect("contact update");
+        assert!(blocked_contact.error.is_none());
+        inbound("from-blocked-contact", &blocked);
+        assert!(!stored("from-blocked-contact"));
+        assert_eq!(daemon.metrics_snapshot()["counters"]["inbound_blocked_total"], json!(3));
+    }
+
     #[test]
     fn sdk_release_b_attachment_streaming_upload_resume_and_integrity() {
         let daemon = RpcDaemon::test_instance();
@@ -32,6 +32,56 @@
         assert_eq!(too_late.result.expect("result")["result"], json!("TooLateToCancel"));
     }
 
+    #[test]
+    fn sdk_cancel_message_v2_cancels_pending_stamp_generation() {
+        let daemon = RpcDaemon::test_instance();
+        let policy = daemon
+            .handle_rpc(rpc_request(9, "stamp_policy_set", json!({ "generation_workers": 3 })))
+            .expect("stamp policy");
+        assert_eq!(policy.result.expect("result")["stamp_policy"]["generation_workers"], json!(3));
+
+        let send = daemon
+            .handle_rpc(rpc_request(
+                10,
+                "send_message_v2",
+                json!({
+                    "id": "stamped-1",
+                    "source": "src",
+                    "destination": "dst",
+                    "title": "",
+                    "content": "hello",
+                    "stamp_cost": 8
+                }),
+            ))
+            .expect("send");
+        assert!(send.error.is_none());
+
+        let task = daemon.begin_stamp_task("stamped-1", 8);
+        assert_eq!(task.workers(), 3);
+        while daemon.take_event().is_some() {}
+        daemon.record_stamp_progress("stamped-1", 4096);
+        let event = daemon.take_event().expect("stamp progress event");
+        assert_eq!(event.event_type, "stamp_progress");
+        assert_eq!(event.payload["message_id"], json!("stamped-1"));
+        assert_eq!(event.payload["attempts"], json!(4096));
+        assert_eq!(event.payload["target_cost"], json!(8));
+
+        let cancel = daemon
+            .handle_rpc(rpc_request(
+                11,
+                "sdk_cancel_message_v2",
+                json!({ "message_id": "stamped-1" }),
+            ))
+            .expect("cancel");
+        assert_eq!(cancel.result.expect("result")["result"], json!("Accepted"));
+        assert!(task.is_cancelled());
+
+        daemon.record_stamp_progress("stamped-1", 8192);
+        if let Some(event) = daemon.take_event() {
+            assert_ne!(event.event_type, "stamp_progress");
+        }
+    }
+
     #[test]
     fn sdk_status_v2_returns_message_record() {
         let daemon = RpcDaemon::test_instance();
@@ -145,6 +195,57 @@
         assert_eq!(status.result.expect("result")["message"]["receipt_status"], json!("delivered"));
     }
 
+    #[test]
+    fn delivery_trace_returns_ordered_entries_and_rejects_unknown_ids() {
+        let daemon = RpcDaemon::test_instance();
+        let unknown = daemon
+            .handle_rpc(rpc_request(49, "delivery_trace", json!({ "message_id": "missing" })))
+            .expect("trace unknown");
+        assert_eq!(unknown.error.expect("error").code, "SDK_VALIDATION_INVALID_ARGUMENT");
+
+        daemon
+            .handle_rpc(rpc_request(
+                50,
+                "send_message_v2",
+                json!({
+                    "id": "trace-1",
+                    "source": "src",
+                    "destination": "dst",
+                    "title": "",
+                    "content": "hello"
+                }),
+            ))
+            .expect("send");
+        let pending = daemon
+            .handle_rpc(rpc_request(51, "delivery_trace", json!({ "message_id": "trace-1" })))
+            .expect("trace pending")
+            .result
+            .expect("result");
+        assert_eq!(pending["terminal"], json!(false));
+
+        daemon
+            .handle_rpc(rpc_request(
+                52,
+                "record_receipt",
+                json!({ "message_id": "trace-1", "status": "delivered" }),
+            ))
+            .expect("receipt");
+        let done = daemon
+            .handle_rpc(rpc_request(53, "delivery_trace", json!({ "message_id": "trace-1" })))
+            .expect("trace done")
+            .result
+            .expect("result");
+        assert_eq!(done["terminal"], json!(true));
+        let statuses = done["trace"]
+            .as_array()
+            .expect("trace entries")
+            .iter()
+            .map(|entry| entry["status"].as_str().expect("status").to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(statuses.first().map(String::as_str), Some("queued"));
+        assert_eq!(statuses.last().map(String::as_str), Some("delivered"));
+    }
+
     #[test]
     fn sdk_property_event_sequence_is_monotonic() {
         let daemon = RpcDaemon::test_instance();
@@ -513,6 +614,114 @@
         assert_eq!(response.result.expect("result")["accepted"], json!(true));
     }
 
+    fn insert_pending_outbound(daemon: &RpcDaemon, id: &str) {
+        daemon
+            .store
+            .insert_message(&MessageRecord {
+                id: id.to_string(),
+                source: "source.d".to_string(),
+                destination: "destination.d".to_string(),
+                title: String::new(),
+                content: "pending".to_string(),
+                timestamp: now_i64(),
+                direction: "out".to_string(),
+                fields: None,
+                receipt_status: None,
+                reply_to: None,
+            })
+            .expect("insert pending outbound");
+    }
+
+    #[test]
+    fn sdk_shutdown_v2_graceful_reports_pending_without_blocking_and_refuses_sends() {
+        let daemon = RpcDaemon::test_instance();
+        insert_pending_outbound(&daemon, "drain-pending");
+        daemon.store.update_receipt_status("drain-pending", "sending").expect("mark sending");
+        insert_pending_outbound(&daemon, "drain-delivered");
+        daemon.store.update_receipt_status("drain-delivered", "delivered").expect("mark delivered");
+        let mut events = daemon.subscribe_events();
+
+        let shutdown = |id| {
+            daemon
+                .handle_rpc(rpc_request(
+                    id,
+                    "sdk_shutdown_v2",
+                    json!({ "mode": "graceful", "drain_timeout_ms": u64::MAX }),
+                ))
+                .expect("shutdown")
+                .result
+                .expect("result")
+        };
+        let started = std::time::Instant::now();
+        let result = shutdown(45);
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+        assert_eq!(result["accepted"], json!(true));
+        assert_eq!(result["drain_timeout_ms"], json!(MAX_SHUTDOWN_DRAIN_TIMEOUT_MS));
+        assert_eq!(result["pending_messages"], json!(1));
+
+        daemon.store.update_receipt_status("drain-pending", "sent").expect("mark sent");
+        assert_eq!(shutdown(48)["pending_messages"], json!(0));
+        let requested = std::iter::from_fn(|| events.try_recv().ok())
+            .filter(|event| event.event_type == "runtime_shutdown_requested")
+            .count();
+        assert_eq!(requested, 1, "polling the drain must not repeat the shutdown event");
+
+        let send = daemon
+            .handle_rpc(rpc_request(
+                46,
+                "send_message",
+                json!({
+                    "id": "after-shutdown",
+                    "source": "source.d",
+                    "destination": "destination.d",
+                    "content": "late"
+                }),
+            ))
+            .expect("send response");
+        assert_eq!(send.error.expect("send refused").code, "SDK_RUNTIME_INVALID_STATE");
+        assert!(daemon.store.get_message("after-shutdown").expect("lookup").is_none());
+    }
+
+    #[test]
+    fn sdk_shutdown_v2_immediate_skips_draining() {
+        let daemon = RpcDaemon::test_instance();
+        insert_pending_outbound(&daemon, "immediate-pending");
+
+        let started = std::time::Instant::now();
+        let response = daemon
+            .handle_rpc(rpc_request(
+                47,
+                "sdk_shutdown_v2",
+                json!({ "mode": "immediate", "drain_timeout_ms": 60_000 }),
+            ))
+            .expect("shutdown");
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+        let result = response.result.expect("result");
+        assert_eq!(result["drain_timeout_ms"], JsonValue::Null);
+        assert_eq!(result["pending_messages"], json!(1));
+        assert_eq!(result["abandoned_messages"], json!(1));
+
+        let send = daemon
+            .handle_rpc(rpc_request(
+                48,
+                "send_message",
+                json!({
+                    "id": "after-immediate",
+                    "source": "source.d",
+                    "destination": "destination.d",
+                    "content": "not draining"
+                }),
+            ))
+            .expect("send response");
+        assert!(send.error.is_none(), "immediate shutdown must not enter the draining state");
+        let snapshot = daemon
+            .handle_rpc(rpc_request(49, "sdk_snapshot_v2", json!({})))
+            .expect("snapshot")
+            .result
+            .expect("result");
+        assert_ne!(snapshot["state"], json!("draining"));
+    }
+
     #[test]
     fn sdk_snapshot_v2_returns_runtime_summary() {
         let daemon = RpcDaemon::test_instance();
@@ -536,6 +745,142 @@
         assert!(result.get("event_stream_position").is_some());
     }
 
+    #[test]
+    fn sdk_snapshot_v2_reports_queue_and_buffer_gauges() {
+        let daemon = RpcDaemon::test_instance();
+        daemon.set_inbound_pending_probe(Arc::new(|| 3));
+        for (id, receipt_status) in [("gauge-queued", None), ("gauge-sent", Some("sent: direct"))] {
+            daemon
+                .store
+                .insert_message(&MessageRecord {
+                    id: id.into(),
+                    source: "src".into(),
+                    destination: "dst".into(),
+                    title: String::new(),
+                    content: "hello".into(),
+                    timestamp: 1,
+                    direction: "out".into(),
+                    fields: None,
+                    receipt_status: receipt_status.map(str::to_string),
+                    reply_to: None,
+                })
+                .expect("insert");
+        }
+        daemon.publish_event(RpcEvent { event_type: "test".into(), payload: json!({}) });
+
+        let result = daemon
+            .handle_rpc(rpc_request(2, "sdk_snapshot_v2", json!({ "include_counts": true })))
+            .expect("snapshot")
+            .result
+            .expect("result");
+        assert_eq!(result["messages_stored"], json!(2));
+        assert_eq!(result["outbound_queue_depth"], json!(1));
+        assert_eq!(result["inbound_pending"], json!(3));
+        assert_eq!(result["event_buffer_capacity"], json!(SDK_EVENT_LOG_CAPACITY));
+        assert!(result["event_buffer_used"].as_u64().expect("event_buffer_used") >= 1);
+    }
+
+    #[test]
+    fn sdk_snapshot_v2_since_revision_returns_only_changed_fields() {
+        let daemon = RpcDaemon::test_instance();
+        let snapshot_since = |id: u64, since_revision: u64| {
+            daemon
+                .handle_rpc(rpc_request(
+                    id,
+                    "sdk_snapshot_v2",
+                    json!({ "include_counts": true, "since_revision": since_revision }),
+                ))
+                .expect("snapshot")
+                .result
+                .expect("result")
+        };
+
+        let full = snapshot_since(11, 0);
+        assert_eq!(full["unchanged"], json!(false));
+        assert_eq!(full["changed"]["runtime_id"], json!("test-identity"));
+        assert_eq!(full["changed"]["config_revision"], json!(0));
+        let revision = full["snapshot_revision"].as_u64().expect("snapshot_revision");
+
+        let idle = snapshot_since(12, revision);
+        assert_eq!(idle["unchanged"], json!(true));
+        assert_eq!(idle["snapshot_revision"], json!(revision));
+        assert_eq!(idle["changed"], json!({}));
+
+        daemon
+            .handle_rpc(rpc_request(
+                13,
+                "sdk_configure_v2",
+                json!({
+                    "expected_revision": 0,
+                    "patch": { "event_stream": { "max_poll_events": 64 } }
+                }),
+            ))
+            .expect("configure");
+        let after_configure = snapshot_since(14, revision);
+        assert_eq!(after_configure["unchanged"], json!(false));
+        assert_eq!(after_configure["changed"]["config_revision"], json!(1));
+        assert!(after_configure["changed"].get("runtime_id").is_none());
+
+        let from_zero = snapshot_since(15, 0);
+        assert_eq!(from_zero["changed"]["runtime_id"], json!("test-identity"));
+        let from_future = snapshot_since(16, 10_000);
+        assert_eq!(from_future["changed"]["runtime_id"], json!("test-identity"));
+    }
+
+    #[test]
+    fn announce_interval_config_rearms_scheduler_and_is_reported_in_snapshot() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        let local = tokio::task::LocalSet::new();
+        local.block_on(&runtime, async {
+            let daemon = std::rc::Rc::new(RpcDaemon::test_instance());
+            let mut events = daemon.subscribe_events();
+            let snapshot_interval = |id: u64| {
+                daemon
+                    .handle_rpc(rpc_request(id, "sdk_snapshot_v2", json!({})))
+                    .expect("snapshot")
+                    .result
+                    .expect("result")["announce_interval_secs"]
+                    .clone()
+            };
+            let configure = |id: u64, revision: u64, interval: JsonValue| {
+                let response = daemon
+                    .handle_rpc(rpc_request(
+                        id,
+                        "sdk_configure_v2",
+                        json!({
+                            "expected_revision": revision,
+                            "patch": { "announce_interval_secs": interval }
+                        }),
+                    ))
+                    .expect("configure");
+                assert!(response.error.is_none());
+            };
+            let announces_sent = |events: &mut tokio::sync::broadcast::Receiver<RpcEvent>| {
+                std::iter::from_fn(|| events.try_recv().ok())
+                    .filter(|event| event.event_type == "announce_sent")
+                    .count()
+            };
+
+            let _scheduler = daemon.clone().start_announce_scheduler(60);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            assert_eq!(announces_sent(&mut events), 1, "startup announce");
+            assert_eq!(snapshot_interval(20), json!(60));
+
+            configure(21, 0, json!(1));
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            assert_eq!(snapshot_interval(22), json!(10));
+            assert_eq!(announces_sent(&mut events), 0, "re-arming must not announce");
+
+            configure(23, 1, json!(300));
+            assert_eq!(snapshot_interval(24), json!(300));
+            configure(25, 2, JsonValue::Null);
+            assert_eq!(snapshot_interval(26), json!(60));
+        });
+    }
+
     #[test]
     fn sdk_race_cancel_and_receipt_updates_converge_to_terminal_state() {
         let daemon = RpcDaemon::test_instance();
@@ -623,3 +968,2930 @@
             assert_eq!(second_cancel.result.expect("second cancel result")["result"], json!("AlreadyTerminal"));
         }
     }
+
+    #[test]
+    fn version_reports_contract_release_and_compiled_features() {
+        let daemon = RpcDaemon::test_instance();
+        let response = daemon
+            .handle_rpc(RpcRequest { id: 90, method: "version".to_string(), params: None })
+            .expect("version");
+        assert!(response.error.is_none());
+        let result = response.result.expect("result");
+        assert_eq!(result["contract_release"], json!(CONTRACT_RELEASE));
+        assert_eq!(result["schema_namespace"], json!("v2"));
+        assert_eq!(result["supported_contract_versions"], json!(SUPPORTED_CONTRACT_VERSIONS));
+        assert_eq!(result["crate_version"], json!(env!("CARGO_PKG_VERSION")));
+        assert_eq!(result["git_hash"], json!(option_env!("LXMF_GIT_HASH")));
+        assert_eq!(result["features"], json!(RpcDaemon::compiled_features()));
+        let features = result["features"].as_array().expect("features array");
+        let enabled = env!("RNS_RPC_FEATURES").split(',').filter(|name| !name.is_empty());
+        let mut expected = enabled.map(|name| json!(name)).collect::<Vec<_>>();
+        if cfg!(debug_assertions) {
+            expected.push(json!("debug_assertions"));
+        }
+        assert_eq!(features, &expected);
+        assert!(RpcDaemon::capabilities().contains(&"version"));
+        assert!(RpcDaemon::capabilities().contains(&"version_info"));
+        let alias = daemon
+            .handle_rpc(RpcRequest { id: 91, method: "version_info".to_string(), params: None })
+            .expect("version_info");
+        assert_eq!(alias.result, Some(result));
+    }
+
+    #[test]
+    fn dry_run_send_returns_plan_without_creating_record() {
+        let daemon = RpcDaemon::test_instance();
+        let response = daemon
+            .handle_rpc(rpc_request(
+                91,
+                "send_message_v2",
+                json!({
+                    "id": "dry-run-1",
+                    "source": "src",
+                    "destination": "dst",
+                    "title": "check",
+                    "content": "hello dry run",
+                    "method": "opportunistic",
+                    "dry_run": true
+                }),
+            ))
+            .expect("dry-run send");
+        assert!(response.error.is_none());
+        let result = response.result.expect("result");
+        assert_eq!(result["dry_run"], json!(true));
+        assert_eq!(result["message_id"], json!("dry-run-1"));
+        assert_eq!(result["plan"]["method"], json!("opportunistic"));
+        let wire_size = result["plan"]["wire_size"].as_u64().expect("wire size");
+        assert!(wire_size > "hello dry run".len() as u64 + 96);
+
+        assert!(daemon.store.get_message("dry-run-1").expect("lookup").is_none());
+        assert!(daemon.delivery_traces.lock().expect("traces").get("dry-run-1").is_none());
+        while let Some(event) = daemon.take_event() {
+            assert_ne!(event.event_type, "outbound");
+        }
+
+        let invalid = daemon.handle_rpc(rpc_request(
+            92,
+            "send_message",
+            json!({
+                "id": "dry-run-2",
+                "source": " ",
+                "destination": "dst",
+                "content": "hello",
+                "dry_run": true
+            }),
+        ));
+        assert_eq!(invalid.expect_err("blank source").kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn dry_run_send_is_rejected_by_the_same_checks_as_a_real_send() {
+        let daemon = RpcDaemon::test_instance();
+        let configured = daemon
+            .handle_rpc(rpc_request(
+                1,
+                "sdk_configure_v2",
+                json!({ "expected_revision": 0, "patch": { "per_destination_rate_limit": 1 } }),
+            ))
+            .expect("configure");
+        assert!(configured.error.is_none());
+        let send = |id: &str, destination: &str, extra: JsonValue, dry_run: bool| {
+            let mut params = json!({
+                "id": id,
+                "source": "src",
+                "destination": destination,
+                "content": "hello",
+                "dry_run": dry_run
+            });
+            merge_json_patch(&mut params, &extra);
+            daemon.handle_rpc(rpc_request(2, "send_message_v2", params)).expect("send")
+        };
+
+        assert!(send("sent", "dst", json!({}), false).error.is_none());
+        let limited = send("planned", "dst", json!({}), true).error.expect("rate limited");
+        assert_eq!(limited.code, "SDK_RUNTIME_RATE_LIMITED");
+        assert!(send("planned", "other", json!({}), true).error.is_none());
+
+        let ticket = daemon
+            .handle_rpc(rpc_request(
+                3,
+                "ticket_generate",
+                json!({ "destination": "peer-a", "ttl_secs": 600 }),
+            ))
+            .expect("generate")
+            .result
+            .expect("result")["ticket"]
+            .clone();
+        daemon
+            .handle_rpc(rpc_request(4, "ticket_revoke", json!({ "ticket": ticket })))
+            .expect("revoke");
+        let revoked =
+            send("ticketed", "peer-a", json!({ "ticket": ticket }), true).error.expect("revoked");
+        assert_eq!(revoked.code, "SDK_SECURITY_TICKET_REVOKED");
+
+        *daemon.sdk_draining.lock().expect("sdk_draining mutex poisoned") = true;
+        let draining = send("draining", "other", json!({}), true).error.expect("draining");
+        assert_eq!(draining.code, "SDK_RUNTIME_INVALID_STATE");
+    }
+
+    #[test]
+    fn send_with_source_private_key_requires_the_matching_source_hash() {
+        let daemon = RpcDaemon::test_instance();
+        let signer = PrivateIdentity::new_from_name("source-key-check");
+        let key_hex = hex::encode(signer.to_private_key_bytes());
+        let derived = hex::encode(signer.address_hash().as_slice());
+        let send = |id: &str, source: &str, key: &str, dry_run: bool| {
+            daemon
+                .handle_rpc(rpc_request(
+                    1,
+                    "send_message_v2",
+                    json!({
+                        "id": id,
+                        "source": source,
+                        "destination": "dst",
+                        "content": "hello",
+                        "source_private_key": key,
+                        "dry_run": dry_run
+                    }),
+                ))
+                .expect("send")
+        };
+
+        assert!(send("matching", &derived.to_ascii_uppercase(), &key_hex, false).error.is_none());
+        for dry_run in [false, true] {
+            let unparseable = send("label", "src", &key_hex, dry_run).error.expect("unparseable");
+            assert_eq!(unparseable.code, "SDK_VALIDATION_INVALID_ARGUMENT");
+            assert_eq!(unparseable.details.as_ref().expect("details")["field"], json!("source"));
+
+            let mismatched = send("other", "00112233445566778899aabbccddeeff", &key_hex, dry_run)
+                .error
+                .expect("mismatched");
+            assert!(mismatched.message.contains("does not match source_private_key"));
+
+            let bad_key = send("bad-key", &derived, "zz", dry_run).error.expect("bad key");
+            assert_eq!(
+                bad_key.details.as_ref().expect("details")["field"],
+                json!("source_private_key")
+            );
+        }
+        assert!(daemon.store.get_message("label").expect("lookup").is_none());
+    }
+
+    fn delivery_trace(transitions: &[(&str, i64)]) -> Vec<DeliveryTraceEntry> {
+        transitions
+            .iter()
+            .map(|(status, timestamp)| DeliveryTraceEntry {
+                status: (*status).to_string(),
+                timestamp: *timestamp,
+                timestamp_ms: *timestamp * 1000,
+                reason_code: None,
+                detail: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn delivery_metrics_aggregates_rate_and_latency_percentiles() {
+        let base = 1_000;
+        let mut traces = (1..=19)
+            .map(|latency| {
+                delivery_trace(&[
+                    ("queued", base),
+                    ("sent: direct", base),
+                    ("delivered", base + latency),
+                ])
+            })
+            .collect::<Vec<_>>();
+        traces.push(delivery_trace(&[
+            ("queued", base),
+            ("sent: propagated", base),
+            ("delivered", base + 100),
+        ]));
+        traces.push(delivery_trace(&[("queued", base), ("failed: no path", base + 5)]));
+        traces.push(delivery_trace(&[("queued", base), ("sent: direct", base), ("expired", base)]));
+        traces.push(delivery_trace(&[("queued", base), ("sending", base)]));
+        traces.push(delivery_trace(&[("queued", 10), ("delivered", 20)]));
+
+        let metrics = aggregate_delivery_metrics(traces.iter().map(Vec::as_slice), base * 1000);
+        assert_eq!(metrics["total"], 23);
+        assert_eq!(metrics["delivered"], 20);
+        assert_eq!(metrics["failed"], 2);
+        assert_eq!(metrics["pending"], 1);
+        let success_rate = metrics["success_rate"].as_f64().expect("success rate");
+        assert!((success_rate - 20.0 / 22.0).abs() < 1e-9);
+        let avg = metrics["latency_ms"]["avg"].as_f64().expect("avg latency");
+        assert!((avg - (190_000.0 + 100_000.0) / 20.0).abs() < 1e-9);
+        assert_eq!(metrics["latency_ms"]["p95"], 19_000);
+        assert_eq!(metrics["latency_ms"]["samples"], 20);
+        assert_eq!(
+            metrics["by_method"]["direct"],
+            json!({ "total": 20, "delivered": 19, "failed": 1 })
+        );
+        assert_eq!(metrics["by_method"]["propagated"]["delivered"], 1);
+        assert_eq!(metrics["by_method"]["unknown"]["total"], 2);
+    }
+
+    #[test]
+    fn trace_get_returns_persisted_trace_after_memory_eviction() {
+        let daemon = RpcDaemon::test_instance();
+        assert!(send_titled(&daemon, "traced", "hello").error.is_none());
+        daemon.delivery_traces.lock().expect("delivery traces mutex poisoned").clear();
+
+        let result = daemon
+            .handle_rpc(rpc_request(1, "trace_get", json!({ "message_id": "traced" })))
+            .expect("trace_get")
+            .result
+            .expect("result");
+        let trace = result["trace"].as_array().expect("trace");
+        let statuses = trace.iter().map(|entry| entry["status"].clone()).collect::<Vec<_>>();
+        assert_eq!(statuses, vec![json!("queued"), json!("sending"), json!("sent: direct")]);
+        assert_eq!(trace[2]["detail"], json!("direct"));
+        assert_eq!(trace[0]["detail"], JsonValue::Null);
+
+        let delivery_trace = daemon
+            .handle_rpc(rpc_request(2, "delivery_trace", json!({ "message_id": "traced" })))
+            .expect("delivery_trace")
+            .result
+            .expect("result");
+        assert_eq!(delivery_trace["trace"], result["trace"]);
+
+        assert_eq!(delivery_trace["terminal"], result["terminal"]);
+
+        for method in ["trace_get", "delivery_trace"] {
+            let unknown = daemon
+                .handle_rpc(rpc_request(3, method, json!({ "message_id": "never-traced" })))
+                .expect(method);
+            assert_eq!(unknown.error.expect("error").code, "SDK_VALIDATION_INVALID_ARGUMENT");
+        }
+    }
+
+    #[test]
+    fn delivery_metrics_rpc_reports_window_over_persisted_traces() {
+        let daemon = RpcDaemon::test_instance();
+        let now_ms = now_millis_u64() as i64;
+        let traces: [(&str, &[(&str, i64)]); 3] = [
+            ("recent-ok", &[("queued", now_ms - 1_250), ("sent: direct", now_ms - 1_200), ("delivered", now_ms - 1_000)]),
+            ("recent-failed", &[("queued", now_ms - 2_000), ("failed: timeout", now_ms - 1_000)]),
+            ("old-ok", &[("queued", now_ms - 7_200_000), ("delivered", now_ms - 7_000_000)]),
+        ];
+        for (message_id, transitions) in traces {
+            daemon
+                .store
+                .insert_message(&MessageRecord {
+                    id: message_id.to_string(),
+                    source: "src".to_string(),
+                    destination: "dst".to_string(),
+                    title: String::new(),
+                    content: String::new(),
+                    timestamp: now_ms / 1000,
+                    direction: "out".to_string(),
+                    fields: None,
+                    receipt_status: None,
+                    reply_to: None,
+                })
+                .expect("insert message");
+            for (status, timestamp_ms) in transitions {
+                let entry = DeliveryTraceEntry {
+                    status: (*status).to_string(),
+                    timestamp: timestamp_ms / 1000,
+                    timestamp_ms: *timestamp_ms,
+                    reason_code: None,
+                    detail: None,
+                };
+                daemon.store.append_delivery_trace(message_id, &entry, 32).expect("append trace");
+            }
+        }
+        // Only the store holds these traces, as after a restart or eviction.
+        assert!(daemon.delivery_traces.lock().expect("delivery traces mutex poisoned").is_empty());
+
+        let response = daemon
+            .handle_rpc(rpc_request(1, "delivery_metrics", json!({ "window_ms": 600_000 })))
+            .expect("delivery metrics");
+        let result = response.result.expect("result");
+        assert_eq!(result["window_ms"], 600_000);
+        assert!(result["window_start_ms"].as_i64().expect("window start") >= now_ms - 600_000);
+        assert_eq!(result["total"], 2);
+        assert_eq!(result["success_rate"], 0.5);
+        assert_eq!(result["latency_ms"]["p95"], 250);
+
+        let empty = daemon
+            .handle_rpc(rpc_request(2, "delivery_metrics", json!({ "window_ms": 0 })))
+            .expect("delivery metrics");
+        assert_eq!(empty.error.expect("error").code, "SDK_VALIDATION_INVALID_ARGUMENT");
+    }
+
+    #[test]
+    fn propagation_stats_counts_ingest_fetch_and_rejected_stamps() {
+        let daemon = RpcDaemon::test_instance();
+        daemon.set_propagation_state(true, None, 0);
+        daemon
+            .handle_rpc(rpc_request(
+                1,
+                "propagation_ingest",
+                json!({ "transient_id": "t-1", "payload_hex": "00112233" }),
+            ))
+            .expect("ingest first");
+        daemon
+            .handle_rpc(rpc_request(
+                2,
+                "propagation_ingest",
+                json!({ "transient_id": "t-2", "payload_hex": "aabb" }),
+            ))
+            .expect("ingest second");
+        daemon
+            .handle_rpc(rpc_request(3, "propagation_fetch", json!({ "transient_id": "t-1" })))
+            .expect("fetch hit");
+        assert!(daemon
+            .handle_rpc(rpc_request(4, "propagation_fetch", json!({ "transient_id": "missing" })))
+            .is_err());
+        daemon.set_propagation_state(true, None, 64);
+        let unstamped = daemon
+            .handle_rpc(rpc_request(
+                5,
+                "propagation_ingest",
+                json!({ "transient_id": "t-3", "payload_hex": "00".repeat(40) }),
+            ))
+            .expect("ingest unstamped");
+        assert_eq!(unstamped.error.expect("error").code, "SDK_VALIDATION_INVALID_STAMP");
+
+        let response = daemon
+            .handle_rpc(rpc_request(6, "propagation_stats", json!({})))
+            .expect("propagation stats");
+        let stats = response.result.expect("result")["stats"].clone();
+        assert_eq!(stats["ingested_total"], 2);
+        assert_eq!(stats["last_ingest_count"], 1);
+        assert_eq!(stats["fetches_served"], 1);
+        assert_eq!(stats["fetch_misses"], 1);
+        assert_eq!(stats["rejected_stamps"], 1);
+        assert_eq!(stats["store_messages"], 2);
+        assert_eq!(stats["store_bytes"], 6);
+    }
+
+    /// Appends a propagation stamp worth exactly `value` to `lxmf_data`.
+    fn stamped_propagation_payload(lxmf_data: &[u8], value: u32) -> Vec<u8> {
+        use lxmf_core::stamper::{stamp_value, stamp_workblock, WORKBLOCK_EXPAND_ROUNDS_PN};
+        let transient_id = Sha256::digest(lxmf_data);
+        let workblock = stamp_workblock(&transient_id, WORKBLOCK_EXPAND_ROUNDS_PN);
+        let stamp = (0u64..)
+            .map(|nonce| {
+                let mut stamp = [0u8; 32];
+                stamp[..8].copy_from_slice(&nonce.to_le_bytes());
+                stamp
+            })
+            .find(|stamp| stamp_value(&workblock, stamp) == value)
+            .expect("stamp");
+        [lxmf_data, stamp.as_slice()].concat()
+    }
+
+    #[test]
+    fn propagation_ingest_stores_stamped_messages_without_their_stamp() {
+        let daemon = RpcDaemon::test_instance();
+        daemon.set_propagation_state(true, None, PROPAGATION_COST_MIN);
+        daemon.stamp_policy.lock().expect("stamp policy").flexibility = PROPAGATION_COST_MIN - 4;
+        let lxmf_data = b"lxmf propagated payload".to_vec();
+        let response = daemon
+            .handle_rpc(rpc_request(
+                1,
+                "propagation_ingest",
+                json!({ "payload_hex": hex::encode(stamped_propagation_payload(&lxmf_data, 4)) }),
+            ))
+            .expect("ingest");
+        let transient_id = response.result.expect("result")["transient_id"].clone();
+        assert_eq!(transient_id, json!(hex::encode(Sha256::digest(&lxmf_data))));
+
+        let fetched = daemon
+            .handle_rpc(rpc_request(2, "propagation_fetch", json!({ "transient_id": transient_id })))
+            .expect("fetch");
+        assert_eq!(fetched.result.expect("result")["payload_hex"], json!(hex::encode(&lxmf_data)));
+        assert_eq!(daemon.propagation_state.lock().expect("propagation").rejected_stamps, 0);
+    }
+
+    #[derive(Default)]
+    struct RecordingSyncBridge {
+        syncs: Mutex<Vec<PropagationSyncRequest>>,
+    }
+
+    impl OutboundBridge for RecordingSyncBridge {
+        fn deliver(
+            &self,
+            _record: &MessageRecord,
+            _options: &OutboundDeliveryOptions,
+        ) -> Result<(), std::io::Error> {
+            Ok(())
+        }
+
+        fn sync_propagation(&self, request: &PropagationSyncRequest) -> Result<(), std::io::Error> {
+            self.syncs.lock().expect("syncs").push(request.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn sync_now_requires_a_bridge_that_can_sync() {
+        let daemon = RpcDaemon::test_instance();
+        daemon
+            .handle_rpc(rpc_request(
+                1,
+                "set_outbound_propagation_node",
+                json!({ "peer": "aabbccddeeff00112233445566778899" }),
+            ))
+            .expect("select node");
+        let response = daemon.handle_rpc(rpc_request(2, "sync_now", json!({}))).expect("sync");
+        assert_eq!(response.error.expect("error").code, "SDK_CAPABILITY_DISABLED");
+        let state = daemon.propagation_state.lock().expect("propagation").clone();
+        assert_eq!(state.sync_state, 0);
+    }
+
+    #[test]
+    fn sync_now_starts_one_bridge_sync_and_reports_a_running_sync() {
+        let bridge = Arc::new(RecordingSyncBridge::default());
+        let store = MessagesStore::in_memory().expect("store");
+        let daemon =
+            RpcDaemon::with_store_and_bridge(store, "test-identity".into(), bridge.clone());
+        let missing = daemon
+            .handle_rpc(rpc_request(1, "sync_now", json!({})))
+            .expect("sync without node")
+            .error
+            .expect("error");
+        assert_eq!(missing.code, "SDK_RUNTIME_NO_PROPAGATION_NODE");
+        assert_eq!(
+            missing.details.expect("details")["prerequisite"],
+            json!("outbound_propagation_node")
+        );
+
+        daemon
+            .handle_rpc(rpc_request(
+                2,
+                "set_outbound_propagation_node",
+                json!({ "peer": "aabbccddeeff00112233445566778899" }),
+            ))
+            .expect("select node");
+        while daemon.take_event().is_some() {}
+        let started = daemon
+            .handle_rpc(rpc_request(3, "sync_now", json!({})))
+            .expect("sync now")
+            .result
+            .expect("result");
+        assert_eq!(started["already_running"], json!(false));
+        assert_eq!(started["sync"]["state"], json!(PR_PATH_REQUESTED));
+        assert_eq!(started["sync"]["state_name"], json!("path_requested"));
+        assert_eq!(started["sync"]["selected_node"], json!("aabbccddeeff00112233445566778899"));
+        let progress = std::iter::from_fn(|| daemon.take_event())
+            .filter(|event| event.event_type == "propagation_sync_progress")
+            .collect::<Vec<_>>();
+        assert_eq!(progress.len(), 1);
+        assert_eq!(progress[0].payload["state_name"], json!("path_requested"));
+
+        let again = daemon
+            .handle_rpc(rpc_request(4, "sync_now", json!({})))
+            .expect("sync again")
+            .result
+            .expect("result");
+        assert_eq!(again["already_running"], json!(true));
+        assert_eq!(again["sync"]["state"], json!(PR_PATH_REQUESTED));
+        assert!(daemon.take_event().is_none());
+        assert_eq!(
+            *bridge.syncs.lock().expect("syncs"),
+            vec![PropagationSyncRequest {
+                node: "aabbccddeeff00112233445566778899".into(),
+                max_messages: PROPAGATION_SYNC_MAX_MESSAGES,
+            }]
+        );
+    }
+
+    #[test]
+    fn propagated_message_lifetime_counts_from_its_lxmf_timestamp() {
+        let daemon = RpcDaemon::test_instance();
+        daemon.set_propagation_state(true, None, 0);
+        daemon
+            .handle_rpc(rpc_request(
+                1,
+                "sdk_configure_v2",
+                json!({ "expected_revision": 0, "patch": { "propagation_message_ttl_ms": 60_000 } }),
+            ))
+            .expect("configure");
+        let wire_hex = |timestamp_ms: u64| {
+            let payload = lxmf_core::Payload::new(
+                timestamp_ms as f64 / 1000.0,
+                Some(b"hello".to_vec()),
+                None,
+                None,
+                None,
+            );
+            let mut message = WireMessage::new([1; 16], [2; 16], payload);
+            message.signature = Some([0; 64]);
+            hex::encode(message.pack().expect("pack"))
+        };
+        let ingest = |id: &str, payload_hex: String| {
+            daemon
+                .handle_rpc(rpc_request(
+                    2,
+                    "propagation_ingest",
+                    json!({ "transient_id": id, "payload_hex": payload_hex }),
+                ))
+                .expect("ingest");
+        };
+
+        let before_ms = now_millis_u64();
+        let sent_ms = before_ms - 120_000;
+        ingest("stale", wire_hex(sent_ms));
+        assert_eq!(
+            daemon.propagation_payloads.lock().expect("payloads")["stale"].expires_at_ms,
+            Some(sent_ms + 60_000)
+        );
+        ingest("future", wire_hex(before_ms + 3_600_000));
+        ingest("opaque", "0011".to_string());
+        let after_ms = now_millis_u64();
+        {
+            let payloads = daemon.propagation_payloads.lock().expect("payloads");
+            assert!(!payloads.contains_key("stale"), "purged by the next ingest");
+            // Clock skew cannot stretch a message's lifetime past the TTL.
+            for id in ["future", "opaque"] {
+                let expires = payloads[id].expires_at_ms.expect("expiry");
+                assert!((before_ms + 60_000..=after_ms + 60_000).contains(&expires), "{id}");
+            }
+        }
+
+        let stale = daemon
+            .handle_rpc(rpc_request(3, "propagation_fetch", json!({ "transient_id": "stale" })))
+            .expect("fetch stale");
+        assert_eq!(stale.error.expect("error").code, "SDK_RUNTIME_MESSAGE_EXPIRED");
+        let opaque = daemon
+            .handle_rpc(rpc_request(4, "propagation_fetch", json!({ "transient_id": "opaque" })))
+            .expect("fetch opaque");
+        assert_eq!(opaque.result.expect("result")["payload_hex"], json!("0011"));
+    }
+
+    #[test]
+    fn propagation_fetch_reports_expired_messages_separately_from_missing_ones() {
+        let daemon = RpcDaemon::test_instance();
+        daemon.set_propagation_state(true, None, 0);
+        daemon
+            .handle_rpc(rpc_request(
+                1,
+                "sdk_configure_v2",
+                json!({ "expected_revision": 0, "patch": { "propagation_message_ttl_ms": 0 } }),
+            ))
+            .expect("configure");
+        daemon
+            .handle_rpc(rpc_request(
+                2,
+                "propagation_ingest",
+                json!({ "transient_id": "forever", "payload_hex": "2233" }),
+            ))
+            .expect("ingest without ttl");
+        daemon
+            .handle_rpc(rpc_request(
+                3,
+                "sdk_configure_v2",
+                json!({ "expected_revision": 1, "patch": { "propagation_message_ttl_ms": 60_000 } }),
+            ))
+            .expect("configure ttl");
+        daemon
+            .handle_rpc(rpc_request(
+                3,
+                "propagation_ingest",
+                json!({ "transient_id": "short", "payload_hex": "0011" }),
+            ))
+            .expect("ingest short-lived");
+        {
+            let payloads = daemon.propagation_payloads.lock().expect("payloads");
+            assert!(payloads["short"].expires_at_ms.is_some());
+            assert_eq!(payloads["forever"].expires_at_ms, None);
+        }
+
+        daemon
+            .propagation_payloads
+            .lock()
+            .expect("payloads")
+            .get_mut("short")
+            .expect("short")
+            .expires_at_ms = Some(1);
+        let (store_messages, _) = daemon.propagation_store_usage();
+        assert_eq!(store_messages, 1);
+        let expired = daemon
+            .handle_rpc(rpc_request(4, "propagation_fetch", json!({ "transient_id": "short" })))
+            .expect("fetch expired");
+        let error = expired.error.expect("error");
+        assert_eq!(error.code, "SDK_RUNTIME_MESSAGE_EXPIRED");
+        assert_eq!(error.details.expect("details")["transient_id"], json!("short"));
+
+        daemon
+            .handle_rpc(rpc_request(
+                5,
+                "propagation_ingest",
+                json!({ "transient_id": "later", "payload_hex": "4455" }),
+            ))
+            .expect("ingest purges");
+        assert!(!daemon.propagation_payloads.lock().expect("payloads").contains_key("short"));
+        let purged = daemon
+            .handle_rpc(rpc_request(6, "propagation_fetch", json!({ "transient_id": "short" })))
+            .expect("fetch purged");
+        assert_eq!(purged.error.expect("error").code, "SDK_RUNTIME_MESSAGE_EXPIRED");
+        assert!(daemon
+            .handle_rpc(rpc_request(7, "propagation_fetch", json!({ "transient_id": "missing" })))
+            .is_err());
+        let forever = daemon
+            .handle_rpc(rpc_request(8, "propagation_fetch", json!({ "transient_id": "forever" })))
+            .expect("fetch live");
+        assert_eq!(forever.result.expect("result")["payload_hex"], json!("2233"));
+    }
+
+    #[test]
+    fn auto_contacts_adds_verified_peers_after_delivered_round_trip() {
+        let daemon = RpcDaemon::test_instance();
+        daemon
+            .handle_rpc(rpc_request(1, "set_delivery_policy", json!({ "auto_contacts": true })))
+            .expect("set delivery policy");
+        let now = now_i64();
+        daemon
+            .accept_announce_with_details(
+                "peer-verified".into(),
+                now,
+                Some("Verified Vera".into()),
+                Some("delivery_app_data".into()),
+            )
+            .expect("verified announce");
+        daemon
+            .accept_announce_with_details(
+                "peer-unverified".into(),
+                now,
+                Some("Guessed Gus".into()),
+                Some("app_data_utf8".into()),
+            )
+            .expect("unverified announce");
+
+        let sends = [("m-verified", "peer-verified"), ("m-unverified", "peer-unverified")];
+        for (id, destination) in sends {
+            daemon
+                .handle_rpc(rpc_request(
+                    2,
+                    "send_message",
+                    json!({
+                        "id": id,
+                        "source": "local",
+                        "destination": destination,
+                        "content": "hi",
+                    }),
+                ))
+                .expect("send");
+            daemon
+                .handle_rpc(rpc_request(
+                    3,
+                    "record_receipt",
+                    json!({ "message_id": id, "status": "delivered" }),
+                ))
+                .expect("receipt");
+        }
+
+        let contacts = daemon.sdk_contacts.lock().expect("sdk_contacts mutex poisoned").clone();
+        assert_eq!(contacts.len(), 1);
+        let contact = contacts.get("peer-verified").expect("verified peer contact");
+        assert_eq!(contact.display_name.as_deref(), Some("Verified Vera"));
+        assert!(!contacts.contains_key("peer-unverified"));
+    }
+
+    #[test]
+    fn receipt_is_recorded_and_published_when_auto_contact_creation_fails() {
+        let (db_path, _) = event_persist_paths("auto-contact-store-failure");
+        let daemon = RpcDaemon::with_store(
+            MessagesStore::open(db_path.as_path()).expect("open sqlite store"),
+            "local".into(),
+        );
+        daemon
+            .handle_rpc(rpc_request(1, "set_delivery_policy", json!({ "auto_contacts": true })))
+            .expect("set delivery policy");
+        daemon
+            .accept_announce_with_details(
+                "peer-verified".into(),
+                now_i64(),
+                Some("Verified Vera".into()),
+                Some("delivery_app_data".into()),
+            )
+            .expect("verified announce");
+        daemon
+            .handle_rpc(rpc_request(
+                2,
+                "send_message",
+                json!({
+                    "id": "m-verified",
+                    "source": "local",
+                    "destination": "peer-verified",
+                    "content": "hi",
+                }),
+            ))
+            .expect("send");
+
+        let sqlite = rusqlite::Connection::open(db_path.as_path()).expect("open sqlite");
+        sqlite
+            .execute_batch(
+                "CREATE TRIGGER reject_domain_insert BEFORE INSERT ON sdk_domain_state
+                 BEGIN SELECT RAISE(ABORT, 'disk full'); END;
+                 CREATE TRIGGER reject_domain_update BEFORE UPDATE ON sdk_domain_state
+                 BEGIN SELECT RAISE(ABORT, 'disk full'); END;",
+            )
+            .expect("install failing triggers");
+        let mut events = daemon.subscribe_events();
+        let response = daemon
+            .handle_rpc(rpc_request(
+                3,
+                "record_receipt",
+                json!({ "message_id": "m-verified", "status": "delivered" }),
+            ))
+            .expect("receipt must not fail on contact store errors");
+        assert_eq!(response.result.expect("result")["updated"], json!(true));
+
+        let published = std::iter::from_fn(|| events.try_recv().ok())
+            .filter(|event| event.event_type == "receipt")
+            .count();
+        assert_eq!(published, 1);
+        let stored = daemon.store.get_message("m-verified").expect("get").expect("message");
+        assert_eq!(stored.receipt_status.as_deref(), Some("delivered"));
+        let log = daemon.daemon_log.lock().expect("daemon log mutex poisoned");
+        assert!(log.lines.iter().any(|line| line.contains("auto contact for m-verified")));
+        drop(log);
+        let _ = std::fs::remove_file(db_path);
+    }
+
+    #[test]
+    fn peer_rtt_reports_percentiles_and_empty_peer_lists() {
+        let daemon = RpcDaemon::test_instance();
+        let empty = daemon.handle_rpc(rpc_request(1, "peer_rtt", json!({}))).expect("peer_rtt");
+        assert_eq!(empty.result.expect("result")["peers"], json!([]));
+
+        for rtt_ms in (10..=100).step_by(10) {
+            daemon.record_peer_rtt_sample("peer-a", rtt_ms);
+        }
+        let measured = daemon
+            .handle_rpc(rpc_request(2, "peer_rtt", json!({ "peer": "peer-a" })))
+            .expect("peer_rtt")
+            .result
+            .expect("result");
+        assert_eq!(measured["rtt_ms_p50"], json!(50));
+        assert_eq!(measured["rtt_ms_p95"], json!(100));
+        assert_eq!(measured["samples"], json!(10));
+
+        let unmeasured = daemon
+            .handle_rpc(rpc_request(3, "peer_rtt", json!({ "peer": "peer-b" })))
+            .expect("peer_rtt")
+            .result
+            .expect("result");
+        assert_eq!(unmeasured["rtt_ms_p50"], JsonValue::Null);
+        assert_eq!(unmeasured["samples"], json!(0));
+
+        daemon
+            .handle_rpc(rpc_request(
+                4,
+                "send_message_v2",
+                json!({
+                    "id": "rtt-1",
+                    "source": "src",
+                    "destination": "peer-c",
+                    "title": "",
+                    "content": "hello"
+                }),
+            ))
+            .expect("send");
+        daemon
+            .handle_rpc(rpc_request(
+                5,
+                "record_receipt",
+                json!({ "message_id": "rtt-1", "status": "delivered" }),
+            ))
+            .expect("receipt");
+        let listed = daemon
+            .handle_rpc(rpc_request(6, "peer_rtt", json!({})))
+            .expect("peer_rtt")
+            .result
+            .expect("result");
+        let peers = listed["peers"].as_array().expect("peers");
+        let peer_c = peers.iter().find(|row| row["peer"] == json!("peer-c")).expect("peer-c");
+        assert_eq!(peer_c["samples"], json!(1));
+    }
+
+    #[test]
+    fn pending_rtt_timers_expire_and_never_block_new_sends() {
+        let daemon = RpcDaemon::test_instance();
+        let now_ms = now_millis_u64();
+        let stale_ms = now_ms - PEER_RTT_PENDING_MAX_AGE_MS;
+        daemon.start_rtt_timer_at("stale", "peer-a", stale_ms);
+        for index in 1..PEER_RTT_PENDING_CAPACITY {
+            daemon.start_rtt_timer_at(&format!("fresh-{index}"), "peer-a", now_ms - 1_000);
+        }
+
+        daemon.start_rtt_timer_at("full-1", "peer-b", now_ms);
+        {
+            let pending = &daemon.peer_rtt.lock().expect("peer rtt").pending;
+            assert!(!pending.contains_key("stale"), "expired timers are dropped");
+            assert!(pending.contains_key("full-1"));
+        }
+
+        daemon.start_rtt_timer_at("full-2", "peer-b", now_ms);
+        let pending = &daemon.peer_rtt.lock().expect("peer rtt").pending;
+        assert_eq!(pending.len(), PEER_RTT_PENDING_CAPACITY);
+        assert!(pending.contains_key("full-2"), "the oldest live timer makes room");
+    }
+
+    fn send_titled(daemon: &RpcDaemon, id: &str, title: &str) -> RpcResponse {
+        daemon
+            .handle_rpc(rpc_request(
+                80,
+                "send_message_v2",
+                json!({
+                    "id": id,
+                    "source": "src",
+                    "destination": "dst",
+                    "title": title,
+                    "content": "hello",
+                }),
+            ))
+            .expect("send")
+    }
+
+    fn stored_title(daemon: &RpcDaemon, id: &str) -> String {
+        daemon.store.get_message(id).expect("load").expect("stored message").title
+    }
+
+    #[test]
+    fn outbound_titles_respect_max_title_bytes_in_reject_mode() {
+        let daemon = RpcDaemon::test_instance();
+        daemon
+            .handle_rpc(rpc_request(1, "set_delivery_policy", json!({ "max_title_bytes": 8 })))
+            .expect("set delivery policy");
+
+        assert!(send_titled(&daemon, "at-limit", "12345678").error.is_none());
+        assert_eq!(stored_title(&daemon, "at-limit"), "12345678");
+
+        let over = send_titled(&daemon, "over-limit", "123456789").error.expect("error");
+        assert_eq!(over.code, "SDK_VALIDATION_CONTENT_TOO_LARGE");
+        let details = over.details.expect("details");
+        assert_eq!(details["field"], json!("title"));
+        assert_eq!(details["limit_name"], json!("max_title_bytes"));
+        assert_eq!(details["observed"], json!(9));
+        assert!(daemon.store.get_message("over-limit").expect("load").is_none());
+    }
+
+    #[test]
+    fn outbound_titles_truncate_on_char_boundary_and_strip_controls() {
+        let daemon = RpcDaemon::test_instance();
+        daemon
+            .handle_rpc(rpc_request(
+                1,
+                "set_delivery_policy",
+                json!({ "max_title_bytes": 7, "title_limit_mode": "truncate" }),
+            ))
+            .expect("set delivery policy");
+
+        assert!(send_titled(&daemon, "controls", "a\u{1b}[2Jb\nc").error.is_none());
+        assert_eq!(stored_title(&daemon, "controls"), "a[2Jb c");
+
+        assert!(send_titled(&daemon, "multibyte", "abcdefé").error.is_none());
+        assert_eq!(stored_title(&daemon, "multibyte"), "abcdef");
+    }
+
+    #[test]
+    fn outbound_content_and_title_are_capped_in_bytes_by_runtime_config() {
+        let daemon = RpcDaemon::test_instance();
+        let configured = daemon
+            .handle_rpc(rpc_request(
+                1,
+                "sdk_configure_v2",
+                json!({
+                    "expected_revision": 0,
+                    "patch": { "max_content_bytes": 8, "max_title_wire_bytes": 4 },
+                }),
+            ))
+            .expect("configure");
+        assert!(configured.error.is_none());
+
+        let send = |id: &str, title: &str, content: &str| {
+            daemon
+                .handle_rpc(rpc_request(
+                    2,
+                    "send_message_v2",
+                    json!({
+                        "id": id,
+                        "source": "src",
+                        "destination": "dst",
+                        "title": title,
+                        "content": content,
+                    }),
+                ))
+                .expect("send")
+        };
+        assert!(send("at-limit", "abcd", "éééé").error.is_none());
+
+        // Five characters but nine bytes: the limit is on the wire size.
+        let error = send("multibyte", "", "ééééa").error.expect("content error");
+        assert_eq!(error.code, "SDK_VALIDATION_CONTENT_TOO_LARGE");
+        let details = error.details.expect("details");
+        assert_eq!(details["field"], json!("content"));
+        assert_eq!(details["limit_name"], json!("max_content_bytes"));
+        assert_eq!(details["limit"], json!(8));
+        assert_eq!(details["observed"], json!(9));
+        assert!(daemon.store.get_message("multibyte").expect("load").is_none());
+
+        let error = send("long-title", "abcde", "ok").error.expect("title error");
+        assert_eq!(error.code, "SDK_VALIDATION_CONTENT_TOO_LARGE");
+        let details = error.details.expect("details");
+        assert_eq!(details["field"], json!("title"));
+        assert_eq!(details["limit_name"], json!("max_title_wire_bytes"));
+
+        // A delivery policy limit replaces the runtime cap, whether it is
+        // tighter and truncates or looser than the cap.
+        daemon
+            .handle_rpc(rpc_request(
+                3,
+                "set_delivery_policy",
+                json!({ "max_title_bytes": 4, "title_limit_mode": "truncate" }),
+            ))
+            .expect("set delivery policy");
+        assert!(send("truncated-title", "abcde", "ok").error.is_none());
+        assert_eq!(stored_title(&daemon, "truncated-title"), "abcd");
+        daemon
+            .handle_rpc(rpc_request(
+                3,
+                "set_delivery_policy",
+                json!({ "max_title_bytes": 16, "title_limit_mode": "reject" }),
+            ))
+            .expect("set delivery policy");
+        assert!(send("policy-title", "abcdefgh", "ok").error.is_none());
+        assert_eq!(stored_title(&daemon, "policy-title"), "abcdefgh");
+
+        let rejected = daemon
+            .handle_rpc(rpc_request(
+                4,
+                "sdk_configure_v2",
+                json!({ "expected_revision": 1, "patch": { "max_content_bytes": -1 } }),
+            ))
+            .expect("configure");
+        assert_eq!(rejected.error.expect("error").code, "SDK_VALIDATION_INVALID_ARGUMENT");
+    }
+    #[test]
+    fn stalled_outbound_messages_fail_after_receipt_timeout() {
+        let daemon = RpcDaemon::test_instance();
+        let configured = daemon
+            .handle_rpc(rpc_request(
+                1,
+                "sdk_configure_v2",
+                json!({
+                    "expected_revision": 0,
+                    "patch": {
+                        "receipt_timeout_ms": 1_000,
+                        "propagation_receipt_timeout_ms": 5_000,
+                    },
+                }),
+            ))
+            .expect("configure");
+        assert!(configured.error.is_none());
+
+        let send = |id: &str, method: Option<&str>| {
+            let response = daemon
+                .handle_rpc(rpc_request(
+                    2,
+                    "send_message_v2",
+                    json!({
+                        "id": id,
+                        "source": "src",
+                        "destination": "dst",
+                        "content": "hello",
+                        "method": method,
+                    }),
+                ))
+                .expect("send");
+            assert!(response.error.is_none());
+        };
+        let record_receipt = |id: &str, status: &str| {
+            daemon
+                .handle_rpc(rpc_request(
+                    3,
+                    "record_receipt",
+                    json!({ "message_id": id, "status": status }),
+                ))
+                .expect("record receipt");
+        };
+        let status = |id: &str| {
+            daemon.store.get_message(id).expect("load").expect("message").receipt_status
+        };
+        send("stalled", None);
+        send("delivered", None);
+        send("progressing", None);
+        send("propagated", Some("propagated"));
+        record_receipt("delivered", "delivered");
+        while daemon.take_event().is_some() {}
+
+        // Backdate every watch, then let one message make progress.
+        for watch in daemon.receipt_watches.lock().expect("watches").values_mut() {
+            watch.last_progress_ms = 0;
+        }
+        record_receipt("progressing", "sending: link resource");
+        let now_ms = 2_000;
+        assert_eq!(daemon.expire_stale_receipts_at(now_ms), 1);
+
+        assert_eq!(status("stalled").as_deref(), Some("failed: timeout waiting for receipt"));
+        assert_eq!(status("delivered").as_deref(), Some("delivered"));
+        assert_eq!(status("progressing").as_deref(), Some("sending: link resource"));
+        assert_eq!(status("propagated").as_deref(), Some("sent: propagated"));
+
+        let failed = std::iter::from_fn(|| daemon.take_event())
+            .filter(|event| event.event_type == "delivery_failed")
+            .collect::<Vec<_>>();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].payload["message_id"], json!("stalled"));
+        assert_eq!(failed[0].payload["reason_code"], json!("timeout"));
+        assert_eq!(failed[0].payload["timeout_ms"], json!(1_000));
+
+        // A receipt landing after the timeout cannot revive the message.
+        record_receipt("stalled", "delivered");
+        assert_eq!(status("stalled").as_deref(), Some("failed: timeout waiting for receipt"));
+
+        // Propagated sends use their own, longer timeout.
+        assert_eq!(daemon.expire_stale_receipts_at(now_ms + 5_000), 1);
+        assert_eq!(status("propagated").as_deref(), Some("failed: timeout waiting for receipt"));
+    }
+
+    #[derive(Default)]
+    struct CancellingBridge {
+        cancelled: Mutex<Vec<String>>,
+    }
+
+    impl OutboundBridge for CancellingBridge {
+        fn deliver(
+            &self,
+            _record: &MessageRecord,
+            _options: &OutboundDeliveryOptions,
+        ) -> Result<(), std::io::Error> {
+            Ok(())
+        }
+
+        fn cancel_delivery(&self, message_id: &str) -> Result<bool, std::io::Error> {
+            self.cancelled.lock().expect("cancelled").push(message_id.to_string());
+            Ok(true)
+        }
+    }
+
+    #[test]
+    fn receipt_watches_survive_a_restart_and_timeouts_cancel_bridge_delivery() {
+        let (db_path, _) = event_persist_paths("receipt-watch-restart");
+        let send = |daemon: &RpcDaemon, id: &str, method: Option<&str>| {
+            let response = daemon
+                .handle_rpc(rpc_request(
+                    1,
+                    "send_message_v2",
+                    json!({
+                        "id": id,
+                        "source": "src",
+                        "destination": "dst",
+                        "content": "hello",
+                        "method": method,
+                    }),
+                ))
+                .expect("send");
+            assert!(response.error.is_none());
+        };
+        {
+            let store = MessagesStore::open(db_path.as_path()).expect("open sqlite store");
+            let daemon = RpcDaemon::with_store(store, "watch-node".to_string());
+            send(&daemon, "pending", None);
+            send(&daemon, "pending-propagated", Some("propagated"));
+            send(&daemon, "delivered", None);
+            daemon
+                .handle_rpc(rpc_request(
+                    2,
+                    "record_receipt",
+                    json!({ "message_id": "delivered", "status": "delivered" }),
+                ))
+                .expect("record receipt");
+            send_scheduled(&daemon, "scheduled", now_millis_u64() + 3_600_000);
+        }
+
+        let bridge = Arc::new(CancellingBridge::default());
+        let store = MessagesStore::open(db_path.as_path()).expect("reopen sqlite store");
+        let daemon = RpcDaemon::with_store_and_bridge(store, "watch-node".into(), bridge.clone());
+        {
+            let watches = daemon.receipt_watches.lock().expect("watches");
+            let mut watched = watches.keys().cloned().collect::<Vec<_>>();
+            watched.sort();
+            assert_eq!(watched, ["pending", "pending-propagated"]);
+            assert!(watches["pending-propagated"].propagated);
+            assert!(!watches["pending"].propagated);
+        }
+
+        let timeout_ms = daemon.sdk_receipt_timeout_ms(false);
+        assert_eq!(daemon.expire_stale_receipts_at(now_millis_u64() + timeout_ms), 1);
+        assert_eq!(
+            receipt_status(&daemon, "pending").as_deref(),
+            Some("failed: timeout waiting for receipt")
+        );
+        assert_eq!(bridge.cancelled.lock().expect("cancelled").as_slice(), ["pending"]);
+        let _ = std::fs::remove_file(db_path);
+    }
+
+    #[test]
+    fn idempotency_keys_deduplicate_sends_across_restarts() {
+        let (db_path, _) = event_persist_paths("idempotency-restart");
+        let send = |daemon: &RpcDaemon, id: &str, content: &str| {
+            daemon
+                .handle_rpc(rpc_request(
+                    1,
+                    "sdk_send_v2",
+                    json!({
+                        "id": id,
+                        "source": "src",
+                        "destination": "dst",
+                        "content": content,
+                        "fields": { "_sdk": { "idempotency_key": "idem-1" } },
+                    }),
+                ))
+                .expect("send")
+        };
+        {
+            let store = MessagesStore::open(db_path.as_path()).expect("open sqlite store");
+            let daemon = RpcDaemon::with_store(store, "idem-node".to_string());
+            let first = send(&daemon, "first", "hello").result.expect("result");
+            assert_eq!(first["message_id"], json!("first"));
+            assert_eq!(first["deduplicated"], json!(false));
+        }
+
+        let store = MessagesStore::open(db_path.as_path()).expect("reopen sqlite store");
+        let daemon = RpcDaemon::with_store(store, "idem-node".to_string());
+        let replay = send(&daemon, "second", "hello").result.expect("result");
+        assert_eq!(replay["message_id"], json!("first"));
+        assert_eq!(replay["deduplicated"], json!(true));
+        assert!(daemon.store.get_message("second").expect("load").is_none());
+        let status = daemon
+            .handle_rpc(rpc_request(2, "sdk_status_v2", json!({ "message_id": "first" })))
+            .expect("status")
+            .result
+            .expect("result");
+        assert_eq!(status["deduplicated"], json!(true));
+
+        let conflict = send(&daemon, "third", "changed");
+        assert_eq!(conflict.error.expect("error").code, "SDK_VALIDATION_IDEMPOTENCY_CONFLICT");
+
+        daemon
+            .handle_rpc(rpc_request(3, "sdk_configure_v2", json!({
+                "expected_revision": 0,
+                "patch": { "idempotency_ttl_ms": 0 },
+            })))
+            .expect("configure");
+        let expired = send(&daemon, "fourth", "hello").result.expect("result");
+        assert_eq!(expired["message_id"], json!("fourth"));
+        assert_eq!(expired["deduplicated"], json!(false));
+        let _ = std::fs::remove_file(db_path);
+    }
+
+    fn send_with_attachments(daemon: &RpcDaemon, id: &str, sizes: &[usize]) -> RpcResponse {
+        let attachments: Vec<JsonValue> = sizes
+            .iter()
+            .enumerate()
+            .map(|(index, size)| {
+                json!({ "name": format!("file-{index}.bin"), "data": vec![7u8; *size] })
+            })
+            .collect();
+        daemon
+            .handle_rpc(rpc_request(
+                81,
+                "send_message_v2",
+                json!({
+                    "id": id,
+                    "source": "src",
+                    "destination": "dst",
+                    "title": "",
+                    "content": "hello",
+                    "fields": { "attachments": attachments },
+                }),
+            ))
+            .expect("send")
+    }
+
+    #[test]
+    fn outbound_attachments_respect_policy_limits_before_storing() {
+        let daemon = RpcDaemon::test_instance();
+        assert!(send_titled(&daemon, "no-attachments", "plain").error.is_none());
+        assert!(send_with_attachments(&daemon, "defaults", &[64, 64]).error.is_none());
+
+        daemon
+            .handle_rpc(rpc_request(
+                1,
+                "set_delivery_policy",
+                json!({ "max_attachment_bytes": 100, "max_attachments": 2 }),
+            ))
+            .expect("set delivery policy");
+
+        assert!(send_with_attachments(&daemon, "at-limit", &[60, 40]).error.is_none());
+
+        let error = send_with_attachments(&daemon, "too-large", &[60, 41])
+            .error
+            .expect("size error");
+        assert_eq!(error.code, "SDK_VALIDATION_ATTACHMENT_LIMIT_EXCEEDED");
+        let details = error.details.expect("details");
+        assert_eq!(details["attachment"], json!("file-1.bin"));
+        assert_eq!(details["limit_name"], json!("max_attachment_bytes"));
+        assert_eq!(details["limit"], json!(100));
+        assert_eq!(details["observed"], json!(101));
+        assert!(daemon.store.get_message("too-large").expect("load").is_none());
+
+        let error = send_with_attachments(&daemon, "too-many", &[1, 1, 1])
+            .error
+            .expect("count error");
+        assert_eq!(error.code, "SDK_VALIDATION_ATTACHMENT_LIMIT_EXCEEDED");
+        let details = error.details.expect("details");
+        assert_eq!(details["attachment"], json!("file-2.bin"));
+        assert_eq!(details["limit_name"], json!("max_attachments"));
+        assert_eq!(details["observed"], json!(3));
+        assert!(daemon.store.get_message("too-many").expect("load").is_none());
+    }
+
+    fn logs_tail(daemon: &RpcDaemon, params: JsonValue) -> JsonValue {
+        let response =
+            daemon.handle_rpc(rpc_request(70, "logs_tail", params)).expect("logs_tail");
+        assert!(response.error.is_none());
+        response.result.expect("result")
+    }
+
+    #[test]
+    fn logs_tail_returns_only_new_lines_and_advances_offset() {
+        let daemon = RpcDaemon::test_instance();
+        daemon.record_log_line("line-0");
+        daemon.record_log_line("line-1");
+
+        let first = logs_tail(&daemon, json!({}));
+        assert_eq!(first["lines"], json!(["line-0", "line-1"]));
+        assert_eq!(first["next_offset"], json!(2));
+
+        let idle = logs_tail(&daemon, json!({ "after_offset": 2 }));
+        assert_eq!(idle["lines"], json!([]));
+        assert_eq!(idle["next_offset"], json!(2));
+
+        daemon.record_log_line("line-2");
+        daemon.record_log_line("line-3");
+        daemon.record_log_line("line-4");
+        let limited = logs_tail(&daemon, json!({ "after_offset": 2, "max_lines": 2 }));
+        assert_eq!(limited["lines"], json!(["line-2", "line-3"]));
+        assert_eq!(limited["next_offset"], json!(4));
+        let rest = logs_tail(&daemon, json!({ "after_offset": 4 }));
+        assert_eq!(rest["lines"], json!(["line-4"]));
+        assert_eq!(rest["next_offset"], json!(5));
+        assert_eq!(rest["missed_lines"], json!(0));
+    }
+
+    #[test]
+    fn logs_tail_reports_lines_evicted_before_offset() {
+        let daemon = RpcDaemon::test_instance();
+        for index in 0..DAEMON_LOG_CAPACITY + 3 {
+            daemon.record_log_line(format!("line-{index}"));
+        }
+
+        let result = logs_tail(&daemon, json!({ "after_offset": 1, "max_lines": 1 }));
+        assert_eq!(result["first_offset"], json!(3));
+        assert_eq!(result["missed_lines"], json!(2));
+        assert_eq!(result["lines"], json!(["line-3"]));
+        assert_eq!(result["next_offset"], json!(4));
+    }
+
+    #[test]
+    fn logs_tail_filters_by_level_and_treats_unknown_lines_as_info() {
+        let daemon = RpcDaemon::test_instance();
+        for line in [
+            "DEBUG cache warmed",
+            "[daemon] rx data len=3",
+            "2026-01-01T00:00:00Z WARN interface flapping",
+            "[error] bridge send failed",
+            "TRACE frame bytes",
+        ] {
+            daemon.record_log_line(line);
+        }
+
+        let warn = logs_tail(&daemon, json!({ "log_level": "warn" }));
+        assert_eq!(
+            warn["lines"],
+            json!(["2026-01-01T00:00:00Z WARN interface flapping", "[error] bridge send failed"])
+        );
+        assert_eq!(warn["next_offset"], json!(5));
+
+        let info = logs_tail(&daemon, json!({ "log_level": "INFO", "after_offset": 0, "max_lines": 2 }));
+        assert_eq!(
+            info["lines"],
+            json!(["[daemon] rx data len=3", "2026-01-01T00:00:00Z WARN interface flapping"])
+        );
+        assert_eq!(info["next_offset"], json!(3));
+
+        let trace = logs_tail(&daemon, json!({ "log_level": "TRACE" }));
+        assert_eq!(trace["lines"], logs_tail(&daemon, json!({}))["lines"]);
+        assert_eq!(trace["lines"].as_array().map(Vec::len), Some(5));
+
+        let invalid = daemon
+            .handle_rpc(rpc_request(71, "logs_tail", json!({ "log_level": "loud" })))
+            .expect("logs_tail");
+        assert_eq!(invalid.error.expect("error").code, "SDK_VALIDATION_INVALID_ARGUMENT");
+    }
+
+    #[derive(Default)]
+    struct RecordingPingBridge {
+        pings: Mutex<Vec<PeerPingRequest>>,
+    }
+
+    impl OutboundBridge for RecordingPingBridge {
+        fn deliver(
+            &self,
+            _record: &MessageRecord,
+            _options: &OutboundDeliveryOptions,
+        ) -> Result<(), std::io::Error> {
+            Ok(())
+        }
+
+        fn ping(&self, request: &PeerPingRequest) -> Result<(), std::io::Error> {
+            self.pings.lock().expect("pings").push(request.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn peer_ping_requires_a_transport_bridge() {
+        let daemon = RpcDaemon::test_instance();
+        let response = daemon
+            .handle_rpc(rpc_request(1, "peer_ping", json!({ "peer": "peer-a" })))
+            .expect("peer_ping");
+        assert_eq!(response.error.expect("error").code, "SDK_CAPABILITY_DISABLED");
+    }
+
+    #[test]
+    fn peer_ping_reports_reachable_no_path_and_no_response() {
+        let bridge = Arc::new(RecordingPingBridge::default());
+        let store = MessagesStore::in_memory().expect("store");
+        let daemon =
+            RpcDaemon::with_store_and_bridge(store, "test-identity".into(), bridge.clone());
+
+        let cases = [
+            (PeerPingOutcome::Reachable, Some(42), "reachable", json!(42)),
+            (PeerPingOutcome::NoPath, None, "no_path", JsonValue::Null),
+            (PeerPingOutcome::NoResponse, Some(42), "no_response", JsonValue::Null),
+        ];
+        for (index, (outcome, rtt_ms, label, expected_rtt)) in cases.into_iter().enumerate() {
+            let started = daemon
+                .handle_rpc(rpc_request(
+                    index as u64,
+                    "peer_ping",
+                    json!({ "peer": "peer-a", "probe": true, "timeout_ms": 500 }),
+                ))
+                .expect("peer_ping")
+                .result
+                .expect("result");
+            assert_eq!(started["outcome"], JsonValue::Null);
+            let ping_id = started["ping_id"].as_str().expect("ping_id").to_string();
+            let request = bridge.pings.lock().expect("pings").last().cloned().expect("ping");
+            assert_eq!(request.ping_id, ping_id);
+            assert!(request.probe);
+            assert_eq!(request.timeout_ms, 500);
+
+            while daemon.take_event().is_some() {}
+            daemon.record_peer_ping_result(&ping_id, outcome, rtt_ms);
+            let event = daemon.take_event().expect("peer_ping event");
+            assert_eq!(event.event_type, "peer_ping");
+            assert_eq!(event.payload["outcome"], json!(label));
+
+            let finished = daemon
+                .handle_rpc(rpc_request(10, "peer_ping", json!({ "ping_id": ping_id })))
+                .expect("peer_ping")
+                .result
+                .expect("result");
+            assert_eq!(finished["outcome"], json!(label));
+            assert_eq!(finished["rtt_ms"], expected_rtt);
+        }
+    }
+
+    fn send_scheduled(daemon: &RpcDaemon, id: &str, scheduled_ts_ms: u64) -> JsonValue {
+        daemon
+            .handle_rpc(rpc_request(
+                1,
+                "send_message_v2",
+                json!({
+                    "id": id,
+                    "source": "src",
+                    "destination": "dst",
+                    "content": "later",
+                    "scheduled_ts_ms": scheduled_ts_ms,
+                }),
+            ))
+            .expect("send")
+            .result
+            .expect("result")
+    }
+
+    fn receipt_status(daemon: &RpcDaemon, id: &str) -> Option<String> {
+        daemon.store.get_message(id).expect("get").and_then(|record| record.receipt_status)
+    }
+
+    #[test]
+    fn scheduled_send_holds_until_due_and_can_be_cancelled() {
+        let daemon = RpcDaemon::test_instance();
+        let far_future = now_millis_u64() + 3_600_000;
+        let result = send_scheduled(&daemon, "sched-1", far_future);
+        assert_eq!(result["scheduled_ts_ms"], json!(far_future));
+        assert_eq!(receipt_status(&daemon, "sched-1").as_deref(), Some("scheduled"));
+        assert_eq!(daemon.dispatch_due_scheduled_sends(), 0);
+
+        let cancel = daemon
+            .handle_rpc(rpc_request(
+                2,
+                "sdk_cancel_message_v2",
+                json!({ "message_id": "sched-1" }),
+            ))
+            .expect("cancel")
+            .result
+            .expect("result");
+        assert_eq!(cancel["result"], json!("Accepted"));
+        assert_eq!(receipt_status(&daemon, "sched-1").as_deref(), Some("cancelled"));
+        assert!(daemon.scheduled_sends.lock().expect("scheduled").is_empty());
+
+        let soon = now_millis_u64() + 200;
+        send_scheduled(&daemon, "sched-2", soon);
+        assert_eq!(daemon.dispatch_due_scheduled_sends(), 0);
+        std::thread::sleep(std::time::Duration::from_millis(220));
+        assert_eq!(daemon.dispatch_due_scheduled_sends(), 1);
+        assert_eq!(receipt_status(&daemon, "sched-2").as_deref(), Some("sent: direct"));
+    }
+
+    #[test]
+    fn scheduled_sends_survive_a_restart() {
+        let (db_path, _) = event_persist_paths("scheduled-restart");
+        let far_future = now_millis_u64() + 3_600_000;
+        let soon = now_millis_u64() + 500;
+        {
+            let store = MessagesStore::open(db_path.as_path()).expect("open sqlite store");
+            let daemon = RpcDaemon::with_store(store, "sched-node".to_string());
+            send_scheduled(&daemon, "sched-later", far_future);
+            send_scheduled(&daemon, "sched-soon", soon);
+            send_scheduled(&daemon, "sched-cancelled", far_future);
+            daemon
+                .handle_rpc(rpc_request(
+                    2,
+                    "sdk_cancel_message_v2",
+                    json!({ "message_id": "sched-cancelled" }),
+                ))
+                .expect("cancel");
+        }
+
+        let store = MessagesStore::open(db_path.as_path()).expect("reopen sqlite store");
+        let daemon = RpcDaemon::with_store(store, "sched-node".to_string());
+        assert_eq!(daemon.scheduled_sends.lock().expect("scheduled").len(), 2);
+        std::thread::sleep(
+            std::time::Duration::from_millis(soon.saturating_sub(now_millis_u64()))
+                + std::time::Duration::from_millis(20),
+        );
+        assert_eq!(daemon.dispatch_due_scheduled_sends(), 1);
+        assert_eq!(receipt_status(&daemon, "sched-soon").as_deref(), Some("sent: direct"));
+        assert_eq!(receipt_status(&daemon, "sched-later").as_deref(), Some("scheduled"));
+        let persisted = daemon.store.list_scheduled_sends().expect("list");
+        assert_eq!(
+            persisted.iter().map(|entry| entry.message_id.as_str()).collect::<Vec<_>>(),
+            ["sched-later"]
+        );
+        assert_eq!(persisted[0].due_ms, far_future);
+        let _ = std::fs::remove_file(db_path);
+    }
+
+    #[test]
+    fn unreadable_scheduled_send_is_failed_on_restore() {
+        let (db_path, _) = event_persist_paths("scheduled-unreadable");
+        {
+            let store = MessagesStore::open(db_path.as_path()).expect("open sqlite store");
+            let daemon = RpcDaemon::with_store(store, "sched-node".to_string());
+            send_scheduled(&daemon, "sched-broken", now_millis_u64() + 3_600_000);
+            daemon
+                .store
+                .put_scheduled_send(&ScheduledSendRecord {
+                    message_id: "sched-broken".to_string(),
+                    due_ms: 1,
+                    method: None,
+                    stamp_cost: None,
+                    options: json!({ "stamp_cost": "not-a-number" }),
+                })
+                .expect("overwrite options");
+        }
+
+        let store = MessagesStore::open(db_path.as_path()).expect("reopen sqlite store");
+        let daemon = RpcDaemon::with_store(store, "sched-node".to_string());
+        assert!(daemon.scheduled_sends.lock().expect("scheduled").is_empty());
+        assert!(daemon.store.list_scheduled_sends().expect("list").is_empty());
+        let status = receipt_status(&daemon, "sched-broken").expect("status");
+        assert!(status.starts_with("failed: stored delivery options unreadable"), "{status}");
+        let _ = std::fs::remove_file(db_path);
+    }
+
+    #[test]
+    fn scheduled_send_in_the_past_sends_immediately() {
+        let daemon = RpcDaemon::test_instance();
+        let result = send_scheduled(&daemon, "sched-past", 1);
+        assert_eq!(result["scheduled_ts_ms"], JsonValue::Null);
+        assert_eq!(receipt_status(&daemon, "sched-past").as_deref(), Some("sent: direct"));
+        assert!(daemon.scheduled_sends.lock().expect("scheduled").is_empty());
+    }
+
+    #[derive(Default)]
+    struct PathlessBridge {
+        delivered: Mutex<Vec<String>>,
+        path_requests: Mutex<Vec<(String, Duration)>>,
+    }
+
+    impl OutboundBridge for PathlessBridge {
+        fn deliver(
+            &self,
+            record: &MessageRecord,
+            _options: &OutboundDeliveryOptions,
+        ) -> Result<(), std::io::Error> {
+            self.delivered.lock().expect("delivered").push(record.id.clone());
+            Ok(())
+        }
+
+        fn await_path(&self, destination: &str, timeout: Duration) -> Result<bool, std::io::Error> {
+            self.path_requests.lock().expect("path requests").push((destination.into(), timeout));
+            Ok(false)
+        }
+    }
+
+    #[test]
+    fn failed_sends_do_not_use_up_the_destination_rate_limit() {
+        let bridge = Arc::new(PathlessBridge::default());
+        let store = MessagesStore::in_memory().expect("store");
+        let daemon = RpcDaemon::with_store_and_bridge(store, "test-identity".into(), bridge);
+        let configured = daemon
+            .handle_rpc(rpc_request(
+                1,
+                "sdk_configure_v2",
+                json!({ "expected_revision": 0, "patch": { "per_destination_rate_limit": 1 } }),
+            ))
+            .expect("configure");
+        assert!(configured.error.is_none());
+        let send = |id: &str, fail_fast: bool| {
+            daemon
+                .handle_rpc(rpc_request(
+                    2,
+                    "send_message_v2",
+                    json!({
+                        "id": id,
+                        "source": "src",
+                        "destination": "dst",
+                        "content": "hello",
+                        "fail_fast_no_path": fail_fast
+                    }),
+                ))
+                .expect("send")
+        };
+
+        for id in ["rate-no-path-1", "rate-no-path-2"] {
+            assert_eq!(send(id, true).error.expect("no path").code, "SDK_RUNTIME_NO_PATH");
+        }
+        assert!(send("rate-sent", false).error.is_none());
+        assert_eq!(
+            send("rate-limited", false).error.expect("limited").code,
+            "SDK_RUNTIME_RATE_LIMITED"
+        );
+    }
+
+    #[test]
+    fn fail_fast_send_without_path_returns_no_path_and_is_not_queued() {
+        let bridge = Arc::new(PathlessBridge::default());
+        let store = MessagesStore::in_memory().expect("store");
+        let daemon =
+            RpcDaemon::with_store_and_bridge(store, "test-identity".into(), bridge.clone());
+        let send = |id: &str, fail_fast: bool| {
+            daemon
+                .handle_rpc(rpc_request(
+                    1,
+                    "send_message_v2",
+                    json!({
+                        "id": id,
+                        "source": "src",
+                        "destination": "dst",
+                        "content": "hello",
+                        "fail_fast_no_path": fail_fast
+                    }),
+                ))
+                .expect("send")
+        };
+
+        let rejected = send("fast-1", true);
+        assert_eq!(rejected.error.expect("error").code, "SDK_RUNTIME_NO_PATH");
+        assert!(daemon.store.get_message("fast-1").expect("load").is_none());
+        assert!(bridge.delivered.lock().expect("delivered").is_empty());
+        assert_eq!(
+            bridge.path_requests.lock().expect("path requests").as_slice(),
+            &[("dst".to_string(), FAIL_FAST_PATH_TIMEOUT)]
+        );
+
+        let queued = send("slow-1", false);
+        assert!(queued.error.is_none());
+        assert!(daemon.store.get_message("slow-1").expect("load").is_some());
+        assert_eq!(bridge.delivered.lock().expect("delivered").as_slice(), ["slow-1"]);
+    }
+
+    struct DeliverOnlyBridge;
+
+    impl OutboundBridge for DeliverOnlyBridge {
+        fn deliver(
+            &self,
+            _record: &MessageRecord,
+            _options: &OutboundDeliveryOptions,
+        ) -> Result<(), std::io::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn fail_fast_send_without_path_lookup_reports_capability_disabled() {
+        let store = MessagesStore::in_memory().expect("store");
+        let daemon =
+            RpcDaemon::with_store_and_bridge(store, "test-identity".into(), Arc::new(DeliverOnlyBridge));
+        let request = rpc_request(
+            1,
+            "sdk_send_v2",
+            json!({
+                "id": "fast-1",
+                "source": "src",
+                "destination": " dst ",
+                "content": "hello",
+                "fail_fast_no_path": true
+            }),
+        );
+        assert_eq!(fail_fast_path_destination(&request).as_deref(), Some("dst"));
+
+        let response = daemon.handle_rpc(request).expect("send");
+        assert_eq!(response.error.expect("error").code, "SDK_CAPABILITY_DISABLED");
+        assert!(daemon.store.get_message("fast-1").expect("load").is_none());
+
+        let queued = rpc_request(
+            2,
+            "sdk_send_v2",
+            json!({ "source": "src", "destination": "dst", "content": "hello" }),
+        );
+        assert_eq!(fail_fast_path_destination(&queued), None);
+    }
+
+    #[test]
+    fn stamp_status_reports_disabled_then_costs_respecting_propagation_minimum() {
+        let daemon = RpcDaemon::test_instance();
+        let stamp_status = |id| {
+            daemon
+                .handle_rpc(rpc_request(id, "stamp_status", json!({})))
+                .expect("stamp status")
+                .result
+                .expect("result")["stamp_status"]
+                .clone()
+        };
+        assert_eq!(stamp_status(1), json!({ "enabled": false }));
+
+        daemon
+            .handle_rpc(rpc_request(
+                2,
+                "propagation_enable",
+                json!({ "enabled": true, "target_cost": 4 }),
+            ))
+            .expect("enable propagation");
+        daemon
+            .handle_rpc(rpc_request(3, "stamp_policy_set", json!({ "flexibility": 3 })))
+            .expect("set flexibility");
+        let status = stamp_status(4);
+        assert_eq!(status["enabled"], json!(true));
+        assert_eq!(status["propagation_stamp_cost"], json!(PROPAGATION_COST_MIN));
+        assert_eq!(status["flexibility"], json!(3));
+        assert_eq!(status["peering_cost"], json!(PEERING_COST));
+        assert_eq!(
+            status["accepted_range"],
+            json!([PROPAGATION_COST_MIN - 3, PROPAGATION_COST_MIN])
+        );
+
+        daemon
+            .handle_rpc(rpc_request(
+                5,
+                "propagation_enable",
+                json!({ "enabled": true, "target_cost": 20 }),
+            ))
+            .expect("raise cost");
+        let status = stamp_status(6);
+        assert_eq!(status["propagation_stamp_cost"], json!(20));
+        assert_eq!(status["accepted_range"], json!([17, 20]));
+    }
+
+    #[test]
+    fn whoami_reports_local_addressing_or_not_ready() {
+        let daemon = RpcDaemon::test_instance();
+        daemon.set_delivery_destination_hash(Some("delivery-hash".into()));
+        daemon.set_local_display_name(Some("  Base Camp ".into()));
+        let whoami = daemon
+            .handle_rpc(rpc_request(1, "whoami", json!({})))
+            .expect("whoami")
+            .result
+            .expect("result");
+        assert_eq!(
+            whoami,
+            json!({
+                "identity_hash": "test-identity",
+                "delivery_destination_hash": "delivery-hash",
+                "display_name": "Base Camp",
+                "profile": "desktop-full",
+            })
+        );
+
+        let unstarted = RpcDaemon::test_instance_with_identity("");
+        let response = unstarted.handle_rpc(rpc_request(2, "whoami", json!({}))).expect("whoami");
+        assert!(response.result.is_none());
+        assert_eq!(response.error.expect("error").code, "SDK_RUNTIME_INVALID_STATE");
+    }
+
+    #[test]
+    fn adaptive_stamp_cost_follows_propagation_store_fill() {
+        let daemon = RpcDaemon::test_instance();
+        let stamp_status = |id| {
+            daemon
+                .handle_rpc(rpc_request(id, "stamp_status", json!({})))
+                .expect("stamp status")
+                .result
+                .expect("result")["stamp_status"]
+                .clone()
+        };
+        let fill = |bytes: usize| {
+            let mut payloads = daemon.propagation_payloads.lock().expect("payloads");
+            payloads.clear();
+            payloads.insert(
+                "bulk".to_string(),
+                PropagationPayload { payload_hex: "00".repeat(bytes), expires_at_ms: None },
+            );
+        };
+        daemon
+            .handle_rpc(rpc_request(
+                1,
+                "propagation_enable",
+                json!({ "enabled": true, "target_cost": 16 }),
+            ))
+            .expect("enable propagation");
+        daemon
+            .handle_rpc(rpc_request(2, "stamp_policy_set", json!({ "flexibility": 3 })))
+            .expect("set flexibility");
+        fill(SYNC_LIMIT as usize * 1000);
+        let fixed = stamp_status(3);
+        assert!(fixed.get("adaptive_cost").is_none());
+        assert_eq!(fixed["accepted_range"], json!([13, 16]));
+        assert_eq!(daemon.required_propagation_stamp_cost(), 16);
+
+        daemon
+            .handle_rpc(rpc_request(4, "stamp_policy_set", json!({ "mode": "adaptive" })))
+            .expect("set adaptive");
+        let full = stamp_status(5);
+        assert_eq!(full["mode"], json!("adaptive"));
+        assert_eq!(full["propagation_stamp_cost"], json!(16));
+        assert_eq!(full["adaptive_cost"], json!(19));
+        assert_eq!(full["accepted_range"], json!([16, 19]));
+
+        fill((PROPAGATION_LIMIT + SYNC_LIMIT) as usize * 500);
+        let half = stamp_status(6)["adaptive_cost"].as_u64().expect("adaptive cost");
+        assert!((14..19).contains(&half), "{half}");
+
+        fill(PROPAGATION_LIMIT as usize * 1000);
+        assert_eq!(stamp_status(7)["adaptive_cost"], json!(PROPAGATION_COST_MIN));
+        assert_eq!(adaptive_stamp_cost(0, 0, usize::MAX), PROPAGATION_COST_MIN);
+    }
+
+    #[derive(Default)]
+    struct StampCostBridge {
+        advertised: Mutex<Vec<(u32, u32)>>,
+    }
+
+    impl AnnounceBridge for StampCostBridge {
+        fn announce_now(&self) -> Result<(), std::io::Error> {
+            Ok(())
+        }
+
+        fn set_propagation_stamp_cost(
+            &self,
+            cost: u32,
+            flexibility: u32,
+        ) -> Result<(), std::io::Error> {
+            self.advertised.lock().expect("advertised").push((cost, flexibility));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn adaptive_stamp_cost_is_enforced_on_ingest_and_advertised() {
+        let bridge = Arc::new(StampCostBridge::default());
+        let store = MessagesStore::in_memory().expect("store");
+        let daemon = RpcDaemon::with_store_and_bridges(
+            store,
+            "test-identity".into(),
+            None,
+            Some(bridge.clone()),
+        );
+        let ingest = |id: u64, lxmf_data: &[u8], value: u32| {
+            daemon
+                .handle_rpc(rpc_request(
+                    id,
+                    "propagation_ingest",
+                    json!({
+                        "payload_hex": hex::encode(stamped_propagation_payload(lxmf_data, value))
+                    }),
+                ))
+                .expect("ingest")
+        };
+        daemon.set_propagation_state(true, None, PROPAGATION_COST_MIN);
+        daemon
+            .handle_rpc(rpc_request(
+                1,
+                "stamp_policy_set",
+                json!({ "mode": "adaptive", "flexibility": 12 }),
+            ))
+            .expect("set adaptive");
+        assert_eq!(bridge.advertised.lock().expect("advertised").last(), Some(&(13, 12)));
+
+        // An empty store asks for the floor, so a cost-1 stamp is within flexibility.
+        assert!(ingest(2, b"first", 1).error.is_none());
+
+        daemon.propagation_payloads.lock().expect("payloads").insert(
+            "bulk".to_string(),
+            PropagationPayload {
+                payload_hex: "00".repeat(3_580_000),
+                expires_at_ms: None,
+            },
+        );
+        // A third of the way to a full sync the node asks for 17, accepting 5.
+        let rejected = ingest(3, b"second", 3);
+        assert_eq!(rejected.error.expect("error").code, "SDK_VALIDATION_INVALID_STAMP");
+        assert_eq!(daemon.propagation_state.lock().expect("propagation").rejected_stamps, 1);
+        assert!(ingest(4, b"third", 5).error.is_none());
+        assert_eq!(bridge.advertised.lock().expect("advertised").last(), Some(&(17, 12)));
+    }
+
+    #[test]
+    fn health_reports_interface_links_queue_depth_and_last_announce() {
+        let store = MessagesStore::in_memory().expect("store");
+        let daemon = RpcDaemon::with_store_and_bridge(
+            store,
+            "test-identity".into(),
+            Arc::new(RecordingPingBridge::default()),
+        );
+        let iface = |name: &str, enabled: bool| InterfaceRecord {
+            kind: "tcp_client".into(),
+            enabled,
+            host: Some("127.0.0.1".into()),
+            port: Some(4242),
+            name: Some(name.into()),
+        };
+        daemon.replace_interfaces(vec![iface("a", true), iface("b", true), iface("c", false)]);
+        let health = |id| {
+            daemon
+                .handle_rpc(rpc_request(id, "health", json!({})))
+                .expect("health")
+                .result
+                .expect("result")
+        };
+
+        let result = health(1);
+        assert_eq!(result["transport_up"], json!(true));
+        assert_eq!(result["interfaces_up"], json!(2));
+        assert_eq!(result["interfaces_total"], json!(2));
+        assert_eq!(result["propagation_sync_state"], json!("idle"));
+        assert_eq!(result["outbound_queue_depth"], json!(0));
+        assert_eq!(result["last_announce_ts_ms"], JsonValue::Null);
+
+        daemon.set_interface_link_up("b", false);
+        daemon.schedule_announce_for_test(7);
+        let result = health(2);
+        assert_eq!(result["interfaces_up"], json!(1));
+        assert!(result["last_announce_ts_ms"].as_u64().is_some());
+
+        daemon.set_interface_link_up("b", true);
+        assert_eq!(health(3)["interfaces_up"], json!(2));
+    }
+
+    #[test]
+    fn list_interfaces_reports_traffic_counters_and_idle_secs() {
+        let daemon = RpcDaemon::test_instance();
+        let iface = |name: &str| InterfaceRecord {
+            kind: "tcp_client".into(),
+            enabled: true,
+            host: Some("127.0.0.1".into()),
+            port: Some(4242),
+            name: Some(name.into()),
+        };
+        daemon.replace_interfaces(vec![iface("busy"), iface("quiet")]);
+        let last_activity_secs = now_i64() as u64 - 30;
+        daemon.set_interface_traffic(
+            "busy",
+            InterfaceTraffic {
+                bytes_in: 512,
+                bytes_out: 128,
+                packets_in: 4,
+                packets_out: 2,
+                last_activity_secs: Some(last_activity_secs),
+            },
+        );
+
+        let result = daemon
+            .handle_rpc(rpc_request(1, "list_interfaces", json!({})))
+            .expect("list interfaces")
+            .result
+            .expect("result");
+        let busy = &result["interfaces"][0];
+        assert_eq!(busy["name"], json!("busy"));
+        assert_eq!(busy["bytes_in"], json!(512));
+        assert_eq!(busy["bytes_out"], json!(128));
+        assert_eq!(busy["packets_in"], json!(4));
+        assert_eq!(busy["packets_out"], json!(2));
+        assert!(busy["idle_secs"].as_u64().is_some_and(|idle| idle >= 30));
+        let quiet = &result["interfaces"][1];
+        assert_eq!(quiet["bytes_in"], json!(0));
+        assert_eq!(quiet["packets_out"], json!(0));
+        assert_eq!(quiet["idle_secs"], JsonValue::Null);
+    }
+
+    #[test]
+    fn interface_link_state_events_fire_once_per_transition() {
+        let daemon = RpcDaemon::test_instance();
+        while daemon.take_event().is_some() {}
+
+        daemon.set_interface_link_state("ble0", InterfaceLinkState::Connecting, None);
+        daemon.set_interface_link_state("ble0", InterfaceLinkState::Connecting, None);
+        daemon.set_interface_link_state("ble0", InterfaceLinkState::Connected, None);
+        daemon.set_interface_link_state(
+            "ble0",
+            InterfaceLinkState::Disconnected,
+            Some("gatt disconnected"),
+        );
+        daemon.set_interface_link_state(
+            "ble0",
+            InterfaceLinkState::Disconnected,
+            Some("gatt disconnected"),
+        );
+
+        let mut events = Vec::new();
+        while let Some(event) = daemon.take_event() {
+            if event.event_type == "interface_link_state" {
+                events.push(event.payload);
+            }
+        }
+        let states = events.iter().map(|payload| payload["state"].clone()).collect::<Vec<_>>();
+        assert_eq!(states, vec![json!("connecting"), json!("connected"), json!("disconnected")]);
+        assert_eq!(events[0]["interface_name"], json!("ble0"));
+        assert_eq!(events[2]["detail"], json!("gatt disconnected"));
+    }
+
+    #[test]
+    fn peers_announcing_identical_names_get_hash_suffixed_display_names() {
+        let daemon = RpcDaemon::test_instance();
+        for (id, peer, name) in [
+            (1, "aabbcc0011", "Relay"),
+            (2, "aabbcc2233", " relay "),
+            (3, "ffee000000", "Base"),
+        ] {
+            daemon
+                .handle_rpc(rpc_request(
+                    id,
+                    "announce_received",
+                    json!({ "peer": peer, "timestamp": id, "name": name }),
+                ))
+                .expect("announce");
+        }
+
+        let peers = daemon
+            .handle_rpc(rpc_request(4, "list_peers", json!({ "name_suffix_len": 4 })))
+            .expect("list peers")
+            .result
+            .expect("result")["peers"]
+            .clone();
+        let by_peer = |hash: &str| {
+            peers.as_array().expect("peers").iter().find(|p| p["peer"] == hash).cloned().unwrap()
+        };
+        assert_eq!(by_peer("aabbcc0011")["name"], json!("Relay"));
+        assert_eq!(by_peer("aabbcc0011")["display_name"], json!("Relay (aabbcc0)"));
+        assert_eq!(by_peer("aabbcc2233")["display_name"], json!("relay (aabbcc2)"));
+        assert_eq!(by_peer("aabbcc2233")["name_conflict"], json!(true));
+        assert_eq!(by_peer("ffee000000")["display_name"], json!("Base"));
+        assert_eq!(by_peer("ffee000000")["name_conflict"], json!(false));
+
+        let described = daemon
+            .handle_rpc(rpc_request(5, "peer_describe", json!({ "peer": "aabbcc0011" })))
+            .expect("describe")
+            .result
+            .expect("result")["peer"]
+            .clone();
+        assert_eq!(described["display_name"], json!("Relay (aabbcc0)"));
+        assert_eq!(described["conflicting_peers"], json!(["aabbcc2233"]));
+
+        let err = daemon
+            .handle_rpc(rpc_request(6, "peer_describe", json!({ "peer": "missing" })))
+            .expect("peer_describe")
+            .error
+            .expect("unknown peer");
+        assert_eq!(err.code, "SDK_RUNTIME_NOT_FOUND");
+    }
+
+    #[test]
+    fn revoked_tickets_leave_the_list_and_block_sends_that_reference_them() {
+        let daemon = RpcDaemon::test_instance();
+        let generated = daemon
+            .handle_rpc(rpc_request(
+                1,
+                "ticket_generate",
+                json!({ "destination": "peer-a", "ttl_secs": 600 }),
+            ))
+            .expect("generate")
+            .result
+            .expect("result");
+        let ticket = generated["ticket"].as_str().expect("ticket").to_string();
+
+        let listed = daemon
+            .handle_rpc(rpc_request(2, "tickets_list", json!({})))
+            .expect("list")
+            .result
+            .expect("result");
+        assert_eq!(listed["tickets"][0]["ticket"], json!(ticket));
+        assert_eq!(listed["tickets"][0]["issuer"], json!(daemon.local_delivery_hash()));
+        assert_eq!(listed["tickets"][0]["audience"], json!("peer-a"));
+        assert_eq!(listed["tickets"][0]["expires_at"], generated["expires_at"]);
+
+        let revoke = |id: u64, ticket: &str| {
+            daemon
+                .handle_rpc(rpc_request(id, "ticket_revoke", json!({ "ticket": ticket })))
+                .expect("revoke")
+                .result
+                .expect("result")
+        };
+        assert_eq!(revoke(3, &ticket)["revoked"], json!(true));
+        assert_eq!(revoke(4, &ticket)["revoked"], json!(false));
+        assert_eq!(revoke(5, "unknown-ticket")["revoked"], json!(false));
+        let listed = daemon
+            .handle_rpc(rpc_request(6, "tickets_list", json!({})))
+            .expect("list")
+            .result
+            .expect("result");
+        assert_eq!(listed["tickets"], json!([]));
+
+        let rejected = daemon
+            .handle_rpc(rpc_request(
+                7,
+                "send_message_v2",
+                json!({
+                    "id": "ticketed-1",
+                    "source": "src",
+                    "destination": "peer-a",
+                    "content": "hello",
+                    "ticket": ticket
+                }),
+            ))
+            .expect("send");
+        assert_eq!(rejected.error.expect("error").code, "SDK_SECURITY_TICKET_REVOKED");
+        assert!(daemon.store.get_message("ticketed-1").expect("load").is_none());
+    }
+
+    #[test]
+    fn ticket_revoke_and_send_compare_tickets_after_normalizing() {
+        let daemon = RpcDaemon::test_instance();
+        let ticket = daemon
+            .handle_rpc(rpc_request(
+                1,
+                "ticket_generate",
+                json!({ "destination": "peer-a", "ttl_secs": 60 }),
+            ))
+            .expect("generate")
+            .result
+            .expect("result")["ticket"]
+            .as_str()
+            .expect("ticket")
+            .to_string();
+
+        let revoked = daemon
+            .handle_rpc(rpc_request(
+                2,
+                "ticket_revoke",
+                json!({ "ticket": format!("  {}\n", ticket.to_ascii_uppercase()) }),
+            ))
+            .expect("revoke")
+            .result
+            .expect("result");
+        assert_eq!(revoked["revoked"], json!(true));
+        assert_eq!(revoked["ticket"], json!(ticket));
+
+        for (id, presented) in [("lower", ticket.clone()), ("upper", ticket.to_ascii_uppercase())] {
+            let rejected = daemon
+                .handle_rpc(rpc_request(
+                    3,
+                    "send_message_v2",
+                    json!({
+                        "id": id,
+                        "source": "src",
+                        "destination": "peer-a",
+                        "content": "hello",
+                        "ticket": format!(" {presented} ")
+                    }),
+                ))
+                .expect("send");
+            assert_eq!(rejected.error.expect("error").code, "SDK_SECURITY_TICKET_REVOKED");
+        }
+    }
+
+    #[test]
+    fn tickets_for_the_same_destination_are_listed_and_revoked_independently() {
+        let daemon = RpcDaemon::test_instance();
+        let generate = |id: u64, ttl_secs: u64| {
+            daemon
+                .handle_rpc(rpc_request(
+                    id,
+                    "ticket_generate",
+                    json!({ "destination": "peer-a", "ttl_secs": ttl_secs }),
+                ))
+                .expect("generate")
+                .result
+                .expect("result")["ticket"]
+                .as_str()
+                .expect("ticket")
+                .to_string()
+        };
+        let first = generate(1, 600);
+        let second = generate(2, 1_200);
+        assert_ne!(first, second);
+
+        let listed = daemon
+            .handle_rpc(rpc_request(3, "tickets_list", json!({})))
+            .expect("list")
+            .result
+            .expect("result");
+        assert_eq!(listed["tickets"][0]["ticket"], json!(first));
+        assert_eq!(listed["tickets"][1]["ticket"], json!(second));
+
+        let revoked = daemon
+            .handle_rpc(rpc_request(4, "ticket_revoke", json!({ "ticket": first })))
+            .expect("revoke")
+            .result
+            .expect("result");
+        assert_eq!(revoked["revoked"], json!(true));
+        let listed = daemon
+            .handle_rpc(rpc_request(5, "tickets_list", json!({})))
+            .expect("list")
+            .result
+            .expect("result");
+        assert_eq!(listed["tickets"].as_array().map(Vec::len), Some(1));
+        assert_eq!(listed["tickets"][0]["ticket"], json!(second));
+    }
+
+    #[test]
+    fn ticket_revocations_survive_a_restart() {
+        let (db_path, _) = event_persist_paths("ticket-revoke-restart");
+        let ticket = {
+            let store = MessagesStore::open(db_path.as_path()).expect("open sqlite store");
+            let daemon = RpcDaemon::with_store(store, "ticket-node".to_string());
+            let ticket = daemon
+                .handle_rpc(rpc_request(
+                    1,
+                    "ticket_generate",
+                    json!({ "destination": "peer-a", "ttl_secs": 600 }),
+                ))
+                .expect("generate")
+                .result
+                .expect("result")["ticket"]
+                .as_str()
+                .expect("ticket")
+                .to_string();
+            let revoked = daemon
+                .handle_rpc(rpc_request(2, "ticket_revoke", json!({ "ticket": ticket })))
+                .expect("revoke")
+                .result
+                .expect("result");
+            assert_eq!(revoked["revoked"], json!(true));
+            ticket
+        };
+
+        let store = MessagesStore::open(db_path.as_path()).expect("reopen sqlite store");
+        let daemon = RpcDaemon::with_store(store, "ticket-node".to_string());
+        let rejected = daemon
+            .handle_rpc(rpc_request(
+                3,
+                "send_message_v2",
+                json!({
+                    "id": "ticketed-after-restart",
+                    "source": "src",
+                    "destination": "peer-a",
+                    "content": "hello",
+                    "ticket": ticket
+                }),
+            ))
+            .expect("send");
+        assert_eq!(rejected.error.expect("error").code, "SDK_SECURITY_TICKET_REVOKED");
+        let _ = std::fs::remove_file(db_path);
+    }
+
+    #[test]
+    fn peers_import_merges_exported_bundle_without_clobbering_fresher_peers() {
+        let source = RpcDaemon::test_instance();
+        for (id, peer, timestamp, capabilities) in [
+            (1, "peer-a", 100, json!([])),
+            (2, "peer-b", 200, json!([])),
+            (3, "node-p", 300, json!(["propagation"])),
+        ] {
+            source
+                .handle_rpc(rpc_request(
+                    id,
+                    "announce_received",
+                    json!({
+                        "peer": peer,
+                        "timestamp": timestamp,
+                        "name": peer,
+                        "app_data_hex": "c0",
+                        "capabilities": capabilities
+                    }),
+                ))
+                .expect("announce");
+        }
+        let bundle = source
+            .handle_rpc(rpc_request(4, "peers_export", json!({})))
+            .expect("export")
+            .result
+            .expect("result")["bundle"]
+            .clone();
+        assert_eq!(bundle["version"], json!("peer_bundle_v1"));
+        assert_eq!(bundle["peers"].as_array().expect("peers").len(), 3);
+        assert_eq!(bundle["propagation_nodes"][0]["peer"], json!("node-p"));
+        assert_eq!(bundle["propagation_nodes"][0]["app_data_hex"], json!("c0"));
+
+        let target = RpcDaemon::test_instance();
+        target
+            .handle_rpc(rpc_request(
+                5,
+                "announce_received",
+                json!({ "peer": "peer-b", "timestamp": 900, "name": "fresh" }),
+            ))
+            .expect("announce");
+        let summary = target
+            .handle_rpc(rpc_request(6, "peers_import", json!({ "bundle": bundle })))
+            .expect("import")
+            .result
+            .expect("result");
+        assert_eq!(summary["peers_imported"], json!(2));
+        assert_eq!(summary["peers_skipped"], json!(1));
+        assert_eq!(summary["propagation_nodes_imported"], json!(1));
+
+        let peers = target.peers.lock().expect("peers").clone();
+        assert_eq!(peers["peer-a"].last_seen, 100);
+        assert_eq!(peers["peer-b"].last_seen, 900);
+        assert_eq!(peers["peer-b"].name.as_deref(), Some("fresh"));
+        let nodes = target
+            .handle_rpc(rpc_request(7, "list_propagation_nodes", json!({})))
+            .expect("nodes")
+            .result
+            .expect("result");
+        assert_eq!(nodes["nodes"][0]["peer"], json!("node-p"));
+
+        let mut future = bundle.clone();
+        future["version"] = json!("peer_bundle_v99");
+        let rejected = target
+            .handle_rpc(rpc_request(8, "peers_import", json!({ "bundle": future })))
+            .expect("import");
+        assert_eq!(rejected.error.expect("error").code, "SDK_VALIDATION_INVALID_ARGUMENT");
+    }
+
+    #[derive(Default)]
+    struct IdentityBridge {
+        keys: Mutex<HashMap<String, String>>,
+    }
+
+    impl OutboundBridge for IdentityBridge {
+        fn deliver(
+            &self,
+            _record: &MessageRecord,
+            _options: &OutboundDeliveryOptions,
+        ) -> Result<(), std::io::Error> {
+            Ok(())
+        }
+
+        fn peer_public_key(&self, destination: &str) -> Result<Option<String>, std::io::Error> {
+            Ok(self.keys.lock().expect("keys").get(destination).cloned())
+        }
+
+        fn restore_peer_identity(
+            &self,
+            destination: &str,
+            public_key: &str,
+        ) -> Result<(), std::io::Error> {
+            self.keys.lock().expect("keys").insert(destination.into(), public_key.into());
+            Ok(())
+        }
+
+        fn forget_peers(&self, destinations: &[String]) -> Result<(), std::io::Error> {
+            let mut keys = self.keys.lock().expect("keys");
+            for destination in destinations {
+                keys.remove(destination);
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn peer_bundles_carry_public_keys_and_imports_survive_a_restart() {
+        let key = "ab".repeat(64);
+        let source_bridge = Arc::new(IdentityBridge::default());
+        source_bridge.keys.lock().expect("keys").insert("peer-a".into(), key.clone());
+        let source = RpcDaemon::with_store_and_bridge(
+            MessagesStore::in_memory().expect("store"),
+            "source".into(),
+            source_bridge,
+        );
+        source
+            .handle_rpc(rpc_request(
+                1,
+                "announce_received",
+                json!({ "peer": "peer-a", "timestamp": 100, "name": "Alice" }),
+            ))
+            .expect("announce");
+        source
+            .handle_rpc(rpc_request(
+                2,
+                "announce_received",
+                json!({ "peer": "node-p", "timestamp": 50, "capabilities": ["propagation"] }),
+            ))
+            .expect("announce");
+        // Newer announces than the propagation node's fill more than one scan page.
+        for index in 0..ANNOUNCE_SCAN_PAGE + 10 {
+            source
+                .store
+                .insert_announce(&AnnounceRecord {
+                    id: format!("bulk-{index}"),
+                    peer: format!("bulk-{index}"),
+                    timestamp: 200,
+                    name: None,
+                    name_source: None,
+                    first_seen: 200,
+                    seen_count: 1,
+                    app_data_hex: None,
+                    capabilities: Vec::new(),
+                    rssi: None,
+                    snr: None,
+                    q: None,
+                    stamp_cost_flexibility: None,
+                    peering_cost: None,
+                })
+                .expect("insert announce");
+        }
+        let mut bundle = source
+            .handle_rpc(rpc_request(3, "peers_export", json!({})))
+            .expect("export")
+            .result
+            .expect("result")["bundle"]
+            .clone();
+        assert_eq!(bundle["identities"], json!({ "peer-a": key }));
+        assert_eq!(bundle["propagation_nodes"][0]["peer"], json!("node-p"));
+        bundle["identities"]["peer-bad"] = json!("not-a-key");
+
+        let (db_path, _) = event_persist_paths("peer-identities-restart");
+        {
+            let bridge = Arc::new(IdentityBridge::default());
+            let store = MessagesStore::open(db_path.as_path()).expect("open sqlite store");
+            let target = RpcDaemon::with_store_and_bridge(store, "target".into(), bridge.clone());
+            let summary = target
+                .handle_rpc(rpc_request(4, "peers_import", json!({ "bundle": bundle })))
+                .expect("import")
+                .result
+                .expect("result");
+            assert_eq!(summary["identities_imported"], json!(1));
+            assert_eq!(summary["identities_rejected"], json!(1));
+            assert_eq!(bridge.keys.lock().expect("keys").get("peer-a"), Some(&key));
+        }
+
+        let bridge = Arc::new(IdentityBridge::default());
+        let store = MessagesStore::open(db_path.as_path()).expect("reopen sqlite store");
+        let target = RpcDaemon::with_store_and_bridge(store, "target".into(), bridge.clone());
+        assert_eq!(bridge.keys.lock().expect("keys").get("peer-a"), Some(&key));
+        let peers = target.peers.lock().expect("peers").clone();
+        assert_eq!(peers["peer-a"].name.as_deref(), Some("Alice"));
+        let exported = target
+            .handle_rpc(rpc_request(5, "peers_export", json!({})))
+            .expect("export")
+            .result
+            .expect("result");
+        assert_eq!(exported["bundle"]["identities"]["peer-a"], json!(key));
+
+        target.handle_rpc(rpc_request(6, "clear_peers", json!({}))).expect("clear peers");
+        assert!(target.store.list_peer_identities().expect("list").is_empty());
+        let _ = std::fs::remove_file(db_path);
+    }
+
+    #[test]
+    fn peers_prune_removes_stale_peers_but_keeps_fresh_and_selected_nodes() {
+        let bridge = Arc::new(IdentityBridge::default());
+        for peer in ["peer-fresh", "peer-stale", "node-stale"] {
+            bridge.keys.lock().expect("keys").insert(peer.into(), "ab".repeat(64));
+        }
+        let daemon = RpcDaemon::with_store_and_bridge(
+            MessagesStore::in_memory().expect("store"),
+            "test-identity".into(),
+            bridge.clone(),
+        );
+        let now = now_i64();
+        for (id, peer, timestamp) in
+            [(1, "peer-fresh", now), (2, "peer-stale", now - 7_200), (3, "node-stale", now - 7_200)]
+        {
+            daemon
+                .handle_rpc(rpc_request(
+                    id,
+                    "announce_received",
+                    json!({ "peer": peer, "timestamp": timestamp, "capabilities": ["propagation"] }),
+                ))
+                .expect("announce");
+        }
+        daemon
+            .handle_rpc(rpc_request(
+                4,
+                "set_outbound_propagation_node",
+                json!({ "peer": "node-stale" }),
+            ))
+            .expect("select node");
+
+        let result = daemon
+            .handle_rpc(rpc_request(5, "peers_prune", json!({ "max_age_secs": 3_600 })))
+            .expect("prune")
+            .result
+            .expect("result");
+        assert_eq!(result["pruned"], json!(1));
+        assert_eq!(result["peers"], json!(["peer-stale"]));
+
+        let peers = daemon.peers.lock().expect("peers").clone();
+        assert!(peers.contains_key("peer-fresh"));
+        assert!(peers.contains_key("node-stale"));
+        assert!(!peers.contains_key("peer-stale"));
+        let persisted = daemon
+            .store
+            .list_announces(16, None, None)
+            .expect("announces")
+            .into_iter()
+            .map(|announce| announce.peer)
+            .collect::<Vec<_>>();
+        assert!(!persisted.iter().any(|peer| peer == "peer-stale"));
+        assert!(persisted.iter().any(|peer| peer == "peer-fresh"));
+        let cached = bridge.keys.lock().expect("keys").clone();
+        assert!(!cached.contains_key("peer-stale"));
+        assert!(cached.contains_key("peer-fresh"));
+        assert!(cached.contains_key("node-stale"));
+    }
+
+    #[test]
+    fn validation_errors_carry_field_and_reason_details() {
+        let daemon = RpcDaemon::test_instance();
+        let send = |id: u64, params: JsonValue| {
+            daemon.handle_rpc(rpc_request(id, "sdk_send_v2", params)).expect("send").error.expect("error")
+        };
+
+        let empty = send(
+            1,
+            json!({ "id": "v-1", "source": "src", "destination": "  ", "content": "hi" }),
+        );
+        assert_eq!(empty.code, "SDK_VALIDATION_INVALID_ARGUMENT");
+        let details = empty.details.expect("details");
+        assert_eq!(details["field"], json!("destination"));
+        assert_eq!(details["reason"], json!("empty"));
+
+        let missing = send(2, json!({ "id": "v-2", "source": "src", "content": "hi" }));
+        let details = missing.details.expect("details");
+        assert_eq!(details["field"], json!("destination"));
+        assert_eq!(details["reason"], json!("missing"));
+
+        let label = daemon
+            .handle_rpc(rpc_request(
+                3,
+                "sdk_marker_create_v2",
+                json!({ "label": " ", "position": { "lat": 1.0, "lon": 2.0 } }),
+            ))
+            .expect("marker create")
+            .error
+            .expect("error");
+        let details = label.details.expect("details");
+        assert_eq!(details["field"], json!("label"));
+        assert_eq!(details["reason"], json!("empty"));
+
+        // Details are never guessed from the message text.
+        let unstructured =
+            RpcError::new("SDK_VALIDATION_INVALID_ARGUMENT", "marker label must not be empty");
+        assert!(unstructured.details.is_none());
+
+        let plain = RpcError::new("SDK_RUNTIME_INVALID_STATE", "draining");
+        assert_eq!(serde_json::to_value(&plain).expect("serialize")["details"], json!({}));
+        let framed = crate::rpc::codec::encode_frame(&plain).expect("encode");
+        let decoded: RpcError = crate::rpc::codec::decode_frame(&framed).expect("decode");
+        assert!(decoded.details.expect("details").is_empty());
+    }
+
+    #[derive(Default)]
+    struct ReconnectBridge {
+        reconnected: Mutex<Vec<String>>,
+    }
+
+    impl OutboundBridge for ReconnectBridge {
+        fn deliver(
+            &self,
+            _record: &MessageRecord,
+            _options: &OutboundDeliveryOptions,
+        ) -> Result<(), std::io::Error> {
+            Ok(())
+        }
+
+        fn reconnect_interface(&self, name: &str) -> Result<bool, std::io::Error> {
+            self.reconnected.lock().expect("reconnected").push(name.to_string());
+            Ok(true)
+        }
+    }
+
+    #[test]
+    fn reconnect_resets_named_or_all_interfaces_and_rejects_unknown_names() {
+        let bridge = Arc::new(ReconnectBridge::default());
+        let store = MessagesStore::in_memory().expect("store");
+        let daemon =
+            RpcDaemon::with_store_and_bridge(store, "test-identity".into(), bridge.clone());
+        daemon.replace_interfaces(vec![
+            InterfaceRecord {
+                kind: "tcp_client".into(),
+                enabled: true,
+                host: Some("10.0.0.1".into()),
+                port: Some(4242),
+                name: Some("uplink".into()),
+            },
+            InterfaceRecord {
+                kind: "udp_multicast".into(),
+                enabled: true,
+                host: None,
+                port: None,
+                name: None,
+            },
+            InterfaceRecord {
+                kind: "tcp_client".into(),
+                enabled: false,
+                host: None,
+                port: None,
+                name: Some("spare".into()),
+            },
+        ]);
+
+        let result = daemon
+            .handle_rpc(rpc_request(1, "reconnect", json!({ "interface_name": "uplink" })))
+            .expect("reconnect")
+            .result
+            .expect("result");
+        assert_eq!(result["reconnected"], json!(["uplink"]));
+        // Link state comes from the interface once it is back up, not from
+        // the request.
+        assert!(std::iter::from_fn(|| daemon.take_event())
+            .all(|event| event.event_type != "interface_link_state"));
+
+        let result = daemon
+            .handle_rpc(rpc_request(2, "reconnect", json!({})))
+            .expect("reconnect all")
+            .result
+            .expect("result");
+        assert_eq!(result["reconnected"], json!(["uplink", "udp_multicast"]));
+        assert_eq!(
+            bridge.reconnected.lock().expect("reconnected").as_slice(),
+            ["uplink", "uplink", "udp_multicast"]
+        );
+
+        let missing = daemon
+            .handle_rpc(rpc_request(3, "reconnect", json!({ "interface_name": "nope" })))
+            .expect("reconnect");
+        assert_eq!(missing.error.expect("error").code, "SDK_RUNTIME_NOT_FOUND");
+    }
+
+    #[derive(Default)]
+    struct RotatingBridge {
+        rotations: Mutex<u32>,
+    }
+
+    impl AnnounceBridge for RotatingBridge {
+        fn announce_now(&self) -> Result<(), std::io::Error> {
+            Ok(())
+        }
+
+        fn rotate_identity(&self) -> Result<IdentityRotation, std::io::Error> {
+            let mut rotations = self.rotations.lock().expect("rotations");
+            *rotations += 1;
+            Ok(IdentityRotation {
+                identity_hash: format!("identity-{rotations}"),
+                delivery_destination_hash: format!("delivery-{rotations}"),
+            })
+        }
+    }
+
+    #[test]
+    fn identity_rotate_requires_confirmation_and_reports_both_destinations() {
+        let bridge = Arc::new(RotatingBridge::default());
+        let store = MessagesStore::in_memory().expect("store");
+        let daemon = RpcDaemon::with_store_and_bridges(
+            store,
+            "test-identity".into(),
+            None,
+            Some(bridge.clone()),
+        );
+        daemon.set_delivery_destination_hash(Some("delivery-0".into()));
+
+        let unconfirmed =
+            daemon.handle_rpc(rpc_request(1, "identity_rotate", json!({}))).expect("rotate");
+        assert_eq!(unconfirmed.error.expect("error").code, "SDK_VALIDATION_INVALID_ARGUMENT");
+        assert_eq!(*bridge.rotations.lock().expect("rotations"), 0);
+
+        let result = daemon
+            .handle_rpc(rpc_request(2, "identity_rotate", json!({ "confirm": true })))
+            .expect("rotate")
+            .result
+            .expect("result");
+        assert_eq!(result["previous_delivery_destination_hash"], json!("delivery-0"));
+        assert_eq!(result["delivery_destination_hash"], json!("delivery-1"));
+        assert_eq!(result["previous_identity_hash"], json!("test-identity"));
+        assert_eq!(result["identity_hash"], json!("identity-1"));
+
+        let status = daemon
+            .handle_rpc(rpc_request(3, "status", json!({})))
+            .expect("status")
+            .result
+            .expect("result");
+        assert_eq!(status["delivery_destination_hash"], json!("delivery-1"));
+        let events = std::iter::from_fn(|| daemon.take_event()).collect::<Vec<_>>();
+        let rotated = events
+            .iter()
+            .find(|event| event.event_type == "identity_rotated")
+            .expect("identity_rotated event");
+        assert_eq!(rotated.payload["previous_delivery_destination_hash"], json!("delivery-0"));
+        // The bridge announces asynchronously and reports back when it is done.
+        assert!(events.iter().all(|event| event.event_type != "announce_sent"));
+
+        let second = daemon
+            .handle_rpc(rpc_request(5, "identity_rotate", json!({ "confirm": true })))
+            .expect("rotate")
+            .result
+            .expect("result");
+        assert_eq!(second["previous_identity_hash"], json!("identity-1"));
+        assert_eq!(second["previous_delivery_destination_hash"], json!("delivery-1"));
+        assert_eq!(second["identity_hash"], json!("identity-2"));
+        let whoami = daemon
+            .handle_rpc(rpc_request(6, "whoami", json!({})))
+            .expect("whoami")
+            .result
+            .expect("result");
+        assert_eq!(whoami["identity_hash"], json!("identity-2"));
+
+        let unsupported = RpcDaemon::test_instance()
+            .handle_rpc(rpc_request(4, "identity_rotate", json!({ "confirm": true })))
+            .expect("rotate");
+        assert_eq!(unsupported.error.expect("error").code, "SDK_CAPABILITY_DISABLED");
+    }
+
+    #[test]
+    fn identity_rotation_announce_is_reported_only_once_the_bridge_completes_it() {
+        let daemon = RpcDaemon::test_instance();
+
+        daemon.record_identity_announce("delivery-1", Ok(()));
+        let sent = daemon.take_event().expect("announce_sent event");
+        assert_eq!(sent.event_type, "announce_sent");
+        assert_eq!(sent.payload["delivery_destination_hash"], json!("delivery-1"));
+
+        daemon.record_identity_announce("delivery-2", Err("announce task panicked".into()));
+        let failed = daemon.take_event().expect("announce_failed event");
+        assert_eq!(failed.event_type, "announce_failed");
+        assert_eq!(failed.payload["delivery_destination_hash"], json!("delivery-2"));
+        assert_eq!(failed.payload["error"], json!("announce task panicked"));
+    }
+
+    #[derive(Default)]
+    struct NamingBridge {
+        published: Mutex<Vec<String>>,
+    }
+
+    impl AnnounceBridge for NamingBridge {
+        fn announce_now(&self) -> Result<(), std::io::Error> {
+            Ok(())
+        }
+
+        fn set_display_name(
+            &self,
+            display_name: &str,
+        ) -> Result<DisplayNameUpdate, std::io::Error> {
+            let trimmed = display_name.trim();
+            if trimmed.is_empty() || trimmed.chars().count() > 64 {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "display name must be 1-64 characters",
+                ));
+            }
+            self.published.lock().expect("published").push(trimmed.to_string());
+            // Names starting with "offline" stand in for a failed announce.
+            let announced = !trimmed.starts_with("offline");
+            Ok(DisplayNameUpdate { display_name: trimmed.to_string(), announced })
+        }
+    }
+
+    #[test]
+    fn set_display_name_publishes_and_updates_whoami() {
+        let bridge = Arc::new(NamingBridge::default());
+        let store = MessagesStore::in_memory().expect("store");
+        let daemon = RpcDaemon::with_store_and_bridges(
+            store,
+            "test-identity".into(),
+            None,
+            Some(bridge.clone()),
+        );
+
+        let result = daemon
+            .handle_rpc(rpc_request(
+                1,
+                "set_display_name",
+                json!({ "display_name": "  Base Camp " }),
+            ))
+            .expect("set_display_name")
+            .result
+            .expect("result");
+        assert_eq!(result, json!({ "display_name": "Base Camp", "announced": true }));
+        assert_eq!(bridge.published.lock().expect("published").as_slice(), ["Base Camp"]);
+        assert!(std::iter::from_fn(|| daemon.take_event())
+            .any(|event| event.event_type == "announce_sent"));
+
+        let whoami = daemon
+            .handle_rpc(rpc_request(2, "whoami", json!({})))
+            .expect("whoami")
+            .result
+            .expect("result");
+        assert_eq!(whoami["display_name"], json!("Base Camp"));
+
+        let too_long = daemon
+            .handle_rpc(rpc_request(
+                3,
+                "set_display_name",
+                json!({ "display_name": "n".repeat(65) }),
+            ))
+            .expect("set_display_name");
+        assert_eq!(too_long.error.expect("error").code, "SDK_VALIDATION_INVALID_ARGUMENT");
+        assert_eq!(bridge.published.lock().expect("published").len(), 1);
+
+        let unannounced = daemon
+            .handle_rpc(rpc_request(5, "set_display_name", json!({ "display_name": "offline" })))
+            .expect("set_display_name")
+            .result
+            .expect("result");
+        assert_eq!(unannounced, json!({ "display_name": "offline", "announced": false }));
+        assert!(!std::iter::from_fn(|| daemon.take_event())
+            .any(|event| event.event_type == "announce_sent"));
+        let whoami = daemon
+            .handle_rpc(rpc_request(6, "whoami", json!({})))
+            .expect("whoami")
+            .result
+            .expect("result");
+        assert_eq!(whoami["display_name"], json!("offline"));
+
+        let unsupported = RpcDaemon::test_instance()
+            .handle_rpc(rpc_request(4, "set_display_name", json!({ "display_name": "Alpha" })))
+            .expect("set_display_name");
+        assert_eq!(unsupported.error.expect("error").code, "SDK_CAPABILITY_DISABLED");
+    }
+
+    #[derive(Default)]
+    struct DirectedBridge {
+        targets: Mutex<Vec<String>>,
+    }
+
+    impl AnnounceBridge for DirectedBridge {
+        fn announce_now(&self) -> Result<(), std::io::Error> {
+            Ok(())
+        }
+
+        fn announce_to(&self, destination: &str) -> Result<(), std::io::Error> {
+            self.targets.lock().expect("targets").push(destination.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn announce_once_to_targets_a_single_peer() {
+        let bridge = Arc::new(DirectedBridge::default());
+        let store = MessagesStore::in_memory().expect("store");
+        let daemon = RpcDaemon::with_store_and_bridges(
+            store,
+            "test-identity".into(),
+            None,
+            Some(bridge.clone()),
+        );
+        let peer = "AABBCCDDEEFF00112233445566778899";
+
+        let result = daemon
+            .handle_rpc(rpc_request(1, "announce_once_to", json!({ "peer": peer })))
+            .expect("announce_once_to")
+            .result
+            .expect("result");
+        assert_eq!(result["target"], json!(peer.to_ascii_lowercase()));
+        assert_eq!(bridge.targets.lock().expect("targets").as_slice(), [peer.to_ascii_lowercase()]);
+        // Nothing counts as announced until the bridge reports back.
+        assert!(!std::iter::from_fn(|| daemon.take_event())
+            .any(|event| event.event_type == "announce_sent"));
+
+        let target = peer.to_ascii_lowercase();
+        daemon.record_directed_announce(&target, false);
+        let events = std::iter::from_fn(|| daemon.take_event()).collect::<Vec<_>>();
+        assert!(!events.iter().any(|event| event.event_type == "announce_sent"));
+        let skipped = events
+            .iter()
+            .find(|event| event.event_type == "announce_skipped")
+            .expect("announce_skipped event");
+        assert_eq!(skipped.payload["target"], json!(target));
+        assert_eq!(skipped.payload["reason"], json!("no_path"));
+
+        daemon.record_directed_announce(&target, true);
+        let sent = std::iter::from_fn(|| daemon.take_event())
+            .find(|event| event.event_type == "announce_sent")
+            .expect("announce_sent event");
+        assert_eq!(sent.payload["target"], json!(target));
+
+        for bad in [json!({}), json!({ "peer": "not-a-hash" }), json!({ "peer": "aabb" })] {
+            let response = daemon
+                .handle_rpc(rpc_request(2, "announce_once_to", bad))
+                .expect("announce_once_to");
+            assert_eq!(response.error.expect("error").code, "SDK_VALIDATION_INVALID_ARGUMENT");
+        }
+        assert_eq!(bridge.targets.lock().expect("targets").len(), 1);
+
+        let unsupported = RpcDaemon::test_instance()
+            .handle_rpc(rpc_request(3, "announce_once_to", json!({ "peer": peer })))
+            .expect("announce_once_to");
+        assert_eq!(unsupported.error.expect("error").code, "SDK_CAPABILITY_DISABLED");
+    }
+
+    #[derive(Default)]
+    struct RoleBridge {
+        roles: Mutex<Vec<bool>>,
+        announces: Mutex<usize>,
+    }
+
+    impl AnnounceBridge for RoleBridge {
+        fn announce_now(&self) -> Result<(), std::io::Error> {
+            *self.announces.lock().expect("announces") += 1;
+            Ok(())
+        }
+
+        fn set_propagation_node(&self, enabled: bool) -> Result<(), std::io::Error> {
+            self.roles.lock().expect("roles").push(enabled);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn propagation_node_config_toggles_role_live() {
+        let bridge = Arc::new(RoleBridge::default());
+        let store = MessagesStore::in_memory().expect("store");
+        let daemon = RpcDaemon::with_store_and_bridges(
+            store,
+            "test-identity".into(),
+            None,
+            Some(bridge.clone()),
+        );
+        let configure = |revision: u64, patch: JsonValue| {
+            daemon
+                .handle_rpc(rpc_request(
+                    1,
+                    "sdk_configure_v2",
+                    json!({ "expected_revision": revision, "patch": patch }),
+                ))
+                .expect("configure")
+        };
+        let snapshot_role = || {
+            daemon
+                .handle_rpc(rpc_request(2, "sdk_snapshot_v2", json!({})))
+                .expect("snapshot")
+                .result
+                .expect("result")["propagation_node"]
+                .clone()
+        };
+        let ingest = |transient_id: &str| {
+            daemon
+                .handle_rpc(rpc_request(
+                    3,
+                    "propagation_ingest",
+                    json!({ "transient_id": transient_id, "payload_hex": "00ff" }),
+                ))
+                .expect("ingest")
+        };
+        assert_eq!(snapshot_role(), json!(false));
+        assert_eq!(ingest("t-0").error.expect("error").code, "SDK_CAPABILITY_DISABLED");
+
+        assert!(configure(0, json!({ "propagation_node": true })).error.is_none());
+        assert_eq!(snapshot_role(), json!(true));
+        assert_eq!(bridge.roles.lock().expect("roles").as_slice(), [true]);
+        assert_eq!(*bridge.announces.lock().expect("announces"), 1);
+        assert!(std::iter::from_fn(|| daemon.take_event())
+            .any(|event| event.event_type == "announce_sent"));
+        assert!(ingest("t-1").error.is_none());
+
+        assert!(configure(1, json!({ "propagation_node": false })).error.is_none());
+        assert_eq!(snapshot_role(), json!(false));
+        assert_eq!(bridge.roles.lock().expect("roles").as_slice(), [true, false]);
+        assert_eq!(*bridge.announces.lock().expect("announces"), 1);
+        assert_eq!(ingest("t-2").error.expect("error").code, "SDK_CAPABILITY_DISABLED");
+        // Messages stored while the node was propagating stay fetchable.
+        let fetched = daemon
+            .handle_rpc(rpc_request(4, "propagation_fetch", json!({ "transient_id": "t-1" })))
+            .expect("fetch");
+        assert!(fetched.error.is_none());
+
+        let rejected = configure(2, json!({ "propagation_node": "yes" }));
+        assert_eq!(rejected.error.expect("error").code, "SDK_VALIDATION_INVALID_ARGUMENT");
+    }
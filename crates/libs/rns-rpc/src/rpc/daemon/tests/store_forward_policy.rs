@@ -143,6 +143,7 @@ fn sdk_store_forward_expiry_marks_old_non_terminal_records() {
             direction: "out".to_string(),
             fields: None,
             receipt_status: None,
+            reply_to: None,
         })
         .expect("insert old record");
 
@@ -167,3 +168,734 @@ fn sdk_store_forward_expiry_marks_old_non_terminal_records() {
         .expect("old record should still exist after expiry marking");
     assert_eq!(old.receipt_status.as_deref(), Some("expired"));
 }
+
+#[test]
+fn sdk_store_forward_expiry_emits_batch_event_and_dead_letter_log() {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let run_id = SystemTime::now().duration_since(UNIX_EPOCH).expect("unix epoch").as_nanos();
+    let dead_letter_path = std::env::temp_dir()
+        .join(format!("lxmf-rs-dead-letter-{run_id}-{}.jsonl", std::process::id()));
+    let daemon = RpcDaemon::test_instance();
+    // A one-byte cap rotates the log after the first batch.
+    daemon.enable_dead_letter_log(&dead_letter_path, 1).expect("enable dead-letter log");
+    let redirected = daemon
+        .handle_rpc(rpc_request(
+            929,
+            "sdk_configure_v2",
+            json!({
+                "expected_revision": 0,
+                "patch": { "store_forward": { "dead_letter_path": "/etc/passwd" } }
+            }),
+        ))
+        .expect("configure");
+    let error = redirected.error.expect("dead_letter_path must not be remotely configurable");
+    assert_eq!(error.code, "SDK_VALIDATION_INVALID_ARGUMENT");
+    let configure = daemon
+        .handle_rpc(rpc_request(
+            930,
+            "sdk_configure_v2",
+            json!({
+                "expected_revision": 0,
+                "patch": { "store_forward": { "max_message_age_ms": 5 } }
+            }),
+        ))
+        .expect("configure");
+    assert!(configure.error.is_none());
+
+    for (id, destination) in [("sf-exp-1", "destination.a"), ("sf-exp-2", "destination.b")] {
+        daemon
+            .store
+            .insert_message(&MessageRecord {
+                id: id.to_string(),
+                source: "source.e".to_string(),
+                destination: destination.to_string(),
+                title: "".to_string(),
+                content: "payload".to_string(),
+                timestamp: now_i64().saturating_sub(1_000),
+                direction: "out".to_string(),
+                fields: None,
+                receipt_status: None,
+                reply_to: None,
+            })
+            .expect("insert expiring record");
+    }
+    let mut events = daemon.subscribe_events();
+
+    let send = daemon
+        .handle_rpc(rpc_request(
+            931,
+            "send_message",
+            json!({
+                "id": "sf-exp-trigger",
+                "source": "source.e",
+                "destination": "destination.e",
+                "content": "payload-new"
+            }),
+        ))
+        .expect("send trigger");
+    assert!(send.error.is_none());
+
+    let batch = std::iter::from_fn(|| events.try_recv().ok())
+        .find(|event| event.event_type == "messages_expired_batch")
+        .expect("batch expiry event");
+    assert_eq!(batch.payload["expired_count"], 2);
+    assert_eq!(
+        batch.payload["messages"],
+        json!([
+            { "message_id": "sf-exp-1", "destination": "destination.a" },
+            { "message_id": "sf-exp-2", "destination": "destination.b" },
+        ])
+    );
+
+    let rotated_path = dead_letter_path.with_extension("jsonl.1");
+    assert_eq!(std::fs::read_to_string(&dead_letter_path).expect("dead-letter log"), "");
+    let dead_letters = std::fs::read_to_string(&rotated_path).expect("rotated dead-letter log");
+    let logged = dead_letters
+        .lines()
+        .map(|line| serde_json::from_str::<JsonValue>(line).expect("dead-letter entry"))
+        .map(|entry| entry["message_id"].as_str().expect("message id").to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(logged, vec!["sf-exp-1", "sf-exp-2"]);
+
+    let _ = std::fs::remove_file(&dead_letter_path);
+    let _ = std::fs::remove_file(&rotated_path);
+}
+
+fn accept_propagation_announce(daemon: &RpcDaemon, peer: &str, timestamp: i64, cost: Option<u32>) {
+    daemon
+        .accept_announce_with_metadata(
+            peer.to_string(),
+            timestamp,
+            None,
+            None,
+            None,
+            Some(vec!["propagation".to_string()]),
+            None,
+            None,
+            None,
+            cost,
+            None,
+            Some(cost),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .expect("accept announce");
+}
+
+fn enable_propagation_reselect(daemon: &RpcDaemon) {
+    let configure = daemon
+        .handle_rpc(rpc_request(
+            930,
+            "sdk_configure_v2",
+            json!({
+                "expected_revision": 0,
+                "patch": {
+                    "extensions": {
+                        "propagation_reselect": { "enabled": true, "stale_after_secs": 600 }
+                    }
+                }
+            }),
+        ))
+        .expect("configure");
+    assert!(configure.error.is_none());
+}
+
+#[test]
+fn propagation_reselect_replaces_stale_selection_with_cheapest_fresh_node() {
+    let daemon = RpcDaemon::test_instance();
+    let now = now_i64();
+    accept_propagation_announce(&daemon, "node-stale", now - 7_200, Some(1));
+    accept_propagation_announce(&daemon, "node-costly", now - 30, Some(20));
+    accept_propagation_announce(&daemon, "node-cheap", now - 120, Some(4));
+    *daemon.outbound_propagation_node.lock().expect("propagation node") =
+        Some("node-stale".to_string());
+    enable_propagation_reselect(&daemon);
+    while daemon.take_event().is_some() {}
+
+    let response = daemon
+        .handle_rpc(rpc_request(931, "get_outbound_propagation_node", json!({})))
+        .expect("get propagation node");
+    let result = response.result.expect("result");
+    assert_eq!(result["peer"], json!("node-cheap"));
+    assert_eq!(result["pinned"], json!(false));
+
+    let event = daemon.take_event().expect("reselection event");
+    assert_eq!(event.event_type, "propagation_node_reselected");
+    assert_eq!(event.payload["previous"], json!("node-stale"));
+    assert_eq!(event.payload["peer"], json!("node-cheap"));
+}
+
+#[test]
+fn propagation_reselect_ranks_by_announced_stamp_cost_not_peering_cost() {
+    let daemon = RpcDaemon::test_instance();
+    let now = now_i64();
+    accept_propagation_announce(&daemon, "node-stale", now - 7_200, Some(1));
+    for (id, peer, stamp_cost, peering_cost) in
+        [(935, "node-cheap-peering", 16_u32, 2_u32), (936, "node-cheap-stamp", 8, 24)]
+    {
+        let app_data =
+            rmp_serde::to_vec(&(false, now, true, 256_u32, 10_240_u32, (stamp_cost, 2_u32, peering_cost)))
+                .expect("encode announce app data");
+        let response = daemon
+            .handle_rpc(rpc_request(
+                id,
+                "announce_received",
+                json!({
+                    "peer": peer,
+                    "timestamp": now - 60,
+                    "app_data_hex": hex::encode(app_data),
+                    "capabilities": ["propagation"]
+                }),
+            ))
+            .expect("announce");
+        assert!(response.error.is_none());
+    }
+    *daemon.outbound_propagation_node.lock().expect("propagation node") =
+        Some("node-stale".to_string());
+    enable_propagation_reselect(&daemon);
+    while daemon.take_event().is_some() {}
+
+    let result = daemon
+        .handle_rpc(rpc_request(937, "get_outbound_propagation_node", json!({})))
+        .expect("get propagation node")
+        .result
+        .expect("result");
+    assert_eq!(result["peer"], json!("node-cheap-stamp"));
+    let event = daemon.take_event().expect("reselection event");
+    assert_eq!(event.event_type, "propagation_node_reselected");
+    assert_eq!(event.payload["stamp_cost"], json!(8));
+}
+
+#[test]
+fn propagation_reselect_keeps_manually_pinned_node() {
+    let daemon = RpcDaemon::test_instance();
+    let now = now_i64();
+    accept_propagation_announce(&daemon, "node-pinned", now - 7_200, Some(1));
+    accept_propagation_announce(&daemon, "node-fresh", now - 10, Some(1));
+    enable_propagation_reselect(&daemon);
+    let set = daemon
+        .handle_rpc(rpc_request(
+            932,
+            "set_outbound_propagation_node",
+            json!({ "peer": "node-pinned" }),
+        ))
+        .expect("set propagation node");
+    assert!(set.error.is_none());
+    while daemon.take_event().is_some() {}
+
+    let pinned = daemon
+        .handle_rpc(rpc_request(933, "get_outbound_propagation_node", json!({})))
+        .expect("get propagation node")
+        .result
+        .expect("result");
+    assert_eq!(pinned["peer"], json!("node-pinned"));
+    assert_eq!(pinned["pinned"], json!(true));
+    while let Some(event) = daemon.take_event() {
+        assert_ne!(event.event_type, "propagation_node_reselected");
+    }
+
+    let cleared = daemon
+        .handle_rpc(rpc_request(934, "set_outbound_propagation_node", json!({ "peer": null })))
+        .expect("clear propagation node")
+        .result
+        .expect("result");
+    assert_eq!(cleared["peer"], JsonValue::Null);
+    assert!(!*daemon.outbound_propagation_node_pinned.lock().expect("pin"));
+}
+
+fn relay_candidates(daemon: &RpcDaemon, exclude: &[&str]) -> JsonValue {
+    daemon
+        .handle_rpc(rpc_request(940, "list_propagation_nodes", json!({ "exclude": exclude })))
+        .expect("list propagation nodes")
+        .result
+        .expect("result")["relay_candidates"]
+        .clone()
+}
+
+fn set_selection_strategy(daemon: &RpcDaemon, revision: u64, strategy: &str) {
+    let configure = daemon
+        .handle_rpc(rpc_request(
+            941,
+            "sdk_configure_v2",
+            json!({
+                "expected_revision": revision,
+                "patch": { "propagation_selection_strategy": strategy }
+            }),
+        ))
+        .expect("configure");
+    assert!(configure.error.is_none(), "{:?}", configure.error);
+}
+
+#[test]
+fn propagation_relay_candidates_follow_selection_strategy() {
+    let daemon = RpcDaemon::test_instance();
+    let now = now_i64();
+    accept_propagation_announce(&daemon, "node-old-cheap", now - 600, Some(2));
+    accept_propagation_announce(&daemon, "node-recent", now - 10, Some(16));
+    accept_propagation_announce(&daemon, "node-mid", now - 60, None);
+    *daemon.outbound_propagation_node.lock().expect("propagation node") =
+        Some("node-mid".to_string());
+
+    assert_eq!(
+        relay_candidates(&daemon, &[]),
+        json!(["node-mid", "node-recent", "node-old-cheap"])
+    );
+    assert_eq!(relay_candidates(&daemon, &["node-mid"]), json!(["node-recent", "node-old-cheap"]));
+
+    set_selection_strategy(&daemon, 0, "lowest_cost");
+    assert_eq!(
+        relay_candidates(&daemon, &[]),
+        json!(["node-old-cheap", "node-recent", "node-mid"])
+    );
+    assert_eq!(relay_candidates(&daemon, &["node-old-cheap"]), json!(["node-recent", "node-mid"]));
+
+    set_selection_strategy(&daemon, 1, "most_recent");
+    assert_eq!(
+        relay_candidates(&daemon, &[]),
+        json!(["node-recent", "node-mid", "node-old-cheap"])
+    );
+
+    let rejected = daemon
+        .handle_rpc(rpc_request(
+            942,
+            "sdk_configure_v2",
+            json!({
+                "expected_revision": 2,
+                "patch": { "propagation_selection_strategy": "random" }
+            }),
+        ))
+        .expect("configure");
+    assert_eq!(rejected.error.expect("error").code, "SDK_VALIDATION_INVALID_ARGUMENT");
+}
+
+#[test]
+fn prune_messages_removes_old_terminal_records_only() {
+    let daemon = RpcDaemon::test_instance();
+    let empty = daemon
+        .handle_rpc(rpc_request(960, "prune_messages", json!({ "older_than_ms": 0 })))
+        .expect("prune empty")
+        .result
+        .expect("result");
+    assert_eq!(empty["pruned"], json!(0));
+
+    let old = now_i64().saturating_sub(3_600);
+    for (id, receipt_status) in [
+        ("prune-delivered", Some("delivered")),
+        ("prune-failed", Some("failed: timeout")),
+        ("prune-in-flight", Some("sending")),
+        ("prune-queued", None),
+    ] {
+        daemon
+            .store
+            .insert_message(&MessageRecord {
+                id: id.to_string(),
+                source: "source.p".to_string(),
+                destination: "destination.p".to_string(),
+                title: "".to_string(),
+                content: "payload".to_string(),
+                timestamp: old,
+                direction: "out".to_string(),
+                fields: None,
+                receipt_status: receipt_status.map(str::to_string),
+                reply_to: None,
+            })
+            .expect("insert record");
+        daemon.append_delivery_trace(id, receipt_status.unwrap_or("queued").to_string());
+    }
+
+    let pruned = daemon
+        .handle_rpc(rpc_request(961, "prune_messages", json!({ "older_than_ms": 60_000 })))
+        .expect("prune")
+        .result
+        .expect("result");
+    assert_eq!(pruned["pruned"], json!(2));
+    assert!(daemon.store.get_message("prune-delivered").expect("load").is_none());
+    assert!(daemon.store.get_message("prune-failed").expect("load").is_none());
+    assert!(daemon.store.get_message("prune-in-flight").expect("load").is_some());
+    assert!(daemon.store.get_message("prune-queued").expect("load").is_some());
+    assert!(daemon.delivery_trace_entries("prune-delivered").expect("trace").is_empty());
+    assert!(daemon.delivery_trace_entries("prune-failed").expect("trace").is_empty());
+    assert_eq!(daemon.delivery_trace_entries("prune-queued").expect("trace").len(), 1);
+}
+
+#[test]
+fn messages_search_filters_server_side_and_pages_newest_first() {
+    let daemon = RpcDaemon::test_instance();
+    for (id, timestamp, title) in [
+        ("search-1", 1_000, "Field report"),
+        ("search-2", 2_000, "lunch"),
+        ("search-3", 3_000, "FIELD notes"),
+        ("search-4", 4_000, "field kit"),
+    ] {
+        daemon
+            .store
+            .insert_message(&MessageRecord {
+                id: id.to_string(),
+                source: "source.s".to_string(),
+                destination: "destination.s".to_string(),
+                title: title.to_string(),
+                content: "payload".to_string(),
+                timestamp,
+                direction: "in".to_string(),
+                fields: None,
+                receipt_status: None,
+                reply_to: None,
+            })
+            .expect("insert record");
+    }
+    let search = |id: u64, params: JsonValue| {
+        daemon
+            .handle_rpc(rpc_request(id, "messages_search", params))
+            .expect("messages_search")
+            .result
+            .expect("result")
+    };
+    let ids = |result: &JsonValue| {
+        result["messages"]
+            .as_array()
+            .expect("messages")
+            .iter()
+            .map(|message| message["id"].as_str().expect("id").to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let first = search(970, json!({ "query": "field", "limit": 2 }));
+    assert_eq!(ids(&first), vec!["search-4", "search-3"]);
+    let cursor = first["next_cursor"].as_str().expect("next_cursor").to_string();
+    let second = search(971, json!({ "query": "field", "limit": 2, "cursor": cursor }));
+    assert_eq!(ids(&second), vec!["search-1"]);
+    assert_eq!(second["next_cursor"], JsonValue::Null);
+
+    let window = search(972, json!({ "from_ts_ms": 2_000_000, "to_ts_ms": 3_000_000 }));
+    assert_eq!(ids(&window), vec!["search-3", "search-2"]);
+
+    let other_peer = search(973, json!({ "source": "someone.else" }));
+    assert!(ids(&other_peer).is_empty());
+}
+
+#[test]
+fn messages_export_bundles_a_conversation_oldest_first() {
+    let daemon = RpcDaemon::test_instance();
+    for (id, timestamp, source, destination, direction, receipt_status) in [
+        ("export-1", 1_000, "peer.a", "local.s", "in", None),
+        ("export-2", 2_000, "local.s", "PEER.A", "out", Some("delivered")),
+        ("export-3", 3_000, "peer.b", "local.s", "in", None),
+        ("export-4", 4_000, "peer.a", "local.s", "in", None),
+    ] {
+        daemon
+            .store
+            .insert_message(&MessageRecord {
+                id: id.to_string(),
+                source: source.to_string(),
+                destination: destination.to_string(),
+                title: "t".to_string(),
+                content: format!("body {id}"),
+                timestamp,
+                direction: direction.to_string(),
+                fields: Some(json!({ "k": id })),
+                receipt_status: receipt_status.map(str::to_string),
+                reply_to: None,
+            })
+            .expect("insert record");
+    }
+    let export = |id: u64, params: JsonValue| {
+        daemon.handle_rpc(rpc_request(id, "messages_export", params)).expect("messages_export")
+    };
+    let bundle =
+        |id: u64, params: JsonValue| export(id, params).result.expect("result")["bundle"].clone();
+    let ids = |bundle: &JsonValue| {
+        bundle["messages"]
+            .as_array()
+            .expect("messages")
+            .iter()
+            .map(|message| message["id"].as_str().expect("id").to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let conversation = bundle(980, json!({ "peer": "peer.a" }));
+    assert_eq!(conversation["version"], json!("message_bundle_v1"));
+    assert_eq!(conversation["peer"], json!("peer.a"));
+    assert_eq!(ids(&conversation), vec!["export-1", "export-2", "export-4"]);
+    let reply = &conversation["messages"][1];
+    assert_eq!(reply["direction"], json!("out"));
+    assert_eq!(reply["receipt_status"], json!("delivered"));
+    assert_eq!(reply["fields"], json!({ "k": "export-2" }));
+    assert_eq!(reply["content"], json!("body export-2"));
+
+    let window =
+        bundle(981, json!({ "peer": "peer.a", "from_ts_ms": 2_000_000, "to_ts_ms": 4_000_000 }));
+    assert_eq!(ids(&window), vec!["export-2", "export-4"]);
+    assert_eq!(ids(&bundle(982, json!({}))).len(), 4);
+    assert_eq!(conversation["next_cursor"], JsonValue::Null);
+
+    let first = bundle(985, json!({ "peer": "peer.a", "limit": 2 }));
+    assert_eq!(ids(&first), vec!["export-1", "export-2"]);
+    let cursor = first["next_cursor"].as_str().expect("next_cursor");
+    let second = bundle(986, json!({ "peer": "peer.a", "limit": 2, "cursor": cursor }));
+    assert_eq!(ids(&second), vec!["export-4"]);
+    assert_eq!(second["next_cursor"], JsonValue::Null);
+
+    let empty = bundle(983, json!({ "peer": "nobody" }));
+    assert_eq!(empty["version"], json!("message_bundle_v1"));
+    assert_eq!(empty["messages"], json!([]));
+
+    let inverted = export(984, json!({ "from_ts_ms": 5, "to_ts_ms": 1 }));
+    assert_eq!(inverted.error.expect("error").code, "SDK_VALIDATION_INVALID_ARGUMENT");
+}
+
+#[test]
+fn replies_link_to_parent_and_filter_by_thread() {
+    let daemon = RpcDaemon::test_instance();
+    let sent = daemon
+        .handle_rpc(rpc_request(
+            980,
+            "sdk_send_v2",
+            json!({
+                "id": "thread-reply-out",
+                "source": "src",
+                "destination": "dst",
+                "title": "",
+                "content": "reply to a message we never stored",
+                "fields": { "8": "thread-root" }
+            }),
+        ))
+        .expect("sdk_send_v2");
+    assert!(sent.error.is_none(), "advisory link must not block sending");
+    let stored = daemon.store.get_message("thread-reply-out").expect("load").expect("record");
+    assert_eq!(stored.reply_to.as_deref(), Some("thread-root"));
+
+    let received = daemon
+        .handle_rpc(rpc_request(
+            981,
+            "receive_message",
+            json!({
+                "id": "thread-reply-in",
+                "source": "dst",
+                "destination": "src",
+                "title": "",
+                "content": "inbound reply",
+                "fields": { "8": "thread-root" }
+            }),
+        ))
+        .expect("receive_message");
+    assert!(received.error.is_none());
+    let inbound = std::iter::from_fn(|| daemon.take_event())
+        .find(|event| event.event_type == "inbound")
+        .expect("inbound event");
+    assert_eq!(inbound.payload["message"]["reply_to"], json!("thread-root"));
+
+    let result = daemon
+        .handle_rpc(rpc_request(982, "messages_search", json!({ "thread": "thread-root" })))
+        .expect("messages_search")
+        .result
+        .expect("result");
+    let mut ids = result["messages"]
+        .as_array()
+        .expect("messages")
+        .iter()
+        .map(|message| message["id"].as_str().expect("id").to_string())
+        .collect::<Vec<_>>();
+    ids.sort();
+    assert_eq!(ids, vec!["thread-reply-in", "thread-reply-out"]);
+}
+
+#[test]
+fn attachment_get_returns_decoded_bytes_with_checksum() {
+    let daemon = RpcDaemon::test_instance();
+    for (id, fields) in [
+        (
+            "attach-canonical",
+            json!({ "attachments": [
+                { "name": "a.txt", "data": [104, 105] },
+                { "name": "b.bin", "data": "hex:0a0b0c" },
+            ] }),
+        ),
+        ("attach-wire", json!({ "5": [["wire.bin", [1, 2, 3]]] })),
+    ] {
+        daemon
+            .store
+            .insert_message(&MessageRecord {
+                id: id.to_string(),
+                source: "source.a".to_string(),
+                destination: "destination.a".to_string(),
+                title: String::new(),
+                content: String::new(),
+                timestamp: 1_000,
+                direction: "in".to_string(),
+                fields: Some(fields),
+                receipt_status: None,
+                reply_to: None,
+            })
+            .expect("insert record");
+    }
+
+    let response = daemon
+        .handle_rpc(rpc_request(
+            980,
+            "attachment_get",
+            json!({ "message_id": "attach-canonical", "index": 1 }),
+        ))
+        .expect("attachment_get");
+    let attachment = &response.result.expect("result")["attachment"];
+    assert_eq!(attachment["name"], json!("b.bin"));
+    assert_eq!(attachment["size_bytes"], json!(3));
+    assert_eq!(attachment["bytes_base64"], json!("CgsM"));
+    assert_eq!(attachment["checksum_sha256"], json!(encode_hex(Sha256::digest([10, 11, 12]))));
+
+    let response = daemon
+        .handle_rpc(rpc_request(
+            981,
+            "attachment_get",
+            json!({ "message_id": "attach-wire", "index": 0 }),
+        ))
+        .expect("attachment_get");
+    let attachment = &response.result.expect("result")["attachment"];
+    assert_eq!(attachment["name"], json!("wire.bin"));
+    assert_eq!(attachment["bytes_base64"], json!("AQID"));
+
+    let response = daemon
+        .handle_rpc(rpc_request(
+            982,
+            "attachment_get",
+            json!({ "message_id": "attach-canonical", "index": 2 }),
+        ))
+        .expect("attachment_get");
+    let error = response.error.expect("error");
+    assert_eq!(error.code, "SDK_VALIDATION_INVALID_ARGUMENT");
+    assert!(error.message.contains("message has 2 attachments"));
+    assert_eq!(error.details.expect("details")["attachment_count"], json!(2));
+}
+
+#[test]
+fn attachment_get_reports_stored_checksums_and_resolves_store_references() {
+    let daemon = RpcDaemon::test_instance();
+    daemon
+        .handle_rpc(rpc_request(
+            983,
+            "receive_message",
+            json!({
+                "id": "attach-tampered",
+                "source": "source.a",
+                "destination": "destination.a",
+                "title": "",
+                "content": "",
+                "fields": { "attachments": [{ "name": "a.bin", "data": [1, 2, 3] }] },
+            }),
+        ))
+        .expect("receive");
+    let mut stored = daemon.store.get_message("attach-tampered").expect("get").expect("stored");
+    stored.fields.as_mut().expect("fields")["attachments"][0]["data"] = json!([9, 9, 9]);
+    daemon.store.insert_message(&stored).expect("rewrite record");
+
+    let response = daemon
+        .handle_rpc(rpc_request(
+            984,
+            "attachment_get",
+            json!({ "message_id": "attach-tampered", "index": 0 }),
+        ))
+        .expect("attachment_get");
+    let attachment = &response.result.expect("result")["attachment"];
+    assert_eq!(attachment["bytes_base64"], json!("CQkJ"));
+    assert_eq!(attachment["checksum_sha256"], json!(encode_hex(Sha256::digest([1, 2, 3]))));
+
+    let stored_attachment = daemon
+        .handle_rpc(rpc_request(
+            985,
+            "sdk_attachment_store_v2",
+            json!({
+                "name": "report.txt",
+                "content_type": "text/plain",
+                "bytes_base64": "aGVsbG8=",
+            }),
+        ))
+        .expect("attachment store");
+    let attachment_id = stored_attachment.result.expect("result")["attachment"]["attachment_id"]
+        .as_str()
+        .expect("attachment id")
+        .to_string();
+    daemon
+        .store
+        .insert_message(&MessageRecord {
+            id: "attach-reference".to_string(),
+            source: "source.a".to_string(),
+            destination: "destination.a".to_string(),
+            title: String::new(),
+            content: String::new(),
+            timestamp: 1_000,
+            direction: "out".to_string(),
+            fields: Some(json!({ "attachments": [
+                { "attachment_id": attachment_id },
+                { "attachment_id": "attachment-missing" },
+            ] })),
+            receipt_status: None,
+            reply_to: None,
+        })
+        .expect("insert record");
+
+    let response = daemon
+        .handle_rpc(rpc_request(
+            986,
+            "attachment_get",
+            json!({ "message_id": "attach-reference", "index": 0 }),
+        ))
+        .expect("attachment_get");
+    let attachment = &response.result.expect("result")["attachment"];
+    assert_eq!(attachment["name"], json!("report.txt"));
+    assert_eq!(attachment["content_type"], json!("text/plain"));
+    assert_eq!(attachment["bytes_base64"], json!("aGVsbG8="));
+    assert_eq!(attachment["checksum_sha256"], json!(encode_hex(Sha256::digest(b"hello"))));
+
+    let response = daemon
+        .handle_rpc(rpc_request(
+            987,
+            "attachment_get",
+            json!({ "message_id": "attach-reference", "index": 1 }),
+        ))
+        .expect("attachment_get");
+    assert_eq!(response.error.expect("error").code, "SDK_RUNTIME_NOT_FOUND");
+}
+
+#[test]
+fn inbound_attachments_record_detected_media_type_and_dimensions() {
+    let daemon = RpcDaemon::test_instance();
+    let mut png = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR".to_vec();
+    png.extend_from_slice(&[0, 0, 0, 64, 0, 0, 0, 32, 8, 6, 0, 0, 0]);
+    daemon
+        .handle_rpc(rpc_request(
+            990,
+            "receive_message",
+            json!({
+                "id": "inbound-media",
+                "source": "source.a",
+                "destination": "destination.a",
+                "title": "",
+                "content": "photo",
+                "fields": { "5": [["photo.png", png], ["notes.dat", [1, 2, 3]]] },
+            }),
+        ))
+        .expect("receive");
+
+    let stored = daemon.store.get_message("inbound-media").expect("get").expect("stored");
+    let media = &stored.fields.expect("fields")["_attachments"];
+    assert_eq!(media[0]["content_type"], json!("image/png"));
+    assert_eq!(media[0]["extensions"], json!({ "width": 64, "height": 32 }));
+    assert_eq!(media[1]["content_type"], json!("application/octet-stream"));
+    assert_eq!(media[1]["extensions"], json!({}));
+
+    let response = daemon
+        .handle_rpc(rpc_request(
+            991,
+            "attachment_get",
+            json!({ "message_id": "inbound-media", "index": 0 }),
+        ))
+        .expect("attachment_get");
+    let attachment = &response.result.expect("result")["attachment"];
+    assert_eq!(attachment["content_type"], json!("image/png"));
+    assert_eq!(attachment["extensions"]["width"], json!(64));
+    assert_eq!(attachment["extensions"]["height"], json!(32));
+}
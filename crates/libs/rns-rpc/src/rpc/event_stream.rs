@@ -0,0 +1,92 @@
+use std::collections::VecDeque;
+
+use serde_json::json;
+use tokio::sync::broadcast::{self, error::RecvError, error::TryRecvError};
+
+use crate::rpc::RpcEvent;
+
+/// Live event feed for a long-lived `/events` connection. Replayed events are
+/// served before the daemon broadcast channel, and each batch is a run of
+/// newline-delimited JSON lines bounded by the runtime event stream limits.
+pub struct EventStream {
+    receiver: broadcast::Receiver<RpcEvent>,
+    replay: VecDeque<RpcEvent>,
+    max_event_bytes: usize,
+    max_batch_bytes: usize,
+    carry: Option<Vec<u8>>,
+}
+
+impl EventStream {
+    pub(crate) fn new(
+        receiver: broadcast::Receiver<RpcEvent>,
+        replay: VecDeque<RpcEvent>,
+        max_event_bytes: usize,
+        max_batch_bytes: usize,
+    ) -> Self {
+        Self { receiver, replay, max_event_bytes, max_batch_bytes, carry: None }
+    }
+
+    /// Waits for at least one line and returns everything already queued behind
+    /// it, up to `max_batch_bytes`. Events the subscriber fell behind on, or that
+    /// exceed the size limits, are reported as `dropped_count` control lines.
+    /// Returns `None` once the daemon has shut the channel down. Cancel safe: the
+    /// only await happens before anything has been taken off the stream.
+    pub async fn next_batch(&mut self) -> Option<Vec<u8>> {
+        let mut batch = self.carry.take().unwrap_or_default();
+        while batch.is_empty() {
+            if let Some(event) = self.replay.pop_front() {
+                self.push_event(&mut batch, &event);
+                continue;
+            }
+            match self.receiver.recv().await {
+                Ok(event) => self.push_event(&mut batch, &event),
+                Err(RecvError::Lagged(dropped)) => self.push_dropped(&mut batch, dropped),
+                Err(RecvError::Closed) => return None,
+            }
+        }
+        while self.carry.is_none() {
+            if let Some(event) = self.replay.pop_front() {
+                self.push_event(&mut batch, &event);
+                continue;
+            }
+            match self.receiver.try_recv() {
+                Ok(event) => self.push_event(&mut batch, &event),
+                Err(TryRecvError::Lagged(dropped)) => self.push_dropped(&mut batch, dropped),
+                Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+            }
+        }
+        Some(batch)
+    }
+
+    fn push_event(&mut self, batch: &mut Vec<u8>, event: &RpcEvent) {
+        if event.event_type == "sdk_lifecycle_trace" {
+            return;
+        }
+        let payload_bytes = event.payload.to_string().len();
+        let Ok(mut line) = serde_json::to_vec(event) else {
+            return;
+        };
+        line.push(b'\n');
+        if payload_bytes > self.max_event_bytes || line.len() > self.max_batch_bytes {
+            self.push_dropped(batch, 1);
+            return;
+        }
+        self.push_line(batch, line);
+    }
+
+    fn push_dropped(&mut self, batch: &mut Vec<u8>, dropped: u64) {
+        let mut line = json!({ "control": "dropped_count", "dropped_count": dropped })
+            .to_string()
+            .into_bytes();
+        line.push(b'\n');
+        self.push_line(batch, line);
+    }
+
+    fn push_line(&mut self, batch: &mut Vec<u8>, line: Vec<u8>) {
+        if !batch.is_empty() && batch.len() + line.len() > self.max_batch_bytes {
+            self.carry = Some(line);
+            return;
+        }
+        batch.extend_from_slice(&line);
+    }
+}
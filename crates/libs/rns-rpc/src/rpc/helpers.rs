@@ -34,6 +34,95 @@ fn merge_fields_with_options(
     Some(JsonValue::Object(root))
 }
 
+/// Approximates the LXMF wire size: destination and source hashes, the
+/// signature, and the msgpack-encoded `[timestamp, title, content, fields]`.
+fn estimate_outbound_plan(
+    record: &MessageRecord,
+    options: &OutboundDeliveryOptions,
+) -> OutboundPlan {
+    const LXMF_OVERHEAD_BYTES: usize = 16 + 16 + 64;
+    let payload = rmp_serde::to_vec(&(
+        record.timestamp as f64,
+        record.title.as_str(),
+        record.content.as_str(),
+        record.fields.as_ref(),
+    ))
+    .ok();
+    OutboundPlan {
+        method: options.method.clone().unwrap_or_else(|| "direct".to_string()),
+        wire_size: payload.map(|bytes| LXMF_OVERHEAD_BYTES + bytes.len()),
+        target_interface: None,
+        destination_identity_known: false,
+    }
+}
+
+/// The idempotency key a send carries in `fields._sdk.idempotency_key`, with
+/// a hash of what it sends: title, content and fields other than `_sdk`.
+fn idempotency_claim(
+    title: &str,
+    content: &str,
+    fields: Option<&JsonValue>,
+) -> Option<(String, String)> {
+    let key = fields?
+        .get("_sdk")?
+        .get("idempotency_key")?
+        .as_str()
+        .map(str::trim)
+        .filter(|key| !key.is_empty())?
+        .to_string();
+    let mut payload_fields = fields.cloned().unwrap_or(JsonValue::Null);
+    if let Some(map) = payload_fields.as_object_mut() {
+        map.remove("_sdk");
+    }
+    let payload = json!({ "title": title, "content": content, "fields": payload_fields });
+    Some((key, encode_hex(Sha256::digest(payload.to_string().as_bytes()))))
+}
+
+/// Folds tabs and line breaks into spaces and drops every other control
+/// character, so titles cannot carry terminal escape sequences.
+fn sanitize_title(title: &str) -> String {
+    title
+        .chars()
+        .filter_map(|ch| match ch {
+            '\t' | '\n' | '\r' => Some(' '),
+            ch if ch.is_control() => None,
+            ch => Some(ch),
+        })
+        .collect()
+}
+
+/// Short detail carried by a trace status of the form `stage:detail`, such
+/// as the error after `failed:` or the relay hash after
+/// `propagation_relay_selected:`.
+fn delivery_trace_detail(status: &str) -> Option<String> {
+    let (_, detail) = status.split_once(':')?;
+    let mut detail = detail.trim().to_string();
+    if detail.is_empty() {
+        return None;
+    }
+    truncate_at_char_boundary(&mut detail, MAX_DELIVERY_TRACE_DETAIL_BYTES);
+    Some(detail)
+}
+
+fn truncate_at_char_boundary(value: &mut String, max_bytes: usize) {
+    if value.len() <= max_bytes {
+        return;
+    }
+    let mut end = max_bytes;
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    value.truncate(end);
+}
+
+fn nearest_rank_percentile(sorted: &[u64], percentile: usize) -> Option<u64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (sorted.len() * percentile).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied()
+}
+
 fn now_i64() -> i64 {
     std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -67,6 +156,12 @@ fn clean_optional_text(value: Option<String>) -> Option<String> {
     value.map(|value| value.trim().to_string()).filter(|value| !value.is_empty())
 }
 
+/// Canonical form of a delivery ticket: tickets are hex digests, so stored,
+/// revoked and presented tickets compare case-insensitively.
+fn normalize_ticket(ticket: &str) -> String {
+    ticket.trim().to_ascii_lowercase()
+}
+
 fn normalize_capabilities(values: Vec<String>) -> Vec<String> {
     let mut seen = HashSet::new();
     let mut out = Vec::new();
@@ -80,6 +175,53 @@ fn normalize_capabilities(values: Vec<String>) -> Vec<String> {
     out
 }
 
+/// Display names for peers whose announced name collides (ignoring case and
+/// surrounding whitespace) with another peer's. Each gets a peer-hash suffix of
+/// at least `suffix_len` characters, grown until the colliding group is
+/// unambiguous. Peers with unique names are omitted; zero disables the suffix.
+fn disambiguated_peer_names<'a>(
+    peers: impl IntoIterator<Item = &'a PeerRecord>,
+    suffix_len: usize,
+) -> HashMap<String, String> {
+    let mut display_names = HashMap::new();
+    if suffix_len == 0 {
+        return display_names;
+    }
+    let mut groups: HashMap<String, Vec<(&str, &str)>> = HashMap::new();
+    for peer in peers {
+        let Some(name) = peer.name.as_deref().map(str::trim).filter(|name| !name.is_empty())
+        else {
+            continue;
+        };
+        groups.entry(name.to_lowercase()).or_default().push((peer.peer.as_str(), name));
+    }
+    for members in groups.into_values().filter(|members| members.len() > 1) {
+        let suffix = |hash: &str, len: usize| first_n_chars(hash, len).unwrap_or(hash.to_string());
+        let longest = members.iter().map(|(hash, _)| hash.chars().count()).max().unwrap_or(0);
+        let mut len = suffix_len.min(longest);
+        while len < longest
+            && members.iter().map(|(hash, _)| suffix(hash, len)).collect::<HashSet<_>>().len()
+                < members.len()
+        {
+            len += 1;
+        }
+        for (hash, name) in members {
+            display_names.insert(hash.to_string(), format!("{name} ({})", suffix(hash, len)));
+        }
+    }
+    display_names
+}
+
+/// A peer record with its `display_name` (the raw `name` unless it collides
+/// with another peer's) and a `name_conflict` flag.
+fn peer_display_view(peer: &PeerRecord, display_names: &HashMap<String, String>) -> JsonValue {
+    let mut view = json!(peer);
+    let display_name = display_names.get(&peer.peer).cloned().or_else(|| peer.name.clone());
+    view["display_name"] = json!(display_name);
+    view["name_conflict"] = json!(display_names.contains_key(&peer.peer));
+    view
+}
+
 fn parse_capabilities_from_app_data_hex(app_data_hex: Option<&str>) -> Vec<String> {
     let Some(raw_hex) = app_data_hex.map(str::trim).filter(|value| !value.is_empty()) else {
         return Vec::new();
@@ -175,25 +317,36 @@ fn parse_fuzzy_u32(value: &MsgPackValue) -> Option<u32> {
     }
 }
 
+/// The stamp-cost entry of a propagation node announce: either
+/// `[stamp_cost, flexibility, peering_cost]` or a map with those keys.
+fn announce_costs_from_app_data_hex(app_data_hex: Option<&str>) -> Option<MsgPackValue> {
+    let raw_hex = app_data_hex.map(str::trim).filter(|value| !value.is_empty())?;
+    let app_data = hex::decode(raw_hex).ok()?;
+    let value = rmp_serde::from_slice::<MsgPackValue>(&app_data).ok()?;
+    let MsgPackValue::Array(mut entries) = value else {
+        return None;
+    };
+    (entries.len() > 5).then(|| entries.swap_remove(5))
+}
+
+fn parse_announce_stamp_cost_from_app_data_hex(app_data_hex: Option<&str>) -> Option<u32> {
+    match announce_costs_from_app_data_hex(app_data_hex)? {
+        MsgPackValue::Array(values) => values.first().and_then(parse_fuzzy_u32),
+        MsgPackValue::Map(entries) => entries
+            .iter()
+            .find(|(key, _)| msgpack_key_to_string(key).as_deref() == Some("stamp_cost"))
+            .and_then(|(_, value)| parse_fuzzy_u32(value)),
+        _ => None,
+    }
+}
+
 fn parse_announce_costs_from_app_data_hex(
     app_data_hex: Option<&str>,
 ) -> (Option<u32>, Option<u32>) {
-    let Some(raw_hex) = app_data_hex.map(str::trim).filter(|value| !value.is_empty()) else {
+    let Some(costs) = announce_costs_from_app_data_hex(app_data_hex) else {
         return (None, None);
     };
-    let Ok(app_data) = hex::decode(raw_hex) else {
-        return (None, None);
-    };
-    let Ok(value) = rmp_serde::from_slice::<MsgPackValue>(&app_data) else {
-        return (None, None);
-    };
-    let Some(entries) = value.as_array() else {
-        return (None, None);
-    };
-    let Some(costs) = entries.get(5) else {
-        return (None, None);
-    };
-    if let MsgPackValue::Array(values) = costs {
+    if let MsgPackValue::Array(values) = &costs {
         return (values.get(1).and_then(parse_fuzzy_u32), values.get(2).and_then(parse_fuzzy_u32));
     }
     let MsgPackValue::Map(entries) = costs else {
@@ -201,7 +354,7 @@ fn parse_announce_costs_from_app_data_hex(
     };
     let mut stamp_cost_flexibility = None;
     let mut peering_cost = None;
-    for (key, value) in entries {
+    for (key, value) in &entries {
         let Some(key) = msgpack_key_to_string(key) else {
             continue;
         };
@@ -255,6 +408,49 @@ fn msgpack_key_to_string(key: &MsgPackValue) -> Option<String> {
     }
 }
 
+fn parse_daemon_log_level(value: &str) -> Option<DaemonLogLevel> {
+    match value.trim().to_ascii_uppercase().as_str() {
+        "TRACE" => Some(DaemonLogLevel::Trace),
+        "DEBUG" => Some(DaemonLogLevel::Debug),
+        "INFO" => Some(DaemonLogLevel::Info),
+        "WARN" | "WARNING" => Some(DaemonLogLevel::Warn),
+        "ERROR" => Some(DaemonLogLevel::Error),
+        _ => None,
+    }
+}
+
+/// Reads the level from the first two tokens of a log line, so both `WARN msg`
+/// and `<timestamp> [warn] msg` are recognised. Anything else counts as info.
+fn daemon_log_line_level(line: &str) -> DaemonLogLevel {
+    line.split_whitespace()
+        .take(2)
+        .find_map(|token| {
+            parse_daemon_log_level(token.trim_matches(|c| matches!(c, '[' | ']' | ':')))
+        })
+        .unwrap_or(DaemonLogLevel::Info)
+}
+
+/// True for a 16-byte destination hash written as 32 hex characters.
+fn is_destination_hash(value: &str) -> bool {
+    value.len() == 32 && value.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+/// Hash identifying an inbound message for duplicate suppression. It covers
+/// the message id and everything the message was sent with, so distinct
+/// messages sharing content are never collapsed.
+fn inbound_transient_hash(record: &MessageRecord) -> String {
+    let mut hasher = Sha256::new();
+    for part in [&record.id, &record.source, &record.destination, &record.title, &record.content] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    hasher.update(record.timestamp.to_be_bytes());
+    if let Some(fields) = record.fields.as_ref() {
+        hasher.update(fields.to_string().as_bytes());
+    }
+    encode_hex(hasher.finalize())
+}
+
 fn encode_hex(bytes: impl AsRef<[u8]>) -> String {
     const HEX: &[u8; 16] = b"0123456789abcdef";
     let bytes = bytes.as_ref();
@@ -268,4 +464,136 @@ fn encode_hex(bytes: impl AsRef<[u8]>) -> String {
 
 const LEGACY_EVENT_QUEUE_CAPACITY: usize = 32;
 const SDK_EVENT_LOG_CAPACITY: usize = 1024;
+const DAEMON_LOG_CAPACITY: usize = 4096;
+const INBOUND_DEDUP_WINDOW: usize = 4096;
+const DAEMON_LOG_DEFAULT_TAIL: usize = 400;
+const DEFAULT_PEER_NAME_SUFFIX_LEN: usize = 6;
+const PEER_RTT_WINDOW: usize = 64;
+const DEFAULT_RECEIPT_TIMEOUT_MS: u64 = 10 * 60 * 1000;
+const DEFAULT_PROPAGATION_RECEIPT_TIMEOUT_MS: u64 = 24 * 60 * 60 * 1000;
+const DEFAULT_PROPAGATION_MESSAGE_TTL_MS: u64 = 30 * 24 * 60 * 60 * 1000;
+const PROPAGATION_EXPIRED_ID_CAPACITY: usize = 1024;
+const FIELD_ATTACHMENT_MEDIA: &str = "_attachments";
+const PR_PATH_REQUESTED: u32 = 0x01;
+const PR_COMPLETE: u32 = 0x07;
+const PR_NO_PATH: u32 = 0xF0;
+const PROPAGATION_SYNC_STALE_SECS: i64 = 120;
+const PROPAGATION_SYNC_MAX_MESSAGES: usize = 256;
+const PEER_RTT_PENDING_CAPACITY: usize = 2048;
+/// A send still unanswered after this long no longer yields a useful RTT sample.
+const PEER_RTT_PENDING_MAX_AGE_MS: u64 = 60 * 60 * 1000;
+const PEER_PING_HISTORY: usize = 256;
+const PEER_PING_DEFAULT_TIMEOUT_MS: u64 = 15_000;
+/// How long a `fail_fast_no_path` send may wait for a path before failing.
+pub const FAIL_FAST_PATH_TIMEOUT: Duration = Duration::from_secs(2);
+const DEFAULT_SHUTDOWN_DRAIN_TIMEOUT_MS: u64 = 5_000;
+const MAX_SHUTDOWN_DRAIN_TIMEOUT_MS: u64 = 300_000;
+const DEFAULT_MIN_COMPRESS_BYTES: usize = 1_024;
+const DEFAULT_MAX_BODY_BYTES: usize = 1_048_576;
+const DEFAULT_MAX_ATTACHMENT_BYTES: usize = 16 * 1024 * 1024;
+const DEFAULT_MAX_ATTACHMENTS: usize = 32;
+const PEER_BUNDLE_VERSION: &str = "peer_bundle_v1";
+/// Hex length of a public identity: the X25519 then Ed25519 public keys.
+const PUBLIC_IDENTITY_HEX_LEN: usize = 128;
+const ANNOUNCE_SCAN_PAGE: usize = 500;
+const MESSAGE_BUNDLE_VERSION: &str = "message_bundle_v1";
+const MAX_DELIVERY_TRACE_ENTRIES: usize = 32;
+const MAX_DELIVERY_TRACE_DETAIL_BYTES: usize = 128;
+
+/// `{ field, reason }` details of a validation error.
+fn field_error_details(field: &str, reason: &str) -> JsonMap<String, JsonValue> {
+    let mut details = JsonMap::new();
+    details.insert("field".to_string(), JsonValue::String(field.to_string()));
+    details.insert("reason".to_string(), JsonValue::String(reason.to_string()));
+    details
+}
+
+/// A request field rejected by the daemon's own checks. It travels inside an
+/// `InvalidInput` I/O error so the dispatcher can report it as structured
+/// details rather than only as text.
+#[derive(Debug)]
+struct InvalidField {
+    field: &'static str,
+    reason: &'static str,
+    message: String,
+}
+
+impl std::fmt::Display for InvalidField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for InvalidField {}
+
+fn invalid_field_error(
+    field: &'static str,
+    reason: &'static str,
+    message: impl Into<String>,
+) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        InvalidField { field, reason, message: message.into() },
+    )
+}
+
+/// Details of an `InvalidInput` error raised while handling a request: taken
+/// from an [`InvalidField`] when the daemon rejected the field itself, or
+/// from serde's `missing field`/`unknown field`/`invalid ...` errors, which
+/// only report the offending key in their text.
+fn validation_error_details(error: &std::io::Error) -> Option<JsonMap<String, JsonValue>> {
+    if let Some(invalid) = error.get_ref().and_then(|inner| inner.downcast_ref::<InvalidField>()) {
+        return Some(field_error_details(invalid.field, invalid.reason));
+    }
+    let message = error.to_string();
+    let backticked = |prefix: &str| {
+        let rest = &message[message.find(prefix)? + prefix.len()..];
+        let rest = rest.strip_prefix('`')?;
+        Some(rest[..rest.find('`')?].to_string())
+    };
+    let (field, reason) = if let Some(field) = backticked("missing field ") {
+        (Some(field), "missing")
+    } else if let Some(field) = backticked("unknown field ") {
+        (Some(field), "unknown")
+    } else if message.starts_with("invalid type") {
+        (None, "invalid_type")
+    } else if message.starts_with("invalid value") {
+        (None, "invalid_value")
+    } else {
+        return None;
+    };
+    let mut details = JsonMap::new();
+    if let Some(field) = field {
+        details.insert("field".to_string(), JsonValue::String(field));
+    }
+    details.insert("reason".to_string(), JsonValue::String(reason.to_string()));
+    Some(details)
+}
+const DEFAULT_TELEMETRY_POINTS_PER_PEER: usize = 256;
+/// Floor applied to a configured `announce_interval_secs` to avoid flooding.
+const MIN_ANNOUNCE_INTERVAL_SECS: u64 = 10;
+const DEFAULT_EVENT_PERSIST_MAX_BYTES: u64 = 4 * 1024 * 1024;
+const DEFAULT_EVENT_PERSIST_MAX_AGE_SECS: u64 = 86_400;
+/// Size at which the dead-letter log is rotated to `<path>.1`.
+pub const DEFAULT_DEAD_LETTER_MAX_BYTES: u64 = 4 * 1024 * 1024;
 const SDK_STREAM_ID: &str = "sdk-events";
+const CONTRACT_RELEASE: &str = "v2.5";
+const SCHEMA_NAMESPACE: &str = "v2";
+const SUPPORTED_CONTRACT_VERSIONS: &[u16] = &[2];
+/// Peer name sources decoded from structured announce app data, as opposed
+/// to names guessed from free-form bytes.
+const VERIFIED_PEER_NAME_SOURCES: &[&str] = &["delivery_app_data", "pn_meta"];
+
+/// Stamp cost an adaptive propagation node requires while holding
+/// `store_bytes`: `PROPAGATION_COST_MIN` up to one transfer's worth
+/// (`PROPAGATION_LIMIT` KB), rising linearly to `cost + flexibility` at a
+/// full sync's worth (`SYNC_LIMIT` KB).
+fn adaptive_stamp_cost(cost: u32, flexibility: u32, store_bytes: usize) -> u32 {
+    let floor = PROPAGATION_COST_MIN;
+    let ceiling = cost.max(floor).saturating_add(flexibility);
+    let (low, high) = (PROPAGATION_LIMIT as usize * 1000, SYNC_LIMIT as usize * 1000);
+    let filled = store_bytes.clamp(low, high) - low;
+    let span = u64::from(ceiling - floor);
+    let raise = (span * filled as u64).div_ceil((high - low) as u64);
+    floor + u32::try_from(raise).unwrap_or(u32::MAX).min(ceiling - floor)
+}
@@ -1,10 +1,35 @@
-use std::io;
+use std::io::{self, Read as _, Write as _};
 use std::net::SocketAddr;
 
-use crate::rpc::{codec, RpcDaemon, RpcRequest, RpcResponse};
+use crate::rpc::event_stream::EventStream;
+use crate::rpc::{codec, RpcDaemon, RpcError, RpcRequest, RpcResponse};
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine as _;
+use flate2::read::{GzDecoder, ZlibDecoder};
+use flate2::write::{GzEncoder, ZlibEncoder};
+use flate2::Compression;
+use serde::Deserialize;
 use serde_json::json;
+use sha1::{Digest, Sha1};
 
 const HEADER_END: &[u8] = b"\r\n\r\n";
+const WEBSOCKET_ACCEPT_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+pub const WEBSOCKET_OPCODE_TEXT: u8 = 0x1;
+pub const WEBSOCKET_OPCODE_CLOSE: u8 = 0x8;
+pub const WEBSOCKET_OPCODE_PING: u8 = 0x9;
+pub const WEBSOCKET_OPCODE_PONG: u8 = 0xA;
+/// Close status for a peer that broke the framing rules (RFC 6455 7.4.1).
+pub const WEBSOCKET_CLOSE_PROTOCOL_ERROR: u16 = 1002;
+/// Close status for a frame larger than the server accepts.
+pub const WEBSOCKET_CLOSE_TOO_BIG: u16 = 1009;
+
+/// A `POST /rpc` body: one request, or an array of requests answered in order.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RpcRequestBody {
+    Batch(Vec<RpcRequest>),
+    Single(RpcRequest),
+}
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct TransportAuthContext {
@@ -134,16 +159,42 @@ pub fn handle_http_request_with_transport_auth(
                     return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "body incomplete"));
                 }
                 let body = &request[body_start..body_start + content_length];
-                let rpc_request: RpcRequest = codec::decode_frame(body)?;
+                // Oversized batches are refused on their declared length,
+                // before any of the body is decoded or authenticated.
+                if is_batch_frame(body) {
+                    let max_body_bytes = daemon.http_max_body_bytes();
+                    if content_length > max_body_bytes {
+                        return build_rpc_error_response(
+                            0,
+                            batch_too_large_error(content_length, max_body_bytes),
+                        );
+                    }
+                }
+                let rpc_body: RpcRequestBody = codec::decode_frame(body)?;
+                let request_id = match &rpc_body {
+                    RpcRequestBody::Single(rpc_request) => rpc_request.id,
+                    RpcRequestBody::Batch(_) => 0,
+                };
                 if let Err(error) = daemon.authorize_http_request_with_transport(
                     &parsed_headers,
                     peer_ip.as_deref(),
                     transport_auth.as_ref(),
                 ) {
-                    return build_rpc_error_response(rpc_request.id, error);
+                    return build_rpc_error_response(request_id, error);
                 }
-                let rpc_response = daemon.handle_rpc(rpc_request)?;
-                let response_body = codec::encode_frame(&rpc_response).map_err(io::Error::other)?;
+                let response_body = match rpc_body {
+                    RpcRequestBody::Single(rpc_request) => {
+                        let rpc_response = daemon.handle_rpc(rpc_request)?;
+                        codec::encode_frame(&rpc_response).map_err(io::Error::other)?
+                    }
+                    RpcRequestBody::Batch(rpc_requests) => {
+                        let rpc_responses = rpc_requests
+                            .into_iter()
+                            .map(|rpc_request| handle_batch_item(daemon, rpc_request))
+                            .collect::<Vec<_>>();
+                        codec::encode_frame(&rpc_responses).map_err(io::Error::other)?
+                    }
+                };
                 Ok(build_response(StatusCode::Ok, &response_body))
             }
             _ => Err(io::Error::new(io::ErrorKind::InvalidInput, "unsupported request")),
@@ -152,7 +203,309 @@ pub fn handle_http_request_with_transport_auth(
     if response.is_err() {
         daemon.metrics_record_http_error();
     }
-    response
+    response.map(|response| encode_response(request, response, daemon.http_min_compress_bytes()))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ContentEncoding {
+    Gzip,
+    Deflate,
+}
+
+impl ContentEncoding {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Deflate => "deflate",
+        }
+    }
+}
+
+/// Picks the response encoding from `Accept-Encoding`, preferring gzip. Codings
+/// listed with `q=0` are treated as refused.
+fn accepted_encoding(headers: &[(String, String)]) -> Option<ContentEncoding> {
+    let accept = header_value(headers, "accept-encoding")?;
+    let accepted = |name: &str| {
+        accept.split(',').any(|entry| {
+            let mut parts = entry.split(';').map(str::trim);
+            let coding = parts.next().unwrap_or_default();
+            let refused = parts.any(|param| {
+                param
+                    .strip_prefix("q=")
+                    .and_then(|q| q.trim().parse::<f32>().ok())
+                    .is_some_and(|q| q <= 0.0)
+            });
+            coding.eq_ignore_ascii_case(name) && !refused
+        })
+    };
+    if accepted("gzip") {
+        Some(ContentEncoding::Gzip)
+    } else if accepted("deflate") {
+        Some(ContentEncoding::Deflate)
+    } else {
+        None
+    }
+}
+
+/// Compresses the body of a built response when the client accepts gzip or
+/// deflate and the body is at least `min_compress_bytes`. Anything else, or a
+/// body that does not shrink, is returned unchanged.
+fn encode_response(request: &[u8], response: Vec<u8>, min_compress_bytes: usize) -> Vec<u8> {
+    let Some(request_header_end) = find_header_end(request) else {
+        return response;
+    };
+    let Some(encoding) = accepted_encoding(&parse_headers(&request[..request_header_end])) else {
+        return response;
+    };
+    let Some(header_end) = find_header_end(&response) else {
+        return response;
+    };
+    let body = &response[header_end + HEADER_END.len()..];
+    if body.is_empty() || body.len() < min_compress_bytes {
+        return response;
+    }
+    let compressed = match encoding {
+        ContentEncoding::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(body).and_then(|()| encoder.finish())
+        }
+        ContentEncoding::Deflate => {
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(body).and_then(|()| encoder.finish())
+        }
+    };
+    let Ok(compressed) = compressed else {
+        return response;
+    };
+    if compressed.len() >= body.len() {
+        return response;
+    }
+
+    let headers = String::from_utf8_lossy(&response[..header_end]);
+    let mut encoded = Vec::with_capacity(header_end + compressed.len() + 64);
+    for line in headers.lines() {
+        if line.to_ascii_lowercase().starts_with("content-length:") {
+            continue;
+        }
+        encoded.extend_from_slice(line.as_bytes());
+        encoded.extend_from_slice(b"\r\n");
+    }
+    encoded.extend_from_slice(format!("Content-Encoding: {}\r\n", encoding.as_str()).as_bytes());
+    encoded.extend_from_slice(b"Vary: Accept-Encoding\r\n");
+    encoded.extend_from_slice(format!("Content-Length: {}\r\n", compressed.len()).as_bytes());
+    encoded.extend_from_slice(b"\r\n");
+    encoded.extend_from_slice(&compressed);
+    encoded
+}
+
+/// Undoes any `Content-Encoding` on a response body. The decoded size is capped
+/// at `max_body_bytes` whether or not the body was compressed, so a small
+/// compressed body cannot expand without bound and the limit a caller sets
+/// does not depend on what the server chose to compress.
+pub fn decode_response_body(
+    headers: &[u8],
+    body: &[u8],
+    max_body_bytes: usize,
+) -> io::Result<Vec<u8>> {
+    let parsed_headers = parse_headers(headers);
+    let encoding = match header_value(&parsed_headers, "content-encoding")
+        .map(str::to_ascii_lowercase)
+        .as_deref()
+    {
+        None | Some("identity") => {
+            if body.len() > max_body_bytes {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "response body exceeds max_body_bytes",
+                ));
+            }
+            return Ok(body.to_vec());
+        }
+        Some("gzip") => ContentEncoding::Gzip,
+        Some("deflate") => ContentEncoding::Deflate,
+        Some(other) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported content-encoding '{other}'"),
+            ))
+        }
+    };
+    let limit = u64::try_from(max_body_bytes).unwrap_or(u64::MAX).saturating_add(1);
+    let mut decoded = Vec::new();
+    match encoding {
+        ContentEncoding::Gzip => GzDecoder::new(body).take(limit).read_to_end(&mut decoded)?,
+        ContentEncoding::Deflate => ZlibDecoder::new(body).take(limit).read_to_end(&mut decoded)?,
+    };
+    if decoded.len() > max_body_bytes {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "decoded response body exceeds max_body_bytes",
+        ));
+    }
+    Ok(decoded)
+}
+
+/// Returns true for a `GET /events` request asking to upgrade to a WebSocket.
+pub fn is_event_stream_upgrade(request: &[u8]) -> bool {
+    let Some(header_end) = find_header_end(request) else {
+        return false;
+    };
+    let headers = &request[..header_end];
+    let Some((method, path)) = parse_request_line(headers) else {
+        return false;
+    };
+    let (path_only, _query) = split_path_and_query(path.as_str());
+    method == "GET"
+        && path_only == "/events"
+        && header_value(&parse_headers(headers), "upgrade")
+            .is_some_and(|value| value.eq_ignore_ascii_case("websocket"))
+}
+
+/// Handles the WebSocket handshake for `/events`. On success the returned
+/// response is a `101 Switching Protocols` and the stream should be pumped
+/// with [`encode_websocket_text_frame`] until it ends; otherwise the response
+/// is a regular error and no stream is returned. An optional `cursor` query
+/// parameter replays retained events after it before live ones.
+pub fn open_event_stream(
+    daemon: &RpcDaemon,
+    request: &[u8],
+    peer_addr: Option<SocketAddr>,
+    transport_auth: Option<TransportAuthContext>,
+) -> io::Result<(Vec<u8>, Option<EventStream>)> {
+    let header_end = find_header_end(request)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing headers"))?;
+    let headers = &request[..header_end];
+    let parsed_headers = parse_headers(headers);
+    let peer_ip = peer_addr.map(|addr| addr.ip().to_string());
+    let (method, path) = parse_request_line(headers)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid request line"))?;
+    let (path_only, query) = split_path_and_query(path.as_str());
+    daemon.metrics_record_http_request(method.as_str(), path_only);
+    if let Err(error) = daemon.authorize_http_request_with_transport(
+        &parsed_headers,
+        peer_ip.as_deref(),
+        transport_auth.as_ref(),
+    ) {
+        return Ok((build_rpc_error_response(0, error)?, None));
+    }
+    let Some(key) = header_value(&parsed_headers, "sec-websocket-key") else {
+        return Ok((build_error_response("missing sec-websocket-key"), None));
+    };
+    let cursor = query_param(query, "cursor");
+    let stream = match daemon.open_event_stream(cursor.as_deref()) {
+        Ok(stream) => stream,
+        Err(error) => return Ok((build_rpc_error_response(0, error)?, None)),
+    };
+    let response = format!(
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+         Sec-WebSocket-Accept: {}\r\n\r\n",
+        websocket_accept_key(key)
+    );
+    Ok((response.into_bytes(), Some(stream)))
+}
+
+/// Wraps a payload in a single unmasked WebSocket text frame, as sent by a server.
+pub fn encode_websocket_text_frame(payload: &[u8]) -> Vec<u8> {
+    encode_websocket_frame(WEBSOCKET_OPCODE_TEXT, payload)
+}
+
+/// A close frame carrying `status`, as sent by a server.
+pub fn encode_websocket_close_frame(status: u16) -> Vec<u8> {
+    encode_websocket_frame(WEBSOCKET_OPCODE_CLOSE, &status.to_be_bytes())
+}
+
+/// Wraps a payload in a single unmasked, final WebSocket frame of `opcode`.
+pub fn encode_websocket_frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(payload.len() + 10);
+    frame.push(0x80 | (opcode & 0x0F));
+    match payload.len() {
+        len @ 0..=125 => frame.push(len as u8),
+        len @ 126..=0xFFFF => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    frame
+}
+
+/// A frame received from a WebSocket client, with its payload unmasked.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WebSocketFrame {
+    pub opcode: u8,
+    pub payload: Vec<u8>,
+}
+
+/// Why a client frame was refused; maps to the close status to send back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WebSocketFrameError {
+    /// Unmasked frame, or a fragmented or oversized control frame.
+    Protocol,
+    /// Payload larger than the caller's limit.
+    TooBig,
+}
+
+impl WebSocketFrameError {
+    pub fn close_status(self) -> u16 {
+        match self {
+            Self::Protocol => WEBSOCKET_CLOSE_PROTOCOL_ERROR,
+            Self::TooBig => WEBSOCKET_CLOSE_TOO_BIG,
+        }
+    }
+}
+
+/// Decodes the client frame at the front of `buffer`, returning it with the
+/// number of bytes it used, or `None` while the frame is still incomplete.
+/// Client frames must be masked, and control frames must be final and carry
+/// at most 125 bytes.
+pub fn decode_websocket_client_frame(
+    buffer: &[u8],
+    max_payload: usize,
+) -> Result<Option<(WebSocketFrame, usize)>, WebSocketFrameError> {
+    let (Some(&first), Some(&second)) = (buffer.first(), buffer.get(1)) else {
+        return Ok(None);
+    };
+    let opcode = first & 0x0F;
+    let is_control = opcode & 0x08 != 0;
+    if second & 0x80 == 0 || (is_control && (first & 0x80 == 0 || second & 0x7F > 125)) {
+        return Err(WebSocketFrameError::Protocol);
+    }
+    let (payload_len, mut offset) = match second & 0x7F {
+        126 => match buffer.get(2..4) {
+            Some(bytes) => (u64::from(u16::from_be_bytes([bytes[0], bytes[1]])), 4),
+            None => return Ok(None),
+        },
+        127 => match buffer.get(2..10) {
+            Some(bytes) => (u64::from_be_bytes(bytes.try_into().expect("eight bytes")), 10),
+            None => return Ok(None),
+        },
+        len => (u64::from(len), 2),
+    };
+    let payload_len = usize::try_from(payload_len)
+        .ok()
+        .filter(|len| *len <= max_payload)
+        .ok_or(WebSocketFrameError::TooBig)?;
+    let Some(mask) = buffer.get(offset..offset + 4) else {
+        return Ok(None);
+    };
+    let mask = [mask[0], mask[1], mask[2], mask[3]];
+    offset += 4;
+    let Some(masked) = buffer.get(offset..offset + payload_len) else {
+        return Ok(None);
+    };
+    let payload = masked.iter().enumerate().map(|(index, byte)| byte ^ mask[index % 4]).collect();
+    Ok(Some((WebSocketFrame { opcode, payload }, offset + payload_len)))
+}
+
+fn websocket_accept_key(key: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(key.trim().as_bytes());
+    hasher.update(WEBSOCKET_ACCEPT_GUID.as_bytes());
+    BASE64_STANDARD.encode(hasher.finalize())
 }
 
 pub fn find_header_end(request: &[u8]) -> Option<usize> {
@@ -194,6 +547,13 @@ fn parse_headers(headers: &[u8]) -> Vec<(String, String)> {
         .collect()
 }
 
+fn header_value<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(header, _)| header.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
 fn split_path_and_query(path: &str) -> (&str, &str) {
     match path.split_once('?') {
         Some((path_only, query)) => (path_only, query),
@@ -281,6 +641,38 @@ fn build_response_with_content_type(
     response
 }
 
+/// Whether a `POST /rpc` frame carries a batch: its MessagePack payload
+/// starts with an array marker (fixarray, array 16 or array 32).
+fn is_batch_frame(frame: &[u8]) -> bool {
+    matches!(frame.get(4), Some(0x90..=0x9f | 0xdc | 0xdd))
+}
+
+/// Dispatches one request of a batch. Transport-level failures become an error
+/// response for that entry so the rest of the batch still runs.
+fn handle_batch_item(daemon: &RpcDaemon, request: RpcRequest) -> RpcResponse {
+    let id = request.id;
+    daemon.handle_rpc(request).unwrap_or_else(|err| {
+        let code = if err.kind() == io::ErrorKind::InvalidInput {
+            "SDK_VALIDATION_INVALID_ARGUMENT"
+        } else {
+            "SDK_INTERNAL_ERROR"
+        };
+        RpcResponse { id, result: None, error: Some(RpcError::new(code, err.to_string())) }
+    })
+}
+
+fn batch_too_large_error(body_bytes: usize, max_body_bytes: usize) -> RpcError {
+    let mut error = RpcError::new(
+        "SDK_VALIDATION_BATCH_TOO_LARGE",
+        format!("rpc batch body of {body_bytes} bytes exceeds max_body_bytes {max_body_bytes}"),
+    );
+    let mut details = serde_json::Map::new();
+    details.insert("body_bytes".to_string(), json!(body_bytes));
+    details.insert("max_body_bytes".to_string(), json!(max_body_bytes));
+    error.details = Some(Box::new(details));
+    error
+}
+
 fn build_rpc_error_response(id: u64, error: crate::rpc::RpcError) -> io::Result<Vec<u8>> {
     let response = RpcResponse { id, result: None, error: Some(error) };
     let body = codec::encode_frame(&response).map_err(io::Error::other)?;
@@ -295,7 +687,7 @@ pub fn build_error_response(message: &str) -> Vec<u8> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::rpc::{RpcDaemon, RpcRequest};
+    use crate::rpc::{RpcDaemon, RpcEvent, RpcRequest};
 
     fn parse_status_line(response: &[u8]) -> &str {
         std::str::from_utf8(response).expect("utf8 response").lines().next().expect("status line")
@@ -423,4 +815,420 @@ mod tests {
                 >= 1
         );
     }
+
+    fn test_event(index: u64) -> RpcEvent {
+        RpcEvent { event_type: "inbound".to_string(), payload: json!({ "index": index }) }
+    }
+
+    fn batch_lines(batch: &[u8]) -> Vec<serde_json::Value> {
+        std::str::from_utf8(batch)
+            .expect("utf8 batch")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("json line"))
+            .collect()
+    }
+
+    #[test]
+    fn websocket_handshake_and_framing_follow_rfc6455() {
+        assert_eq!(
+            websocket_accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+        assert_eq!(encode_websocket_text_frame(b"hi"), vec![0x81, 2, b'h', b'i']);
+        let medium = encode_websocket_text_frame(&[b'x'; 300]);
+        assert_eq!(&medium[..4], &[0x81, 126, 0x01, 0x2C]);
+        let large = encode_websocket_text_frame(&vec![b'x'; 70_000]);
+        assert_eq!(large[1], 127);
+        assert_eq!(large.len(), 70_000 + 10);
+        assert_eq!(encode_websocket_close_frame(1000), vec![0x88, 2, 0x03, 0xE8]);
+    }
+
+    fn masked_client_frame(first: u8, payload: &[u8]) -> Vec<u8> {
+        let mask = [0x12, 0x34, 0x56, 0x78];
+        let mut frame = vec![first, 0x80 | payload.len() as u8];
+        frame.extend_from_slice(&mask);
+        frame.extend(payload.iter().enumerate().map(|(index, byte)| byte ^ mask[index % 4]));
+        frame
+    }
+
+    #[test]
+    fn websocket_client_frames_are_unmasked_and_validated() {
+        let mut buffer = masked_client_frame(0x89, b"ping!");
+        buffer.extend_from_slice(&masked_client_frame(0x88, &[0x03, 0xE8]));
+
+        assert_eq!(decode_websocket_client_frame(&buffer[..6], 125), Ok(None));
+        let (ping, used) =
+            decode_websocket_client_frame(&buffer, 125).expect("valid").expect("complete");
+        assert_eq!(
+            ping,
+            WebSocketFrame { opcode: WEBSOCKET_OPCODE_PING, payload: b"ping!".to_vec() }
+        );
+        let (close, _) =
+            decode_websocket_client_frame(&buffer[used..], 125).expect("valid").expect("complete");
+        assert_eq!(close.opcode, WEBSOCKET_OPCODE_CLOSE);
+        assert_eq!(close.payload, vec![0x03, 0xE8]);
+
+        let unmasked = encode_websocket_text_frame(b"hi");
+        assert_eq!(
+            decode_websocket_client_frame(&unmasked, 125),
+            Err(WebSocketFrameError::Protocol)
+        );
+        let fragmented_ping = masked_client_frame(0x09, b"");
+        assert_eq!(
+            decode_websocket_client_frame(&fragmented_ping, 125),
+            Err(WebSocketFrameError::Protocol)
+        );
+        let text = masked_client_frame(0x81, b"too long");
+        assert_eq!(decode_websocket_client_frame(&text, 4), Err(WebSocketFrameError::TooBig));
+    }
+
+    #[tokio::test]
+    async fn event_stream_upgrade_replays_events_after_cursor() {
+        let daemon = RpcDaemon::test_instance();
+        for index in 1..=3 {
+            daemon.emit_event(test_event(index));
+        }
+        let poll = daemon
+            .handle_rpc(RpcRequest {
+                id: 1,
+                method: "sdk_poll_events_v2".to_string(),
+                params: Some(json!({ "cursor": null, "max": 1 })),
+            })
+            .expect("poll response");
+        let cursor = poll.result.expect("poll result")["next_cursor"]
+            .as_str()
+            .expect("next cursor")
+            .to_string();
+
+        let request = format!(
+            "GET /events?cursor={cursor} HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\n\
+             Connection: Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n"
+        );
+        assert!(is_event_stream_upgrade(request.as_bytes()));
+        let (response, stream) = open_event_stream(
+            &daemon,
+            request.as_bytes(),
+            Some("127.0.0.1:9".parse().expect("socket")),
+            None,
+        )
+        .expect("upgrade response");
+        let response = String::from_utf8(response).expect("utf8 response");
+        assert!(response.starts_with("HTTP/1.1 101 Switching Protocols"));
+        assert!(response.contains("Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+
+        let mut stream = stream.expect("event stream");
+        let lines = batch_lines(&stream.next_batch().await.expect("replay batch"));
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["payload"]["index"], json!(2));
+        assert_eq!(lines[1]["payload"]["index"], json!(3));
+
+        daemon.emit_event(test_event(4));
+        let lines = batch_lines(&stream.next_batch().await.expect("live batch"));
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0]["event_type"], json!("inbound"));
+        assert_eq!(lines[0]["payload"]["index"], json!(4));
+    }
+
+    #[tokio::test]
+    async fn event_stream_reports_lagged_events_as_dropped_count_frame() {
+        let daemon = RpcDaemon::test_instance();
+        let mut stream = daemon.open_event_stream(None).expect("event stream");
+        for index in 0..70 {
+            daemon.emit_event(test_event(index));
+        }
+
+        let lines = batch_lines(&stream.next_batch().await.expect("batch"));
+        assert_eq!(lines[0], json!({ "control": "dropped_count", "dropped_count": 6 }));
+        assert_eq!(lines.len(), 65);
+        assert_eq!(lines[1]["payload"]["index"], json!(6));
+    }
+
+    #[test]
+    fn event_stream_upgrade_rejects_foreign_cursor() {
+        let daemon = RpcDaemon::test_instance();
+        let request = b"GET /events?cursor=v2:other:stream:1 HTTP/1.1\r\nHost: localhost\r\n\
+                        Upgrade: websocket\r\nSec-WebSocket-Key: a2V5\r\n\r\n";
+        let (response, stream) =
+            open_event_stream(&daemon, request, Some("127.0.0.1:9".parse().expect("socket")), None)
+                .expect("error response");
+        assert!(stream.is_none());
+        let header_end = find_header_end(&response).expect("header end");
+        let rpc_response: RpcResponse =
+            codec::decode_frame(&response[header_end + HEADER_END.len()..]).expect("rpc response");
+        let error = rpc_response.error.expect("cursor error");
+        assert_eq!(error.code, "SDK_RUNTIME_INVALID_CURSOR");
+    }
+
+    fn response_header(response: &[u8], name: &str) -> Option<String> {
+        let header_end = find_header_end(response)?;
+        header_value(&parse_headers(&response[..header_end]), name).map(str::to_string)
+    }
+
+    #[test]
+    fn responses_are_gzip_compressed_only_when_accepted_and_above_threshold() {
+        let daemon = RpcDaemon::test_instance();
+        let configured = daemon
+            .handle_rpc(RpcRequest {
+                id: 1,
+                method: "sdk_configure_v2".to_string(),
+                params: Some(json!({
+                    "expected_revision": 0,
+                    "patch": { "rpc_backend": { "min_compress_bytes": 64 } }
+                })),
+            })
+            .expect("configure");
+        assert!(configured.error.is_none());
+        let peer = Some("127.0.0.1:5".parse().expect("socket"));
+
+        let plain = handle_http_request_with_peer(
+            &daemon,
+            b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n",
+            peer,
+        )
+        .expect("plain response");
+        assert!(response_header(&plain, "content-encoding").is_none());
+
+        let small = handle_http_request_with_peer(
+            &daemon,
+            b"GET /healthz HTTP/1.1\r\nHost: localhost\r\nAccept-Encoding: gzip\r\n\r\n",
+            peer,
+        )
+        .expect("small response");
+        assert!(response_header(&small, "content-encoding").is_none());
+
+        let compressed = handle_http_request_with_peer(
+            &daemon,
+            b"GET /metrics HTTP/1.1\r\nHost: localhost\r\nAccept-Encoding: br, gzip\r\n\r\n",
+            peer,
+        )
+        .expect("compressed response");
+        assert_eq!(response_header(&compressed, "content-encoding").as_deref(), Some("gzip"));
+        let body = crate::e2e_harness::parse_http_response_body_with_limit(&compressed, 1_048_576)
+            .expect("decoded body");
+        let metrics: serde_json::Value = serde_json::from_slice(&body).expect("metrics json");
+        assert!(metric_counter(&metrics, "http_requests_total") >= 2);
+        assert!(body.len() > 64);
+    }
+
+    #[test]
+    fn decoded_response_body_is_bounded_by_max_body_bytes() {
+        let daemon = RpcDaemon::test_instance();
+        let request =
+            b"GET /metrics HTTP/1.1\r\nHost: localhost\r\nAccept-Encoding: deflate\r\n\r\n";
+        let response = handle_http_request_with_peer(
+            &daemon,
+            request,
+            Some("127.0.0.1:5".parse().expect("socket")),
+        )
+        .expect("compressed response");
+        assert_eq!(response_header(&response, "content-encoding").as_deref(), Some("deflate"));
+        let decoded = crate::e2e_harness::parse_http_response_body_with_limit(&response, 1_048_576)
+            .expect("decoded body");
+
+        let error =
+            crate::e2e_harness::parse_http_response_body_with_limit(&response, decoded.len() - 1)
+                .expect_err("oversized decoded body");
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(crate::e2e_harness::parse_http_response_body_with_limit(&response, decoded.len())
+            .is_ok());
+    }
+
+    #[test]
+    fn uncompressed_response_body_is_bounded_by_max_body_bytes() {
+        let daemon = RpcDaemon::test_instance();
+        let request = b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n";
+        let response = handle_http_request_with_peer(
+            &daemon,
+            request,
+            Some("127.0.0.1:5".parse().expect("socket")),
+        )
+        .expect("plain response");
+        assert_eq!(response_header(&response, "content-encoding"), None);
+        let body = crate::e2e_harness::parse_http_response_body_with_limit(&response, 1_048_576)
+            .expect("body");
+
+        let error =
+            crate::e2e_harness::parse_http_response_body_with_limit(&response, body.len() - 1)
+                .expect_err("oversized body");
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    fn post_rpc(daemon: &RpcDaemon, body: &[u8]) -> Vec<u8> {
+        let request = crate::e2e_harness::build_http_post("/rpc", "localhost", body);
+        handle_http_request_with_peer(
+            daemon,
+            &request,
+            Some("127.0.0.1:6".parse().expect("socket")),
+        )
+        .expect("rpc response")
+    }
+
+    fn post_rpc_batch(daemon: &RpcDaemon, requests: &[RpcRequest]) -> Vec<RpcResponse> {
+        let body = crate::e2e_harness::build_rpc_batch_frame(requests).expect("batch frame");
+        let response = post_rpc(daemon, &body);
+        assert!(response.starts_with(b"HTTP/1.1 200 OK\r\n"));
+        let body = crate::e2e_harness::parse_http_response_body_with_limit(&response, 1_048_576)
+            .expect("response body");
+        crate::e2e_harness::parse_rpc_batch_frame(&body).expect("batch responses")
+    }
+
+    #[test]
+    fn rpc_batch_returns_responses_in_request_order() {
+        let daemon = RpcDaemon::test_instance();
+        let responses = post_rpc_batch(
+            &daemon,
+            &[
+                RpcRequest { id: 7, method: "list_messages".to_string(), params: None },
+                RpcRequest { id: 3, method: "list_peers".to_string(), params: None },
+            ],
+        );
+        assert_eq!(responses.iter().map(|response| response.id).collect::<Vec<_>>(), vec![7, 3]);
+        assert!(responses.iter().all(|response| response.error.is_none()));
+        assert!(responses[0]
+            .result
+            .as_ref()
+            .is_some_and(|result| result.get("messages").is_some()));
+        assert!(responses[1].result.as_ref().is_some_and(|result| result.get("peers").is_some()));
+
+        let single = crate::e2e_harness::build_rpc_frame(1, "list_peers", None).expect("frame");
+        let response = post_rpc(&daemon, &single);
+        let body = crate::e2e_harness::parse_http_response_body_with_limit(&response, 1_048_576)
+            .expect("response body");
+        assert_eq!(crate::e2e_harness::parse_rpc_frame(&body).expect("single response").id, 1);
+    }
+
+    #[test]
+    fn rpc_batch_isolates_failing_requests() {
+        let daemon = RpcDaemon::test_instance();
+        let responses = post_rpc_batch(
+            &daemon,
+            &[
+                RpcRequest { id: 1, method: "list_peers".to_string(), params: None },
+                RpcRequest {
+                    id: 2,
+                    method: "send_message".to_string(),
+                    params: Some(json!({ "content": 42 })),
+                },
+                RpcRequest { id: 3, method: "list_messages".to_string(), params: None },
+            ],
+        );
+        assert_eq!(responses.iter().map(|response| response.id).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert!(responses[0].error.is_none());
+        assert_eq!(
+            responses[1].error.as_ref().map(|error| error.code.as_str()),
+            Some("SDK_VALIDATION_INVALID_ARGUMENT")
+        );
+        assert!(responses[2].error.is_none());
+    }
+
+    #[test]
+    fn rpc_batch_empty_array_returns_empty_array() {
+        let daemon = RpcDaemon::test_instance();
+        assert!(post_rpc_batch(&daemon, &[]).is_empty());
+    }
+
+    #[test]
+    fn rpc_batch_larger_than_max_body_bytes_is_rejected() {
+        let daemon = RpcDaemon::test_instance();
+        let configured = daemon
+            .handle_rpc(RpcRequest {
+                id: 1,
+                method: "sdk_configure_v2".to_string(),
+                params: Some(json!({
+                    "expected_revision": 0,
+                    "patch": { "rpc_backend": { "max_body_bytes": 1024 } }
+                })),
+            })
+            .expect("configure");
+        assert!(configured.error.is_none(), "{:?}", configured.error);
+
+        let requests = (0..64)
+            .map(|id| RpcRequest {
+                id,
+                method: "list_peers".to_string(),
+                params: Some(json!({ "note": "batch padding" })),
+            })
+            .collect::<Vec<_>>();
+        let body = crate::e2e_harness::build_rpc_batch_frame(&requests).expect("batch frame");
+        assert!(body.len() > 1024);
+        let response = post_rpc(&daemon, &body);
+        let body = crate::e2e_harness::parse_http_response_body_with_limit(&response, 1_048_576)
+            .expect("response body");
+        let response = crate::e2e_harness::parse_rpc_frame(&body).expect("error response");
+        let error = response.error.expect("batch error");
+        assert_eq!(error.code, "SDK_VALIDATION_BATCH_TOO_LARGE");
+        let details = error.details.expect("details");
+        assert_eq!(details["max_body_bytes"], json!(1024));
+
+        // The length alone decides: an undecodable oversized batch gets the
+        // same answer, so the body is never parsed.
+        let mut undecodable = crate::e2e_harness::build_rpc_batch_frame(&requests).expect("frame");
+        let tail = undecodable.len() - 16;
+        undecodable[tail..].fill(0xc1);
+        let response = post_rpc(&daemon, &undecodable);
+        let body = crate::e2e_harness::parse_http_response_body_with_limit(&response, 1_048_576)
+            .expect("response body");
+        let response = crate::e2e_harness::parse_rpc_frame(&body).expect("error response");
+        assert_eq!(response.error.expect("batch error").code, "SDK_VALIDATION_BATCH_TOO_LARGE");
+
+        assert_eq!(post_rpc_batch(&daemon, &requests[..4]).len(), 4);
+    }
+
+    #[test]
+    fn version_requires_auth_like_every_other_rpc() {
+        let daemon = RpcDaemon::test_instance();
+        let negotiated = daemon
+            .handle_rpc(RpcRequest {
+                id: 1,
+                method: "sdk_negotiate_v2".to_string(),
+                params: Some(json!({
+                    "supported_contract_versions": [2],
+                    "requested_capabilities": [],
+                    "config": {
+                        "profile": "desktop-full",
+                        "bind_mode": "remote",
+                        "auth_mode": "api_key",
+                        "rpc_backend": { "api_key_auth": { "api_key": "test-api-key" } }
+                    }
+                })),
+            })
+            .expect("negotiate");
+        assert!(negotiated.error.is_none(), "{:?}", negotiated.error);
+
+        let call = |method: &str, api_key: Option<&str>| {
+            let body = crate::e2e_harness::build_rpc_frame(2, method, None).expect("frame");
+            let mut request = crate::e2e_harness::build_http_post("/rpc", "localhost", &body);
+            if let Some(api_key) = api_key {
+                let header = format!("X-Api-Key: {api_key}\r\n");
+                let at = request.windows(2).position(|pair| pair == b"\r\n").expect("line") + 2;
+                request.splice(at..at, header.into_bytes());
+            }
+            let response = handle_http_request_with_peer(
+                &daemon,
+                &request,
+                Some("203.0.113.9:1442".parse().expect("socket")),
+            )
+            .expect("rpc response");
+            let body =
+                crate::e2e_harness::parse_http_response_body_with_limit(&response, 1_048_576)
+                    .expect("response body");
+            crate::e2e_harness::parse_rpc_frame(&body).expect("rpc frame")
+        };
+
+        for method in ["version", "version_info", "status"] {
+            let denied = call(method, None);
+            assert_eq!(denied.error.expect("auth error").code, "SDK_SECURITY_AUTH_REQUIRED");
+        }
+
+        let version = call("version", Some("test-api-key"));
+        assert!(version.error.is_none(), "{:?}", version.error);
+        let result = version.result.expect("result");
+        assert_eq!(result["contract_release"], json!(crate::rpc::CONTRACT_RELEASE));
+        assert_eq!(result["schema_namespace"], json!("v2"));
+        assert_eq!(result["supported_contract_versions"], json!([2]));
+        let expected_profile = if cfg!(debug_assertions) { "debug" } else { "release" };
+        assert_eq!(result["build_profile"], json!(expected_profile));
+        assert_eq!(result["crate_version"], json!(env!("CARGO_PKG_VERSION")));
+        assert!(result["features"].is_array());
+    }
 }
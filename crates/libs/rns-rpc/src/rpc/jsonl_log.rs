@@ -0,0 +1,114 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// A JSON-lines file the daemon keeps open for appending on its own behalf.
+/// The location is chosen by the host at startup, and the file size is tracked
+/// in memory so callers can decide when to compact without a `stat` per write.
+#[derive(Debug)]
+pub(crate) struct JsonlLog {
+    path: PathBuf,
+    file: File,
+    len: u64,
+}
+
+impl JsonlLog {
+    pub(crate) fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        if path.as_os_str().is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "log path must not be empty"));
+        }
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = Self::open_for_append(&path)?;
+        let len = file.metadata()?.len();
+        Ok(Self { path, file, len })
+    }
+
+    fn open_for_append(path: &Path) -> io::Result<File> {
+        OpenOptions::new().create(true).append(true).open(path)
+    }
+
+    pub(crate) fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub(crate) fn read_to_string(&self) -> io::Result<String> {
+        std::fs::read_to_string(&self.path)
+    }
+
+    pub(crate) fn append(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.file.write_all(bytes)?;
+        self.len = self.len.saturating_add(bytes.len() as u64);
+        Ok(())
+    }
+
+    /// Swaps the file's contents for `body` through a staging file and rename,
+    /// then reopens it so later appends land in the new file.
+    pub(crate) fn replace(&mut self, body: &[u8]) -> io::Result<()> {
+        let staging_path = self.path.with_extension("compact");
+        std::fs::write(&staging_path, body)?;
+        std::fs::rename(&staging_path, &self.path)?;
+        self.file = Self::open_for_append(&self.path)?;
+        self.len = body.len() as u64;
+        Ok(())
+    }
+
+    /// Moves the current file aside to `<path>.1`, replacing any earlier
+    /// rotation, and starts a fresh file at `path`.
+    pub(crate) fn rotate(&mut self) -> io::Result<()> {
+        let mut rotated = self.path.clone().into_os_string();
+        rotated.push(".1");
+        std::fs::rename(&self.path, rotated)?;
+        self.file = Self::open_for_append(&self.path)?;
+        self.len = 0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jsonl_log_tracks_length_across_append_and_replace() {
+        let path = std::env::temp_dir()
+            .join(format!("lxmf-rs-jsonl-log-{}", std::process::id()))
+            .join("log.jsonl");
+        let _ = std::fs::remove_file(&path);
+
+        let mut log = JsonlLog::open(&path).expect("open log");
+        log.append(b"{\"a\":1}\n").expect("append");
+        log.append(b"{\"b\":2}\n").expect("append");
+        assert_eq!(log.len(), 16);
+
+        log.replace(b"{\"b\":2}\n").expect("replace");
+        log.append(b"{\"c\":3}\n").expect("append after replace");
+        assert_eq!(log.read_to_string().expect("read"), "{\"b\":2}\n{\"c\":3}\n");
+        assert_eq!(JsonlLog::open(&path).expect("reopen").len(), log.len());
+
+        let _ = std::fs::remove_dir_all(path.parent().expect("parent"));
+    }
+
+    #[test]
+    fn jsonl_log_rotate_keeps_one_previous_file() {
+        let dir = std::env::temp_dir().join(format!("lxmf-rs-jsonl-rotate-{}", std::process::id()));
+        let path = dir.join("log.jsonl");
+        let rotated = dir.join("log.jsonl.1");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut log = JsonlLog::open(&path).expect("open log");
+        log.append(b"{\"a\":1}\n").expect("append");
+        log.rotate().expect("rotate");
+        log.append(b"{\"b\":2}\n").expect("append");
+        log.rotate().expect("rotate again");
+        log.append(b"{\"c\":3}\n").expect("append after rotate");
+
+        assert_eq!(log.len(), 8);
+        assert_eq!(log.read_to_string().expect("read"), "{\"c\":3}\n");
+        assert_eq!(std::fs::read_to_string(&rotated).expect("rotated"), "{\"b\":2}\n");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,181 @@
+/// Media type assumed for attachments whose leading bytes match no known format.
+pub(crate) const OCTET_STREAM: &str = "application/octet-stream";
+
+/// What the leading bytes of an attachment reveal: its media type and, for
+/// images whose header carries them, the pixel dimensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct MediaInfo {
+    pub content_type: &'static str,
+    pub dimensions: Option<(u32, u32)>,
+}
+
+/// Detects the media type of `bytes` from magic numbers. Unknown formats
+/// report `application/octet-stream`, and dimensions are left out whenever the
+/// header is truncated or malformed rather than guessed.
+pub(crate) fn sniff_media(bytes: &[u8]) -> MediaInfo {
+    let (content_type, dimensions) = if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        ("image/png", png_dimensions(bytes))
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        ("image/jpeg", jpeg_dimensions(bytes))
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        ("image/gif", gif_dimensions(bytes))
+    } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        ("image/webp", webp_dimensions(bytes))
+    } else if bytes.starts_with(b"BM") && bytes.len() >= 26 {
+        ("image/bmp", bmp_dimensions(bytes))
+    } else if bytes.starts_with(b"%PDF-") {
+        ("application/pdf", None)
+    } else if bytes.starts_with(b"PK\x03\x04") {
+        ("application/zip", None)
+    } else if bytes.starts_with(&[0x1F, 0x8B]) {
+        ("application/gzip", None)
+    } else if bytes.starts_with(b"OggS") {
+        ("audio/ogg", None)
+    } else {
+        (OCTET_STREAM, None)
+    };
+    MediaInfo { content_type, dimensions: dimensions.filter(|(w, h)| *w > 0 && *h > 0) }
+}
+
+fn be_u16(bytes: &[u8], at: usize) -> Option<u32> {
+    let raw = bytes.get(at..at + 2)?;
+    Some(u32::from(u16::from_be_bytes([raw[0], raw[1]])))
+}
+
+fn le_u16(bytes: &[u8], at: usize) -> Option<u32> {
+    let raw = bytes.get(at..at + 2)?;
+    Some(u32::from(u16::from_le_bytes([raw[0], raw[1]])))
+}
+
+fn be_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    let raw = bytes.get(at..at + 4)?;
+    Some(u32::from_be_bytes([raw[0], raw[1], raw[2], raw[3]]))
+}
+
+fn le_u24(bytes: &[u8], at: usize) -> Option<u32> {
+    let raw = bytes.get(at..at + 3)?;
+    Some(u32::from_le_bytes([raw[0], raw[1], raw[2], 0]))
+}
+
+fn le_i32(bytes: &[u8], at: usize) -> Option<i32> {
+    let raw = bytes.get(at..at + 4)?;
+    Some(i32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]))
+}
+
+/// The IHDR chunk always comes first, so width and height sit at fixed offsets.
+fn png_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    if bytes.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    Some((be_u32(bytes, 16)?, be_u32(bytes, 20)?))
+}
+
+fn gif_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    Some((le_u16(bytes, 6)?, le_u16(bytes, 8)?))
+}
+
+/// Walks the marker segments up to the first start-of-frame, which holds
+/// height then width.
+fn jpeg_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let mut at = 2;
+    loop {
+        if *bytes.get(at)? != 0xFF {
+            return None;
+        }
+        let marker = *bytes.get(at + 1)?;
+        match marker {
+            0xFF => at += 1,
+            0xD8 | 0x01 | 0xD0..=0xD7 => at += 2,
+            0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                return Some((be_u16(bytes, at + 7)?, be_u16(bytes, at + 5)?));
+            }
+            0xD9 | 0xDA => return None,
+            _ => at += 2 + usize::try_from(be_u16(bytes, at + 2)?).ok()?,
+        }
+    }
+}
+
+fn webp_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    match bytes.get(12..16)? {
+        b"VP8 " => {
+            if bytes.get(23..26)? != [0x9D, 0x01, 0x2A] {
+                return None;
+            }
+            Some((le_u16(bytes, 26)? & 0x3FFF, le_u16(bytes, 28)? & 0x3FFF))
+        }
+        b"VP8L" => {
+            if *bytes.get(20)? != 0x2F {
+                return None;
+            }
+            let bits = u32::from_le_bytes(bytes.get(21..25)?.try_into().ok()?);
+            Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1))
+        }
+        b"VP8X" => Some((le_u24(bytes, 24)? + 1, le_u24(bytes, 27)? + 1)),
+        _ => None,
+    }
+}
+
+/// Bottom-up bitmaps store a negative height, so only its magnitude counts.
+fn bmp_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let width = u32::try_from(le_i32(bytes, 18)?).ok()?;
+    Some((width, le_i32(bytes, 22)?.unsigned_abs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR".to_vec();
+        bytes.extend_from_slice(&width.to_be_bytes());
+        bytes.extend_from_slice(&height.to_be_bytes());
+        bytes.extend_from_slice(&[8, 6, 0, 0, 0]);
+        bytes
+    }
+
+    #[test]
+    fn sniff_media_reads_image_dimensions_from_headers() {
+        assert_eq!(
+            sniff_media(&png(640, 480)),
+            MediaInfo { content_type: "image/png", dimensions: Some((640, 480)) }
+        );
+
+        let gif = b"GIF89a\x20\x03\x58\x02\x00".to_vec();
+        assert_eq!(sniff_media(&gif).dimensions, Some((800, 600)));
+
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x4A, 0x46];
+        jpeg.extend_from_slice(&[0xFF, 0xC0, 0x00, 0x11, 0x08, 0x01, 0xE0, 0x02, 0x80]);
+        assert_eq!(
+            sniff_media(&jpeg),
+            MediaInfo { content_type: "image/jpeg", dimensions: Some((640, 480)) }
+        );
+
+        let mut webp = b"RIFF\x00\x00\x00\x00WEBPVP8X\x0a\x00\x00\x00\x00\x00\x00\x00".to_vec();
+        webp.extend_from_slice(&[0x3F, 0x01, 0x00, 0xEF, 0x00, 0x00]);
+        assert_eq!(sniff_media(&webp).dimensions, Some((320, 240)));
+
+        let mut bmp = vec![0; 26];
+        bmp[..2].copy_from_slice(b"BM");
+        bmp[18..22].copy_from_slice(&16_i32.to_le_bytes());
+        bmp[22..26].copy_from_slice(&(-9_i32).to_le_bytes());
+        assert_eq!(sniff_media(&bmp).dimensions, Some((16, 9)));
+    }
+
+    #[test]
+    fn sniff_media_falls_back_without_guessing() {
+        assert_eq!(
+            sniff_media(b"hello"),
+            MediaInfo { content_type: OCTET_STREAM, dimensions: None }
+        );
+        assert_eq!(sniff_media(&[]).content_type, OCTET_STREAM);
+        assert_eq!(sniff_media(b"%PDF-1.7\n").content_type, "application/pdf");
+
+        let truncated = &png(640, 480)[..18];
+        assert_eq!(
+            sniff_media(truncated),
+            MediaInfo { content_type: "image/png", dimensions: None }
+        );
+        let jpeg_without_frame = [0xFF, 0xD8, 0xFF, 0xD9];
+        assert_eq!(sniff_media(&jpeg_without_frame).dimensions, None);
+    }
+}
@@ -0,0 +1,82 @@
+//! ULID-style message ids: a 48-bit millisecond timestamp followed by 80
+//! random bits, Crockford base32 encoded into 26 characters. Ids sort
+//! lexicographically in creation order, including several minted within the
+//! same millisecond in this process.
+
+use rand_core::{OsRng, RngCore};
+use std::sync::Mutex;
+
+const CROCKFORD_BASE32: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const TIMESTAMP_MASK: u64 = (1 << 48) - 1;
+const RANDOM_MASK: u128 = (1 << 80) - 1;
+const ENCODED_LEN: usize = 26;
+
+/// Last `(timestamp_ms, random)` pair handed out.
+static LAST_ID: Mutex<(u64, u128)> = Mutex::new((0, 0));
+
+/// Mints a new monotonic message id.
+pub fn generate_message_id() -> String {
+    let (timestamp_ms, random) = next_id_parts(super::now_millis_u64());
+    encode(timestamp_ms, random)
+}
+
+fn next_id_parts(now_ms: u64) -> (u64, u128) {
+    let mut last = LAST_ID.lock().expect("message id mutex poisoned");
+    let now_ms = now_ms & TIMESTAMP_MASK;
+    let next = if now_ms > last.0 {
+        (now_ms, random_bits())
+    } else if last.1 < RANDOM_MASK {
+        // Same millisecond, or the clock stepped back: keep the previous
+        // timestamp and bump the random part so ids keep increasing.
+        (last.0, last.1 + 1)
+    } else {
+        (last.0 + 1, random_bits())
+    };
+    *last = next;
+    next
+}
+
+fn random_bits() -> u128 {
+    let mut bytes = [0_u8; 16];
+    OsRng.fill_bytes(&mut bytes[6..]);
+    // Leave headroom so same-millisecond increments rarely carry.
+    (u128::from_be_bytes(bytes) & RANDOM_MASK) >> 1
+}
+
+fn encode(timestamp_ms: u64, random: u128) -> String {
+    let value = (u128::from(timestamp_ms) << 80) | random;
+    (0..ENCODED_LEN)
+        .map(|index| {
+            let shift = 5 * (ENCODED_LEN - 1 - index);
+            char::from(CROCKFORD_BASE32[((value >> shift) & 0x1f) as usize])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_minted_in_the_same_millisecond_are_distinct_and_ordered() {
+        let now_ms = super::super::now_millis_u64();
+        let ids = (0..256)
+            .map(|_| {
+                let (timestamp_ms, random) = next_id_parts(now_ms);
+                encode(timestamp_ms, random)
+            })
+            .collect::<Vec<_>>();
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(ids.iter().all(|id| id.len() == ENCODED_LEN));
+
+        let later = generate_message_id();
+        assert!(ids.last().is_some_and(|last| *last < later));
+    }
+
+    #[test]
+    fn encoding_places_the_timestamp_first() {
+        assert_eq!(encode(0, 0), "0".repeat(ENCODED_LEN));
+        assert_eq!(&encode(1_700_000_000_000, RANDOM_MASK)[..10], "01HF7YAT00");
+        assert!(encode(1, 0) > encode(0, RANDOM_MASK));
+    }
+}
@@ -1,7 +1,12 @@
 pub mod codec;
 mod daemon;
 pub mod event_sink;
+pub mod event_stream;
 pub mod http;
+mod jsonl_log;
+mod media;
+pub mod message_id;
+pub mod outbound_file;
 pub mod replay;
 mod send_request;
 
@@ -9,14 +14,26 @@ use rmpv::Value as MsgPackValue;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Map as JsonMap, Value as JsonValue};
 
-use crate::storage::messages::{AnnounceRecord, MessageRecord, MessagesStore};
+use crate::storage::messages::{
+    reply_to_from_fields, AnnounceRecord, DeliveryTraceEntry, IdempotencyRecord, MessageRecord,
+    MessageSearchFilter, MessagesStore, PeerIdentityRecord, PeerRecord, ScheduledSendRecord,
+};
 use sha2::{Digest, Sha256};
-use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use tokio::sync::broadcast;
 use tokio::time::Duration;
 
-use send_request::parse_outbound_send_request;
+use jsonl_log::JsonlLog;
+use lxmf_core::identity::PrivateIdentity;
+use lxmf_core::message::WireMessage;
+use lxmf_core::propagation::{PEERING_COST, PROPAGATION_COST_MIN, PROPAGATION_LIMIT, SYNC_LIMIT};
+use lxmf_core::stamper::validate_pn_stamp;
+use media::{sniff_media, OCTET_STREAM};
+pub use message_id::generate_message_id;
+pub use send_request::fail_fast_path_destination;
+use send_request::{parse_outbound_send_request, NormalizedSendRequest};
 
 include!("types.rs");
 include!("params.rs");
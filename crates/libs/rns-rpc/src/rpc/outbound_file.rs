@@ -0,0 +1,46 @@
+use super::{OutboundBridge, OutboundDeliveryOptions};
+use crate::MessageRecord;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Outbound bridge that appends every delivered record to a file as one JSON
+/// line and always reports success. Meant for integration tests and offline
+/// demos that need to observe deliveries without a transport.
+pub struct FileOutboundBridge {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl FileOutboundBridge {
+    pub fn new(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if path.as_os_str().is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "outbound file path must not be empty",
+            ));
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self { path, file: Mutex::new(file) })
+    }
+
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+}
+
+impl OutboundBridge for FileOutboundBridge {
+    fn deliver(
+        &self,
+        record: &MessageRecord,
+        _options: &OutboundDeliveryOptions,
+    ) -> Result<(), io::Error> {
+        let mut line = serde_json::to_vec(record).map_err(io::Error::other)?;
+        line.push(b'\n');
+        let mut file = self.file.lock().expect("outbound file mutex poisoned");
+        file.write_all(&line)?;
+        file.flush()
+    }
+}
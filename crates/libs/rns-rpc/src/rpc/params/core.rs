@@ -34,6 +34,8 @@ struct AnnounceReceivedParams {
     #[serde(default)]
     q: Option<f64>,
     #[serde(default)]
+    stamp_cost: Option<u32>,
+    #[serde(default)]
     stamp_cost_flexibility: Option<u32>,
     #[serde(default)]
     peering_cost: Option<u32>,
@@ -61,6 +63,16 @@ struct DeliveryPolicyParams {
     ignored_destinations: Option<Vec<String>>,
     #[serde(default)]
     prioritised_destinations: Option<Vec<String>>,
+    #[serde(default)]
+    auto_contacts: Option<bool>,
+    #[serde(default)]
+    max_title_bytes: Option<usize>,
+    #[serde(default)]
+    title_limit_mode: Option<LengthLimitMode>,
+    #[serde(default)]
+    max_attachment_bytes: Option<usize>,
+    #[serde(default)]
+    max_attachments: Option<usize>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -96,6 +108,10 @@ struct StampPolicySetParams {
     target_cost: Option<u32>,
     #[serde(default)]
     flexibility: Option<u32>,
+    #[serde(default)]
+    generation_workers: Option<usize>,
+    #[serde(default)]
+    mode: Option<StampCostMode>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -105,6 +121,31 @@ struct TicketGenerateParams {
     ttl_secs: Option<u64>,
 }
 
+#[derive(Debug, Deserialize)]
+struct TicketRevokeParams {
+    ticket: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct MessagesSearchParams {
+    #[serde(default)]
+    query: Option<String>,
+    #[serde(default)]
+    source: Option<String>,
+    #[serde(default)]
+    destination: Option<String>,
+    #[serde(default)]
+    from_ts_ms: Option<u64>,
+    #[serde(default)]
+    to_ts_ms: Option<u64>,
+    #[serde(default)]
+    thread: Option<String>,
+    #[serde(default)]
+    limit: Option<usize>,
+    #[serde(default)]
+    cursor: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Default)]
 struct ListAnnouncesParams {
     #[serde(default)]
@@ -115,14 +156,126 @@ struct ListAnnouncesParams {
     cursor: Option<String>,
 }
 
+#[derive(Debug, Deserialize, Default)]
+struct PeerRttParams {
+    #[serde(default)]
+    peer: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct ListPeersParams {
+    #[serde(default)]
+    name_suffix_len: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct IdentityRotateParams {
+    #[serde(default)]
+    confirm: bool,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct AnnounceOnceToParams {
+    #[serde(default)]
+    peer: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SetDisplayNameParams {
+    display_name: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct ReconnectParams {
+    #[serde(default)]
+    interface_name: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct MessagesExportParams {
+    #[serde(default)]
+    peer: Option<String>,
+    #[serde(default)]
+    from_ts_ms: Option<u64>,
+    #[serde(default)]
+    to_ts_ms: Option<u64>,
+    #[serde(default)]
+    limit: Option<usize>,
+    #[serde(default)]
+    cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PeersImportParams {
+    bundle: PeerBundle,
+}
+
+#[derive(Debug, Deserialize)]
+struct PeerDescribeParams {
+    peer: String,
+    #[serde(default)]
+    name_suffix_len: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct PeerPingParams {
+    #[serde(default)]
+    peer: Option<String>,
+    #[serde(default)]
+    ping_id: Option<String>,
+    #[serde(default)]
+    probe: bool,
+    #[serde(default)]
+    timeout_ms: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct LogsTailParams {
+    #[serde(default)]
+    after_offset: Option<u64>,
+    #[serde(default)]
+    max_lines: Option<usize>,
+    /// Minimum level to return; `TRACE` returns every line.
+    #[serde(default)]
+    log_level: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PruneMessagesParams {
+    older_than_ms: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct PeersPruneParams {
+    max_age_secs: u64,
+}
+
 #[derive(Debug, Deserialize)]
 struct SetOutboundPropagationNodeParams {
     #[serde(default)]
     peer: Option<String>,
 }
 
+#[derive(Debug, Default, Deserialize)]
+struct ListPropagationNodesParams {
+    /// Nodes already tried without success in the caller's current attempt.
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AttachmentGetParams {
+    message_id: String,
+    index: usize,
+}
+
 #[derive(Debug, Deserialize)]
 struct MessageDeliveryTraceParams {
     message_id: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct DeliveryMetricsParams {
+    window_ms: u64,
+}
+
@@ -211,6 +211,12 @@ struct SdkTopicSubscriptionV2Params {
     extensions: JsonMap<String, JsonValue>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SdkSendBatchV2Params {
+    messages: Vec<JsonValue>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 struct SdkTopicPublishV2Params {
@@ -560,6 +566,8 @@ struct SdkPollEventsV2Params {
     #[serde(default)]
     cursor: Option<String>,
     max: usize,
+    #[serde(default)]
+    event_types: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -586,6 +594,8 @@ struct SdkConfigureV2Params {
 struct SdkSnapshotV2Params {
     #[serde(default)]
     include_counts: bool,
+    #[serde(default)]
+    since_revision: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -594,6 +604,8 @@ struct SdkShutdownV2Params {
     mode: String,
     #[serde(default)]
     flush_timeout_ms: Option<u64>,
+    #[serde(default)]
+    drain_timeout_ms: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -656,9 +668,19 @@ struct SdkRpcBackendConfig {
     #[serde(default)]
     max_body_bytes: Option<usize>,
     #[serde(default)]
+    min_compress_bytes: Option<usize>,
+    #[serde(default)]
     token_auth: Option<SdkTokenAuthConfig>,
     #[serde(default)]
     mtls_auth: Option<SdkMtlsAuthConfig>,
+    #[serde(default)]
+    api_key_auth: Option<SdkApiKeyAuthConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SdkApiKeyAuthConfig {
+    api_key: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -693,5 +715,7 @@ struct PropagationNodeRecord {
     last_seen: i64,
     #[serde(default)]
     capabilities: Vec<String>,
+    #[serde(default)]
+    peering_cost: Option<u32>,
     selected: bool,
 }
@@ -1,11 +1,13 @@
-use super::OutboundDeliveryOptions;
+use super::message_id::generate_message_id;
+use super::{OutboundDeliveryOptions, RpcRequest};
 use serde::Deserialize;
 use serde_json::Value as JsonValue;
 use std::io::{Error, ErrorKind};
 
 #[derive(Debug, Deserialize)]
 struct SendMessageParams {
-    id: String,
+    #[serde(default)]
+    id: Option<String>,
     source: String,
     destination: String,
     #[serde(default)]
@@ -14,11 +16,14 @@ struct SendMessageParams {
     fields: Option<JsonValue>,
     #[serde(default)]
     source_private_key: Option<String>,
+    #[serde(default)]
+    dry_run: bool,
 }
 
 #[derive(Debug, Deserialize)]
 struct SendMessageV2Params {
-    id: String,
+    #[serde(default)]
+    id: Option<String>,
     source: String,
     destination: String,
     #[serde(default)]
@@ -32,9 +37,17 @@ struct SendMessageV2Params {
     #[serde(default)]
     include_ticket: Option<bool>,
     #[serde(default)]
+    ticket: Option<String>,
+    #[serde(default)]
     try_propagation_on_fail: Option<bool>,
     #[serde(default)]
     source_private_key: Option<String>,
+    #[serde(default)]
+    scheduled_ts_ms: Option<u64>,
+    #[serde(default)]
+    fail_fast_no_path: bool,
+    #[serde(default)]
+    dry_run: bool,
 }
 
 #[derive(Debug)]
@@ -49,12 +62,43 @@ pub(super) struct NormalizedSendRequest {
     pub(super) stamp_cost: Option<u32>,
     pub(super) options: OutboundDeliveryOptions,
     pub(super) include_ticket: Option<bool>,
+    pub(super) dry_run: bool,
 }
 
 pub(super) fn parse_outbound_send_request(
     method: &str,
     params: JsonValue,
 ) -> Result<NormalizedSendRequest, Error> {
+    let request = parse_send_params(method, params)?;
+    if request.destination.trim().is_empty() {
+        return Err(super::invalid_field_error(
+            "destination",
+            "empty",
+            "destination must not be empty",
+        ));
+    }
+    Ok(request)
+}
+
+/// Destination of a send that asked for `fail_fast_no_path`, so hosts that
+/// cannot block inside the handler can wait for the path before dispatching.
+pub fn fail_fast_path_destination(request: &RpcRequest) -> Option<String> {
+    if !matches!(request.method.as_str(), "send_message_v2" | "sdk_send_v2") {
+        return None;
+    }
+    let params = request.params.as_ref()?;
+    if params.get("fail_fast_no_path").and_then(JsonValue::as_bool) != Some(true) {
+        return None;
+    }
+    params
+        .get("destination")
+        .and_then(JsonValue::as_str)
+        .map(str::trim)
+        .filter(|destination| !destination.is_empty())
+        .map(str::to_string)
+}
+
+fn parse_send_params(method: &str, params: JsonValue) -> Result<NormalizedSendRequest, Error> {
     match method {
         "send_message" => {
             let parsed: SendMessageParams = serde_json::from_value(params)
@@ -65,7 +109,7 @@ pub(super) fn parse_outbound_send_request(
                 ..Default::default()
             };
             Ok(NormalizedSendRequest {
-                id: parsed.id,
+                id: message_id_or_generated(parsed.id),
                 source: parsed.source,
                 destination: parsed.destination,
                 title: parsed.title,
@@ -75,6 +119,7 @@ pub(super) fn parse_outbound_send_request(
                 stamp_cost: None,
                 options,
                 include_ticket: None,
+                dry_run: parsed.dry_run,
             })
         }
         "send_message_v2" | "sdk_send_v2" => {
@@ -84,7 +129,7 @@ pub(super) fn parse_outbound_send_request(
             let outbound_method = parsed.method.clone();
             let include_ticket = parsed.include_ticket;
             Ok(NormalizedSendRequest {
-                id: parsed.id,
+                id: message_id_or_generated(parsed.id),
                 source: parsed.source,
                 destination: parsed.destination,
                 title: parsed.title,
@@ -97,10 +142,14 @@ pub(super) fn parse_outbound_send_request(
                     stamp_cost: parsed.stamp_cost,
                     include_ticket: include_ticket.unwrap_or_default(),
                     try_propagation_on_fail: parsed.try_propagation_on_fail.unwrap_or_default(),
-                    ticket: None,
+                    ticket: parsed.ticket,
                     source_private_key: parsed.source_private_key,
+                    stamp_task: None,
+                    scheduled_ts_ms: parsed.scheduled_ts_ms,
+                    fail_fast_no_path: parsed.fail_fast_no_path,
                 },
                 include_ticket,
+                dry_run: parsed.dry_run,
             })
         }
         _ => {
@@ -109,6 +158,11 @@ pub(super) fn parse_outbound_send_request(
     }
 }
 
+/// Client-supplied ids are kept as-is; missing or blank ones are minted here.
+fn message_id_or_generated(id: Option<String>) -> String {
+    id.filter(|id| !id.trim().is_empty()).unwrap_or_else(generate_message_id)
+}
+
 fn validate_outbound_fields_strict(fields: Option<&JsonValue>) -> Result<(), Error> {
     let Some(JsonValue::Object(map)) = fields else {
         return Ok(());
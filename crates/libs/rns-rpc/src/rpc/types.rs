@@ -24,7 +24,8 @@ pub struct RpcError {
     pub retryable: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub is_user_actionable: Option<bool>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Machine-readable context. Always serialized, as an empty object when unset.
+    #[serde(default, serialize_with = "serialize_error_details")]
     pub details: Option<Box<JsonMap<String, JsonValue>>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cause_code: Option<String>,
@@ -57,6 +58,13 @@ impl RpcError {
         }
     }
 
+    /// Attaches machine-readable context, such as the `{ field, reason }` of
+    /// a validation failure.
+    pub fn with_details(mut self, details: JsonMap<String, JsonValue>) -> Self {
+        self.details = Some(Box::new(details));
+        self
+    }
+
     fn category_for_code(code: &str) -> Option<String> {
         if code.contains("_VALIDATION_") {
             return Some("Validation".to_string());
@@ -95,6 +103,16 @@ impl RpcError {
     }
 }
 
+fn serialize_error_details<S: serde::Serializer>(
+    details: &Option<Box<JsonMap<String, JsonValue>>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match details {
+        Some(details) => details.serialize(serializer),
+        None => JsonMap::new().serialize(serializer),
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct InterfaceRecord {
     #[serde(rename = "type")]
@@ -105,13 +123,59 @@ pub struct InterfaceRecord {
     pub name: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct DeliveryPolicy {
     pub auth_required: bool,
     pub allowed_destinations: Vec<String>,
     pub denied_destinations: Vec<String>,
     pub ignored_destinations: Vec<String>,
     pub prioritised_destinations: Vec<String>,
+    #[serde(default)]
+    pub auto_contacts: bool,
+    #[serde(default)]
+    pub max_title_bytes: usize,
+    #[serde(default)]
+    pub title_limit_mode: LengthLimitMode,
+    /// Upper bound on the decoded bytes of all attachments in one send; 0 disables the check.
+    #[serde(default = "default_max_attachment_bytes")]
+    pub max_attachment_bytes: usize,
+    /// Upper bound on the number of attachments in one send; 0 disables the check.
+    #[serde(default = "default_max_attachments")]
+    pub max_attachments: usize,
+}
+
+impl Default for DeliveryPolicy {
+    fn default() -> Self {
+        Self {
+            auth_required: false,
+            allowed_destinations: Vec::new(),
+            denied_destinations: Vec::new(),
+            ignored_destinations: Vec::new(),
+            prioritised_destinations: Vec::new(),
+            auto_contacts: false,
+            max_title_bytes: 0,
+            title_limit_mode: LengthLimitMode::default(),
+            max_attachment_bytes: DEFAULT_MAX_ATTACHMENT_BYTES,
+            max_attachments: DEFAULT_MAX_ATTACHMENTS,
+        }
+    }
+}
+
+fn default_max_attachment_bytes() -> usize {
+    DEFAULT_MAX_ATTACHMENT_BYTES
+}
+
+fn default_max_attachments() -> usize {
+    DEFAULT_MAX_ATTACHMENTS
+}
+
+/// How an outbound text field longer than its configured limit is handled.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LengthLimitMode {
+    #[default]
+    Reject,
+    Truncate,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
@@ -130,12 +194,157 @@ pub struct PropagationState {
     pub last_sync_started: Option<i64>,
     pub last_sync_completed: Option<i64>,
     pub last_sync_error: Option<String>,
+    #[serde(default)]
+    pub total_fetched: usize,
+    #[serde(default)]
+    pub fetch_misses: usize,
+    #[serde(default)]
+    pub rejected_stamps: usize,
+}
+
+/// How the propagation stamp cost is derived. `Static` always requires the
+/// configured cost; `Adaptive` scales it with how full the propagation store is.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StampCostMode {
+    #[default]
+    Static,
+    Adaptive,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
 pub struct StampPolicy {
     pub target_cost: u32,
     pub flexibility: u32,
+    #[serde(default)]
+    pub generation_workers: usize,
+    #[serde(default)]
+    pub mode: StampCostMode,
+}
+
+/// Shared handle for an outbound stamp being generated by the delivery bridge.
+#[derive(Debug, Clone, Default)]
+pub struct StampTaskHandle {
+    target_cost: u32,
+    workers: usize,
+    cancel: Arc<AtomicBool>,
+}
+
+impl StampTaskHandle {
+    pub fn target_cost(&self) -> u32 {
+        self.target_cost
+    }
+
+    pub fn workers(&self) -> usize {
+        self.workers
+    }
+
+    pub fn cancel_flag(&self) -> &AtomicBool {
+        &self.cancel
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+impl PartialEq for StampTaskHandle {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.cancel, &other.cancel)
+    }
+}
+
+impl Eq for StampTaskHandle {}
+
+/// Result of an active reachability probe against a peer.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PeerPingOutcome {
+    Reachable,
+    /// No path to the peer could be resolved before the timeout.
+    NoPath,
+    /// A path is known but the round-trip probe went unanswered.
+    NoResponse,
+}
+
+/// Cumulative traffic carried by one interface since daemon start, as
+/// reported by the transport workers.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InterfaceTraffic {
+    pub bytes_in: u64,
+    pub bytes_out: u64,
+    pub packets_in: u64,
+    pub packets_out: u64,
+    /// Epoch seconds of the last packet in either direction.
+    pub last_activity_secs: Option<u64>,
+}
+
+/// Connection state of a transport interface, published as
+/// `interface_link_state` events when it changes.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InterfaceLinkState {
+    Connecting,
+    Connected,
+    Disconnected,
+}
+
+/// A reachability probe handed to the outbound bridge by `peer_ping`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerPingRequest {
+    pub ping_id: String,
+    pub peer: String,
+    pub probe: bool,
+    pub timeout_ms: u64,
+}
+
+/// A propagation sync handed to the outbound bridge by `sync_now`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropagationSyncRequest {
+    pub node: String,
+    pub max_messages: usize,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+struct PeerPingRecord {
+    ping_id: String,
+    peer: String,
+    probe: bool,
+    outcome: Option<PeerPingOutcome>,
+    rtt_ms: Option<u64>,
+    started_at_ms: u64,
+    finished_at_ms: Option<u64>,
+}
+
+/// An outbound message stored with a future `scheduled_ts_ms`, waiting for
+/// [`RpcDaemon::dispatch_due_scheduled_sends`].
+#[derive(Debug)]
+struct ScheduledSend {
+    due_ms: u64,
+    record: MessageRecord,
+    method: Option<String>,
+    stamp_cost: Option<u32>,
+    options: OutboundDeliveryOptions,
+}
+
+/// An outbound message still waiting for a terminal receipt, failed by
+/// [`RpcDaemon::expire_stale_receipts`] once it stops making progress.
+#[derive(Debug, Clone, Copy)]
+struct ReceiptWatch {
+    last_progress_ms: u64,
+    propagated: bool,
+}
+
+/// A payload held by this node as a propagation node, dropped once
+/// `expires_at_ms` passes.
+#[derive(Debug, Clone)]
+struct PropagationPayload {
+    payload_hex: String,
+    expires_at_ms: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -145,14 +354,6 @@ pub struct TicketRecord {
     pub expires_at: i64,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
-pub struct DeliveryTraceEntry {
-    pub status: String,
-    pub timestamp: i64,
-    #[serde(default)]
-    pub reason_code: Option<String>,
-}
-
 const RPC_METRIC_LATENCY_BUCKETS_MS: [u64; 10] = [1, 5, 10, 25, 50, 100, 250, 500, 1_000, 5_000];
 
 #[derive(Debug, Clone)]
@@ -235,6 +436,11 @@ struct RpcMetrics {
     sdk_event_sink_error_total: u64,
     sdk_event_sink_skipped_total: u64,
     sdk_auth_failures_total: u64,
+    sdk_audit_log_errors_total: u64,
+    inbound_decode_dropped_total: u64,
+    inbound_rate_limited_total: u64,
+    inbound_blocked_total: u64,
+    duplicate_suppressed_total: u64,
     http_requests_by_route: BTreeMap<String, u64>,
     rpc_requests_by_method: BTreeMap<String, u64>,
     rpc_errors_by_method: BTreeMap<String, u64>,
@@ -245,26 +451,181 @@ struct RpcMetrics {
     sdk_auth_latency_ms: RpcLatencyHistogram,
 }
 
+/// Bounded tail of daemon log lines. Offsets count every line ever recorded,
+/// so readers can resume from an offset and tell how many lines were evicted.
+#[derive(Debug, Default)]
+struct DaemonLogBuffer {
+    lines: VecDeque<String>,
+    first_offset: u64,
+}
+
+/// Transient hashes of recently stored inbound messages, oldest first.
+/// Older history is checked against the store instead.
+#[derive(Debug, Default)]
+struct InboundDedupWindow {
+    order: VecDeque<String>,
+    seen: HashSet<String>,
+}
+
+impl InboundDedupWindow {
+    /// Remembers a stored message, evicting the oldest past the window.
+    fn claim(&mut self, transient_hash: String) {
+        if !self.seen.insert(transient_hash.clone()) {
+            return;
+        }
+        self.order.push_back(transient_hash);
+        while self.order.len() > INBOUND_DEDUP_WINDOW {
+            if let Some(evicted) = self.order.pop_front() {
+                self.seen.remove(&evicted);
+            }
+        }
+    }
+}
+
+/// Severity used to filter `logs_tail`. Ordered from most to least verbose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum DaemonLogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+/// Outbound send times awaiting a delivery receipt, and the recent round-trip
+/// samples per peer derived from them.
+#[derive(Debug, Default)]
+struct PeerRttState {
+    pending: HashMap<String, (String, u64)>,
+    samples: HashMap<String, VecDeque<u64>>,
+}
+
+/// Last value of each `sdk_snapshot_v2` field and the snapshot revision at
+/// which it last changed, so callers can ask only for what moved since a
+/// revision they already hold.
+#[derive(Debug, Default)]
+struct SnapshotRevisions {
+    revision: u64,
+    fields: BTreeMap<String, (JsonValue, u64)>,
+}
+
+/// A propagation node as last announced, for automatic reselection.
+#[derive(Debug, Clone, Copy)]
+struct PropagationCandidate {
+    last_seen: i64,
+    stamp_cost: Option<u32>,
+}
+
+/// Announced propagation nodes and the best of them by stamp cost, then
+/// recency. Kept up to date as announces arrive so reselection does not
+/// rescan the announce table.
+#[derive(Debug, Default)]
+struct PropagationCandidates {
+    loaded: bool,
+    nodes: HashMap<String, PropagationCandidate>,
+    best: Option<String>,
+}
+
+impl PropagationCandidates {
+    fn rank<'a>(
+        peer: &'a str,
+        candidate: &PropagationCandidate,
+    ) -> (u32, std::cmp::Reverse<i64>, &'a str) {
+        (candidate.stamp_cost.unwrap_or(u32::MAX), std::cmp::Reverse(candidate.last_seen), peer)
+    }
+
+    /// Records a stored announce if it comes from a propagation node. An
+    /// explicit `stamp_cost` wins over the one encoded in its app data.
+    fn observe_announce(&mut self, announce: &AnnounceRecord, stamp_cost: Option<u32>) {
+        if !announce.capabilities.iter().any(|cap| cap == "propagation") {
+            return;
+        }
+        let stamp_cost = stamp_cost
+            .or_else(|| parse_announce_stamp_cost_from_app_data_hex(announce.app_data_hex.as_deref()));
+        self.observe(&announce.peer, announce.timestamp, stamp_cost);
+    }
+
+    /// Records an announce, ignoring ones older than what is already known.
+    fn observe(&mut self, peer: &str, last_seen: i64, stamp_cost: Option<u32>) {
+        let candidate = PropagationCandidate { last_seen, stamp_cost };
+        if let Some(known) = self.nodes.get(peer) {
+            if known.last_seen > last_seen {
+                return;
+            }
+        }
+        let previous = self.nodes.insert(peer.to_string(), candidate);
+        let best = self.best.as_deref().and_then(|best| Some((best, self.nodes.get(best)?)));
+        match best {
+            Some((best, _)) if best == peer => {
+                let cost = |node: PropagationCandidate| node.stamp_cost.unwrap_or(u32::MAX);
+                let got_worse = previous.is_some_and(|previous| cost(previous) < cost(candidate));
+                if got_worse {
+                    self.best = self.min_by_rank(|_, _| true);
+                }
+            }
+            Some((best, current)) if Self::rank(best, current) <= Self::rank(peer, &candidate) => {}
+            _ => self.best = Some(peer.to_string()),
+        }
+    }
+
+    fn last_seen(&self, peer: &str) -> Option<i64> {
+        self.nodes.get(peer).map(|candidate| candidate.last_seen)
+    }
+
+    /// The best node other than `previous` seen at or after `cutoff`.
+    fn replacement(&self, previous: &str, cutoff: i64) -> Option<(String, PropagationCandidate)> {
+        let fresh = |peer: &str, candidate: &PropagationCandidate| {
+            peer != previous && candidate.last_seen >= cutoff
+        };
+        let peer = match self.best.as_deref() {
+            Some(best) if self.nodes.get(best).is_some_and(|node| fresh(best, node)) => {
+                Some(best.to_string())
+            }
+            _ => self.min_by_rank(fresh),
+        }?;
+        let candidate = *self.nodes.get(&peer)?;
+        Some((peer, candidate))
+    }
+
+    fn min_by_rank(&self, keep: impl Fn(&str, &PropagationCandidate) -> bool) -> Option<String> {
+        self.nodes
+            .iter()
+            .filter(|(peer, candidate)| keep(peer, candidate))
+            .min_by(|(a, a_node), (b, b_node)| Self::rank(a, a_node).cmp(&Self::rank(b, b_node)))
+            .map(|(peer, _)| peer.clone())
+    }
+}
+
 pub struct RpcDaemon {
     store: MessagesStore,
     identity_hash: String,
+    /// Identity hash after an `identity_rotate`; `identity_hash` stays the
+    /// runtime id so event cursors survive the rotation.
+    rotated_identity_hash: Mutex<Option<String>>,
     delivery_destination_hash: Mutex<Option<String>>,
+    local_display_name: Mutex<Option<String>>,
     events: broadcast::Sender<RpcEvent>,
     event_queue: Mutex<VecDeque<RpcEvent>>,
     sdk_event_log: Mutex<VecDeque<SequencedRpcEvent>>,
     sdk_next_event_seq: Mutex<u64>,
     sdk_dropped_event_count: Mutex<u64>,
+    sdk_event_persist: Mutex<Option<JsonlLog>>,
+    dead_letter_log: Mutex<Option<DeadLetterSink>>,
+    sdk_audit_log: Mutex<Option<SecurityAuditSink>>,
     sdk_active_contract_version: Mutex<u16>,
     sdk_profile: Mutex<String>,
     sdk_config_revision: Mutex<u64>,
+    sdk_snapshot_revisions: Mutex<SnapshotRevisions>,
     sdk_runtime_config: Mutex<JsonValue>,
     sdk_config_apply_lock: Mutex<()>,
     sdk_effective_capabilities: Mutex<Vec<String>>,
     sdk_stream_degraded: Mutex<bool>,
+    sdk_draining: Mutex<bool>,
     sdk_seen_jti: Mutex<HashMap<String, u64>>,
     sdk_rate_window_started_ms: Mutex<u64>,
     sdk_rate_ip_counts: Mutex<HashMap<String, u32>>,
     sdk_rate_principal_counts: Mutex<HashMap<String, u32>>,
+    sdk_destination_send_times: Mutex<HashMap<String, VecDeque<u64>>>,
     sdk_domain_state_lock: Mutex<()>,
     sdk_next_domain_seq: Mutex<u64>,
     sdk_topics: Mutex<HashMap<String, SdkTopicRecord>>,
@@ -285,31 +646,180 @@ pub struct RpcDaemon {
     sdk_voice_sessions: Mutex<HashMap<String, SdkVoiceSessionRecord>>,
     peers: Mutex<HashMap<String, PeerRecord>>,
     interfaces: Mutex<Vec<InterfaceRecord>>,
+    interfaces_down: Mutex<HashSet<String>>,
+    interface_link_states: Mutex<HashMap<String, InterfaceLinkState>>,
+    interface_traffic: Mutex<HashMap<String, InterfaceTraffic>>,
+    last_announce_ts_ms: Mutex<Option<u64>>,
+    announce_interval_default_secs: Mutex<u64>,
+    announce_rearm: tokio::sync::Notify,
     delivery_policy: Mutex<DeliveryPolicy>,
     propagation_state: Mutex<PropagationState>,
-    propagation_payloads: Mutex<HashMap<String, String>>,
+    propagation_payloads: Mutex<HashMap<String, PropagationPayload>>,
+    propagation_expired_ids: Mutex<VecDeque<String>>,
     outbound_propagation_node: Mutex<Option<String>>,
+    outbound_propagation_node_pinned: Mutex<bool>,
+    propagation_candidates: Mutex<PropagationCandidates>,
     paper_ingest_seen: Mutex<HashSet<String>>,
     stamp_policy: Mutex<StampPolicy>,
+    stamp_tasks: Mutex<HashMap<String, StampTaskHandle>>,
     ticket_cache: Mutex<HashMap<String, TicketRecord>>,
+    revoked_tickets: Mutex<HashSet<String>>,
     delivery_traces: Mutex<HashMap<String, Vec<DeliveryTraceEntry>>>,
     delivery_status_lock: Mutex<()>,
     sdk_metrics: Mutex<RpcMetrics>,
+    daemon_log: Mutex<DaemonLogBuffer>,
+    inbound_dedup: Mutex<InboundDedupWindow>,
+    peer_rtt: Mutex<PeerRttState>,
+    peer_pings: Mutex<VecDeque<PeerPingRecord>>,
+    scheduled_sends: Mutex<Vec<ScheduledSend>>,
+    receipt_watches: Mutex<HashMap<String, ReceiptWatch>>,
+    inbound_pending_probe: Mutex<Option<InboundPendingProbe>>,
     outbound_bridge: Option<Arc<dyn OutboundBridge>>,
     announce_bridge: Option<Arc<dyn AnnounceBridge>>,
     event_sink_bridges: Vec<Arc<dyn EventSinkBridge>>,
 }
 
+/// Reports how many inbound frames are waiting to be decoded.
+pub type InboundPendingProbe = Arc<dyn Fn() -> u64 + Send + Sync>;
+
 pub trait OutboundBridge: Send + Sync {
     fn deliver(
         &self,
         record: &MessageRecord,
         options: &OutboundDeliveryOptions,
     ) -> Result<(), std::io::Error>;
+
+    /// Plans delivery of `record` without transmitting it. Bridges that encode
+    /// the wire message should override this to report the exact size.
+    fn plan(
+        &self,
+        record: &MessageRecord,
+        options: &OutboundDeliveryOptions,
+    ) -> Result<OutboundPlan, std::io::Error> {
+        Ok(estimate_outbound_plan(record, options))
+    }
+
+    /// Starts a reachability probe; the outcome is reported back through
+    /// [`RpcDaemon::record_peer_ping_result`].
+    fn ping(&self, _request: &PeerPingRequest) -> Result<(), std::io::Error> {
+        Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "peer ping not supported"))
+    }
+
+    /// Requests a path to `destination` and reports whether one is known
+    /// within `timeout`. Bridges without path knowledge report `Unsupported`,
+    /// which fails fail-fast sends with `SDK_CAPABILITY_DISABLED`.
+    fn await_path(&self, _destination: &str, _timeout: Duration) -> Result<bool, std::io::Error> {
+        Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "path lookup not supported"))
+    }
+
+    /// Stops any delivery work still running for `message_id`, such as link
+    /// setup or a fallback attempt, so a message reported as failed or
+    /// cancelled is not sent afterwards. Reports whether work was stopped.
+    fn cancel_delivery(&self, _message_id: &str) -> Result<bool, std::io::Error> {
+        Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "delivery cancel not supported"))
+    }
+
+    /// Returns the hex public identity (encryption then signing key) the
+    /// transport holds for `destination`, if it has seen one.
+    fn peer_public_key(&self, _destination: &str) -> Result<Option<String>, std::io::Error> {
+        Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "peer identities not supported"))
+    }
+
+    /// Registers `public_key`, in the form returned by [`Self::peer_public_key`],
+    /// as the identity behind `destination` so messages to it can be encrypted
+    /// before it is heard announcing. Fails with `InvalidInput` when the key
+    /// does not derive that destination.
+    fn restore_peer_identity(
+        &self,
+        _destination: &str,
+        _public_key: &str,
+    ) -> Result<(), std::io::Error> {
+        Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "peer identities not supported"))
+    }
+
+    /// Forgets the identities the transport holds for `destinations`, so a
+    /// pruned peer has to announce again before it can be reached.
+    fn forget_peers(&self, _destinations: &[String]) -> Result<(), std::io::Error> {
+        Ok(())
+    }
+
+    /// Drops and re-establishes the named interface's connection, keeping
+    /// queued traffic. Reports `false` when the transport has no such interface.
+    fn reconnect_interface(&self, _name: &str) -> Result<bool, std::io::Error> {
+        Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "reconnect not supported"))
+    }
+    /// Starts downloading the messages waiting on a propagation node. Each
+    /// step is reported back through [`RpcDaemon::update_propagation_sync_state`]
+    /// and received messages through [`RpcDaemon::accept_inbound`].
+    fn sync_propagation(&self, _request: &PropagationSyncRequest) -> Result<(), std::io::Error> {
+        Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "propagation sync not supported"))
+    }
 }
 
 pub trait AnnounceBridge: Send + Sync {
     fn announce_now(&self) -> Result<(), std::io::Error>;
+
+    /// Starts an announce toward a single peer, identified by its destination
+    /// hash, along the path the transport already holds for it, and refreshes
+    /// that path. Whether anything was announced is reported back through
+    /// [`RpcDaemon::record_directed_announce`].
+    fn announce_to(&self, _destination: &str) -> Result<(), std::io::Error> {
+        Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "directed announce not supported"))
+    }
+
+    /// Replaces the local identity with a freshly generated one, persists it
+    /// and starts announcing the new delivery destination. Messages signed
+    /// under the previous identity keep it until they reach a terminal state.
+    /// The announce outcome is reported back through
+    /// [`RpcDaemon::record_identity_announce`].
+    fn rotate_identity(&self) -> Result<IdentityRotation, std::io::Error> {
+        Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "identity rotation not supported"))
+    }
+
+    /// Replaces and persists the display name carried in delivery announces,
+    /// then announces it. Returns the normalized name and whether the
+    /// announce went out; names that cannot be carried on the wire fail with
+    /// `InvalidInput` and leave the current name in place.
+    fn set_display_name(&self, _display_name: &str) -> Result<DisplayNameUpdate, std::io::Error> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "display name updates not supported",
+        ))
+    }
+
+    /// Sets whether the announce app-data advertises this node as a
+    /// propagation node. Takes effect on the next announce.
+    fn set_propagation_node(&self, _enabled: bool) -> Result<(), std::io::Error> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "propagation node role not supported",
+        ))
+    }
+
+    /// Sets the stamp cost and flexibility advertised in propagation node
+    /// announces. Takes effect on the next announce.
+    fn set_propagation_stamp_cost(
+        &self,
+        _cost: u32,
+        _flexibility: u32,
+    ) -> Result<(), std::io::Error> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "propagation stamp cost not supported",
+        ))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct IdentityRotation {
+    pub identity_hash: String,
+    pub delivery_destination_hash: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct DisplayNameUpdate {
+    pub display_name: String,
+    pub announced: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -342,6 +852,27 @@ pub struct OutboundDeliveryOptions {
     pub ticket: Option<String>,
     #[serde(default)]
     pub source_private_key: Option<String>,
+    #[serde(skip)]
+    pub stamp_task: Option<StampTaskHandle>,
+    /// Wall-clock send time in unix milliseconds. The daemon holds the message
+    /// until then; a missing or past time sends immediately.
+    #[serde(default)]
+    pub scheduled_ts_ms: Option<u64>,
+    /// Reject the send with `SDK_RUNTIME_NO_PATH` instead of queueing it when
+    /// no path to the destination turns up within the fail-fast deadline.
+    #[serde(default)]
+    pub fail_fast_no_path: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct OutboundPlan {
+    pub method: String,
+    #[serde(default)]
+    pub wire_size: Option<usize>,
+    #[serde(default)]
+    pub target_interface: Option<String>,
+    #[serde(default)]
+    pub destination_identity_known: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -356,16 +887,72 @@ struct SequencedRpcEvent {
     event: RpcEvent,
 }
 
+#[derive(Debug, Clone, Copy)]
+struct EventPersistLimits {
+    max_bytes: u64,
+    max_age_secs: u64,
+}
+
+/// One line of the security audit log: the outcome of a single HTTP auth decision.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
-pub struct PeerRecord {
-    pub peer: String,
-    pub last_seen: i64,
-    #[serde(default)]
-    pub name: Option<String>,
+struct SecurityAuditRecord {
+    ts_ms: u64,
+    decision: String,
+    outcome: String,
+    reason_code: String,
+    source: String,
+    principal: Option<String>,
+    auth_mode: String,
+}
+
+/// Where security audit records go, fixed by the host at startup.
+#[derive(Debug)]
+struct SecurityAuditSink {
+    log: JsonlLog,
+    include_allowed: bool,
+}
+
+/// Where expired store-and-forward messages are logged, fixed by the host at
+/// startup. The file is rotated once it grows past `max_bytes`.
+#[derive(Debug)]
+struct DeadLetterSink {
+    log: JsonlLog,
+    max_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistedRpcEvent {
+    seq_no: u64,
+    persisted_at: i64,
+    event: RpcEvent,
+}
+
+/// One page of the conversation archive produced by `messages_export`: stored
+/// messages matching the filter, oldest first, with fields and receipt status.
+/// `next_cursor` resumes the export after this page; `None` on the last one.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct MessageBundle {
+    pub version: String,
+    pub exported_at: i64,
+    pub peer: Option<String>,
+    pub from_ts_ms: Option<u64>,
+    pub to_ts_ms: Option<u64>,
+    pub messages: Vec<MessageRecord>,
+    pub next_cursor: Option<String>,
+}
+
+/// Known peers and propagation nodes exported by `peers_export` so another node
+/// can be seeded with them through `peers_import`. `version` names the format.
+/// `identities` maps destination hashes to the hex public identity behind them,
+/// which the importing node needs to encrypt messages to those peers.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PeerBundle {
+    pub version: String,
+    pub exported_at: i64,
     #[serde(default)]
-    pub name_source: Option<String>,
+    pub peers: Vec<PeerRecord>,
     #[serde(default)]
-    pub first_seen: i64,
+    pub propagation_nodes: Vec<AnnounceRecord>,
     #[serde(default)]
-    pub seen_count: u64,
+    pub identities: BTreeMap<String, String>,
 }
@@ -1,4 +1,5 @@
 use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize)]
@@ -12,6 +13,88 @@ pub struct MessageRecord {
     pub direction: String,
     pub fields: Option<JsonValue>,
     pub receipt_status: Option<String>,
+    /// Parent message id carried in the LXMF thread field, for replies.
+    pub reply_to: Option<String>,
+}
+
+/// An outbound message held until `due_ms`, persisted so a restart keeps it
+/// scheduled. `options` is the serialized delivery options of the send.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledSendRecord {
+    pub message_id: String,
+    pub due_ms: u64,
+    pub method: Option<String>,
+    pub stamp_cost: Option<u32>,
+    pub options: JsonValue,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PeerRecord {
+    pub peer: String,
+    pub last_seen: i64,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub name_source: Option<String>,
+    #[serde(default)]
+    pub first_seen: i64,
+    #[serde(default)]
+    pub seen_count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DeliveryTraceEntry {
+    pub status: String,
+    pub timestamp: i64,
+    /// The same instant as `timestamp`, in milliseconds. Entries persisted
+    /// before this was recorded are migrated to `timestamp * 1000`.
+    #[serde(default)]
+    pub timestamp_ms: i64,
+    #[serde(default)]
+    pub reason_code: Option<String>,
+    #[serde(default)]
+    pub detail: Option<String>,
+}
+
+/// A peer seeded through `peers_import`: its record, when the bundle listed
+/// it as a peer, and the public identity the bundle carried for it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeerIdentityRecord {
+    pub peer: String,
+    pub record: Option<PeerRecord>,
+    pub public_key: Option<String>,
+}
+
+/// The first send made under an idempotency key. A repeat of the same
+/// payload under the key is answered with `message_id` instead of sending
+/// again; `replayed` records that this has happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdempotencyRecord {
+    pub source: String,
+    pub destination: String,
+    pub key: String,
+    pub payload_hash: String,
+    pub message_id: String,
+    pub created_ms: u64,
+    pub replayed: bool,
+}
+
+/// JSON key of the LXMF thread field (`FIELD_THREAD`, 0x08).
+pub const FIELD_THREAD_KEY: &str = "8";
+
+/// Reads the parent message id from the thread field. Peers that send the
+/// id as raw bytes arrive as a byte array and are returned hex-encoded.
+pub fn reply_to_from_fields(fields: Option<&JsonValue>) -> Option<String> {
+    match fields?.get(FIELD_THREAD_KEY)? {
+        JsonValue::String(id) => Some(id.trim().to_string()).filter(|id| !id.is_empty()),
+        JsonValue::Array(bytes) if !bytes.is_empty() => bytes
+            .iter()
+            .map(|byte| {
+                byte.as_u64().filter(|byte| *byte <= 0xff).map(|byte| format!("{byte:02x}"))
+            })
+            .collect(),
+        _ => None,
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -33,8 +116,45 @@ pub struct AnnounceRecord {
     pub peering_cost: Option<u32>,
 }
 
+/// Filter for [`MessagesStore::search_messages`]. Bounds are unix
+/// milliseconds and inclusive.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageSearchFilter {
+    pub query: Option<String>,
+    pub source: Option<String>,
+    pub destination: Option<String>,
+    pub from_ts_ms: Option<u64>,
+    pub to_ts_ms: Option<u64>,
+    /// Matches the thread root and every message replying to it.
+    pub thread: Option<String>,
+    /// Matches messages sent to or received from this peer.
+    pub peer: Option<String>,
+}
+
 pub struct MessagesStore {
     conn: Connection,
+    counts: std::sync::Mutex<MessageCounts>,
+}
+
+/// Row counts kept in step with writes so status polling never scans the
+/// messages table. Bulk deletes and expiry recount from the table instead.
+#[derive(Debug, Clone, Copy, Default)]
+struct MessageCounts {
+    stored: u64,
+    pending_outbound: u64,
+}
+
+/// Mirrors the SQL used by [`MessagesStore::count_pending_outbound_messages`].
+fn is_pending_outbound(direction: &str, receipt_status: Option<&str>) -> bool {
+    if direction != "out" {
+        return false;
+    }
+    let Some(status) = receipt_status.filter(|status| !status.trim().is_empty()) else {
+        return true;
+    };
+    let status = status.to_ascii_lowercase();
+    !status.starts_with("sent")
+        && !matches!(status.as_str(), "cancelled" | "delivered" | "failed" | "expired" | "rejected")
 }
 
 impl MessagesStore {
@@ -42,23 +162,26 @@ impl MessagesStore {
 
     pub fn in_memory() -> rusqlite::Result<Self> {
         let conn = Connection::open_in_memory()?;
-        let store = Self { conn };
+        let store = Self { conn, counts: Default::default() };
         store.init_schema()?;
+        store.recount_messages()?;
         Ok(store)
     }
 
     pub fn open(path: &std::path::Path) -> rusqlite::Result<Self> {
         let conn = Connection::open(path)?;
-        let store = Self { conn };
+        let store = Self { conn, counts: Default::default() };
         store.init_schema()?;
+        store.recount_messages()?;
         Ok(store)
     }
 
     pub fn insert_message(&self, record: &MessageRecord) -> rusqlite::Result<()> {
         let fields_json =
             record.fields.as_ref().map(|value| serde_json::to_string(value).unwrap_or_default());
+        let previous = self.message_state(&record.id)?;
         self.conn.execute(
-            "INSERT OR REPLACE INTO messages (id, source, destination, title, content, timestamp, direction, fields, receipt_status) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            "INSERT OR REPLACE INTO messages (id, source, destination, title, content, timestamp, direction, fields, receipt_status, reply_to) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
             params![
                 &record.id,
                 &record.source,
@@ -69,8 +192,21 @@ impl MessagesStore {
                 &record.direction,
                 fields_json,
                 &record.receipt_status,
+                &record.reply_to,
             ],
         )?;
+        let mut counts = self.counts.lock().expect("message counts mutex poisoned");
+        match previous {
+            Some((direction, status)) => {
+                if is_pending_outbound(&direction, status.as_deref()) {
+                    counts.pending_outbound = counts.pending_outbound.saturating_sub(1);
+                }
+            }
+            None => counts.stored += 1,
+        }
+        if is_pending_outbound(&record.direction, record.receipt_status.as_deref()) {
+            counts.pending_outbound += 1;
+        }
         Ok(())
     }
 
@@ -82,7 +218,7 @@ impl MessagesStore {
         let mut records = Vec::new();
         if let Some(ts) = before_ts {
             let mut stmt = self.conn.prepare(
-                "SELECT id, source, destination, title, content, timestamp, direction, fields, receipt_status FROM messages WHERE timestamp < ?1 ORDER BY timestamp DESC LIMIT ?2",
+                "SELECT id, source, destination, title, content, timestamp, direction, fields, receipt_status, reply_to FROM messages WHERE timestamp < ?1 ORDER BY timestamp DESC LIMIT ?2",
             )?;
             let mut rows = stmt.query(params![ts, limit as i64])?;
             while let Some(row) = rows.next()? {
@@ -100,11 +236,12 @@ impl MessagesStore {
                     direction: row.get(6)?,
                     fields,
                     receipt_status,
+                    reply_to: row.get(9)?,
                 });
             }
         } else {
             let mut stmt = self.conn.prepare(
-                "SELECT id, source, destination, title, content, timestamp, direction, fields, receipt_status FROM messages ORDER BY timestamp DESC LIMIT ?1",
+                "SELECT id, source, destination, title, content, timestamp, direction, fields, receipt_status, reply_to FROM messages ORDER BY timestamp DESC LIMIT ?1",
             )?;
             let mut rows = stmt.query(params![limit as i64])?;
             while let Some(row) = rows.next()? {
@@ -122,15 +259,136 @@ impl MessagesStore {
                     direction: row.get(6)?,
                     fields,
                     receipt_status,
+                    reply_to: row.get(9)?,
                 });
             }
         }
         Ok(records)
     }
 
+    /// Returns up to `limit` messages matching `filter`, newest first, resuming
+    /// after the `(timestamp, id)` position in `before`. `query` matches title
+    /// or content with SQLite `LIKE`, so case folding covers ASCII only.
+    pub fn search_messages(
+        &self,
+        filter: &MessageSearchFilter,
+        before: Option<(i64, &str)>,
+        limit: usize,
+    ) -> rusqlite::Result<Vec<MessageRecord>> {
+        self.select_messages(filter, before, false, limit)
+    }
+
+    /// Returns up to `limit` messages matching `filter`, oldest first,
+    /// resuming after the `(timestamp, id)` position in `after`.
+    pub fn export_messages(
+        &self,
+        filter: &MessageSearchFilter,
+        after: Option<(i64, &str)>,
+        limit: usize,
+    ) -> rusqlite::Result<Vec<MessageRecord>> {
+        self.select_messages(filter, after, true, limit)
+    }
+
+    fn select_messages(
+        &self,
+        filter: &MessageSearchFilter,
+        resume: Option<(i64, &str)>,
+        ascending: bool,
+        limit: usize,
+    ) -> rusqlite::Result<Vec<MessageRecord>> {
+        use rusqlite::types::Value;
+
+        let ms_bound = |ts_ms: u64| Value::Integer(i64::try_from(ts_ms).unwrap_or(i64::MAX));
+        let mut clauses = Vec::new();
+        let mut values = Vec::new();
+        if let Some(query) =
+            filter.query.as_deref().map(str::trim).filter(|query| !query.is_empty())
+        {
+            let escaped = query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+            values.push(Value::Text(format!("%{escaped}%")));
+            let n = values.len();
+            clauses.push(format!("(title LIKE ?{n} ESCAPE '\\' OR content LIKE ?{n} ESCAPE '\\')"));
+        }
+        for (column, value) in [("source", &filter.source), ("destination", &filter.destination)] {
+            if let Some(value) = value.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+                values.push(Value::Text(value.to_ascii_lowercase()));
+                clauses.push(format!("LOWER({column}) = ?{}", values.len()));
+            }
+        }
+        if let Some(peer) = filter.peer.as_deref().map(str::trim).filter(|peer| !peer.is_empty()) {
+            values.push(Value::Text(peer.to_ascii_lowercase()));
+            let n = values.len();
+            clauses.push(format!("(LOWER(source) = ?{n} OR LOWER(destination) = ?{n})"));
+        }
+        if let Some(thread) = filter.thread.as_deref().map(str::trim).filter(|id| !id.is_empty()) {
+            values.push(Value::Text(thread.to_string()));
+            let n = values.len();
+            clauses.push(format!("(id = ?{n} OR reply_to = ?{n})"));
+        }
+        if let Some(from_ts_ms) = filter.from_ts_ms {
+            values.push(ms_bound(from_ts_ms));
+            clauses.push(format!("timestamp * 1000 >= ?{}", values.len()));
+        }
+        if let Some(to_ts_ms) = filter.to_ts_ms {
+            values.push(ms_bound(to_ts_ms));
+            clauses.push(format!("timestamp * 1000 <= ?{}", values.len()));
+        }
+        let (cmp, order) = if ascending { (">", "ASC") } else { ("<", "DESC") };
+        if let Some((resume_ts, resume_id)) = resume {
+            values.push(Value::Integer(resume_ts));
+            values.push(Value::Text(resume_id.to_string()));
+            let (ts, id) = (values.len() - 1, values.len());
+            clauses
+                .push(format!("(timestamp {cmp} ?{ts} OR (timestamp = ?{ts} AND id {cmp} ?{id}))"));
+        }
+        values.push(Value::Integer(i64::try_from(limit).unwrap_or(i64::MAX)));
+        let where_clause = if clauses.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", clauses.join(" AND "))
+        };
+        let sql = format!(
+            "SELECT id, source, destination, title, content, timestamp, direction, fields, receipt_status, reply_to FROM messages {where_clause} ORDER BY timestamp {order}, id {order} LIMIT ?{}",
+            values.len()
+        );
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let mut rows = stmt.query(rusqlite::params_from_iter(values))?;
+        let mut records = Vec::new();
+        while let Some(row) = rows.next()? {
+            let fields_json: Option<String> = row.get(7)?;
+            let fields = fields_json.as_ref().and_then(|value| serde_json::from_str(value).ok());
+            records.push(MessageRecord {
+                id: row.get(0)?,
+                source: row.get(1)?,
+                destination: row.get(2)?,
+                title: row.get(3)?,
+                content: row.get(4)?,
+                timestamp: row.get(5)?,
+                direction: row.get(6)?,
+                fields,
+                receipt_status: row.get(8)?,
+                reply_to: row.get(9)?,
+            });
+        }
+        Ok(records)
+    }
+
+    /// Whether an inbound message with this id is already stored.
+    pub fn has_inbound_message(&self, message_id: &str) -> rusqlite::Result<bool> {
+        self.conn
+            .query_row(
+                "SELECT 1 FROM messages WHERE id = ?1 AND direction = 'in' LIMIT 1",
+                params![message_id],
+                |_| Ok(()),
+            )
+            .optional()
+            .map(|row| row.is_some())
+    }
+
     pub fn get_message(&self, message_id: &str) -> rusqlite::Result<Option<MessageRecord>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, source, destination, title, content, timestamp, direction, fields, receipt_status FROM messages WHERE id = ?1 LIMIT 1",
+            "SELECT id, source, destination, title, content, timestamp, direction, fields, receipt_status, reply_to FROM messages WHERE id = ?1 LIMIT 1",
         )?;
         stmt.query_row(params![message_id], |row| {
             let fields_json: Option<String> = row.get(7)?;
@@ -146,6 +404,7 @@ impl MessagesStore {
                 direction: row.get(6)?,
                 fields,
                 receipt_status,
+                reply_to: row.get(9)?,
             })
         })
         .optional()
@@ -173,6 +432,58 @@ impl MessagesStore {
         Ok((queued.max(0) as u64, in_flight.max(0) as u64))
     }
 
+    /// Counts outbound messages that have not yet reached a sent or terminal
+    /// receipt status.
+    pub fn count_pending_outbound_messages(&self) -> rusqlite::Result<u64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*)
+             FROM messages
+             WHERE direction = 'out'
+               AND (
+                    receipt_status IS NULL
+                    OR TRIM(receipt_status) = ''
+                    OR (
+                        LOWER(receipt_status) NOT LIKE 'sent%'
+                        AND LOWER(receipt_status) NOT IN ('cancelled', 'delivered', 'failed', 'expired', 'rejected')
+                    )
+               )",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(count.max(0) as u64)
+    }
+
+    /// Number of stored messages, from the write-maintained counter.
+    pub fn stored_message_count(&self) -> u64 {
+        self.counts.lock().expect("message counts mutex poisoned").stored
+    }
+
+    /// Same as [`Self::count_pending_outbound_messages`], from the
+    /// write-maintained counter instead of a table scan.
+    pub fn pending_outbound_count(&self) -> u64 {
+        self.counts.lock().expect("message counts mutex poisoned").pending_outbound
+    }
+
+    /// Ids and receipt statuses of outbound messages handed to delivery that
+    /// have not reached a terminal status. Scheduled sends are left out
+    /// because they have not been dispatched yet.
+    pub fn list_unsettled_outbound(&self) -> rusqlite::Result<Vec<(String, Option<String>)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, receipt_status
+             FROM messages
+             WHERE direction = 'out'
+               AND (
+                    receipt_status IS NULL
+                    OR (
+                        LOWER(TRIM(receipt_status)) NOT LIKE 'failed%'
+                        AND LOWER(TRIM(receipt_status)) NOT IN ('scheduled', 'cancelled', 'delivered', 'expired', 'rejected')
+                    )
+               )",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect()
+    }
+
     pub fn count_outbound_messages(&self) -> rusqlite::Result<u64> {
         let count: i64 = self.conn.query_row(
             "SELECT COUNT(*) FROM messages WHERE direction = 'out'",
@@ -209,6 +520,9 @@ impl MessagesStore {
                 params![message_id],
             )?;
         }
+        if !ids.is_empty() {
+            self.recount_messages()?;
+        }
         Ok(ids)
     }
 
@@ -285,20 +599,255 @@ impl MessagesStore {
         ids.dedup();
         for message_id in ids.iter() {
             self.conn.execute("DELETE FROM messages WHERE id = ?1", params![message_id])?;
+            self.conn.execute(
+                "DELETE FROM delivery_traces WHERE message_id = ?1",
+                params![message_id],
+            )?;
+            self.conn.execute(
+                "DELETE FROM message_batches WHERE message_id = ?1",
+                params![message_id],
+            )?;
+        }
+        if !ids.is_empty() {
+            self.recount_messages()?;
+        }
+        Ok(ids)
+    }
+
+    /// Deletes delivered or failed messages stamped before `ts_ms` (unix
+    /// milliseconds) and returns the ids removed. Messages that are still
+    /// queued, in flight or retrying are kept regardless of age.
+    pub fn prune_delivered_before(&self, ts_ms: u64) -> rusqlite::Result<Vec<String>> {
+        let cutoff_ts = i64::try_from(ts_ms.div_ceil(1_000)).unwrap_or(i64::MAX);
+        let ids = {
+            let mut stmt = self.conn.prepare(
+                "SELECT id FROM messages
+                 WHERE timestamp < ?1
+                   AND receipt_status IS NOT NULL
+                   AND (
+                        LOWER(TRIM(receipt_status)) = 'delivered'
+                        OR LOWER(TRIM(receipt_status)) LIKE 'failed%'
+                   )",
+            )?;
+            let rows = stmt.query_map(params![cutoff_ts], |row| row.get::<_, String>(0))?;
+            rows.collect::<rusqlite::Result<Vec<_>>>()?
+        };
+        for message_id in &ids {
+            self.conn.execute("DELETE FROM messages WHERE id = ?1", params![message_id])?;
+            self.conn.execute(
+                "DELETE FROM delivery_traces WHERE message_id = ?1",
+                params![message_id],
+            )?;
+            self.conn.execute(
+                "DELETE FROM message_batches WHERE message_id = ?1",
+                params![message_id],
+            )?;
+        }
+        if !ids.is_empty() {
+            self.recount_messages()?;
         }
         Ok(ids)
     }
 
     pub fn update_receipt_status(&self, message_id: &str, status: &str) -> rusqlite::Result<()> {
+        let Some((direction, previous_status)) = self.message_state(message_id)? else {
+            return Ok(());
+        };
+        let was_pending = is_pending_outbound(&direction, previous_status.as_deref());
         self.conn.execute(
             "UPDATE messages SET receipt_status = ?1 WHERE id = ?2",
             params![status, message_id],
         )?;
+        let is_pending = is_pending_outbound(&direction, Some(status));
+        let mut counts = self.counts.lock().expect("message counts mutex poisoned");
+        match (was_pending, is_pending) {
+            (true, false) => counts.pending_outbound = counts.pending_outbound.saturating_sub(1),
+            (false, true) => counts.pending_outbound += 1,
+            _ => {}
+        }
         Ok(())
     }
 
     pub fn clear_messages(&self) -> rusqlite::Result<()> {
         self.conn.execute("DELETE FROM messages", [])?;
+        self.conn.execute("DELETE FROM delivery_traces", [])?;
+        self.conn.execute("DELETE FROM message_batches", [])?;
+        self.conn.execute("DELETE FROM idempotency_keys", [])?;
+        *self.counts.lock().expect("message counts mutex poisoned") = MessageCounts::default();
+        Ok(())
+    }
+
+    /// Direction and receipt status of a stored message.
+    fn message_state(
+        &self,
+        message_id: &str,
+    ) -> rusqlite::Result<Option<(String, Option<String>)>> {
+        self.conn
+            .query_row(
+                "SELECT direction, receipt_status FROM messages WHERE id = ?1",
+                params![message_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+    }
+
+    fn recount_messages(&self) -> rusqlite::Result<()> {
+        let stored: i64 =
+            self.conn.query_row("SELECT COUNT(*) FROM messages", [], |row| row.get(0))?;
+        let pending_outbound = self.count_pending_outbound_messages()?;
+        *self.counts.lock().expect("message counts mutex poisoned") =
+            MessageCounts { stored: stored.max(0) as u64, pending_outbound };
+        Ok(())
+    }
+
+    /// Appends one trace entry for a stored `message_id`, keeping only its
+    /// newest `max_entries` rows. Entries for unknown or already pruned
+    /// messages are dropped so traces never outlive their message.
+    pub fn append_delivery_trace(
+        &self,
+        message_id: &str,
+        entry: &DeliveryTraceEntry,
+        max_entries: usize,
+    ) -> rusqlite::Result<()> {
+        let inserted = self.conn.execute(
+            "INSERT INTO delivery_traces
+                (message_id, status, timestamp, reason_code, detail, timestamp_ms)
+             SELECT ?1, ?2, ?3, ?4, ?5, ?6
+             WHERE EXISTS (SELECT 1 FROM messages WHERE id = ?1)",
+            params![
+                message_id,
+                &entry.status,
+                entry.timestamp,
+                &entry.reason_code,
+                &entry.detail,
+                entry.timestamp_ms
+            ],
+        )?;
+        if inserted == 0 {
+            return Ok(());
+        }
+        self.conn.execute(
+            "DELETE FROM delivery_traces
+             WHERE message_id = ?1
+               AND seq NOT IN (
+                    SELECT seq FROM delivery_traces
+                    WHERE message_id = ?1
+                    ORDER BY seq DESC
+                    LIMIT ?2
+               )",
+            params![message_id, max_entries as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Returns the persisted trace for `message_id` oldest first; empty when
+    /// the message was never traced.
+    pub fn get_delivery_trace(
+        &self,
+        message_id: &str,
+    ) -> rusqlite::Result<Vec<DeliveryTraceEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT status, timestamp, reason_code, detail, timestamp_ms
+             FROM delivery_traces
+             WHERE message_id = ?1
+             ORDER BY seq ASC",
+        )?;
+        let rows = stmt.query_map(params![message_id], delivery_trace_entry_from_row)?;
+        rows.collect()
+    }
+
+    /// Returns the persisted traces, oldest entry first, of every message
+    /// with a transition at or after `since_ms`. Each trace is complete, so
+    /// it may start before `since_ms`.
+    pub fn list_delivery_traces_since(
+        &self,
+        since_ms: i64,
+    ) -> rusqlite::Result<Vec<Vec<DeliveryTraceEntry>>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT status, timestamp, reason_code, detail, timestamp_ms, message_id
+             FROM delivery_traces
+             WHERE message_id IN (
+                    SELECT message_id FROM delivery_traces WHERE timestamp_ms >= ?1
+             )
+             ORDER BY message_id ASC, seq ASC",
+        )?;
+        let mut rows = stmt.query(params![since_ms])?;
+        let mut traces: Vec<Vec<DeliveryTraceEntry>> = Vec::new();
+        let mut current: Option<String> = None;
+        while let Some(row) = rows.next()? {
+            let message_id: String = row.get(5)?;
+            if current.as_deref() != Some(message_id.as_str()) {
+                traces.push(Vec::new());
+                current = Some(message_id);
+            }
+            if let Some(trace) = traces.last_mut() {
+                trace.push(delivery_trace_entry_from_row(row)?);
+            }
+        }
+        Ok(traces)
+    }
+
+    /// Records that `message_id` was submitted as part of `batch_id`. Batch
+    /// membership is daemon bookkeeping and never enters the message fields.
+    pub fn set_message_batch(&self, message_id: &str, batch_id: &str) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO message_batches (message_id, batch_id) VALUES (?1, ?2)",
+            params![message_id, batch_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn message_batch(&self, message_id: &str) -> rusqlite::Result<Option<String>> {
+        self.conn
+            .query_row(
+                "SELECT batch_id FROM message_batches WHERE message_id = ?1",
+                params![message_id],
+                |row| row.get(0),
+            )
+            .optional()
+    }
+
+    /// Appends a telemetry point decoded from inbound message `message_id`,
+    /// then drops the oldest points from `peer` beyond `max_per_peer`. Each
+    /// call writes one row, so a busy location stream never rewrites the rest.
+    pub fn insert_inbound_telemetry(
+        &self,
+        message_id: &str,
+        peer: &str,
+        point: &JsonValue,
+        max_per_peer: usize,
+    ) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO inbound_telemetry (message_id, peer, point) VALUES (?1, ?2, ?3)",
+            params![message_id, peer, point.to_string()],
+        )?;
+        self.conn.execute(
+            "DELETE FROM inbound_telemetry WHERE peer = ?1 AND seq NOT IN (
+                SELECT seq FROM inbound_telemetry WHERE peer = ?1 ORDER BY seq DESC LIMIT ?2
+             )",
+            params![peer, i64::try_from(max_per_peer).unwrap_or(i64::MAX)],
+        )?;
+        Ok(())
+    }
+
+    /// Every stored inbound telemetry point, oldest arrival first.
+    pub fn list_inbound_telemetry(&self) -> rusqlite::Result<Vec<JsonValue>> {
+        let mut stmt = self.conn.prepare("SELECT point FROM inbound_telemetry ORDER BY seq ASC")?;
+        let rows = stmt.query_map([], |row| {
+            let point: String = row.get(0)?;
+            serde_json::from_str(&point).map_err(|err| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    Box::new(err),
+                )
+            })
+        })?;
+        rows.collect()
+    }
+
+    pub fn clear_inbound_telemetry(&self) -> rusqlite::Result<()> {
+        self.conn.execute("DELETE FROM inbound_telemetry", [])?;
         Ok(())
     }
 
@@ -385,11 +934,211 @@ impl MessagesStore {
         Ok(records)
     }
 
+    /// Peers whose most recent announce is older than `cutoff_ts` (seconds).
+    pub fn stale_announce_peers(&self, cutoff_ts: i64) -> rusqlite::Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT peer FROM announces GROUP BY peer HAVING MAX(timestamp) < ?1")?;
+        let rows = stmt.query_map(params![cutoff_ts], |row| row.get(0))?;
+        rows.collect()
+    }
+
+    pub fn delete_peer_announces(&self, peer: &str) -> rusqlite::Result<usize> {
+        self.conn.execute("DELETE FROM announces WHERE peer = ?1", params![peer])
+    }
+
     pub fn clear_announces(&self) -> rusqlite::Result<()> {
         self.conn.execute("DELETE FROM announces", [])?;
         Ok(())
     }
 
+    /// Stores an imported peer record and the public key it was exported
+    /// with. Either may be missing, which keeps what is already stored.
+    pub fn put_peer_identity(
+        &self,
+        peer: &str,
+        record: Option<&PeerRecord>,
+        public_key: Option<&str>,
+    ) -> rusqlite::Result<()> {
+        let record_json = record
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+        self.conn.execute(
+            "INSERT INTO peer_identities (peer, record, public_key) VALUES (?1, ?2, ?3)
+             ON CONFLICT(peer) DO UPDATE SET
+                record = COALESCE(excluded.record, peer_identities.record),
+                public_key = COALESCE(excluded.public_key, peer_identities.public_key)",
+            params![peer, record_json, public_key],
+        )?;
+        Ok(())
+    }
+
+    /// Returns every stored peer identity, ordered by peer.
+    pub fn list_peer_identities(&self) -> rusqlite::Result<Vec<PeerIdentityRecord>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT peer, record, public_key FROM peer_identities ORDER BY peer ASC")?;
+        let rows = stmt.query_map([], |row| {
+            let record_json: Option<String> = row.get(1)?;
+            let record = record_json
+                .map(|record_json| serde_json::from_str(&record_json))
+                .transpose()
+                .map_err(|err| {
+                    rusqlite::Error::FromSqlConversionFailure(
+                        1,
+                        rusqlite::types::Type::Text,
+                        Box::new(err),
+                    )
+                })?;
+            Ok(PeerIdentityRecord { peer: row.get(0)?, record, public_key: row.get(2)? })
+        })?;
+        rows.collect()
+    }
+
+    pub fn delete_peer_identity(&self, peer: &str) -> rusqlite::Result<usize> {
+        self.conn.execute("DELETE FROM peer_identities WHERE peer = ?1", params![peer])
+    }
+
+    pub fn clear_peer_identities(&self) -> rusqlite::Result<()> {
+        self.conn.execute("DELETE FROM peer_identities", [])?;
+        Ok(())
+    }
+
+    /// Records a revoked ticket. Revoking it again keeps the first time.
+    pub fn put_revoked_ticket(&self, ticket: &str, revoked_at: i64) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO revoked_tickets (ticket, revoked_at) VALUES (?1, ?2)",
+            params![ticket, revoked_at],
+        )?;
+        Ok(())
+    }
+
+    pub fn list_revoked_tickets(&self) -> rusqlite::Result<Vec<String>> {
+        let mut stmt =
+            self.conn.prepare("SELECT ticket FROM revoked_tickets ORDER BY revoked_at ASC")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        rows.collect()
+    }
+
+    pub fn put_idempotency_record(&self, record: &IdempotencyRecord) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO idempotency_keys
+                (source, destination, key, payload_hash, message_id, created_ms, replayed)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                &record.source,
+                &record.destination,
+                &record.key,
+                &record.payload_hash,
+                &record.message_id,
+                i64::try_from(record.created_ms).unwrap_or(i64::MAX),
+                record.replayed
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_idempotency_record(
+        &self,
+        source: &str,
+        destination: &str,
+        key: &str,
+    ) -> rusqlite::Result<Option<IdempotencyRecord>> {
+        self.conn
+            .query_row(
+                "SELECT source, destination, key, payload_hash, message_id, created_ms, replayed
+                 FROM idempotency_keys WHERE source = ?1 AND destination = ?2 AND key = ?3",
+                params![source, destination, key],
+                |row| {
+                    Ok(IdempotencyRecord {
+                        source: row.get(0)?,
+                        destination: row.get(1)?,
+                        key: row.get(2)?,
+                        payload_hash: row.get(3)?,
+                        message_id: row.get(4)?,
+                        created_ms: u64::try_from(row.get::<_, i64>(5)?).unwrap_or(0),
+                        replayed: row.get(6)?,
+                    })
+                },
+            )
+            .optional()
+    }
+
+    /// Drops idempotency keys created at or before `cutoff_ms`.
+    pub fn prune_idempotency_records(&self, cutoff_ms: u64) -> rusqlite::Result<usize> {
+        self.conn.execute(
+            "DELETE FROM idempotency_keys WHERE created_ms <= ?1",
+            params![i64::try_from(cutoff_ms).unwrap_or(i64::MAX)],
+        )
+    }
+
+    /// Whether a send of `message_id` was answered again from its
+    /// idempotency key.
+    pub fn message_replayed(&self, message_id: &str) -> rusqlite::Result<bool> {
+        self.conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM idempotency_keys WHERE message_id = ?1 AND replayed)",
+            params![message_id],
+            |row| row.get(0),
+        )
+    }
+
+    pub fn put_scheduled_send(&self, record: &ScheduledSendRecord) -> rusqlite::Result<()> {
+        let options_json = serde_json::to_string(&record.options)
+            .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+        self.conn.execute(
+            "INSERT INTO scheduled_sends (message_id, due_ms, method, stamp_cost, options)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(message_id) DO UPDATE SET
+                due_ms = excluded.due_ms,
+                method = excluded.method,
+                stamp_cost = excluded.stamp_cost,
+                options = excluded.options",
+            params![
+                &record.message_id,
+                i64::try_from(record.due_ms).unwrap_or(i64::MAX),
+                &record.method,
+                record.stamp_cost,
+                options_json
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_scheduled_send(&self, message_id: &str) -> rusqlite::Result<bool> {
+        let deleted = self
+            .conn
+            .execute("DELETE FROM scheduled_sends WHERE message_id = ?1", params![message_id])?;
+        Ok(deleted > 0)
+    }
+
+    /// Returns every persisted scheduled send, earliest due first.
+    pub fn list_scheduled_sends(&self) -> rusqlite::Result<Vec<ScheduledSendRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT message_id, due_ms, method, stamp_cost, options
+             FROM scheduled_sends
+             ORDER BY due_ms ASC, message_id ASC",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let options_json: String = row.get(4)?;
+            let options = serde_json::from_str(&options_json).map_err(|err| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    4,
+                    rusqlite::types::Type::Text,
+                    Box::new(err),
+                )
+            })?;
+            Ok(ScheduledSendRecord {
+                message_id: row.get(0)?,
+                due_ms: u64::try_from(row.get::<_, i64>(1)?).unwrap_or(0),
+                method: row.get(2)?,
+                stamp_cost: row.get(3)?,
+                options,
+            })
+        })?;
+        rows.collect()
+    }
+
     pub fn put_sdk_domain_snapshot(&self, snapshot: &JsonValue) -> rusqlite::Result<()> {
         let snapshot_json = serde_json::to_string(snapshot)
             .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
@@ -438,7 +1187,8 @@ impl MessagesStore {
                 timestamp INTEGER NOT NULL,
                 direction TEXT NOT NULL,
                 fields TEXT,
-                receipt_status TEXT
+                receipt_status TEXT,
+                reply_to TEXT
             );
             CREATE TABLE IF NOT EXISTS announces (
                 id TEXT PRIMARY KEY,
@@ -459,12 +1209,61 @@ impl MessagesStore {
             CREATE TABLE IF NOT EXISTS sdk_domain_state (
                 domain TEXT PRIMARY KEY,
                 state_json TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS delivery_traces (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                message_id TEXT NOT NULL,
+                status TEXT NOT NULL,
+                timestamp INTEGER NOT NULL,
+                reason_code TEXT,
+                detail TEXT
+            );
+            CREATE INDEX IF NOT EXISTS delivery_traces_message_id
+                ON delivery_traces (message_id, seq);
+            CREATE TABLE IF NOT EXISTS message_batches (
+                message_id TEXT PRIMARY KEY,
+                batch_id TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS inbound_telemetry (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                message_id TEXT NOT NULL UNIQUE,
+                peer TEXT NOT NULL,
+                point TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS inbound_telemetry_peer
+                ON inbound_telemetry (peer, seq);
+            CREATE TABLE IF NOT EXISTS scheduled_sends (
+                message_id TEXT PRIMARY KEY,
+                due_ms INTEGER NOT NULL,
+                method TEXT,
+                stamp_cost INTEGER,
+                options TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS idempotency_keys (
+                source TEXT NOT NULL,
+                destination TEXT NOT NULL,
+                key TEXT NOT NULL,
+                payload_hash TEXT NOT NULL,
+                message_id TEXT NOT NULL,
+                created_ms INTEGER NOT NULL,
+                replayed INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (source, destination, key)
+            );
+            CREATE TABLE IF NOT EXISTS peer_identities (
+                peer TEXT PRIMARY KEY,
+                record TEXT,
+                public_key TEXT
+            );
+            CREATE TABLE IF NOT EXISTS revoked_tickets (
+                ticket TEXT PRIMARY KEY,
+                revoked_at INTEGER NOT NULL
             );",
         )?;
         let _ = self.conn.execute("ALTER TABLE messages ADD COLUMN title TEXT", []);
         let _ = self.conn.execute("UPDATE messages SET title = '' WHERE title IS NULL", []);
         let _ = self.conn.execute("ALTER TABLE messages ADD COLUMN fields TEXT", []);
         let _ = self.conn.execute("ALTER TABLE messages ADD COLUMN receipt_status TEXT", []);
+        let _ = self.conn.execute("ALTER TABLE messages ADD COLUMN reply_to TEXT", []);
         let _ = self.conn.execute("ALTER TABLE announces ADD COLUMN name TEXT", []);
         let _ = self.conn.execute("ALTER TABLE announces ADD COLUMN name_source TEXT", []);
         let _ = self.conn.execute("ALTER TABLE announces ADD COLUMN first_seen INTEGER", []);
@@ -478,10 +1277,31 @@ impl MessagesStore {
             .conn
             .execute("ALTER TABLE announces ADD COLUMN stamp_cost_flexibility INTEGER", []);
         let _ = self.conn.execute("ALTER TABLE announces ADD COLUMN peering_cost INTEGER", []);
+        let _ =
+            self.conn.execute("ALTER TABLE delivery_traces ADD COLUMN timestamp_ms INTEGER", []);
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS delivery_traces_timestamp_ms
+                ON delivery_traces (timestamp_ms)",
+            [],
+        )?;
+        self.conn.execute(
+            "UPDATE delivery_traces SET timestamp_ms = timestamp * 1000 WHERE timestamp_ms IS NULL",
+            [],
+        )?;
         Ok(())
     }
 }
 
+fn delivery_trace_entry_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<DeliveryTraceEntry> {
+    Ok(DeliveryTraceEntry {
+        status: row.get(0)?,
+        timestamp: row.get(1)?,
+        reason_code: row.get(2)?,
+        detail: row.get(3)?,
+        timestamp_ms: row.get(4)?,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -498,7 +1318,73 @@ mod tests {
             direction: "out".to_string(),
             fields: None,
             receipt_status: receipt_status.map(ToString::to_string),
+            reply_to: None,
+        }
+    }
+
+    #[test]
+    fn message_batches_are_pruned_with_their_messages() {
+        let store = MessagesStore::in_memory().expect("in-memory store");
+        store.insert_message(&outbound_message("a", 1, Some("delivered"))).expect("insert a");
+        store.insert_message(&outbound_message("b", 5_000, Some("delivered"))).expect("insert b");
+        store.set_message_batch("a", "batch-1").expect("batch a");
+        store.set_message_batch("b", "batch-1").expect("batch b");
+        assert_eq!(store.message_batch("a").expect("lookup"), Some("batch-1".to_string()));
+        assert_eq!(store.message_batch("missing").expect("lookup"), None);
+
+        assert_eq!(store.prune_delivered_before(2_000_000).expect("prune"), vec!["a"]);
+        assert_eq!(store.message_batch("a").expect("lookup"), None);
+        assert_eq!(store.message_batch("b").expect("lookup"), Some("batch-1".to_string()));
+        let stored = store.get_message("b").expect("get").expect("message");
+        assert!(stored.fields.is_none());
+    }
+
+    #[test]
+    fn inbound_telemetry_keeps_the_newest_points_per_peer() {
+        let store = MessagesStore::in_memory().expect("in-memory store");
+        for (id, peer) in [("m1", "a"), ("m2", "a"), ("m3", "b"), ("m4", "a")] {
+            store
+                .insert_inbound_telemetry(id, peer, &serde_json::json!({ "id": id }), 2)
+                .expect("insert point");
         }
+        let ids = |store: &MessagesStore| {
+            store
+                .list_inbound_telemetry()
+                .expect("list")
+                .iter()
+                .map(|point| point["id"].as_str().expect("id").to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&store), vec!["m2", "m3", "m4"]);
+
+        store.clear_inbound_telemetry().expect("clear");
+        assert!(ids(&store).is_empty());
+    }
+
+    #[test]
+    fn scheduled_sends_roundtrip_in_due_order() {
+        let store = MessagesStore::in_memory().expect("in-memory store");
+        let later = ScheduledSendRecord {
+            message_id: "later".to_string(),
+            due_ms: 2_000,
+            method: Some("propagated".to_string()),
+            stamp_cost: Some(8),
+            options: json!({ "method": "propagated", "max_retries": 2 }),
+        };
+        let sooner = ScheduledSendRecord {
+            message_id: "sooner".to_string(),
+            due_ms: 1_000,
+            method: None,
+            stamp_cost: None,
+            options: json!({}),
+        };
+        store.put_scheduled_send(&later).expect("persist later");
+        store.put_scheduled_send(&sooner).expect("persist sooner");
+
+        assert_eq!(store.list_scheduled_sends().expect("list"), vec![sooner.clone(), later]);
+        assert!(store.delete_scheduled_send("later").expect("delete"));
+        assert!(!store.delete_scheduled_send("later").expect("delete again"));
+        assert_eq!(store.list_scheduled_sends().expect("list"), vec![sooner]);
     }
 
     #[test]
@@ -572,4 +1458,198 @@ mod tests {
             "non-terminal record should remain when terminal records satisfy prune count"
         );
     }
+
+    #[test]
+    fn prune_delivered_before_removes_only_old_terminal_records() {
+        let store = MessagesStore::in_memory().expect("in-memory store");
+        assert!(store.prune_delivered_before(u64::MAX).expect("prune empty").is_empty());
+
+        for (id, timestamp, status) in [
+            ("old-delivered", 10, Some("delivered")),
+            ("old-failed", 10, Some("failed: timeout")),
+            ("old-queued", 10, None),
+            ("old-sent", 10, Some("sent: direct")),
+            ("old-retrying", 10, Some("retrying: relay")),
+            ("new-delivered", 20, Some("delivered")),
+        ] {
+            store.insert_message(&outbound_message(id, timestamp, status)).expect("insert");
+        }
+
+        let mut pruned = store.prune_delivered_before(15_000).expect("prune");
+        pruned.sort();
+        assert_eq!(pruned, vec!["old-delivered", "old-failed"]);
+        for id in ["old-delivered", "old-failed"] {
+            assert!(store.get_message(id).expect("load").is_none(), "{id} should be pruned");
+        }
+        for id in ["old-queued", "old-sent", "old-retrying", "new-delivered"] {
+            assert!(store.get_message(id).expect("load").is_some(), "{id} should be kept");
+        }
+    }
+
+    #[test]
+    fn message_counters_track_writes_without_scanning() {
+        let store = MessagesStore::in_memory().expect("in-memory store");
+        store.insert_message(&outbound_message("queued", 10, None)).expect("insert");
+        store.insert_message(&outbound_message("sent", 10, Some("sent: direct"))).expect("insert");
+        let mut inbound = outbound_message("inbound", 10, None);
+        inbound.direction = "in".to_string();
+        store.insert_message(&inbound).expect("insert");
+        store.insert_message(&outbound_message("queued", 11, None)).expect("replace");
+        assert_eq!(store.stored_message_count(), 3);
+        assert_eq!(store.pending_outbound_count(), 1);
+
+        store.update_receipt_status("queued", "delivered").expect("deliver");
+        store.update_receipt_status("sent", "retrying: relay").expect("retry");
+        store.update_receipt_status("missing", "delivered").expect("missing");
+        assert_eq!(store.pending_outbound_count(), 1);
+        assert_eq!(
+            store.pending_outbound_count(),
+            store.count_pending_outbound_messages().unwrap()
+        );
+
+        store.prune_delivered_before(u64::MAX).expect("prune");
+        assert_eq!(store.stored_message_count(), 2);
+        store.clear_messages().expect("clear");
+        assert_eq!(store.stored_message_count(), 0);
+        assert_eq!(store.pending_outbound_count(), 0);
+    }
+
+    #[test]
+    fn search_messages_filters_text_peers_and_time_newest_first() {
+        let store = MessagesStore::in_memory().expect("in-memory store");
+        for (id, timestamp, destination, title, content) in [
+            ("m1", 10, "peer-a", "Lunch", "see you at noon"),
+            ("m2", 20, "peer-b", "status", "100% done_ok"),
+            ("m3", 30, "PEER-A", "note", "LUNCH moved"),
+            ("m4", 40, "peer-a", "other", "nothing here"),
+        ] {
+            let mut record = outbound_message(id, timestamp, None);
+            record.destination = destination.to_string();
+            record.title = title.to_string();
+            record.content = content.to_string();
+            store.insert_message(&record).expect("insert");
+        }
+        let ids = |filter: &MessageSearchFilter, before: Option<(i64, &str)>, limit: usize| {
+            store
+                .search_messages(filter, before, limit)
+                .expect("search")
+                .into_iter()
+                .map(|record| record.id)
+                .collect::<Vec<_>>()
+        };
+
+        let lunch = MessageSearchFilter { query: Some("lunch".into()), ..Default::default() };
+        assert_eq!(ids(&lunch, None, 10), vec!["m3", "m1"]);
+        let literal = MessageSearchFilter { query: Some("0% done_".into()), ..Default::default() };
+        assert_eq!(ids(&literal, None, 10), vec!["m2"]);
+
+        let peer_a =
+            MessageSearchFilter { destination: Some("peer-a".into()), ..Default::default() };
+        assert_eq!(ids(&peer_a, None, 2), vec!["m4", "m3"]);
+        assert_eq!(ids(&peer_a, Some((30, "m3")), 2), vec!["m1"]);
+
+        let window = MessageSearchFilter {
+            query: Some("  ".into()),
+            from_ts_ms: Some(20_000),
+            to_ts_ms: Some(30_000),
+            ..Default::default()
+        };
+        assert_eq!(ids(&window, None, 10), vec!["m3", "m2"]);
+    }
+
+    #[test]
+    fn search_messages_thread_filter_matches_root_and_replies() {
+        let store = MessagesStore::in_memory().expect("store");
+        for (id, timestamp, reply_to) in
+            [("root", 10, None), ("r1", 20, Some("root")), ("other", 30, None)]
+        {
+            let mut record = outbound_message(id, timestamp, None);
+            record.reply_to = reply_to.map(ToString::to_string);
+            store.insert_message(&record).expect("insert");
+        }
+
+        let thread = MessageSearchFilter { thread: Some("root".into()), ..Default::default() };
+        let found = store.search_messages(&thread, None, 10).expect("search");
+        let ids = found.iter().map(|record| record.id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["r1", "root"]);
+        assert_eq!(found[0].reply_to.as_deref(), Some("root"));
+    }
+
+    #[test]
+    fn reply_to_from_fields_reads_string_and_byte_ids() {
+        assert_eq!(
+            reply_to_from_fields(Some(&json!({ "8": "abc123" }))).as_deref(),
+            Some("abc123")
+        );
+        assert_eq!(
+            reply_to_from_fields(Some(&json!({ "8": [0, 171, 255] }))).as_deref(),
+            Some("00abff")
+        );
+        assert_eq!(reply_to_from_fields(Some(&json!({ "8": [256] }))), None);
+        assert_eq!(reply_to_from_fields(Some(&json!({ "title": "x" }))), None);
+        assert_eq!(reply_to_from_fields(None), None);
+    }
+
+    fn trace_entry(status: &str, timestamp: i64) -> DeliveryTraceEntry {
+        DeliveryTraceEntry {
+            status: status.to_string(),
+            timestamp,
+            timestamp_ms: timestamp * 1000,
+            reason_code: None,
+            detail: None,
+        }
+    }
+
+    #[test]
+    fn delivery_traces_are_bounded_and_pruned_with_their_messages() {
+        let store = MessagesStore::in_memory().expect("in-memory store");
+        store.append_delivery_trace("old", &trace_entry("queued", 0), 3).expect("append");
+        assert!(store.get_delivery_trace("old").expect("trace").is_empty());
+        store.insert_message(&outbound_message("old", 10, Some("delivered"))).expect("insert old");
+        store.insert_message(&outbound_message("new", 50, Some("delivered"))).expect("insert new");
+        for timestamp in 0..5 {
+            store
+                .append_delivery_trace("old", &trace_entry("queued", timestamp), 3)
+                .expect("append");
+        }
+        store.append_delivery_trace("new", &trace_entry("delivered", 50), 3).expect("append");
+
+        let trace = store.get_delivery_trace("old").expect("trace");
+        assert_eq!(trace.iter().map(|entry| entry.timestamp).collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert!(store.get_delivery_trace("never").expect("trace").is_empty());
+
+        assert_eq!(store.prune_delivered_before(20_000).expect("prune"), vec!["old"]);
+        assert!(store.get_delivery_trace("old").expect("trace").is_empty());
+        assert_eq!(store.get_delivery_trace("new").expect("trace").len(), 1);
+
+        store.clear_messages().expect("clear");
+        assert!(store.get_delivery_trace("new").expect("trace").is_empty());
+    }
+
+    #[test]
+    fn delivery_traces_are_listed_by_window_and_backfilled_in_milliseconds() {
+        let store = MessagesStore::in_memory().expect("in-memory store");
+        store.insert_message(&outbound_message("early", 10, None)).expect("insert early");
+        store.insert_message(&outbound_message("late", 10, None)).expect("insert late");
+        store.append_delivery_trace("early", &trace_entry("queued", 10), 8).expect("append");
+        store.append_delivery_trace("late", &trace_entry("queued", 10), 8).expect("append");
+        store.append_delivery_trace("late", &trace_entry("delivered", 30), 8).expect("append");
+
+        let traces = store.list_delivery_traces_since(20_000).expect("list");
+        assert_eq!(traces.len(), 1);
+        let statuses = traces[0].iter().map(|entry| entry.status.as_str()).collect::<Vec<_>>();
+        assert_eq!(statuses, vec!["queued", "delivered"]);
+        assert_eq!(store.list_delivery_traces_since(0).expect("list").len(), 2);
+
+        store
+            .conn
+            .execute("UPDATE delivery_traces SET timestamp_ms = NULL", [])
+            .expect("drop timestamp_ms");
+        store.init_schema().expect("migrate");
+        let trace = store.get_delivery_trace("late").expect("trace");
+        assert_eq!(
+            trace.iter().map(|entry| entry.timestamp_ms).collect::<Vec<_>>(),
+            vec![10_000, 30_000]
+        );
+    }
 }
@@ -450,6 +450,11 @@ impl Link {
         self.request_time.elapsed()
     }
 
+    /// Round-trip time measured between the link request and its proof.
+    pub fn rtt(&self) -> Duration {
+        self.rtt
+    }
+
     pub fn status(&self) -> LinkStatus {
         self.status
     }
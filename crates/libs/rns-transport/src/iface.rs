@@ -1,5 +1,6 @@
 pub mod driver;
 pub mod hdlc;
+pub mod pacer;
 pub mod tcp_client;
 pub mod tcp_server;
 pub mod udp;
@@ -10,17 +11,19 @@ use std::sync::OnceLock;
 use std::time::Duration;
 
 use tokio::sync::mpsc;
+use tokio::sync::Notify;
 use tokio::task;
 use tokio_util::sync::CancellationToken;
 
 use crate::hash::AddressHash;
 use crate::hash::Hash;
 use crate::packet::Packet;
+use crate::time::now_epoch_secs_u64;
 
 pub use driver::{InterfaceDriver, InterfaceDriverFactory};
+pub use pacer::{OutboundPacer, PacedSend};
 
 pub type InterfaceTxSender = mpsc::Sender<TxMessage>;
-pub type InterfaceTxReceiver = mpsc::Receiver<TxMessage>;
 
 pub type InterfaceRxSender = mpsc::Sender<RxMessage>;
 pub type InterfaceRxReceiver = mpsc::Receiver<RxMessage>;
@@ -50,11 +53,134 @@ pub struct RxMessage {
     pub packet: Packet,
 }
 
+/// Cumulative traffic carried by one interface since it was spawned,
+/// including the children spawned under it with
+/// [`InterfaceManager::spawn_child`].
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub struct InterfaceTraffic {
+    pub bytes_in: u64,
+    pub bytes_out: u64,
+    pub packets_in: u64,
+    pub packets_out: u64,
+    /// Epoch seconds of the last packet in either direction.
+    pub last_activity_secs: Option<u64>,
+}
+
+impl InterfaceTraffic {
+    fn record_in(&mut self, packet: &Packet) {
+        self.packets_in += 1;
+        self.bytes_in += packet.wire_len() as u64;
+        self.last_activity_secs = Some(now_epoch_secs_u64());
+    }
+
+    fn record_out(&mut self, packet: &Packet) {
+        self.packets_out += 1;
+        self.bytes_out += packet.wire_len() as u64;
+        self.last_activity_secs = Some(now_epoch_secs_u64());
+    }
+}
+
+/// Link state an interface worker reports as it connects and disconnects.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum InterfaceLinkState {
+    Connecting,
+    Connected,
+    Disconnected,
+}
+
+/// One link transition reported by an interface worker.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct InterfaceLinkEvent {
+    pub address: AddressHash,
+    pub state: InterfaceLinkState,
+}
+
+/// Handle an interface worker uses to report link transitions. Repeating the
+/// last reported state sends nothing, so redial loops can report on every
+/// attempt.
+#[derive(Clone)]
+pub struct InterfaceLinkReporter {
+    address: AddressHash,
+    events: Option<mpsc::Sender<InterfaceLinkEvent>>,
+    last: Arc<Mutex<Option<InterfaceLinkState>>>,
+}
+
+impl InterfaceLinkReporter {
+    fn new(address: AddressHash, events: Option<mpsc::Sender<InterfaceLinkEvent>>) -> Self {
+        Self { address, events, last: Arc::default() }
+    }
+
+    pub fn report(&self, state: InterfaceLinkState) {
+        if let Ok(mut last) = self.last.lock() {
+            if last.replace(state) == Some(state) {
+                return;
+            }
+        }
+        let Some(events) = self.events.as_ref() else {
+            return;
+        };
+        if events.try_send(InterfaceLinkEvent { address: self.address, state }).is_err() {
+            log::debug!("iface: link event queue full, dropped {:?} on {}", state, self.address);
+        }
+    }
+}
+
+/// Receiving end of an interface transmit queue. Calling `recv` again marks
+/// the previously received packet as written, which hands the next paced
+/// packet for this interface straight back to the caller.
+pub struct InterfaceTxReceiver {
+    inner: mpsc::Receiver<TxMessage>,
+    pacer: Arc<Mutex<OutboundPacer>>,
+    traffic: Arc<Mutex<InterfaceTraffic>>,
+    awaiting_completion: bool,
+}
+
+impl InterfaceTxReceiver {
+    fn new(
+        inner: mpsc::Receiver<TxMessage>,
+        pacer: Arc<Mutex<OutboundPacer>>,
+        traffic: Arc<Mutex<InterfaceTraffic>>,
+    ) -> Self {
+        Self { inner, pacer, traffic, awaiting_completion: false }
+    }
+
+    pub async fn recv(&mut self) -> Option<TxMessage> {
+        if std::mem::take(&mut self.awaiting_completion) {
+            let released = self.pacer.lock().ok().and_then(|mut pacer| pacer.complete());
+            if let Some(message) = released {
+                self.awaiting_completion = true;
+                self.record_out(&message);
+                return Some(message);
+            }
+        }
+        let message = self.inner.recv().await?;
+        self.awaiting_completion = true;
+        self.record_out(&message);
+        Some(message)
+    }
+
+    /// Stops counting packets taken off this queue, for interfaces that
+    /// discard what is queued on them and carry traffic through children.
+    pub(crate) fn detach_traffic(&mut self) {
+        self.traffic = Arc::default();
+    }
+
+    fn record_out(&self, message: &TxMessage) {
+        if let Ok(mut traffic) = self.traffic.lock() {
+            traffic.record_out(&message.packet);
+        }
+    }
+}
+
 pub struct InterfaceChannel {
     pub address: AddressHash,
     pub rx_channel: InterfaceRxSender,
     pub tx_channel: InterfaceTxReceiver,
     pub stop: CancellationToken,
+    /// Signalled by [`InterfaceManager::reset`] to drop the current connection.
+    pub reset: Arc<Notify>,
+    /// Reports connect and disconnect transitions to the manager.
+    pub link: InterfaceLinkReporter,
 }
 
 impl InterfaceChannel {
@@ -63,7 +189,9 @@ impl InterfaceChannel {
     }
 
     pub fn make_tx_channel(cap: usize) -> (InterfaceTxSender, InterfaceTxReceiver) {
-        mpsc::channel(cap)
+        let (tx_send, tx_recv) = mpsc::channel(cap);
+        let pacer = Arc::new(Mutex::new(OutboundPacer::new(cap)));
+        (tx_send, InterfaceTxReceiver::new(tx_recv, pacer, Arc::default()))
     }
 
     pub fn new(
@@ -72,7 +200,8 @@ impl InterfaceChannel {
         address: AddressHash,
         stop: CancellationToken,
     ) -> Self {
-        Self { address, rx_channel, tx_channel, stop }
+        let link = InterfaceLinkReporter::new(address, None);
+        Self { address, rx_channel, tx_channel, stop, reset: Arc::default(), link }
     }
 
     pub fn address(&self) -> &AddressHash {
@@ -91,7 +220,18 @@ pub trait Interface {
 struct LocalInterface {
     address: AddressHash,
     tx_send: InterfaceTxSender,
+    pacer: Arc<Mutex<OutboundPacer>>,
+    traffic: Arc<Mutex<InterfaceTraffic>>,
     stop: CancellationToken,
+    reset: Arc<Notify>,
+}
+
+impl LocalInterface {
+    fn abandon_in_flight(&self) {
+        if let Ok(mut pacer) = self.pacer.lock() {
+            pacer.abandon();
+        }
+    }
 }
 
 pub struct InterfaceContext<T: Interface> {
@@ -106,9 +246,18 @@ pub struct InterfaceManager {
     rx_send: InterfaceRxSender,
     cancel: CancellationToken,
     ifaces: Vec<LocalInterface>,
+    max_in_flight: usize,
+    link_send: mpsc::Sender<InterfaceLinkEvent>,
+    link_recv: Option<mpsc::Receiver<InterfaceLinkEvent>>,
 }
 
 const DEFAULT_IFACE_TX_QUEUE_CAPACITY: usize = 128;
+/// Link transitions buffered until [`InterfaceManager::take_link_events`] is
+/// drained; later ones are dropped.
+const IFACE_LINK_EVENT_CAPACITY: usize = 256;
+/// Packets an interface may have queued for writing before further sends are
+/// paced per destination.
+pub const DEFAULT_IFACE_MAX_IN_FLIGHT: usize = 32;
 const IFACE_TX_ENQUEUE_TIMEOUT_MS: u64 = 200;
 
 fn tx_diag_enabled() -> bool {
@@ -131,29 +280,88 @@ impl InterfaceManager {
     pub fn new(rx_cap: usize) -> Self {
         let (rx_send, rx_recv) = InterfaceChannel::make_rx_channel(rx_cap);
         let rx_recv = Arc::new(tokio::sync::Mutex::new(rx_recv));
+        let (link_send, link_recv) = mpsc::channel(IFACE_LINK_EVENT_CAPACITY);
+
+        Self {
+            counter: 0,
+            rx_recv,
+            rx_send,
+            cancel: CancellationToken::new(),
+            ifaces: Vec::new(),
+            max_in_flight: DEFAULT_IFACE_MAX_IN_FLIGHT,
+            link_send,
+            link_recv: Some(link_recv),
+        }
+    }
+
+    /// Hands out the stream of link transitions reported by interface
+    /// workers. Only the first call returns it.
+    pub fn take_link_events(&mut self) -> Option<mpsc::Receiver<InterfaceLinkEvent>> {
+        self.link_recv.take()
+    }
 
-        Self { counter: 0, rx_recv, rx_send, cancel: CancellationToken::new(), ifaces: Vec::new() }
+    /// Caps in-flight packets on every interface, including ones already spawned.
+    pub fn set_max_in_flight(&mut self, max_in_flight: usize) {
+        self.max_in_flight = max_in_flight;
+        for iface in &self.ifaces {
+            if let Ok(mut pacer) = iface.pacer.lock() {
+                pacer.set_max_in_flight(max_in_flight);
+            }
+        }
     }
 
     pub fn new_channel(&mut self, tx_cap: usize) -> InterfaceChannel {
+        self.new_channel_with_traffic(tx_cap, Arc::default())
+    }
+
+    fn new_channel_with_traffic(
+        &mut self,
+        tx_cap: usize,
+        traffic: Arc<Mutex<InterfaceTraffic>>,
+    ) -> InterfaceChannel {
         self.counter += 1;
 
         let counter_bytes = self.counter.to_le_bytes();
         let address = AddressHash::new_from_hash(&Hash::new_from_slice(&counter_bytes[..]));
 
-        let (tx_send, tx_recv) = InterfaceChannel::make_tx_channel(tx_cap);
+        let (tx_send, tx_recv) = mpsc::channel(tx_cap);
+        let pacer = Arc::new(Mutex::new(OutboundPacer::new(self.max_in_flight)));
+        let tx_recv = InterfaceTxReceiver::new(tx_recv, pacer.clone(), traffic.clone());
 
         log::debug!("iface: create channel {}", address);
 
         let stop = CancellationToken::new();
-
-        self.ifaces.push(LocalInterface { address, tx_send, stop: stop.clone() });
-
-        InterfaceChannel { rx_channel: self.rx_send.clone(), tx_channel: tx_recv, address, stop }
+        let reset = Arc::new(Notify::new());
+
+        self.ifaces.push(LocalInterface {
+            address,
+            tx_send,
+            pacer,
+            traffic,
+            stop: stop.clone(),
+            reset: reset.clone(),
+        });
+
+        InterfaceChannel {
+            rx_channel: self.rx_send.clone(),
+            tx_channel: tx_recv,
+            address,
+            stop,
+            reset,
+            link: InterfaceLinkReporter::new(address, Some(self.link_send.clone())),
+        }
     }
 
     pub fn new_context<T: Interface>(&mut self, inner: T) -> InterfaceContext<T> {
-        let channel = self.new_channel(DEFAULT_IFACE_TX_QUEUE_CAPACITY);
+        self.new_context_with_traffic(inner, Arc::default())
+    }
+
+    fn new_context_with_traffic<T: Interface>(
+        &mut self,
+        inner: T,
+        traffic: Arc<Mutex<InterfaceTraffic>>,
+    ) -> InterfaceContext<T> {
+        let channel = self.new_channel_with_traffic(DEFAULT_IFACE_TX_QUEUE_CAPACITY, traffic);
 
         let inner = Arc::new(Mutex::new(inner));
 
@@ -166,7 +374,45 @@ impl InterfaceManager {
         R: std::future::Future<Output = ()> + Send + 'static,
         R::Output: Send + 'static,
     {
-        let context = self.new_context(inner);
+        self.spawn_with_traffic(inner, worker, Arc::default())
+    }
+
+    /// Spawns an interface whose traffic counts toward `parent`, such as a
+    /// client accepted by a server interface. The totals stay with the parent
+    /// when the child goes away, so clients that reconnect keep adding to
+    /// them. Without a spawned `parent` the child counts on its own.
+    pub fn spawn_child<T: Interface, F, R>(
+        &mut self,
+        parent: &AddressHash,
+        inner: T,
+        worker: F,
+    ) -> AddressHash
+    where
+        F: FnOnce(InterfaceContext<T>) -> R,
+        R: std::future::Future<Output = ()> + Send + 'static,
+        R::Output: Send + 'static,
+    {
+        let traffic = self
+            .ifaces
+            .iter()
+            .find(|iface| iface.address == *parent)
+            .map(|iface| iface.traffic.clone())
+            .unwrap_or_default();
+        self.spawn_with_traffic(inner, worker, traffic)
+    }
+
+    fn spawn_with_traffic<T: Interface, F, R>(
+        &mut self,
+        inner: T,
+        worker: F,
+        traffic: Arc<Mutex<InterfaceTraffic>>,
+    ) -> AddressHash
+    where
+        F: FnOnce(InterfaceContext<T>) -> R,
+        R: std::future::Future<Output = ()> + Send + 'static,
+        R::Output: Send + 'static,
+    {
+        let context = self.new_context_with_traffic(inner, traffic);
         let address = *context.channel.address();
 
         task::spawn(worker(context));
@@ -178,6 +424,45 @@ impl InterfaceManager {
         self.rx_recv.clone()
     }
 
+    /// Traffic counters for a spawned interface, or `None` once it has been
+    /// cleaned up.
+    pub fn traffic(&self, address: &AddressHash) -> Option<InterfaceTraffic> {
+        self.ifaces
+            .iter()
+            .find(|iface| iface.address == *address)
+            .and_then(|iface| iface.traffic.lock().ok().map(|traffic| *traffic))
+    }
+
+    pub fn record_rx(&self, message: &RxMessage) {
+        let Some(iface) = self.ifaces.iter().find(|iface| iface.address == message.address) else {
+            return;
+        };
+        if let Ok(mut traffic) = iface.traffic.lock() {
+            traffic.record_in(&message.packet);
+        }
+    }
+
+    /// Asks a spawned interface to drop its current connection and redial,
+    /// keeping its queued packets. Returns `false` for unknown or stopped
+    /// interfaces. A worker that is between connections redials straight
+    /// away; one that is busy picks the request up at its next wait.
+    pub fn reset(&self, address: &AddressHash) -> bool {
+        let Some(iface) = self
+            .ifaces
+            .iter()
+            .find(|iface| iface.address == *address && !iface.stop.is_cancelled())
+        else {
+            return false;
+        };
+        iface.reset.notify_one();
+        true
+    }
+
+    /// Returns true while the interface at `address` is spawned and running.
+    pub fn is_running(&self, address: &AddressHash) -> bool {
+        self.ifaces.iter().any(|iface| iface.address == *address && !iface.stop.is_cancelled())
+    }
+
     pub fn cleanup(&mut self) {
         self.ifaces.retain(|iface| !iface.stop.is_cancelled());
     }
@@ -199,6 +484,26 @@ impl InterfaceManager {
 
             if should_send && !iface.stop.is_cancelled() {
                 trace.matched_ifaces += 1;
+                let paced = match iface.pacer.lock() {
+                    Ok(mut pacer) => pacer.submit(message),
+                    Err(_) => PacedSend::Dispatch(message),
+                };
+                let message = match paced {
+                    PacedSend::Dispatch(message) => message,
+                    PacedSend::Queued => {
+                        trace.sent_ifaces += 1;
+                        continue;
+                    }
+                    PacedSend::Rejected => {
+                        trace.failed_ifaces += 1;
+                        log::warn!(
+                            "iface: paced tx queue full on {} for {:?}",
+                            iface.address,
+                            message.tx_type
+                        );
+                        continue;
+                    }
+                };
                 match iface.tx_send.try_send(message) {
                     Ok(()) => {
                         trace.sent_ifaces += 1;
@@ -222,6 +527,7 @@ impl InterfaceManager {
                             }
                             Ok(Err(_)) => {
                                 trace.failed_ifaces += 1;
+                                iface.abandon_in_flight();
                                 log::warn!(
                                     "iface: tx queue closed on {} for {:?}",
                                     iface.address,
@@ -230,6 +536,7 @@ impl InterfaceManager {
                             }
                             Err(_) => {
                                 trace.failed_ifaces += 1;
+                                iface.abandon_in_flight();
                                 log::warn!(
                                     "iface: tx queue full timeout on {} for {:?}",
                                     iface.address,
@@ -240,6 +547,7 @@ impl InterfaceManager {
                     }
                     Err(mpsc::error::TrySendError::Closed(_)) => {
                         trace.failed_ifaces += 1;
+                        iface.abandon_in_flight();
                         log::warn!(
                             "iface: tx queue closed on {} for {:?}",
                             iface.address,
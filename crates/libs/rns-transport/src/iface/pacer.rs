@@ -0,0 +1,176 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::hash::AddressHash;
+
+use super::TxMessage;
+
+/// Packets held back per interface before new sends are rejected.
+const MAX_PACED_QUEUE_LEN: usize = 1_024;
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, PartialEq, Eq)]
+pub enum PacedSend {
+    /// Hand this message to the interface now. It is not necessarily the one
+    /// just submitted when other destinations were already waiting.
+    Dispatch(TxMessage),
+    Queued,
+    Rejected,
+}
+
+/// Caps the number of packets in flight on one interface. Sends beyond the
+/// cap wait in per-destination queues that are drained round-robin, so one
+/// busy conversation cannot monopolize a slow link.
+#[derive(Debug)]
+pub struct OutboundPacer {
+    max_in_flight: usize,
+    in_flight: usize,
+    queued: usize,
+    queues: HashMap<AddressHash, VecDeque<TxMessage>>,
+    order: VecDeque<AddressHash>,
+}
+
+impl OutboundPacer {
+    pub fn new(max_in_flight: usize) -> Self {
+        Self {
+            max_in_flight: max_in_flight.max(1),
+            in_flight: 0,
+            queued: 0,
+            queues: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    pub fn set_max_in_flight(&mut self, max_in_flight: usize) {
+        self.max_in_flight = max_in_flight.max(1);
+    }
+
+    pub fn submit(&mut self, message: TxMessage) -> PacedSend {
+        if self.queued == 0 && self.in_flight < self.max_in_flight {
+            self.in_flight += 1;
+            return PacedSend::Dispatch(message);
+        }
+        if self.queued >= MAX_PACED_QUEUE_LEN {
+            return PacedSend::Rejected;
+        }
+        self.enqueue(message);
+        match self.release() {
+            Some(next) => PacedSend::Dispatch(next),
+            None => PacedSend::Queued,
+        }
+    }
+
+    /// Marks one in-flight packet as written and returns the next queued
+    /// packet, if any, which is then counted as in flight.
+    pub fn complete(&mut self) -> Option<TxMessage> {
+        self.in_flight = self.in_flight.saturating_sub(1);
+        self.release()
+    }
+
+    /// Forgets an in-flight packet the interface never accepted.
+    pub fn abandon(&mut self) {
+        self.in_flight = self.in_flight.saturating_sub(1);
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.in_flight
+    }
+
+    pub fn queued(&self) -> usize {
+        self.queued
+    }
+
+    fn enqueue(&mut self, message: TxMessage) {
+        let destination = message.packet.destination;
+        let queue = self.queues.entry(destination).or_default();
+        if queue.is_empty() {
+            self.order.push_back(destination);
+        }
+        queue.push_back(message);
+        self.queued += 1;
+    }
+
+    fn release(&mut self) -> Option<TxMessage> {
+        if self.in_flight >= self.max_in_flight {
+            return None;
+        }
+        let destination = self.order.pop_front()?;
+        let queue = self.queues.get_mut(&destination)?;
+        let message = queue.pop_front()?;
+        if queue.is_empty() {
+            self.queues.remove(&destination);
+        } else {
+            self.order.push_back(destination);
+        }
+        self.queued -= 1;
+        self.in_flight += 1;
+        Some(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OutboundPacer, PacedSend, MAX_PACED_QUEUE_LEN};
+    use crate::hash::AddressHash;
+    use crate::iface::{TxMessage, TxMessageType};
+    use crate::packet::{Packet, PacketDataBuffer};
+
+    fn message(destination: u8, seq: u8) -> TxMessage {
+        let packet = Packet {
+            destination: AddressHash::new([destination; 16]),
+            data: PacketDataBuffer::new_from_slice(&[seq]),
+            ..Default::default()
+        };
+        TxMessage { tx_type: TxMessageType::Broadcast(None), packet }
+    }
+
+    fn tag(message: &TxMessage) -> (u8, u8) {
+        (message.packet.destination.as_slice()[0], message.packet.data.as_slice()[0])
+    }
+
+    #[test]
+    fn sends_beyond_cap_queue_until_earlier_ones_complete() {
+        let mut pacer = OutboundPacer::new(2);
+        assert_eq!(pacer.submit(message(1, 0)), PacedSend::Dispatch(message(1, 0)));
+        assert_eq!(pacer.submit(message(1, 1)), PacedSend::Dispatch(message(1, 1)));
+        assert_eq!(pacer.submit(message(1, 2)), PacedSend::Queued);
+        assert_eq!(pacer.submit(message(1, 3)), PacedSend::Queued);
+        assert_eq!((pacer.in_flight(), pacer.queued()), (2, 2));
+
+        assert_eq!(pacer.complete().as_ref().map(tag), Some((1, 2)));
+        assert_eq!(pacer.complete().as_ref().map(tag), Some((1, 3)));
+        assert_eq!(pacer.complete(), None);
+        assert_eq!(pacer.complete(), None);
+        assert_eq!((pacer.in_flight(), pacer.queued()), (0, 0));
+    }
+
+    #[test]
+    fn queued_sends_interleave_across_destinations() {
+        let mut pacer = OutboundPacer::new(1);
+        assert!(matches!(pacer.submit(message(1, 0)), PacedSend::Dispatch(_)));
+        for seq in 1..4 {
+            assert_eq!(pacer.submit(message(1, seq)), PacedSend::Queued);
+        }
+        assert_eq!(pacer.submit(message(2, 0)), PacedSend::Queued);
+        assert_eq!(pacer.submit(message(3, 0)), PacedSend::Queued);
+        assert_eq!(pacer.submit(message(2, 1)), PacedSend::Queued);
+
+        let mut released = Vec::new();
+        while let Some(next) = pacer.complete() {
+            released.push(tag(&next));
+        }
+        assert_eq!(released, vec![(1, 1), (2, 0), (3, 0), (1, 2), (2, 1), (1, 3)]);
+    }
+
+    #[test]
+    fn abandoned_dispatch_frees_a_slot_and_full_queue_rejects() {
+        let mut pacer = OutboundPacer::new(1);
+        assert!(matches!(pacer.submit(message(1, 0)), PacedSend::Dispatch(_)));
+        pacer.abandon();
+        assert!(matches!(pacer.submit(message(1, 1)), PacedSend::Dispatch(_)));
+
+        for _ in 0..MAX_PACED_QUEUE_LEN {
+            assert_eq!(pacer.submit(message(2, 0)), PacedSend::Queued);
+        }
+        assert_eq!(pacer.submit(message(3, 0)), PacedSend::Rejected);
+    }
+}
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 use std::sync::OnceLock;
+use std::time::Duration;
 
 use tokio::io::AsyncWriteExt;
 use tokio::net::TcpStream;
@@ -16,7 +17,7 @@ use tokio::io::AsyncReadExt;
 use alloc::string::String;
 
 use super::hdlc::Hdlc;
-use super::{Interface, InterfaceContext};
+use super::{Interface, InterfaceContext, InterfaceLinkState};
 
 // TCP packet tracing is kept off by default and gated by diagnostics env flags.
 const PACKET_TRACE: bool = false;
@@ -37,23 +38,74 @@ fn tx_diag_enabled() -> bool {
     })
 }
 
+/// Redial delay used when `TcpSettings::reconnect_backoff` is unset.
+pub const DEFAULT_RECONNECT_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Socket tuning for TCP interfaces. Unset fields keep the historical
+/// behaviour: no connect timeout, OS keepalive defaults and a fixed
+/// `DEFAULT_RECONNECT_BACKOFF` between dial attempts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TcpSettings {
+    pub connect_timeout: Option<Duration>,
+    pub keepalive: Option<Duration>,
+    pub reconnect_backoff: Option<Duration>,
+}
+
+impl TcpSettings {
+    fn backoff(&self) -> Duration {
+        self.reconnect_backoff.unwrap_or(DEFAULT_RECONNECT_BACKOFF)
+    }
+
+    /// Enables TCP keepalive on `stream` when an idle time is configured.
+    fn apply(&self, stream: &TcpStream) {
+        let Some(idle) = self.keepalive else {
+            return;
+        };
+        let keepalive = socket2::TcpKeepalive::new().with_time(idle);
+        if let Err(err) = socket2::SockRef::from(stream).set_tcp_keepalive(&keepalive) {
+            log::warn!("tcp_client: couldn't enable keepalive: {}", err);
+        }
+    }
+
+    async fn connect(&self, addr: &str) -> Result<TcpStream, RnsError> {
+        let stream = match self.connect_timeout {
+            Some(limit) => tokio::time::timeout(limit, TcpStream::connect(addr))
+                .await
+                .map_err(|_| RnsError::ConnectionError)?,
+            None => TcpStream::connect(addr).await,
+        }
+        .map_err(|_| RnsError::ConnectionError)?;
+        self.apply(&stream);
+        Ok(stream)
+    }
+}
+
 pub struct TcpClient {
     addr: String,
     stream: Option<TcpStream>,
+    settings: TcpSettings,
 }
 
 impl TcpClient {
     pub fn new<T: Into<String>>(addr: T) -> Self {
-        Self { addr: addr.into(), stream: None }
+        Self { addr: addr.into(), stream: None, settings: TcpSettings::default() }
     }
 
     pub fn new_from_stream<T: Into<String>>(addr: T, stream: TcpStream) -> Self {
-        Self { addr: addr.into(), stream: Some(stream) }
+        Self { addr: addr.into(), stream: Some(stream), settings: TcpSettings::default() }
+    }
+
+    pub fn with_settings(mut self, settings: TcpSettings) -> Self {
+        self.settings = settings;
+        self
     }
 
     pub async fn spawn(context: InterfaceContext<TcpClient>) {
         let iface_stop = context.channel.stop.clone();
+        let iface_reset = context.channel.reset.clone();
+        let link = context.channel.link.clone();
         let addr = { context.inner.lock().unwrap().addr.clone() };
+        let settings = { context.inner.lock().unwrap().settings };
         let iface_address = context.channel.address;
         let mut stream = { context.inner.lock().unwrap().stream.take() };
 
@@ -70,17 +122,23 @@ impl TcpClient {
                 match stream.take() {
                     Some(stream) => {
                         running = false;
+                        settings.apply(&stream);
                         Ok(stream)
                     }
-                    None => TcpStream::connect(addr.clone())
-                        .await
-                        .map_err(|_| RnsError::ConnectionError),
+                    None => {
+                        link.report(InterfaceLinkState::Connecting);
+                        settings.connect(&addr).await
+                    }
                 }
             };
 
             if stream.is_err() {
                 log::info!("tcp_client: couldn't connect to <{}>", addr);
-                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                link.report(InterfaceLinkState::Disconnected);
+                tokio::select! {
+                    _ = tokio::time::sleep(settings.backoff()) => {}
+                    _ = iface_reset.notified() => {}
+                }
                 continue;
             }
 
@@ -91,6 +149,7 @@ impl TcpClient {
             let (read_stream, write_stream) = stream.into_split();
 
             log::info!("tcp_client connected to <{}>", addr);
+            link.report(InterfaceLinkState::Connected);
 
             // Use protocol MTU-scale buffers, not size_of::<Packet>(), since packet
             // struct size does not reflect serialized wire size and can silently drop
@@ -101,6 +160,7 @@ impl TcpClient {
             let rx_task = {
                 let cancel = cancel.clone();
                 let stop = stop.clone();
+                let reset = iface_reset.clone();
                 let mut stream = read_stream;
                 let rx_channel = rx_channel.clone();
 
@@ -117,6 +177,11 @@ impl TcpClient {
                             _ = stop.cancelled() => {
                                     break;
                             }
+                            _ = reset.notified() => {
+                                    log::info!("tcp_client: reconnect requested");
+                                    stop.cancel();
+                                    break;
+                            }
                             result = stream.read(&mut tcp_buffer[..]) => {
                                     match result {
                                         Ok(0) => {
@@ -286,6 +351,7 @@ impl TcpClient {
             rx_task.await.unwrap();
 
             log::info!("tcp_client: disconnected from <{}>", addr);
+            link.report(InterfaceLinkState::Disconnected);
         }
 
         iface_stop.cancel();
@@ -297,3 +363,87 @@ impl Interface for TcpClient {
         2048
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{TcpClient, TcpSettings};
+    use crate::hash::AddressHash;
+    use crate::iface::InterfaceLinkState::{Connected, Connecting, Disconnected};
+    use crate::iface::{InterfaceLinkEvent, InterfaceManager};
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc::Receiver;
+    use tokio::time::{timeout, Duration};
+
+    #[tokio::test]
+    async fn reset_drops_the_connection_and_redials() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr").to_string();
+        let mut manager = InterfaceManager::new(16);
+        let iface = manager.spawn(TcpClient::new(addr), TcpClient::spawn);
+
+        let (mut first, _) =
+            timeout(Duration::from_secs(2), listener.accept()).await.expect("first dial").unwrap();
+        // Give the worker a moment to start waiting on the reset signal.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(manager.reset(&iface));
+
+        let mut buf = [0u8; 1];
+        let read = timeout(Duration::from_secs(2), first.read(&mut buf)).await.expect("close");
+        assert_eq!(read.expect("read"), 0);
+        timeout(Duration::from_secs(2), listener.accept()).await.expect("redial").unwrap();
+
+        assert!(!manager.reset(&AddressHash::new_from_slice(&[0u8; 16])));
+    }
+
+    #[tokio::test]
+    async fn link_transitions_are_reported_once_per_change() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr").to_string();
+        let mut manager = InterfaceManager::new(16);
+        let mut events = manager.take_link_events().expect("link events");
+        assert!(manager.take_link_events().is_none());
+        let iface = manager.spawn(TcpClient::new(addr), TcpClient::spawn);
+
+        let (first, _) =
+            timeout(Duration::from_secs(2), listener.accept()).await.expect("dial").unwrap();
+        assert_eq!(
+            next_link_event(&mut events).await,
+            InterfaceLinkEvent { address: iface, state: Connecting }
+        );
+        assert_eq!(next_link_event(&mut events).await.state, Connected);
+
+        drop(first);
+        assert_eq!(next_link_event(&mut events).await.state, Disconnected);
+        assert_eq!(next_link_event(&mut events).await.state, Connecting);
+        timeout(Duration::from_secs(2), listener.accept()).await.expect("redial").unwrap();
+        assert_eq!(next_link_event(&mut events).await.state, Connected);
+    }
+
+    async fn next_link_event(events: &mut Receiver<InterfaceLinkEvent>) -> InterfaceLinkEvent {
+        timeout(Duration::from_secs(2), events.recv()).await.expect("event").expect("open")
+    }
+
+    #[tokio::test]
+    async fn settings_apply_keepalive_and_bound_the_dial() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr").to_string();
+
+        let plain = TcpSettings::default().connect(&addr).await.expect("plain dial");
+        assert!(!socket2::SockRef::from(&plain).keepalive().expect("keepalive"));
+
+        let settings = TcpSettings {
+            connect_timeout: Some(Duration::from_millis(500)),
+            keepalive: Some(Duration::from_secs(30)),
+            reconnect_backoff: None,
+        };
+        let tuned = settings.connect(&addr).await.expect("tuned dial");
+        assert!(socket2::SockRef::from(&tuned).keepalive().expect("keepalive"));
+
+        // Nothing listens on a dropped listener's port, so the dial fails
+        // well within the configured timeout instead of hanging.
+        drop(listener);
+        let refused = timeout(Duration::from_secs(2), settings.connect(&addr)).await;
+        assert!(refused.expect("bounded dial").is_err());
+    }
+}
@@ -2,15 +2,18 @@ use alloc::string::String;
 use std::sync::Arc;
 
 use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
 
 use crate::error::RnsError;
+use crate::hash::AddressHash;
 
-use super::tcp_client::TcpClient;
-use super::{Interface, InterfaceContext, InterfaceManager};
+use super::tcp_client::{TcpClient, TcpSettings};
+use super::{Interface, InterfaceContext, InterfaceLinkState, InterfaceManager};
 
 pub struct TcpServer {
     addr: String,
     iface_manager: Arc<tokio::sync::Mutex<InterfaceManager>>,
+    settings: TcpSettings,
 }
 
 impl TcpServer {
@@ -18,17 +21,33 @@ impl TcpServer {
         addr: T,
         iface_manager: Arc<tokio::sync::Mutex<InterfaceManager>>,
     ) -> Self {
-        Self { addr: addr.into(), iface_manager }
+        Self { addr: addr.into(), iface_manager, settings: TcpSettings::default() }
+    }
+
+    /// Settings handed to every accepted client connection.
+    pub fn with_settings(mut self, settings: TcpSettings) -> Self {
+        self.settings = settings;
+        self
     }
 
     pub async fn spawn(context: InterfaceContext<Self>) {
         let addr = { context.inner.lock().unwrap().addr.clone() };
 
         let iface_manager = { context.inner.lock().unwrap().iface_manager.clone() };
-
-        let (_, tx_channel) = context.channel.split();
+        let settings = { context.inner.lock().unwrap().settings };
+        let link = context.channel.link.clone();
+        let iface_reset = context.channel.reset.clone();
+        let server = *context.channel.address();
+
+        // Packets queued on the listener are dropped; its traffic is what the
+        // accepted clients carry, counted on their shared totals.
+        let (_, mut tx_channel) = context.channel.split();
+        tx_channel.detach_traffic();
         let tx_channel = Arc::new(tokio::sync::Mutex::new(tx_channel));
 
+        // Interfaces spawned for accepted clients, dropped on reset.
+        let mut clients: Vec<AddressHash> = Vec::new();
+
         loop {
             if context.cancel.is_cancelled() {
                 break;
@@ -39,21 +58,28 @@ impl TcpServer {
 
             if listener.is_err() {
                 log::warn!("tcp_server: couldn't bind to <{}>", addr);
-                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                link.report(InterfaceLinkState::Disconnected);
+                tokio::select! {
+                    _ = tokio::time::sleep(std::time::Duration::from_secs(5)) => {}
+                    _ = iface_reset.notified() => {}
+                }
                 continue;
             }
 
             log::info!("tcp_server: listen on <{}>", addr);
+            link.report(InterfaceLinkState::Connected);
 
             let listener = listener.unwrap();
+            let stop = CancellationToken::new();
 
             let tx_task = {
                 let cancel = context.cancel.clone();
+                let stop = stop.clone();
                 let tx_channel = tx_channel.clone();
 
                 tokio::spawn(async move {
                     loop {
-                        if cancel.is_cancelled() {
+                        if cancel.is_cancelled() || stop.is_cancelled() {
                             break;
                         }
 
@@ -63,6 +89,9 @@ impl TcpServer {
                             _ = cancel.cancelled() => {
                                 break;
                             }
+                            _ = stop.cancelled() => {
+                                break;
+                            }
                             // Skip all tx messages
                             _ = tx_channel.recv() => {}
                         }
@@ -82,6 +111,15 @@ impl TcpServer {
                         break;
                     }
 
+                    _ = iface_reset.notified() => {
+                        log::info!("tcp_server: reconnect requested on <{}>", addr);
+                        let iface_manager = iface_manager.lock().await;
+                        for client in clients.drain(..) {
+                            iface_manager.reset(&client);
+                        }
+                        break;
+                    }
+
                     client = listener.accept() => {
                         if let Ok(client) = client {
                             log::info!(
@@ -92,16 +130,23 @@ impl TcpServer {
 
                             let mut iface_manager = iface_manager.lock().await;
 
-                            iface_manager.spawn(
-                                TcpClient::new_from_stream(client.1.to_string(), client.0),
+                            clients.retain(|client| iface_manager.is_running(client));
+                            clients.push(iface_manager.spawn_child(
+                                &server,
+                                TcpClient::new_from_stream(client.1.to_string(), client.0)
+                                    .with_settings(settings),
                                 TcpClient::spawn,
-                            );
+                            ));
                         }
                     }
                 }
             }
 
+            stop.cancel();
+            drop(listener);
             let _ = tokio::join!(tx_task);
+            log::info!("tcp_server: closed <{}>", addr);
+            link.report(InterfaceLinkState::Disconnected);
         }
     }
 }
@@ -111,3 +156,91 @@ impl Interface for TcpServer {
         2048
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::TcpServer;
+    use crate::iface::InterfaceLinkState::{Connected, Disconnected};
+    use crate::iface::{InterfaceManager, TxMessage, TxMessageType};
+    use crate::packet::Packet;
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+    use tokio::time::{timeout, Duration};
+
+    #[tokio::test]
+    async fn reset_drops_accepted_clients_and_listens_again() {
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .expect("free port")
+            .to_string();
+        let manager = Arc::new(tokio::sync::Mutex::new(InterfaceManager::new(16)));
+        let mut events = manager.lock().await.take_link_events().expect("link events");
+        let server = manager
+            .lock()
+            .await
+            .spawn(TcpServer::new(addr.clone(), manager.clone()), TcpServer::spawn);
+        // Accepted clients report on their own interfaces; keep the server's.
+        let mut server_states = move || {
+            std::iter::from_fn(|| events.try_recv().ok())
+                .filter(|event| event.address == server)
+                .map(|event| event.state)
+                .collect::<Vec<_>>()
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(server_states(), vec![Connected]);
+
+        let mut client = TcpStream::connect(&addr).await.expect("connect");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(manager.lock().await.reset(&server));
+
+        let mut buf = [0u8; 1];
+        let read = timeout(Duration::from_secs(2), client.read(&mut buf)).await.expect("close");
+        assert_eq!(read.expect("read"), 0);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(server_states(), vec![Disconnected, Connected]);
+        TcpStream::connect(&addr).await.expect("listening again");
+    }
+
+    #[tokio::test]
+    async fn traffic_of_accepted_clients_outlives_their_connections() {
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .expect("free port")
+            .to_string();
+        let manager = Arc::new(tokio::sync::Mutex::new(InterfaceManager::new(16)));
+        let server = manager
+            .lock()
+            .await
+            .spawn(TcpServer::new(addr.clone(), manager.clone()), TcpServer::spawn);
+        let receiver = manager.lock().await.receiver();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        for _ in 0..2 {
+            let mut client = TcpStream::connect(&addr).await.expect("connect");
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            let message =
+                TxMessage { tx_type: TxMessageType::Broadcast(None), packet: Packet::default() };
+            manager.lock().await.send(message).await;
+            let mut buf = [0u8; 64];
+            let read = timeout(Duration::from_secs(2), client.read(&mut buf)).await.expect("frame");
+            let read = read.expect("read");
+            assert!(read > 0);
+            // Echo the frame so the client's interface also carries it inbound.
+            client.write_all(&buf[..read]).await.expect("echo");
+            let received =
+                timeout(Duration::from_secs(2), receiver.lock().await.recv()).await.expect("rx");
+            manager.lock().await.record_rx(&received.expect("rx open"));
+            drop(client);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            manager.lock().await.cleanup();
+        }
+
+        let traffic = manager.lock().await.traffic(&server).expect("server traffic");
+        assert_eq!(traffic.packets_in, 2);
+        assert_eq!(traffic.packets_out, 2);
+        assert_eq!(traffic.bytes_in, traffic.bytes_out);
+        assert_eq!(traffic.bytes_out, 2 * Packet::default().wire_len() as u64);
+        assert!(traffic.last_activity_secs.is_some());
+    }
+}
@@ -1,3 +1,4 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::sync::Arc;
 
 use tokio::net::UdpSocket;
@@ -9,7 +10,7 @@ use crate::iface::RxMessage;
 use crate::packet::Packet;
 use crate::serde::Serialize;
 
-use super::{Interface, InterfaceContext};
+use super::{Interface, InterfaceContext, InterfaceLinkState};
 
 // UDP trace logging stays on by default for packet-level network bring-up visibility.
 const PACKET_TRACE: bool = true;
@@ -17,17 +18,40 @@ const PACKET_TRACE: bool = true;
 pub struct UdpInterface {
     bind_addr: String,
     forward_addr: Option<String>,
+    multicast: Option<(IpAddr, u32)>,
 }
 
 impl UdpInterface {
     pub fn new<T: Into<String>>(bind_addr: T, forward_addr: Option<T>) -> Self {
-        Self { bind_addr: bind_addr.into(), forward_addr: forward_addr.map(Into::into) }
+        Self {
+            bind_addr: bind_addr.into(),
+            forward_addr: forward_addr.map(Into::into),
+            multicast: None,
+        }
+    }
+
+    /// Listens on `port` on all addresses, joins `group` and sends every packet
+    /// to `group:port`. `ttl` bounds how many hops IPv4 packets travel; IPv6
+    /// groups use the system default hop limit.
+    pub fn multicast(group: IpAddr, port: u16, ttl: u32) -> Self {
+        let any: IpAddr = match group {
+            IpAddr::V4(_) => Ipv4Addr::UNSPECIFIED.into(),
+            IpAddr::V6(_) => Ipv6Addr::UNSPECIFIED.into(),
+        };
+        Self {
+            bind_addr: SocketAddr::new(any, port).to_string(),
+            forward_addr: Some(SocketAddr::new(group, port).to_string()),
+            multicast: Some((group, ttl)),
+        }
     }
 
     pub async fn spawn(context: InterfaceContext<Self>) {
         let bind_addr = { context.inner.lock().unwrap().bind_addr.clone() };
         let forward_addr = { context.inner.lock().unwrap().forward_addr.clone() };
+        let multicast = { context.inner.lock().unwrap().multicast };
         let iface_address = context.channel.address;
+        let link = context.channel.link.clone();
+        let iface_reset = context.channel.reset.clone();
 
         let (rx_channel, tx_channel) = context.channel.split();
         let tx_channel = Arc::new(tokio::sync::Mutex::new(tx_channel));
@@ -42,7 +66,11 @@ impl UdpInterface {
 
             if socket.is_err() {
                 log::info!("udp_interface: couldn't bind to <{}>", bind_addr);
-                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                link.report(InterfaceLinkState::Disconnected);
+                tokio::select! {
+                    _ = tokio::time::sleep(std::time::Duration::from_secs(5)) => {}
+                    _ = iface_reset.notified() => {}
+                }
                 continue;
             }
 
@@ -50,10 +78,22 @@ impl UdpInterface {
             let stop = CancellationToken::new();
 
             let socket = socket.unwrap();
+            if let Some((group, ttl)) = multicast {
+                if let Err(err) = join_multicast_group(&socket, group, ttl) {
+                    log::info!("udp_interface: couldn't join multicast group <{}>: {}", group, err);
+                    link.report(InterfaceLinkState::Disconnected);
+                    tokio::select! {
+                        _ = tokio::time::sleep(std::time::Duration::from_secs(5)) => {}
+                        _ = iface_reset.notified() => {}
+                    }
+                    continue;
+                }
+            }
             let read_socket = Arc::new(socket);
             let write_socket = read_socket.clone();
 
             log::info!("udp_interface bound to <{}>", bind_addr);
+            link.report(InterfaceLinkState::Connected);
 
             const BUFFER_SIZE: usize = core::mem::size_of::<Packet>() * 3;
 
@@ -63,6 +103,7 @@ impl UdpInterface {
                 let stop = stop.clone();
                 let socket = read_socket;
                 let rx_channel = rx_channel.clone();
+                let reset = iface_reset.clone();
 
                 tokio::spawn(async move {
                     loop {
@@ -75,6 +116,11 @@ impl UdpInterface {
                             _ = stop.cancelled() => {
                                     break;
                             }
+                            _ = reset.notified() => {
+                                    log::info!("udp_interface: reconnect requested");
+                                    stop.cancel();
+                                    break;
+                            }
                             result = socket.recv_from(&mut rx_buffer) => {
                                 match result {
                                     Ok((0, _)) => {
@@ -147,7 +193,18 @@ impl UdpInterface {
             rx_task.await.unwrap();
 
             log::info!("udp_interface <{}>: closed", bind_addr);
+            link.report(InterfaceLinkState::Disconnected);
+        }
+    }
+}
+
+fn join_multicast_group(socket: &UdpSocket, group: IpAddr, ttl: u32) -> std::io::Result<()> {
+    match group {
+        IpAddr::V4(group) => {
+            socket.join_multicast_v4(group, Ipv4Addr::UNSPECIFIED)?;
+            socket.set_multicast_ttl_v4(ttl)
         }
+        IpAddr::V6(group) => socket.join_multicast_v6(&group, 0),
     }
 }
 
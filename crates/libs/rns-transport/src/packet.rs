@@ -312,6 +312,13 @@ impl Packet {
         Ok(Self { header, ifac: None, destination, transport, context, data })
     }
 
+    /// Serialized length of the packet as written by [`Packet::to_bytes`].
+    pub fn wire_len(&self) -> usize {
+        let transport_len =
+            if self.header.header_type == HeaderType::Type2 { ADDRESS_HASH_SIZE } else { 0 };
+        2 + transport_len + ADDRESS_HASH_SIZE + 1 + self.data.len()
+    }
+
     pub fn to_bytes(&self) -> Result<Vec<u8>, RnsError> {
         let mut out = Vec::with_capacity(2 + ADDRESS_HASH_SIZE + 1 + self.data.len());
 
@@ -1,4 +1,5 @@
 use super::*;
+use crate::iface::DEFAULT_IFACE_MAX_IN_FLIGHT;
 
 impl TransportConfig {
     pub fn new<T: Into<String>>(name: T, identity: &PrivateIdentity, broadcast: bool) -> Self {
@@ -16,6 +17,7 @@ impl TransportConfig {
             link_idle_timeout_secs: 900,
             resource_retry_interval_secs: 2,
             resource_retry_limit: 5,
+            iface_max_in_flight: DEFAULT_IFACE_MAX_IN_FLIGHT,
             ratchet_store_path: None,
         }
     }
@@ -63,6 +65,10 @@ impl TransportConfig {
         self.resource_retry_limit = limit;
     }
 
+    pub fn set_iface_max_in_flight(&mut self, max_in_flight: usize) {
+        self.iface_max_in_flight = max_in_flight;
+    }
+
     pub fn set_ratchet_store_path(&mut self, path: PathBuf) {
         self.ratchet_store_path = Some(path);
     }
@@ -84,6 +90,7 @@ impl Default for TransportConfig {
             link_idle_timeout_secs: 900,
             resource_retry_interval_secs: 2,
             resource_retry_limit: 5,
+            iface_max_in_flight: DEFAULT_IFACE_MAX_IN_FLIGHT,
             ratchet_store_path: None,
         }
     }
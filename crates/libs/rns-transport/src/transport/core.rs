@@ -11,7 +11,8 @@ impl Transport {
         let (iface_messages_tx, _) = tokio::sync::broadcast::channel(16);
         let (resource_events_tx, _) = tokio::sync::broadcast::channel(16);
 
-        let iface_manager = InterfaceManager::new(128);
+        let mut iface_manager = InterfaceManager::new(128);
+        iface_manager.set_max_in_flight(config.iface_max_in_flight);
 
         let rx_receiver = iface_manager.receiver();
 
@@ -98,6 +99,8 @@ impl Transport {
                                     request_id: payload.request_id(),
                                     hops: None,
                                     interface: None,
+                                    received_on: None,
+                                    link_id: Some(event.id),
                                 });
                             }
                         }
@@ -193,6 +196,30 @@ impl Transport {
         handler.send_packet(packet).await;
     }
 
+    /// Announces `destination` only on the interface holding the path to
+    /// `target` and asks that interface for a fresh path to it. Without a
+    /// known path nothing is announced and the path request goes out on
+    /// every interface. Returns whether a path to `target` was known.
+    pub async fn send_announce_to(
+        &self,
+        destination: &Arc<Mutex<SingleInputDestination>>,
+        app_data: Option<&[u8]>,
+        target: &AddressHash,
+    ) -> bool {
+        let mut handler = self.handler.lock().await;
+        let Some(iface) = handler.path_table.next_hop_iface(target) else {
+            handler.request_path(target, None, None).await;
+            return false;
+        };
+        let packet =
+            destination.lock().await.announce(OsRng, app_data).expect("valid announce packet");
+        eprintln!("[tp] announce_tx dst={} target={} iface={}", packet.destination, target, iface);
+        handler.send(TxMessage { tx_type: TxMessageType::Direct(iface), packet }).await;
+        let request = handler.path_requests.generate(target, None);
+        handler.send(TxMessage { tx_type: TxMessageType::Direct(iface), packet: request }).await;
+        true
+    }
+
     pub async fn set_receipt_handler(&mut self, handler: Box<dyn ReceiptHandler>) {
         self.handler.lock().await.receipt_handler = Some(Arc::from(handler));
     }
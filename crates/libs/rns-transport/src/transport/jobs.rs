@@ -102,6 +102,7 @@ pub(super) async fn manage_transport(
                         let packet = message.packet;
 
                         let mut handler = handler_arc.lock().await;
+                        handler.iface_manager.lock().await.record_rx(&message);
 
                         if PACKET_TRACE {
                             log::debug!("tp: << rx({}) = {} {}", message.address, packet, packet.hash());
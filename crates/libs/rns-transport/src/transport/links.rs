@@ -196,16 +196,19 @@ impl Transport {
         identity: PrivateIdentity,
         name: DestinationName,
     ) -> Arc<Mutex<SingleInputDestination>> {
-        let destination = SingleInputDestination::new(identity, name);
-        let address_hash = destination.desc.address_hash;
-
-        log::debug!("tp({}): add destination {}", self.name, address_hash);
+        let destination = Arc::new(Mutex::new(SingleInputDestination::new(identity, name)));
+        self.register_destination(destination.clone()).await;
+        destination
+    }
 
-        let destination = Arc::new(Mutex::new(destination));
+    /// Registers an already built destination so inbound traffic for it is
+    /// accepted. Usable on a shared transport, e.g. after an identity change.
+    pub async fn register_destination(&self, destination: Arc<Mutex<SingleInputDestination>>) {
+        let address_hash = destination.lock().await.desc.address_hash;
 
-        self.handler.lock().await.single_in_destinations.insert(address_hash, destination.clone());
+        log::debug!("tp({}): add destination {}", self.name, address_hash);
 
-        destination
+        self.handler.lock().await.single_in_destinations.insert(address_hash, destination);
     }
 
     pub async fn has_destination(&self, address: &AddressHash) -> bool {
@@ -135,6 +135,10 @@ pub struct ReceivedData {
     pub request_id: Option<[u8; 16]>,
     pub hops: Option<u8>,
     pub interface: Option<Vec<u8>>,
+    /// Interface the packet arrived on; `None` for data delivered over a link.
+    pub received_on: Option<AddressHash>,
+    /// Link the data was delivered over.
+    pub link_id: Option<LinkId>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -157,6 +161,7 @@ pub struct TransportConfig {
     link_idle_timeout_secs: u64,
     resource_retry_interval_secs: u64,
     resource_retry_limit: u8,
+    iface_max_in_flight: usize,
     ratchet_store_path: Option<PathBuf>,
 }
 
@@ -189,3 +189,26 @@ async fn send_packet_with_outcome_drops_announce_without_route() {
 
     assert_eq!(outcome, SendPacketOutcome::DroppedNoRoute);
 }
+
+#[tokio::test]
+async fn send_announce_to_needs_a_path_to_the_target() {
+    let identity = PrivateIdentity::new_from_rand(OsRng);
+    let transport = Transport::new(TransportConfig::new("test", &identity, false));
+    let handler = transport.get_handler();
+    let local = Arc::new(Mutex::new(SingleInputDestination::new(
+        PrivateIdentity::new_from_rand(OsRng),
+        DestinationName::new("lxmf", "delivery"),
+    )));
+
+    let mut remote = SingleInputDestination::new(
+        PrivateIdentity::new_from_rand(OsRng),
+        DestinationName::new("lxmf", "delivery"),
+    );
+    let announce = remote.announce(OsRng, None).expect("valid announce packet");
+    let target = announce.destination;
+
+    assert!(!transport.send_announce_to(&local, None, &target).await);
+
+    handle_announce(&announce, handler.lock().await, AddressHash::new_from_rand(OsRng)).await;
+    assert!(transport.send_announce_to(&local, None, &target).await);
+}
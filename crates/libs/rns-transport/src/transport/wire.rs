@@ -314,6 +314,8 @@ pub(super) async fn handle_data<'a>(
                     },
                     hops: Some(packet.header.hops),
                     interface: packet.transport.map(|value| value.as_slice().to_vec()),
+                    received_on: Some(iface),
+                    link_id: None,
                 })
                 .ok();
         } else {
@@ -312,6 +312,7 @@ fn sdk_matrix_capability_table_matches_profile_capabilities() {
         "sdk.capability.remote_commands",
         "sdk.capability.voice_signaling",
         "sdk.capability.group_delivery",
+        "sdk.capability.send_batch",
         "sdk.capability.shared_instance_rpc_auth",
         "sdk.capability.key_management",
         "sdk.capability.plugin_host",